@@ -0,0 +1,72 @@
+//! Spawning and despawning a bar per mob against reusing bars from an [`EntityPool`].
+//!
+//! Run with `cargo bench --bench entity_pool`. Each round shows a wave of bars and then takes
+//! them all away again, the way a floor of mobs spawns and dies.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use game::utils::EntityPool;
+
+const WAVE: usize = 64;
+const ROUNDS: usize = 2_000;
+
+#[derive(Component)]
+struct Bar;
+
+fn churn(world: &mut World) {
+    let bars: Vec<Entity> = (0..WAVE)
+        .map(|_| {
+            world
+                .spawn((Bar, Transform::default(), Visibility::Inherited))
+                .id()
+        })
+        .collect();
+    for bar in bars {
+        world.despawn(bar);
+    }
+}
+
+fn pooled(world: &mut World, pool: &mut EntityPool<Bar>) {
+    let bars: Vec<Entity> = (0..WAVE)
+        .map(|_| match pool.acquire() {
+            Some(bar) => {
+                *world.get_mut::<Visibility>(bar).unwrap() = Visibility::Inherited;
+                bar
+            }
+            None => world
+                .spawn((Bar, Transform::default(), Visibility::Inherited))
+                .id(),
+        })
+        .collect();
+    for bar in bars {
+        *world.get_mut::<Visibility>(bar).unwrap() = Visibility::Hidden;
+        pool.release(bar);
+    }
+}
+
+fn time(mut round: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        round();
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let mut world = World::new();
+    let spawned = time(|| churn(black_box(&mut world)));
+
+    let mut world = World::new();
+    let mut pool = EntityPool::<Bar>::default();
+    let reused = time(|| pooled(black_box(&mut world), &mut pool));
+
+    println!("{ROUNDS} waves of {WAVE} bars");
+    println!("  spawn/despawn:   {spawned:?}");
+    println!("  acquire/release: {reused:?}");
+    println!(
+        "  speedup:         {:.2}x",
+        spawned.as_secs_f64() / reused.as_secs_f64()
+    );
+}
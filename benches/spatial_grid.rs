@@ -0,0 +1,61 @@
+//! Finding the entities around the player by scanning every entity against looking them up
+//! in a [`SpatialGrid`].
+//!
+//! Run with `cargo bench --bench spatial_grid`. The floor is a 64x64 tile grid with 600
+//! entities scattered over it, about what a big floor with a swarm on it holds.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use game::dungeon::SpatialGrid;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const TILE: f32 = 32.0;
+const FLOOR_TILES: f32 = 64.0;
+const ENTITIES: usize = 600;
+const LOOKUPS: usize = 100_000;
+const REACH: f32 = TILE * 1.3;
+
+fn time(mut lookup: impl FnMut(Vec2) -> usize, points: &[Vec2]) -> (Duration, usize) {
+    let start = Instant::now();
+    let found = points.iter().map(|&point| lookup(black_box(point))).sum();
+    (start.elapsed(), found)
+}
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut world = World::new();
+    let side = TILE * FLOOR_TILES;
+    let mut random_point = || Vec2::new(rng.gen_range(0.0..side), rng.gen_range(0.0..side));
+
+    let entities: Vec<(Entity, Vec2)> = (0..ENTITIES)
+        .map(|_| (world.spawn_empty().id(), random_point()))
+        .collect();
+    let mut grid = SpatialGrid::default();
+    for &(entity, pos) in &entities {
+        grid.insert(entity, pos);
+    }
+    let points: Vec<Vec2> = (0..LOOKUPS).map(|_| random_point()).collect();
+
+    let (scanned, scan_found) = time(
+        |point| {
+            entities
+                .iter()
+                .filter(|(_, pos)| pos.distance(point) <= REACH)
+                .count()
+        },
+        &points,
+    );
+    let (looked_up, grid_found) = time(|point| grid.within(point, REACH).count(), &points);
+    assert_eq!(scan_found, grid_found);
+
+    println!("{LOOKUPS} lookups among {ENTITIES} entities");
+    println!("  scan every entity: {scanned:?}");
+    println!("  spatial grid:      {looked_up:?}");
+    println!(
+        "  speedup:           {:.2}x",
+        scanned.as_secs_f64() / looked_up.as_secs_f64()
+    );
+}
@@ -116,6 +116,8 @@ struct DefineEntityInput {
     fields: Vec<FieldDef>,
     id_name: Ident,
     sprite_config: Option<SpriteConfig>,
+    /// Whether specs can be replaced at runtime, as content packs do.
+    overridable: bool,
     variants: Vec<VariantDef>,
 }
 
@@ -138,34 +140,26 @@ impl Parse for DefineEntityInput {
         let id_name: Ident = input.parse()?;
         input.parse::<Token![;]>()?;
 
-        let sprite_config = if input.peek(Ident) {
+        let mut sprite_config = None;
+        let mut overridable = false;
+        loop {
             let keyword: Ident = input.parse()?;
             if keyword == "sprites" {
-                let config = input.parse::<SpriteConfig>()?;
+                sprite_config = Some(input.parse::<SpriteConfig>()?);
+                input.parse::<Token![;]>()?;
+            } else if keyword == "overridable" {
+                overridable = true;
                 input.parse::<Token![;]>()?;
-                Some(config)
             } else if keyword == "variants" {
-                let variants_content;
-                braced!(variants_content in input);
-                let mut variants = Vec::new();
-                while !variants_content.is_empty() {
-                    variants.push(variants_content.parse::<VariantDef>()?);
-                }
-                return Ok(DefineEntityInput {
-                    spec_name,
-                    fields,
-                    id_name,
-                    sprite_config: None,
-                    variants,
-                });
+                break;
             } else {
-                return Err(syn::Error::new(keyword.span(), "expected `sprites` or `variants`"));
+                return Err(syn::Error::new(
+                    keyword.span(),
+                    "expected `sprites`, `overridable` or `variants`",
+                ));
             }
-        } else {
-            None
-        };
+        }
 
-        input.parse::<Ident>()?; // "variants"
         let variants_content;
         braced!(variants_content in input);
         let mut variants = Vec::new();
@@ -178,6 +172,7 @@ impl Parse for DefineEntityInput {
             fields,
             id_name,
             sprite_config,
+            overridable,
             variants,
         })
     }
@@ -190,6 +185,7 @@ pub fn define_entity(input: TokenStream) -> TokenStream {
         fields,
         id_name,
         sprite_config,
+        overridable,
         variants,
     } = parse_macro_input!(input as DefineEntityInput);
 
@@ -216,13 +212,17 @@ pub fn define_entity(input: TokenStream) -> TokenStream {
         }
     });
 
-    let spec_arms = variants.iter().map(|v| {
-        let variant = &v.name;
-        let static_name = format_ident!("{}_SPEC", to_screaming_snake_case(&v.name.to_string()));
-        quote! {
-            #id_name::#variant => &*#static_name
-        }
-    });
+    let spec_arms: Vec<_> = variants
+        .iter()
+        .map(|v| {
+            let variant = &v.name;
+            let static_name =
+                format_ident!("{}_SPEC", to_screaming_snake_case(&v.name.to_string()));
+            quote! {
+                #id_name::#variant => &*#static_name
+            }
+        })
+        .collect();
 
     let all_variants = variants.iter().map(|v| {
         let variant = &v.name;
@@ -273,26 +273,68 @@ pub fn define_entity(input: TokenStream) -> TokenStream {
         quote! {}
     };
 
+    let overrides_name = format_ident!(
+        "{}_OVERRIDES",
+        to_screaming_snake_case(&id_name.to_string())
+    );
+    let (overrides_static, override_lookup, override_methods) = if overridable {
+        (
+            quote! {
+                static #overrides_name: std::sync::OnceLock<
+                    std::collections::HashMap<#id_name, #spec_name>,
+                > = std::sync::OnceLock::new();
+            },
+            quote! {
+                if let Some(spec) = #overrides_name.get().and_then(|overrides| overrides.get(self)) {
+                    return spec;
+                }
+            },
+            quote! {
+                /// Replaces the specs of the given IDs for the rest of the run. Only the first
+                /// call takes effect.
+                pub fn set_overrides(overrides: std::collections::HashMap<#id_name, #spec_name>) {
+                    let _ = #overrides_name.set(overrides);
+                }
+
+                /// The spec as compiled in, ignoring any override.
+                pub fn base_spec(&self) -> &'static #spec_name {
+                    match self {
+                        #(#spec_arms),*
+                    }
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
+
     let expanded = quote! {
         #[derive(Debug, Clone)]
         pub struct #spec_name {
             #(#field_defs),*
         }
 
-        #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+        #[derive(
+            Debug, Copy, Clone, Eq, PartialEq, Hash, ::serde::Serialize, ::serde::Deserialize,
+        )]
         pub enum #id_name {
             #(#variant_names),*
         }
 
         #(#static_specs)*
 
+        #overrides_static
+
         impl #id_name {
             pub fn spec(&self) -> &'static #spec_name {
+                #override_lookup
                 match self {
                     #(#spec_arms),*
                 }
             }
 
+            #override_methods
+
             pub const ALL: &'static [#id_name] = &[
                 #(#all_variants),*
             ];
@@ -317,6 +359,9 @@ pub fn define_entity(input: TokenStream) -> TokenStream {
 ///
 ///     id RecipeId;
 ///
+///     // Optional: lets `RecipeId::set_overrides` replace specs at runtime
+///     overridable;
+///
 ///     entries {
 ///         BronzeIngot { name: "Bronze Ingot", output: ItemId::BronzeIngot }
 ///     }
@@ -0,0 +1,157 @@
+//! Damage, defense, crits and dodge.
+
+use rand::Rng;
+use tracing::instrument;
+
+/// Represents an attack with variable damage range.
+/// Damage is rolled randomly between min_damage and max_damage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attack {
+    /// Minimum damage this attack can deal (before defense)
+    pub min_damage: i32,
+    /// Maximum damage this attack can deal (before defense)
+    pub max_damage: i32,
+}
+
+impl Attack {
+    /// Create a new Attack with explicit min/max damage values
+    pub fn new(min_damage: i32, max_damage: i32) -> Self {
+        Self { min_damage, max_damage }
+    }
+
+    /// Roll a random damage value within the attack's range
+    pub fn roll_damage(&self) -> i32 {
+        self.roll_damage_with(&mut rand::thread_rng())
+    }
+
+    /// Roll a damage value within the attack's range from `rng`
+    pub fn roll_damage_with(&self, rng: &mut impl Rng) -> i32 {
+        if self.min_damage >= self.max_damage {
+            return self.min_damage;
+        }
+        rng.gen_range(self.min_damage..=self.max_damage)
+    }
+}
+
+impl Default for Attack {
+    fn default() -> Self {
+        Self {
+            min_damage: 1,
+            max_damage: 1,
+        }
+    }
+}
+
+const DEFENSE_CONSTANT: f64 = 50.0;
+
+pub fn calculate_damage_reduction(defense: i32) -> f64 {
+    let def = defense.max(0) as f64;
+    def / (def + DEFENSE_CONSTANT)
+}
+
+#[instrument(level = "debug", ret)]
+pub fn apply_defense(raw_damage: i32, defense: i32) -> i32 {
+    let reduction = calculate_damage_reduction(defense);
+    let damage_multiplier = 1.0 - reduction;
+    (raw_damage as f64 * damage_multiplier).round() as i32
+}
+
+pub fn apply_goldfind(base_gold: i32, goldfind: i32) -> i32 {
+    let multiplier = 1.0 + (goldfind as f64 / 100.0);
+    ((base_gold as f64) * multiplier).round() as i32
+}
+
+/// How far an attack's damage strays from its stat, either way.
+pub const ATTACK_VARIANCE: f64 = 0.25;
+
+/// How hard a critical lands before any Crit DMG, in percent of the normal hit.
+pub const BASE_CRIT_MULTIPLIER_PERCENT: i32 = 150;
+
+/// Dodge never gets better than this, so gear can't make the player untouchable.
+pub const MAX_DODGE_PERCENT: i32 = 50;
+
+/// Odds of a critical hit and how hard one lands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CritProfile {
+    /// Chance in percent.
+    pub chance: i32,
+    /// A critical's damage, in percent of the normal hit.
+    pub multiplier_percent: i32,
+}
+
+impl CritProfile {
+    /// Rolls whether `damage` lands as a critical. Returns the damage dealt and whether it
+    /// was critical.
+    pub fn roll(&self, damage: i32, rng: &mut impl Rng) -> (i32, bool) {
+        if self.chance <= 0 || rng.gen_range(0..100) >= self.chance {
+            return (damage, false);
+        }
+        (damage * self.multiplier_percent / 100, true)
+    }
+}
+
+/// Whether a blow against someone with `dodge` percent misses them entirely.
+pub fn roll_dodge(dodge: i32, rng: &mut impl Rng) -> bool {
+    dodge > 0 && rng.gen_range(0..100) < dodge
+}
+
+/// A mob's damage range around its `attack` stat.
+pub fn mob_attack_value(attack: i32) -> Attack {
+    let variance = (attack as f64 * ATTACK_VARIANCE).round() as i32;
+    Attack::new((attack - variance).max(1), attack + variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn defense_reduction_follows_diminishing_returns() {
+        let reduction_0 = calculate_damage_reduction(0);
+        let reduction_25 = calculate_damage_reduction(25);
+        let reduction_50 = calculate_damage_reduction(50);
+        let reduction_100 = calculate_damage_reduction(100);
+
+        assert!((reduction_0 - 0.0).abs() < 0.01);
+        assert!((reduction_25 - 0.333).abs() < 0.01);
+        assert!((reduction_50 - 0.5).abs() < 0.01);
+        assert!((reduction_100 - 0.667).abs() < 0.01);
+    }
+
+    #[test]
+    fn apply_defense_reduces_damage_correctly() {
+        assert_eq!(apply_defense(100, 50), 50);
+        assert_eq!(apply_defense(10, 50), 5);
+
+        assert_eq!(apply_defense(100, 0), 100);
+
+        let reduced = apply_defense(100, 1000);
+        assert!(reduced > 0);
+        assert!(reduced < 10);
+    }
+
+    #[test]
+    fn crits_multiply_damage_only_when_they_land() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let always = CritProfile { chance: 100, multiplier_percent: 150 };
+        assert_eq!(always.roll(10, &mut rng), (15, true));
+        let never = CritProfile { chance: 0, multiplier_percent: 150 };
+        assert_eq!(never.roll(10, &mut rng), (10, false));
+    }
+
+    #[test]
+    fn zero_dodge_never_dodges() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!((0..50).all(|_| !roll_dodge(0, &mut rng)));
+        assert!((0..50).all(|_| roll_dodge(100, &mut rng)));
+    }
+
+    #[test]
+    fn mob_attacks_spread_a_quarter_either_way() {
+        assert_eq!(mob_attack_value(20), Attack::new(15, 25));
+        assert_eq!(mob_attack_value(1), Attack::new(1, 1));
+    }
+}
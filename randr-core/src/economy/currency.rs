@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Everything the player can pay with. Gold is the common coin; war tokens and harvest
+/// coins belong to a kind of world event and are only earned while it runs; boss sigils
+/// drop from bosses.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Currency {
+    Gold,
+    WarToken,
+    HarvestCoin,
+    BossSigil,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 4] = [
+        Currency::Gold,
+        Currency::WarToken,
+        Currency::HarvestCoin,
+        Currency::BossSigil,
+    ];
+
+    /// Gold paid for each unit when the currency is cashed out.
+    pub fn gold_value(self) -> i32 {
+        match self {
+            Currency::Gold => 1,
+            Currency::WarToken => 3,
+            Currency::HarvestCoin => 2,
+            Currency::BossSigil => 50,
+        }
+    }
+
+    /// Localization key for the currency's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            Currency::Gold => "currency.gold",
+            Currency::WarToken => "currency.war_token",
+            Currency::HarvestCoin => "currency.harvest_coin",
+            Currency::BossSigil => "currency.boss_sigil",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum WalletError {
+    #[error("Not enough {currency:?}: need {need}, have {have}")]
+    NotEnough {
+        currency: Currency,
+        need: i32,
+        have: i32,
+    },
+    #[error("Amount must not be negative: {0}")]
+    NegativeAmount(i32),
+}
+
+/// The player's carried balance in every currency. Banked gold is kept apart in the game's
+/// `BankedGold`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Component))]
+pub struct Wallet {
+    balances: HashMap<Currency, i32>,
+}
+
+impl Wallet {
+    pub fn with_gold(amount: i32) -> Self {
+        let mut wallet = Self::default();
+        wallet.add(Currency::Gold, amount);
+        wallet
+    }
+
+    pub fn balance(&self, currency: Currency) -> i32 {
+        self.balances.get(&currency).copied().unwrap_or(0)
+    }
+
+    pub fn gold(&self) -> i32 {
+        self.balance(Currency::Gold)
+    }
+
+    pub fn add(&mut self, currency: Currency, amount: i32) {
+        *self.balances.entry(currency).or_insert(0) += amount;
+    }
+
+    /// Takes up to `amount`, stopping at zero.
+    pub fn subtract(&mut self, currency: Currency, amount: i32) {
+        let balance = self.balances.entry(currency).or_insert(0);
+        *balance = (*balance - amount).max(0);
+    }
+
+    /// Spends `amount` if the balance covers it.
+    pub fn spend(&mut self, currency: Currency, amount: i32) -> Result<(), WalletError> {
+        if amount < 0 {
+            return Err(WalletError::NegativeAmount(amount));
+        }
+        let have = self.balance(currency);
+        if have < amount {
+            return Err(WalletError::NotEnough {
+                currency,
+                need: amount,
+                have,
+            });
+        }
+        self.balances.insert(currency, have - amount);
+        Ok(())
+    }
+
+    /// Sets the `currency` balance outright.
+    pub fn set(&mut self, currency: Currency, amount: i32) -> Result<(), WalletError> {
+        if amount < 0 {
+            return Err(WalletError::NegativeAmount(amount));
+        }
+        self.balances.insert(currency, amount);
+        Ok(())
+    }
+
+    /// Empties the `currency` balance and returns what it was worth in gold.
+    pub fn cash_out(&mut self, currency: Currency) -> i32 {
+        let amount = self.balances.remove(&currency).unwrap_or(0);
+        amount * currency.gold_value()
+    }
+
+    /// Every currency with a non-zero balance, in [`Currency::ALL`] order.
+    pub fn held(&self) -> impl Iterator<Item = (Currency, i32)> + '_ {
+        Currency::ALL
+            .into_iter()
+            .map(|currency| (currency, self.balance(currency)))
+            .filter(|(_, amount)| *amount != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spending_needs_enough_of_that_currency() {
+        let mut wallet = Wallet::with_gold(100);
+        wallet.add(Currency::WarToken, 5);
+
+        assert_eq!(
+            wallet.spend(Currency::HarvestCoin, 1),
+            Err(WalletError::NotEnough {
+                currency: Currency::HarvestCoin,
+                need: 1,
+                have: 0
+            })
+        );
+        assert!(wallet.spend(Currency::WarToken, 6).is_err());
+        assert_eq!(wallet.spend(Currency::WarToken, -1), Err(WalletError::NegativeAmount(-1)));
+        assert_eq!(wallet.spend(Currency::WarToken, 5), Ok(()));
+        assert_eq!(wallet.balance(Currency::WarToken), 0);
+        assert_eq!(wallet.gold(), 100);
+    }
+
+    #[test]
+    fn cash_out_converts_one_currency_to_gold() {
+        let mut wallet = Wallet::default();
+        wallet.add(Currency::WarToken, 4);
+        wallet.add(Currency::HarvestCoin, 2);
+
+        assert_eq!(wallet.cash_out(Currency::WarToken), 4 * Currency::WarToken.gold_value());
+        assert_eq!(wallet.balance(Currency::WarToken), 0);
+        assert_eq!(wallet.balance(Currency::HarvestCoin), 2);
+        assert_eq!(wallet.cash_out(Currency::WarToken), 0);
+    }
+
+    #[test]
+    fn subtract_stops_at_zero_and_held_skips_empty_balances() {
+        let mut wallet = Wallet::with_gold(30);
+        wallet.add(Currency::BossSigil, 1);
+        wallet.subtract(Currency::Gold, 50);
+
+        assert_eq!(wallet.gold(), 0);
+        assert_eq!(wallet.held().collect::<Vec<_>>(), [(Currency::BossSigil, 1)]);
+        assert_eq!(wallet.set(Currency::Gold, -5), Err(WalletError::NegativeAmount(-5)));
+    }
+}
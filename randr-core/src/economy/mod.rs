@@ -0,0 +1,7 @@
+//! What things are worth and what the player pays with.
+
+mod currency;
+mod worth;
+
+pub use currency::{Currency, Wallet, WalletError};
+pub use worth::WorthGold;
@@ -0,0 +1,357 @@
+//! Floor layouts: how many chests, rocks, stations, NPCs and mobs a floor gets, and which
+//! free tiles they land on.
+//!
+//! The generator is generic over the mob id, so the game hands it its own ids and turns
+//! each [`Placement`] into an entity. The same rules, tiles and seed always give the same
+//! layout.
+
+use std::ops::RangeInclusive;
+
+use glam::Vec2;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Two positions closer than this are the same tile.
+pub const POSITION_PROXIMITY_THRESHOLD: f32 = 1.0;
+
+/// What a floor can place, and how many of each. Mob lists pair a mob with a weight,
+/// except `guaranteed_mobs`, which pairs it with a count.
+#[derive(Debug, Clone)]
+pub struct FloorRules<M> {
+    pub chests: RangeInclusive<u32>,
+    pub stairs: RangeInclusive<u32>,
+    pub rocks: RangeInclusive<u32>,
+    /// How many kinds of rock there are to pick from.
+    pub rock_kinds: usize,
+    /// How many sprite variants each rock kind has.
+    pub rock_variants: u8,
+    pub herbs: RangeInclusive<u32>,
+    pub shrines: RangeInclusive<u32>,
+    pub dummies: RangeInclusive<u32>,
+    pub forges: RangeInclusive<u32>,
+    /// Chance of a single forge, used when `forges` is empty.
+    pub forge_chance: Option<f64>,
+    pub anvils: RangeInclusive<u32>,
+    /// Chance of a single anvil, used when `anvils` is empty.
+    pub anvil_chance: Option<f64>,
+    pub npcs: Vec<(M, RangeInclusive<u32>)>,
+    pub npc_chances: Vec<(M, f64)>,
+    pub guaranteed_mobs: Vec<(M, u32)>,
+    pub mobs: Vec<(M, u32)>,
+    pub mob_count: RangeInclusive<u32>,
+    /// Whether weighted mobs wander around their spawn point instead of standing still.
+    pub roaming: bool,
+    pub nests: Vec<(M, u32)>,
+    pub nest_count: RangeInclusive<u32>,
+}
+
+impl<M> Default for FloorRules<M> {
+    fn default() -> Self {
+        Self {
+            chests: 0..=0,
+            stairs: 0..=0,
+            rocks: 0..=0,
+            rock_kinds: 1,
+            rock_variants: 1,
+            herbs: 0..=0,
+            shrines: 0..=0,
+            dummies: 0..=0,
+            forges: 0..=0,
+            forge_chance: None,
+            anvils: 0..=0,
+            anvil_chance: None,
+            npcs: Vec::new(),
+            npc_chances: Vec::new(),
+            guaranteed_mobs: Vec::new(),
+            mobs: Vec::new(),
+            mob_count: 0..=0,
+            roaming: false,
+            nests: Vec::new(),
+            nest_count: 0..=0,
+        }
+    }
+}
+
+/// Something placed on a floor by [`generate_floor`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement<M> {
+    Chest,
+    Stairs,
+    /// `kind` indexes the game's rock kinds.
+    Rock { kind: usize, sprite_variant: u8 },
+    Herb,
+    Shrine,
+    TrainingDummy,
+    Forge,
+    Anvil,
+    Npc(M),
+    Mob(M),
+    RoamingMob(M),
+    Nest(M),
+}
+
+/// Everything [`generate_floor`] placed, in the order it was placed.
+#[derive(Debug)]
+pub struct GeneratedFloor<M> {
+    pub placements: Vec<(Vec2, Placement<M>)>,
+}
+
+impl<M> Default for GeneratedFloor<M> {
+    fn default() -> Self {
+        Self {
+            placements: Vec::new(),
+        }
+    }
+}
+
+impl<M> GeneratedFloor<M> {
+    pub fn place(&mut self, pos: Vec2, placement: Placement<M>) {
+        self.placements.push((pos, placement));
+    }
+}
+
+/// Places a floor's entities on the `available` tiles not already `used`. The same seed
+/// always places the same entities in the same spots.
+pub fn generate_floor<M: Copy>(
+    rules: &FloorRules<M>,
+    available: &[Vec2],
+    mut used: Vec<Vec2>,
+    seed: u64,
+) -> GeneratedFloor<M> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut floor = GeneratedFloor::default();
+    let mut placer = Placer {
+        floor: &mut floor,
+        available,
+        used: &mut used,
+        rng: &mut rng,
+    };
+
+    placer.place_counted(&rules.chests, |_| Placement::Chest);
+    placer.place_counted(&rules.stairs, |_| Placement::Stairs);
+    placer.place_counted(&rules.rocks, |rng| Placement::Rock {
+        kind: rng.gen_range(0..rules.rock_kinds.max(1)),
+        sprite_variant: rng.gen_range(0..rules.rock_variants.max(1)),
+    });
+    placer.place_counted(&rules.herbs, |_| Placement::Herb);
+    placer.place_counted(&rules.shrines, |_| Placement::Shrine);
+    placer.place_counted(&rules.dummies, |_| Placement::TrainingDummy);
+    placer.place_stations(rules);
+    placer.place_npcs(rules);
+    placer.place_mobs(rules);
+    placer.place_nests(rules);
+
+    floor
+}
+
+/// The floor being filled and what it's filled from.
+struct Placer<'a, M> {
+    floor: &'a mut GeneratedFloor<M>,
+    available: &'a [Vec2],
+    used: &'a mut Vec<Vec2>,
+    rng: &'a mut StdRng,
+}
+
+impl<M: Copy> Placer<'_, M> {
+    /// Places up to `count` entities on free tiles, stopping early if the floor fills up.
+    fn place_n(&mut self, count: u32, mut create: impl FnMut(&mut StdRng) -> Placement<M>) {
+        for _ in 0..count {
+            let Some(pos) = find_spawn_position(self.available, self.used, self.rng) else {
+                break;
+            };
+            let placement = create(self.rng);
+            self.floor.place(pos, placement);
+            self.used.push(pos);
+        }
+    }
+
+    fn place_counted(
+        &mut self,
+        range: &RangeInclusive<u32>,
+        create: impl FnMut(&mut StdRng) -> Placement<M>,
+    ) {
+        if *range.end() == 0 {
+            return;
+        }
+        let count = self.rng.gen_range(range.clone());
+        self.place_n(count, create);
+    }
+
+    fn place_stations(&mut self, rules: &FloorRules<M>) {
+        let forges = self.station_count(&rules.forges, rules.forge_chance);
+        self.place_n(forges, |_| Placement::Forge);
+        let anvils = self.station_count(&rules.anvils, rules.anvil_chance);
+        self.place_n(anvils, |_| Placement::Anvil);
+    }
+
+    fn station_count(&mut self, range: &RangeInclusive<u32>, chance: Option<f64>) -> u32 {
+        if *range.end() > 0 {
+            self.rng.gen_range(range.clone())
+        } else if let Some(chance) = chance {
+            u32::from(self.rng.gen_bool(chance))
+        } else {
+            0
+        }
+    }
+
+    fn place_npcs(&mut self, rules: &FloorRules<M>) {
+        for (mob_id, count) in &rules.npcs {
+            let count = self.rng.gen_range(count.clone());
+            self.place_n(count, |_| Placement::Npc(*mob_id));
+        }
+        for (mob_id, chance) in &rules.npc_chances {
+            if self.rng.gen_bool(*chance) {
+                self.place_n(1, |_| Placement::Npc(*mob_id));
+            }
+        }
+    }
+
+    fn place_mobs(&mut self, rules: &FloorRules<M>) {
+        for (mob_id, count) in &rules.guaranteed_mobs {
+            self.place_n(*count, |_| Placement::Mob(*mob_id));
+        }
+
+        let total_weight: u32 = rules.mobs.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 || *rules.mob_count.end() == 0 {
+            return;
+        }
+        let count = self.rng.gen_range(rules.mob_count.clone());
+        let placement = if rules.roaming {
+            Placement::RoamingMob
+        } else {
+            Placement::Mob
+        };
+        self.place_n(count, |rng| placement(pick_mob(&rules.mobs, total_weight, rng)));
+    }
+
+    fn place_nests(&mut self, rules: &FloorRules<M>) {
+        let total_weight: u32 = rules.nests.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 || *rules.nest_count.end() == 0 {
+            return;
+        }
+        let count = self.rng.gen_range(rules.nest_count.clone());
+        self.place_n(count, |rng| Placement::Nest(pick_mob(&rules.nests, total_weight, rng)));
+    }
+}
+
+pub fn is_position_used(pos: Vec2, used: &[Vec2]) -> bool {
+    used.iter().any(|used_pos| pos.distance(*used_pos) < POSITION_PROXIMITY_THRESHOLD)
+}
+
+pub fn find_spawn_position(available: &[Vec2], used: &[Vec2], rng: &mut impl Rng) -> Option<Vec2> {
+    let candidates: Vec<_> = available
+        .iter()
+        .filter(|pos| !is_position_used(**pos, used))
+        .collect();
+
+    candidates.choose(rng).copied().copied()
+}
+
+fn pick_mob<M: Copy>(entries: &[(M, u32)], total_weight: u32, rng: &mut impl Rng) -> M {
+    weighted_select(entries, total_weight, rng).unwrap_or(entries[0]).0
+}
+
+fn weighted_select<M: Copy>(
+    entries: &[(M, u32)],
+    total_weight: u32,
+    rng: &mut impl Rng,
+) -> Option<(M, u32)> {
+    if entries.is_empty() {
+        return None;
+    }
+
+    let roll = rng.gen_range(0..total_weight);
+    let mut cumulative = 0;
+
+    for &(mob_id, weight) in entries {
+        cumulative += weight;
+        if roll < cumulative {
+            return Some((mob_id, weight));
+        }
+    }
+
+    entries.first().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<Vec2> {
+        (0..10)
+            .flat_map(|x| (0..10).map(move |y| Vec2::new(x as f32, y as f32) * 16.0))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_places_the_same_floor() {
+        let rules = FloorRules::<u8> {
+            chests: 1..=3,
+            rocks: 2..=5,
+            herbs: 0..=2,
+            ..Default::default()
+        };
+        let available = grid();
+        let used = vec![Vec2::ZERO];
+
+        let positions = |seed| {
+            generate_floor(&rules, &available, used.clone(), seed)
+                .placements
+                .into_iter()
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>()
+        };
+
+        let first = positions(7);
+        assert!(!first.is_empty());
+        assert!(!first.contains(&Vec2::ZERO));
+        assert_eq!(first, positions(7));
+    }
+
+    #[test]
+    fn placements_stop_when_the_floor_is_full() {
+        let rules = FloorRules::<u8> {
+            chests: 5..=5,
+            ..Default::default()
+        };
+        let available = [Vec2::ZERO, Vec2::new(16.0, 0.0)];
+
+        let floor = generate_floor(&rules, &available, Vec::new(), 1);
+        assert_eq!(floor.placements.len(), 2);
+    }
+
+    #[test]
+    fn roaming_rules_place_roaming_mobs() {
+        let rules = FloorRules {
+            mobs: vec![(1u8, 1)],
+            mob_count: 3..=3,
+            roaming: true,
+            ..Default::default()
+        };
+
+        let floor = generate_floor(&rules, &grid(), Vec::new(), 3);
+        assert_eq!(floor.placements.len(), 3);
+        assert!(floor
+            .placements
+            .iter()
+            .all(|(_, placement)| *placement == Placement::RoamingMob(1)));
+    }
+
+    #[test]
+    fn weighted_select_returns_entry() {
+        let entries = vec![(1u8, 5), (2u8, 3)];
+
+        let mut rng = rand::thread_rng();
+        let result = weighted_select(&entries, 8, &mut rng);
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn weighted_select_empty_returns_none() {
+        let entries: Vec<(u8, u32)> = vec![];
+        let mut rng = rand::thread_rng();
+        let result = weighted_select(&entries, 0, &mut rng);
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::item::Item;
+use crate::stats::StatType;
+
+/// How an item measures up against whatever is equipped in its slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquipmentComparison {
+    /// Name of the equipped item, or `None` when the slot is empty.
+    pub equipped_name: Option<String>,
+    /// Every stat either item has, with the item's value and the change equipping it makes.
+    pub deltas: Vec<(StatType, i32, i32)>,
+}
+
+impl EquipmentComparison {
+    pub fn new(item: &Item, equipped: Option<&Item>) -> Self {
+        let stats = stat_values(item);
+        let equipped_stats = equipped.map(stat_values).unwrap_or_default();
+        let equipped_value = |stat: StatType| {
+            equipped_stats
+                .iter()
+                .find(|(t, _)| *t == stat)
+                .map_or(0, |(_, v)| *v)
+        };
+
+        let mut deltas: Vec<_> = stats
+            .iter()
+            .map(|&(stat, value)| (stat, value, value - equipped_value(stat)))
+            .collect();
+        // Stats only the equipped item has are lost entirely.
+        deltas.extend(
+            equipped_stats
+                .iter()
+                .filter(|(stat, _)| !stats.iter().any(|(t, _)| t == stat))
+                .map(|&(stat, value)| (stat, 0, -value)),
+        );
+
+        Self {
+            equipped_name: equipped.map(|item| item.name.clone()),
+            deltas,
+        }
+    }
+}
+
+fn stat_values(item: &Item) -> Vec<(StatType, i32)> {
+    item.stats
+        .stats()
+        .iter()
+        .map(|(t, si)| (*t, si.current_value))
+        .collect()
+}
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 
-use bevy::prelude::*;
 use uuid::Uuid;
 
 use crate::item::Item;
@@ -26,11 +25,17 @@ pub struct AddItemResult {
 pub struct InventoryItem {
     pub item: Item,
     pub quantity: u32,
+    /// Added to since the player last looked at it.
+    pub is_new: bool,
 }
 
 impl InventoryItem {
     pub fn new(item: Item) -> Self {
-        Self { item, quantity: 1 }
+        Self {
+            item,
+            quantity: 1,
+            is_new: false,
+        }
     }
 
     pub fn uuid(&self) -> Uuid {
@@ -46,7 +51,8 @@ impl InventoryItem {
     }
 }
 
-#[derive(Resource, Component, Default, Debug, Clone)]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Resource, bevy_ecs::prelude::Component))]
 pub struct Inventory {
     pub items: Vec<InventoryItem>,
     max_slots: usize,
@@ -63,6 +69,15 @@ impl Inventory {
         }
     }
 
+    /// Create an empty inventory with `max_slots` slots.
+    pub fn with_max_slots(max_slots: usize) -> Self {
+        Inventory {
+            items: Vec::new(),
+            max_slots,
+            equipment: HashMap::new(),
+        }
+    }
+
     /// Create an inventory with unlimited slots (for storage).
     pub fn new_unlimited() -> Self {
         Inventory {
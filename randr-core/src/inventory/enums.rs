@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::enums::ItemType;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum EquipmentSlot {
+    Weapon,
+    OffHand,
+    /// Data written before there were two ring slots calls this one `Ring`.
+    #[serde(alias = "Ring")]
+    Ring1,
+    Ring2,
+    Amulet,
+    Cape,
+    Tool,
+    Head,
+    Chest,
+    Hands,
+    Feet,
+    Legs,
+}
+
+impl EquipmentSlot {
+    pub fn all() -> &'static [EquipmentSlot] {
+        &[
+            EquipmentSlot::Weapon,
+            EquipmentSlot::OffHand,
+            EquipmentSlot::Ring1,
+            EquipmentSlot::Ring2,
+            EquipmentSlot::Amulet,
+            EquipmentSlot::Cape,
+            EquipmentSlot::Tool,
+            EquipmentSlot::Head,
+            EquipmentSlot::Chest,
+            EquipmentSlot::Hands,
+            EquipmentSlot::Feet,
+            EquipmentSlot::Legs,
+        ]
+    }
+
+    /// Localization key for the slot's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            EquipmentSlot::Weapon => "slot.weapon",
+            EquipmentSlot::OffHand => "slot.off_hand",
+            EquipmentSlot::Ring1 => "slot.ring1",
+            EquipmentSlot::Ring2 => "slot.ring2",
+            EquipmentSlot::Amulet => "slot.amulet",
+            EquipmentSlot::Cape => "slot.cape",
+            EquipmentSlot::Tool => "slot.tool",
+            EquipmentSlot::Head => "slot.head",
+            EquipmentSlot::Chest => "slot.chest",
+            EquipmentSlot::Hands => "slot.hands",
+            EquipmentSlot::Feet => "slot.feet",
+            EquipmentSlot::Legs => "slot.legs",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InventoryError {
+    Full,
+    /// A split must leave at least one item on both stacks.
+    InvalidSplit,
+}
+
+/// Order [`ManagesItems::sort_items`](super::ManagesItems::sort_items) puts the backpack in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum InventorySort {
+    #[default]
+    Type,
+    Value,
+    Quality,
+    /// Favorites first, otherwise keeping the current order.
+    Favorites,
+    /// Newly added stacks first, otherwise keeping the current order.
+    New,
+}
+
+impl InventorySort {
+    pub const ALL: [InventorySort; 5] = [
+        InventorySort::Type,
+        InventorySort::Value,
+        InventorySort::Quality,
+        InventorySort::Favorites,
+        InventorySort::New,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the sort mode's label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            InventorySort::Type => "inventory.sort_type",
+            InventorySort::Value => "inventory.sort_value",
+            InventorySort::Quality => "inventory.sort_quality",
+            InventorySort::Favorites => "inventory.sort_favorites",
+            InventorySort::New => "inventory.sort_new",
+        }
+    }
+}
+
+/// Category filter for the backpack view.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, Serialize, Deserialize)]
+pub enum ItemCategory {
+    #[default]
+    All,
+    Equipment,
+    Materials,
+    Consumables,
+}
+
+impl ItemCategory {
+    pub const ALL: [ItemCategory; 4] = [
+        ItemCategory::All,
+        ItemCategory::Equipment,
+        ItemCategory::Materials,
+        ItemCategory::Consumables,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn contains(self, item_type: ItemType) -> bool {
+        match self {
+            ItemCategory::All => true,
+            ItemCategory::Equipment => matches!(item_type, ItemType::Equipment(_)),
+            ItemCategory::Materials => matches!(item_type, ItemType::Material(_)),
+            ItemCategory::Consumables => matches!(item_type, ItemType::Consumable(_)),
+        }
+    }
+
+    /// Localization key for the category's label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            ItemCategory::All => "inventory.filter_all",
+            ItemCategory::Equipment => "inventory.filter_equipment",
+            ItemCategory::Materials => "inventory.filter_materials",
+            ItemCategory::Consumables => "inventory.filter_consumables",
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{EquipmentSlot, FindsItems, Inventory, ManagesEquipment, ManagesItems};
+
+/// How many loadouts the player can keep, one per number key.
+pub const MAX_LOADOUTS: usize = 4;
+
+/// Names the loadouts start with.
+pub const DEFAULT_LOADOUT_NAMES: [&str; MAX_LOADOUTS] =
+    ["Combat", "Mining", "Loadout 3", "Loadout 4"];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LoadoutError {
+    #[error("There is no loadout {0}")]
+    Unknown(usize),
+    #[error("Loadout {0} is empty")]
+    Empty(usize),
+    #[error("Loadout items are missing from the backpack and storage")]
+    Missing(Vec<EquipmentSlot>),
+    #[error("The backpack has no room for the gear being swapped out")]
+    BackpackFull,
+}
+
+/// A named set of gear, as the item in each slot it covers. Slots it does not cover are
+/// left alone when it is equipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Loadout {
+    pub name: String,
+    pub items: HashMap<EquipmentSlot, Uuid>,
+}
+
+impl Loadout {
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            items: HashMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Whether everything in the loadout is equipped right now.
+    pub fn is_worn(&self, inventory: &Inventory) -> bool {
+        !self.is_empty()
+            && self.items.iter().all(|(slot, uuid)| {
+                inventory
+                    .get_equipped_item(*slot)
+                    .is_some_and(|equipped| equipped.uuid() == *uuid)
+            })
+    }
+}
+
+/// The player's loadouts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Component))]
+pub struct Loadouts {
+    loadouts: Vec<Loadout>,
+}
+
+impl Default for Loadouts {
+    fn default() -> Self {
+        Self {
+            loadouts: DEFAULT_LOADOUT_NAMES
+                .into_iter()
+                .map(Loadout::named)
+                .collect(),
+        }
+    }
+}
+
+impl Loadouts {
+    pub fn get(&self, index: usize) -> Option<&Loadout> {
+        self.loadouts.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Loadout> {
+        self.loadouts.iter()
+    }
+
+    /// Saves what `inventory` has equipped as loadout `index`, keeping its name.
+    pub fn save(&mut self, index: usize, inventory: &Inventory) -> Result<(), LoadoutError> {
+        let loadout = self
+            .loadouts
+            .get_mut(index)
+            .ok_or(LoadoutError::Unknown(index))?;
+        loadout.items = inventory
+            .equipment()
+            .iter()
+            .map(|(slot, equipped)| (*slot, equipped.uuid()))
+            .collect();
+        Ok(())
+    }
+
+    /// Equips loadout `index`, taking items from the backpack or, when given, `storage`.
+    /// Nothing changes unless every item can be found and the backpack has room for what
+    /// comes off. Returns the items taken out of storage.
+    pub fn switch(
+        &self,
+        index: usize,
+        inventory: &mut Inventory,
+        storage: Option<&mut Inventory>,
+    ) -> Result<Vec<Uuid>, LoadoutError> {
+        let loadout = self.get(index).ok_or(LoadoutError::Unknown(index))?;
+        if loadout.is_empty() {
+            return Err(LoadoutError::Empty(index));
+        }
+
+        let mut missing = Vec::new();
+        let mut from_storage = Vec::new();
+        for (&slot, &uuid) in &loadout.items {
+            let equipped = inventory.get_equipped_item(slot).map(|e| e.uuid());
+            if equipped == Some(uuid) || inventory.find_item_index_by_uuid(uuid).is_some() {
+                continue;
+            }
+            match storage.as_deref() {
+                Some(stored) if stored.find_item_index_by_uuid(uuid).is_some() => {
+                    from_storage.push((slot, uuid));
+                }
+                _ => missing.push(slot),
+            }
+        }
+        if !missing.is_empty() {
+            missing.sort_by_key(|slot| EquipmentSlot::all().iter().position(|s| s == slot));
+            return Err(LoadoutError::Missing(missing));
+        }
+
+        // Gear coming out of storage pushes whatever it replaces into the backpack.
+        let displaced = from_storage
+            .iter()
+            .filter(|(slot, _)| inventory.get_equipped_item(*slot).is_some())
+            .count();
+        if inventory.items.len() + displaced > inventory.max_slots() {
+            return Err(LoadoutError::BackpackFull);
+        }
+
+        for (&slot, &uuid) in &loadout.items {
+            if inventory.find_item_index_by_uuid(uuid).is_some() {
+                inventory.equip_from_inventory(uuid, slot);
+            }
+        }
+        let mut taken = Vec::new();
+        if let Some(stored) = storage {
+            for (slot, uuid) in from_storage {
+                if let Some(stack) = stored.remove_item(uuid) {
+                    inventory.equip_item(stack.item, slot);
+                    taken.push(uuid);
+                }
+            }
+        }
+        Ok(taken)
+    }
+}
@@ -0,0 +1,15 @@
+mod comparison;
+mod definition;
+mod enums;
+mod equipment;
+mod loadout;
+#[cfg(test)]
+mod tests;
+mod traits;
+
+pub use comparison::EquipmentComparison;
+pub use definition::{AddItemResult, EquipmentMap, Inventory, InventoryItem};
+pub use enums::{EquipmentSlot, InventoryError, InventorySort, ItemCategory};
+pub use equipment::HasEquipment;
+pub use loadout::{Loadout, LoadoutError, Loadouts, DEFAULT_LOADOUT_NAMES, MAX_LOADOUTS};
+pub use traits::{FindsItems, HasInventory, ManagesEquipment, ManagesItems};
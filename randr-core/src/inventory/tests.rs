@@ -2,7 +2,7 @@
 use uuid::Uuid;
 
 #[cfg(test)]
-use crate::assets::SpriteSheetKey;
+use crate::item::ItemSheet;
 #[cfg(test)]
 use crate::item::enums::{EquipmentType, ItemQuality, ItemType, MaterialType};
 #[cfg(test)]
@@ -11,7 +11,7 @@ use crate::item::{Item, ItemId, SpriteInfo};
 use crate::stats::{StatSheet, StatType};
 
 #[cfg(test)]
-use super::{EquipmentSlot, FindsItems, HasInventory, Inventory, InventoryError, InventoryItem, ManagesEquipment, ManagesItems};
+use super::{EquipmentComparison, EquipmentSlot, FindsItems, HasInventory, Inventory, InventoryError, InventoryItem, InventorySort, LoadoutError, Loadouts, ManagesEquipment, ManagesItems};
 
 
 #[cfg(test)]
@@ -23,6 +23,7 @@ fn create_test_weapon(id: ItemId, attack: i32) -> Item {
         name: "Test Weapon".to_string(),
         is_equipped: false,
         is_locked: false,
+        is_favorite: false,
         num_upgrades: 0,
         max_upgrades: 5,
         max_stack_quantity: 1,
@@ -30,7 +31,7 @@ fn create_test_weapon(id: ItemId, attack: i32) -> Item {
         stats: StatSheet::new().with(StatType::Attack, attack),
         gold_value: 100,
         quality: ItemQuality::Normal,
-        sprite: SpriteInfo { name: String::new(), sheet_key: SpriteSheetKey::IconItems },
+        sprite: SpriteInfo { name: String::new(), sheet_key: ItemSheet::IconItems },
     }
 }
 
@@ -43,6 +44,7 @@ fn create_test_shield(id: ItemId, defense: i32) -> Item {
         name: "Test Shield".to_string(),
         is_equipped: false,
         is_locked: false,
+        is_favorite: false,
         num_upgrades: 0,
         max_upgrades: 5,
         max_stack_quantity: 1,
@@ -50,7 +52,7 @@ fn create_test_shield(id: ItemId, defense: i32) -> Item {
         stats: StatSheet::new().with(StatType::Defense, defense),
         gold_value: 80,
         quality: ItemQuality::Normal,
-        sprite: SpriteInfo { name: String::new(), sheet_key: SpriteSheetKey::IconItems },
+        sprite: SpriteInfo { name: String::new(), sheet_key: ItemSheet::IconItems },
     }
 }
 
@@ -63,6 +65,7 @@ fn create_test_material(id: ItemId) -> Item {
         name: "Test Material".to_string(),
         is_equipped: false,
         is_locked: false,
+        is_favorite: false,
         num_upgrades: 0,
         max_upgrades: 0,
         max_stack_quantity: 99,
@@ -70,7 +73,7 @@ fn create_test_material(id: ItemId) -> Item {
         stats: StatSheet::new(),
         gold_value: 10,
         quality: ItemQuality::Normal,
-        sprite: SpriteInfo { name: String::new(), sheet_key: SpriteSheetKey::IconItems },
+        sprite: SpriteInfo { name: String::new(), sheet_key: ItemSheet::IconItems },
     }
 }
 
@@ -427,6 +430,20 @@ fn decrease_item_quantity_removes_when_zero() {
     assert!(holder.inventory().items.is_empty());
 }
 
+#[test]
+fn decrease_item_quantity_takes_from_every_split_stack() {
+    let mut holder = MockInventoryHolder::new();
+    for _ in 0..10 {
+        holder.add_to_inv(create_test_material(ItemId::IronOre)).unwrap();
+    }
+    holder.split_stack(0, 5).unwrap();
+
+    holder.decrease_item_quantity(ItemId::IronOre, 7);
+
+    assert_eq!(holder.count_item(ItemId::IronOre), 3);
+    assert_eq!(holder.inventory().items.len(), 1);
+}
+
 #[test]
 fn decrease_item_quantity_works_on_equipment() {
     let mut holder = MockInventoryHolder::new();
@@ -704,10 +721,13 @@ fn remove_item_returns_removed_item() {
 fn equipment_slot_all_returns_all_slots() {
     let all_slots = EquipmentSlot::all();
 
-    assert_eq!(all_slots.len(), 9);
+    assert_eq!(all_slots.len(), 12);
     assert!(all_slots.contains(&EquipmentSlot::Weapon));
     assert!(all_slots.contains(&EquipmentSlot::OffHand));
-    assert!(all_slots.contains(&EquipmentSlot::Ring));
+    assert!(all_slots.contains(&EquipmentSlot::Ring1));
+    assert!(all_slots.contains(&EquipmentSlot::Ring2));
+    assert!(all_slots.contains(&EquipmentSlot::Amulet));
+    assert!(all_slots.contains(&EquipmentSlot::Cape));
     assert!(all_slots.contains(&EquipmentSlot::Tool));
     assert!(all_slots.contains(&EquipmentSlot::Head));
     assert!(all_slots.contains(&EquipmentSlot::Chest));
@@ -717,6 +737,26 @@ fn equipment_slot_all_returns_all_slots() {
 }
 
 
+#[test]
+fn second_ring_goes_on_the_other_hand() {
+    let mut holder = MockInventoryHolder::new();
+    let mut ring = create_test_weapon(ItemId::GoldRing, 0);
+    ring.item_type = ItemType::Equipment(EquipmentType::Ring);
+    ring.stats = StatSheet::new().with(StatType::GoldFind, 10);
+    let other_ring = Item { item_uuid: Uuid::new_v4(), ..ring.clone() };
+
+    assert_eq!(holder.slot_for(ring.item_type), Some(EquipmentSlot::Ring1));
+    holder.equip_item(ring.clone(), EquipmentSlot::Ring1);
+    assert_eq!(holder.slot_for(ring.item_type), Some(EquipmentSlot::Ring2));
+    holder.equip_item(other_ring, EquipmentSlot::Ring2);
+
+    // With both hands full, a third ring replaces the first.
+    assert_eq!(holder.slot_for(ring.item_type), Some(EquipmentSlot::Ring1));
+    assert_eq!(holder.inventory().sum_equipment_stats(StatType::GoldFind), 20);
+    assert!(ring.item_type.fits_slot(EquipmentSlot::Ring2));
+    assert!(!ring.item_type.fits_slot(EquipmentSlot::Amulet));
+}
+
 #[test]
 fn user_flow_add_equip_unequip_remove() {
     let mut holder = MockInventoryHolder::new();
@@ -781,7 +821,7 @@ fn user_flow_full_inventory_management() {
 
     // Player has full inventory (15 weapons)
     for i in 0..15 {
-        let weapon = create_test_weapon(ItemId::Sword, i as i32);
+        let weapon = create_test_weapon(ItemId::Sword, i);
         holder.add_to_inv(weapon).unwrap();
     }
     assert_eq!(holder.inventory().items.len(), 15);
@@ -800,3 +840,238 @@ fn user_flow_full_inventory_management() {
     assert!(holder.add_to_inv(extra).is_ok());
     assert_eq!(holder.inventory().items.len(), 15);
 }
+
+// ============================================================================
+// Sorting and Stack Splitting Tests
+// ============================================================================
+
+#[test]
+fn sort_items_by_type_puts_equipment_before_materials() {
+    let mut holder = MockInventoryHolder::new();
+    holder.add_to_inv(create_test_material(ItemId::IronOre)).unwrap();
+    holder.add_to_inv(create_test_weapon(ItemId::Sword, 5)).unwrap();
+
+    holder.sort_items(InventorySort::Type);
+
+    let names: Vec<&str> = holder.inventory().items.iter().map(|i| i.item.name.as_str()).collect();
+    assert_eq!(names, ["Test Weapon", "Test Material"]);
+}
+
+#[test]
+fn sort_items_by_value_and_quality_are_descending() {
+    let mut holder = MockInventoryHolder::new();
+    let mut rough = create_test_weapon(ItemId::Dagger, 1);
+    rough.quality = ItemQuality::Poor;
+    let mut fine = create_test_shield(ItemId::BasicShield, 1);
+    fine.quality = ItemQuality::Masterworked;
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+    holder.add_to_inv(rough).unwrap();
+    holder.add_to_inv(fine).unwrap();
+
+    holder.sort_items(InventorySort::Value);
+    let values: Vec<i32> = holder.inventory().items.iter().map(|i| i.item.gold_value).collect();
+    assert_eq!(values, [100, 80, 10]);
+
+    holder.sort_items(InventorySort::Quality);
+    assert_eq!(holder.inventory().items[0].item.quality, ItemQuality::Masterworked);
+    assert_eq!(holder.inventory().items[2].item.quality, ItemQuality::Poor);
+}
+
+#[test]
+fn sort_items_by_favorites_and_new_floats_them_up_in_order() {
+    let mut holder = MockInventoryHolder::new();
+    let mut starred = create_test_shield(ItemId::BasicShield, 1);
+    starred.is_favorite = true;
+    holder.add_to_inv(create_test_weapon(ItemId::Dagger, 1)).unwrap();
+    holder.add_to_inv(create_test_weapon(ItemId::Sword, 1)).unwrap();
+    holder.add_to_inv(starred).unwrap();
+    for inv_item in &mut holder.inventory_mut().items {
+        inv_item.is_new = false;
+    }
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+
+    holder.sort_items(InventorySort::Favorites);
+    let ids: Vec<ItemId> = holder.inventory().items.iter().map(|i| i.item.item_id).collect();
+    assert_eq!(ids, [ItemId::BasicShield, ItemId::Dagger, ItemId::Sword, ItemId::Coal]);
+
+    holder.sort_items(InventorySort::New);
+    let ids: Vec<ItemId> = holder.inventory().items.iter().map(|i| i.item.item_id).collect();
+    assert_eq!(ids, [ItemId::Coal, ItemId::BasicShield, ItemId::Dagger, ItemId::Sword]);
+}
+
+#[test]
+fn added_stacks_are_new_but_unequipped_gear_is_not() {
+    let mut holder = MockInventoryHolder::new();
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+    assert!(holder.inventory().items[0].is_new);
+
+    holder.inventory_mut().items[0].is_new = false;
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+    assert!(holder.inventory().items[0].is_new, "topping up a stack marks it new again");
+
+    holder.equip_item(create_test_weapon(ItemId::Sword, 5), EquipmentSlot::Weapon);
+    holder.unequip_item(EquipmentSlot::Weapon).unwrap();
+    assert!(!holder.inventory().items[1].is_new);
+}
+
+#[test]
+fn split_stack_moves_amount_into_new_stack() {
+    let mut holder = MockInventoryHolder::new();
+    for _ in 0..50 {
+        holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+    }
+
+    let new_index = holder.split_stack(0, 20).unwrap();
+
+    assert_eq!(new_index, 1);
+    let items = &holder.inventory().items;
+    assert_eq!(items[0].quantity, 30);
+    assert_eq!(items[1].quantity, 20);
+    assert_ne!(items[0].uuid(), items[1].uuid());
+    assert_eq!(holder.count_item(ItemId::Coal), 50);
+}
+
+#[test]
+fn split_stack_rejects_invalid_amounts_and_full_inventory() {
+    let mut holder = MockInventoryHolder::new();
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+
+    assert!(matches!(holder.split_stack(0, 0), Err(InventoryError::InvalidSplit)));
+    assert!(matches!(holder.split_stack(0, 2), Err(InventoryError::InvalidSplit)));
+    assert!(matches!(holder.split_stack(5, 1), Err(InventoryError::InvalidSplit)));
+
+    for i in 0..14 {
+        holder.add_to_inv(create_test_weapon(ItemId::Sword, i)).unwrap();
+    }
+    assert!(matches!(holder.split_stack(0, 1), Err(InventoryError::Full)));
+    assert_eq!(holder.inventory().items[0].quantity, 2);
+}
+
+#[test]
+fn take_junk_removes_only_unlocked_unstarred_items_below_threshold() {
+    let mut holder = MockInventoryHolder::new();
+    let mut rusty = create_test_weapon(ItemId::Dagger, 1);
+    rusty.quality = ItemQuality::Poor;
+    let mut heirloom = create_test_weapon(ItemId::Sword, 1);
+    heirloom.quality = ItemQuality::Poor;
+    heirloom.is_locked = true;
+    let mut keepsake = create_test_weapon(ItemId::BonkStick, 1);
+    keepsake.quality = ItemQuality::Poor;
+    keepsake.is_favorite = true;
+    holder.add_to_inv(rusty).unwrap();
+    holder.add_to_inv(heirloom).unwrap();
+    holder.add_to_inv(keepsake).unwrap();
+    holder.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+
+    assert_eq!(holder.junk_items(ItemQuality::Poor).len(), 0);
+    assert_eq!(holder.junk_items(ItemQuality::Normal).len(), 1);
+
+    let junk = holder.take_junk(ItemQuality::Normal);
+    assert_eq!(junk.len(), 1);
+    assert_eq!(junk[0].item.item_id, ItemId::Dagger);
+    let left: Vec<ItemId> = holder.inventory().items.iter().map(|i| i.item.item_id).collect();
+    assert_eq!(left, [ItemId::Sword, ItemId::BonkStick, ItemId::Coal]);
+}
+
+#[test]
+fn compare_to_equipped_reports_gains_and_losses() {
+    let mut holder = MockInventoryHolder::new();
+    let equipped = create_test_weapon(ItemId::Sword, 10);
+    let equipped_uuid = equipped.item_uuid;
+    holder.equip_item(equipped, EquipmentSlot::Weapon);
+
+    let better = create_test_weapon(ItemId::Sword, 15);
+    assert_eq!(
+        holder.compare_to_equipped(&better),
+        Some(EquipmentComparison {
+            equipped_name: Some("Test Weapon".to_string()),
+            deltas: vec![(StatType::Attack, 15, 5)],
+        })
+    );
+
+    let mut different = create_test_weapon(ItemId::Sword, 0);
+    different.stats = StatSheet::new().with(StatType::Defense, 3);
+    let deltas = holder.compare_to_equipped(&different).unwrap().deltas;
+    assert_eq!(deltas, [(StatType::Defense, 3, 3), (StatType::Attack, 0, -10)]);
+
+    let equipped = holder.get_equipped_item(EquipmentSlot::Weapon).unwrap().item.clone();
+    assert_eq!(equipped.item_uuid, equipped_uuid);
+    assert_eq!(holder.compare_to_equipped(&equipped), None);
+    assert_eq!(holder.compare_to_equipped(&create_test_material(ItemId::Coal)), None);
+}
+
+#[test]
+fn compare_to_equipped_against_an_empty_slot() {
+    let holder = MockInventoryHolder::new();
+    let comparison = holder.compare_to_equipped(&create_test_weapon(ItemId::Sword, 4)).unwrap();
+
+    assert_eq!(comparison.equipped_name, None);
+    assert_eq!(comparison.deltas, [(StatType::Attack, 4, 4)]);
+}
+
+#[test]
+fn loadouts_swap_gear_from_the_backpack() {
+    let mut inventory = Inventory::new();
+    let mut loadouts = Loadouts::default();
+    let sword = create_test_weapon(ItemId::Sword, 10);
+    let pickaxe = create_test_weapon(ItemId::CopperPickaxe, 2);
+    let (sword_uuid, pickaxe_uuid) = (sword.item_uuid, pickaxe.item_uuid);
+
+    inventory.equip_item(sword, EquipmentSlot::Weapon);
+    loadouts.save(0, &inventory).unwrap();
+    inventory.equip_item(pickaxe, EquipmentSlot::Weapon);
+    loadouts.save(1, &inventory).unwrap();
+    assert!(loadouts.get(1).unwrap().is_worn(&inventory));
+
+    assert_eq!(loadouts.switch(0, &mut inventory, None), Ok(Vec::new()));
+    assert_eq!(inventory.get_equipped_item(EquipmentSlot::Weapon).unwrap().uuid(), sword_uuid);
+    assert!(inventory.find_item_index_by_uuid(pickaxe_uuid).is_some());
+    assert!(loadouts.get(0).unwrap().is_worn(&inventory));
+    assert_eq!(loadouts.switch(2, &mut inventory, None), Err(LoadoutError::Empty(2)));
+    assert_eq!(loadouts.switch(9, &mut inventory, None), Err(LoadoutError::Unknown(9)));
+}
+
+#[test]
+fn loadouts_take_missing_gear_from_storage_or_refuse() {
+    let mut inventory = Inventory::new();
+    let mut storage = Inventory::new_unlimited();
+    let mut loadouts = Loadouts::default();
+    let sword = create_test_weapon(ItemId::Sword, 10);
+    let sword_uuid = sword.item_uuid;
+
+    inventory.equip_item(sword, EquipmentSlot::Weapon);
+    loadouts.save(0, &inventory).unwrap();
+    let stored = inventory.remove_item(sword_uuid).unwrap();
+    storage.add_to_inv(stored.item).unwrap();
+
+    assert_eq!(
+        loadouts.switch(0, &mut inventory, None),
+        Err(LoadoutError::Missing(vec![EquipmentSlot::Weapon]))
+    );
+    assert_eq!(loadouts.switch(0, &mut inventory, Some(&mut storage)), Ok(vec![sword_uuid]));
+    assert_eq!(inventory.get_equipped_item(EquipmentSlot::Weapon).unwrap().uuid(), sword_uuid);
+    assert!(storage.items.is_empty());
+}
+
+#[test]
+fn loadouts_need_backpack_room_for_what_comes_off() {
+    let mut inventory = Inventory::with_max_slots(1);
+    let mut storage = Inventory::new_unlimited();
+    let mut loadouts = Loadouts::default();
+    let sword = create_test_weapon(ItemId::Sword, 10);
+    let sword_uuid = sword.item_uuid;
+
+    inventory.equip_item(sword, EquipmentSlot::Weapon);
+    loadouts.save(0, &inventory).unwrap();
+    let stored = inventory.remove_item(sword_uuid).unwrap();
+    storage.add_to_inv(stored.item).unwrap();
+    inventory.equip_item(create_test_weapon(ItemId::Dagger, 3), EquipmentSlot::Weapon);
+    inventory.add_to_inv(create_test_material(ItemId::Coal)).unwrap();
+
+    assert_eq!(
+        loadouts.switch(0, &mut inventory, Some(&mut storage)),
+        Err(LoadoutError::BackpackFull)
+    );
+    assert_eq!(storage.items.len(), 1);
+}
@@ -1,9 +1,10 @@
+use std::cmp::Reverse;
+
 use uuid::Uuid;
 
+use crate::item::enums::{ItemQuality, ItemType};
 use crate::item::{Item, ItemId};
-use crate::stats::StatType;
-
-use super::{AddItemResult, EquipmentSlot, Inventory, InventoryError, InventoryItem};
+use super::{AddItemResult, EquipmentComparison, EquipmentSlot, Inventory, InventoryError, InventoryItem, InventorySort};
 
 // =============================================================================
 // Core Trait - Required methods only
@@ -31,18 +32,18 @@ pub trait ManagesItems: HasInventory {
         let inv = self.inventory_mut();
 
         // Try to stack with existing item of same kind (only for non-equipment)
-        if !item.item_type.is_equipment() {
-            if let Some((index, existing)) = inv.items.iter_mut()
+        if !item.item_type.is_equipment()
+            && let Some((index, existing)) = inv.items.iter_mut()
                 .enumerate()
                 .find(|(_, i)| i.item.item_id == item.item_id && i.quantity < i.item.max_stack_quantity)
-            {
-                existing.quantity += 1;
-                return Ok(AddItemResult {
-                    was_stacked: true,
-                    total_quantity: existing.quantity,
-                    slot_index: index,
-                });
-            }
+        {
+            existing.quantity += 1;
+            existing.is_new = true;
+            return Ok(AddItemResult {
+                was_stacked: true,
+                total_quantity: existing.quantity,
+                slot_index: index,
+            });
         }
 
         // Otherwise add new slot
@@ -50,7 +51,10 @@ pub trait ManagesItems: HasInventory {
             return Err(InventoryError::Full);
         }
         let slot_index = inv.items.len();
-        inv.items.push(InventoryItem::new(item));
+        inv.items.push(InventoryItem {
+            is_new: true,
+            ..InventoryItem::new(item)
+        });
         Ok(AddItemResult {
             was_stacked: false,
             total_quantity: 1,
@@ -81,16 +85,82 @@ pub trait ManagesItems: HasInventory {
             .sum()
     }
 
-    /// Decrease item quantity, removing if it reaches zero.
-    /// Searches both inventory items and equipment.
+    /// Reorder the items vec. Stacks that compare equal keep their relative order.
+    fn sort_items(&mut self, sort: InventorySort) {
+        let items = &mut self.inventory_mut().items;
+        match sort {
+            InventorySort::Type => items.sort_by(|a, b| {
+                type_rank(a.item.item_type)
+                    .cmp(&type_rank(b.item.item_type))
+                    .then_with(|| a.item.name.cmp(&b.item.name))
+            }),
+            InventorySort::Value => items.sort_by_key(|i| Reverse(i.item.gold_value)),
+            InventorySort::Quality => items.sort_by(|a, b| {
+                b.item.quality.cmp(&a.item.quality).then_with(|| a.item.name.cmp(&b.item.name))
+            }),
+            InventorySort::Favorites => items.sort_by_key(|i| !i.item.is_favorite),
+            InventorySort::New => items.sort_by_key(|i| !i.is_new),
+        }
+    }
+
+    /// Move `amount` items off the stack at `index` into a new stack right after it.
+    /// Returns the index of the new stack.
+    fn split_stack(&mut self, index: usize, amount: u32) -> Result<usize, InventoryError> {
+        let inv = self.inventory_mut();
+        let Some(stack) = inv.items.get(index) else {
+            return Err(InventoryError::InvalidSplit);
+        };
+        if amount == 0 || amount >= stack.quantity {
+            return Err(InventoryError::InvalidSplit);
+        }
+        if inv.items.len() >= inv.max_slots() {
+            return Err(InventoryError::Full);
+        }
+
+        let mut item = stack.item.clone();
+        item.item_uuid = Uuid::new_v4();
+        let is_new = stack.is_new;
+        inv.items[index].decrease_quantity(amount);
+        inv.items.insert(index + 1, InventoryItem { item, quantity: amount, is_new });
+        Ok(index + 1)
+    }
+
+    /// Backpack stacks the store's sell-all-junk action would sell: anything below
+    /// `threshold` quality that isn't locked, a favorite, or a quest item.
+    fn junk_items(&self, threshold: ItemQuality) -> Vec<&InventoryItem> {
+        self.inventory()
+            .items
+            .iter()
+            .filter(|i| is_junk(&i.item, threshold))
+            .collect()
+    }
+
+    /// Removes and returns every stack [`ManagesItems::junk_items`] lists.
+    fn take_junk(&mut self, threshold: ItemQuality) -> Vec<InventoryItem> {
+        let items = std::mem::take(&mut self.inventory_mut().items);
+        let (junk, kept) = items.into_iter().partition(|i| is_junk(&i.item, threshold));
+        self.inventory_mut().items = kept;
+        junk
+    }
+
+    /// Decrease item quantity, removing each stack that reaches zero.
+    /// Takes from one inventory stack after another until `amount` is gone, then from
+    /// equipment.
     fn decrease_item_quantity(&mut self, item_id: ItemId, amount: u32) {
+        let mut remaining = amount;
 
         // Check inventory items
-        if let Some(index) = self.inventory().items.iter().position(|i| i.item.item_id == item_id) {
-            self.inventory_mut().items[index].decrease_quantity(amount);
-            if self.inventory().items[index].quantity == 0 {
-                self.inventory_mut().items.remove(index);
+        let items = &mut self.inventory_mut().items;
+        for stack in items.iter_mut().filter(|i| i.item.item_id == item_id) {
+            let take = stack.quantity.min(remaining);
+            stack.decrease_quantity(take);
+            remaining -= take;
+            if remaining == 0 {
+                break;
             }
+        }
+        items.retain(|i| i.item.item_id != item_id || i.quantity > 0);
+        if remaining == 0 {
             return;
         }
 
@@ -102,7 +172,7 @@ pub trait ManagesItems: HasInventory {
         if let Some(slot) = slot_to_remove {
             let equipment = self.inventory_mut().equipment_mut();
             if let Some(inv) = equipment.get_mut(&slot) {
-                inv.decrease_quantity(amount);
+                inv.decrease_quantity(remaining);
                 if inv.quantity == 0 {
                     equipment.remove(&slot);
                 }
@@ -114,6 +184,23 @@ pub trait ManagesItems: HasInventory {
 // Blanket implementation for all types with HasInventory
 impl<T: HasInventory> ManagesItems for T {}
 
+fn is_junk(item: &Item, threshold: ItemQuality) -> bool {
+    !item.is_locked
+        && !item.is_favorite
+        && item.item_type != ItemType::QuestItem
+        && item.quality < threshold
+}
+
+/// Grouping used by [`InventorySort::Type`]: gear first, then materials, then consumables.
+fn type_rank(item_type: ItemType) -> u8 {
+    match item_type {
+        ItemType::Equipment(_) => 0,
+        ItemType::Material(_) => 1,
+        ItemType::Consumable(_) => 2,
+        ItemType::QuestItem => 3,
+    }
+}
+
 // =============================================================================
 // ManagesEquipment - Equipment hashmap management
 // =============================================================================
@@ -135,11 +222,27 @@ pub trait ManagesEquipment: HasInventory + ManagesItems {
 
         if let Some(mut inv_item) = self.inventory_mut().equipment_mut().remove(&slot) {
             inv_item.item.set_is_equipped(false);
-            self.add_to_inv(inv_item.item)?;
+            let result = self.add_to_inv(inv_item.item)?;
+            // Taking gear off isn't acquiring it.
+            self.inventory_mut().items[result.slot_index].is_new = false;
         }
         Ok(())
     }
 
+    /// The slot `item_type` would be equipped in: the first of its slots that is empty, or
+    /// its first slot when all are taken, so a second ring goes on the other hand.
+    fn slot_for(&self, item_type: ItemType) -> Option<EquipmentSlot> {
+        let ItemType::Equipment(equipment) = item_type else {
+            return None;
+        };
+        let slots = equipment.slots();
+        slots
+            .iter()
+            .copied()
+            .find(|slot| self.get_equipped_item(*slot).is_none())
+            .or(slots.first().copied())
+    }
+
     fn equip_item(&mut self, mut item: Item, slot: EquipmentSlot) {
         let _ = self.unequip_item(slot);
         item.set_is_equipped(true);
@@ -156,25 +259,15 @@ pub trait ManagesEquipment: HasInventory + ManagesItems {
         }
     }
 
-    /// Get comparison stats from the equipped item in the same slot as the given item.
-    /// Returns None if the item is not equipment, or Some(empty vec) if slot is empty.
-    fn get_comparison_stats(&self, item: &crate::item::Item) -> Option<Vec<(StatType, i32)>> {
-        let slot = item.item_type.equipment_slot()?;
-
-        let comparison: Vec<_> = self
-            .get_equipped_item(slot)
-            .map(|equipped| {
-                equipped
-                    .item
-                    .stats
-                    .stats()
-                    .iter()
-                    .map(|(t, si)| (*t, si.current_value))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        Some(comparison)
+    /// Compares `item` with the item equipped in the slot it would go in. Returns None if the
+    /// item is not equipment or is itself equipped.
+    fn compare_to_equipped(&self, item: &Item) -> Option<EquipmentComparison> {
+        let slot = self.slot_for(item.item_type)?;
+        if self.inventory().equipment().values().any(|e| e.uuid() == item.item_uuid) {
+            return None;
+        }
+        let equipped = self.get_equipped_item(slot);
+        Some(EquipmentComparison::new(item, equipped.map(|e| &e.item)))
     }
 }
 
@@ -2,9 +2,9 @@ use uuid::Uuid;
 
 use crate::{item::enums::{ItemError, ItemQuality, UpgradeResult}, stats::{StatSheet, StatType}};
 
-pub use super::definitions::ItemId;
 pub use super::enums::ItemType;
-pub use super::sprite_info::SpriteInfo;
+pub use super::id::ItemId;
+pub use super::sprite_info::{ItemSheet, SpriteInfo};
 
 #[derive(Debug, Clone)]
 pub struct Item {
@@ -14,6 +14,8 @@ pub struct Item {
     pub name: String,
     pub is_equipped: bool,
     pub is_locked: bool,
+    /// Starred by the player. Favorites stay put when junk is sold and can be sorted first.
+    pub is_favorite: bool,
     pub num_upgrades: i32,
     pub max_upgrades: i32,
     pub max_stack_quantity: u32,
@@ -77,4 +79,40 @@ impl Item {
         }
     }
 
+    /// Drops the item one quality tier. Returns the new quality, or `None` if it was
+    /// already Poor.
+    pub fn downgrade_quality(&mut self) -> Option<ItemQuality> {
+        self.quality = self.quality.previous_quality()?;
+        self.recalculate_stats();
+        Some(self.quality)
+    }
+
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Item {
+    /// A Normal-quality item with no stats or upgrades, for tests that don't need a real spec.
+    /// Equipment doesn't stack; everything else stacks to 99.
+    pub fn test(item_id: ItemId, item_type: ItemType) -> Self {
+        Self {
+            item_uuid: Uuid::new_v4(),
+            item_id,
+            item_type,
+            name: format!("{item_id:?}"),
+            is_equipped: false,
+            is_locked: false,
+            is_favorite: false,
+            num_upgrades: 0,
+            max_upgrades: 0,
+            max_stack_quantity: if item_type.is_equipment() { 1 } else { 99 },
+            base_stats: StatSheet::new(),
+            stats: StatSheet::new(),
+            gold_value: 10,
+            quality: ItemQuality::Normal,
+            sprite: SpriteInfo {
+                name: String::new(),
+                sheet_key: ItemSheet::IconItems,
+            },
+        }
+    }
 }
@@ -0,0 +1,319 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::skills::blacksmith_quality_bonus;
+use crate::stats::StatSheet;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum ItemType {
+    Equipment(EquipmentType),
+    Material(MaterialType),
+    Consumable(ConsumableType),
+    QuestItem,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum EquipmentType {
+    Weapon,
+    /// A weapon that looses arrows instead of swinging.
+    Bow,
+    Shield,
+    /// Worn in either ring slot.
+    Ring,
+    Amulet,
+    Tool(ToolKind),
+    Armor(crate::inventory::EquipmentSlot),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum MaterialType {
+    Ore,
+    Fuel,
+    Gem,
+    CraftingMaterial,
+    UpgradeStone,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum ConsumableType {
+    Potion,
+    Food,
+    Scroll,
+    /// Thrown at weakened mobs to capture them for the ranch.
+    Orb,
+    /// Thrown in the dungeon, bursting on the first mob or wall it hits.
+    Throwable,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum ToolKind {
+    Pickaxe,
+}
+
+impl ItemType {
+    pub fn is_equipment(&self) -> bool {
+        matches!(self, ItemType::Equipment(_))
+    }
+
+    pub fn is_stackable(&self) -> bool {
+        !self.is_equipment() && !self.is_quest_item()
+    }
+
+    pub fn is_tool(&self) -> bool {
+        matches!(self, ItemType::Equipment(EquipmentType::Tool(_)))
+    }
+
+    pub fn is_material(&self) -> bool {
+        matches!(self, ItemType::Material(_))
+    }
+
+    pub fn is_consumable(&self) -> bool {
+        matches!(self, ItemType::Consumable(_))
+    }
+
+    pub fn is_quest_item(&self) -> bool {
+        matches!(self, ItemType::QuestItem)
+    }
+
+    pub fn equipment_slot(&self) -> Option<crate::inventory::EquipmentSlot> {
+        match self {
+            ItemType::Equipment(eq) => Some(eq.slot()),
+            _ => None,
+        }
+    }
+
+    /// Whether this item can be equipped in `slot`.
+    pub fn fits_slot(&self, slot: crate::inventory::EquipmentSlot) -> bool {
+        match self {
+            ItemType::Equipment(eq) => eq.slots().contains(&slot),
+            _ => false,
+        }
+    }
+}
+
+impl EquipmentType {
+    /// The slot this equipment goes in by default.
+    pub fn slot(&self) -> crate::inventory::EquipmentSlot {
+        self.slots()[0]
+    }
+
+    /// Every slot this equipment can go in, in the order they are filled.
+    pub fn slots(&self) -> &'static [crate::inventory::EquipmentSlot] {
+        use crate::inventory::EquipmentSlot;
+        match self {
+            EquipmentType::Weapon | EquipmentType::Bow => &[EquipmentSlot::Weapon],
+            EquipmentType::Shield => &[EquipmentSlot::OffHand],
+            EquipmentType::Ring => &[EquipmentSlot::Ring1, EquipmentSlot::Ring2],
+            EquipmentType::Amulet => &[EquipmentSlot::Amulet],
+            EquipmentType::Tool(_) => &[EquipmentSlot::Tool],
+            EquipmentType::Armor(slot) => {
+                let all = EquipmentSlot::all();
+                let index = all.iter().position(|s| s == slot).unwrap_or(0);
+                &all[index..=index]
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ItemType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemType::Equipment(eq) => write!(f, "Equipment ({})", eq),
+            ItemType::Material(mat) => write!(f, "Material ({})", mat),
+            ItemType::Consumable(con) => write!(f, "Consumable ({})", con),
+            ItemType::QuestItem => write!(f, "Quest Item"),
+        }
+    }
+}
+
+impl std::fmt::Display for EquipmentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquipmentType::Weapon => write!(f, "Weapon"),
+            EquipmentType::Bow => write!(f, "Bow"),
+            EquipmentType::Shield => write!(f, "Shield"),
+            EquipmentType::Ring => write!(f, "Ring"),
+            EquipmentType::Amulet => write!(f, "Amulet"),
+            EquipmentType::Tool(kind) => write!(f, "{}", kind),
+            EquipmentType::Armor(slot) => write!(f, "{:?}", slot),
+        }
+    }
+}
+
+impl std::fmt::Display for MaterialType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaterialType::Ore => write!(f, "Ore"),
+            MaterialType::Fuel => write!(f, "Fuel"),
+            MaterialType::Gem => write!(f, "Gem"),
+            MaterialType::CraftingMaterial => write!(f, "Crafting Material"),
+            MaterialType::UpgradeStone => write!(f, "Upgrade Stone"),
+        }
+    }
+}
+
+impl std::fmt::Display for ConsumableType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsumableType::Potion => write!(f, "Potion"),
+            ConsumableType::Food => write!(f, "Food"),
+            ConsumableType::Scroll => write!(f, "Scroll"),
+            ConsumableType::Orb => write!(f, "Orb"),
+            ConsumableType::Throwable => write!(f, "Throwable"),
+        }
+    }
+}
+
+impl std::fmt::Display for ToolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToolKind::Pickaxe => write!(f, "Pickaxe"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ItemError {
+    MaxUpgradesReached,
+    NotEquipment,
+    MaxQualityReached,
+    NotAConsumable,
+}
+
+/// Result of an item upgrade, containing the new level and stat increases
+#[derive(Debug, Clone)]
+pub struct UpgradeResult {
+    /// The new upgrade level after the upgrade
+    pub new_level: i32,
+    /// The delta of stats that were increased
+    pub stat_increases: StatSheet,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ItemQuality {
+    Poor,
+    Normal,
+    Improved,
+    WellForged,
+    Masterworked,
+    Mythic
+}
+impl ItemQuality {
+    pub const ALL: [ItemQuality; 6] = [
+        ItemQuality::Poor,
+        ItemQuality::Normal,
+        ItemQuality::Improved,
+        ItemQuality::WellForged,
+        ItemQuality::Masterworked,
+        ItemQuality::Mythic,
+    ];
+
+    /// Returns the human-readable display name for this quality level
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ItemQuality::Poor => "Poor",
+            ItemQuality::Normal => "Normal",
+            ItemQuality::Improved => "Improved",
+            ItemQuality::WellForged => "Well-Forged",
+            ItemQuality::Masterworked => "Masterworked",
+            ItemQuality::Mythic => "Mythic",
+        }
+    }
+
+    pub fn next_quality(&self) -> Option<ItemQuality>{
+            match self {
+                ItemQuality::Poor         => Some(ItemQuality::Normal),
+                ItemQuality::Normal       => Some(ItemQuality::Improved),
+                ItemQuality::Improved     => Some(ItemQuality::WellForged),
+                ItemQuality::WellForged   => Some(ItemQuality::Masterworked),
+                ItemQuality::Masterworked => Some(ItemQuality::Mythic),
+                ItemQuality::Mythic       => None
+            }
+    }
+    pub fn previous_quality(&self) -> Option<ItemQuality> {
+        match self {
+            ItemQuality::Poor => None,
+            ItemQuality::Normal => Some(ItemQuality::Poor),
+            ItemQuality::Improved => Some(ItemQuality::Normal),
+            ItemQuality::WellForged => Some(ItemQuality::Improved),
+            ItemQuality::Masterworked => Some(ItemQuality::WellForged),
+            ItemQuality::Mythic => Some(ItemQuality::Masterworked),
+        }
+    }
+
+    /// Chance that a Magic Rock lifts an item of this quality to the next, before any
+    /// streak bonus.
+    pub fn upgrade_success_chance(&self) -> f64 {
+        match self {
+            ItemQuality::Poor => 0.9,
+            ItemQuality::Normal => 0.75,
+            ItemQuality::Improved => 0.6,
+            ItemQuality::WellForged => 0.45,
+            ItemQuality::Masterworked => 0.3,
+            ItemQuality::Mythic => 0.0,
+        }
+    }
+
+    pub fn roll() -> Self {
+        Self::roll_with_bonus(0)
+    }
+
+    pub fn roll_with_bonus(blacksmith_level: u32) -> Self {
+        let mut rng = rand::thread_rng();
+        let bonus = blacksmith_quality_bonus(blacksmith_level);
+        Self::from_roll(rng.gen_range(0..100) + bonus)
+    }
+
+    /// The quality a d100 roll lands on, bonuses included. Anything past 97 is Mythic.
+    pub fn from_roll(roll: i32) -> Self {
+        match roll {
+            ..=9    => ItemQuality::Poor,
+            10..=69 => ItemQuality::Normal,
+            70..=84 => ItemQuality::Improved,
+            85..=94 => ItemQuality::WellForged,
+            95..=97 => ItemQuality::Masterworked,
+            _       => ItemQuality::Mythic,
+        }
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            ItemQuality::Poor         => 0.80,
+            ItemQuality::Normal       => 1.0,
+            ItemQuality::Improved     => 1.2,
+            ItemQuality::WellForged   => 1.4,
+            ItemQuality::Masterworked => 1.6,
+            ItemQuality::Mythic       => 1.8,
+        }
+    }
+
+    pub fn value_multiplier(&self) -> f64 {
+        match self {
+            ItemQuality::Poor         => 0.90,
+            ItemQuality::Normal       => 1.0,
+            ItemQuality::Improved     => 1.1,
+            ItemQuality::WellForged   => 1.2,
+            ItemQuality::Masterworked => 1.3,
+            ItemQuality::Mythic       => 1.4,
+        }
+    }
+    pub fn upgrade_cost_multiplier(&self) -> f64 {
+        match self {
+            ItemQuality::Poor         => 0.90,
+            ItemQuality::Normal       => 1.0,
+            ItemQuality::Improved     => 1.1,
+            ItemQuality::WellForged   => 1.2,
+            ItemQuality::Masterworked => 1.3,
+            ItemQuality::Mythic       => 1.4,
+        }
+    }
+    pub fn multiply_stats(&self, sheet: &StatSheet) -> StatSheet {
+        let multiplier = self.multiplier();
+        let mut result = sheet.clone();
+        for stat in result.stats_mut().values_mut() {
+            stat.current_value = ((stat.current_value as f64) * multiplier).round() as i32;
+            stat.max_value     = ((stat.max_value     as f64) * multiplier).round() as i32;
+        }
+        result
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ItemId {
+    Sword,
+    Dagger,
+    GoldSword,
+    IronSword,
+    CopperSword,
+    BonkStick,
+    BasicShield,
+    IronHelmet,
+    IronChestplate,
+    IronGauntlets,
+    IronGreaves,
+    IronLeggings,
+    GoldHelmet,
+    GoldChestplate,
+    GoldGauntlets,
+    GoldGreaves,
+    GoldLeggings,
+    CopperHelmet,
+    CopperChestplate,
+    CopperGauntlets,
+    CopperGreaves,
+    CopperLeggings,
+    CopperPickaxe,
+    GoldRing,
+    ImbaRing,
+    SilverAmulet,
+    TravelersCape,
+    Coal,
+    CopperOre,
+    IronOre,
+    GoldOre,
+    GoldIngot,
+    IronIngot,
+    CopperIngot,
+    BlueCrystal,
+    RedCrystal,
+    GreenCrystal,
+    WhiteCrystal,
+    OrangeCrystal,
+    YellowCrystal,
+    Cowhide,
+    SlimeGel,
+    Herb,
+    BasicHPPotion,
+    QualityUpgradeStone,
+    CaptureOrb,
+    Emberbrand,
+    Sunguard,
+    Shortbow,
+    FireFlask,
+    WardingRune,
+    NovaScroll,
+    FrostScroll,
+    MightScroll,
+    StoneScroll,
+    SummonScroll,
+    AmplifyScroll,
+}
+
+impl ItemId {
+    pub const ALL: &'static [ItemId] = &[
+        ItemId::Sword,
+        ItemId::Dagger,
+        ItemId::GoldSword,
+        ItemId::IronSword,
+        ItemId::CopperSword,
+        ItemId::BonkStick,
+        ItemId::BasicShield,
+        ItemId::IronHelmet,
+        ItemId::IronChestplate,
+        ItemId::IronGauntlets,
+        ItemId::IronGreaves,
+        ItemId::IronLeggings,
+        ItemId::GoldHelmet,
+        ItemId::GoldChestplate,
+        ItemId::GoldGauntlets,
+        ItemId::GoldGreaves,
+        ItemId::GoldLeggings,
+        ItemId::CopperHelmet,
+        ItemId::CopperChestplate,
+        ItemId::CopperGauntlets,
+        ItemId::CopperGreaves,
+        ItemId::CopperLeggings,
+        ItemId::CopperPickaxe,
+        ItemId::GoldRing,
+        ItemId::ImbaRing,
+        ItemId::SilverAmulet,
+        ItemId::TravelersCape,
+        ItemId::Coal,
+        ItemId::CopperOre,
+        ItemId::IronOre,
+        ItemId::GoldOre,
+        ItemId::GoldIngot,
+        ItemId::IronIngot,
+        ItemId::CopperIngot,
+        ItemId::BlueCrystal,
+        ItemId::RedCrystal,
+        ItemId::GreenCrystal,
+        ItemId::WhiteCrystal,
+        ItemId::OrangeCrystal,
+        ItemId::YellowCrystal,
+        ItemId::Cowhide,
+        ItemId::SlimeGel,
+        ItemId::Herb,
+        ItemId::BasicHPPotion,
+        ItemId::QualityUpgradeStone,
+        ItemId::CaptureOrb,
+        ItemId::Emberbrand,
+        ItemId::Sunguard,
+        ItemId::Shortbow,
+        ItemId::FireFlask,
+        ItemId::WardingRune,
+        ItemId::NovaScroll,
+        ItemId::FrostScroll,
+        ItemId::MightScroll,
+        ItemId::StoneScroll,
+        ItemId::SummonScroll,
+        ItemId::AmplifyScroll,
+    ];
+}
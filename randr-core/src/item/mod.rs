@@ -0,0 +1,12 @@
+//! Items as the player holds them: their kinds, quality, stats and upgrades.
+
+pub mod definition;
+pub mod enums;
+pub mod id;
+pub mod sprite_info;
+mod traits;
+
+pub use definition::Item;
+pub use enums::{ItemType, UpgradeResult};
+pub use id::ItemId;
+pub use sprite_info::{ItemSheet, SpriteInfo};
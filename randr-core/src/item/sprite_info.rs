@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+/// The sprite sheets item icons come from. The game maps each to its loaded asset.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize)]
+pub enum ItemSheet {
+    #[default]
+    IconItems,
+    GoldSword,
+    IronSword,
+    CopperSword,
+    CraftingMaterials,
+    Headgear,
+    Chestplates,
+    Leggings,
+    Greaves,
+    GoldRing,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteInfo {
+    pub name: String,
+    pub sheet_key: ItemSheet,
+}
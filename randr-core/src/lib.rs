@@ -0,0 +1,15 @@
+//! Game rules that don't depend on Bevy: combat math, skill bonuses, stats, items,
+//! inventories, currencies and floor layouts.
+//!
+//! The `game` crate wraps these in components and systems. Code here takes and returns
+//! plain values, so it can be tested, benchmarked and reused by a frontend without
+//! building an app. The `bevy` feature derives `Component` and `Resource` on the types the
+//! game keeps in the world.
+
+pub mod combat;
+pub mod economy;
+pub mod floor;
+pub mod inventory;
+pub mod item;
+pub mod skills;
+pub mod stats;
@@ -1,3 +1,5 @@
+//! What each skill level is worth.
+
 pub struct BlacksmithBonuses {
     pub quality_bonus: i32,
     pub speed_multiplier: f32,
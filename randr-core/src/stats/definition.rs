@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-use bevy::prelude::*;
 use serde::Deserialize;
 
-#[derive(Resource, Component, Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Resource, bevy_ecs::prelude::Component))]
 #[serde(from = "HashMap<StatType, i32>")]
 pub struct StatSheet {
    pub stats: HashMap<StatType, StatInstance>
@@ -17,12 +17,6 @@ impl From<HashMap<StatType, i32>> for StatSheet {
     }
 }
 
-impl Default for StatSheet {
-    fn default() -> Self {
-        Self { stats: HashMap::new() }
-    }
-}
-
 impl StatSheet {
     pub fn new() -> Self {
         Self::default()
@@ -76,7 +70,7 @@ impl StatSheet {
 
 /// A single stat with current and max values.
 ///
-/// Note: `max_value` is only meaningful for `Health`. For other stats
+/// Note: `max_value` is only meaningful for `Health` and `Mana`. For other stats
 /// (Attack, Defense, Mining, GoldFind), `max_value` equals `current_value`
 /// and is not used for capping. This design keeps the struct uniform
 /// across all stat types.
@@ -84,7 +78,7 @@ impl StatSheet {
 pub struct StatInstance {
     pub stat_type: StatType,
     pub current_value: i32,
-    /// Only meaningful for Health (used to cap healing). For other stats,
+    /// Only meaningful for Health and Mana (used to cap healing and regen). For other stats,
     /// this mirrors current_value.
     pub max_value: i32,
 }
@@ -115,11 +109,33 @@ pub enum StatType {
     GoldFind,
     Mining,
     MagicFind,
+    /// Bends merchant prices in the player's favour.
+    Charisma,
+    /// Chance in percent that a hit lands as a critical.
+    CritChance,
+    /// Extra damage a critical deals, in percent on top of the base multiplier.
+    CritDamage,
+    /// Chance in percent to avoid a mob's blow entirely.
+    Dodge,
+    /// Spent casting spells. Like Health, it has a max it regenerates up to.
+    Mana,
 }
 
 impl StatType {
     pub fn all() -> &'static [StatType] {
-        &[StatType::Health, StatType::Attack, StatType::Defense, StatType::GoldFind, StatType::Mining, StatType::MagicFind]
+        &[
+            StatType::Health,
+            StatType::Attack,
+            StatType::Defense,
+            StatType::GoldFind,
+            StatType::Mining,
+            StatType::MagicFind,
+            StatType::Charisma,
+            StatType::CritChance,
+            StatType::CritDamage,
+            StatType::Dodge,
+            StatType::Mana,
+        ]
     }
 
     /// Returns the short display name for this stat type.
@@ -131,6 +147,11 @@ impl StatType {
             StatType::GoldFind => "Gold Find",
             StatType::Mining => "Mining",
             StatType::MagicFind => "Magic Find",
+            StatType::Charisma => "Charisma",
+            StatType::CritChance => "Crit",
+            StatType::CritDamage => "Crit DMG",
+            StatType::Dodge => "Dodge",
+            StatType::Mana => "MP",
         }
     }
 
@@ -0,0 +1,7 @@
+pub mod definition;
+pub mod traits;
+pub use definition::*;
+pub use traits::*;
+
+#[cfg(test)]
+mod tests;
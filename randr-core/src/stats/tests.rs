@@ -7,13 +7,18 @@ use crate::stats::{HasStats, StatSheet, StatType};
 fn stat_type_all_returns_all_stat_types() {
     let all_stats = StatType::all();
 
-    assert_eq!(all_stats.len(), 6);
+    assert_eq!(all_stats.len(), 11);
     assert!(all_stats.contains(&StatType::Health));
     assert!(all_stats.contains(&StatType::Attack));
     assert!(all_stats.contains(&StatType::Defense));
     assert!(all_stats.contains(&StatType::Mining));
     assert!(all_stats.contains(&StatType::GoldFind));
     assert!(all_stats.contains(&StatType::MagicFind));
+    assert!(all_stats.contains(&StatType::Charisma));
+    assert!(all_stats.contains(&StatType::CritChance));
+    assert!(all_stats.contains(&StatType::CritDamage));
+    assert!(all_stats.contains(&StatType::Dodge));
+    assert!(all_stats.contains(&StatType::Mana));
 }
 
 #[test]
@@ -0,0 +1,256 @@
+//! Checks sprite sheet metadata against its image when it is loaded, and packs the small
+//! per-item sheets into one atlas once their images are in.
+//!
+//! Sprites drawn from the same texture can be batched into a single draw call. Item icons
+//! are spread over a dozen sheets, so a dungeon floor full of drops and a grid of gear
+//! switch textures constantly. After packing, every item sheet's [`SpriteSheet`] points into
+//! the shared atlas under the same sprite names, so callers don't change.
+
+use std::collections::HashMap;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::TextureAtlasBuilder;
+use bevy::prelude::*;
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+use super::sprites::{SpriteRect, SpriteSheetMeta};
+use super::{GameSprites, SpriteSheet, SpriteSheetKey};
+
+/// Sheets whose sprites are packed together.
+pub const PACKED_SHEETS: [SpriteSheetKey; 10] = [
+    SpriteSheetKey::IconItems,
+    SpriteSheetKey::CraftingMaterials,
+    SpriteSheetKey::GoldSword,
+    SpriteSheetKey::IronSword,
+    SpriteSheetKey::CopperSword,
+    SpriteSheetKey::Headgear,
+    SpriteSheetKey::Chestplates,
+    SpriteSheetKey::Leggings,
+    SpriteSheetKey::Greaves,
+    SpriteSheetKey::GoldRing,
+];
+
+/// Largest side of the packed atlas. Past this the sheets are left as they are.
+const MAX_PACKED_SIZE: u32 = 2048;
+
+/// A problem with a sprite sheet's metadata.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AtlasIssue {
+    #[error("'{0}' has no area")]
+    Empty(String),
+    #[error("'{name}' runs past the {width}x{height} sheet")]
+    OutOfBounds { name: String, width: u32, height: u32 },
+    #[error("'{0}' is both a frame and a slice")]
+    Duplicate(String),
+}
+
+/// Everything wrong with a sheet's frames and slices, sorted by name.
+pub fn audit_sheet(meta: &SpriteSheetMeta) -> Vec<AtlasIssue> {
+    let size = meta.meta.size;
+    let frames = meta.frames.iter().map(|(name, frame)| (name, frame.frame));
+    let slices = meta
+        .meta
+        .slices
+        .iter()
+        .filter_map(|slice| Some((&slice.name, slice.keys.first()?.bounds)));
+
+    let mut issues: Vec<_> = frames
+        .chain(slices)
+        .filter_map(|(name, rect): (&String, SpriteRect)| {
+            if rect.w == 0 || rect.h == 0 {
+                Some(AtlasIssue::Empty(name.clone()))
+            } else if rect.x + rect.w > size.w || rect.y + rect.h > size.h {
+                Some(AtlasIssue::OutOfBounds {
+                    name: name.clone(),
+                    width: size.w,
+                    height: size.h,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    issues.extend(
+        meta.meta
+            .slices
+            .iter()
+            .filter(|slice| meta.frames.contains_key(&slice.name))
+            .map(|slice| AtlasIssue::Duplicate(slice.name.clone())),
+    );
+    issues.sort_by_key(|issue| issue.to_string());
+    issues
+}
+
+/// The packed item atlas, once built.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedAtlas {
+    /// How many sheets were merged. 0 if packing failed and the sheets were left alone.
+    pub sheets: usize,
+    pub sprites: usize,
+    pub size: UVec2,
+}
+
+/// Copies `rect` out of an RGBA image.
+fn crop(image: &Image, rect: URect) -> Option<Image> {
+    if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb {
+        return None;
+    }
+    let data = image.data.as_ref()?;
+    let stride = image.width() as usize * 4;
+    let row = rect.width() as usize * 4;
+    let mut pixels = Vec::with_capacity(row * rect.height() as usize);
+    for y in rect.min.y..rect.max.y {
+        let start = y as usize * stride + rect.min.x as usize * 4;
+        pixels.extend_from_slice(data.get(start..start + row)?);
+    }
+    Some(Image::new(
+        Extent3d {
+            width: rect.width(),
+            height: rect.height(),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// Waits for every item sheet's image, then packs them and repoints the sheets.
+pub(super) fn pack_item_sheets(
+    mut commands: Commands,
+    mut game_sprites: ResMut<GameSprites>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let sheets: Vec<(SpriteSheetKey, SpriteSheet)> = PACKED_SHEETS
+        .iter()
+        .filter_map(|&key| Some((key, game_sprites.get(key)?.clone())))
+        .collect();
+    if sheets.iter().any(|(_, sheet)| !images.contains(&sheet.texture)) {
+        return;
+    }
+
+    let mut entries: Vec<(SpriteSheetKey, String, Image)> = Vec::new();
+    for (key, sheet) in &sheets {
+        let (Some(image), Some(layout)) = (images.get(&sheet.texture), layouts.get(&sheet.layout))
+        else {
+            continue;
+        };
+        for (name, &index) in &sheet.sprites {
+            let cropped = layout
+                .textures
+                .get(index)
+                .and_then(|rect| crop(image, *rect));
+            match cropped {
+                Some(cropped) => entries.push((*key, name.clone(), cropped)),
+                None => warn!("Can't pack '{name}' from {}", key.asset_name()),
+            }
+        }
+    }
+
+    let mut builder = TextureAtlasBuilder::default();
+    builder
+        .max_size(UVec2::splat(MAX_PACKED_SIZE))
+        .padding(UVec2::ONE);
+    for (_, _, image) in &entries {
+        builder.add_texture(None, image);
+    }
+    let (layout, _, atlas_image) = match builder.build() {
+        Ok(built) => built,
+        Err(e) => {
+            warn!("Leaving item sheets unpacked: {e}");
+            commands.insert_resource(PackedAtlas {
+                sheets: 0,
+                sprites: 0,
+                size: UVec2::ZERO,
+            });
+            return;
+        }
+    };
+
+    let packed = PackedAtlas {
+        sheets: sheets.len(),
+        sprites: entries.len(),
+        size: layout.size,
+    };
+    let texture = images.add(atlas_image);
+    let layout = layouts.add(layout);
+    let mut names: HashMap<SpriteSheetKey, HashMap<String, usize>> = HashMap::new();
+    for (index, (key, name, _)) in entries.into_iter().enumerate() {
+        names.entry(key).or_default().insert(name, index);
+    }
+    for (key, sprites) in names {
+        game_sprites.insert(
+            key,
+            SpriteSheet {
+                texture: texture.clone(),
+                layout: layout.clone(),
+                sprites,
+            },
+        );
+    }
+
+    info!(
+        "Packed {} sprites from {} item sheets into a {}x{} atlas",
+        packed.sprites, packed.sheets, packed.size.x, packed.size.y
+    );
+    commands.insert_resource(packed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_flags_bad_frames_and_slices() {
+        let json = r#"{
+            "frames": {
+                "fine": {"frame": {"x": 0, "y": 0, "w": 16, "h": 16}},
+                "hollow": {"frame": {"x": 16, "y": 0, "w": 0, "h": 16}},
+                "spill": {"frame": {"x": 24, "y": 0, "w": 16, "h": 16}}
+            },
+            "meta": {
+                "size": {"w": 32, "h": 16},
+                "slices": [{"name": "fine", "keys": [{"bounds": {"x": 0, "y": 0, "w": 8, "h": 8}}]}]
+            }
+        }"#;
+        let meta: SpriteSheetMeta = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            audit_sheet(&meta),
+            [
+                AtlasIssue::Duplicate("fine".to_string()),
+                AtlasIssue::Empty("hollow".to_string()),
+                AtlasIssue::OutOfBounds {
+                    name: "spill".to_string(),
+                    width: 32,
+                    height: 16,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn crop_copies_the_rect() {
+        let pixels = (0..4 * 2 * 4).map(|i| i as u8).collect();
+        let image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixels,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+
+        let cropped = crop(&image, URect::new(1, 1, 3, 2)).unwrap();
+        assert_eq!(cropped.width(), 2);
+        assert_eq!(
+            cropped.data.unwrap(),
+            (20..28).map(|i| i as u8).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,6 +1,8 @@
+mod atlas;
 mod sprite_slices;
 mod sprites;
 
+pub use atlas::{audit_sheet, AtlasIssue, PackedAtlas, PACKED_SHEETS};
 pub use sprite_slices::{
     DetailPanelSlice, DungeonTileSlice, FightBannerSlice, GridSlotSlice, HealthBarSlice,
     ItemDetailIconsSlice, NineSlice, ShopBgSlice, ThreeSlice, TravelBookSlice, UiAllSlice,
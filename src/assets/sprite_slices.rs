@@ -401,7 +401,7 @@ impl ItemDetailIconsSlice {
             StatType::Health => Self::HealthIcon,
             StatType::Attack => Self::AttackIcon,
             StatType::Defense => Self::DefenseIcon,
-            _ => Self::DefaultStatIcon, // GoldFind, Mining, MagicFind
+            _ => Self::DefaultStatIcon, // GoldFind, Mining, MagicFind, Charisma, crit and dodge
         }
     }
 }
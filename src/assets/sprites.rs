@@ -5,6 +5,10 @@ use bevy::{
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use crate::item::ItemSheet;
+
+use super::atlas::{audit_sheet, pack_item_sheets, PackedAtlas};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumIter, Deserialize)]
 pub enum SpriteSheetKey {
     UiAll,
@@ -74,6 +78,23 @@ impl SpriteSheetKey {
     }
 }
 
+impl From<ItemSheet> for SpriteSheetKey {
+    fn from(sheet: ItemSheet) -> Self {
+        match sheet {
+            ItemSheet::IconItems => Self::IconItems,
+            ItemSheet::GoldSword => Self::GoldSword,
+            ItemSheet::IronSword => Self::IronSword,
+            ItemSheet::CopperSword => Self::CopperSword,
+            ItemSheet::CraftingMaterials => Self::CraftingMaterials,
+            ItemSheet::Headgear => Self::Headgear,
+            ItemSheet::Chestplates => Self::Chestplates,
+            ItemSheet::Leggings => Self::Leggings,
+            ItemSheet::Greaves => Self::Greaves,
+            ItemSheet::GoldRing => Self::GoldRing,
+        }
+    }
+}
+
 #[derive(Asset, TypePath, Debug, Deserialize)]
 pub struct SpriteSheetMeta {
     pub frames: HashMap<String, SpriteFrameMeta>,
@@ -197,7 +218,11 @@ impl Plugin for AssetPlugin {
             .init_asset_loader::<SpriteSheetMetaLoader>()
             .init_resource::<GameSprites>()
             .init_resource::<GameFonts>()
-            .add_systems(PreStartup, load_sprites);
+            .add_systems(PreStartup, load_sprites)
+            .add_systems(
+                Update,
+                pack_item_sheets.run_if(not(resource_exists::<PackedAtlas>)),
+            );
     }
 }
 
@@ -297,7 +322,7 @@ impl GameSprites {
         self.sheets.get(&key)
     }
 
-    fn insert(&mut self, key: SpriteSheetKey, sheet: SpriteSheet) {
+    pub(super) fn insert(&mut self, key: SpriteSheetKey, sheet: SpriteSheet) {
         self.sheets.insert(key, sheet);
     }
 
@@ -318,6 +343,9 @@ fn load_sprites(
         };
         let meta: SpriteSheetMeta = serde_json::from_str(&json_str)
             .unwrap_or_else(|e| panic!("Failed to parse {}: {}", json_path, e));
+        for issue in audit_sheet(&meta) {
+            warn!("Sprite sheet '{}': {issue}", key.asset_name());
+        }
 
         let png_path = if meta.meta.image.is_empty() {
             format!("sprites/{}.png", key.asset_name())
@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::economy::WorthGold;
+use crate::item::{Item, ItemId, ItemRegistry, SavedItem};
+
+/// Where the auction house is saved.
+pub const AUCTION_SAVE_PATH: &str = "auction_house.ron";
+
+/// Share of the asking price paid up front to list an item. Kept even if it goes unsold.
+pub const LISTING_FEE_PERCENT: i32 = 5;
+
+/// Share of the final bid the house keeps when a player listing sells.
+pub const SALE_CUT_PERCENT: i32 = 10;
+
+/// Days a listing stays open before it is settled.
+pub const LISTING_DAYS: u32 = 2;
+
+/// Most listings the simulated traders keep up at once.
+pub const MAX_TRADER_LISTINGS: usize = 8;
+
+/// Traders who bid on the player's listings each day.
+const BIDDERS_PER_DAY: usize = 3;
+
+/// How far an item's price can wander from its base value.
+const PRICE_FACTOR_RANGE: RangeInclusive<f32> = 0.6..=1.8;
+
+/// Largest change to an item's price factor in a single day.
+const DAILY_DRIFT: f32 = 0.15;
+
+/// The names the simulated traders go by.
+pub const TRADER_NAMES: [&str; 6] = ["Brom", "Ilsa", "Corvin", "Marta", "Quill", "Yorrick"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum AuctionError {
+    #[error("No listing with id {0}")]
+    NoSuchListing(u32),
+    #[error("Listing {0} belongs to the player")]
+    OwnListing(u32),
+    #[error("Asking price must be positive: {0}")]
+    InvalidPrice(i32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Seller {
+    Player,
+    // Index into `TRADER_NAMES`
+    Trader(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Listing {
+    pub id: u32,
+    pub item: Item,
+    pub seller: Seller,
+    /// Buyout price for trader listings; the reserve bids must reach for the player's.
+    pub asking: i32,
+    pub best_bid: Option<i32>,
+    /// Day the listing is settled on.
+    pub ends_on: u32,
+}
+
+impl Listing {
+    pub fn days_left(&self, today: u32) -> u32 {
+        self.ends_on.saturating_sub(today)
+    }
+}
+
+/// A [`Listing`] as saved, with its item reduced to a [`SavedItem`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedListing {
+    pub id: u32,
+    pub item: SavedItem,
+    pub seller: Seller,
+    pub asking: i32,
+    pub best_bid: Option<i32>,
+    pub ends_on: u32,
+}
+
+/// Everything the auction house needs to pick up where it left off.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedAuctionHouse {
+    pub listings: Vec<SavedListing>,
+    pub price_factors: HashMap<ItemId, f32>,
+    pub next_id: u32,
+    pub last_day: Option<u32>,
+}
+
+/// What became of one of the player's listings when it closed.
+#[derive(Debug, Clone)]
+pub enum Settlement {
+    Sold {
+        item_name: String,
+        bid: i32,
+        // The bid less the house's cut
+        proceeds: i32,
+    },
+    Returned {
+        item: Item,
+        asking: i32,
+    },
+}
+
+/// The town auction house, where simulated traders list their own wares and bid on the
+/// player's. Each item's price drifts from day to day around its base value.
+#[derive(Resource, Debug, Default)]
+pub struct AuctionHouse {
+    pub listings: Vec<Listing>,
+    price_factors: HashMap<ItemId, f32>,
+    next_id: u32,
+    /// The last day simulated, so days missed while in town are caught up. `None` until
+    /// the market first opens.
+    pub last_day: Option<u32>,
+}
+
+impl AuctionHouse {
+    pub fn to_saved(&self) -> SavedAuctionHouse {
+        SavedAuctionHouse {
+            listings: self
+                .listings
+                .iter()
+                .map(|l| SavedListing {
+                    id: l.id,
+                    item: SavedItem::from(&l.item),
+                    seller: l.seller,
+                    asking: l.asking,
+                    best_bid: l.best_bid,
+                    ends_on: l.ends_on,
+                })
+                .collect(),
+            price_factors: self.price_factors.clone(),
+            next_id: self.next_id,
+            last_day: self.last_day,
+        }
+    }
+
+    /// Rebuilds a saved house. Listings whose item no longer exists are dropped.
+    pub fn from_saved(saved: SavedAuctionHouse, registry: &ItemRegistry) -> Self {
+        Self {
+            listings: saved
+                .listings
+                .into_iter()
+                .filter_map(|l| {
+                    Some(Listing {
+                        id: l.id,
+                        item: l.item.restore(registry)?,
+                        seller: l.seller,
+                        asking: l.asking,
+                        best_bid: l.best_bid,
+                        ends_on: l.ends_on,
+                    })
+                })
+                .collect(),
+            price_factors: saved.price_factors,
+            next_id: saved.next_id,
+            last_day: saved.last_day,
+        }
+    }
+
+    /// What the market currently values `item` at.
+    pub fn market_price(&self, item: &Item) -> i32 {
+        let factor = self.price_factors.get(&item.item_id).copied().unwrap_or(1.0);
+        ((item.gold_value() as f32 * factor).round() as i32).max(1)
+    }
+
+    /// Fee charged to list an item at `asking`.
+    pub fn listing_fee(asking: i32) -> i32 {
+        (asking * LISTING_FEE_PERCENT / 100).max(1)
+    }
+
+    /// What the player receives for a sale at `bid`.
+    pub fn proceeds(bid: i32) -> i32 {
+        bid - bid * SALE_CUT_PERCENT / 100
+    }
+
+    /// Listings the player can buy, in listing order.
+    pub fn for_sale(&self) -> impl Iterator<Item = &Listing> {
+        self.listings.iter().filter(|l| l.seller != Seller::Player)
+    }
+
+    /// The player's open listings.
+    pub fn player_listings(&self) -> impl Iterator<Item = &Listing> {
+        self.listings.iter().filter(|l| l.seller == Seller::Player)
+    }
+
+    /// Opens a player listing that closes [`LISTING_DAYS`] after `today`. The fee is the
+    /// caller's to collect.
+    pub fn list(&mut self, item: Item, asking: i32, today: u32) -> Result<u32, AuctionError> {
+        if asking <= 0 {
+            return Err(AuctionError::InvalidPrice(asking));
+        }
+        Ok(self.push(item, Seller::Player, asking, today))
+    }
+
+    /// Removes a trader listing so it can be bought at its asking price.
+    pub fn buy(&mut self, id: u32) -> Result<Listing, AuctionError> {
+        let index = self
+            .listings
+            .iter()
+            .position(|l| l.id == id)
+            .ok_or(AuctionError::NoSuchListing(id))?;
+        if self.listings[index].seller == Seller::Player {
+            return Err(AuctionError::OwnListing(id));
+        }
+        Ok(self.listings.remove(index))
+    }
+
+    /// Puts a listing back, e.g. when the buyer couldn't pay or carry it.
+    pub fn restore(&mut self, listing: Listing) {
+        self.listings.push(listing);
+    }
+
+    /// Runs the market for `day`: prices drift, traders bid on the player's listings and
+    /// post `new_stock`, and listings ending today are settled.
+    pub fn simulate_day(
+        &mut self,
+        day: u32,
+        rng: &mut impl Rng,
+        new_stock: Vec<Item>,
+    ) -> Vec<Settlement> {
+        self.last_day = Some(day);
+        self.drift_prices(rng);
+        self.place_bids(rng);
+
+        for item in new_stock {
+            if self.for_sale().count() >= MAX_TRADER_LISTINGS {
+                break;
+            }
+            let asking = (self.market_price(&item) as f32 * rng.gen_range(0.9..1.4)) as i32;
+            let trader = rng.gen_range(0..TRADER_NAMES.len());
+            self.push(item, Seller::Trader(trader), asking.max(1), day);
+        }
+
+        self.settle(day)
+    }
+
+    fn push(&mut self, item: Item, seller: Seller, asking: i32, today: u32) -> u32 {
+        self.next_id += 1;
+        self.price_factors.entry(item.item_id).or_insert(1.0);
+        self.listings.push(Listing {
+            id: self.next_id,
+            item,
+            seller,
+            asking,
+            best_bid: None,
+            ends_on: today + LISTING_DAYS,
+        });
+        self.next_id
+    }
+
+    fn drift_prices(&mut self, rng: &mut impl Rng) {
+        for factor in self.price_factors.values_mut() {
+            *factor = (*factor + rng.gen_range(-DAILY_DRIFT..=DAILY_DRIFT))
+                .clamp(*PRICE_FACTOR_RANGE.start(), *PRICE_FACTOR_RANGE.end());
+        }
+    }
+
+    /// Each trader values a listing somewhere around its market price and outbids the
+    /// current best by a tenth if that still looks like a bargain. Listings priced well
+    /// above market draw no bids at all.
+    fn place_bids(&mut self, rng: &mut impl Rng) {
+        let markets: Vec<i32> =
+            self.listings.iter().map(|l| self.market_price(&l.item)).collect();
+        for (listing, market) in self.listings.iter_mut().zip(markets) {
+            if listing.seller != Seller::Player {
+                continue;
+            }
+            for _ in 0..BIDDERS_PER_DAY {
+                let valuation = (market as f32 * rng.gen_range(0.8..1.3)) as i32;
+                let floor = match listing.best_bid {
+                    Some(bid) => bid + (bid / 10).max(1),
+                    None => listing.asking,
+                };
+                if valuation >= floor {
+                    listing.best_bid = Some(floor);
+                }
+            }
+        }
+    }
+
+    /// Closes every listing ending on or before `day`. Expired trader listings are simply
+    /// withdrawn.
+    fn settle(&mut self, day: u32) -> Vec<Settlement> {
+        let (ended, open): (Vec<Listing>, Vec<Listing>) = std::mem::take(&mut self.listings)
+            .into_iter()
+            .partition(|l| l.ends_on <= day);
+        self.listings = open;
+
+        ended
+            .into_iter()
+            .filter(|l| l.seller == Seller::Player)
+            .map(|l| match l.best_bid {
+                Some(bid) => Settlement::Sold {
+                    item_name: l.item.name,
+                    bid,
+                    proceeds: Self::proceeds(bid),
+                },
+                None => Settlement::Returned {
+                    item: l.item,
+                    asking: l.asking,
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::item::enums::{ItemType, MaterialType};
+    use crate::save::{from_ron, to_ron};
+
+    fn ore(gold_value: i32) -> Item {
+        Item {
+            gold_value,
+            ..Item::test(ItemId::IronOre, ItemType::Material(MaterialType::Ore))
+        }
+    }
+
+    #[test]
+    fn fees_and_cuts_round_down_but_never_free() {
+        assert_eq!(AuctionHouse::listing_fee(200), 10);
+        assert_eq!(AuctionHouse::listing_fee(3), 1);
+        assert_eq!(AuctionHouse::proceeds(200), 180);
+    }
+
+    #[test]
+    fn player_listings_cannot_be_bought_or_listed_for_free() {
+        let mut house = AuctionHouse::default();
+        assert_eq!(house.list(ore(10), 0, 0), Err(AuctionError::InvalidPrice(0)));
+
+        let id = house.list(ore(10), 12, 0).unwrap();
+        assert_eq!(house.buy(id).unwrap_err(), AuctionError::OwnListing(id));
+        assert_eq!(house.buy(99).unwrap_err(), AuctionError::NoSuchListing(99));
+        assert_eq!(house.player_listings().count(), 1);
+    }
+
+    #[test]
+    fn cheap_listings_sell_and_overpriced_ones_come_back() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut house = AuctionHouse::default();
+        house.list(ore(100), 50, 0).unwrap();
+        house.list(ore(100), 10_000, 0).unwrap();
+
+        assert!(house.simulate_day(1, &mut rng, Vec::new()).is_empty());
+        let settlements = house.simulate_day(LISTING_DAYS, &mut rng, Vec::new());
+
+        assert_eq!(settlements.len(), 2);
+        assert!(matches!(
+            settlements[0],
+            Settlement::Sold { bid, proceeds, .. } if bid >= 50 && proceeds < bid
+        ));
+        assert!(matches!(settlements[1], Settlement::Returned { .. }));
+        assert_eq!(house.player_listings().count(), 0);
+    }
+
+    #[test]
+    fn prices_drift_within_bounds_and_traders_cap_their_stock() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let mut house = AuctionHouse::default();
+        let stock = (0..MAX_TRADER_LISTINGS + 4).map(|_| ore(100)).collect();
+        house.simulate_day(1, &mut rng, stock);
+        assert_eq!(house.for_sale().count(), MAX_TRADER_LISTINGS);
+
+        for day in 2..60 {
+            house.simulate_day(day, &mut rng, Vec::new());
+            let price = house.market_price(&ore(100));
+            assert!((60..=180).contains(&price), "price {price} on day {day}");
+        }
+    }
+
+    #[test]
+    fn saves_keep_listings_prices_and_ids() {
+        let registry = ItemRegistry::from_assets();
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut house = AuctionHouse::default();
+        house.list(registry.spawn(ItemId::IronOre), 40, 0).unwrap();
+        house.simulate_day(1, &mut rng, vec![registry.spawn(ItemId::IronOre)]);
+
+        let saved: SavedAuctionHouse = from_ron(&to_ron(&house.to_saved()).unwrap()).unwrap();
+        let loaded = AuctionHouse::from_saved(saved, &registry);
+
+        let ore = registry.spawn(ItemId::IronOre);
+        assert_eq!(loaded.to_saved(), house.to_saved());
+        assert_eq!(loaded.market_price(&ore), house.market_price(&ore));
+    }
+}
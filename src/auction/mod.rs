@@ -0,0 +1,8 @@
+pub mod market;
+pub mod plugin;
+
+pub use market::{
+    AuctionError, AuctionHouse, Listing, SavedAuctionHouse, SavedListing, Seller, Settlement,
+    AUCTION_SAVE_PATH, LISTING_DAYS, LISTING_FEE_PERCENT, SALE_CUT_PERCENT, TRADER_NAMES,
+};
+pub use plugin::{AuctionPlugin, AuctionRequest};
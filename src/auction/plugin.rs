@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::economy::{Currency, Wallet};
+use crate::game::{IrreversibleAction, ShowToast, UndoHistory};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::{ItemId, ItemRegistry};
+use crate::location::TownId;
+use crate::player::PlayerMarker;
+use crate::save::{load_ron, save_ron};
+use crate::states::{AppState, ScreenSet};
+use crate::storage::Storage;
+use crate::world_event::GameClock;
+
+use super::market::{AuctionHouse, SavedAuctionHouse, Settlement, AUCTION_SAVE_PATH};
+
+/// Most new listings the traders put up in a day.
+const DAILY_TRADER_LISTINGS: usize = 3;
+
+/// Something the player asked the auction house for.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionRequest {
+    /// Buy a trader listing at its asking price.
+    Buy { listing_id: u32 },
+    /// List one unit of the backpack item at `inventory_index`, paying the listing fee.
+    List { inventory_index: usize, asking: i32 },
+}
+
+pub struct AuctionPlugin;
+
+impl Plugin for AuctionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AuctionHouse>()
+            .add_message::<AuctionRequest>()
+            .add_systems(OnExit(AppState::Loading), load_auction_house)
+            .add_systems(
+                Update,
+                (
+                    simulate_market,
+                    handle_auction_requests.run_if(on_message::<AuctionRequest>),
+                    save_auction_house.run_if(
+                        resource_changed::<AuctionHouse>.and(not(resource_added::<AuctionHouse>)),
+                    ),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// Listings hold items, so the house is restored once the item registry is loaded.
+fn load_auction_house(mut house: ResMut<AuctionHouse>, registry: Res<ItemRegistry>) {
+    let saved = load_ron::<SavedAuctionHouse>(AUCTION_SAVE_PATH);
+    *house = AuctionHouse::from_saved(saved, &registry);
+}
+
+fn save_auction_house(house: Res<AuctionHouse>) {
+    if let Err(e) = save_ron(AUCTION_SAVE_PATH, &house.to_saved()) {
+        warn!("{e}");
+    }
+}
+
+/// Catches the market up to the calendar, paying out and returning the player's closed
+/// listings.
+fn simulate_market(
+    clock: Res<GameClock>,
+    mut house: ResMut<AuctionHouse>,
+    mut player: Query<(&mut Wallet, &mut Inventory), With<PlayerMarker>>,
+    mut storage: ResMut<Storage>,
+    registry: Option<Res<ItemRegistry>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Some(registry) = registry else {
+        return;
+    };
+    let today = clock.day();
+    let first_day = house.last_day.map_or(today, |day| day + 1);
+    if first_day > today {
+        return;
+    }
+    let Ok((mut wallet, mut inventory)) = player.single_mut() else {
+        return;
+    };
+
+    let pool: Vec<ItemId> = TownId::ALL
+        .iter()
+        .flat_map(|town| town.spec().merchant_pool.iter().map(|(id, _)| *id))
+        .collect();
+    let mut rng = rand::thread_rng();
+
+    for day in first_day..=today {
+        let count = rng.gen_range(0..=DAILY_TRADER_LISTINGS);
+        let stock = pool
+            .choose_multiple(&mut rng, count)
+            .map(|&id| registry.spawn(id))
+            .collect();
+
+        for settlement in house.simulate_day(day, &mut rng, stock) {
+            let message = match settlement {
+                Settlement::Sold {
+                    item_name,
+                    bid,
+                    proceeds,
+                } => {
+                    wallet.add(Currency::Gold, proceeds);
+                    loc.format(
+                        "auction.sold",
+                        &[("item", &item_name), ("bid", &bid), ("gold", &proceeds)],
+                    )
+                }
+                Settlement::Returned { item, asking } => {
+                    let name = item.name.clone();
+                    if inventory.add_to_inv(item.clone()).is_ok() {
+                        loc.format("auction.returned", &[("item", &name)])
+                    } else if let Err(e) = storage.deposit(item.clone()) {
+                        // Nowhere to put it, so it stays up for another round, free of charge.
+                        debug!("{e:?}");
+                        let _ = house.list(item, asking, day);
+                        loc.format("auction.relisted", &[("item", &name)])
+                    } else {
+                        loc.format("auction.returned_to_storage", &[("item", &name)])
+                    }
+                }
+            };
+            toast_writer.write(ShowToast::new(message));
+        }
+    }
+}
+
+fn handle_auction_requests(
+    mut requests: MessageReader<AuctionRequest>,
+    mut house: ResMut<AuctionHouse>,
+    clock: Res<GameClock>,
+    mut player: Query<(&mut Wallet, &mut Inventory), With<PlayerMarker>>,
+    mut history: ResMut<UndoHistory>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok((mut wallet, mut inventory)) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        let message = match *request {
+            AuctionRequest::Buy { listing_id } => {
+                let listing = match house.buy(listing_id) {
+                    Ok(listing) => listing,
+                    Err(e) => {
+                        warn!("{e}");
+                        continue;
+                    }
+                };
+                let name = listing.item.name.clone();
+                let price = listing.asking;
+
+                if wallet.spend(Currency::Gold, price).is_err() {
+                    house.restore(listing);
+                    loc.format(
+                        "auction.not_enough_gold",
+                        &[("need", &price), ("have", &wallet.gold())],
+                    )
+                } else if inventory.add_to_inv(listing.item.clone()).is_err() {
+                    wallet.add(Currency::Gold, price);
+                    house.restore(listing);
+                    loc.get("auction.inventory_full").to_string()
+                } else {
+                    history.record_irreversible(IrreversibleAction::Purchase);
+                    loc.format("auction.bought", &[("item", &name), ("price", &price)])
+                }
+            }
+            AuctionRequest::List {
+                inventory_index,
+                asking,
+            } => {
+                let Some(inv_item) = inventory.get_inventory_items().get(inventory_index) else {
+                    continue;
+                };
+                if inv_item.item.is_locked {
+                    toast_writer.write(ShowToast::new(loc.get("auction.item_locked")));
+                    continue;
+                }
+                let item = inv_item.item.clone();
+                let fee = AuctionHouse::listing_fee(asking);
+
+                if let Err(e) = wallet.spend(Currency::Gold, fee) {
+                    debug!("{e}");
+                    toast_writer.write(ShowToast::new(loc.format(
+                        "auction.not_enough_gold",
+                        &[("need", &fee), ("have", &wallet.gold())],
+                    )));
+                    continue;
+                }
+                let name = item.name.clone();
+                let item_id = item.item_id;
+                match house.list(item, asking, clock.day()) {
+                    Ok(_) => {
+                        inventory.decrease_item_quantity(item_id, 1);
+                        history.record_irreversible(IrreversibleAction::AuctionListing);
+                        loc.format(
+                            "auction.listed",
+                            &[("item", &name), ("price", &asking), ("fee", &fee)],
+                        )
+                    }
+                    Err(e) => {
+                        warn!("{e}");
+                        wallet.add(Currency::Gold, fee);
+                        continue;
+                    }
+                }
+            }
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
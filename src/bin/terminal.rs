@@ -0,0 +1,19 @@
+//! `randr-terminal`: the game in a plain terminal. Type `help` for the commands.
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+
+use game::headless::{headless_plugins, SIMULATION_FRAME};
+use game::plugins::GamePlugin;
+use game::terminal::TerminalPlugin;
+
+fn main() -> AppExit {
+    App::new()
+        // Logs would interleave with the game's text.
+        .add_plugins(headless_plugins().disable::<LogPlugin>())
+        .add_plugins(ScheduleRunnerPlugin::run_loop(SIMULATION_FRAME))
+        .add_plugins(GamePlugin)
+        .add_plugins(TerminalPlugin)
+        .run()
+}
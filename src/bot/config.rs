@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+/// Settings for a smoke-test run, read from `--bot <minutes> [--bot-seed <seed>] [--headless]`.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct BotConfig {
+    pub duration_secs: f32,
+    /// Seeds the bot's choices. Logged at startup so a failing run can be repeated.
+    pub seed: u64,
+    /// Run without a window.
+    pub headless: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BotConfigError {
+    #[error("Usage: --bot <minutes> [--bot-seed <seed>] [--headless]")]
+    Usage,
+    #[error("Invalid number of minutes: {0}")]
+    InvalidMinutes(String),
+    #[error("Invalid seed: {0}")]
+    InvalidSeed(String),
+}
+
+impl BotConfig {
+    /// Reads bot settings from command line arguments, without the program name. `None` when
+    /// `--bot` is not given; unrelated arguments are ignored.
+    pub fn from_args(
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<Option<Self>, BotConfigError> {
+        let mut minutes = None;
+        let mut seed = None;
+        let mut headless = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--bot" => {
+                    let value = args.next().ok_or(BotConfigError::Usage)?;
+                    let parsed = value.parse::<f32>().ok().filter(|m| *m > 0.0);
+                    minutes = Some(parsed.ok_or(BotConfigError::InvalidMinutes(value))?);
+                }
+                "--bot-seed" => {
+                    let value = args.next().ok_or(BotConfigError::Usage)?;
+                    let parsed = value.parse::<u64>().ok();
+                    seed = Some(parsed.ok_or(BotConfigError::InvalidSeed(value))?);
+                }
+                "--headless" => headless = true,
+                _ => {}
+            }
+        }
+
+        let Some(minutes) = minutes else {
+            if seed.is_some() || headless {
+                return Err(BotConfigError::Usage);
+            }
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            duration_secs: minutes * 60.0,
+            seed: seed.unwrap_or_else(rand::random),
+            headless,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<BotConfig>, BotConfigError> {
+        BotConfig::from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_bot_flags() {
+        let config = parse(&["--bot", "2.5", "--bot-seed", "7", "--headless"])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(config.duration_secs, 150.0);
+        assert_eq!(config.seed, 7);
+        assert!(config.headless);
+        assert_eq!(parse(&["--fullscreen"]), Ok(None));
+    }
+
+    #[test]
+    fn rejects_bad_bot_flags() {
+        assert_eq!(parse(&["--bot"]), Err(BotConfigError::Usage));
+        assert_eq!(parse(&["--headless"]), Err(BotConfigError::Usage));
+        assert_eq!(
+            parse(&["--bot", "0"]),
+            Err(BotConfigError::InvalidMinutes("0".into()))
+        );
+        assert_eq!(
+            parse(&["--bot", "1", "--bot-seed", "x"]),
+            Err(BotConfigError::InvalidSeed("x".into()))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::economy::{Currency, Wallet};
+use crate::inventory::Inventory;
+use crate::item::ItemId;
+use crate::mob::{Health, MobId};
+use crate::stats::{StatSheet, StatType};
+
+/// Game state the bot treats as a bug, whatever actions led to it.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvariantViolation {
+    #[error("Player health is {current}/{max}")]
+    PlayerHealth { current: i32, max: i32 },
+    #[error("Player mana is {current}/{max}")]
+    PlayerMana { current: i32, max: i32 },
+    #[error("Negative {currency:?} balance: {balance}")]
+    NegativeBalance { currency: Currency, balance: i32 },
+    #[error("Negative banked gold: {0}")]
+    NegativeBank(i32),
+    #[error("{used} backpack slots used of {max}")]
+    BackpackOverflow { used: usize, max: usize },
+    #[error("Empty stack of {0:?} left in the backpack")]
+    EmptyStack(ItemId),
+    #[error("{mob:?} health is {current}/{max}")]
+    MobHealth { mob: MobId, current: i32, max: i32 },
+}
+
+/// Every invariant the player's health, mana, wallet, bank and backpack break.
+pub fn check_player(
+    stats: &StatSheet,
+    wallet: &Wallet,
+    banked_gold: i32,
+    inventory: &Inventory,
+) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+
+    let (current, max) = (
+        stats.value(StatType::Health),
+        stats.max_value(StatType::Health),
+    );
+    if !(0..=max).contains(&current) {
+        violations.push(InvariantViolation::PlayerHealth { current, max });
+    }
+    let (current, max) = (stats.value(StatType::Mana), stats.max_value(StatType::Mana));
+    if !(0..=max).contains(&current) {
+        violations.push(InvariantViolation::PlayerMana { current, max });
+    }
+
+    for currency in Currency::ALL {
+        let balance = wallet.balance(currency);
+        if balance < 0 {
+            violations.push(InvariantViolation::NegativeBalance { currency, balance });
+        }
+    }
+    if banked_gold < 0 {
+        violations.push(InvariantViolation::NegativeBank(banked_gold));
+    }
+
+    let (used, max) = (inventory.items.len(), inventory.max_slots());
+    if used > max {
+        violations.push(InvariantViolation::BackpackOverflow { used, max });
+    }
+    violations.extend(
+        inventory
+            .items
+            .iter()
+            .filter(|slot| slot.quantity == 0)
+            .map(|slot| InvariantViolation::EmptyStack(slot.item.item_id)),
+    );
+
+    violations
+}
+
+/// The violation, if any, in a mob's health.
+pub fn check_mob(mob: MobId, health: &Health) -> Option<InvariantViolation> {
+    (!(0..=health.max).contains(&health.current)).then_some(InvariantViolation::MobHealth {
+        mob,
+        current: health.current,
+        max: health.max,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(current: i32, max: i32) -> StatSheet {
+        let mut stats = StatSheet::new().with(StatType::Health, max);
+        if let Some(health) = stats.stat_mut(StatType::Health) {
+            health.current_value = current;
+        }
+        stats
+    }
+
+    #[test]
+    fn a_fresh_player_is_valid() {
+        let violations = check_player(
+            &stats(50, 50),
+            &Wallet::with_gold(100),
+            0,
+            &Inventory::new(),
+        );
+
+        assert!(violations.is_empty(), "{violations:?}");
+    }
+
+    #[test]
+    fn flags_overhealing_and_debt() {
+        let violations = check_player(&stats(60, 50), &Wallet::default(), -5, &Inventory::new());
+
+        assert_eq!(
+            violations,
+            vec![
+                InvariantViolation::PlayerHealth {
+                    current: 60,
+                    max: 50
+                },
+                InvariantViolation::NegativeBank(-5),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_mob_health_above_max() {
+        let health = Health {
+            current: 12,
+            max: 10,
+        };
+
+        assert!(check_mob(MobId::Goblin, &Health::new(10)).is_none());
+        assert_eq!(
+            check_mob(MobId::Goblin, &health),
+            Some(InvariantViolation::MobHealth {
+                mob: MobId::Goblin,
+                current: 12,
+                max: 10
+            })
+        );
+    }
+}
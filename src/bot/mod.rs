@@ -0,0 +1,11 @@
+//! Smoke-test bot. Launched with `--bot <minutes>`, it plays the game through the same
+//! [`GameAction`](crate::input::GameAction) messages the keyboard sends, checks invariants
+//! every frame, and exits non-zero on the first violation or panic.
+
+mod config;
+mod invariants;
+mod plugin;
+
+pub use config::{BotConfig, BotConfigError};
+pub use invariants::{check_mob, check_player, InvariantViolation};
+pub use plugin::{bot_active, BotPlugin, BOT_FAILURE_REPLAY};
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::economy::Wallet;
+use crate::input::{GameAction, HeldDirection, NavigationDirection};
+use crate::inventory::Inventory;
+use crate::mob::{Health, MobMarker};
+use crate::player::{BankedGold, PlayerMarker};
+use crate::replay::ReplayState;
+use crate::states::AppState;
+use crate::stats::StatSheet;
+use crate::ui::MenuSelection;
+
+use super::config::BotConfig;
+use super::invariants::{check_mob, check_player};
+
+/// Seconds between bot decisions, a little slower than a player mashing keys.
+const DECISION_INTERVAL_SECS: f32 = 0.2;
+
+/// Replay name the session recording is saved under when an invariant breaks, so the run
+/// can be watched with `replay play bot-failure`.
+pub const BOT_FAILURE_REPLAY: &str = "bot-failure";
+
+const DIRECTIONS: [NavigationDirection; 4] = [
+    NavigationDirection::Up,
+    NavigationDirection::Down,
+    NavigationDirection::Left,
+    NavigationDirection::Right,
+];
+
+/// Run condition: true when the game was launched in bot mode.
+pub fn bot_active(config: Option<Res<BotConfig>>) -> bool {
+    config.is_some()
+}
+
+#[derive(Resource)]
+struct BotRun {
+    rng: StdRng,
+    decision: Timer,
+    started_at: f32,
+    actions: u64,
+}
+
+/// One bot decision.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BotMove {
+    Walk(Option<NavigationDirection>),
+    Act(GameAction),
+}
+
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_bot_run.run_if(bot_active))
+            .add_systems(PreUpdate, drive_bot.run_if(resource_exists::<BotRun>))
+            .add_systems(
+                Last,
+                (
+                    check_invariants,
+                    // A violation ends the run before the timer does.
+                    finish_bot_run.run_if(resource_exists::<BotRun>),
+                )
+                    .chain()
+                    .run_if(resource_exists::<BotRun>),
+            );
+    }
+}
+
+fn start_bot_run(mut commands: Commands, config: Res<BotConfig>, time: Res<Time>) {
+    info!(
+        seed = config.seed,
+        minutes = config.duration_secs / 60.0,
+        headless = config.headless,
+        "Bot run started"
+    );
+    commands.insert_resource(BotRun {
+        rng: StdRng::seed_from_u64(config.seed),
+        decision: Timer::from_seconds(DECISION_INTERVAL_SECS, TimerMode::Repeating),
+        started_at: time.elapsed_secs(),
+        actions: 0,
+    });
+}
+
+fn drive_bot(
+    time: Res<Time>,
+    state: Res<State<AppState>>,
+    menu: Res<MenuSelection>,
+    mut run: ResMut<BotRun>,
+    mut held: ResMut<HeldDirection>,
+    mut action_writer: MessageWriter<GameAction>,
+) {
+    if !run.decision.tick(time.delta()).just_finished() {
+        return;
+    }
+    let state = *state.get();
+    if state == AppState::Loading {
+        return;
+    }
+
+    match choose(state, &mut run.rng) {
+        BotMove::Walk(direction) => held.0 = direction,
+        // Quitting from the main menu would end the run early.
+        BotMove::Act(GameAction::Select) if state == AppState::Menu && menu.is_quit() => {}
+        BotMove::Act(action) => {
+            if state != AppState::Dungeon {
+                held.0 = None;
+            }
+            action_writer.write(action);
+            run.actions += 1;
+        }
+    }
+}
+
+/// What the bot does next in `state`. Dungeon time is mostly walking, fighting and
+/// interacting with rocks, chests, stations and NPCs, with a few presses inside whatever
+/// modal that opened; every other screen gets menu navigation.
+fn choose(state: AppState, rng: &mut impl Rng) -> BotMove {
+    let roll = rng.gen_range(0..100);
+    let direction = DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())];
+    match state {
+        AppState::Dungeon => match roll {
+            0..=39 => BotMove::Walk(Some(direction)),
+            40..=44 => BotMove::Walk(None),
+            45..=64 => BotMove::Act(GameAction::Attack),
+            65..=79 => BotMove::Act(GameAction::Interact),
+            80..=86 => BotMove::Act(GameAction::Navigate(direction)),
+            87..=91 => BotMove::Act(GameAction::Select),
+            92..=94 => BotMove::Act(GameAction::CloseModal),
+            95 => BotMove::Act(GameAction::OpenInventory),
+            96..=97 => BotMove::Act(GameAction::Cast),
+            _ => BotMove::Act(GameAction::ThrowFlask),
+        },
+        AppState::Menu => match roll {
+            0..=69 => BotMove::Act(GameAction::Navigate(direction)),
+            _ => BotMove::Act(GameAction::Select),
+        },
+        _ => match roll {
+            0..=49 => BotMove::Act(GameAction::Navigate(direction)),
+            50..=64 => BotMove::Act(GameAction::Select),
+            65..=74 => BotMove::Act(GameAction::NextTab),
+            75..=87 => BotMove::Act(GameAction::Back),
+            _ => BotMove::Act(GameAction::CloseModal),
+        },
+    }
+}
+
+fn check_invariants(
+    mut commands: Commands,
+    player: Query<(&StatSheet, &Wallet, &BankedGold, &Inventory), With<PlayerMarker>>,
+    mobs: Query<(&MobMarker, &Health)>,
+    replay: Res<ReplayState>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    let mut violations: Vec<_> = player
+        .iter()
+        .flat_map(|(stats, wallet, banked, inventory)| {
+            check_player(stats, wallet, banked.0, inventory)
+        })
+        .collect();
+    violations.extend(
+        mobs.iter()
+            .filter_map(|(marker, health)| check_mob(marker.0, health)),
+    );
+    if violations.is_empty() {
+        return;
+    }
+
+    for violation in &violations {
+        error!(%violation, "Bot run hit an invariant violation");
+    }
+    match replay.save(BOT_FAILURE_REPLAY) {
+        Ok(path) => error!(path = %path.display(), "Saved the failing run"),
+        Err(e) => error!(error = %e, "Could not save the failing run"),
+    }
+    commands.remove_resource::<BotRun>();
+    exit.write(AppExit::error());
+}
+
+fn finish_bot_run(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<BotConfig>,
+    run: Res<BotRun>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if time.elapsed_secs() - run.started_at < config.duration_secs {
+        return;
+    }
+    info!(actions = run.actions, "Bot run finished without violations");
+    commands.remove_resource::<BotRun>();
+    exit.write(AppExit::Success);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_makes_the_same_moves() {
+        let moves = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..50)
+                .map(|_| choose(AppState::Dungeon, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(moves(3), moves(3));
+    }
+
+    #[test]
+    fn menus_only_get_menu_input() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..200 {
+            let choice = choose(AppState::Menu, &mut rng);
+            assert!(
+                matches!(
+                    choice,
+                    BotMove::Act(GameAction::Navigate(_) | GameAction::Select)
+                ),
+                "{choice:?}"
+            );
+        }
+    }
+}
@@ -24,3 +24,20 @@ impl Default for Chest {
         }
     }
 }
+
+impl Chest {
+    /// The chest waiting in the weekly challenge's vault.
+    pub fn reward() -> Self {
+        let loot = LootTable::new()
+            .with(ItemId::GoldRing, 1, 1, StatRange(1, 1))
+            .with(ItemId::QualityUpgradeStone, 1, 1, StatRange(2, 3))
+            .with(ItemId::GoldIngot, 1, 2, StatRange(2, 4))
+            .with(ItemId::GoldSword, 1, 3, StatRange(1, 1))
+            .with(ItemId::BasicHPPotion, 1, 1, StatRange(5, 8))
+            .build();
+        Self {
+            loot,
+            is_locked: false
+        }
+    }
+}
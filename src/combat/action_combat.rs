@@ -1,12 +1,19 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::input::trigger_player_attack;
+use crate::input::{throw_flask, trigger_player_attack, GameAction};
 use crate::states::AppState;
 use crate::ui::screens::ActiveModal;
 
-use super::events::{DamageEntity, EntityDied, GoldGained, LootDropped, XpGained};
-use super::systems::{damage, death_rewards, hitbox_cleanup, hitbox_collision, rewards};
+use super::events::{
+    AttackDodged, BossPartDestroyed, DamageEntity, EntityDied, GoldGained, HitLanded,
+    LootDropped, XpGained,
+};
+use super::feel::{self, CombatFeelConfig, HitStop};
+use super::log::CombatLog;
+use super::systems::{
+    combat_log, contact, damage, death_rewards, hitbox_cleanup, hitbox_collision, rewards,
+};
 
 fn no_modal(modal: Res<ActiveModal>) -> bool {
     modal.modal.is_none()
@@ -17,31 +24,52 @@ pub struct ActionCombatPlugin;
 impl Plugin for ActionCombatPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<DamageEntity>()
+            .add_message::<BossPartDestroyed>()
+            .add_message::<HitLanded>()
+            .add_message::<AttackDodged>()
             .add_message::<GoldGained>()
             .add_message::<XpGained>()
             .add_message::<LootDropped>()
+            .init_resource::<CombatLog>()
+            .init_resource::<CombatFeelConfig>()
+            .init_resource::<HitStop>()
             .add_systems(
                 Update,
                 (
                     trigger_player_attack.run_if(no_modal),
+                    throw_flask.run_if(on_message::<GameAction>),
                     hitbox_collision::handle_hitbox_collisions.run_if(on_message::<CollisionStart>),
+                    hitbox_collision::handle_projectile_hits.run_if(on_message::<CollisionStart>),
+                    contact::strike_on_contact.run_if(on_message::<CollisionStart>),
+                    contact::strike_with_projectiles.run_if(on_message::<CollisionStart>),
                     damage::apply_damage.run_if(on_message::<DamageEntity>),
+                    combat_log::log_hits.run_if(on_message::<HitLanded>),
+                    combat_log::log_dodges.run_if(on_message::<AttackDodged>),
+                    feel::knock_back_struck_mobs.run_if(on_message::<HitLanded>),
                     (
                         death_rewards::grant_kill_gold,
                         death_rewards::grant_kill_xp,
+                        death_rewards::grant_boss_sigils,
                         death_rewards::roll_kill_loot,
+                        death_rewards::grant_nest_bounty,
+                        combat_log::log_kills,
                     )
                         .run_if(on_message::<EntityDied>),
                     death_rewards::mark_death_processed.run_if(on_message::<EntityDied>),
                     death_rewards::mark_mob_dying.run_if(on_message::<EntityDied>),
                     (
                         rewards::apply_gold_gain.run_if(on_message::<GoldGained>),
+                        rewards::apply_loot_drops.run_if(on_message::<LootDropped>),
                         rewards::apply_xp_gain.run_if(on_message::<XpGained>),
                     ),
                     hitbox_cleanup::cleanup_expired_hitboxes,
+                    contact::expire_projectiles,
+                    feel::tick_invulnerability,
+                    feel::run_hit_stop,
                 )
                     .chain()
                     .run_if(in_state(AppState::Dungeon)),
-            );
+            )
+            .add_systems(OnExit(AppState::Dungeon), feel::end_hit_stop);
     }
 }
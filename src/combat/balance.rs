@@ -0,0 +1,250 @@
+//! Test-only balance harness: pits a player built for a given level against every hostile
+//! mob thousands of times and reports how often the player wins and how long kills take.
+//!
+//! Fights are turn-based stand-ins for live combat. The player strikes, then the mob, using
+//! the same damage ranges, crits, dodge and defense as the real hit systems, with stats
+//! rolled from the mob's RON spec. Print the table while retuning specs with
+//! `cargo test balance_table -- --nocapture`.
+
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use randr_core::combat::{apply_defense, mob_attack_value, roll_dodge, Attack, CritProfile};
+
+use super::system::{
+    player_attack_value, player_crit_profile, player_effective_defense, player_effective_dodge,
+};
+use crate::data::StatRange;
+use crate::game::player::apply_level_gains;
+use crate::inventory::Inventory;
+use crate::mob::MobId;
+use crate::player::default_player_stats;
+use crate::stats::{HasStats, StatType};
+
+/// Fights behind each matchup.
+pub const FIGHTS: u32 = 2_000;
+
+/// Rounds after which a fight counts as lost: neither side can finish the other.
+const MAX_ROUNDS: u32 = 500;
+
+/// A player to send against the mobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalancePlayer {
+    pub level: i32,
+    pub combat_level: u32,
+    /// Attack and defense from equipment, added on top of the level's stats.
+    pub gear_attack: i32,
+    pub gear_defense: i32,
+}
+
+impl BalancePlayer {
+    /// A player at `level` whose combat skill and gear kept pace: combat skill at the same
+    /// level, and gear worth one attack and half a defense per level.
+    pub fn at_level(level: i32) -> Self {
+        Self {
+            level,
+            combat_level: level.max(1) as u32,
+            gear_attack: level,
+            gear_defense: level / 2,
+        }
+    }
+
+    fn fighter(&self) -> Fighter {
+        let mut stats = default_player_stats();
+        for level in 2..=self.level {
+            apply_level_gains(&mut stats, level);
+        }
+        // Equipment bonuses sum into the same totals as base stats.
+        stats.increase_stat(StatType::Attack, self.gear_attack);
+        stats.increase_stat(StatType::Defense, self.gear_defense);
+        let inventory = Inventory::new();
+
+        Fighter {
+            health: stats.max_hp(),
+            attack: player_attack_value(&stats, &inventory, self.combat_level),
+            defense: player_effective_defense(&stats, &inventory, self.combat_level),
+            crit: player_crit_profile(&stats, &inventory),
+            dodge: player_effective_dodge(&stats, &inventory),
+        }
+    }
+}
+
+/// One side of a fight.
+#[derive(Debug, Clone)]
+struct Fighter {
+    health: i32,
+    attack: Attack,
+    defense: i32,
+    crit: CritProfile,
+    dodge: i32,
+}
+
+impl Fighter {
+    fn mob(mob: MobId, rng: &mut impl Rng) -> Self {
+        let spec = mob.spec();
+        let mut roll = |range: StatRange| rng.gen_range(range.start()..=range.end());
+        Self {
+            health: roll(spec.max_health),
+            attack: mob_attack_value(roll(spec.attack)),
+            defense: roll(spec.defense),
+            crit: CritProfile::default(),
+            dodge: 0,
+        }
+    }
+
+    /// Damage one blow against `target` deals, or 0 if dodged.
+    fn strike(&self, target: &Fighter, rng: &mut impl Rng) -> i32 {
+        if roll_dodge(target.dodge, rng) {
+            return 0;
+        }
+        let (raw, _) = self.crit.roll(self.attack.roll_damage_with(rng), rng);
+        apply_defense(raw, target.defense)
+    }
+}
+
+/// How one fight ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FightOutcome {
+    pub won: bool,
+    pub rounds: u32,
+}
+
+fn fight(player: &Fighter, mob: MobId, rng: &mut impl Rng) -> FightOutcome {
+    let enemy = Fighter::mob(mob, rng);
+    let (mut health, mut enemy_health) = (player.health, enemy.health);
+    for rounds in 1..=MAX_ROUNDS {
+        enemy_health -= player.strike(&enemy, rng);
+        if enemy_health <= 0 {
+            return FightOutcome { won: true, rounds };
+        }
+        health -= enemy.strike(player, rng);
+        if health <= 0 {
+            return FightOutcome { won: false, rounds };
+        }
+    }
+    FightOutcome {
+        won: false,
+        rounds: MAX_ROUNDS,
+    }
+}
+
+/// Results of many fights between one player and one mob.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matchup {
+    pub mob: MobId,
+    pub level: i32,
+    pub win_rate: f64,
+    /// Average rounds to kill the mob, over the fights the player won.
+    pub rounds_to_kill: Option<f64>,
+}
+
+/// Runs `fights` fights of `player` against `mob`. The same seed gives the same results.
+pub fn matchup(player: BalancePlayer, mob: MobId, fights: u32, seed: u64) -> Matchup {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let fighter = player.fighter();
+    let won: Vec<u32> = (0..fights)
+        .map(|_| fight(&fighter, mob, &mut rng))
+        .filter(|outcome| outcome.won)
+        .map(|outcome| outcome.rounds)
+        .collect();
+
+    Matchup {
+        mob,
+        level: player.level,
+        win_rate: won.len() as f64 / fights.max(1) as f64,
+        rounds_to_kill: (!won.is_empty())
+            .then(|| won.iter().sum::<u32>() as f64 / won.len() as f64),
+    }
+}
+
+/// Mobs that fight back. Merchants and captives have no attack.
+pub fn hostile_mobs() -> impl Iterator<Item = MobId> {
+    MobId::ALL
+        .iter()
+        .copied()
+        .filter(|mob| mob.spec().attack.end() > 0)
+}
+
+/// Player levels each hostile mob is meant to be fought at, from the floors it spawns on.
+pub fn intended_band(mob: MobId) -> Option<RangeInclusive<i32>> {
+    match mob {
+        MobId::Slime => Some(1..=3),
+        MobId::Goblin => Some(1..=5),
+        MobId::DwarfMiner => Some(2..=6),
+        MobId::DwarfDefender | MobId::DwarfWarrior => Some(3..=8),
+        MobId::DwarfKing => Some(8..=12),
+        MobId::Colossus => Some(20..=25),
+        MobId::Merchant | MobId::TravelingMerchant | MobId::Captive => None,
+    }
+}
+
+/// Win rate and rounds to kill for every hostile mob at each of `levels`, one mob per row.
+pub fn balance_table(levels: &[i32], fights: u32) -> String {
+    let mut table = format!("{:<16}", "mob");
+    for level in levels {
+        let _ = write!(table, "{:>14}", format!("L{level}"));
+    }
+    for mob in hostile_mobs() {
+        let _ = write!(table, "\n{:<16}", format!("{mob:?}"));
+        for &level in levels {
+            let result = matchup(BalancePlayer::at_level(level), mob, fights, level as u64);
+            let rounds = result
+                .rounds_to_kill
+                .map_or("-".to_string(), |rounds| format!("{rounds:.1}"));
+            let cell = format!("{:.0}% {rounds}", result.win_rate * 100.0);
+            let _ = write!(table, "{cell:>14}");
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_hostile_mob_has_a_band() {
+        for mob in hostile_mobs() {
+            assert!(intended_band(mob).is_some(), "{mob:?} has no level band");
+        }
+    }
+
+    #[test]
+    fn no_mob_is_unbeatable_in_its_band() {
+        for mob in hostile_mobs() {
+            let band = intended_band(mob).unwrap();
+            for level in band.clone() {
+                let result = matchup(BalancePlayer::at_level(level), mob, FIGHTS, 1);
+                assert!(
+                    result.win_rate > 0.0,
+                    "{mob:?} is unbeatable at level {level}"
+                );
+            }
+            let top = matchup(BalancePlayer::at_level(*band.end()), mob, FIGHTS, 1);
+            assert!(
+                top.win_rate >= 0.5,
+                "{mob:?} still wins most fights at level {}: {:.0}%",
+                band.end(),
+                top.win_rate * 100.0
+            );
+        }
+    }
+
+    #[test]
+    fn matchups_are_reproducible() {
+        let player = BalancePlayer::at_level(5);
+        assert_eq!(
+            matchup(player, MobId::Goblin, 200, 9),
+            matchup(player, MobId::Goblin, 200, 9)
+        );
+    }
+
+    #[test]
+    fn balance_table() {
+        let table = super::balance_table(&[1, 3, 5, 8, 12, 16, 20, 25], FIGHTS);
+        println!("{table}");
+        assert_eq!(table.lines().count(), 1 + hostile_mobs().count());
+    }
+}
@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 
+use crate::item::Item;
+use crate::mob::PartKind;
+
+use super::log::DamageBreakdown;
+
 #[derive(Message, Debug, Clone)]
 pub struct PlayerAttackMob {
     pub target: Entity,
@@ -24,6 +29,25 @@ pub struct DamageEntity {
     pub amount: i32,
 }
 
+/// A player attack connected, with the numbers behind its damage.
+#[derive(Message, Debug, Clone)]
+pub struct HitLanded {
+    pub target: Entity,
+    pub damage: i32,
+    pub breakdown: DamageBreakdown,
+}
+
+/// The player dodged a mob's blow.
+#[derive(Message, Debug, Clone)]
+pub struct AttackDodged;
+
+/// A colossal boss lost one of its parts.
+#[derive(Message, Debug, Clone)]
+pub struct BossPartDestroyed {
+    pub boss: Entity,
+    pub part: PartKind,
+}
+
 #[derive(Message, Debug, Clone)]
 pub struct GoldGained {
     pub amount: i32,
@@ -36,7 +60,9 @@ pub struct XpGained {
     pub source: String,
 }
 
+/// A mob's drop, picked up, sold, or left behind according to the player's loot filter.
 #[derive(Message, Debug, Clone)]
 pub struct LootDropped {
-    pub item_name: String,
+    pub item: Item,
+    pub quantity: i32,
 }
@@ -0,0 +1,189 @@
+//! What makes a hit feel like a hit: a shove away from the blow, a moment of safety for the
+//! player after getting hurt, and a few frames where the world holds still.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::game::PlayerDamaged;
+use crate::mob::BossParts;
+use crate::ui::DungeonPlayer;
+
+use super::events::HitLanded;
+
+/// Tuning for knockback, invulnerability frames and hit-stop.
+#[derive(Resource, Clone, Debug)]
+pub struct CombatFeelConfig {
+    /// How long the player can't be hurt again after taking a hit.
+    pub invulnerable_secs: f32,
+    /// Speed of the shove a hit gives, in tiles per second.
+    pub knockback_tiles_per_second: f32,
+    pub knockback_secs: f32,
+    /// Frames the physics world holds still for when a hit lands.
+    pub hit_stop_frames: u32,
+}
+
+impl Default for CombatFeelConfig {
+    fn default() -> Self {
+        Self {
+            invulnerable_secs: 0.75,
+            knockback_tiles_per_second: 8.0,
+            knockback_secs: 0.12,
+            hit_stop_frames: 3,
+        }
+    }
+}
+
+/// The player was hurt a moment ago and can't be hurt again until this runs out.
+#[derive(Component, Deref, DerefMut)]
+pub struct Invulnerable(pub Timer);
+
+impl Invulnerable {
+    pub fn new(config: &CombatFeelConfig) -> Self {
+        Self(Timer::from_seconds(config.invulnerable_secs, TimerMode::Once))
+    }
+}
+
+/// A shove in `direction`. While it lasts, the movement systems hand the entity's velocity
+/// over to it.
+#[derive(Component, Debug)]
+pub struct Knockback {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+impl Knockback {
+    /// A shove for something at `target`, struck from `source`.
+    pub fn away_from(source: Vec2, target: Vec2, config: &CombatFeelConfig) -> Self {
+        Self::along(target - source, config)
+    }
+
+    pub fn along(direction: Vec2, config: &CombatFeelConfig) -> Self {
+        Self {
+            direction: direction.normalize_or_zero(),
+            timer: Timer::from_seconds(config.knockback_secs, TimerMode::Once),
+        }
+    }
+}
+
+/// Frames of hit-stop still to run. Physics is paused while any are left.
+#[derive(Resource, Default, Debug)]
+pub struct HitStop {
+    frames_left: u32,
+}
+
+impl HitStop {
+    pub fn start(&mut self, frames: u32) {
+        self.frames_left = self.frames_left.max(frames);
+    }
+
+    /// Counts one frame down. Returns whether that was the last one.
+    pub fn tick(&mut self) -> bool {
+        if self.frames_left == 0 {
+            return false;
+        }
+        self.frames_left -= 1;
+        self.frames_left == 0
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.frames_left > 0
+    }
+}
+
+/// Mobs the player hits are shoved away from them. Bosses hold their ground.
+pub fn knock_back_struck_mobs(
+    mut commands: Commands,
+    mut hits: MessageReader<HitLanded>,
+    config: Res<CombatFeelConfig>,
+    dungeon_player: Query<&Transform, With<DungeonPlayer>>,
+    targets: Query<&Transform, (Without<BossParts>, Without<DungeonPlayer>)>,
+) {
+    let Ok(player) = dungeon_player.single() else {
+        return;
+    };
+    let player = player.translation.truncate();
+    for hit in hits.read() {
+        let Ok(target) = targets.get(hit.target) else {
+            continue;
+        };
+        commands.entity(hit.target).try_insert(Knockback::away_from(
+            player,
+            target.translation.truncate(),
+            &config,
+        ));
+    }
+}
+
+/// Freezes physics for a few frames whenever a hit lands, either way round.
+pub fn run_hit_stop(
+    mut hits: MessageReader<HitLanded>,
+    mut damaged: MessageReader<PlayerDamaged>,
+    config: Res<CombatFeelConfig>,
+    mut hit_stop: ResMut<HitStop>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    let struck = hits.read().count() > 0;
+    let hurt = damaged.read().any(|d| d.amount > 0);
+    if struck || hurt {
+        hit_stop.start(config.hit_stop_frames);
+        if hit_stop.is_active() {
+            physics_time.pause();
+        }
+        return;
+    }
+    if hit_stop.tick() {
+        physics_time.unpause();
+    }
+}
+
+/// Lets physics run again if the player leaves the dungeon mid hit-stop.
+pub fn end_hit_stop(mut hit_stop: ResMut<HitStop>, mut physics_time: ResMut<Time<Physics>>) {
+    if hit_stop.is_active() {
+        *hit_stop = HitStop::default();
+        physics_time.unpause();
+    }
+}
+
+/// Counts down the player's invulnerability, showing them faded until it runs out.
+pub fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player: Query<(Entity, &mut Invulnerable, &mut Sprite), With<DungeonPlayer>>,
+) {
+    for (entity, mut invulnerable, mut sprite) in &mut player {
+        if invulnerable.tick(time.delta()).just_finished() {
+            sprite.color.set_alpha(1.0);
+            commands.entity(entity).remove::<Invulnerable>();
+        } else {
+            sprite.color.set_alpha(0.5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_stop_runs_for_the_longest_request() {
+        let mut hit_stop = HitStop::default();
+        assert!(!hit_stop.tick());
+
+        hit_stop.start(3);
+        hit_stop.start(1);
+        assert!(!hit_stop.tick());
+        assert!(!hit_stop.tick());
+        assert!(hit_stop.tick());
+        assert!(!hit_stop.is_active());
+    }
+
+    #[test]
+    fn knockback_points_away_from_the_blow() {
+        let config = CombatFeelConfig::default();
+        let shove = Knockback::away_from(Vec2::ZERO, Vec2::new(0.0, -4.0), &config);
+        assert!((shove.direction - Vec2::NEG_Y).length() < 1e-4);
+
+        let on_top = Knockback::away_from(Vec2::ONE, Vec2::ONE, &config);
+        assert_eq!(on_top.direction, Vec2::ZERO);
+    }
+}
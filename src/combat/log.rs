@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::mob::MobId;
+
+use super::system::{calculate_damage_reduction, AttackSources};
+
+/// Entries the combat log keeps before dropping the oldest.
+pub const COMBAT_LOG_LENGTH: usize = 6;
+
+/// How much the combat log reports.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum CombatLogVerbosity {
+    /// Damage dealt, nothing else.
+    Minimal,
+    /// Damage dealt and kills.
+    #[default]
+    Normal,
+    /// Everything in Normal, plus the roll and how much defense soaked up.
+    Detailed,
+    /// Everything in Detailed, with each hit expanded into the sources of its attack, the
+    /// crit multiplier and the defense it went through.
+    Verbose,
+}
+
+impl CombatLogVerbosity {
+    pub const ALL: [CombatLogVerbosity; 4] = [
+        CombatLogVerbosity::Minimal,
+        CombatLogVerbosity::Normal,
+        CombatLogVerbosity::Detailed,
+        CombatLogVerbosity::Verbose,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|v| *v == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the level's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            CombatLogVerbosity::Minimal => "combat_log.minimal",
+            CombatLogVerbosity::Normal => "combat_log.normal",
+            CombatLogVerbosity::Detailed => "combat_log.detailed",
+            CombatLogVerbosity::Verbose => "combat_log.verbose",
+        }
+    }
+
+    /// Whether hits keep their [`DamageBreakdown`].
+    pub fn keeps_breakdown(self) -> bool {
+        matches!(
+            self,
+            CombatLogVerbosity::Detailed | CombatLogVerbosity::Verbose
+        )
+    }
+}
+
+/// The numbers behind a hit: what the attack was summed from, the rolled damage and the
+/// range it was rolled from, any crit, and the defense it went through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageBreakdown {
+    pub sources: AttackSources,
+    pub roll: i32,
+    pub min_damage: i32,
+    pub max_damage: i32,
+    pub critical: bool,
+    /// What the roll was scaled by, in percent; 100 unless the hit was a critical.
+    pub crit_multiplier_percent: i32,
+    /// The roll after any crit, before defense.
+    pub dealt: i32,
+    pub defense: i32,
+    /// What got through defense.
+    pub damage: i32,
+}
+
+impl DamageBreakdown {
+    /// Share of the roll defense took away, in whole percent.
+    pub fn mitigation_percent(&self) -> i32 {
+        (calculate_damage_reduction(self.defense) * 100.0).round() as i32
+    }
+}
+
+/// One line of the combat log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombatLogEntry {
+    Hit {
+        target: MobId,
+        damage: i32,
+        critical: bool,
+        /// Only kept at [`CombatLogVerbosity::Detailed`] and above.
+        breakdown: Option<DamageBreakdown>,
+    },
+    Kill {
+        target: MobId,
+    },
+    /// The player dodged a blow.
+    Dodge,
+}
+
+impl CombatLogEntry {
+    pub fn hit(
+        target: MobId,
+        damage: i32,
+        breakdown: DamageBreakdown,
+        verbosity: CombatLogVerbosity,
+    ) -> Self {
+        CombatLogEntry::Hit {
+            target,
+            damage,
+            critical: breakdown.critical,
+            breakdown: verbosity.keeps_breakdown().then_some(breakdown),
+        }
+    }
+
+    /// A kill line, or `None` when the log is too terse to report kills.
+    pub fn kill(target: MobId, verbosity: CombatLogVerbosity) -> Option<Self> {
+        (verbosity != CombatLogVerbosity::Minimal).then_some(CombatLogEntry::Kill { target })
+    }
+
+    /// A dodge line, or `None` when the log only reports damage dealt.
+    pub fn dodge(verbosity: CombatLogVerbosity) -> Option<Self> {
+        (verbosity != CombatLogVerbosity::Minimal).then_some(CombatLogEntry::Dodge)
+    }
+}
+
+/// The most recent combat log entries, oldest first.
+#[derive(Resource, Debug, Default)]
+pub struct CombatLog {
+    entries: VecDeque<CombatLogEntry>,
+    /// Every entry ever pushed, including ones since dropped or cleared.
+    pushed: usize,
+}
+
+impl CombatLog {
+    pub fn push(&mut self, entry: CombatLogEntry) {
+        self.entries.push_back(entry);
+        self.pushed += 1;
+        if self.entries.len() > COMBAT_LOG_LENGTH {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &CombatLogEntry> {
+        self.entries.iter()
+    }
+
+    /// Entries pushed after the first `seen`, as counted by [`CombatLog::pushed`], that are
+    /// still kept.
+    pub fn since(&self, seen: usize) -> impl Iterator<Item = &CombatLogEntry> {
+        let new = self.pushed.saturating_sub(seen).min(self.entries.len());
+        self.entries.iter().skip(self.entries.len() - new)
+    }
+
+    pub fn pushed(&self) -> usize {
+        self.pushed
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BREAKDOWN: DamageBreakdown = DamageBreakdown {
+        sources: AttackSources {
+            base: 10,
+            equipment: 4,
+            skill: 1,
+            buffs: 1,
+        },
+        roll: 16,
+        min_damage: 12,
+        max_damage: 20,
+        critical: false,
+        crit_multiplier_percent: 100,
+        dealt: 16,
+        defense: 50,
+        damage: 8,
+    };
+
+    #[test]
+    fn verbosity_decides_what_entries_keep() {
+        let hit = |verbosity| CombatLogEntry::hit(MobId::Goblin, 8, BREAKDOWN, verbosity);
+        assert_eq!(
+            hit(CombatLogVerbosity::Normal),
+            CombatLogEntry::Hit {
+                target: MobId::Goblin,
+                damage: 8,
+                critical: false,
+                breakdown: None,
+            }
+        );
+        assert!(matches!(
+            hit(CombatLogVerbosity::Detailed),
+            CombatLogEntry::Hit {
+                breakdown: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            hit(CombatLogVerbosity::Verbose),
+            CombatLogEntry::Hit {
+                breakdown: Some(_),
+                ..
+            }
+        ));
+
+        assert_eq!(
+            CombatLogEntry::kill(MobId::Goblin, CombatLogVerbosity::Minimal),
+            None
+        );
+        assert!(CombatLogEntry::kill(MobId::Goblin, CombatLogVerbosity::Normal).is_some());
+        assert_eq!(CombatLogEntry::dodge(CombatLogVerbosity::Minimal), None);
+    }
+
+    #[test]
+    fn crits_are_reported_at_every_verbosity() {
+        let critical = DamageBreakdown {
+            critical: true,
+            crit_multiplier_percent: 150,
+            dealt: 24,
+            ..BREAKDOWN
+        };
+        let hit = CombatLogEntry::hit(MobId::Goblin, 24, critical, CombatLogVerbosity::Minimal);
+        assert!(matches!(hit, CombatLogEntry::Hit { critical: true, .. }));
+    }
+
+    #[test]
+    fn breakdown_reports_mitigation_as_a_percent() {
+        assert_eq!(BREAKDOWN.mitigation_percent(), 50);
+    }
+
+    #[test]
+    fn log_keeps_only_the_latest_entries() {
+        let mut log = CombatLog::default();
+        for _ in 0..COMBAT_LOG_LENGTH + 2 {
+            log.push(CombatLogEntry::Kill {
+                target: MobId::Goblin,
+            });
+        }
+        assert_eq!(log.entries().count(), COMBAT_LOG_LENGTH);
+    }
+
+    #[test]
+    fn since_skips_entries_already_seen() {
+        let mut log = CombatLog::default();
+        log.push(CombatLogEntry::Dodge);
+        let seen = log.pushed();
+        log.push(CombatLogEntry::Kill {
+            target: MobId::Goblin,
+        });
+        assert_eq!(
+            log.since(seen).collect::<Vec<_>>(),
+            [&CombatLogEntry::Kill {
+                target: MobId::Goblin,
+            }]
+        );
+        assert_eq!(log.since(log.pushed()).count(), 0);
+    }
+
+    #[test]
+    fn verbosity_cycles_back_to_minimal() {
+        assert_eq!(
+            CombatLogVerbosity::Detailed.next(),
+            CombatLogVerbosity::Verbose
+        );
+        assert_eq!(
+            CombatLogVerbosity::Verbose.next(),
+            CombatLogVerbosity::Minimal
+        );
+    }
+}
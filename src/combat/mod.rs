@@ -1,8 +1,12 @@
 pub mod action_combat;
 pub mod hitbox;
-mod attack;
+#[cfg(test)]
+mod balance;
 pub mod events;
+pub mod feel;
+mod log;
 pub mod plugin;
+pub mod projectile;
 mod result;
 mod system;
 pub mod systems;
@@ -11,9 +15,18 @@ mod tests;
 pub use hitbox::{AttackHitbox, AttackHitboxBundle, Attacking, HitEntities, HitboxLifetime};
 pub use action_combat::ActionCombatPlugin;
 pub use events::{
-    DamageEntity, DealDamage, EntityDied, GoldGained, LootDropped, PlayerAttackMob, XpGained,
+    AttackDodged, BossPartDestroyed, DamageEntity, DealDamage, EntityDied, GoldGained, HitLanded,
+    LootDropped, PlayerAttackMob, XpGained,
 };
+pub use feel::{CombatFeelConfig, HitStop, Invulnerable, Knockback};
+pub use log::{CombatLog, CombatLogEntry, CombatLogVerbosity, DamageBreakdown, COMBAT_LOG_LENGTH};
 pub use plugin::{ActiveCombat, CombatPlugin};
+pub use projectile::{Projectile, ProjectileBundle, ProjectileLifetime};
+pub(crate) use system::{apply_defense, player_effective_goldfind, player_effective_magicfind};
+pub use system::{
+    player_attack_sources, player_crit_profile, player_effective_dodge, roll_dodge, roll_hit,
+    AttackSources, CritProfile, BASE_CRIT_MULTIPLIER_PERCENT, MAX_DODGE_PERCENT,
+};
 
 #[cfg(test)]
-pub(crate) use system::{apply_defense, calculate_damage_reduction};
+pub(crate) use system::process_player_defeat;
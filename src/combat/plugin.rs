@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use tracing::instrument;
 
@@ -7,14 +8,16 @@ use super::system::{
     player_effective_magicfind, process_player_defeat,
 };
 
+use crate::economy::Wallet;
 use crate::entities::Progression;
+use crate::game::ItemFound;
 use crate::inventory::Inventory;
 use crate::item::ItemRegistry;
 use crate::loot::collect_loot_drops;
 use crate::mob::{
     CombatStats, DeathProcessed, GoldReward, Health, MobLootTable, MobMarker, XpReward,
 };
-use crate::player::{PlayerGold, PlayerMarker};
+use crate::player::PlayerMarker;
 use crate::ui::DyingMob;
 use crate::plugins::MobDefeated;
 use crate::skills::{SkillType, SkillXpGained, Skills};
@@ -108,14 +111,21 @@ fn process_player_attack(
     }
 }
 
+/// What a kill reports: the defeat itself, combat XP, and the items it dropped.
+#[derive(SystemParam)]
+struct KillWriters<'w> {
+    defeated: MessageWriter<'w, MobDefeated>,
+    skill_xp: MessageWriter<'w, SkillXpGained>,
+    found: MessageWriter<'w, ItemFound>,
+}
+
 #[instrument(level = "debug", skip_all)]
 fn handle_mob_death(
     mut commands: Commands,
     mut events: MessageReader<EntityDied>,
-    mut mob_defeated_events: MessageWriter<MobDefeated>,
-    mut skill_xp_events: MessageWriter<SkillXpGained>,
+    mut writers: KillWriters,
     mut player: Query<
-        (&mut StatSheet, &mut Inventory, &mut PlayerGold, &mut Progression),
+        (&mut StatSheet, &mut Inventory, &mut Wallet, &mut Progression),
         With<PlayerMarker>,
     >,
     mut mob_query: Query<(
@@ -127,7 +137,7 @@ fn handle_mob_death(
     )>,
     registry: Res<ItemRegistry>,
 ) {
-    let Ok((mut stats, mut inventory, mut gold, mut progression)) = player.single_mut() else {
+    let Ok((mut stats, mut inventory, mut wallet, mut progression)) = player.single_mut() else {
         return;
     };
 
@@ -155,17 +165,18 @@ fn handle_mob_death(
         apply_victory_rewards_direct(
             &mut stats,
             &inventory,
-            &mut gold,
+            &mut wallet,
             &mut progression,
             gold_reward.0,
             xp_reward.0,
         );
 
+        writers.found.write_batch(ItemFound::from_loot(&loot_drops));
         collect_loot_drops(&mut *inventory, &loot_drops);
 
-        mob_defeated_events.write(MobDefeated { mob_id });
+        writers.defeated.write(MobDefeated { mob_id });
 
-        skill_xp_events.write(SkillXpGained {
+        writers.skill_xp.write(SkillXpGained {
             skill: SkillType::Combat,
             amount: xp_reward.0 as u64,
         });
@@ -180,9 +191,9 @@ fn handle_mob_death(
 fn handle_player_death(
     mut commands: Commands,
     mut events: MessageReader<EntityDied>,
-    mut player: Query<(&mut StatSheet, &mut PlayerGold), With<PlayerMarker>>,
+    mut player: Query<(&mut StatSheet, &mut Wallet), With<PlayerMarker>>,
 ) {
-    let Ok((mut stats, mut player_gold)) = player.single_mut() else {
+    let Ok((mut stats, mut wallet)) = player.single_mut() else {
         return;
     };
 
@@ -191,7 +202,7 @@ fn handle_player_death(
             continue;
         }
 
-        process_player_defeat(&mut stats, &mut player_gold);
+        process_player_defeat(&mut stats, &mut wallet);
         commands.remove_resource::<ActiveCombat>();
     }
 }
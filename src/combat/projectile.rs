@@ -0,0 +1,119 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use randr_core::combat::Attack;
+
+use crate::dungeon::{player_projectile_layers, projectile_layers};
+use crate::mob::CombatStats;
+
+const PROJECTILE_SIZE: f32 = 5.0;
+const MOB_SHOT_COLOR: Color = Color::srgb(0.85, 0.75, 0.55);
+const ARROW_COLOR: Color = Color::srgb(0.9, 0.9, 0.8);
+const FLASK_COLOR: Color = Color::srgb(0.95, 0.45, 0.15);
+const PROJECTILE_LIFETIME_SECS: f32 = 2.0;
+
+/// How far either side of its power a flask's damage can land.
+const FLASK_VARIANCE: f32 = 0.2;
+
+/// Something flying across the floor. Mob shots hurt the player; arrows and flasks hurt mobs.
+#[derive(Component, Debug, Clone)]
+pub enum Projectile {
+    /// Fired by a ranged mob, carrying the shooter's stats so it hits as hard as they do.
+    Mob(CombatStats),
+    /// Loosed from the player's bow. It hits like a swing of the bow would.
+    Arrow,
+    /// Thrown by the player. It hits for the flask's own damage, whatever is equipped.
+    Flask(Attack),
+}
+
+impl Projectile {
+    /// A thrown flask that bursts for around `power` damage.
+    pub fn flask(power: i32) -> Self {
+        let variance = (power as f32 * FLASK_VARIANCE).round() as i32;
+        Projectile::Flask(Attack::new((power - variance).max(1), power + variance))
+    }
+
+    pub fn is_from_player(&self) -> bool {
+        !matches!(self, Projectile::Mob(_))
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Projectile::Mob(_) => MOB_SHOT_COLOR,
+            Projectile::Arrow => ARROW_COLOR,
+            Projectile::Flask(_) => FLASK_COLOR,
+        }
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct ProjectileLifetime(pub Timer);
+
+impl Default for ProjectileLifetime {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            PROJECTILE_LIFETIME_SECS,
+            TimerMode::Once,
+        ))
+    }
+}
+
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    projectile: Projectile,
+    lifetime: ProjectileLifetime,
+    sprite: Sprite,
+    rigid_body: RigidBody,
+    velocity: LinearVelocity,
+    collider: Collider,
+    sensor: Sensor,
+    events: CollisionEventsEnabled,
+    layers: CollisionLayers,
+    transform: Transform,
+}
+
+impl ProjectileBundle {
+    pub fn new(projectile: Projectile, position: Vec2, velocity: Vec2) -> Self {
+        let layers = if projectile.is_from_player() {
+            player_projectile_layers()
+        } else {
+            projectile_layers()
+        };
+        Self {
+            sprite: Sprite::from_color(projectile.color(), Vec2::splat(PROJECTILE_SIZE)),
+            projectile,
+            lifetime: ProjectileLifetime::default(),
+            rigid_body: RigidBody::Kinematic,
+            velocity: LinearVelocity(velocity),
+            collider: Collider::circle(PROJECTILE_SIZE / 2.0),
+            sensor: Sensor,
+            events: CollisionEventsEnabled,
+            layers,
+            transform: Transform::from_translation(position.extend(0.5)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flasks_burst_around_their_power() {
+        let Projectile::Flask(attack) = Projectile::flask(10) else {
+            panic!("expected a flask");
+        };
+        assert_eq!(attack, Attack::new(8, 12));
+
+        let Projectile::Flask(weak) = Projectile::flask(1) else {
+            panic!("expected a flask");
+        };
+        assert_eq!(weak.min_damage, 1);
+    }
+
+    #[test]
+    fn only_mob_shots_come_from_mobs() {
+        assert!(Projectile::Arrow.is_from_player());
+        assert!(Projectile::flask(5).is_from_player());
+        assert!(!Projectile::Mob(CombatStats { attack: 3, defense: 0 }).is_from_player());
+    }
+}
@@ -1,4 +1,8 @@
 #[derive(Debug, Clone)]
 pub struct AttackResult {
     pub target_died: bool,
+    /// Damage dealt after defense.
+    pub damage: i32,
+    /// The target dodged, so the blow did nothing.
+    pub dodged: bool,
 }
@@ -1,45 +1,101 @@
+use rand::Rng;
+use randr_core::combat::{mob_attack_value, Attack, ATTACK_VARIANCE};
 use tracing::instrument;
 
-use super::attack::Attack;
+use super::log::DamageBreakdown;
 use super::result::AttackResult;
 use crate::{
+    economy::{Currency, Wallet},
     inventory::Inventory,
     skills::{combat_attack_bonus, combat_defense_bonus},
     stats::{HasStats, StatSheet, StatType},
 };
 
-const DEFENSE_CONSTANT: f64 = 50.0;
+pub use randr_core::combat::{
+    apply_defense, apply_goldfind, calculate_damage_reduction, roll_dodge, CritProfile,
+    BASE_CRIT_MULTIPLIER_PERCENT, MAX_DODGE_PERCENT,
+};
 
-pub fn calculate_damage_reduction(defense: i32) -> f64 {
-    let def = defense.max(0) as f64;
-    def / (def + DEFENSE_CONSTANT)
+pub fn player_take_damage(stats: &mut StatSheet, amount: i32) {
+    stats.decrease_stat(StatType::Health, amount);
 }
 
-#[instrument(level = "debug", ret)]
-pub fn apply_defense(raw_damage: i32, defense: i32) -> i32 {
-    let reduction = calculate_damage_reduction(defense);
-    let damage_multiplier = 1.0 - reduction;
-    (raw_damage as f64 * damage_multiplier).round() as i32
+/// What an attack's damage is summed from before it's rolled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AttackSources {
+    pub base: i32,
+    pub equipment: i32,
+    pub skill: i32,
+    pub buffs: i32,
 }
 
-pub fn apply_goldfind(base_gold: i32, goldfind: i32) -> i32 {
-    let multiplier = 1.0 + (goldfind as f64 / 100.0);
-    ((base_gold as f64) * multiplier).round() as i32
-}
+impl AttackSources {
+    /// Damage that doesn't come from the player's stats, such as a spell or a flask.
+    pub fn flat(damage: i32) -> Self {
+        Self {
+            base: damage,
+            ..Self::default()
+        }
+    }
+
+    pub fn total(&self) -> i32 {
+        self.base + self.equipment + self.skill + self.buffs
+    }
 
-const ATTACK_VARIANCE: f64 = 0.25;
+    /// The range the total rolls in.
+    pub fn attack(&self) -> Attack {
+        let total = self.total();
+        let variance = (total as f64 * ATTACK_VARIANCE).round() as i32;
+        Attack::new((total - variance).max(1), total + variance)
+    }
+}
 
-pub fn player_take_damage(stats: &mut StatSheet, amount: i32) {
-    stats.decrease_stat(StatType::Health, amount);
+/// The player's attack, split by where it comes from. Buffs are already part of the stat
+/// sheet's attack, so the `buffs` they add are taken back out of the base.
+pub fn player_attack_sources(
+    stats: &StatSheet,
+    inventory: &Inventory,
+    combat_level: u32,
+    buffs: i32,
+) -> AttackSources {
+    AttackSources {
+        base: stats.attack() - buffs,
+        equipment: inventory.sum_equipment_stats(StatType::Attack),
+        skill: combat_attack_bonus(combat_level),
+        buffs,
+    }
 }
 
 pub fn player_attack_value(stats: &StatSheet, inventory: &Inventory, combat_level: u32) -> Attack {
-    let base = stats.attack();
-    let equipment_bonus = inventory.sum_equipment_stats(StatType::Attack);
-    let skill_bonus = combat_attack_bonus(combat_level);
-    let total = base + equipment_bonus + skill_bonus;
-    let variance = (total as f64 * ATTACK_VARIANCE).round() as i32;
-    Attack::new((total - variance).max(1), total + variance)
+    player_attack_sources(stats, inventory, combat_level, 0).attack()
+}
+
+/// Rolls one hit of `attack` through crits and the target's `defense`, keeping each step.
+pub fn roll_hit(
+    sources: AttackSources,
+    attack: &Attack,
+    crit: CritProfile,
+    defense: i32,
+    rng: &mut impl Rng,
+) -> DamageBreakdown {
+    let roll = attack.roll_damage_with(rng);
+    let (dealt, critical) = crit.roll(roll, rng);
+    let crit_multiplier_percent = if critical {
+        crit.multiplier_percent
+    } else {
+        100
+    };
+    DamageBreakdown {
+        sources,
+        roll,
+        min_damage: attack.min_damage,
+        max_damage: attack.max_damage,
+        critical,
+        crit_multiplier_percent,
+        dealt,
+        defense,
+        damage: apply_defense(dealt, defense),
+    }
 }
 
 pub fn player_effective_defense(stats: &StatSheet, inventory: &Inventory, combat_level: u32) -> i32 {
@@ -49,6 +105,23 @@ pub fn player_effective_defense(stats: &StatSheet, inventory: &Inventory, combat
     base + equipment_bonus + skill_bonus
 }
 
+pub fn player_crit_profile(stats: &StatSheet, inventory: &Inventory) -> CritProfile {
+    let chance =
+        stats.value(StatType::CritChance) + inventory.sum_equipment_stats(StatType::CritChance);
+    let bonus =
+        stats.value(StatType::CritDamage) + inventory.sum_equipment_stats(StatType::CritDamage);
+    CritProfile {
+        chance: chance.clamp(0, 100),
+        multiplier_percent: BASE_CRIT_MULTIPLIER_PERCENT + bonus.max(0),
+    }
+}
+
+pub fn player_effective_dodge(stats: &StatSheet, inventory: &Inventory) -> i32 {
+    let base = stats.value(StatType::Dodge);
+    let equipment_bonus = inventory.sum_equipment_stats(StatType::Dodge);
+    (base + equipment_bonus).clamp(0, MAX_DODGE_PERCENT)
+}
+
 pub fn player_effective_magicfind(stats: &StatSheet, inventory: &Inventory) -> i32 {
     let base = stats.value(StatType::MagicFind);
     let equipment_bonus = inventory.sum_equipment_stats(StatType::MagicFind);
@@ -61,9 +134,10 @@ pub fn player_effective_goldfind(stats: &StatSheet, inventory: &Inventory) -> i3
     base + equipment_bonus
 }
 
-pub fn process_player_defeat(stats: &mut StatSheet, gold: &mut crate::player::PlayerGold) {
-    let gold_lost = ((gold.0 as f64) * 0.05).round() as i32;
-    gold.subtract(gold_lost);
+/// Applies the defeat penalty. Only carried gold is at risk; banked gold is untouched.
+pub fn process_player_defeat(stats: &mut StatSheet, wallet: &mut Wallet) {
+    let gold_lost = ((wallet.gold() as f64) * 0.05).round() as i32;
+    wallet.subtract(Currency::Gold, gold_lost);
 
     let max_hp = stats.max_hp();
     stats.increase_stat(StatType::Health, max_hp);
@@ -72,14 +146,14 @@ pub fn process_player_defeat(stats: &mut StatSheet, gold: &mut crate::player::Pl
 pub fn apply_victory_rewards_direct(
     stats: &mut StatSheet,
     inventory: &Inventory,
-    gold: &mut crate::player::PlayerGold,
+    wallet: &mut Wallet,
     progression: &mut crate::entities::Progression,
     base_gold: i32,
     base_xp: i32,
 ) {
     let goldfind = player_effective_goldfind(stats, inventory);
     let gold_gained = apply_goldfind(base_gold, goldfind);
-    gold.add(gold_gained);
+    wallet.add(Currency::Gold, gold_gained);
 
     progression.add_xp(base_xp);
 }
@@ -95,13 +169,16 @@ pub fn player_attacks_entity(
     combat_level: u32,
 ) -> AttackResult {
     let player_attack = player_attack_value(player_stats, player_inventory, combat_level);
-    let raw_damage = player_attack.roll_damage();
+    let (raw_damage, _) = player_crit_profile(player_stats, player_inventory)
+        .roll(player_attack.roll_damage(), &mut rand::thread_rng());
     let damage = apply_defense(raw_damage, mob_combat_stats.defense);
 
     mob_health.take_damage(damage);
 
     AttackResult {
         target_died: !mob_health.is_alive(),
+        damage,
+        dodged: false,
     }
 }
 
@@ -112,17 +189,25 @@ pub fn entity_attacks_player(
     player_inventory: &Inventory,
     combat_level: u32,
 ) -> AttackResult {
+    let dodge = player_effective_dodge(player_stats, player_inventory);
+    if roll_dodge(dodge, &mut rand::thread_rng()) {
+        return AttackResult {
+            target_died: false,
+            damage: 0,
+            dodged: true,
+        };
+    }
+
     let defense = player_effective_defense(player_stats, player_inventory, combat_level);
 
-    let base_attack = mob_combat_stats.attack;
-    let variance = (base_attack as f64 * ATTACK_VARIANCE).round() as i32;
-    let mob_attack = Attack::new((base_attack - variance).max(1), base_attack + variance);
-    let raw_damage = mob_attack.roll_damage();
+    let raw_damage = mob_attack_value(mob_combat_stats.attack).roll_damage();
     let damage = apply_defense(raw_damage, defense);
 
     player_take_damage(player_stats, damage);
 
     AttackResult {
         target_died: player_stats.hp() <= 0,
+        damage,
+        dodged: false,
     }
 }
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use crate::combat::events::{AttackDodged, EntityDied, HitLanded};
+use crate::combat::log::{CombatLog, CombatLogEntry};
+use crate::mob::components::{DeathProcessed, MobMarker};
+use crate::settings::GameSettings;
+
+pub fn log_hits(
+    mut events: MessageReader<HitLanded>,
+    mobs: Query<&MobMarker>,
+    settings: Res<GameSettings>,
+    mut log: ResMut<CombatLog>,
+) {
+    for event in events.read() {
+        let Ok(marker) = mobs.get(event.target) else {
+            continue;
+        };
+        log.push(CombatLogEntry::hit(
+            marker.0,
+            event.damage,
+            event.breakdown,
+            settings.combat_log,
+        ));
+    }
+}
+
+pub fn log_kills(
+    mut events: MessageReader<EntityDied>,
+    mobs: Query<(&MobMarker, &DeathProcessed)>,
+    settings: Res<GameSettings>,
+    mut log: ResMut<CombatLog>,
+) {
+    for event in events.read() {
+        let Ok((marker, death_processed)) = mobs.get(event.entity) else {
+            continue;
+        };
+        if event.is_player || death_processed.0 {
+            continue;
+        }
+        if let Some(entry) = CombatLogEntry::kill(marker.0, settings.combat_log) {
+            log.push(entry);
+        }
+    }
+}
+
+pub fn log_dodges(
+    mut events: MessageReader<AttackDodged>,
+    settings: Res<GameSettings>,
+    mut log: ResMut<CombatLog>,
+) {
+    for _ in events.read() {
+        if let Some(entry) = CombatLogEntry::dodge(settings.combat_log) {
+            log.push(entry);
+        }
+    }
+}
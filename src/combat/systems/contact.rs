@@ -0,0 +1,245 @@
+use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::combat::events::AttackDodged;
+use crate::combat::feel::{CombatFeelConfig, Invulnerable, Knockback};
+use crate::combat::projectile::{Projectile, ProjectileLifetime};
+use crate::combat::result::AttackResult;
+use crate::combat::system::{entity_attacks_player, process_player_defeat};
+use crate::dungeon::{FloorTransition, Roaming};
+use crate::economy::Wallet;
+use crate::game::{PlayerDamaged, ShowToast};
+use crate::i18n::Localization;
+use crate::inventory::Inventory;
+use crate::mob::components::{CombatStats, Health};
+use crate::mob::elite::{EliteModifier, VAMPIRIC_LIFESTEAL};
+use crate::player::PlayerMarker;
+use crate::skills::{SkillType, Skills};
+use crate::stats::{HasStats, StatSheet};
+use crate::ui::{DungeonPlayer, DyingMob};
+
+/// The player's side of a blow from a mob: their stats and purse, their combat level, and
+/// what a blow reports back.
+#[derive(SystemParam)]
+pub struct PlayerBlows<'w, 's> {
+    player: Query<
+        'w,
+        's,
+        (&'static mut StatSheet, &'static Inventory, &'static mut Wallet),
+        With<PlayerMarker>,
+    >,
+    skills: Res<'w, Skills>,
+    loc: Res<'w, Localization>,
+    damaged_writer: MessageWriter<'w, PlayerDamaged>,
+    dodged_writer: MessageWriter<'w, AttackDodged>,
+    transition_writer: MessageWriter<'w, FloorTransition>,
+    toast_writer: MessageWriter<'w, ShowToast>,
+}
+
+type ContactMobs<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static CombatStats,
+        &'static mut Roaming,
+        &'static mut Health,
+        &'static Transform,
+        Option<&'static EliteModifier>,
+    ),
+    (Without<DyingMob>, Without<DungeonPlayer>),
+>;
+
+/// Roaming mobs strike the player when they touch, then back off for a moment. A knockout
+/// sends the player home, short a little gold. Vampiric elites heal from the blow. A player
+/// still shaking off the last hit isn't hurt again, and a dodged blow does nothing.
+pub fn strike_on_contact(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    dungeon_player: Query<(Entity, &Transform, Has<Invulnerable>), With<DungeonPlayer>>,
+    mut mobs: ContactMobs,
+    blows: PlayerBlows,
+    feel: Res<CombatFeelConfig>,
+) {
+    let PlayerBlows {
+        mut player,
+        skills,
+        loc,
+        mut damaged_writer,
+        mut dodged_writer,
+        mut transition_writer,
+        mut toast_writer,
+    } = blows;
+    let Ok((player_entity, player_transform, mut invulnerable)) = dungeon_player.single() else {
+        return;
+    };
+    let Ok((mut stats, inventory, mut wallet)) = player.single_mut() else {
+        return;
+    };
+    let combat_level = skills
+        .skill(SkillType::Combat)
+        .map(|s| s.level)
+        .unwrap_or(1);
+
+    for event in collisions.read() {
+        let mob = if event.collider1 == player_entity {
+            event.collider2
+        } else if event.collider2 == player_entity {
+            event.collider1
+        } else {
+            continue;
+        };
+        let Ok((combat_stats, mut roaming, mut health, mob_transform, elite)) = mobs.get_mut(mob)
+        else {
+            continue;
+        };
+
+        roaming.pause();
+        if invulnerable {
+            continue;
+        }
+        let blow = land_blow(
+            combat_stats,
+            &mut stats,
+            inventory,
+            &mut wallet,
+            combat_level,
+            &mut damaged_writer,
+        );
+        if blow.target_died {
+            toast_writer.write(ShowToast::new(loc.get("field.knocked_out")));
+            transition_writer.write(FloorTransition::ReturnToHome);
+            return;
+        }
+        if blow.dodged {
+            dodged_writer.write(AttackDodged);
+            continue;
+        }
+        if elite == Some(&EliteModifier::Vampiric) {
+            health.heal((blow.damage as f32 * VAMPIRIC_LIFESTEAL).round() as i32);
+        }
+        commands.entity(player_entity).insert((
+            Invulnerable::new(&feel),
+            Knockback::away_from(
+                mob_transform.translation.truncate(),
+                player_transform.translation.truncate(),
+                &feel,
+            ),
+        ));
+        invulnerable = true;
+    }
+}
+
+/// Mob shots hurt the player like the mob that fired them, unless the player is still
+/// shaking off the last hit. Whatever a shot runs into, it is used up.
+pub fn strike_with_projectiles(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    dungeon_player: Query<(Entity, Has<Invulnerable>), With<DungeonPlayer>>,
+    projectiles: Query<(&Projectile, &LinearVelocity)>,
+    blows: PlayerBlows,
+    feel: Res<CombatFeelConfig>,
+) {
+    let PlayerBlows {
+        mut player,
+        skills,
+        loc,
+        mut damaged_writer,
+        mut dodged_writer,
+        mut transition_writer,
+        mut toast_writer,
+    } = blows;
+    let Ok((player_entity, mut invulnerable)) = dungeon_player.single() else {
+        return;
+    };
+    let Ok((mut stats, inventory, mut wallet)) = player.single_mut() else {
+        return;
+    };
+    let combat_level = skills
+        .skill(SkillType::Combat)
+        .map(|s| s.level)
+        .unwrap_or(1);
+
+    let mut spent = Vec::new();
+    for event in collisions.read() {
+        let (shot, other) = if projectiles.contains(event.collider1) {
+            (event.collider1, event.collider2)
+        } else if projectiles.contains(event.collider2) {
+            (event.collider2, event.collider1)
+        } else {
+            continue;
+        };
+        let Ok((Projectile::Mob(shooter), velocity)) = projectiles.get(shot) else {
+            continue;
+        };
+        if spent.contains(&shot) {
+            continue;
+        }
+        spent.push(shot);
+        commands.entity(shot).try_despawn();
+        if other != player_entity || invulnerable {
+            continue;
+        }
+
+        let blow = land_blow(
+            shooter,
+            &mut stats,
+            inventory,
+            &mut wallet,
+            combat_level,
+            &mut damaged_writer,
+        );
+        if blow.target_died {
+            toast_writer.write(ShowToast::new(loc.get("field.knocked_out")));
+            transition_writer.write(FloorTransition::ReturnToHome);
+            return;
+        }
+        if blow.dodged {
+            dodged_writer.write(AttackDodged);
+            continue;
+        }
+        commands.entity(player_entity).insert((
+            Invulnerable::new(&feel),
+            Knockback::along(velocity.0, &feel),
+        ));
+        invulnerable = true;
+    }
+}
+
+pub fn expire_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<(Entity, &mut ProjectileLifetime)>,
+) {
+    for (entity, mut lifetime) in &mut projectiles {
+        if lifetime.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Applies one blow from `attacker` to the player, unless they dodge it. A knockout leaves
+/// them already patched up and docked the lost gold.
+fn land_blow(
+    attacker: &CombatStats,
+    stats: &mut StatSheet,
+    inventory: &Inventory,
+    wallet: &mut Wallet,
+    combat_level: u32,
+    damaged_writer: &mut MessageWriter<PlayerDamaged>,
+) -> AttackResult {
+    let result = entity_attacks_player(attacker, stats, inventory, combat_level);
+    if result.dodged {
+        return result;
+    }
+    damaged_writer.write(PlayerDamaged {
+        amount: result.damage,
+        current_hp: stats.hp(),
+        max_hp: stats.max_hp(),
+    });
+
+    if result.target_died {
+        process_player_defeat(stats, wallet);
+    }
+    result
+}
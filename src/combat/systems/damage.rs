@@ -1,26 +1,65 @@
 use bevy::prelude::*;
 
-use crate::combat::events::{DamageEntity, EntityDied};
-use crate::mob::components::Health;
+use crate::combat::events::{BossPartDestroyed, DamageEntity, EntityDied};
+use crate::mob::components::{CombatStats, Health};
+use crate::mob::{ArmorShell, BossParts, PartStrike};
+
+/// What a hit can land on: health, a boss's parts, stats a destroyed part weakens, and an
+/// elite's shell.
+type DamageTarget<'a> = (
+    &'a mut Health,
+    Option<&'a mut BossParts>,
+    Option<&'a mut CombatStats>,
+    Has<ArmorShell>,
+);
 
 pub fn apply_damage(
+    mut commands: Commands,
     mut events: MessageReader<DamageEntity>,
     mut death_writer: MessageWriter<EntityDied>,
-    mut targets: Query<&mut Health>,
+    mut part_writer: MessageWriter<BossPartDestroyed>,
+    mut targets: Query<DamageTarget<'static>>,
     mut already_dead: Local<Vec<Entity>>,
+    mut shattered: Local<Vec<Entity>>,
 ) {
     already_dead.clear();
+    shattered.clear();
 
     for event in events.read() {
         if already_dead.contains(&event.target) {
             continue;
         }
 
-        let Ok(mut health) = targets.get_mut(event.target) else {
+        let Ok((mut health, parts, stats, shelled)) = targets.get_mut(event.target) else {
+            continue;
+        };
+
+        // An armored elite's shell takes the first blow in its place.
+        if shelled && !shattered.contains(&event.target) {
+            shattered.push(event.target);
+            commands.entity(event.target).remove::<ArmorShell>();
             continue;
+        }
+
+        // Hits on a colossal boss land on the targeted part before they reach its core.
+        let amount = match parts.map(|mut parts| parts.strike(event.amount)) {
+            Some(PartStrike::Part { kind, destroyed }) => {
+                if destroyed {
+                    if let Some(mut stats) = stats {
+                        kind.on_destroyed(&mut stats);
+                    }
+                    part_writer.write(BossPartDestroyed {
+                        boss: event.target,
+                        part: kind,
+                    });
+                }
+                continue;
+            }
+            Some(PartStrike::Core(amount)) => amount,
+            None => event.amount,
         };
 
-        health.take_damage(event.amount);
+        health.take_damage(amount);
 
         if !health.is_alive() {
             already_dead.push(event.target);
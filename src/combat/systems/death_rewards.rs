@@ -1,11 +1,19 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::combat::events::{EntityDied, GoldGained, LootDropped, XpGained};
 use crate::combat::system::{apply_goldfind, player_effective_goldfind, player_effective_magicfind};
+use crate::dungeon::{DungeonState, NestEntity};
+use crate::economy::{Currency, Wallet};
+use crate::game::{ItemFound, ShowToast};
+use crate::i18n::Localization;
 use crate::inventory::Inventory;
 use crate::item::ItemRegistry;
-use crate::loot::collect_loot_drops;
+use crate::loot::LootDrop;
+use crate::magic::roll_word_scroll;
 use crate::mob::components::{DeathProcessed, GoldReward, MobLootTable, MobMarker, XpReward};
+use crate::mob::definitions::MobQuality;
+use crate::mob::elite::{kill_reward, EliteModifier, ELITE_MAGIC_FIND};
 use crate::player::PlayerMarker;
 use crate::stats::StatSheet;
 use crate::ui::DyingMob;
@@ -13,7 +21,7 @@ use crate::ui::DyingMob;
 pub fn grant_kill_gold(
     mut events: MessageReader<EntityDied>,
     mut gold_writer: MessageWriter<GoldGained>,
-    mobs: Query<(&MobMarker, &GoldReward, &DeathProcessed)>,
+    mobs: Query<(&MobMarker, &GoldReward, &DeathProcessed, Has<EliteModifier>)>,
     player: Query<(&StatSheet, &Inventory), With<PlayerMarker>>,
 ) {
     let Ok((stats, inventory)) = player.single() else {
@@ -26,7 +34,7 @@ pub fn grant_kill_gold(
             continue;
         }
 
-        let Ok((marker, reward, death_processed)) = mobs.get(event.entity) else {
+        let Ok((marker, reward, death_processed, elite)) = mobs.get(event.entity) else {
             continue;
         };
 
@@ -34,7 +42,7 @@ pub fn grant_kill_gold(
             continue;
         }
 
-        let amount = apply_goldfind(reward.0, goldfind);
+        let amount = apply_goldfind(kill_reward(reward.0, elite), goldfind);
         gold_writer.write(GoldGained {
             amount,
             source: marker.0.spec().name.clone(),
@@ -42,17 +50,55 @@ pub fn grant_kill_gold(
     }
 }
 
+/// Boss sigils awarded for each boss kill.
+const BOSS_SIGILS_PER_KILL: i32 = 1;
+
+pub fn grant_boss_sigils(
+    mut events: MessageReader<EntityDied>,
+    mobs: Query<(&MobMarker, &DeathProcessed)>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.is_player {
+            continue;
+        }
+
+        let Ok((marker, death_processed)) = mobs.get(event.entity) else {
+            continue;
+        };
+
+        if death_processed.0 || !matches!(marker.0.spec().quality, MobQuality::Boss) {
+            continue;
+        }
+
+        wallet.add(Currency::BossSigil, BOSS_SIGILS_PER_KILL);
+        toast_writer.write(ShowToast::new(loc.format(
+            "currency.boss_sigil_found",
+            &[
+                ("amount", &BOSS_SIGILS_PER_KILL),
+                ("total", &wallet.balance(Currency::BossSigil)),
+            ],
+        )));
+    }
+}
+
 pub fn grant_kill_xp(
     mut events: MessageReader<EntityDied>,
     mut xp_writer: MessageWriter<XpGained>,
-    mobs: Query<(&MobMarker, &XpReward, &DeathProcessed)>,
+    mobs: Query<(&MobMarker, &XpReward, &DeathProcessed, Has<EliteModifier>)>,
 ) {
     for event in events.read() {
         if event.is_player {
             continue;
         }
 
-        let Ok((marker, reward, death_processed)) = mobs.get(event.entity) else {
+        let Ok((marker, reward, death_processed, elite)) = mobs.get(event.entity) else {
             continue;
         };
 
@@ -61,7 +107,7 @@ pub fn grant_kill_xp(
         }
 
         xp_writer.write(XpGained {
-            amount: reward.0,
+            amount: kill_reward(reward.0, elite),
             source: marker.0.spec().name.clone(),
         });
     }
@@ -70,21 +116,23 @@ pub fn grant_kill_xp(
 pub fn roll_kill_loot(
     mut events: MessageReader<EntityDied>,
     mut loot_writer: MessageWriter<LootDropped>,
-    mut player: Query<(&StatSheet, &mut Inventory), With<PlayerMarker>>,
-    mobs: Query<(&MobLootTable, &DeathProcessed)>,
+    mut found_writer: MessageWriter<ItemFound>,
+    player: Query<(&StatSheet, &Inventory), With<PlayerMarker>>,
+    mobs: Query<(&MobLootTable, &DeathProcessed, Has<EliteModifier>)>,
     registry: Res<ItemRegistry>,
+    dungeon: Res<DungeonState>,
 ) {
-    let Ok((stats, mut inventory)) = player.single_mut() else {
+    let Ok((stats, inventory)) = player.single() else {
         return;
     };
-    let magic_find = player_effective_magicfind(stats, &inventory);
+    let magic_find = player_effective_magicfind(stats, inventory) + dungeon.loot_magic_find();
 
     for event in events.read() {
         if event.is_player {
             continue;
         }
 
-        let Ok((loot_table, death_processed)) = mobs.get(event.entity) else {
+        let Ok((loot_table, death_processed, elite)) = mobs.get(event.entity) else {
             continue;
         };
 
@@ -92,13 +140,30 @@ pub fn roll_kill_loot(
             continue;
         }
 
-        let drops = loot_table.0.roll_drops(magic_find, &registry);
+        // Elites roll their table twice, with better odds each time.
+        let mut drops = Vec::new();
+        if elite {
+            let elite_find = magic_find + ELITE_MAGIC_FIND;
+            drops.extend(loot_table.0.roll_drops(elite_find, &registry));
+            drops.extend(loot_table.0.roll_drops(elite_find, &registry));
+        } else {
+            drops.extend(loot_table.0.roll_drops(magic_find, &registry));
+        }
+        if let Some(scroll) = roll_word_scroll(dungeon.depth(), &mut rand::thread_rng())
+            .and_then(|word| word.scroll())
+        {
+            drops.push(LootDrop {
+                item: registry.spawn(scroll),
+                quantity: 1,
+            });
+        }
         for drop in &drops {
             loot_writer.write(LootDropped {
-                item_name: drop.item.name.clone(),
+                item: drop.item.clone(),
+                quantity: drop.quantity,
             });
         }
-        collect_loot_drops(&mut *inventory, &drops);
+        found_writer.write_batch(ItemFound::from_loot(&drops));
     }
 }
 
@@ -119,12 +184,72 @@ pub fn mark_death_processed(
     }
 }
 
-pub fn mark_mob_dying(mut commands: Commands, mut events: MessageReader<EntityDied>) {
+/// What a nest's bounty pays out and announces.
+#[derive(SystemParam)]
+pub struct BountyWriters<'w> {
+    gold_writer: MessageWriter<'w, GoldGained>,
+    xp_writer: MessageWriter<'w, XpGained>,
+    toast_writer: MessageWriter<'w, ShowToast>,
+}
+
+/// Pays out for a destroyed nest and clears it away; nests have no death animation.
+pub fn grant_nest_bounty(
+    mut commands: Commands,
+    mut events: MessageReader<EntityDied>,
+    writers: BountyWriters,
+    nests: Query<(&NestEntity, &GoldReward, &XpReward)>,
+    player: Query<(&StatSheet, &Inventory), With<PlayerMarker>>,
+    loc: Res<Localization>,
+) {
+    let BountyWriters {
+        mut gold_writer,
+        mut xp_writer,
+        mut toast_writer,
+    } = writers;
+    let Ok((stats, inventory)) = player.single() else {
+        return;
+    };
+    let goldfind = player_effective_goldfind(stats, inventory);
+
     for event in events.read() {
         if event.is_player {
             continue;
         }
 
+        let Ok((nest, gold, xp)) = nests.get(event.entity) else {
+            continue;
+        };
+
+        let spec = nest.mob_id.spec();
+        let name = loc.format(
+            "nest.name",
+            &[("mob", &loc.mob_name(nest.mob_id, &spec.name))],
+        );
+        gold_writer.write(GoldGained {
+            amount: apply_goldfind(gold.0, goldfind),
+            source: name.clone(),
+        });
+        xp_writer.write(XpGained {
+            amount: xp.0,
+            source: name.clone(),
+        });
+        toast_writer.write(ShowToast::new(
+            loc.format("nest.destroyed", &[("nest", &name)]),
+        ));
+        commands.entity(event.entity).despawn();
+    }
+}
+
+pub fn mark_mob_dying(
+    mut commands: Commands,
+    mut events: MessageReader<EntityDied>,
+    mobs: Query<(), With<MobMarker>>,
+) {
+    for event in events.read() {
+        if event.is_player || !mobs.contains(event.entity) {
+            continue;
+        }
+
         commands.entity(event.entity).insert(DyingMob);
     }
 }
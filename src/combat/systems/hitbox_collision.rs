@@ -1,23 +1,46 @@
 use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use randr_core::combat::Attack;
 
 use crate::combat::hitbox::{AttackHitbox, HitEntities};
-use crate::combat::events::DamageEntity;
-use crate::combat::system::{apply_defense, player_attack_value};
+use crate::combat::events::{DamageEntity, HitLanded};
+use crate::combat::projectile::Projectile;
+use crate::combat::system::{
+    player_attack_sources, player_crit_profile, roll_hit, AttackSources, CritProfile,
+};
+use crate::dungeon::{NestEntity, TrainingDummyEntity};
+use crate::game::ActiveBuffs;
 use crate::inventory::Inventory;
 use crate::mob::components::{CombatStats, MobMarker};
 use crate::player::PlayerMarker;
 use crate::skills::{SkillType, Skills};
-use crate::stats::StatSheet;
+use crate::stats::{StatSheet, StatType};
+
+/// What the player's swings can hit: mobs, the nests that hatch them, and training dummies.
+type Hittable = Or<(With<MobMarker>, With<NestEntity>, With<TrainingDummyEntity>)>;
+
+/// What the player's attack is built from: their stats and gear, Combat level, and buffs.
+#[derive(SystemParam)]
+pub struct PlayerAttacker<'w, 's> {
+    player: Query<'w, 's, (&'static StatSheet, &'static Inventory), With<PlayerMarker>>,
+    skills: Res<'w, Skills>,
+    buffs: Res<'w, ActiveBuffs>,
+}
 
 pub fn handle_hitbox_collisions(
     mut collisions: MessageReader<CollisionStart>,
     mut damage_writer: MessageWriter<DamageEntity>,
+    mut hit_writer: MessageWriter<HitLanded>,
     mut hitboxes: Query<(&AttackHitbox, &mut HitEntities)>,
-    mobs: Query<&CombatStats, With<MobMarker>>,
-    player: Query<(&StatSheet, &Inventory), With<PlayerMarker>>,
-    skills: Res<Skills>,
+    mobs: Query<&CombatStats, Hittable>,
+    attacker: PlayerAttacker,
 ) {
+    let PlayerAttacker {
+        player,
+        skills,
+        buffs,
+    } = attacker;
     let Ok((stats, inventory)) = player.single() else {
         return;
     };
@@ -25,6 +48,12 @@ pub fn handle_hitbox_collisions(
         .skill(SkillType::Combat)
         .map(|s| s.level)
         .unwrap_or(1);
+    let sources = player_attack_sources(
+        stats,
+        inventory,
+        combat_level,
+        buffs.stat_bonus(StatType::Attack),
+    );
 
     for event in collisions.read() {
         let Some((hitbox_entity, target)) = extract_hitbox_and_mob(event, &hitboxes, &mobs) else {
@@ -43,21 +72,122 @@ pub fn handle_hitbox_collisions(
             continue;
         };
 
-        let attack = player_attack_value(stats, inventory, combat_level);
-        let raw_damage = attack.roll_damage();
-        let damage = apply_defense(raw_damage, mob_combat_stats.defense);
-
-        damage_writer.write(DamageEntity {
+        strike(
             target,
-            amount: damage,
-        });
+            sources,
+            &sources.attack(),
+            player_crit_profile(stats, inventory),
+            mob_combat_stats,
+            &mut damage_writer,
+            &mut hit_writer,
+        );
     }
 }
 
+/// Arrows and thrown flasks hurt the first mob they reach. Whatever a player's shot runs
+/// into, it is used up.
+pub fn handle_projectile_hits(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    mut damage_writer: MessageWriter<DamageEntity>,
+    mut hit_writer: MessageWriter<HitLanded>,
+    projectiles: Query<&Projectile>,
+    mobs: Query<&CombatStats, Hittable>,
+    attacker: PlayerAttacker,
+) {
+    let PlayerAttacker {
+        player,
+        skills,
+        buffs,
+    } = attacker;
+    let Ok((stats, inventory)) = player.single() else {
+        return;
+    };
+    let combat_level = skills
+        .skill(SkillType::Combat)
+        .map(|s| s.level)
+        .unwrap_or(1);
+    let sources = player_attack_sources(
+        stats,
+        inventory,
+        combat_level,
+        buffs.stat_bonus(StatType::Attack),
+    );
+
+    let mut spent = Vec::new();
+    for event in collisions.read() {
+        let (shot, other) = if projectiles.contains(event.collider1) {
+            (event.collider1, event.collider2)
+        } else if projectiles.contains(event.collider2) {
+            (event.collider2, event.collider1)
+        } else {
+            continue;
+        };
+        let Ok(projectile) = projectiles.get(shot) else {
+            continue;
+        };
+        if !projectile.is_from_player() || spent.contains(&shot) {
+            continue;
+        }
+        spent.push(shot);
+        commands.entity(shot).try_despawn();
+
+        let Ok(mob_combat_stats) = mobs.get(other) else {
+            continue;
+        };
+        // Flasks burst for their own damage, so the player's crit stats don't apply.
+        let (shot_sources, attack, crit) = match projectile {
+            Projectile::Flask(attack) => (
+                AttackSources::flat((attack.min_damage + attack.max_damage) / 2),
+                attack.clone(),
+                CritProfile::default(),
+            ),
+            _ => (sources, sources.attack(), player_crit_profile(stats, inventory)),
+        };
+        strike(
+            other,
+            shot_sources,
+            &attack,
+            crit,
+            mob_combat_stats,
+            &mut damage_writer,
+            &mut hit_writer,
+        );
+    }
+}
+
+fn strike(
+    target: Entity,
+    sources: AttackSources,
+    attack: &Attack,
+    crit: CritProfile,
+    target_stats: &CombatStats,
+    damage_writer: &mut MessageWriter<DamageEntity>,
+    hit_writer: &mut MessageWriter<HitLanded>,
+) {
+    let breakdown = roll_hit(
+        sources,
+        attack,
+        crit,
+        target_stats.defense,
+        &mut rand::thread_rng(),
+    );
+
+    damage_writer.write(DamageEntity {
+        target,
+        amount: breakdown.damage,
+    });
+    hit_writer.write(HitLanded {
+        target,
+        damage: breakdown.damage,
+        breakdown,
+    });
+}
+
 fn extract_hitbox_and_mob<'a>(
     event: &CollisionStart,
     hitboxes: &Query<(&AttackHitbox, &mut HitEntities)>,
-    mobs: &Query<&CombatStats, With<MobMarker>>,
+    mobs: &Query<&CombatStats, Hittable>,
 ) -> Option<(Entity, Entity)> {
     if hitboxes.contains(event.collider1) && mobs.contains(event.collider2) {
         Some((event.collider1, event.collider2))
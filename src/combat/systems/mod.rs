@@ -1,3 +1,5 @@
+pub mod combat_log;
+pub mod contact;
 pub mod damage;
 pub mod death_rewards;
 pub mod hitbox_cleanup;
@@ -1,35 +1,62 @@
 use bevy::prelude::*;
 
-use crate::combat::events::{GoldGained, XpGained};
+use crate::combat::events::{GoldGained, LootDropped, XpGained};
+use crate::economy::{Currency, Wallet, WorthGold};
 use crate::entities::Progression;
-use crate::player::{PlayerGold, PlayerMarker};
+use crate::guild::GuildHall;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::loot::{LootFilter, LootRule};
+use crate::player::PlayerMarker;
 use crate::skills::{SkillType, SkillXpGained};
 
 pub fn apply_gold_gain(
     mut events: MessageReader<GoldGained>,
-    mut player: Query<&mut PlayerGold, With<PlayerMarker>>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
 ) {
-    let Ok(mut gold) = player.single_mut() else {
+    let Ok(mut wallet) = player.single_mut() else {
         return;
     };
     for event in events.read() {
-        gold.add(event.amount);
+        wallet.add(Currency::Gold, event.amount);
+    }
+}
+
+pub fn apply_loot_drops(
+    mut events: MessageReader<LootDropped>,
+    filter: Res<LootFilter>,
+    mut player: Query<(&mut Inventory, &mut Wallet), With<PlayerMarker>>,
+) {
+    let Ok((mut inventory, mut wallet)) = player.single_mut() else {
+        return;
+    };
+    for event in events.read() {
+        match filter.rule_for(&event.item) {
+            LootRule::Pickup => {
+                for _ in 0..event.quantity {
+                    let _ = inventory.add_to_inv(event.item.clone());
+                }
+            }
+            LootRule::Sell => wallet.add(Currency::Gold, event.item.sell_price() * event.quantity),
+            LootRule::Ignore => {}
+        }
     }
 }
 
 pub fn apply_xp_gain(
     mut events: MessageReader<XpGained>,
     mut player: Query<&mut Progression, With<PlayerMarker>>,
+    guild: Res<GuildHall>,
     mut skill_writer: MessageWriter<SkillXpGained>,
 ) {
     let Ok(mut progression) = player.single_mut() else {
         return;
     };
     for event in events.read() {
-        progression.add_xp(event.amount);
+        let amount = guild.boost_xp(event.amount);
+        progression.add_xp(amount);
         skill_writer.write(SkillXpGained {
             skill: SkillType::Combat,
-            amount: event.amount as u64,
+            amount: amount as u64,
         });
     }
 }
@@ -1,57 +1,109 @@
 #[cfg(test)]
+use rand::{rngs::StdRng, SeedableRng};
+#[cfg(test)]
 use crate::{
-    combat::{apply_defense, calculate_damage_reduction},
-    player::PlayerGold,
+    combat::{
+        player_attack_sources, player_crit_profile, player_effective_dodge,
+        process_player_defeat, roll_hit, AttackSources, CritProfile, MAX_DODGE_PERCENT,
+    },
+    economy::{Currency, Wallet},
+    inventory::Inventory,
+    player::{default_player_stats, BankedGold},
+    stats::StatType,
 };
 
-// ==================== PlayerGold tests ====================
+// ==================== Wallet gold tests ====================
 
 #[test]
-fn player_gold_add_increases_balance() {
-    let mut gold = PlayerGold(0);
-    gold.add(100);
-    assert_eq!(gold.0, 100);
-    gold.add(50);
-    assert_eq!(gold.0, 150);
+fn wallet_gold_add_increases_balance() {
+    let mut gold = Wallet::with_gold(0);
+    gold.add(Currency::Gold, 100);
+    assert_eq!(gold.gold(), 100);
+    gold.add(Currency::Gold, 50);
+    assert_eq!(gold.gold(), 150);
 }
 
 #[test]
-fn player_gold_subtract_decreases_balance() {
-    let mut gold = PlayerGold(100);
-    gold.subtract(30);
-    assert_eq!(gold.0, 70);
+fn wallet_gold_subtract_decreases_balance() {
+    let mut gold = Wallet::with_gold(100);
+    gold.subtract(Currency::Gold, 30);
+    assert_eq!(gold.gold(), 70);
 }
 
 #[test]
-fn player_gold_subtract_floors_at_zero() {
-    let mut gold = PlayerGold(50);
-    gold.subtract(100);
-    assert_eq!(gold.0, 0);
+fn wallet_gold_subtract_floors_at_zero() {
+    let mut gold = Wallet::with_gold(50);
+    gold.subtract(Currency::Gold, 100);
+    assert_eq!(gold.gold(), 0);
 }
 
-// ==================== Defense calculation tests ====================
+#[test]
+fn player_defeat_takes_five_percent_of_carried_gold() {
+    let mut stats = default_player_stats();
+    let mut gold = Wallet::with_gold(200);
+    process_player_defeat(&mut stats, &mut gold);
+    assert_eq!(gold.gold(), 190);
+}
 
 #[test]
-fn defense_reduction_follows_diminishing_returns() {
-    let reduction_0 = calculate_damage_reduction(0);
-    let reduction_25 = calculate_damage_reduction(25);
-    let reduction_50 = calculate_damage_reduction(50);
-    let reduction_100 = calculate_damage_reduction(100);
+fn player_defeat_leaves_banked_gold_alone() {
+    let mut stats = default_player_stats();
+    let mut gold = Wallet::with_gold(300);
+    let mut banked = BankedGold::default();
+    banked.deposit(&mut gold, 100);
+    process_player_defeat(&mut stats, &mut gold);
+    assert_eq!(gold.gold(), 190);
+    assert_eq!(banked.0, 100);
+}
+
+// ==================== Critical hit and dodge tests ====================
 
-    assert!((reduction_0 - 0.0).abs() < 0.01);
-    assert!((reduction_25 - 0.333).abs() < 0.01);
-    assert!((reduction_50 - 0.5).abs() < 0.01);
-    assert!((reduction_100 - 0.667).abs() < 0.01);
+#[test]
+fn player_crit_profile_adds_crit_damage_to_the_base_multiplier() {
+    let mut stats = default_player_stats();
+    stats.insert(StatType::CritDamage.instance(25));
+    let profile = player_crit_profile(&stats, &Inventory::new());
+    assert_eq!(profile.chance, 5);
+    assert_eq!(profile.multiplier_percent, 175);
 }
 
 #[test]
-fn apply_defense_reduces_damage_correctly() {
-    assert_eq!(apply_defense(100, 50), 50);
-    assert_eq!(apply_defense(10, 50), 5);
+fn dodge_is_capped() {
+    let mut stats = default_player_stats();
+    stats.insert(StatType::Dodge.instance(90));
+    assert_eq!(player_effective_dodge(&stats, &Inventory::new()), MAX_DODGE_PERCENT);
+}
 
-    assert_eq!(apply_defense(100, 0), 100);
+// ==================== Damage breakdown tests ====================
 
-    let reduced = apply_defense(100, 1000);
-    assert!(reduced > 0);
-    assert!(reduced < 10);
+#[test]
+fn attack_sources_split_buffs_out_of_the_base() {
+    let mut stats = default_player_stats();
+    let base = stats.value(StatType::Attack);
+    stats.increase_stat(StatType::Attack, 4);
+    let sources = player_attack_sources(&stats, &Inventory::new(), 1, 4);
+    assert_eq!(sources.base, base);
+    assert_eq!(sources.buffs, 4);
+    assert_eq!(sources.total(), stats.value(StatType::Attack) + sources.skill);
+}
+
+#[test]
+fn roll_hit_records_each_step_of_a_critical() {
+    let sources = AttackSources::flat(20);
+    let always = CritProfile {
+        chance: 100,
+        multiplier_percent: 200,
+    };
+    let hit = roll_hit(
+        sources,
+        &sources.attack(),
+        always,
+        50,
+        &mut StdRng::seed_from_u64(3),
+    );
+    assert!(hit.critical);
+    assert_eq!(hit.crit_multiplier_percent, 200);
+    assert_eq!(hit.dealt, hit.roll * 2);
+    assert_eq!(hit.damage, (hit.dealt as f64 / 2.0).round() as i32);
+    assert!((hit.min_damage..=hit.max_damage).contains(&hit.roll));
 }
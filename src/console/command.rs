@@ -0,0 +1,348 @@
+use crate::economy::Currency;
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// Where the `floor` command sends the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloorTarget {
+    Next,
+    Dungeon,
+    Home,
+}
+
+/// What the `replay` command does with the session recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayCommand {
+    Save(String),
+    Play(String),
+    Stop,
+    Restart,
+}
+
+/// A parsed console command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsoleCommand {
+    Help,
+    Clear,
+    GiveItem { item: ItemId, quantity: u32 },
+    SetCurrency { currency: Currency, amount: i32 },
+    Floor(FloorTarget),
+    KillAll(Option<MobId>),
+    Replay(ReplayCommand),
+    /// View a shared profile, from a code or a `.json` file path.
+    ImportProfile(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConsoleError {
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+    #[error("Usage: {0}")]
+    Usage(&'static str),
+    #[error("Unknown item: {0}")]
+    UnknownItem(String),
+    #[error("Unknown mob: {0}")]
+    UnknownMob(String),
+    #[error("Unknown currency: {0}")]
+    UnknownCurrency(String),
+    #[error("Amount must not be negative: {0}")]
+    NegativeAmount(i32),
+    #[error("{0} is only available in debug builds")]
+    DebugOnly(String),
+}
+
+const COMMANDS: &[&str] = &[
+    "help", "clear", "give", "gold", "currency", "floor", "killall", "replay", "profile",
+];
+const DEBUG_COMMANDS: &[&str] = &["give", "gold", "currency", "floor", "killall"];
+const FLOOR_TARGETS: &[&str] = &["next", "dungeon", "home"];
+const REPLAY_ACTIONS: &[&str] = &["save", "play", "stop", "restart"];
+
+impl ConsoleCommand {
+    pub fn parse(input: &str) -> Result<Self, ConsoleError> {
+        let mut parts = input.split_whitespace();
+        let Some(name) = parts.next() else {
+            return Err(ConsoleError::Usage("help"));
+        };
+        let name = name.to_lowercase();
+        let args: Vec<&str> = parts.collect();
+
+        if DEBUG_COMMANDS.contains(&name.as_str()) && !cfg!(debug_assertions) {
+            return Err(ConsoleError::DebugOnly(name));
+        }
+
+        match name.as_str() {
+            "help" => Ok(Self::Help),
+            "clear" => Ok(Self::Clear),
+            "give" => {
+                let usage = ConsoleError::Usage("give <item> [quantity]");
+                let item_name = args.first().ok_or(usage.clone())?;
+                let item = find_item(item_name)
+                    .ok_or_else(|| ConsoleError::UnknownItem(item_name.to_string()))?;
+                let quantity = match args.get(1) {
+                    Some(q) => q.parse().map_err(|_| usage)?,
+                    None => 1,
+                };
+                Ok(Self::GiveItem { item, quantity })
+            }
+            "gold" => {
+                let usage = ConsoleError::Usage("gold <amount>");
+                let amount = args.first().ok_or(usage.clone())?;
+                let amount = parse_amount(amount, usage)?;
+                Ok(Self::SetCurrency {
+                    currency: Currency::Gold,
+                    amount,
+                })
+            }
+            "currency" => {
+                let usage = ConsoleError::Usage("currency <name> <amount>");
+                let (Some(name), Some(amount)) = (args.first(), args.get(1)) else {
+                    return Err(usage);
+                };
+                let currency = find_currency(name)
+                    .ok_or_else(|| ConsoleError::UnknownCurrency(name.to_string()))?;
+                let amount = parse_amount(amount, usage)?;
+                Ok(Self::SetCurrency { currency, amount })
+            }
+            "floor" => match args.first().map(|a| a.to_lowercase()).as_deref() {
+                Some("next") => Ok(Self::Floor(FloorTarget::Next)),
+                Some("dungeon") => Ok(Self::Floor(FloorTarget::Dungeon)),
+                Some("home") => Ok(Self::Floor(FloorTarget::Home)),
+                _ => Err(ConsoleError::Usage("floor <next|dungeon|home>")),
+            },
+            "killall" => match args.first() {
+                Some(mob_name) => find_mob(mob_name)
+                    .map(|mob| Self::KillAll(Some(mob)))
+                    .ok_or_else(|| ConsoleError::UnknownMob(mob_name.to_string())),
+                None => Ok(Self::KillAll(None)),
+            },
+            "replay" => {
+                let action = args.first().map(|a| a.to_lowercase());
+                let command = match (action.as_deref(), args.get(1)) {
+                    (Some("save"), Some(name)) => ReplayCommand::Save(name.to_string()),
+                    (Some("play"), Some(name)) => ReplayCommand::Play(name.to_string()),
+                    (Some("stop"), None) => ReplayCommand::Stop,
+                    (Some("restart"), None) => ReplayCommand::Restart,
+                    _ => {
+                        return Err(ConsoleError::Usage(
+                            "replay <save|play> <name> | replay <stop|restart>",
+                        ))
+                    }
+                };
+                Ok(Self::Replay(command))
+            }
+            "profile" => match args.as_slice() {
+                [source] => Ok(Self::ImportProfile(source.to_string())),
+                _ => Err(ConsoleError::Usage("profile <code|file.json>")),
+            },
+            _ => Err(ConsoleError::UnknownCommand(name)),
+        }
+    }
+
+    pub fn help_lines() -> &'static [&'static str] {
+        &[
+            "help - list commands",
+            "clear - clear the scrollback",
+            "give <item> [quantity] - add items to the inventory",
+            "gold <amount> - set carried gold",
+            "currency <name> <amount> - set a wallet balance",
+            "floor <next|dungeon|home> - move to another floor",
+            "killall [mob] - kill every mob on the floor",
+            "replay <save|play> <name> - save the session recording or play one back",
+            "replay <stop|restart> - stop playback or start a fresh recording",
+            "profile <code|file.json> - view a shared character profile",
+        ]
+    }
+}
+
+fn find_item(name: &str) -> Option<ItemId> {
+    ItemId::ALL
+        .iter()
+        .copied()
+        .find(|id| format!("{id:?}").eq_ignore_ascii_case(name))
+}
+
+fn find_currency(name: &str) -> Option<Currency> {
+    Currency::ALL
+        .iter()
+        .copied()
+        .find(|currency| format!("{currency:?}").eq_ignore_ascii_case(name))
+}
+
+/// Parses a wallet balance, which can't be set below zero.
+fn parse_amount(amount: &str, usage: ConsoleError) -> Result<i32, ConsoleError> {
+    match amount.parse() {
+        Ok(amount) if amount < 0 => Err(ConsoleError::NegativeAmount(amount)),
+        Ok(amount) => Ok(amount),
+        Err(_) => Err(usage),
+    }
+}
+
+fn find_mob(name: &str) -> Option<MobId> {
+    MobId::ALL
+        .iter()
+        .copied()
+        .find(|id| format!("{id:?}").eq_ignore_ascii_case(name))
+}
+
+/// Completes the last word of `input` against command names, or against item and mob IDs
+/// when completing an argument. Returns `None` when there is no unique-prefix completion.
+pub fn complete(input: &str) -> Option<String> {
+    let (head, last) = match input.rfind(' ') {
+        Some(idx) => (&input[..=idx], &input[idx + 1..]),
+        None => ("", input),
+    };
+
+    let candidates: Vec<String> = if head.is_empty() {
+        COMMANDS.iter().map(|c| c.to_string()).collect()
+    } else {
+        match head.split_whitespace().next().map(str::to_lowercase).as_deref() {
+            Some("give") => ItemId::ALL.iter().map(|id| format!("{id:?}")).collect(),
+            Some("killall") => MobId::ALL.iter().map(|id| format!("{id:?}")).collect(),
+            Some("currency") => Currency::ALL.iter().map(|c| format!("{c:?}")).collect(),
+            Some("floor") => FLOOR_TARGETS.iter().map(|t| t.to_string()).collect(),
+            Some("replay") => REPLAY_ACTIONS.iter().map(|a| a.to_string()).collect(),
+            _ => return None,
+        }
+    };
+
+    let lower = last.to_lowercase();
+    let matches: Vec<&String> = candidates
+        .iter()
+        .filter(|c| c.to_lowercase().starts_with(&lower))
+        .collect();
+
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [only] => only.to_string(),
+        many => common_prefix(many),
+    };
+
+    if completed.len() <= last.len() {
+        return None;
+    }
+    Some(format!("{head}{completed}"))
+}
+
+fn common_prefix(words: &[&String]) -> String {
+    let first = words[0];
+    let len = words.iter().fold(first.len(), |len, word| {
+        first
+            .chars()
+            .zip(word.chars())
+            .take(len)
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count()
+    });
+    first.chars().take(len).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_give_with_quantity() {
+        assert_eq!(
+            ConsoleCommand::parse("give coal 5"),
+            Ok(ConsoleCommand::GiveItem {
+                item: ItemId::Coal,
+                quantity: 5
+            })
+        );
+    }
+
+    #[test]
+    fn give_defaults_to_one() {
+        assert_eq!(
+            ConsoleCommand::parse("give IronOre"),
+            Ok(ConsoleCommand::GiveItem {
+                item: ItemId::IronOre,
+                quantity: 1
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_item() {
+        assert_eq!(
+            ConsoleCommand::parse("give banana"),
+            Err(ConsoleError::UnknownItem("banana".to_string()))
+        );
+    }
+
+    #[test]
+    fn currency_commands_validate_name_and_amount() {
+        assert_eq!(
+            ConsoleCommand::parse("currency bosssigil 3"),
+            Ok(ConsoleCommand::SetCurrency {
+                currency: Currency::BossSigil,
+                amount: 3
+            })
+        );
+        assert_eq!(
+            ConsoleCommand::parse("currency doubloon 3"),
+            Err(ConsoleError::UnknownCurrency("doubloon".to_string()))
+        );
+        assert_eq!(ConsoleCommand::parse("gold -5"), Err(ConsoleError::NegativeAmount(-5)));
+        assert!(matches!(ConsoleCommand::parse("gold lots"), Err(ConsoleError::Usage(_))));
+    }
+
+    #[test]
+    fn parses_killall_filter() {
+        assert_eq!(
+            ConsoleCommand::parse("killall slime"),
+            Ok(ConsoleCommand::KillAll(Some(MobId::Slime)))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_command() {
+        assert_eq!(
+            ConsoleCommand::parse("fly"),
+            Err(ConsoleError::UnknownCommand("fly".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_replay_commands() {
+        assert_eq!(
+            ConsoleCommand::parse("replay save crash"),
+            Ok(ConsoleCommand::Replay(ReplayCommand::Save("crash".to_string())))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("replay STOP"),
+            Ok(ConsoleCommand::Replay(ReplayCommand::Stop))
+        );
+        assert!(matches!(ConsoleCommand::parse("replay play"), Err(ConsoleError::Usage(_))));
+    }
+
+    #[test]
+    fn profile_keeps_the_code_as_typed() {
+        assert_eq!(
+            ConsoleCommand::parse("profile eyJ2ZXJz-_"),
+            Ok(ConsoleCommand::ImportProfile("eyJ2ZXJz-_".to_string()))
+        );
+        assert!(matches!(ConsoleCommand::parse("profile"), Err(ConsoleError::Usage(_))));
+    }
+
+    #[test]
+    fn completes_command_names() {
+        assert_eq!(complete("ki"), Some("killall".to_string()));
+    }
+
+    #[test]
+    fn completes_item_ids() {
+        assert_eq!(complete("give basich"), Some("give BasicHPPotion".to_string()));
+    }
+
+    #[test]
+    fn completes_to_common_prefix() {
+        assert_eq!(complete("killall dw"), Some("killall Dwarf".to_string()));
+    }
+
+    #[test]
+    fn no_completion_for_unknown_prefix() {
+        assert_eq!(complete("give zzz"), None);
+    }
+}
@@ -0,0 +1,5 @@
+mod command;
+mod plugin;
+
+pub use command::{complete, ConsoleCommand, ConsoleError, FloorTarget, ReplayCommand};
+pub use plugin::{console_closed, ConsolePlugin, ConsoleState, ConsoleSubmit};
@@ -0,0 +1,376 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::combat::DamageEntity;
+use crate::dungeon::{FloorSeedQueue, FloorTransition};
+use crate::economy::Wallet;
+use crate::game::{ImportedProfile, SharedProfile};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::mob::components::{Health, MobMarker};
+use crate::player::PlayerMarker;
+use crate::replay::{replay_path, ReplayLog, ReplayState};
+
+use super::command::{complete, ConsoleCommand, FloorTarget, ReplayCommand};
+
+const MAX_SCROLLBACK: usize = 200;
+const VISIBLE_LINES: usize = 12;
+const CONSOLE_Z_INDEX: i32 = 500;
+
+/// Open/closed state, input line, history, and scrollback of the dev console.
+#[derive(Resource, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    pub input: String,
+    pub history: Vec<String>,
+    history_cursor: Option<usize>,
+    pub scrollback: Vec<String>,
+}
+
+impl ConsoleState {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.scrollback.push(line.into());
+        if self.scrollback.len() > MAX_SCROLLBACK {
+            let excess = self.scrollback.len() - MAX_SCROLLBACK;
+            self.scrollback.drain(..excess);
+        }
+    }
+
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let idx = match self.history_cursor {
+            Some(0) => 0,
+            Some(i) => i - 1,
+            None => self.history.len() - 1,
+        };
+        self.history_cursor = Some(idx);
+        self.input = self.history[idx].clone();
+    }
+
+    fn history_next(&mut self) {
+        let Some(idx) = self.history_cursor else {
+            return;
+        };
+        if idx + 1 < self.history.len() {
+            self.history_cursor = Some(idx + 1);
+            self.input = self.history[idx + 1].clone();
+        } else {
+            self.history_cursor = None;
+            self.input.clear();
+        }
+    }
+}
+
+/// Run condition: true while the console is not capturing the keyboard.
+pub fn console_closed(console: Option<Res<ConsoleState>>) -> bool {
+    console.is_none_or(|c| !c.open)
+}
+
+/// Submitted console line, executed by [`execute_console_commands`].
+#[derive(Message, Debug, Clone)]
+pub struct ConsoleSubmit(pub String);
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleState>()
+            .init_resource::<ImportedProfile>()
+            .add_message::<ConsoleSubmit>()
+            .add_systems(Startup, spawn_console)
+            .add_systems(
+                Update,
+                (
+                    handle_console_keys,
+                    execute_console_commands.run_if(on_message::<ConsoleSubmit>),
+                    render_console.run_if(resource_changed::<ConsoleState>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleScrollbackText;
+
+#[derive(Component)]
+struct ConsoleInputText;
+
+fn spawn_console(mut commands: Commands) {
+    commands
+        .spawn((
+            ConsoleRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            ZIndex(CONSOLE_Z_INDEX),
+            Visibility::Hidden,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ConsoleScrollbackText,
+                Text::new(""),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+            parent.spawn((
+                ConsoleInputText,
+                Text::new("> "),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.5, 1.0, 0.5)),
+            ));
+        });
+}
+
+fn handle_console_keys(
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    mut console: ResMut<ConsoleState>,
+    mut submit_writer: MessageWriter<ConsoleSubmit>,
+) {
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        if event.key_code == KeyCode::Backquote {
+            console.open = !console.open;
+            continue;
+        }
+
+        if !console.open {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                console.history.push(line.clone());
+                console.history_cursor = None;
+                submit_writer.write(ConsoleSubmit(line));
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => {
+                console.open = false;
+            }
+            Key::Tab => {
+                if let Some(completed) = complete(&console.input) {
+                    console.input = completed;
+                }
+            }
+            Key::ArrowUp => console.history_prev(),
+            Key::ArrowDown => console.history_next(),
+            Key::Space => console.input.push(' '),
+            Key::Character(chars) => console.input.push_str(chars),
+            _ => {}
+        }
+    }
+}
+
+/// What the cheat commands reach into: the player's items and purse, the mobs on the
+/// floor, and the floor itself.
+#[derive(SystemParam)]
+struct CheatTargets<'w, 's> {
+    player: Query<'w, 's, (&'static mut Inventory, &'static mut Wallet), With<PlayerMarker>>,
+    mobs: Query<'w, 's, (Entity, &'static MobMarker, &'static Health)>,
+    registry: Option<Res<'w, ItemRegistry>>,
+    floor_writer: MessageWriter<'w, FloorTransition>,
+    damage_writer: MessageWriter<'w, DamageEntity>,
+}
+
+/// The replay recorder/player and the floor seeds it queues up for playback.
+#[derive(SystemParam)]
+struct ReplayControls<'w> {
+    time: Res<'w, Time>,
+    replay: ResMut<'w, ReplayState>,
+    seed_queue: ResMut<'w, FloorSeedQueue>,
+}
+
+fn execute_console_commands(
+    mut submits: MessageReader<ConsoleSubmit>,
+    mut console: ResMut<ConsoleState>,
+    cheats: CheatTargets,
+    replay: ReplayControls,
+    mut imported: ResMut<ImportedProfile>,
+    loc: Res<Localization>,
+) {
+    let CheatTargets {
+        mut player,
+        mobs,
+        registry,
+        mut floor_writer,
+        mut damage_writer,
+    } = cheats;
+    let ReplayControls {
+        time,
+        mut replay,
+        mut seed_queue,
+    } = replay;
+
+    for ConsoleSubmit(line) in submits.read() {
+        console.print(format!("> {line}"));
+
+        let command = match ConsoleCommand::parse(line) {
+            Ok(command) => command,
+            Err(e) => {
+                console.print(e.to_string());
+                continue;
+            }
+        };
+
+        match command {
+            ConsoleCommand::Help => {
+                for help in ConsoleCommand::help_lines() {
+                    console.print(*help);
+                }
+            }
+            ConsoleCommand::Clear => console.scrollback.clear(),
+            ConsoleCommand::GiveItem { item, quantity } => {
+                let (Ok((mut inventory, _)), Some(registry)) = (player.single_mut(), &registry)
+                else {
+                    console.print(loc.get("console.no_player_items"));
+                    continue;
+                };
+                let added = (0..quantity)
+                    .take_while(|_| inventory.add_to_inv(registry.spawn(item)).is_ok())
+                    .count();
+                console.print(loc.format(
+                    "console.gave_items",
+                    &[("count", &added), ("item", &format!("{item:?}"))],
+                ));
+            }
+            ConsoleCommand::SetCurrency { currency, amount } => {
+                let Ok((_, mut wallet)) = player.single_mut() else {
+                    console.print(loc.get("console.no_player_currency"));
+                    continue;
+                };
+                match wallet.set(currency, amount) {
+                    Ok(()) => console.print(loc.format(
+                        "console.currency_set",
+                        &[("currency", &format!("{currency:?}")), ("amount", &amount)],
+                    )),
+                    Err(e) => console.print(e.to_string()),
+                }
+            }
+            ConsoleCommand::Floor(target) => {
+                floor_writer.write(match target {
+                    FloorTarget::Next => FloorTransition::AdvanceFloor,
+                    FloorTarget::Dungeon => FloorTransition::EnterDoor,
+                    FloorTarget::Home => FloorTransition::ReturnToHome,
+                });
+                console.print(
+                    loc.format("console.moving_floor", &[("floor", &format!("{target:?}"))]),
+                );
+            }
+            ConsoleCommand::KillAll(filter) => {
+                let mut killed = 0;
+                for (entity, marker, health) in &mobs {
+                    if filter.is_some_and(|mob_id| mob_id != marker.0) || !health.is_alive() {
+                        continue;
+                    }
+                    damage_writer.write(DamageEntity {
+                        target: entity,
+                        amount: health.current,
+                    });
+                    killed += 1;
+                }
+                console.print(loc.format("console.killed", &[("count", &killed)]));
+            }
+            ConsoleCommand::Replay(ReplayCommand::Save(name)) => match replay.save(&name) {
+                Ok(path) => console.print(loc.format(
+                    "console.replay_saved",
+                    &[
+                        ("count", &replay.recording().entries.len()),
+                        ("path", &path.display()),
+                    ],
+                )),
+                Err(e) => console.print(e.to_string()),
+            },
+            ConsoleCommand::Replay(ReplayCommand::Play(name)) => {
+                match ReplayLog::load(&replay_path(&name)) {
+                    Ok(log) => {
+                        console.print(loc.format(
+                            "console.replay_playing",
+                            &[
+                                ("name", &name),
+                                ("count", &log.entries.len()),
+                                ("seconds", &format!("{:.1}", log.duration())),
+                            ],
+                        ));
+                        replay.play(log, time.elapsed_secs(), &mut seed_queue);
+                        console.open = false;
+                    }
+                    Err(e) => console.print(e.to_string()),
+                }
+            }
+            ConsoleCommand::Replay(ReplayCommand::Stop) => {
+                if replay.stop(&mut seed_queue) {
+                    console.print(loc.get("console.replay_stopped"));
+                } else {
+                    console.print(loc.get("console.replay_not_playing"));
+                }
+            }
+            ConsoleCommand::Replay(ReplayCommand::Restart) => {
+                replay.restart_recording(time.elapsed_secs());
+                console.print(loc.get("console.recording_started"));
+            }
+            ConsoleCommand::ImportProfile(source) => match SharedProfile::import(&source) {
+                Ok(profile) => {
+                    console
+                        .print(loc.format("console.profile_imported", &[("name", &profile.name)]));
+                    imported.0 = Some(profile);
+                }
+                Err(e) => console.print(e.to_string()),
+            },
+        }
+    }
+}
+
+fn render_console(
+    console: Res<ConsoleState>,
+    mut root: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut scrollback: Query<&mut Text, (With<ConsoleScrollbackText>, Without<ConsoleInputText>)>,
+    mut input: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleScrollbackText>)>,
+) {
+    if let Ok(mut visibility) = root.single_mut() {
+        *visibility = if console.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    if let Ok(mut text) = scrollback.single_mut() {
+        let start = console.scrollback.len().saturating_sub(VISIBLE_LINES);
+        **text = console.scrollback[start..].join("\n");
+    }
+
+    if let Ok(mut text) = input.single_mut() {
+        **text = format!("> {}", console.input);
+    }
+}
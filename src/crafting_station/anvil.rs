@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::item::recipe::RecipeId;
 use crate::skills::{blacksmith_speed_multiplier, SkillType, Skills};
@@ -6,7 +7,7 @@ use crate::skills::{blacksmith_speed_multiplier, SkillType, Skills};
 use super::events::{AnvilCraftingStarted, TryStartAnvilCrafting};
 use super::AnvilActiveTimer;
 
-#[derive(Component, Default, Clone)]
+#[derive(Component, Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AnvilCraftingState {
     pub selected_recipe: Option<RecipeId>,
 }
@@ -0,0 +1,214 @@
+//! The forge apprentice: a late-game hire who smelts from storage while the player is away.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::economy::{Currency, Wallet};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::item::recipe::RecipeId;
+use crate::item::{ItemId, ItemRegistry};
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+use crate::skills::{SkillType, Skills};
+use crate::storage::Storage;
+use crate::world_event::GameClock;
+
+/// Blacksmith level needed before an apprentice will sign on.
+pub const APPRENTICE_LEVEL: u32 = 30;
+
+/// Gold the apprentice is paid at the start of each day.
+pub const APPRENTICE_WAGE: i32 = 40;
+
+/// Seconds away from town the apprentice needs per bar.
+pub const SECONDS_PER_BAR: f32 = 20.0;
+
+/// Coal burned per bar to keep the forge hot.
+const COAL_PER_BAR: u32 = 1;
+
+/// Why the apprentice isn't producing anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprenticeStall {
+    /// Storage is out of coal or the recipe's ore.
+    MissingMaterials,
+    /// Storage has no room for the bars.
+    StorageFull,
+}
+
+/// The hired apprentice, if any, and how far along the current bar is.
+#[derive(Resource, Debug, Default)]
+pub struct Apprentice {
+    /// The smelting recipe being worked, or `None` when no one is hired.
+    pub recipe: Option<RecipeId>,
+    pub stall: Option<ApprenticeStall>,
+    progress: f32,
+    paid_through: u32,
+}
+
+impl Apprentice {
+    /// The setting after the current one: nobody, then each smelting recipe in turn.
+    pub fn next_recipe(&self) -> Option<RecipeId> {
+        let recipes = RecipeId::all_smelting_recipes();
+        match self.recipe.and_then(|r| recipes.iter().position(|&other| other == r)) {
+            None => recipes.first().copied(),
+            Some(index) => recipes.get(index + 1).copied(),
+        }
+    }
+
+    /// Sets the recipe, hiring the apprentice if needed. Wages start with the next day.
+    pub fn assign(&mut self, recipe: Option<RecipeId>, today: u32) {
+        if self.recipe.is_none() {
+            self.paid_through = today;
+            self.progress = 0.0;
+        }
+        self.recipe = recipe;
+        self.stall = None;
+    }
+
+    /// Days of wages owed as of `today`, marking them paid.
+    pub fn wages_due(&mut self, today: u32) -> u32 {
+        let days = today.saturating_sub(self.paid_through);
+        self.paid_through = self.paid_through.max(today);
+        days
+    }
+
+    /// Adds `secs` of work, returning how many bars are now ready to smelt.
+    pub fn work(&mut self, secs: f32) -> u32 {
+        self.progress += secs;
+        let bars = (self.progress / SECONDS_PER_BAR) as u32;
+        self.progress -= bars as f32 * SECONDS_PER_BAR;
+        bars
+    }
+}
+
+/// Everything one bar of `recipe` takes from storage, coal included.
+pub fn bar_materials(recipe: RecipeId) -> HashMap<ItemId, u32> {
+    let mut materials = recipe.spec().ingredients.clone();
+    *materials.entry(ItemId::Coal).or_insert(0) += COAL_PER_BAR;
+    materials
+}
+
+/// Pays the apprentice for each day that has begun. An apprentice who can't be paid quits.
+pub fn pay_apprentice(
+    clock: Res<GameClock>,
+    mut apprentice: ResMut<Apprentice>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    if apprentice.recipe.is_none() {
+        return;
+    }
+    let days = apprentice.wages_due(clock.day());
+    if days == 0 {
+        return;
+    }
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    let wages = days as i32 * APPRENTICE_WAGE;
+    let message = match wallet.spend(Currency::Gold, wages) {
+        Ok(()) => loc.format("apprentice.paid", &[("gold", &wages)]),
+        Err(e) => {
+            debug!("{e}");
+            apprentice.assign(None, clock.day());
+            loc.format("apprentice.quit", &[("gold", &wages)])
+        }
+    };
+    toast_writer.write(ShowToast::new(message));
+}
+
+/// Smelts bars from storage into storage while the player is out of town.
+pub fn run_apprentice(
+    time: Res<Time>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    mut apprentice: ResMut<Apprentice>,
+    mut storage: ResMut<Storage>,
+    registry: Res<ItemRegistry>,
+) {
+    let Some(recipe) = apprentice.recipe else {
+        return;
+    };
+    if dungeon.current_location == Some(town.0.spec().home) {
+        return;
+    }
+
+    let materials = bar_materials(recipe);
+    let output = recipe.spec().output;
+    for _ in 0..apprentice.work(time.delta_secs()) {
+        let stall = if !storage.consume(&materials) {
+            Some(ApprenticeStall::MissingMaterials)
+        } else if storage.deposit(registry.spawn(output)).is_err() {
+            // Put the materials back; nothing was made.
+            for (&item_id, &quantity) in &materials {
+                for _ in 0..quantity {
+                    let _ = storage.deposit(registry.spawn(item_id));
+                }
+            }
+            Some(ApprenticeStall::StorageFull)
+        } else {
+            None
+        };
+
+        apprentice.stall = stall;
+        if stall.is_some() {
+            break;
+        }
+    }
+}
+
+/// Whether the player's smithing is good enough to take on an apprentice.
+pub fn can_hire(skills: &Skills) -> bool {
+    skills
+        .skill(SkillType::Blacksmith)
+        .is_some_and(|skill| skill.level >= APPRENTICE_LEVEL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_cycle_through_every_smelting_recipe_and_back_to_nobody() {
+        let mut apprentice = Apprentice::default();
+        let mut seen = Vec::new();
+        while let Some(recipe) = apprentice.next_recipe() {
+            seen.push(recipe);
+            apprentice.assign(Some(recipe), 0);
+        }
+        assert_eq!(seen, RecipeId::all_smelting_recipes());
+    }
+
+    #[test]
+    fn wages_start_the_day_after_hiring() {
+        let mut apprentice = Apprentice::default();
+        apprentice.assign(Some(RecipeId::IronIngot), 3);
+
+        assert_eq!(apprentice.wages_due(3), 0);
+        assert_eq!(apprentice.wages_due(5), 2);
+        assert_eq!(apprentice.wages_due(5), 0);
+
+        // Changing recipes doesn't reset the pay period.
+        apprentice.assign(Some(RecipeId::GoldIngot), 6);
+        assert_eq!(apprentice.wages_due(6), 1);
+    }
+
+    #[test]
+    fn work_carries_partial_progress_over() {
+        let mut apprentice = Apprentice::default();
+        assert_eq!(apprentice.work(SECONDS_PER_BAR * 0.75), 0);
+        assert_eq!(apprentice.work(SECONDS_PER_BAR * 1.5), 2);
+        assert_eq!(apprentice.work(SECONDS_PER_BAR * 0.2), 0);
+    }
+
+    #[test]
+    fn bars_burn_coal_on_top_of_the_recipe() {
+        let materials = bar_materials(RecipeId::IronIngot);
+        assert_eq!(materials.get(&ItemId::IronOre), Some(&1));
+        assert_eq!(materials.get(&ItemId::Coal), Some(&COAL_PER_BAR));
+    }
+}
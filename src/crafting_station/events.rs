@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::item::{Item, ItemId};
+
 #[derive(Message, Debug, Clone)]
 pub struct TryStartForgeCrafting {
     pub entity: Entity,
@@ -19,3 +21,10 @@ pub struct TryStartAnvilCrafting {
 pub struct AnvilCraftingStarted {
     pub entity: Entity,
 }
+
+/// An item was evolved at the anvil into `item`.
+#[derive(Message, Debug, Clone)]
+pub struct ItemEvolved {
+    pub from: ItemId,
+    pub item: Item,
+}
@@ -1,13 +1,14 @@
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-use crate::item::ItemId;
+use crate::item::{ItemId, ItemRegistry};
 use crate::skills::{blacksmith_speed_multiplier, SkillType, Skills};
 
 use super::events::{ForgeCraftingStarted, TryStartForgeCrafting};
 use super::ForgeActiveTimer;
 
-#[derive(Component, Default, Clone)]
+#[derive(Component, Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ForgeCraftingState {
     pub coal_slot: Option<(ItemId, u32)>,
     pub ore_slot: Option<(ItemId, u32)>,
@@ -19,21 +20,19 @@ impl ForgeCraftingState {
         self.coal_slot.is_some() && self.ore_slot.is_some() && self.product_slot.is_none()
     }
 
-    pub fn get_output_item(&self) -> Option<ItemId> {
-        self.ore_slot.as_ref().map(|(ore_id, _)| match ore_id {
-            ItemId::CopperOre => ItemId::CopperIngot,
-            ItemId::IronOre => ItemId::IronIngot,
-            ItemId::GoldOre => ItemId::GoldIngot,
-            _ => ItemId::IronIngot,
-        })
+    /// The ingot the loaded ore smelts into.
+    pub fn get_output_item(&self, registry: &ItemRegistry) -> Option<ItemId> {
+        self.ore_slot
+            .as_ref()
+            .and_then(|(ore_id, _)| registry.smelt_output(*ore_id))
     }
 
-    pub fn complete_crafting(&mut self) {
-        self.complete_crafting_with_bonus(0.0);
+    pub fn complete_crafting(&mut self, registry: &ItemRegistry) {
+        self.complete_crafting_with_bonus(0.0, registry);
     }
 
-    pub fn complete_crafting_with_bonus(&mut self, bonus_chance: f32) {
-        let Some(output_id) = self.get_output_item() else {
+    pub fn complete_crafting_with_bonus(&mut self, bonus_chance: f32, registry: &ItemRegistry) {
+        let Some(output_id) = self.get_output_item(registry) else {
             return;
         };
 
@@ -0,0 +1,296 @@
+//! Crafting that carries on while the player is elsewhere. A station's contents and the time
+//! left on its timer are saved with a wall-clock timestamp when its floor unloads or the game
+//! quits; when the station spawns again, the real time that has passed comes off the timer,
+//! finishing the craft if it ran out.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::dungeon::FloorId;
+use crate::game::save_slots::now_secs;
+use crate::save::save_ron;
+
+use super::{
+    AnvilActiveTimer, AnvilCraftingState, AnvilTimerFinished, CraftingStationType,
+    ForgeActiveTimer, ForgeCraftingState, ForgeTimerFinished,
+};
+
+/// Where station contents are saved between sessions.
+pub const CRAFTING_SAVE_PATH: &str = "crafting.ron";
+
+/// The floor a crafting station stands on, so its contents can be found again.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StationFloor(pub FloorId);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StationContents {
+    Forge(ForgeCraftingState),
+    Anvil(AnvilCraftingState),
+}
+
+/// One station as it was left.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedStation {
+    pub floor: FloorId,
+    pub contents: StationContents,
+    /// Seconds left on the station's timer, if it was working.
+    pub remaining_secs: Option<f32>,
+    /// Unix seconds when the station was left.
+    pub saved_at: u64,
+}
+
+impl SavedStation {
+    pub fn kind(&self) -> CraftingStationType {
+        match self.contents {
+            StationContents::Forge(_) => CraftingStationType::Forge,
+            StationContents::Anvil(_) => CraftingStationType::Anvil,
+        }
+    }
+
+    /// Seconds left on the timer at unix time `now`; zero once the craft is done.
+    pub fn remaining_at(&self, now: u64) -> Option<f32> {
+        let away = now.saturating_sub(self.saved_at) as f32;
+        self.remaining_secs
+            .map(|remaining| (remaining - away).max(0.0))
+    }
+}
+
+/// Stations whose floors aren't loaded, waiting to be put back.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedStations {
+    pub stations: Vec<SavedStation>,
+}
+
+impl SavedStations {
+    pub fn record(&mut self, station: SavedStation) {
+        self.stations.push(station);
+    }
+
+    /// Removes and returns the first station of `kind` left on `floor`.
+    pub fn take(&mut self, floor: FloorId, kind: CraftingStationType) -> Option<SavedStation> {
+        let index = self
+            .stations
+            .iter()
+            .position(|station| station.floor == floor && station.kind() == kind)?;
+        Some(self.stations.remove(index))
+    }
+
+    /// What the working stations got done between being saved and unix time `now`, or
+    /// `None` if none were working.
+    pub fn away_report(&self, now: u64) -> Option<AwayReport> {
+        let crafts: Vec<AwayCraft> = self
+            .stations
+            .iter()
+            .filter_map(|station| {
+                Some(AwayCraft {
+                    kind: station.kind(),
+                    remaining_secs: station.remaining_at(now)?,
+                })
+            })
+            .collect();
+        let saved_at = self.stations.iter().map(|station| station.saved_at).max()?;
+        (!crafts.is_empty()).then(|| AwayReport {
+            secs_away: now.saturating_sub(saved_at),
+            crafts,
+        })
+    }
+}
+
+/// A craft that kept going while the game was closed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AwayCraft {
+    pub kind: CraftingStationType,
+    /// Zero if the craft finished.
+    pub remaining_secs: f32,
+}
+
+impl AwayCraft {
+    pub fn is_finished(&self) -> bool {
+        self.remaining_secs <= 0.0
+    }
+}
+
+/// The "while you were away" summary, shown once after loading.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct AwayReport {
+    pub secs_away: u64,
+    pub crafts: Vec<AwayCraft>,
+}
+
+type StationSnapshot<'a> = (
+    &'a StationFloor,
+    Option<&'a ForgeCraftingState>,
+    Option<&'a AnvilCraftingState>,
+    Option<&'a ForgeActiveTimer>,
+    Option<&'a AnvilActiveTimer>,
+);
+
+fn snapshot(
+    (floor, forge, anvil, forge_timer, anvil_timer): StationSnapshot,
+    now: u64,
+) -> Option<SavedStation> {
+    let (contents, timer) = match (forge, anvil) {
+        (Some(forge), _) => (
+            StationContents::Forge(forge.clone()),
+            forge_timer.map(|t| &t.0),
+        ),
+        (_, Some(anvil)) => (
+            StationContents::Anvil(anvil.clone()),
+            anvil_timer.map(|t| &t.0),
+        ),
+        _ => return None,
+    };
+    Some(SavedStation {
+        floor: floor.0,
+        contents,
+        remaining_secs: timer.map(Timer::remaining_secs),
+        saved_at: now,
+    })
+}
+
+pub(super) fn save_stations(saved: Res<SavedStations>) {
+    if let Err(e) = save_ron(CRAFTING_SAVE_PATH, &*saved) {
+        warn!("{e}");
+    }
+}
+
+type RestoredStation<'a> = (
+    Entity,
+    &'a StationFloor,
+    Option<&'a mut ForgeCraftingState>,
+    Option<&'a mut AnvilCraftingState>,
+);
+
+/// Puts a station back as it was left once its floor spawns again, taking the time spent
+/// away off its timer.
+pub(super) fn restore_stations(
+    mut commands: Commands,
+    mut saved: ResMut<SavedStations>,
+    mut stations: Query<RestoredStation, Added<StationFloor>>,
+) {
+    let now = now_secs();
+    for (entity, floor, forge, anvil) in &mut stations {
+        let kind = if forge.is_some() {
+            CraftingStationType::Forge
+        } else {
+            CraftingStationType::Anvil
+        };
+        let Some(station) = saved.take(floor.0, kind) else {
+            continue;
+        };
+        let remaining = station.remaining_at(now);
+        match (station.contents, forge, anvil) {
+            (StationContents::Forge(contents), Some(mut state), _) => {
+                *state = contents;
+                match remaining {
+                    Some(secs) if secs > 0.0 => {
+                        let timer = Timer::from_seconds(secs, TimerMode::Once);
+                        commands.entity(entity).insert(ForgeActiveTimer(timer));
+                    }
+                    Some(_) => commands.trigger(ForgeTimerFinished { entity }),
+                    None => {}
+                }
+            }
+            (StationContents::Anvil(contents), _, Some(mut state)) => {
+                *state = contents;
+                match remaining {
+                    Some(secs) if secs > 0.0 => {
+                        let timer = Timer::from_seconds(secs, TimerMode::Once);
+                        commands.entity(entity).insert(AnvilActiveTimer(timer));
+                    }
+                    Some(_) => commands.trigger(AnvilTimerFinished { entity }),
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Keeps a station's contents and timer when its floor unloads.
+pub(super) fn record_unloaded_station(
+    trigger: On<Remove, StationFloor>,
+    stations: Query<StationSnapshot>,
+    mut saved: ResMut<SavedStations>,
+) {
+    let Ok(station) = stations.get(trigger.entity) else {
+        return;
+    };
+    if let Some(station) = snapshot(station, now_secs()) {
+        saved.record(station);
+    }
+}
+
+/// Saves the stations on the current floor too, since quitting doesn't unload it.
+pub(super) fn save_stations_on_exit(
+    stations: Query<StationSnapshot>,
+    mut saved: ResMut<SavedStations>,
+) {
+    let now = now_secs();
+    for station in &stations {
+        if let Some(station) = snapshot(station, now) {
+            saved.record(station);
+        }
+    }
+    if let Err(e) = save_ron(CRAFTING_SAVE_PATH, &*saved) {
+        warn!("{e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    fn forge(remaining_secs: Option<f32>, saved_at: u64) -> SavedStation {
+        SavedStation {
+            floor: FloorId::HomeFloor,
+            contents: StationContents::Forge(ForgeCraftingState::default()),
+            remaining_secs,
+            saved_at,
+        }
+    }
+
+    #[test]
+    fn real_time_comes_off_the_timer() {
+        let station = forge(Some(30.0), 1_000);
+        assert_eq!(station.remaining_at(1_010), Some(20.0));
+        assert_eq!(station.remaining_at(2_000), Some(0.0));
+        assert_eq!(forge(None, 1_000).remaining_at(2_000), None);
+    }
+
+    #[test]
+    fn take_matches_floor_and_kind() {
+        let mut saved = SavedStations::default();
+        saved.record(forge(None, 0));
+        assert!(saved
+            .take(FloorId::HomeFloor, CraftingStationType::Anvil)
+            .is_none());
+        assert!(saved
+            .take(FloorId::HomeFloor, CraftingStationType::Forge)
+            .is_some());
+        assert!(saved.stations.is_empty());
+    }
+
+    #[test]
+    fn report_covers_only_working_stations() {
+        let mut saved = SavedStations::default();
+        saved.record(forge(None, 100));
+        assert_eq!(saved.away_report(500), None);
+
+        saved.record(forge(Some(60.0), 100));
+        saved.record(forge(Some(600.0), 100));
+        let report = saved.away_report(400).unwrap();
+        assert_eq!(report.secs_away, 300);
+        assert!(report.crafts[0].is_finished());
+        assert_eq!(report.crafts[1].remaining_secs, 300.0);
+    }
+
+    #[test]
+    fn saves_round_trip() {
+        let mut saved = SavedStations::default();
+        saved.record(forge(Some(5.0), 42));
+        let ron = to_ron(&saved).unwrap();
+        assert_eq!(from_ron::<SavedStations>(&ron).unwrap(), saved);
+    }
+}
@@ -1,18 +1,29 @@
 mod anvil;
+mod apprentice;
 mod events;
 mod forge;
+mod idle;
 mod plugin;
 
 pub use anvil::AnvilCraftingState;
+pub use apprentice::{
+    can_hire, Apprentice, ApprenticeStall, APPRENTICE_LEVEL, APPRENTICE_WAGE, SECONDS_PER_BAR,
+};
 pub use events::{
-    AnvilCraftingStarted, ForgeCraftingStarted, TryStartAnvilCrafting, TryStartForgeCrafting,
+    AnvilCraftingStarted, ForgeCraftingStarted, ItemEvolved, TryStartAnvilCrafting,
+    TryStartForgeCrafting,
 };
 pub use forge::ForgeCraftingState;
+pub use idle::{
+    AwayCraft, AwayReport, SavedStation, SavedStations, StationContents, StationFloor,
+    CRAFTING_SAVE_PATH,
+};
 pub use plugin::CraftingStationPlugin;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CraftingStationType {
     Forge,
     Anvil,
@@ -1,32 +1,71 @@
 use bevy::prelude::*;
 use tracing::instrument;
 
+use crate::game::save_slots::now_secs;
 use crate::game::{AnvilCraftingCompleteEvent, ForgeCraftingCompleteEvent};
+use crate::item::ItemRegistry;
+use crate::save::load_ron;
+use crate::states::{AppState, ScreenSet};
 
 use super::anvil::handle_try_start_anvil_crafting;
+use super::apprentice::{pay_apprentice, run_apprentice, Apprentice};
 use super::events::{
-    AnvilCraftingStarted, ForgeCraftingStarted, TryStartAnvilCrafting, TryStartForgeCrafting,
+    AnvilCraftingStarted, ForgeCraftingStarted, ItemEvolved, TryStartAnvilCrafting,
+    TryStartForgeCrafting,
 };
 use super::forge::handle_try_start_forge_crafting;
+use super::idle::{
+    record_unloaded_station, restore_stations, save_stations, save_stations_on_exit, SavedStations,
+    StationFloor, CRAFTING_SAVE_PATH,
+};
 use super::{AnvilActiveTimer, AnvilTimerFinished, ForgeActiveTimer, ForgeTimerFinished};
 
 pub struct CraftingStationPlugin;
 
 impl Plugin for CraftingStationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_message::<TryStartForgeCrafting>()
+        let saved = load_ron::<SavedStations>(CRAFTING_SAVE_PATH);
+        if let Some(report) = saved.away_report(now_secs()) {
+            app.insert_resource(report);
+        }
+
+        app.init_resource::<Apprentice>()
+            .insert_resource(saved)
+            .add_message::<TryStartForgeCrafting>()
             .add_message::<ForgeCraftingStarted>()
             .add_message::<TryStartAnvilCrafting>()
             .add_message::<AnvilCraftingStarted>()
+            .add_message::<ItemEvolved>()
             .add_observer(on_forge_timer_finished)
             .add_observer(on_anvil_timer_finished)
+            .add_observer(record_unloaded_station)
             .add_systems(
                 Update,
                 (
                     handle_try_start_forge_crafting.run_if(on_message::<TryStartForgeCrafting>),
                     handle_try_start_anvil_crafting.run_if(on_message::<TryStartAnvilCrafting>),
-                ),
+                )
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(
+                Update,
+                (pay_apprentice, run_apprentice)
+                    .chain()
+                    .run_if(in_state(AppState::Dungeon))
+                    .run_if(resource_exists::<ItemRegistry>),
+            )
+            .add_systems(
+                Update,
+                (
+                    restore_stations.run_if(any_with_component::<StationFloor>),
+                    save_stations.run_if(
+                        resource_changed::<SavedStations>
+                            .and(not(resource_added::<SavedStations>)),
+                    ),
+                )
+                    .chain(),
             )
+            .add_systems(Last, save_stations_on_exit.run_if(on_message::<AppExit>))
             .add_systems(
                 FixedUpdate,
                 (
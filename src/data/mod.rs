@@ -1,5 +1,9 @@
+pub mod mods;
+pub mod packs;
 pub mod plugin;
 mod utils;
 
+pub use mods::{LoadedMods, ModCollision, ModInfo};
+pub use packs::{RecipePack, SpawnPack};
 pub use plugin::DataPlugin;
 pub use utils::StatRange;
@@ -0,0 +1,167 @@
+//! Content packs loaded from `assets/mods/<name>/`.
+//!
+//! `.item.ron` and `.mob.ron` files replace whole specs. Recipes and floors are compiled in
+//! through `define_data!`, so mods change them with `.recipe.ron` and `.spawns.ron` packs
+//! instead (see [`packs`](super::packs)). All IDs are enums, so a mod can rebalance or reskin
+//! existing entries but cannot add new IDs. Each ID a mod changes is also registered under the
+//! mod's name in [`ContentRegistry`](crate::registry::ContentRegistry) (`mymod:iron_sword`),
+//! and saved items are written by content key rather than enum name.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Directory under `assets/` that content packs live in. Each subdirectory is one mod.
+pub const MODS_DIR: &str = "mods";
+
+/// Optional file inside [`MODS_DIR`] listing mod names in the order they should load.
+const LOAD_ORDER_FILE: &str = "load_order.ron";
+
+/// Source label for specs that come from `assets/data`.
+pub const BASE_SOURCE: &str = crate::registry::BASE_NAMESPACE;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct LoadOrder(Vec<String>);
+
+/// Summary of one mod that was merged into the registries.
+#[derive(Debug, Clone)]
+pub struct ModInfo {
+    pub name: String,
+    pub items: usize,
+    pub mobs: usize,
+    pub recipes: usize,
+    pub spawn_tables: usize,
+}
+
+/// A spec that was defined more than once. The later source wins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModCollision {
+    pub id: String,
+    pub previous: String,
+    pub replaced_by: String,
+}
+
+/// Mods merged at startup, in load order, along with every ID they overrode.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LoadedMods {
+    pub mods: Vec<ModInfo>,
+    pub collisions: Vec<ModCollision>,
+}
+
+/// Finds mod directories under `assets_root/mods` and returns them in load order.
+pub fn discover_mods(assets_root: &Path) -> Vec<String> {
+    let mods_root = assets_root.join(MODS_DIR);
+    let Ok(entries) = std::fs::read_dir(&mods_root) else {
+        return Vec::new();
+    };
+
+    let found: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+
+    let order = std::fs::read_to_string(mods_root.join(LOAD_ORDER_FILE))
+        .ok()
+        .and_then(|contents| match ron::from_str::<LoadOrder>(&contents) {
+            Ok(order) => Some(order),
+            Err(e) => {
+                warn!("Ignoring malformed {}: {}", LOAD_ORDER_FILE, e);
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    resolve_load_order(found, &order.0)
+}
+
+/// Orders mods by the explicit load order first, then alphabetically for the rest.
+/// Entries in `order` that were not found on disk are skipped.
+pub fn resolve_load_order(mut found: Vec<String>, order: &[String]) -> Vec<String> {
+    found.sort();
+
+    let mut resolved: Vec<String> = Vec::with_capacity(found.len());
+    for name in order.iter().chain(found.iter()) {
+        if found.contains(name) && !resolved.contains(name) {
+            resolved.push(name.clone());
+        }
+    }
+    resolved
+}
+
+/// Merges `specs` from `source` into `target`, recording any ID that was already present.
+/// Returns how many specs were merged.
+pub fn merge_specs<K, V>(
+    target: &mut HashMap<K, V>,
+    sources: &mut HashMap<K, String>,
+    source: &str,
+    specs: impl IntoIterator<Item = (K, V)>,
+    collisions: &mut Vec<ModCollision>,
+) -> usize
+where
+    K: Eq + Hash + Copy + Debug,
+{
+    let mut merged = 0;
+    for (id, spec) in specs {
+        if let Some(previous) = sources.insert(id, source.to_string()) {
+            warn!("{} overrides {:?} from {}", source, id, previous);
+            collisions.push(ModCollision {
+                id: format!("{id:?}"),
+                previous,
+                replaced_by: source.to_string(),
+            });
+        }
+        target.insert(id, spec);
+        merged += 1;
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn load_order_defaults_to_alphabetical() {
+        let resolved = resolve_load_order(names(&["zeta", "alpha", "mid"]), &[]);
+        assert_eq!(resolved, names(&["alpha", "mid", "zeta"]));
+    }
+
+    #[test]
+    fn load_order_puts_listed_mods_first() {
+        let resolved = resolve_load_order(
+            names(&["alpha", "beta", "gamma"]),
+            &names(&["gamma", "missing", "alpha"]),
+        );
+        assert_eq!(resolved, names(&["gamma", "alpha", "beta"]));
+    }
+
+    #[test]
+    fn merge_specs_reports_collisions_and_keeps_latest() {
+        let mut target = HashMap::new();
+        let mut sources = HashMap::new();
+        let mut collisions = Vec::new();
+
+        merge_specs(&mut target, &mut sources, BASE_SOURCE, [(1, "a"), (2, "b")], &mut collisions);
+        let merged = merge_specs(&mut target, &mut sources, "cool_mod", [(2, "c")], &mut collisions);
+
+        assert_eq!(merged, 1);
+        assert_eq!(target[&2], "c");
+        assert_eq!(
+            collisions,
+            vec![ModCollision {
+                id: "2".to_string(),
+                previous: BASE_SOURCE.to_string(),
+                replaced_by: "cool_mod".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Mod files that change compiled-in `define_data!` content.
+//!
+//! Recipes and floors are not loaded from `assets/data`, so mods cannot ship whole specs for
+//! them. A pack names an existing ID and replaces the moddable parts; everything else keeps the
+//! compiled-in value from `base_spec`.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::dungeon::floor::{FloorId, FloorSpec};
+use crate::item::recipe::specs::RecipeSpec;
+use crate::item::recipe::RecipeId;
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// A `.recipe.ron` file: new ingredients and output quantity for an existing recipe.
+#[derive(Debug, Clone, Deserialize, Asset, TypePath)]
+pub struct RecipePack {
+    pub id: RecipeId,
+    pub ingredients: HashMap<ItemId, u32>,
+    #[serde(default = "one")]
+    pub output_quantity: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl RecipePack {
+    pub fn spec(&self) -> RecipeSpec {
+        RecipeSpec {
+            ingredients: self.ingredients.clone(),
+            output_quantity: self.output_quantity,
+            ..self.id.base_spec().clone()
+        }
+    }
+}
+
+/// A `.spawns.ron` file: the weighted mobs of a floor's spawn table, and optionally how many
+/// of them spawn. Nests, guaranteed mobs and props are left alone.
+#[derive(Debug, Clone, Deserialize, Asset, TypePath)]
+pub struct SpawnPack {
+    pub floor: FloorId,
+    pub mobs: Vec<(MobId, u32)>,
+    #[serde(default)]
+    pub mob_count: Option<(u32, u32)>,
+}
+
+impl SpawnPack {
+    pub fn spec(&self) -> FloorSpec {
+        let mut spec = self.floor.base_spec().clone();
+        spec.spawn_table.replace_mobs(self.mobs.iter().copied());
+        if let Some((min, max)) = self.mob_count {
+            spec.spawn_table.set_mob_count(min..=max);
+        }
+        spec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recipe_pack_keeps_name_and_output() {
+        let pack: RecipePack =
+            ron::from_str("(id: IronIngot, ingredients: {IronOre: 3}, output_quantity: 2)")
+                .unwrap();
+        let spec = pack.spec();
+        let base = RecipeId::IronIngot.base_spec();
+
+        assert_eq!(spec.name, base.name);
+        assert_eq!(spec.output, base.output);
+        assert_eq!(spec.ingredients, HashMap::from([(ItemId::IronOre, 3)]));
+        assert_eq!(spec.output_quantity, 2);
+    }
+
+    #[test]
+    fn spawn_pack_replaces_weighted_mobs_only() {
+        let floor = FloorId::ALL
+            .iter()
+            .copied()
+            .find(|id| !id.base_spec().spawn_table.weighted_mobs().is_empty())
+            .unwrap();
+        let pack = SpawnPack {
+            floor,
+            mobs: vec![(MobId::Goblin, 5)],
+            mob_count: Some((1, 2)),
+        };
+        let spec = pack.spec();
+        let base = &floor.base_spec().spawn_table;
+
+        let mobs = spec.spawn_table.weighted_mobs();
+        assert_eq!(mobs.len(), 1);
+        assert_eq!((mobs[0].mob_id, mobs[0].weight), (MobId::Goblin, 5));
+        assert_eq!(spec.spawn_table.mob_count(), &(1..=2));
+        assert_eq!(spec.spawn_table.weighted_nests().len(), base.weighted_nests().len());
+        assert_eq!(spec.name, floor.base_spec().name);
+    }
+}
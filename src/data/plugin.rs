@@ -1,15 +1,22 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use bevy::asset::LoadedFolder;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_common_assets::ron::RonAssetPlugin;
 
+use crate::dungeon::floor::FloorId;
 use crate::item::definitions::ItemSpec;
+use crate::item::recipe::RecipeId;
 use crate::item::ItemRegistry;
 use crate::mob::definitions::{MobId, MobSpec};
-use crate::registry::Registry;
+use crate::registry::{Content, ContentRegistry, Registry};
 use crate::states::AppState;
 
+use super::mods::{discover_mods, merge_specs, LoadedMods, ModInfo, BASE_SOURCE, MODS_DIR};
+use super::packs::{RecipePack, SpawnPack};
+
 pub struct DataPlugin;
 
 impl Plugin for DataPlugin {
@@ -17,7 +24,10 @@ impl Plugin for DataPlugin {
         app.add_plugins((
             RonAssetPlugin::<MobSpec>::new(&["mob.ron"]),
             RonAssetPlugin::<ItemSpec>::new(&["item.ron"]),
+            RonAssetPlugin::<RecipePack>::new(&["recipe.ron"]),
+            RonAssetPlugin::<SpawnPack>::new(&["spawns.ron"]),
         ))
+        .init_resource::<ContentRegistry>()
         .add_systems(OnEnter(AppState::Loading), start_loading)
         .add_systems(
             Update,
@@ -30,21 +40,40 @@ impl Plugin for DataPlugin {
 struct PendingLoads {
     mob_folder: Handle<LoadedFolder>,
     item_folder: Handle<LoadedFolder>,
+    mod_folders: Vec<(String, Handle<LoadedFolder>)>,
 }
 
 fn start_loading(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mod_folders = discover_mods(Path::new("assets"))
+        .into_iter()
+        .map(|name| {
+            let handle = asset_server.load_folder(format!("{MODS_DIR}/{name}"));
+            (name, handle)
+        })
+        .collect();
+
     commands.insert_resource(PendingLoads {
         mob_folder: asset_server.load_folder("data/mobs"),
         item_folder: asset_server.load_folder("data/items"),
+        mod_folders,
     });
 }
 
+/// The loaded spec files, base game and mods alike.
+#[derive(SystemParam)]
+struct SpecAssets<'w> {
+    mobs: Res<'w, Assets<MobSpec>>,
+    items: Res<'w, Assets<ItemSpec>>,
+    recipes: Res<'w, Assets<RecipePack>>,
+    spawns: Res<'w, Assets<SpawnPack>>,
+}
+
 fn check_loading_complete(
     mut commands: Commands,
     pending: Res<PendingLoads>,
     folders: Res<Assets<LoadedFolder>>,
-    mob_assets: Res<Assets<MobSpec>>,
-    item_assets: Res<Assets<ItemSpec>>,
+    specs: SpecAssets,
+    asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
     let (Some(mob_folder), Some(item_folder)) = (
@@ -57,7 +86,7 @@ fn check_loading_complete(
     let mob_specs: Vec<&MobSpec> = mob_folder
         .handles
         .iter()
-        .filter_map(|h| mob_assets.get(h.id().typed::<MobSpec>()))
+        .filter_map(|h| specs.mobs.get(h.id().typed::<MobSpec>()))
         .collect();
 
     if mob_specs.len() != mob_folder.handles.len() {
@@ -67,28 +96,169 @@ fn check_loading_complete(
     let item_specs: Vec<&ItemSpec> = item_folder
         .handles
         .iter()
-        .filter_map(|h| item_assets.get(h.id().typed::<ItemSpec>()))
+        .filter_map(|h| specs.items.get(h.id().typed::<ItemSpec>()))
         .collect();
 
     if item_specs.len() != item_folder.handles.len() {
         return;
     }
 
-    let mob_map: HashMap<MobId, MobSpec> = mob_specs
-        .into_iter()
-        .map(|spec| (spec.id, spec.clone()))
+    if !pending.mod_folders.iter().all(|(_, handle)| {
+        asset_server.is_loaded_with_dependencies(handle) || mod_failed(&asset_server, handle)
+    }) {
+        return;
+    }
+
+    let mut loaded_mods = LoadedMods::default();
+    let mut mob_map: HashMap<MobId, MobSpec> = HashMap::new();
+    let mut mob_sources = HashMap::new();
+    let mut item_map = HashMap::new();
+    let mut item_sources = HashMap::new();
+    let mut recipe_map = HashMap::new();
+    let mut recipe_sources: HashMap<RecipeId, String> = RecipeId::ALL
+        .iter()
+        .map(|&id| (id, BASE_SOURCE.to_string()))
+        .collect();
+    let mut floor_map = HashMap::new();
+    let mut floor_sources: HashMap<FloorId, String> = FloorId::ALL
+        .iter()
+        .map(|&id| (id, BASE_SOURCE.to_string()))
         .collect();
+    let mut spawn_packs: Vec<(&str, Vec<&SpawnPack>)> = Vec::new();
+    let mut content = ContentRegistry::default();
+
+    merge_specs(
+        &mut mob_map,
+        &mut mob_sources,
+        BASE_SOURCE,
+        mob_specs.into_iter().map(|spec| (spec.id, spec.clone())),
+        &mut loaded_mods.collisions,
+    );
+    merge_specs(
+        &mut item_map,
+        &mut item_sources,
+        BASE_SOURCE,
+        item_specs.into_iter().map(|spec| (spec.id, spec.clone())),
+        &mut loaded_mods.collisions,
+    );
+
+    for (name, handle) in &pending.mod_folders {
+        let Some(folder) = folders
+            .get(handle)
+            .filter(|_| !mod_failed(&asset_server, handle))
+        else {
+            warn!("Mod {} failed to load, skipping", name);
+            continue;
+        };
+
+        let mobs = merge_specs(
+            &mut mob_map,
+            &mut mob_sources,
+            name,
+            folder
+                .handles
+                .iter()
+                .filter_map(|h| h.id().try_typed::<MobSpec>().ok())
+                .filter_map(|id| specs.mobs.get(id))
+                .map(|spec| (spec.id, spec.clone()))
+                .inspect(|(id, _)| register_mod_key(&mut content, name, *id)),
+            &mut loaded_mods.collisions,
+        );
+        let items = merge_specs(
+            &mut item_map,
+            &mut item_sources,
+            name,
+            folder
+                .handles
+                .iter()
+                .filter_map(|h| h.id().try_typed::<ItemSpec>().ok())
+                .filter_map(|id| specs.items.get(id))
+                .map(|spec| (spec.id, spec.clone()))
+                .inspect(|(id, _)| register_mod_key(&mut content, name, *id)),
+            &mut loaded_mods.collisions,
+        );
+
+        let recipes = merge_specs(
+            &mut recipe_map,
+            &mut recipe_sources,
+            name,
+            folder
+                .handles
+                .iter()
+                .filter_map(|h| h.id().try_typed::<RecipePack>().ok())
+                .filter_map(|id| specs.recipes.get(id))
+                .map(|pack| (pack.id, pack.spec()))
+                .inspect(|(id, _)| register_mod_key(&mut content, name, *id)),
+            &mut loaded_mods.collisions,
+        );
+
+        let packs: Vec<&SpawnPack> = folder
+            .handles
+            .iter()
+            .filter_map(|h| h.id().try_typed::<SpawnPack>().ok())
+            .filter_map(|id| specs.spawns.get(id))
+            .collect();
+        let spawn_tables = packs.len();
+        spawn_packs.push((name.as_str(), packs));
+
+        info!(
+            "Loaded mod {} ({} items, {} mobs, {} recipes, {} spawn tables)",
+            name, items, mobs, recipes, spawn_tables
+        );
+        loaded_mods.mods.push(ModInfo {
+            name: name.clone(),
+            items,
+            mobs,
+            recipes,
+            spawn_tables,
+        });
+    }
 
     let mut item_registry = ItemRegistry::new();
-    for spec in item_specs {
-        item_registry.register(spec.clone());
+    for spec in item_map.into_values() {
+        item_registry.register(spec);
     }
 
     crate::mob::data::populate(mob_map.clone());
 
+    // Spawn packs look up mob sizes, so they are built once every mod's mobs are in place.
+    for (name, packs) in spawn_packs {
+        merge_specs(
+            &mut floor_map,
+            &mut floor_sources,
+            name,
+            packs
+                .into_iter()
+                .map(|pack| (pack.floor, pack.spec()))
+                .inspect(|(id, _)| register_mod_key(&mut content, name, *id)),
+            &mut loaded_mods.collisions,
+        );
+    }
+    RecipeId::set_overrides(recipe_map);
+    FloorId::set_overrides(floor_map);
+
     commands.insert_resource(item_registry);
     commands.insert_resource(Registry::new(mob_map));
+    commands.insert_resource(loaded_mods);
+    content.install();
+    commands.insert_resource(content);
 
     commands.remove_resource::<PendingLoads>();
     next_state.set(AppState::Menu);
 }
+
+/// Names `id` in the mod's namespace as well, e.g. `mymod:iron_sword` for a spec it replaces.
+fn register_mod_key<T: Content>(content: &mut ContentRegistry, mod_name: &str, id: T) {
+    if let Err(e) = content.register_mod(mod_name, id) {
+        warn!("Mod {} cannot name {:?}: {}", mod_name, id, e);
+    }
+}
+
+/// Whether a mod folder, or any file in it, failed to load. Such mods are skipped instead of
+/// holding up the loading screen.
+fn mod_failed(asset_server: &AssetServer, handle: &Handle<LoadedFolder>) -> bool {
+    asset_server.load_state(handle).is_failed()
+        || asset_server
+            .recursive_dependency_load_state(handle)
+            .is_failed()
+}
@@ -0,0 +1,17 @@
+//! Branching NPC conversations, loaded from data files. Choices can depend on quest
+//! progress, faction standing and what the player carries, and can hand out items, start
+//! and finish quests, shift reputation or open the NPC's shop.
+
+pub mod plugin;
+pub mod quest;
+pub mod tree;
+
+pub use plugin::{
+    ActiveDialogue, ChoiceConditions, DialogueChosen, DialogueLibrary, DialoguePlugin,
+    DIALOGUE_DIR,
+};
+pub use quest::{quest_flag, quest_state, set_quest_state, QuestState};
+pub use tree::{
+    DialogueChoice, DialogueCondition, DialogueContext, DialogueEffect, DialogueError,
+    DialogueNode, DialogueTree,
+};
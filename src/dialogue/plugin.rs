@@ -0,0 +1,190 @@
+use bevy::asset::LoadedFolder;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use crate::faction::{Reputation, ReputationGained};
+use crate::game::{OpenTownShop, ShowToast};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::mob::MobId;
+use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::world_state::WorldFlags;
+
+use super::quest::{quest_flag, set_quest_state, QuestState};
+use super::tree::{DialogueChoice, DialogueContext, DialogueEffect, DialogueTree};
+
+/// Directory under `assets/` that dialogue trees are loaded from.
+pub const DIALOGUE_DIR: &str = "data/dialogue";
+
+/// Handle to the loaded dialogue folder, read on demand like the help library.
+#[derive(Resource)]
+pub struct DialogueLibrary {
+    folder: Handle<LoadedFolder>,
+}
+
+impl DialogueLibrary {
+    /// The first valid tree spoken by `npc`, if any.
+    pub fn for_npc(
+        &self,
+        npc: MobId,
+        folders: &Assets<LoadedFolder>,
+        assets: &Assets<DialogueTree>,
+    ) -> Option<DialogueTree> {
+        folders
+            .get(&self.folder)?
+            .handles
+            .iter()
+            .filter_map(|h| h.id().try_typed::<DialogueTree>().ok())
+            .filter_map(|id| assets.get(id))
+            .filter(|tree| tree.npc == npc)
+            .find(|tree| match tree.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("{e}");
+                    false
+                }
+            })
+            .cloned()
+    }
+}
+
+/// The conversation underway and the node it is at.
+#[derive(Resource, Debug, Clone)]
+pub struct ActiveDialogue {
+    pub tree: DialogueTree,
+    pub node: String,
+}
+
+/// What the offered choices are checked against: the world's flags, the player's standing
+/// and what they carry.
+#[derive(SystemParam)]
+pub struct ChoiceConditions<'w, 's> {
+    flags: Res<'w, WorldFlags>,
+    reputation: Res<'w, Reputation>,
+    player: Query<'w, 's, &'static Inventory, With<PlayerMarker>>,
+    registry: Res<'w, ItemRegistry>,
+}
+
+impl ChoiceConditions<'_, '_> {
+    /// `None` until the player is spawned.
+    pub fn context(&self) -> Option<DialogueContext<'_>> {
+        Some(DialogueContext {
+            flags: &self.flags,
+            reputation: &self.reputation,
+            inventory: self.player.single().ok()?,
+        })
+    }
+
+    /// Whether the backpack has room for what `choice` gives.
+    pub fn fits(&self, choice: &DialogueChoice) -> bool {
+        self.player
+            .single()
+            .is_ok_and(|inventory| choice.fits(inventory, &self.registry))
+    }
+}
+
+impl ActiveDialogue {
+    pub fn new(tree: DialogueTree) -> Self {
+        let node = tree.start.clone();
+        Self { tree, node }
+    }
+}
+
+/// The player picked a choice; its effects are applied in order.
+#[derive(Message, Debug, Clone)]
+pub struct DialogueChosen {
+    pub effects: Vec<DialogueEffect>,
+}
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<DialogueTree>::new(&["dialogue.ron"]))
+            .add_message::<DialogueChosen>()
+            .add_systems(Startup, load_dialogue_folder)
+            .add_systems(
+                Update,
+                apply_dialogue_effects
+                    .run_if(on_message::<DialogueChosen>)
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+fn load_dialogue_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DialogueLibrary {
+        folder: asset_server.load_folder(DIALOGUE_DIR),
+    });
+}
+
+/// The player's inventory, and the registry the items a choice gives are spawned from.
+#[derive(SystemParam)]
+struct DialogueItems<'w, 's> {
+    player: Query<'w, 's, &'static mut Inventory, With<PlayerMarker>>,
+    registry: Res<'w, ItemRegistry>,
+}
+
+fn apply_dialogue_effects(
+    mut commands: Commands,
+    mut chosen: MessageReader<DialogueChosen>,
+    items: DialogueItems,
+    mut flags: ResMut<WorldFlags>,
+    loc: Res<Localization>,
+    mut reputation_writer: MessageWriter<ReputationGained>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let DialogueItems {
+        mut player,
+        registry,
+    } = items;
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for effect in chosen.read().flat_map(|choice| &choice.effects) {
+        match effect {
+            DialogueEffect::GiveItem(item_id, quantity) => {
+                let name = loc.item_name(*item_id, &registry.get(*item_id).name);
+                let given = (0..*quantity)
+                    .take_while(|_| inventory.add_to_inv(registry.spawn(*item_id)).is_ok())
+                    .count();
+                let key = if given as u32 == *quantity {
+                    "dialogue.received"
+                } else {
+                    "dialogue.inventory_full"
+                };
+                toast_writer.write(ShowToast::new(
+                    loc.format(key, &[("item", &name), ("quantity", &given)]),
+                ));
+            }
+            DialogueEffect::TakeItem(item_id, quantity) => {
+                inventory.decrease_item_quantity(*item_id, *quantity);
+            }
+            DialogueEffect::StartQuest(name) => {
+                set_quest_state(&mut flags, name, QuestState::Started);
+                let title = quest_flag(name);
+                toast_writer.write(ShowToast::new(
+                    loc.format("dialogue.quest_started", &[("quest", &loc.get(&title))]),
+                ));
+            }
+            DialogueEffect::CompleteQuest(name) => {
+                set_quest_state(&mut flags, name, QuestState::Completed);
+                let title = quest_flag(name);
+                toast_writer.write(ShowToast::new(
+                    loc.format("dialogue.quest_completed", &[("quest", &loc.get(&title))]),
+                ));
+            }
+            DialogueEffect::GainReputation(faction, amount) => {
+                reputation_writer.write(ReputationGained {
+                    faction: *faction,
+                    amount: *amount,
+                });
+            }
+            DialogueEffect::OpenShop => commands.trigger(OpenTownShop),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Quests handed out in conversation. A quest's progress lives in the world flags under
+//! `quest.<name>`, so it is saved with them and other systems can read it.
+
+use serde::Deserialize;
+
+use crate::world_state::{FlagValue, WorldFlags};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum QuestState {
+    #[default]
+    NotStarted,
+    Started,
+    Completed,
+}
+
+impl QuestState {
+    fn flag_text(self) -> Option<&'static str> {
+        match self {
+            QuestState::NotStarted => None,
+            QuestState::Started => Some("started"),
+            QuestState::Completed => Some("completed"),
+        }
+    }
+}
+
+/// World flag holding the state of the quest called `name`.
+pub fn quest_flag(name: &str) -> String {
+    format!("quest.{name}")
+}
+
+pub fn quest_state(flags: &WorldFlags, name: &str) -> QuestState {
+    match flags.text(&quest_flag(name)) {
+        Some("started") => QuestState::Started,
+        Some("completed") => QuestState::Completed,
+        _ => QuestState::NotStarted,
+    }
+}
+
+pub fn set_quest_state(flags: &mut WorldFlags, name: &str, state: QuestState) {
+    match state.flag_text() {
+        Some(text) => flags.set(quest_flag(name), FlagValue::Text(text.to_string())),
+        None => {
+            flags.remove(&quest_flag(name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quest_state_round_trips_through_flags() {
+        let mut flags = WorldFlags::default();
+        assert_eq!(quest_state(&flags, "ore"), QuestState::NotStarted);
+
+        set_quest_state(&mut flags, "ore", QuestState::Started);
+        assert!(flags.is_set("quest.ore"));
+        assert_eq!(quest_state(&flags, "ore"), QuestState::Started);
+
+        set_quest_state(&mut flags, "ore", QuestState::Completed);
+        assert_eq!(quest_state(&flags, "ore"), QuestState::Completed);
+        assert_eq!(quest_state(&flags, "other"), QuestState::NotStarted);
+    }
+}
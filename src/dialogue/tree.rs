@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::faction::{FactionId, FactionRank, Reputation};
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::{ItemId, ItemRegistry};
+use crate::mob::MobId;
+use crate::world_state::WorldFlags;
+
+use super::quest::{quest_state, QuestState};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DialogueError {
+    #[error("Dialogue for {npc:?} starts at missing node {node:?}")]
+    MissingStart { npc: MobId, node: String },
+    #[error("Dialogue node {from:?} for {npc:?} leads to missing node {to:?}")]
+    MissingNode {
+        npc: MobId,
+        from: String,
+        to: String,
+    },
+}
+
+/// What must hold for a choice to be offered.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum DialogueCondition {
+    /// The named quest is in this state.
+    Quest(String, QuestState),
+    /// The player holds at least this rank with the faction.
+    MinRank(FactionId, FactionRank),
+    /// The player carries at least this many of the item.
+    HasItem(ItemId, u32),
+}
+
+/// What picking a choice does.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum DialogueEffect {
+    GiveItem(ItemId, u32),
+    TakeItem(ItemId, u32),
+    StartQuest(String),
+    CompleteQuest(String),
+    GainReputation(FactionId, i32),
+    /// Ends the conversation and opens the speaker's shop.
+    OpenShop,
+}
+
+/// What the player can read off the world when choices are checked.
+#[derive(Clone, Copy)]
+pub struct DialogueContext<'a> {
+    pub flags: &'a WorldFlags,
+    pub reputation: &'a Reputation,
+    pub inventory: &'a Inventory,
+}
+
+impl DialogueCondition {
+    pub fn holds(&self, context: DialogueContext) -> bool {
+        match self {
+            DialogueCondition::Quest(name, state) => quest_state(context.flags, name) == *state,
+            DialogueCondition::MinRank(faction, rank) => context.reputation.rank(*faction) >= *rank,
+            DialogueCondition::HasItem(item_id, quantity) => {
+                context.inventory.count_item(*item_id) >= *quantity
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DialogueChoice {
+    /// Localization key for what the player says.
+    pub text: String,
+    #[serde(default)]
+    pub conditions: Vec<DialogueCondition>,
+    #[serde(default)]
+    pub effects: Vec<DialogueEffect>,
+    /// Node to go to next. Choices without one end the conversation.
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl DialogueChoice {
+    pub fn is_available(&self, context: DialogueContext) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.holds(context))
+    }
+
+    /// Whether the backpack has room for every item the choice gives, once the items it
+    /// takes are gone. Effects apply in order, so this is checked the same way.
+    pub fn fits(&self, inventory: &Inventory, registry: &ItemRegistry) -> bool {
+        let mut inventory = inventory.clone();
+        self.effects.iter().all(|effect| match effect {
+            DialogueEffect::TakeItem(item_id, quantity) => {
+                inventory.decrease_item_quantity(*item_id, *quantity);
+                true
+            }
+            DialogueEffect::GiveItem(item_id, quantity) => (0..*quantity)
+                .all(|_| inventory.add_to_inv(registry.spawn(*item_id)).is_ok()),
+            _ => true,
+        })
+    }
+
+    /// Whether picking this choice ends the conversation.
+    pub fn ends(&self) -> bool {
+        self.next.is_none() || self.effects.contains(&DialogueEffect::OpenShop)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DialogueNode {
+    /// Localization key for what the NPC says.
+    pub text: String,
+    pub choices: Vec<DialogueChoice>,
+}
+
+/// A conversation with an NPC, loaded from `assets/data/dialogue/*.dialogue.ron`.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DialogueTree {
+    /// Who speaks it.
+    pub npc: MobId,
+    /// Localization key for the speaker's name, shown as the modal title.
+    pub name: String,
+    pub start: String,
+    pub nodes: HashMap<String, DialogueNode>,
+}
+
+impl DialogueTree {
+    pub fn node(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.get(id)
+    }
+
+    /// Choices at `node` the player currently qualifies for, in order.
+    pub fn available_choices<'a>(
+        &'a self,
+        node: &str,
+        context: DialogueContext,
+    ) -> Vec<&'a DialogueChoice> {
+        self.node(node)
+            .map(|node| {
+                node.choices
+                    .iter()
+                    .filter(|choice| choice.is_available(context))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks that the start and every choice lead to nodes that exist.
+    pub fn validate(&self) -> Result<(), DialogueError> {
+        if !self.nodes.contains_key(&self.start) {
+            return Err(DialogueError::MissingStart {
+                npc: self.npc,
+                node: self.start.clone(),
+            });
+        }
+        for (id, node) in &self.nodes {
+            let missing = node
+                .choices
+                .iter()
+                .filter_map(|choice| choice.next.as_ref())
+                .find(|next| !self.nodes.contains_key(*next));
+            if let Some(next) = missing {
+                return Err(DialogueError::MissingNode {
+                    npc: self.npc,
+                    from: id.clone(),
+                    to: next.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world_state::FlagValue;
+
+    const TREE: &str = r#"(
+        npc: Merchant,
+        name: "Merchant",
+        start: "hello",
+        nodes: {
+            "hello": (
+                text: "Welcome!",
+                choices: [
+                    (text: "Shop", effects: [OpenShop], next: Some("hello")),
+                    (
+                        text: "Got the ore",
+                        conditions: [Quest("ore", Started), HasItem(CopperOre, 5)],
+                        effects: [TakeItem(CopperOre, 5), CompleteQuest("ore")],
+                        next: Some("thanks"),
+                    ),
+                    (text: "Members only", conditions: [MinRank(MerchantsLeague, Member)]),
+                    (text: "Bye"),
+                ],
+            ),
+            "thanks": (text: "Thank you!", choices: [(text: "Bye")]),
+        },
+    )"#;
+
+    fn tree() -> DialogueTree {
+        ron::from_str(TREE).unwrap()
+    }
+
+    #[test]
+    fn trees_parse_and_validate() {
+        let tree = tree();
+        assert_eq!(tree.npc, MobId::Merchant);
+        assert!(tree.validate().is_ok());
+
+        let mut broken = tree.clone();
+        broken.start = "nowhere".to_string();
+        assert!(matches!(
+            broken.validate(),
+            Err(DialogueError::MissingStart { .. })
+        ));
+    }
+
+    #[test]
+    fn choices_show_only_when_their_conditions_hold() {
+        let tree = tree();
+        let mut flags = WorldFlags::default();
+        let mut reputation = Reputation::default();
+        let inventory = Inventory::new();
+        let texts = |flags: &WorldFlags, reputation: &Reputation| -> Vec<String> {
+            let context = DialogueContext {
+                flags,
+                reputation,
+                inventory: &inventory,
+            };
+            tree.available_choices("hello", context)
+                .iter()
+                .map(|choice| choice.text.clone())
+                .collect()
+        };
+
+        assert_eq!(texts(&flags, &reputation), ["Shop", "Bye"]);
+
+        // A started quest alone isn't enough without the ore in hand.
+        flags.set("quest.ore", FlagValue::Text("started".to_string()));
+        assert_eq!(texts(&flags, &reputation), ["Shop", "Bye"]);
+
+        reputation.adjust(FactionId::MerchantsLeague, FactionRank::Member.threshold());
+        assert_eq!(texts(&flags, &reputation), ["Shop", "Members only", "Bye"]);
+    }
+
+    #[test]
+    fn shop_and_leaf_choices_end_the_conversation() {
+        let tree = tree();
+        let choices = &tree.node("hello").unwrap().choices;
+        assert!(choices[0].ends());
+        assert!(!choices[1].ends());
+        assert!(choices[3].ends());
+    }
+
+    #[test]
+    fn gifts_need_room_after_what_is_taken() {
+        let registry = ItemRegistry::from_assets();
+        let mut inventory = Inventory::with_max_slots(1);
+        for _ in 0..5 {
+            inventory.add_to_inv(registry.spawn(ItemId::CopperOre)).unwrap();
+        }
+        let choice = |effects| DialogueChoice {
+            text: String::new(),
+            conditions: Vec::new(),
+            effects,
+            next: None,
+        };
+
+        let gift = choice(vec![DialogueEffect::GiveItem(ItemId::CopperPickaxe, 1)]);
+        assert!(!gift.fits(&inventory, &registry));
+
+        let trade = choice(vec![
+            DialogueEffect::TakeItem(ItemId::CopperOre, 5),
+            DialogueEffect::GiveItem(ItemId::CopperPickaxe, 1),
+        ]);
+        assert!(trade.fits(&inventory, &registry));
+        assert_eq!(inventory.count_item(ItemId::CopperOre), 5);
+    }
+
+    #[test]
+    fn shipped_dialogue_is_valid() {
+        let loc = crate::i18n::Localization::default();
+        for entry in std::fs::read_dir("assets/data/dialogue").unwrap() {
+            let path = entry.unwrap().path();
+            let text = std::fs::read_to_string(&path).unwrap();
+            let tree: DialogueTree = ron::from_str(&text)
+                .unwrap_or_else(|e| panic!("{} failed to parse: {e}", path.display()));
+            assert_eq!(tree.validate(), Ok(()), "{}", path.display());
+
+            let keys = std::iter::once(&tree.name).chain(tree.nodes.values().flat_map(|node| {
+                std::iter::once(&node.text).chain(node.choices.iter().map(|c| &c.text))
+            }));
+            for key in keys {
+                assert_ne!(loc.get(key), key, "{} uses missing key {key}", path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Adaptive difficulty. Recent floors are rated by whether the player died and how quickly
+//! they cleared them, and the rating nudges mob stats and counts within the band the player
+//! picked.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Floors the rating looks back over.
+pub const RECENT_FLOORS: usize = 5;
+
+/// Clears at least this quick count as the player having an easy time.
+const FAST_CLEAR: Duration = Duration::from_secs(45);
+
+/// Clears at least this slow count as a struggle.
+const SLOW_CLEAR: Duration = Duration::from_secs(180);
+
+/// How far adaptive difficulty may move mob scaling away from normal.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum AdaptiveDifficultyBand {
+    /// Mobs always spawn as designed.
+    #[default]
+    Off,
+    /// Up to 10% easier or harder.
+    Gentle,
+    /// Up to 25% easier or harder.
+    Wide,
+}
+
+impl AdaptiveDifficultyBand {
+    pub const ALL: [AdaptiveDifficultyBand; 3] = [
+        AdaptiveDifficultyBand::Off,
+        AdaptiveDifficultyBand::Gentle,
+        AdaptiveDifficultyBand::Wide,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|b| *b == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the band's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            AdaptiveDifficultyBand::Off => "difficulty.off",
+            AdaptiveDifficultyBand::Gentle => "difficulty.gentle",
+            AdaptiveDifficultyBand::Wide => "difficulty.wide",
+        }
+    }
+
+    /// Largest share mob scaling can move by in either direction.
+    pub fn reach(self) -> f32 {
+        match self {
+            AdaptiveDifficultyBand::Off => 0.0,
+            AdaptiveDifficultyBand::Gentle => 0.10,
+            AdaptiveDifficultyBand::Wide => 0.25,
+        }
+    }
+
+    /// The multiplier for a performance `rating` between -1 (struggling) and 1 (cruising).
+    pub fn scale(self, rating: f32) -> f32 {
+        1.0 + rating.clamp(-1.0, 1.0) * self.reach()
+    }
+}
+
+/// How a floor went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloorOutcome {
+    Cleared(Duration),
+    Died,
+}
+
+impl FloorOutcome {
+    /// This floor's share of the rating: dying and slow clears pull it down, fast clears
+    /// push it up.
+    fn score(self) -> f32 {
+        match self {
+            FloorOutcome::Died => -1.0,
+            FloorOutcome::Cleared(time) if time <= FAST_CLEAR => 1.0,
+            FloorOutcome::Cleared(time) if time >= SLOW_CLEAR => -0.5,
+            FloorOutcome::Cleared(_) => 0.0,
+        }
+    }
+}
+
+/// Recent floor outcomes and the scale mobs on the current floor were spawned at.
+#[derive(Resource, Debug)]
+pub struct AdaptiveDifficulty {
+    recent: VecDeque<FloorOutcome>,
+    /// When the current floor was entered, on the virtual clock.
+    pub floor_started: Option<Duration>,
+    floor_scale: f32,
+}
+
+impl Default for AdaptiveDifficulty {
+    fn default() -> Self {
+        Self {
+            recent: VecDeque::new(),
+            floor_started: None,
+            floor_scale: 1.0,
+        }
+    }
+}
+
+impl AdaptiveDifficulty {
+    pub fn record(&mut self, outcome: FloorOutcome) {
+        self.recent.push_back(outcome);
+        if self.recent.len() > RECENT_FLOORS {
+            self.recent.pop_front();
+        }
+    }
+
+    /// The average score of recent floors, from -1 to 1, or 0 before any are recorded.
+    pub fn rating(&self) -> f32 {
+        if self.recent.is_empty() {
+            return 0.0;
+        }
+        self.recent
+            .iter()
+            .map(|outcome| outcome.score())
+            .sum::<f32>()
+            / self.recent.len() as f32
+    }
+
+    /// Fixes the scale for the floor about to spawn, and returns it.
+    pub fn start_floor(&mut self, band: AdaptiveDifficultyBand, now: Duration) -> f32 {
+        self.floor_started = Some(now);
+        self.floor_scale = band.scale(self.rating());
+        self.floor_scale
+    }
+
+    /// Multiplier applied to mobs on the current floor.
+    pub fn floor_scale(&self) -> f32 {
+        self.floor_scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_clears_raise_the_rating_and_deaths_lower_it() {
+        let mut difficulty = AdaptiveDifficulty::default();
+        assert_eq!(difficulty.rating(), 0.0);
+
+        difficulty.record(FloorOutcome::Cleared(Duration::from_secs(20)));
+        difficulty.record(FloorOutcome::Cleared(Duration::from_secs(30)));
+        assert_eq!(difficulty.rating(), 1.0);
+
+        difficulty.record(FloorOutcome::Died);
+        difficulty.record(FloorOutcome::Cleared(Duration::from_secs(300)));
+        assert_eq!(difficulty.rating(), 0.125);
+    }
+
+    #[test]
+    fn only_recent_floors_count() {
+        let mut difficulty = AdaptiveDifficulty::default();
+        difficulty.record(FloorOutcome::Died);
+        for _ in 0..RECENT_FLOORS {
+            difficulty.record(FloorOutcome::Cleared(Duration::from_secs(10)));
+        }
+        assert_eq!(difficulty.rating(), 1.0);
+    }
+
+    #[test]
+    fn scaling_stays_inside_the_band() {
+        assert_eq!(AdaptiveDifficultyBand::Off.scale(1.0), 1.0);
+        assert_eq!(AdaptiveDifficultyBand::Gentle.scale(1.0), 1.1);
+        assert_eq!(AdaptiveDifficultyBand::Wide.scale(-5.0), 0.75);
+
+        let mut difficulty = AdaptiveDifficulty::default();
+        difficulty.record(FloorOutcome::Died);
+        let scale = difficulty.start_floor(AdaptiveDifficultyBand::Gentle, Duration::ZERO);
+        assert_eq!(scale, 0.9);
+        assert_eq!(difficulty.floor_scale(), 0.9);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod adaptive;
+pub mod plugin;
+
+pub use adaptive::{AdaptiveDifficulty, AdaptiveDifficultyBand, FloorOutcome, RECENT_FLOORS};
+pub use plugin::DifficultyPlugin;
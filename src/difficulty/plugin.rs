@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::combat::EntityDied;
+use crate::dungeon::{FloorTransition, SpawnTable};
+use crate::settings::GameSettings;
+
+use super::adaptive::{AdaptiveDifficulty, FloorOutcome};
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AdaptiveDifficulty>().add_systems(
+            Update,
+            (
+                record_floor_clears.run_if(on_message::<FloorTransition>),
+                record_player_deaths.run_if(on_message::<EntityDied>),
+                start_adaptive_floor.run_if(resource_exists_and_changed::<SpawnTable>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Times each floor the player takes the stairs out of.
+fn record_floor_clears(
+    mut transitions: MessageReader<FloorTransition>,
+    mut difficulty: ResMut<AdaptiveDifficulty>,
+    time: Res<Time>,
+) {
+    for transition in transitions.read() {
+        if !matches!(transition, FloorTransition::AdvanceFloor) {
+            continue;
+        }
+        if let Some(started) = difficulty.floor_started.take() {
+            difficulty.record(FloorOutcome::Cleared(
+                time.elapsed().saturating_sub(started),
+            ));
+        }
+    }
+}
+
+fn record_player_deaths(
+    mut deaths: MessageReader<EntityDied>,
+    mut difficulty: ResMut<AdaptiveDifficulty>,
+) {
+    for death in deaths.read() {
+        if death.is_player {
+            difficulty.record(FloorOutcome::Died);
+            difficulty.floor_started = None;
+        }
+    }
+}
+
+/// Settles the scale for the floor about to spawn and thins or fills out its mobs to
+/// match. Change detection is bypassed so the table isn't scaled again next frame.
+fn start_adaptive_floor(
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+    mut difficulty: ResMut<AdaptiveDifficulty>,
+    mut table: ResMut<SpawnTable>,
+) {
+    let scale = difficulty.start_floor(settings.adaptive_difficulty, time.elapsed());
+    if scale != 1.0 {
+        table.bypass_change_detection().scale_mob_count(scale);
+    }
+}
@@ -3,11 +3,15 @@ use super::floor::FloorId;
 #[derive(Debug, Clone)]
 pub struct DungeonConfig {
     floors: Vec<FloorId>,
+    tiered: bool,
 }
 
 impl DungeonConfig {
     pub fn new(floors: Vec<FloorId>) -> Self {
-        Self { floors }
+        Self {
+            floors,
+            tiered: false,
+        }
     }
 
     pub fn floors(&self) -> &[FloorId] {
@@ -17,4 +21,13 @@ impl DungeonConfig {
     pub fn floor_count(&self) -> usize {
         self.floors.len()
     }
+
+    /// Whether the selected [`DungeonTier`](super::DungeonTier) applies here.
+    pub fn is_tiered(&self) -> bool {
+        self.tiered
+    }
+
+    pub fn set_tiered(&mut self, tiered: bool) {
+        self.tiered = tiered;
+    }
 }
@@ -6,6 +6,14 @@ pub const DEFAULT_TILE_SIZE: f32 = 32.0;
 /// Multiplier for interaction radius relative to tile size.
 pub const INTERACTION_RADIUS_MULTIPLIER: f32 = 0.3;
 pub const CHEST_SPRITE_NAME: &str = "Slice_1";
+/// Herb patches are drawn with the herb item's icon.
+pub const HERB_SPRITE_NAME: &str = "Slice_82";
+/// Shrines are drawn as a large crystal from the crafting materials sheet.
+pub const SHRINE_SPRITE_NAME: &str = "diamond_large";
+/// Nests are drawn as a meteorite rock from the crafting materials sheet, tinted.
+pub const NEST_SPRITE_NAME: &str = "meteorite_rock";
+/// Training dummies are drawn as a log from the crafting materials sheet.
+pub const DUMMY_SPRITE_NAME: &str = "log";
 
 /// Configuration for creating entity colliders from sprite dimensions.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -7,6 +7,7 @@ use crate::rock::RockType;
 
 use super::grid::EntitySize;
 use super::physics::{mob_layers, static_entity_layers, trigger_layers};
+use super::systems::NestTimer;
 
 #[derive(Component)]
 pub struct DungeonEntityMarker {
@@ -29,12 +30,37 @@ pub struct RockEntity {
 #[require(RigidBody::Static, Sensor, CollisionLayers = trigger_layers())]
 pub struct StairsEntity;
 
+/// A patch of wild herbs the player can walk over and gather.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+#[require(RigidBody::Static, Sensor, CollisionLayers = trigger_layers())]
+pub struct HerbEntity;
+
+/// A shrine the player can make one offering at: gold for a blessing, a prayer, or a
+/// cleansing.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+#[require(RigidBody::Static, Sensor, CollisionLayers = trigger_layers())]
+pub struct ShrineEntity;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
 #[require(RigidBody::Kinematic, CollisionLayers = mob_layers())]
 pub struct MobEntity {
     pub mob_id: MobId,
 }
 
+/// A nest that keeps hatching mobs of `mob_id` until the player destroys it. It sits on
+/// the mob layer so attacks can land on it.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(RigidBody::Static, CollisionLayers = mob_layers(), NestTimer)]
+pub struct NestEntity {
+    pub mob_id: MobId,
+}
+
+/// A training dummy in town for testing damage. It sits on the mob layer so attacks land
+/// on it, but it has no health, so it never dies.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+#[require(RigidBody::Static, CollisionLayers = mob_layers())]
+pub struct TrainingDummyEntity;
+
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
 #[require(RigidBody::Kinematic, CollisionLayers = mob_layers())]
 pub struct NpcEntity {
@@ -42,6 +42,17 @@ pub struct MerchantInteraction {
     pub entity: Entity,
 }
 
+#[derive(Event, Debug, Clone)]
+pub struct TravelingMerchantInteraction {
+    pub entity: Entity,
+}
+
+/// The player cut a captive loose.
+#[derive(Event, Debug, Clone)]
+pub struct CaptiveFreed {
+    pub entity: Entity,
+}
+
 #[derive(Event, Debug, Clone)]
 pub struct ChestMined {
     pub entity: Entity,
@@ -55,10 +66,17 @@ pub struct RockMined {
     pub rock_type: RockType,
 }
 
+#[derive(Event, Debug, Clone)]
+pub struct HerbGathered {
+    pub entity: Entity,
+    pub pos: Vec2,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MineableEntityType {
     Chest,
     Rock { rock_type: RockType },
+    Herb,
 }
 
 #[derive(Message, Debug, Clone)]
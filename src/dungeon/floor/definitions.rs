@@ -1,28 +1,37 @@
 use crate::dungeon::spawn::SpawnTable;
+use crate::dungeon::tier::DEEP_TILE_DEPTH;
+use crate::location::LocationId;
 use crate::mob::MobId;
 
 entity_macros::define_data! {
     spec FloorSpec {
         pub name: &'static str,
         pub path: &'static str,
+        // How far below town the floor sits; 0 for towns
+        pub depth: u32,
         pub spawn_table: SpawnTable,
     }
 
     id FloorId;
 
+    overridable;
+
     variants {
         HomeFloor {
             name: "Home",
             path: "maps/home_floor.tmx",
+            depth: 0,
             spawn_table: SpawnTable::new()
                 .npc(MobId::Merchant, 1..=1)
                 .forge(1..=1)
                 .anvil(1..=1)
+                .dummy(1..=1)
                 .build(),
         }
         MainDungeon1 {
             name: "Dungeon - Floor 1",
             path: "maps/cave_floor.tmx",
+            depth: 1,
             spawn_table: SpawnTable::new()
                 .mob(MobId::Goblin, 5)
                 .mob(MobId::Slime, 3)
@@ -35,12 +44,15 @@ entity_macros::define_data! {
                 .forge_chance(0.33)
                 .anvil_chance(0.33)
                 .npc_chance(MobId::Merchant, 0.33)
+                .npc_chance(MobId::TravelingMerchant, 0.15)
+                .npc_chance(MobId::Captive, 0.25)
                 .stairs(1..=1)
                 .build(),
         }
         MainDungeon2 {
             name: "Dungeon - Floor 2",
             path: "maps/cave_floor.tmx",
+            depth: 2,
             spawn_table: SpawnTable::new()
                 .mob(MobId::Goblin, 5)
                 .mob(MobId::Slime, 3)
@@ -50,28 +62,192 @@ entity_macros::define_data! {
                 .guaranteed_mob(MobId::DwarfMiner, 1)
                 .guaranteed_mob(MobId::DwarfKing, 1)
                 .rock(0..=4)
+                .shrine(0..=1)
                 .forge_chance(0.33)
                 .anvil_chance(0.33)
                 .npc_chance(MobId::Merchant, 0.33)
+                .npc_chance(MobId::TravelingMerchant, 0.15)
+                .npc_chance(MobId::Captive, 0.25)
                 .stairs(1..=1)
                 .build(),
         }
         MainDungeon3 {
             name: "Dungeon - Floor 3",
             path: "maps/cave_floor.tmx",
+            depth: 3,
             spawn_table: SpawnTable::new()
                 .mob(MobId::Goblin, 5)
                 .mob(MobId::Slime, 3)
                 .mob_count(3..=4)
+                .nest(MobId::Slime, 1)
+                .nests(0..=1)
                 .guaranteed_mob(MobId::DwarfDefender, 1)
                 .guaranteed_mob(MobId::DwarfWarrior, 1)
                 .guaranteed_mob(MobId::DwarfMiner, 1)
                 .guaranteed_mob(MobId::DwarfKing, 1)
                 .rock(0..=4)
+                .shrine(0..=1)
                 .forge_chance(0.33)
                 .anvil_chance(0.33)
                 .npc_chance(MobId::Merchant, 0.33)
+                .npc_chance(MobId::TravelingMerchant, 0.15)
+                .npc_chance(MobId::Captive, 0.25)
+                .build(),
+        }
+        OutpostFloor {
+            name: "Frostpeak Outpost",
+            path: "maps/home_floor.tmx",
+            depth: 0,
+            spawn_table: SpawnTable::new()
+                .npc(MobId::Merchant, 1..=1)
+                .forge(1..=1)
+                .anvil(1..=1)
+                .dummy(1..=1)
+                .build(),
+        }
+        DeepMine1 {
+            name: "Deep Mine - Floor 1",
+            path: "maps/cave_floor.tmx",
+            depth: 4,
+            spawn_table: SpawnTable::new()
+                .mob(MobId::DwarfMiner, 5)
+                .mob(MobId::DwarfWarrior, 3)
+                .mob(MobId::Goblin, 2)
+                .mob_count(4..=5)
+                .guaranteed_mob(MobId::DwarfDefender, 1)
+                .rock(3..=6)
+                .shrine(0..=1)
+                .forge_chance(0.5)
+                .anvil_chance(0.5)
+                .npc_chance(MobId::TravelingMerchant, 0.25)
+                .npc_chance(MobId::Captive, 0.25)
+                .stairs(1..=1)
+                .build(),
+        }
+        DeepMine2 {
+            name: "Deep Mine - Floor 2",
+            path: "maps/cave_floor.tmx",
+            depth: 5,
+            spawn_table: SpawnTable::new()
+                .mob(MobId::DwarfWarrior, 4)
+                .mob(MobId::DwarfDefender, 3)
+                .mob(MobId::DwarfMiner, 3)
+                .mob_count(5..=6)
+                .nest(MobId::DwarfMiner, 1)
+                .nests(0..=1)
+                .guaranteed_mob(MobId::DwarfKing, 1)
+                .rock(3..=6)
+                .shrine(0..=1)
+                .npc_chance(MobId::Merchant, 0.5)
+                .npc_chance(MobId::TravelingMerchant, 0.25)
+                .npc_chance(MobId::Captive, 0.25)
+                .stairs(1..=1)
+                .build(),
+        }
+        ColossusLair {
+            name: "Deep Mine - Colossus Lair",
+            path: "maps/cave_floor.tmx",
+            depth: 6,
+            spawn_table: SpawnTable::new()
+                .guaranteed_mob(MobId::Colossus, 1)
+                .guaranteed_mob(MobId::DwarfDefender, 2)
+                .chest(1..=1)
+                .build(),
+        }
+        VillageField {
+            name: "Village Field",
+            path: "maps/cave_floor.tmx",
+            depth: 1,
+            spawn_table: SpawnTable::new()
+                .field_mobs(LocationId::VillageField)
+                .mob_count(5..=7)
+                .roaming(true)
+                .herb(3..=5)
+                .stairs(1..=1)
+                .build(),
+        }
+        RoadAmbush {
+            name: "Caravan Road - Ambush",
+            path: "maps/cave_floor.tmx",
+            depth: 1,
+            spawn_table: SpawnTable::new()
+                .mob(MobId::Goblin, 5)
+                .mob(MobId::Slime, 2)
+                .mob_count(3..=4)
+                .chest(0..=1)
+                .stairs(1..=1)
+                .build(),
+        }
+        RoadWarband {
+            name: "Caravan Road - Warband",
+            path: "maps/cave_floor.tmx",
+            depth: 2,
+            spawn_table: SpawnTable::new()
+                .mob(MobId::Goblin, 3)
+                .mob(MobId::DwarfWarrior, 2)
+                .mob_count(4..=5)
+                .guaranteed_mob(MobId::DwarfDefender, 1)
+                .chest(1..=1)
+                .stairs(1..=1)
                 .build(),
         }
+        EndlessDepths {
+            name: "Endless Depths",
+            path: "maps/cave_floor.tmx",
+            depth: 1,
+            spawn_table: SpawnTable::new()
+                .mob(MobId::Goblin, 4)
+                .mob(MobId::Slime, 3)
+                .mob(MobId::DwarfMiner, 2)
+                .mob(MobId::DwarfWarrior, 2)
+                .mob(MobId::DwarfDefender, 1)
+                .mob_count(4..=6)
+                .nest(MobId::Goblin, 2)
+                .nest(MobId::Slime, 1)
+                .nests(0..=1)
+                .rock(1..=4)
+                .shrine(0..=1)
+                .chest(0..=1)
+                .npc_chance(MobId::TravelingMerchant, 0.1)
+                .stairs(1..=1)
+                .build(),
+        }
+        ChallengeVault {
+            name: "Challenge Vault",
+            path: "maps/cave_floor.tmx",
+            depth: 1,
+            spawn_table: SpawnTable::new()
+                .chest(1..=1)
+                .stairs(1..=1)
+                .build(),
+        }
+    }
+}
+
+const CAVE_FLOOR_PATH: &str = "maps/cave_floor.tmx";
+const DEEP_CAVE_FLOOR_PATH: &str = "maps/cave_floor_deep.tmx";
+
+impl FloorSpec {
+    /// The map to load when the floor is run at `depth`. Cave floors that deep switch to
+    /// the cracked, bone-strewn deep cave tiles.
+    pub fn map_path(&self, depth: u32) -> &'static str {
+        if self.path == CAVE_FLOOR_PATH && depth >= DEEP_TILE_DEPTH {
+            DEEP_CAVE_FLOOR_PATH
+        } else {
+            self.path
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deep_cave_floors_use_the_deep_tiles() {
+        assert_eq!(FloorId::MainDungeon1.spec().map_path(1), CAVE_FLOOR_PATH);
+        assert_eq!(FloorId::MainDungeon1.spec().map_path(4), DEEP_CAVE_FLOOR_PATH);
+        assert_eq!(FloorId::DeepMine1.spec().map_path(4), DEEP_CAVE_FLOOR_PATH);
+        assert_eq!(FloorId::HomeFloor.spec().map_path(9), "maps/home_floor.tmx");
     }
 }
@@ -0,0 +1,114 @@
+//! Events a single dungeon floor can roll on entry. Unlike run modifiers they last only
+//! for the floor they were rolled on.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::dungeon::spawn::SpawnTable;
+
+/// Chance that a dungeon floor rolls an event at all.
+pub const FLOOR_EVENT_CHANCE: f64 = 0.15;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FloorEvent {
+    /// Twice the rocks, and every rock gives twice the ore.
+    GoldRush,
+    /// Mobs hit harder, but drop better loot.
+    Cursed,
+    /// Half again as many mobs, with slightly better loot.
+    Swarming,
+}
+
+impl FloorEvent {
+    pub const ALL: [FloorEvent; 3] = [
+        FloorEvent::GoldRush,
+        FloorEvent::Cursed,
+        FloorEvent::Swarming,
+    ];
+
+    /// Localization key for the event's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            FloorEvent::GoldRush => "floor_event.gold_rush",
+            FloorEvent::Cursed => "floor_event.cursed",
+            FloorEvent::Swarming => "floor_event.swarming",
+        }
+    }
+
+    /// Localization key for the line explaining what the event does.
+    pub fn description_key(self) -> &'static str {
+        match self {
+            FloorEvent::GoldRush => "floor_event.gold_rush_desc",
+            FloorEvent::Cursed => "floor_event.cursed_desc",
+            FloorEvent::Swarming => "floor_event.swarming_desc",
+        }
+    }
+
+    /// What the quantity of ore mined from rocks is multiplied by.
+    pub fn ore_multiplier(self) -> i32 {
+        match self {
+            FloorEvent::GoldRush => 2,
+            _ => 1,
+        }
+    }
+
+    /// What mob attack is multiplied by, on top of every other stat scaling.
+    pub fn attack_multiplier(self) -> f32 {
+        match self {
+            FloorEvent::Cursed => 1.3,
+            _ => 1.0,
+        }
+    }
+
+    /// Magic Find added to loot rolls.
+    pub fn magic_find(self) -> i32 {
+        match self {
+            FloorEvent::Cursed => 30,
+            FloorEvent::Swarming => 10,
+            _ => 0,
+        }
+    }
+
+    /// Adjusts the floor's spawn table before anything is spawned from it.
+    pub fn apply_to_spawn_table(self, table: &mut SpawnTable) {
+        match self {
+            FloorEvent::GoldRush => table.scale_rock_count(2.0),
+            FloorEvent::Swarming => table.scale_mob_count(1.5),
+            FloorEvent::Cursed => {}
+        }
+    }
+
+    /// Rolls whether a floor gets an event, and which.
+    pub fn roll(rng: &mut impl Rng) -> Option<FloorEvent> {
+        if !rng.gen_bool(FLOOR_EVENT_CHANCE) {
+            return None;
+        }
+        Self::ALL.choose(rng).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn events_turn_up_now_and_then() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let rolled = (0..1000).filter_map(|_| FloorEvent::roll(&mut rng)).count();
+        assert!((100..200).contains(&rolled), "rolled {rolled} events");
+    }
+
+    #[test]
+    fn gold_rush_doubles_rocks() {
+        let mut table = SpawnTable::new().rock(2..=3).mob_count(4..=4).build();
+        FloorEvent::GoldRush.apply_to_spawn_table(&mut table);
+        assert_eq!(*table.rock(), 4..=6);
+        assert_eq!(*table.mob_count(), 4..=4);
+
+        FloorEvent::Swarming.apply_to_spawn_table(&mut table);
+        assert_eq!(*table.mob_count(), 6..=6);
+    }
+}
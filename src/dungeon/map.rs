@@ -0,0 +1,253 @@
+//! Loads each floor's Tiled map.
+//!
+//! With a renderer, bevy_ecs_tiled builds the tilemap and its wall colliders. Its tilemap
+//! plugin needs the render sub-app, so runs without one (headless tests) and builds without
+//! the `gui` feature read the map file directly and spawn only what the game plays on: wall
+//! colliders and the tile positions.
+//! Either way [`FloorMapReady`] reports the floor's tiles in world space.
+
+use std::path::Path;
+
+use ::tiled::{Loader, Properties, PropertyValue};
+use avian2d::prelude::{Collider, RigidBody};
+use bevy::prelude::*;
+use tracing::instrument;
+
+use crate::dungeon::plugin::TiledWallCollider;
+use crate::dungeon::state::TilemapInfo;
+
+pub struct FloorMapPlugin;
+
+impl Plugin for FloorMapPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "gui")]
+        if app.get_sub_app(bevy::render::RenderApp).is_some() {
+            tilemap::build(app);
+            return;
+        }
+        app.add_observer(load_map_file);
+    }
+}
+
+/// Loads the map at `path`, relative to the assets folder, under `floor_root`.
+#[derive(Event, Debug, Clone)]
+pub struct LoadFloorMap {
+    pub path: &'static str,
+    pub floor_root: Entity,
+}
+
+/// A floor's map has loaded.
+#[derive(Event, Debug, Clone)]
+pub struct FloorMapReady {
+    pub info: TilemapInfo,
+    /// Centers of the tiles entities may be placed on.
+    pub spawn_tiles: Vec<Vec2>,
+    /// Centers of the door tiles.
+    pub door_tiles: Vec<Vec2>,
+}
+
+#[cfg(feature = "gui")]
+mod tilemap {
+    use avian2d::prelude::{Collider, RigidBody, Sensor};
+    use bevy::prelude::*;
+    use bevy_ecs_tiled::prelude::*;
+    use tracing::{debug, instrument};
+
+    use crate::dungeon::plugin::TiledWallCollider;
+    use crate::dungeon::state::TilemapInfo;
+    use crate::dungeon::tile_components::{can_have_entity, is_door};
+
+    use super::{FloorMapReady, LoadFloorMap};
+
+    pub(super) fn build(app: &mut App) {
+        app.add_plugins((
+            TiledPlugin::default(),
+            TiledPhysicsPlugin::<TiledPhysicsAvianBackend>::default(),
+        ))
+        .add_observer(load_tiled_map)
+        .add_observer(on_collider_created);
+    }
+
+    fn load_tiled_map(
+        trigger: On<LoadFloorMap>,
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+    ) {
+        let map_handle: Handle<TiledMapAsset> = asset_server.load(trigger.path);
+        commands
+            .spawn((TiledMap(map_handle), ChildOf(trigger.floor_root)))
+            .observe(on_tiled_map_created);
+    }
+
+    type TilemapQuery<'w, 's> = Query<
+        'w,
+        's,
+        (
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static TilemapAnchor,
+            &'static GlobalTransform,
+        ),
+        With<TiledTilemap>,
+    >;
+
+    #[instrument(level = "debug", skip_all)]
+    fn on_tiled_map_created(
+        _trigger: On<TiledEvent<MapCreated>>,
+        mut commands: Commands,
+        spawn_tiles: Query<(&TilePos, &can_have_entity)>,
+        door_tiles: Query<(&TilePos, &is_door)>,
+        tilemap_query: TilemapQuery,
+    ) {
+        let Ok((map_size, grid_size, tile_size, map_type, anchor, transform)) =
+            tilemap_query.single()
+        else {
+            return;
+        };
+
+        let tile_to_world = |pos: &TilePos| {
+            let local = pos.center_in_world(map_size, grid_size, tile_size, map_type, anchor);
+            transform.transform_point(local.extend(0.0)).truncate()
+        };
+
+        let tile = Vec2::new(tile_size.x, tile_size.y);
+        let world_size = Vec2::new(map_size.x as f32, map_size.y as f32) * tile;
+        let center = transform
+            .transform_point((world_size / 2.0).extend(0.0))
+            .truncate();
+
+        commands.trigger(FloorMapReady {
+            info: TilemapInfo {
+                tile_size: tile,
+                world_size,
+                center,
+            },
+            spawn_tiles: spawn_tiles
+                .iter()
+                .filter(|(_, can_spawn)| can_spawn.0)
+                .map(|(pos, _)| tile_to_world(pos))
+                .collect(),
+            door_tiles: door_tiles
+                .iter()
+                .map(|(pos, _)| tile_to_world(pos))
+                .collect(),
+        });
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    fn on_collider_created(
+        trigger: On<TiledEvent<ColliderCreated>>,
+        mut commands: Commands,
+        parent_query: Query<&ChildOf>,
+        door_query: Query<&is_door>,
+        collider_query: Query<(&Collider, &GlobalTransform)>,
+    ) {
+        let collider_entity = trigger.event().origin;
+
+        if let Ok((collider, transform)) = collider_query.get(collider_entity) {
+            let pos = transform.translation();
+            let shape = collider.shape_scaled();
+            let aabb = shape.compute_local_aabb();
+            debug!(
+                entity = ?collider_entity,
+                pos_x = pos.x,
+                pos_y = pos.y,
+                aabb_min_x = aabb.mins.x,
+                aabb_min_y = aabb.mins.y,
+                aabb_max_x = aabb.maxs.x,
+                aabb_max_y = aabb.maxs.y,
+                "wall collider created"
+            );
+        }
+
+        if let Ok(child_of) = parent_query.get(collider_entity) {
+            if door_query.get(child_of.parent()).is_ok() {
+                commands
+                    .entity(collider_entity)
+                    .insert((Sensor, is_door(true)));
+                return;
+            }
+        }
+
+        commands
+            .entity(collider_entity)
+            .insert((RigidBody::Static, TiledWallCollider));
+    }
+}
+
+/// Reads the map file and spawns a static collider per solid tile, laid out the way
+/// bevy_ecs_tiled places tiles: the bottom-left corner at the floor root, one row per tile
+/// height up.
+#[instrument(level = "debug", skip_all, fields(path = %trigger.path))]
+fn load_map_file(trigger: On<LoadFloorMap>, mut commands: Commands) {
+    let map = match Loader::new().load_tmx_map(Path::new("assets").join(trigger.path)) {
+        Ok(map) => map,
+        Err(e) => {
+            warn!("Failed to load floor map {}: {e}", trigger.path);
+            return;
+        }
+    };
+
+    let tile_size = Vec2::new(map.tile_width as f32, map.tile_height as f32);
+    let world_size = Vec2::new(map.width as f32, map.height as f32) * tile_size;
+    let mut spawn_tiles = Vec::new();
+    let mut door_tiles = Vec::new();
+
+    for layer in map.layers() {
+        let offset = Vec2::new(layer.offset_x, -layer.offset_y);
+        let Some(tiles) = layer.as_tile_layer() else {
+            continue;
+        };
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let Some(tile) = tiles
+                    .get_tile(x as i32, y as i32)
+                    .and_then(|layer_tile| layer_tile.get_tile())
+                else {
+                    continue;
+                };
+                // Tiled counts rows down from the top, the world counts up.
+                let row = (map.height - 1 - y) as f32;
+                let pos = offset + (Vec2::new(x as f32, row) + 0.5) * tile_size;
+
+                if has_flag(&tile.properties, "is_solid") {
+                    commands.spawn((
+                        Collider::rectangle(tile_size.x, tile_size.y),
+                        RigidBody::Static,
+                        TiledWallCollider,
+                        Transform::from_translation(pos.extend(0.0)),
+                        ChildOf(trigger.floor_root),
+                    ));
+                }
+                if has_flag(&tile.properties, "can_have_entity") {
+                    spawn_tiles.push(pos);
+                }
+                if has_flag(&tile.properties, "is_door") {
+                    door_tiles.push(pos);
+                }
+            }
+        }
+    }
+
+    commands.trigger(FloorMapReady {
+        info: TilemapInfo {
+            tile_size,
+            world_size,
+            center: world_size / 2.0,
+        },
+        spawn_tiles,
+        door_tiles,
+    });
+}
+
+/// Whether a tile property from `tile_components` is set. Tiled stores each as a class with
+/// one bool member named `0`.
+fn has_flag(properties: &Properties, name: &str) -> bool {
+    matches!(
+        properties.get(name),
+        Some(PropertyValue::ClassValue { properties, .. })
+            if matches!(properties.get("0"), Some(PropertyValue::BoolValue(true)))
+    )
+}
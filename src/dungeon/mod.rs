@@ -4,37 +4,54 @@ pub mod constants;
 pub mod entity;
 pub mod events;
 pub mod floor;
+pub mod floor_event;
 pub mod grid;
+pub mod map;
+pub mod modifier;
 pub mod physics;
 pub mod plugin;
+pub mod spatial;
 pub mod spawn;
 pub mod state;
 pub mod systems;
+pub mod tier;
 pub mod tile;
 pub mod tile_components;
 
 pub use commands::DungeonCommands;
 pub use config::DungeonConfig;
 pub use entity::{
-    ChestEntity, CraftingStationEntity, DoorEntity, DungeonEntityMarker, MobEntity, NpcEntity,
-    RockEntity, StairsEntity,
+    ChestEntity, CraftingStationEntity, DoorEntity, DungeonEntityMarker, HerbEntity, MobEntity,
+    NestEntity, NpcEntity, RockEntity, ShrineEntity, StairsEntity, TrainingDummyEntity,
 };
 pub use floor::{FloorId, FloorSpec};
+pub use floor_event::FloorEvent;
 pub use constants::{ColliderConfig, FORGE_COLLIDER, MOB_COLLIDER, PLAYER_COLLIDER, STAIRS_COLLIDER, STATIC_COLLIDER};
 pub use grid::EntitySize;
-pub use physics::{attack_hitbox_layers, GameLayer};
+pub use map::{FloorMapPlugin, FloorMapReady, LoadFloorMap};
+pub use modifier::ChallengeModifier;
+pub use physics::{
+    attack_hitbox_layers, player_projectile_layers, projectile_layers, GameLayer,
+};
 pub use plugin::{DungeonBuilder, DungeonPlugin, DungeonRegistry, FloorMonsterCount, HasLocation, NoLocation, TiledWallCollider};
+pub use spatial::SpatialGrid;
 pub use spawn::{MobSpawnEntry, SpawnEntityType, SpawnEntry, SpawnTable};
-pub use state::{DepthSorting, DungeonState, MovementConfig, TileWorldSize, TilemapInfo};
+pub use state::{
+    DepthSorting, DungeonState, FloorSeed, FloorSeedQueue, MovementConfig, SeededFloors,
+    TileWorldSize, TilemapInfo,
+};
 pub use systems::{
     detect_nearby_interactables, handle_floor_transition, handle_mob_defeated,
-    handle_player_collisions, handle_player_move, on_map_created, prepare_floor,
-    stop_attacking_player, stop_player_when_idle, SpawnFloor,
+    handle_player_collisions, handle_player_move, on_floor_map_ready, prepare_floor, roam_mobs,
+    stop_attacking_player, stop_player_when_idle, Following, NestBrood, NestBundle, Roaming,
+    SpawnFloor,
 };
+pub use tier::DungeonTier;
 pub use tile::{Tile, TileType};
 pub use tile_components::{can_have_entity, can_spawn_player, is_door, is_solid};
 
 pub use events::{
-    ChestMined, CraftingStationInteraction, FloorReady, FloorTransition, InteractableNearby,
-    MerchantInteraction, MineableEntityType, MiningResult, MoveResult, PlayerMoveIntent, RockMined,
+    CaptiveFreed, ChestMined, CraftingStationInteraction, FloorReady, FloorTransition,
+    HerbGathered, InteractableNearby, MerchantInteraction, MineableEntityType, MiningResult,
+    MoveResult, PlayerMoveIntent, RockMined, TravelingMerchantInteraction,
 };
@@ -0,0 +1,87 @@
+//! Modifiers a whole run can be played under. They stack on top of the floor's depth and
+//! the dungeon tier through the same depth, mob stat and Magic Find hooks.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ChallengeModifier {
+    /// Mobs hit harder and take more to bring down.
+    Fortified,
+    /// Floors run deeper than they are.
+    Abyssal,
+    /// Better loot on every roll.
+    Bountiful,
+    /// Mobs are a little tougher and drop a little more.
+    Frenzied,
+}
+
+impl ChallengeModifier {
+    pub const ALL: [ChallengeModifier; 4] = [
+        ChallengeModifier::Fortified,
+        ChallengeModifier::Abyssal,
+        ChallengeModifier::Bountiful,
+        ChallengeModifier::Frenzied,
+    ];
+
+    /// Localization key for the modifier's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            ChallengeModifier::Fortified => "modifier.fortified",
+            ChallengeModifier::Abyssal => "modifier.abyssal",
+            ChallengeModifier::Bountiful => "modifier.bountiful",
+            ChallengeModifier::Frenzied => "modifier.frenzied",
+        }
+    }
+
+    /// Levels of depth added to every floor.
+    pub fn depth_bonus(self) -> u32 {
+        match self {
+            ChallengeModifier::Abyssal => 3,
+            _ => 0,
+        }
+    }
+
+    /// What mob stats are multiplied by, on top of depth scaling.
+    pub fn stat_multiplier(self) -> f32 {
+        match self {
+            ChallengeModifier::Fortified => 1.25,
+            ChallengeModifier::Frenzied => 1.15,
+            _ => 1.0,
+        }
+    }
+
+    /// Magic Find added to loot rolls.
+    pub fn magic_find(self) -> i32 {
+        match self {
+            ChallengeModifier::Bountiful => 25,
+            ChallengeModifier::Frenzied => 10,
+            _ => 0,
+        }
+    }
+
+    /// Picks `count` different modifiers.
+    pub fn roll(count: usize, rng: &mut impl Rng) -> Vec<ChallengeModifier> {
+        Self::ALL.choose_multiple(rng, count).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn rolled_modifiers_never_repeat() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let modifiers = ChallengeModifier::roll(ChallengeModifier::ALL.len(), &mut rng);
+        assert_eq!(modifiers.len(), ChallengeModifier::ALL.len());
+        assert!(
+            ChallengeModifier::ALL
+                .iter()
+                .all(|modifier| modifiers.contains(modifier))
+        );
+    }
+}
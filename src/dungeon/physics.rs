@@ -9,6 +9,8 @@ pub enum GameLayer {
     StaticEntity,
     Trigger,
     AttackHitbox,
+    Projectile,
+    PlayerProjectile,
 }
 
 /// Collision layers for static entities (chests, rocks, crafting stations)
@@ -23,9 +25,32 @@ pub fn trigger_layers() -> CollisionLayers {
 
 /// Collision layers for mobs and NPCs
 pub fn mob_layers() -> CollisionLayers {
-    CollisionLayers::new(GameLayer::Mob, [GameLayer::Player, GameLayer::AttackHitbox])
+    CollisionLayers::new(
+        GameLayer::Mob,
+        [
+            GameLayer::Player,
+            GameLayer::AttackHitbox,
+            GameLayer::PlayerProjectile,
+        ],
+    )
 }
 
 pub fn attack_hitbox_layers() -> CollisionLayers {
     CollisionLayers::new(GameLayer::AttackHitbox, [GameLayer::Mob])
 }
+
+/// Collision layers for mob projectiles, which stop at walls and the player.
+pub fn projectile_layers() -> CollisionLayers {
+    CollisionLayers::new(
+        GameLayer::Projectile,
+        [GameLayer::Default, GameLayer::Player],
+    )
+}
+
+/// Collision layers for the player's arrows and thrown flasks, which stop at walls and mobs.
+pub fn player_projectile_layers() -> CollisionLayers {
+    CollisionLayers::new(
+        GameLayer::PlayerProjectile,
+        [GameLayer::Default, GameLayer::Mob],
+    )
+}
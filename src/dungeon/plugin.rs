@@ -1,9 +1,7 @@
 use std::collections::HashMap;
 
-use avian2d::prelude::{Collider, CollisionStart, Gravity, PhysicsPlugins, RigidBody, Sensor};
+use avian2d::prelude::{CollisionStart, Gravity, PhysicsPlugins};
 use bevy::prelude::*;
-use bevy_ecs_tiled::prelude::{ColliderCreated, TiledEvent, TiledPhysicsAvianBackend, TiledPhysicsPlugin};
-use tracing::{debug, instrument};
 
 use crate::dungeon::config::DungeonConfig;
 use crate::dungeon::events::{
@@ -12,14 +10,18 @@ use crate::dungeon::events::{
 };
 use crate::plugins::MobDefeated;
 use crate::dungeon::floor::FloorId;
+use crate::dungeon::map::FloorMapPlugin;
+use crate::dungeon::spatial::{remove_from_spatial_grid, update_spatial_grid, SpatialGrid};
 use crate::dungeon::state::{DungeonState, MovementConfig, TileWorldSize};
-use crate::combat::Attacking;
+use crate::combat::{Attacking, Knockback};
 use crate::dungeon::systems::{
-    cleanup_mob_health_bar, detect_nearby_interactables, handle_floor_transition,
-    handle_mob_defeated, handle_player_collisions, handle_player_move, prepare_floor,
-    spawn_mob_health_bars, stop_attacking_player, stop_player_when_idle,
-    update_mob_health_bar_positions, update_mob_health_bar_values, SpawnFloor,
+    apply_knockback, apply_mob_tactics, cleanup_mob_health_bar, detect_nearby_interactables, follow_player,
+    handle_floor_transition, handle_mob_defeated, handle_player_collisions, handle_player_move,
+    hatch_nests, on_floor_map_ready, prepare_floor, roam_mobs, spawn_generated_floor, spawn_mob_health_bars, stop_attacking_player, stop_player_when_idle,
+    Following, MobHealthBarPool, MobTactics, PendingFloor, Roaming, update_mob_health_bar_positions,
+    update_mob_health_bar_values, SpawnFloor,
 };
+use crate::dungeon::entity::NestEntity;
 use crate::dungeon::tile_components::{can_have_entity, can_spawn_player, is_door, is_solid};
 use crate::location::LocationId;
 use crate::states::AppState;
@@ -69,7 +71,7 @@ pub struct DungeonPlugin {
 impl Plugin for DungeonPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PhysicsPlugins::default().with_length_unit(TileWorldSize::default().0))
-            .add_plugins(TiledPhysicsPlugin::<TiledPhysicsAvianBackend>::default())
+            .add_plugins(FloorMapPlugin)
             .insert_resource(Gravity::ZERO)
             .register_type::<is_solid>()
             .register_type::<can_have_entity>()
@@ -80,6 +82,8 @@ impl Plugin for DungeonPlugin {
             .init_resource::<TileWorldSize>()
             .init_resource::<MovementConfig>()
             .init_resource::<InteractableNearby>()
+            .init_resource::<MobHealthBarPool>()
+            .init_resource::<SpatialGrid>()
             .add_message::<FloorTransition>()
             .add_message::<FloorReady>()
             .add_message::<SpawnFloor>()
@@ -87,11 +91,14 @@ impl Plugin for DungeonPlugin {
             .add_message::<MoveResult>()
             .add_message::<CraftingStationInteraction>()
             .add_message::<MiningResult>()
-            .add_observer(on_collider_created)
+            .add_observer(on_floor_map_ready)
             .add_observer(cleanup_mob_health_bar)
+            .add_observer(remove_from_spatial_grid)
             .add_systems(
                 First,
-                detect_nearby_interactables.run_if(in_state(AppState::Dungeon)),
+                (update_spatial_grid, detect_nearby_interactables)
+                    .chain()
+                    .run_if(in_state(AppState::Dungeon)),
             )
             .add_systems(
                 FixedPreUpdate,
@@ -101,6 +108,18 @@ impl Plugin for DungeonPlugin {
                         .run_if(not(any_with_component::<Attacking>)),
                     stop_player_when_idle.run_if(not(any_with_component::<Attacking>)),
                     stop_attacking_player.run_if(any_with_component::<Attacking>),
+                    roam_mobs.run_if(any_with_component::<Roaming>),
+                    apply_mob_tactics
+                        .after(roam_mobs)
+                        .run_if(any_with_component::<MobTactics>),
+                    follow_player.run_if(any_with_component::<Following>),
+                    apply_knockback
+                        .after(handle_player_move)
+                        .after(stop_player_when_idle)
+                        .after(stop_attacking_player)
+                        .after(apply_mob_tactics)
+                        .after(follow_player)
+                        .run_if(any_with_component::<Knockback>),
                 )
                     .run_if(in_state(AppState::Dungeon)),
             )
@@ -108,9 +127,11 @@ impl Plugin for DungeonPlugin {
                 Update,
                 (
                     prepare_floor.run_if(on_message::<SpawnFloor>),
+                    spawn_generated_floor.run_if(resource_exists::<PendingFloor>),
                     handle_player_collisions.run_if(on_message::<CollisionStart>),
                     handle_floor_transition.run_if(on_message::<FloorTransition>),
                     handle_mob_defeated.run_if(on_message::<MobDefeated>),
+                    hatch_nests.run_if(any_with_component::<NestEntity>),
                     spawn_mob_health_bars,
                     update_mob_health_bar_positions,
                     update_mob_health_bar_values,
@@ -173,7 +194,18 @@ impl DungeonBuilder<HasLocation> {
         if let Some(config) = self.configs.get_mut(&location) {
             let mut floors = config.floors().to_vec();
             floors.push(floor);
+            let tiered = config.is_tiered();
             *config = DungeonConfig::new(floors);
+            config.set_tiered(tiered);
+        }
+        self
+    }
+
+    /// Lets the player pick a [`DungeonTier`](crate::dungeon::DungeonTier) for the location.
+    pub fn tiered(mut self) -> Self {
+        let location = self.state.0;
+        if let Some(config) = self.configs.get_mut(&location) {
+            config.set_tiered(true);
         }
         self
     }
@@ -186,39 +218,3 @@ impl DungeonBuilder<HasLocation> {
         }
     }
 }
-
-#[instrument(level = "debug", skip_all)]
-fn on_collider_created(
-    trigger: On<TiledEvent<ColliderCreated>>,
-    mut commands: Commands,
-    parent_query: Query<&ChildOf>,
-    door_query: Query<&is_door>,
-    collider_query: Query<(&Collider, &GlobalTransform)>,
-) {
-    let collider_entity = trigger.event().origin;
-
-    if let Ok((collider, transform)) = collider_query.get(collider_entity) {
-        let pos = transform.translation();
-        let shape = collider.shape_scaled();
-        let aabb = shape.compute_local_aabb();
-        debug!(
-            entity = ?collider_entity,
-            pos_x = pos.x,
-            pos_y = pos.y,
-            aabb_min_x = aabb.mins.x,
-            aabb_min_y = aabb.mins.y,
-            aabb_max_x = aabb.maxs.x,
-            aabb_max_y = aabb.maxs.y,
-            "wall collider created"
-        );
-    }
-
-    if let Ok(child_of) = parent_query.get(collider_entity) {
-        if door_query.get(child_of.parent()).is_ok() {
-            commands.entity(collider_entity).insert((Sensor, is_door(true)));
-            return;
-        }
-    }
-
-    commands.entity(collider_entity).insert((RigidBody::Static, TiledWallCollider));
-}
@@ -0,0 +1,146 @@
+//! A uniform grid over the floor for finding the entities near a point.
+//!
+//! Every [`DungeonEntityMarker`] entity is filed under the grid cell its [`Position`] falls
+//! in, and moved to another cell when it moves. Looking up what is near the player then only
+//! touches the few cells the search circle overlaps instead of every entity on the floor.
+
+use std::collections::HashMap;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonEntityMarker;
+
+/// Side of a grid cell in world units: a few tiles, so a lookup around the player touches
+/// a handful of cells.
+pub const SPATIAL_CELL_SIZE: f32 = 128.0;
+
+/// Where each floor entity is, bucketed by cell.
+#[derive(Resource, Debug)]
+pub struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Entity>>,
+    positions: HashMap<Entity, Vec2>,
+}
+
+impl Default for SpatialGrid {
+    fn default() -> Self {
+        Self::new(SPATIAL_CELL_SIZE)
+    }
+}
+
+impl SpatialGrid {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Files `entity` at `pos`, moving it if it was already in the grid.
+    pub fn insert(&mut self, entity: Entity, pos: Vec2) {
+        let cell = self.cell(pos);
+        if let Some(old) = self.positions.insert(entity, pos) {
+            let old_cell = self.cell(old);
+            if old_cell == cell {
+                return;
+            }
+            self.remove_from_cell(old_cell, entity);
+        }
+        self.cells.entry(cell).or_default().push(entity);
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(pos) = self.positions.remove(&entity) {
+            self.remove_from_cell(self.cell(pos), entity);
+        }
+    }
+
+    fn remove_from_cell(&mut self, cell: IVec2, entity: Entity) {
+        let Some(entities) = self.cells.get_mut(&cell) else {
+            return;
+        };
+        if let Some(index) = entities.iter().position(|&e| e == entity) {
+            entities.swap_remove(index);
+        }
+        if entities.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+
+    /// Every entity within `radius` of `center`, with its position.
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = self.cell(center - Vec2::splat(radius));
+        let max = self.cell(center + Vec2::splat(radius));
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|&entity| (entity, self.positions[&entity]))
+            .filter(move |(_, pos)| pos.distance(center) <= radius)
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// Floor entities whose position changed, including ones just spawned.
+type MovedOnFloor = (With<DungeonEntityMarker>, Changed<Position>);
+
+/// Files floor entities that were spawned or moved since the last run.
+pub fn update_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    moved: Query<(Entity, &Position), MovedOnFloor>,
+) {
+    for (entity, position) in &moved {
+        grid.insert(entity, position.0);
+    }
+}
+
+pub fn remove_from_spatial_grid(
+    trigger: On<Remove, DungeonEntityMarker>,
+    mut grid: ResMut<SpatialGrid>,
+) {
+    grid.remove(trigger.event_target());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_entities_across_cells_and_follows_moves() {
+        let mut world = World::new();
+        let [near, far, mover] = [(); 3].map(|_| world.spawn_empty().id());
+        let mut grid = SpatialGrid::new(16.0);
+        grid.insert(near, Vec2::new(-3.0, 2.0));
+        grid.insert(far, Vec2::new(40.0, 0.0));
+        grid.insert(mover, Vec2::new(100.0, 100.0));
+
+        let found = |grid: &SpatialGrid| {
+            let mut found: Vec<Entity> = grid.within(Vec2::ZERO, 10.0).map(|(e, _)| e).collect();
+            found.sort();
+            found
+        };
+        assert_eq!(found(&grid), [near]);
+
+        grid.insert(mover, Vec2::new(5.0, -5.0));
+        let mut expected = vec![near, mover];
+        expected.sort();
+        assert_eq!(found(&grid), expected);
+
+        grid.remove(near);
+        assert_eq!(found(&grid), [mover]);
+        assert_eq!(grid.len(), 2);
+    }
+}
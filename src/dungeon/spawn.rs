@@ -2,9 +2,12 @@ use std::ops::RangeInclusive;
 
 use bevy::prelude::*;
 use bon::Builder;
+use randr_core::floor::FloorRules;
 
 use super::grid::EntitySize;
+use crate::location::{LocationData, LocationId};
 use crate::mob::MobId;
+use crate::rock::RockType;
 
 #[derive(Debug, Clone)]
 pub struct MobSpawnEntry {
@@ -15,6 +18,8 @@ pub struct MobSpawnEntry {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpawnEntityType {
     Mob(MobId),
+    /// A nest that keeps producing mobs of this kind until it is destroyed.
+    Nest(MobId),
 }
 
 #[derive(Debug, Clone)]
@@ -44,11 +49,22 @@ pub struct SpawnTable {
     #[builder(default = 0..=0)]
     rock: RangeInclusive<u32>,
     #[builder(default = 0..=0)]
+    herb: RangeInclusive<u32>,
+    #[builder(default = 0..=0)]
+    shrine: RangeInclusive<u32>,
+    #[builder(default = 0..=0)]
+    nests: RangeInclusive<u32>,
+    #[builder(default = 0..=0)]
+    dummy: RangeInclusive<u32>,
+    #[builder(default = 0..=0)]
     forge: RangeInclusive<u32>,
     #[builder(default = 0..=0)]
     anvil: RangeInclusive<u32>,
     forge_chance: Option<f64>,
     anvil_chance: Option<f64>,
+    /// Whether weighted mobs wander around their spawn point instead of standing still.
+    #[builder(default)]
+    roaming: bool,
 }
 
 use spawn_table_builder::State;
@@ -64,6 +80,27 @@ impl<S: State> SpawnTableBuilder<S> {
         self
     }
 
+    /// Adds a kind of nest, picked by `weight` among the table's nests.
+    pub fn nest(mut self, mob_id: MobId, weight: u32) -> Self {
+        let size = mob_id.spec().entity_size;
+        self.entries.push(SpawnEntry {
+            entity_type: SpawnEntityType::Nest(mob_id),
+            weight,
+            size,
+        });
+        self
+    }
+
+    /// Adds the roaming mobs of a field location with their weights.
+    pub fn field_mobs(mut self, location: LocationId) -> Self {
+        if let LocationData::Field(field) = &location.spec().data {
+            for (mob_id, weight) in field.weighted_mobs() {
+                self = self.mob(mob_id, weight);
+            }
+        }
+        self
+    }
+
     pub fn guaranteed_mob(mut self, mob_id: MobId, count: u32) -> Self {
         self.guaranteed_mobs.push((mob_id, count));
         self
@@ -92,16 +129,80 @@ impl SpawnTable {
     pub fn weighted_mobs(&self) -> Vec<MobSpawnEntry> {
         self.entries
             .iter()
-            .map(|e| {
-                let SpawnEntityType::Mob(mob_id) = e.entity_type;
-                MobSpawnEntry {
+            .filter_map(|e| match e.entity_type {
+                SpawnEntityType::Mob(mob_id) => Some(MobSpawnEntry {
                     mob_id,
                     weight: e.weight,
-                }
+                }),
+                SpawnEntityType::Nest(_) => None,
             })
             .collect()
     }
 
+    /// The nests this table can place, with the mob each one produces.
+    pub fn weighted_nests(&self) -> Vec<MobSpawnEntry> {
+        self.entries
+            .iter()
+            .filter_map(|e| match e.entity_type {
+                SpawnEntityType::Nest(mob_id) => Some(MobSpawnEntry {
+                    mob_id,
+                    weight: e.weight,
+                }),
+                SpawnEntityType::Mob(_) => None,
+            })
+            .collect()
+    }
+
+    /// Makes `mob_id` spawn `multiplier` times as often without thinning out other mobs:
+    /// its weight and guaranteed count are scaled, and the mob count grows to match.
+    pub fn boost_mob(&mut self, mob_id: MobId, multiplier: u32) {
+        let old_total: u32 = self.weighted_mobs().iter().map(|e| e.weight).sum();
+        for entry in &mut self.entries {
+            if entry.entity_type == SpawnEntityType::Mob(mob_id) {
+                entry.weight *= multiplier;
+            }
+        }
+        for (id, count) in &mut self.guaranteed_mobs {
+            if *id == mob_id {
+                *count *= multiplier;
+            }
+        }
+
+        let new_total: u32 = self.weighted_mobs().iter().map(|e| e.weight).sum();
+        if old_total > 0 && new_total != old_total {
+            let scale = new_total as f32 / old_total as f32;
+            let (start, end) = (*self.mob_count.start(), *self.mob_count.end());
+            self.mob_count =
+                (start as f32 * scale).round() as u32..=(end as f32 * scale).round() as u32;
+        }
+    }
+
+    /// Swaps the weighted mobs for `mobs`, keeping nests and every other spawn as they are.
+    pub fn replace_mobs(&mut self, mobs: impl IntoIterator<Item = (MobId, u32)>) {
+        self.entries
+            .retain(|e| !matches!(e.entity_type, SpawnEntityType::Mob(_)));
+        self.entries
+            .extend(mobs.into_iter().map(|(mob_id, weight)| SpawnEntry {
+                entity_type: SpawnEntityType::Mob(mob_id),
+                weight,
+                size: mob_id.spec().entity_size,
+            }));
+    }
+
+    pub fn set_mob_count(&mut self, mob_count: RangeInclusive<u32>) {
+        self.mob_count = mob_count;
+    }
+
+    /// Scales how many weighted mobs spawn by `factor`, leaving guaranteed mobs alone.
+    pub fn scale_mob_count(&mut self, factor: f32) {
+        self.mob_count = scale_range(&self.mob_count, factor);
+    }
+
+    /// Scales how many rocks spawn by `factor`.
+    pub fn scale_rock_count(&mut self, factor: f32) {
+        self.rock = scale_range(&self.rock, factor);
+    }
+
     pub fn mob_count(&self) -> &RangeInclusive<u32> {
         &self.mob_count
     }
@@ -130,6 +231,26 @@ impl SpawnTable {
         &self.rock
     }
 
+    pub fn herb(&self) -> &RangeInclusive<u32> {
+        &self.herb
+    }
+
+    pub fn shrine(&self) -> &RangeInclusive<u32> {
+        &self.shrine
+    }
+
+    pub fn nests(&self) -> &RangeInclusive<u32> {
+        &self.nests
+    }
+
+    pub fn dummy(&self) -> &RangeInclusive<u32> {
+        &self.dummy
+    }
+
+    pub fn roaming(&self) -> bool {
+        self.roaming
+    }
+
     pub fn forge(&self) -> &RangeInclusive<u32> {
         &self.forge
     }
@@ -145,6 +266,39 @@ impl SpawnTable {
     pub fn anvil_chance(&self) -> Option<f64> {
         self.anvil_chance
     }
+
+    /// The table as the floor generator reads it.
+    pub fn rules(&self) -> FloorRules<MobId> {
+        let weights = |entries: Vec<MobSpawnEntry>| {
+            entries.into_iter().map(|e| (e.mob_id, e.weight)).collect()
+        };
+        FloorRules {
+            chests: self.chest.clone(),
+            stairs: self.stairs.clone(),
+            rocks: self.rock.clone(),
+            rock_kinds: RockType::ALL.len(),
+            rock_variants: RockType::SPRITE_VARIANT_COUNT,
+            herbs: self.herb.clone(),
+            shrines: self.shrine.clone(),
+            dummies: self.dummy.clone(),
+            forges: self.forge.clone(),
+            forge_chance: self.forge_chance,
+            anvils: self.anvil.clone(),
+            anvil_chance: self.anvil_chance,
+            npcs: self.npc_spawns.clone(),
+            npc_chances: self.npc_chances.clone(),
+            guaranteed_mobs: self.guaranteed_mobs.clone(),
+            mobs: weights(self.weighted_mobs()),
+            mob_count: self.mob_count.clone(),
+            roaming: self.roaming,
+            nests: weights(self.weighted_nests()),
+            nest_count: self.nests.clone(),
+        }
+    }
+}
+
+fn scale_range(range: &RangeInclusive<u32>, factor: f32) -> RangeInclusive<u32> {
+    (*range.start() as f32 * factor).round() as u32..=(*range.end() as f32 * factor).round() as u32
 }
 
 #[cfg(test)]
@@ -247,5 +401,72 @@ mod tests {
         let table = SpawnTable::new().rock(3..=3).build();
         assert_eq!(*table.rock(), 3..=3);
     }
-}
 
+    #[test]
+    fn herbs_and_roaming() {
+        let table = SpawnTable::new().herb(2..=4).roaming(true).build();
+        assert_eq!(*table.herb(), 2..=4);
+        assert!(table.roaming());
+        assert!(!SpawnTable::empty().build().roaming());
+    }
+
+    #[test]
+    fn shrine_range() {
+        let table = SpawnTable::new().shrine(0..=1).build();
+        assert_eq!(*table.shrine(), 0..=1);
+        assert_eq!(*SpawnTable::empty().build().shrine(), 0..=0);
+    }
+
+    #[test]
+    fn nests_are_kept_apart_from_weighted_mobs() {
+        init();
+        let table = SpawnTable::new()
+            .mob(MobId::Goblin, 5)
+            .nest(MobId::Slime, 2)
+            .nests(0..=1)
+            .build();
+
+        let mobs = table.weighted_mobs();
+        assert_eq!(mobs.len(), 1);
+        assert_eq!(mobs[0].mob_id, MobId::Goblin);
+        let nests = table.weighted_nests();
+        assert_eq!(nests.len(), 1);
+        assert_eq!(nests[0].mob_id, MobId::Slime);
+        assert_eq!(nests[0].weight, 2);
+        assert_eq!(*table.nests(), 0..=1);
+        assert_eq!(*SpawnTable::empty().build().nests(), 0..=0);
+    }
+
+    #[test]
+    fn boost_mob_multiplies_only_that_mob() {
+        init();
+        let mut table = SpawnTable::new()
+            .mob(MobId::Goblin, 5)
+            .mob(MobId::Slime, 3)
+            .mob_count(3..=4)
+            .guaranteed_mob(MobId::Goblin, 1)
+            .guaranteed_mob(MobId::DwarfKing, 1)
+            .build();
+
+        table.boost_mob(MobId::Goblin, 2);
+
+        let mobs = table.weighted_mobs();
+        assert_eq!(mobs[0].weight, 10);
+        assert_eq!(mobs[1].weight, 3);
+        assert_eq!(*table.mob_count(), 5..=7);
+        assert_eq!(table.guaranteed_mobs(), [(MobId::Goblin, 2), (MobId::DwarfKing, 1)]);
+    }
+
+    #[test]
+    fn scale_mob_count_rounds_both_ends() {
+        let mut table = SpawnTable::new()
+            .mob_count(4..=6)
+            .guaranteed_mob(MobId::DwarfKing, 1)
+            .build();
+
+        table.scale_mob_count(1.25);
+
+        assert_eq!(*table.mob_count(), 5..=8);
+        assert_eq!(table.guaranteed_mobs(), [(MobId::DwarfKing, 1)]);
+    }
+}
@@ -1,7 +1,14 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use crate::dungeon::floor::FloorId;
+use crate::dungeon::floor_event::FloorEvent;
+use crate::dungeon::modifier::ChallengeModifier;
 use crate::dungeon::spawn::SpawnTable;
+use crate::dungeon::tier::{depth_magic_find, depth_stat_multiplier, DungeonTier};
 use crate::dungeon::DungeonRegistry;
 use crate::location::LocationId;
 
@@ -14,6 +21,44 @@ impl Default for TileWorldSize {
     }
 }
 
+/// Seed the current floor's entity spawns were rolled from.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FloorSeed(pub u64);
+
+/// Seeds to use for the next floors instead of rolling fresh ones, front first.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct FloorSeedQueue(pub VecDeque<u64>);
+
+/// Floor seeds for a whole run, drawn in order from one run seed so the same seed always
+/// gives the same floors.
+#[derive(Clone, Debug)]
+pub struct SeededFloors {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl SeededFloors {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn next_floor_seed(&mut self) -> u64 {
+        self.rng.r#gen()
+    }
+
+    /// Queues the seed for the next floor to spawn.
+    pub fn queue_next(&mut self, queue: &mut FloorSeedQueue) {
+        queue.0.push_back(self.next_floor_seed());
+    }
+}
+
 #[derive(Resource, Clone, Copy, Debug)]
 pub struct MovementConfig {
     pub tiles_per_second: f32,
@@ -78,6 +123,18 @@ pub struct DungeonState {
     pub floor_sequence: Vec<FloorId>,
     sequence_location: Option<LocationId>,
     pub dungeon_cleared: bool,
+    /// The tier picked in town for tiered dungeons.
+    pub tier: DungeonTier,
+    // The tier the current location is being run at
+    active_tier: DungeonTier,
+    // Whether floors keep coming for as long as the player takes the stairs
+    endless: bool,
+    // Whether each floor is run a level deeper than the one before
+    descending: bool,
+    // Whether the floors were laid out for this visit instead of taken from the registry
+    route: bool,
+    modifiers: Vec<ChallengeModifier>,
+    floor_event: Option<FloorEvent>,
 }
 
 impl DungeonState {
@@ -88,6 +145,13 @@ impl DungeonState {
 
         self.current_location = Some(location);
         self.floor_index = 0;
+        self.active_tier = if config.is_tiered() {
+            self.tier
+        } else {
+            DungeonTier::Normal
+        };
+        self.endless = false;
+        self.route = false;
 
         let different_location = self.sequence_location != Some(location);
         let should_generate = different_location || self.floor_sequence.is_empty() || self.dungeon_cleared;
@@ -99,18 +163,127 @@ impl DungeonState {
         }
     }
 
+    /// Enters a location whose floors are generated per visit, such as a caravan route,
+    /// instead of registered in the [`DungeonRegistry`].
+    pub fn enter_route(&mut self, location: LocationId, floors: Vec<FloorId>) {
+        self.current_location = Some(location);
+        self.floor_index = 0;
+        self.dungeon_cleared = false;
+        self.sequence_location = Some(location);
+        self.floor_sequence = floors;
+        self.route = true;
+        self.active_tier = DungeonTier::Normal;
+        self.endless = false;
+        self.descending = false;
+        self.modifiers.clear();
+    }
+
+    /// Enters a location that repeats `floor` without end, each one a level deeper than
+    /// the last.
+    pub fn enter_endless(&mut self, location: LocationId, floor: FloorId) {
+        self.enter_route(location, vec![floor]);
+        self.endless = true;
+        self.descending = true;
+    }
+
+    /// Enters a fixed run of `floors`, each a level deeper than the last, played under
+    /// `modifiers`.
+    pub fn enter_challenge(
+        &mut self,
+        location: LocationId,
+        floors: Vec<FloorId>,
+        modifiers: Vec<ChallengeModifier>,
+    ) {
+        self.enter_route(location, floors);
+        self.descending = true;
+        self.modifiers = modifiers;
+    }
+
+    /// Modifiers the current run is played under.
+    pub fn modifiers(&self) -> &[ChallengeModifier] {
+        &self.modifiers
+    }
+
+    /// The event rolled for the current floor, if it got one.
+    pub fn floor_event(&self) -> Option<FloorEvent> {
+        self.floor_event
+    }
+
+    pub fn set_floor_event(&mut self, event: Option<FloorEvent>) {
+        self.floor_event = event;
+    }
+
+    pub fn is_endless(&self) -> bool {
+        self.endless && self.current_location.is_some()
+    }
+
+    /// Whether the player is on a caravan route, endless run or challenge rather than in a
+    /// registered dungeon.
+    pub fn is_on_route(&self) -> bool {
+        self.route && self.current_location.is_some()
+    }
+
+    /// Whether the player is on an endless run or challenge.
+    pub fn is_descending(&self) -> bool {
+        self.descending && self.current_location.is_some()
+    }
+
     pub fn current_floor(&self) -> Option<FloorId> {
         self.floor_sequence.get(self.floor_index).copied()
     }
 
-    pub fn advance_floor(&mut self, registry: &DungeonRegistry) -> Option<FloorId> {
-        let location = self.current_location?;
-        let config = registry.config(location)?;
-        let floor_count = config.floor_count();
+    /// How deep the current floor is being run, counting the active tier's bonus and any
+    /// run modifiers. Town floors stay at 0 whatever the tier.
+    pub fn depth(&self) -> u32 {
+        let Some(floor) = self.current_floor() else {
+            return 0;
+        };
+        let depth = if self.descending {
+            let first = self.floor_sequence.first().map_or(0, |first| first.spec().depth);
+            first + self.floor_index as u32
+        } else {
+            match floor.spec().depth {
+                0 => return 0,
+                depth => depth + self.active_tier.depth_bonus(),
+            }
+        };
+        depth + self.modifiers.iter().map(|m| m.depth_bonus()).sum::<u32>()
+    }
+
+    /// What mob stats on the current floor are multiplied by.
+    pub fn mob_stat_multiplier(&self) -> f32 {
+        self.modifiers
+            .iter()
+            .fold(depth_stat_multiplier(self.depth()), |multiplier, m| {
+                multiplier * m.stat_multiplier()
+            })
+    }
+
+    /// What mob attack on the current floor is multiplied by, on top of
+    /// [`Self::mob_stat_multiplier`].
+    pub fn mob_attack_multiplier(&self) -> f32 {
+        self.floor_event.map_or(1.0, FloorEvent::attack_multiplier)
+    }
+
+    /// Magic Find the current floor adds to loot rolls.
+    pub fn loot_magic_find(&self) -> i32 {
+        depth_magic_find(self.depth())
+            + self.modifiers.iter().map(|m| m.magic_find()).sum::<i32>()
+            + self.floor_event.map_or(0, FloorEvent::magic_find)
+    }
+
+    pub fn advance_floor(&mut self) -> Option<FloorId> {
+        self.current_location?;
 
         self.floor_index += 1;
 
-        if self.floor_index >= floor_count {
+        if self.endless {
+            let floor = *self.floor_sequence.last()?;
+            self.floor_sequence.push(floor);
+            return Some(floor);
+        }
+
+        if self.floor_index >= self.floor_sequence.len() {
             self.dungeon_cleared = true;
             None
         } else {
@@ -118,19 +291,16 @@ impl DungeonState {
         }
     }
 
-    pub fn is_current_floor_final(&self, registry: &DungeonRegistry) -> bool {
-        let Some(location) = self.current_location else {
-            return false;
-        };
-        let Some(config) = registry.config(location) else {
-            return false;
-        };
-        self.floor_index == config.floor_count().saturating_sub(1)
+    pub fn is_current_floor_final(&self) -> bool {
+        self.current_location.is_some()
+            && !self.endless
+            && self.floor_index == self.floor_sequence.len().saturating_sub(1)
     }
 
     pub fn exit_dungeon(&mut self) {
         self.current_location = None;
         self.floor_index = 0;
+        self.floor_event = None;
     }
 
     pub fn get_spawn_config(&self) -> Option<SpawnTable> {
@@ -147,5 +317,98 @@ impl DungeonState {
         self.floor_sequence.clear();
         self.sequence_location = None;
         self.dungeon_cleared = false;
+        self.endless = false;
+        self.descending = false;
+        self.route = false;
+        self.modifiers.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_seed_always_rolls_the_same_floors() {
+        let mut first = SeededFloors::new(42);
+        let mut second = SeededFloors::new(42);
+        let floors: Vec<u64> = (0..5).map(|_| first.next_floor_seed()).collect();
+        assert!(floors.iter().all(|&seed| seed == second.next_floor_seed()));
+        assert_ne!(floors[0], floors[1]);
+    }
+
+    #[test]
+    fn endless_floors_keep_coming_and_get_deeper() {
+        let mut state = DungeonState::default();
+        state.enter_endless(LocationId::EndlessDepths, FloorId::EndlessDepths);
+        assert!(state.is_endless());
+        assert_eq!(state.depth(), 1);
+
+        for _ in 0..5 {
+            assert!(!state.is_current_floor_final());
+            assert_eq!(state.advance_floor(), Some(FloorId::EndlessDepths));
+        }
+        assert_eq!(state.depth(), 6);
+        assert!(!state.dungeon_cleared);
+
+        state.reset_dungeon();
+        state.exit_dungeon();
+        assert!(!state.is_endless());
+    }
+
+    #[test]
+    fn challenge_modifiers_stack_on_depth() {
+        let mut state = DungeonState::default();
+        let floors = vec![FloorId::EndlessDepths; 3];
+        state.enter_challenge(LocationId::EndlessDepths, floors, vec![ChallengeModifier::Abyssal]);
+        assert_eq!(state.depth(), 4);
+        state.advance_floor();
+        assert_eq!(state.depth(), 5);
+
+        state.enter_challenge(
+            LocationId::EndlessDepths,
+            vec![FloorId::EndlessDepths],
+            vec![ChallengeModifier::Fortified, ChallengeModifier::Bountiful],
+        );
+        assert!((state.mob_stat_multiplier() - 1.25).abs() < 1e-6);
+        assert_eq!(state.loot_magic_find(), 25);
+        assert!(state.is_current_floor_final());
+    }
+
+    #[test]
+    fn floor_events_add_to_loot_and_attack() {
+        let mut state = DungeonState::default();
+        state.enter_endless(LocationId::EndlessDepths, FloorId::EndlessDepths);
+        let magic_find = state.loot_magic_find();
+        assert_eq!(state.mob_attack_multiplier(), 1.0);
+
+        state.set_floor_event(Some(FloorEvent::Cursed));
+        assert!((state.mob_attack_multiplier() - 1.3).abs() < 1e-6);
+        assert_eq!(state.loot_magic_find(), magic_find + 30);
+
+        state.exit_dungeon();
+        assert_eq!(state.floor_event(), None);
+    }
+
+    #[test]
+    fn only_routes_end_after_their_last_floor() {
+        let mut state = DungeonState::default();
+        state.enter_route(LocationId::CaravanRoad, vec![FloorId::VillageField; 2]);
+        assert!(state.is_on_route());
+        assert!(state.advance_floor().is_some());
+        assert_eq!(state.advance_floor(), None);
+        assert!(state.is_on_route());
+
+        state.reset_dungeon();
+        state.exit_dungeon();
+        assert!(!state.is_on_route());
+
+        let mut state = DungeonState {
+            current_location: Some(LocationId::MainDungeon),
+            floor_sequence: vec![FloorId::MainDungeon1],
+            ..default()
+        };
+        assert_eq!(state.advance_floor(), None);
+        assert!(!state.is_on_route());
     }
 }
@@ -2,7 +2,8 @@ use bevy::prelude::*;
 
 use crate::dungeon::events::FloorTransition;
 use crate::dungeon::plugin::FloorMonsterCount;
-use crate::dungeon::{DungeonRegistry, DungeonState};
+use crate::dungeon::DungeonState;
+use crate::location::{CombatSubtype, LocationType};
 use crate::plugins::MobDefeated;
 
 pub fn handle_mob_defeated(
@@ -10,14 +11,18 @@ pub fn handle_mob_defeated(
     mut transition_events: MessageWriter<FloorTransition>,
     mut count: ResMut<FloorMonsterCount>,
     state: Res<DungeonState>,
-    registry: Res<DungeonRegistry>,
 ) {
+    // Only dungeons send the player home once cleared; field routes end by moving on.
+    let in_dungeon = state.current_location.is_some_and(|location| {
+        location.location_type() == LocationType::Combat(CombatSubtype::Dungeon)
+    });
+
     for _ in events.read() {
         if count.0 > 0 {
             count.0 -= 1;
         }
 
-        if count.0 == 0 && state.is_current_floor_final(&registry) {
+        if in_dungeon && count.0 == 0 && state.is_current_floor_final() {
             transition_events.write(FloorTransition::ReturnToHome);
         }
     }
@@ -10,6 +10,8 @@ pub struct SpawnFloor {
     pub floor_id: FloorId,
 }
 
+/// Starts the floor. It stays in transition until its entities are spawned, which happens
+/// once the map has loaded and [`PendingFloor`](super::PendingFloor) has finished.
 #[instrument(level = "debug", skip_all)]
 pub fn prepare_floor(
     mut commands: Commands,
@@ -18,7 +20,7 @@ pub fn prepare_floor(
 ) {
     for event in events.read() {
         commands.insert_resource(FloorMonsterCount(0));
-        commands.remove_resource::<TransitionInProgress>();
+        commands.insert_resource(TransitionInProgress);
 
         floor_ready.write(FloorReady {
             floor_id: event.floor_id,
@@ -0,0 +1,68 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::dungeon::constants::DEFAULT_TILE_SIZE;
+use crate::dungeon::{MovementConfig, TileWorldSize};
+use crate::ui::DungeonPlayer;
+
+/// How close a follower gets before it stops to wait, in tiles.
+const FOLLOW_DISTANCE_TILES: f32 = 1.2;
+
+/// A follower's speed as a share of the player's, so it trails a little behind.
+const FOLLOW_SPEED_SHARE: f32 = 0.9;
+
+/// Makes an entity walk after the player, such as a captive freed from a dungeon floor.
+#[derive(Component, Debug, Default)]
+pub struct Following;
+
+/// The direction to walk in from `pos` to keep within `keep_distance` of `target`, or zero
+/// when already close enough.
+pub fn follow_heading(pos: Vec2, target: Vec2, keep_distance: f32) -> Vec2 {
+    let to_target = target - pos;
+    if to_target.length() <= keep_distance {
+        return Vec2::ZERO;
+    }
+    to_target.normalize()
+}
+
+type FollowerFilter = (With<Following>, Without<DungeonPlayer>);
+
+pub fn follow_player(
+    tile_size: Option<Res<TileWorldSize>>,
+    movement: Res<MovementConfig>,
+    player: Query<&Transform, With<DungeonPlayer>>,
+    mut followers: Query<(&Transform, &mut LinearVelocity), FollowerFilter>,
+) {
+    let Ok(player_transform) = player.single() else {
+        return;
+    };
+    let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+    let target = player_transform.translation.truncate();
+    let speed = movement.tiles_per_second * FOLLOW_SPEED_SHARE * tile_size;
+
+    for (transform, mut velocity) in &mut followers {
+        let heading = follow_heading(
+            transform.translation.truncate(),
+            target,
+            FOLLOW_DISTANCE_TILES * tile_size,
+        );
+        velocity.0 = heading * speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_toward_the_player_until_close() {
+        let player = Vec2::new(100.0, 0.0);
+
+        let heading = follow_heading(Vec2::ZERO, player, 32.0);
+        assert!((heading - Vec2::X).length() < 1e-4);
+        assert_eq!(
+            follow_heading(Vec2::new(80.0, 0.0), player, 32.0),
+            Vec2::ZERO
+        );
+    }
+}
@@ -3,43 +3,49 @@ use bevy::prelude::*;
 
 use crate::dungeon::constants::{DEFAULT_TILE_SIZE, INTERACTION_RADIUS_MULTIPLIER};
 use crate::dungeon::events::InteractableNearby;
+use crate::dungeon::spatial::SpatialGrid;
 use crate::dungeon::{
-    ChestEntity, CraftingStationEntity, GameLayer, NpcEntity, RockEntity, TileWorldSize,
+    ChestEntity, CraftingStationEntity, DungeonEntityMarker, HerbEntity, NpcEntity, RockEntity,
+    ShrineEntity, TileWorldSize,
 };
 use crate::ui::screens::DungeonPlayer;
 
+/// Picks the closest interactable whose body is within reach of the player, looking only at
+/// the grid cells around them.
 pub fn detect_nearby_interactables(
     mut nearby: ResMut<InteractableNearby>,
-    spatial_query: SpatialQuery,
+    grid: Res<SpatialGrid>,
     tile_size: Option<Res<TileWorldSize>>,
-    player_query: Query<(Entity, &Position), With<DungeonPlayer>>,
+    player_query: Query<&Position, With<DungeonPlayer>>,
     interactable_query: Query<
-        (),
+        &DungeonEntityMarker,
         Or<(
             With<CraftingStationEntity>,
             With<NpcEntity>,
             With<ChestEntity>,
             With<RockEntity>,
+            With<HerbEntity>,
+            With<ShrineEntity>,
         )>,
     >,
 ) {
-    let Ok((player_entity, &Position(player_pos))) = player_query.single() else {
+    let Ok(&Position(player_pos)) = player_query.single() else {
         nearby.0 = None;
         return;
     };
 
     let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
     let radius = tile_size * INTERACTION_RADIUS_MULTIPLIER;
-    let shape = Collider::circle(radius);
 
-    let filter =
-        SpatialQueryFilter::from_mask([GameLayer::StaticEntity, GameLayer::Mob, GameLayer::Trigger])
-            .with_excluded_entities([player_entity]);
-
-    let entities = spatial_query.shape_intersections(&shape, player_pos, 0.0, &filter);
-
-    nearby.0 = entities
-        .iter()
-        .find(|&&e| interactable_query.get(e).is_ok())
-        .copied();
+    // Entities can be bigger than a tile, so search wide and check each one's own size.
+    nearby.0 = grid
+        .within(player_pos, radius + tile_size)
+        .filter_map(|(entity, at)| {
+            let marker = interactable_query.get(entity).ok()?;
+            let half_size = marker.size.width.max(marker.size.height) / 2.0;
+            let distance = at.distance(player_pos);
+            (distance <= radius + half_size).then_some((entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
 }
@@ -1,21 +1,31 @@
 use bevy::prelude::*;
 
 use crate::assets::{GameSprites, HealthBarSlice, SpriteSheetKey};
-use crate::dungeon::MobEntity;
+use crate::dungeon::{MobEntity, NestEntity};
 use crate::mob::components::Health;
+use crate::utils::EntityPool;
 
 const MOB_HEALTH_BAR_OFFSET_Y: f32 = 5.0;
 
+/// Entities that show a health bar: mobs and the nests that hatch them.
+type HasHealthBar = Or<(With<MobEntity>, With<NestEntity>)>;
+
 #[derive(Component)]
 pub struct MobHealthBar(pub Entity);
 
 #[derive(Component)]
 pub struct MobHealthBarSprite;
 
+/// Bars of mobs that died, hidden until another mob needs one. Mobs die and spawn in waves,
+/// so reusing their bars saves spawning an entity per mob.
+pub type MobHealthBarPool = EntityPool<MobHealthBarSprite>;
+
 pub fn spawn_mob_health_bars(
     mut commands: Commands,
     game_sprites: Res<GameSprites>,
-    mobs: Query<(Entity, &Health), (With<MobEntity>, Without<MobHealthBar>)>,
+    mobs: Query<(Entity, &Health), (HasHealthBar, Without<MobHealthBar>)>,
+    mut pool: ResMut<MobHealthBarPool>,
+    mut parked: Query<(&mut Sprite, &mut Visibility), With<MobHealthBarSprite>>,
 ) {
     let Some(sheet) = game_sprites.get(SpriteSheetKey::UiAll) else {
         return;
@@ -29,21 +39,27 @@ pub fn spawn_mob_health_bars(
             continue;
         };
 
-        let health_bar = commands
-            .spawn((
-                MobHealthBarSprite,
-                sprite,
-                Transform::default(),
-            ))
-            .id();
+        let reused = pool
+            .acquire()
+            .and_then(|bar| Some((bar, parked.get_mut(bar).ok()?)));
+        let health_bar = match reused {
+            Some((bar, (mut bar_sprite, mut visibility))) => {
+                *bar_sprite = sprite;
+                *visibility = Visibility::Inherited;
+                bar
+            }
+            None => commands
+                .spawn((MobHealthBarSprite, sprite, Transform::default()))
+                .id(),
+        };
 
         commands.entity(mob_entity).insert(MobHealthBar(health_bar));
     }
 }
 
 pub fn update_mob_health_bar_positions(
-    mobs: Query<(&Transform, &MobHealthBar), With<MobEntity>>,
-    mut health_bars: Query<&mut Transform, (With<MobHealthBarSprite>, Without<MobEntity>)>,
+    mobs: Query<(&Transform, &MobHealthBar), HasHealthBar>,
+    mut health_bars: Query<&mut Transform, (With<MobHealthBarSprite>, Without<MobHealthBar>)>,
 ) {
     for (mob_transform, health_bar) in &mobs {
         let Ok(mut bar_transform) = health_bars.get_mut(health_bar.0) else {
@@ -57,7 +73,7 @@ pub fn update_mob_health_bar_positions(
 
 pub fn update_mob_health_bar_values(
     game_sprites: Res<GameSprites>,
-    mobs: Query<(&Health, &MobHealthBar), (With<MobEntity>, Changed<Health>)>,
+    mobs: Query<(&Health, &MobHealthBar), (HasHealthBar, Changed<Health>)>,
     mut health_bars: Query<&mut Sprite, With<MobHealthBarSprite>>,
 ) {
     let Some(sheet) = game_sprites.get(SpriteSheetKey::UiAll) else {
@@ -84,14 +100,20 @@ pub fn update_mob_health_bar_values(
     }
 }
 
+/// Hides a dead mob's bar and parks it for the next mob.
 pub fn cleanup_mob_health_bar(
-    trigger: On<Remove, MobEntity>,
+    trigger: On<Remove, MobHealthBar>,
     health_bars: Query<&MobHealthBar>,
-    mut commands: Commands,
+    mut bars: Query<&mut Visibility, With<MobHealthBarSprite>>,
+    mut pool: ResMut<MobHealthBarPool>,
 ) {
     let entity = trigger.event_target();
-    if let Ok(health_bar) = health_bars.get(entity) {
-        commands.entity(health_bar.0).despawn();
+    let Ok(health_bar) = health_bars.get(entity) else {
+        return;
+    };
+    if let Ok(mut visibility) = bars.get_mut(health_bar.0) {
+        *visibility = Visibility::Hidden;
+        pool.release(health_bar.0);
     }
 }
 
@@ -1,18 +1,29 @@
 mod combat;
 mod floor_spawn;
+mod following;
 mod interactable;
 mod mob_health_bar;
 mod movement;
+mod nest;
+mod roaming;
 pub mod spawning;
+mod tactics;
 mod transitions;
 
 pub use combat::handle_mob_defeated;
 pub use floor_spawn::{prepare_floor, SpawnFloor};
+pub use following::{follow_heading, follow_player, Following};
 pub use interactable::detect_nearby_interactables;
 pub use mob_health_bar::{
     cleanup_mob_health_bar, spawn_mob_health_bars, update_mob_health_bar_positions,
-    update_mob_health_bar_values, MobHealthBar, MobHealthBarSprite,
+    update_mob_health_bar_values, MobHealthBar, MobHealthBarPool, MobHealthBarSprite,
 };
-pub use movement::{handle_player_collisions, handle_player_move, stop_attacking_player, stop_player_when_idle};
-pub use spawning::on_map_created;
-pub use transitions::{handle_floor_transition, TransitionInProgress};
+pub use nest::{hatch_nests, NestBrood, NestBundle, NestTimer};
+pub use movement::{
+    apply_knockback, handle_player_collisions, handle_player_move, stop_attacking_player,
+    stop_player_when_idle,
+};
+pub use roaming::{roam_mobs, Roaming};
+pub use spawning::{on_floor_map_ready, spawn_generated_floor, PendingFloor};
+pub use tactics::{apply_mob_tactics, MobTactics};
+pub use transitions::{handle_floor_transition, FloorTransitions, TransitionInProgress};
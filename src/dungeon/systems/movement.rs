@@ -2,7 +2,7 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 use tracing::{debug, instrument};
 
-use crate::combat::Attacking;
+use crate::combat::{Attacking, CombatFeelConfig, HitStop, Knockback};
 use crate::dungeon::events::{FloorTransition, PlayerMoveIntent};
 use crate::dungeon::tile_components::is_door;
 use crate::dungeon::{DoorEntity, MobEntity, MovementConfig, StairsEntity, TileWorldSize};
@@ -120,3 +120,27 @@ pub fn stop_attacking_player(
         vel.0 = Vec2::ZERO;
     }
 }
+
+/// Runs after everything else that steers velocity, so a shove wins over walking, roaming
+/// and tactics until it wears off.
+pub fn apply_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<CombatFeelConfig>,
+    hit_stop: Res<HitStop>,
+    tile_size: Res<TileWorldSize>,
+    mut knocked: Query<(Entity, &mut Knockback, &mut LinearVelocity)>,
+) {
+    if hit_stop.is_active() {
+        return;
+    }
+    let speed = config.knockback_tiles_per_second * tile_size.0;
+    for (entity, mut knockback, mut velocity) in &mut knocked {
+        if knockback.timer.tick(time.delta()).just_finished() {
+            velocity.0 = Vec2::ZERO;
+            commands.entity(entity).remove::<Knockback>();
+            continue;
+        }
+        velocity.0 = knockback.direction * speed;
+    }
+}
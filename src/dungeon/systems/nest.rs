@@ -0,0 +1,132 @@
+//! Nests hatch a new mob every few seconds, up to a cap, for as long as they stand.
+
+use bevy::prelude::*;
+
+use crate::dungeon::{DungeonEntityMarker, EntitySize, MobEntity, NestEntity, TileWorldSize};
+use crate::mob::components::{CombatStats, GoldReward, Health, XpReward};
+use crate::mob::definitions::MobSpec;
+use crate::ui::DyingMob;
+
+use super::{MobTactics, Roaming};
+
+/// Seconds between hatchings.
+const NEST_HATCH_SECS: f32 = 8.0;
+
+/// Most mobs a nest keeps alive at once; it waits for one to die before hatching more.
+const NEST_BROOD_CAP: usize = 3;
+
+/// A nest's health, as a multiple of the toughest mob it hatches.
+const NEST_HEALTH_MULTIPLIER: f32 = 4.0;
+
+/// Gold and XP for destroying a nest, as a multiple of the best its mob would give.
+const NEST_REWARD_MULTIPLIER: i32 = 5;
+
+#[derive(Component, Debug)]
+pub struct NestTimer(pub Timer);
+
+impl Default for NestTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(NEST_HATCH_SECS, TimerMode::Repeating))
+    }
+}
+
+/// Marks a mob hatched by the nest entity it holds, so the nest can count its brood.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct NestBrood(pub Entity);
+
+/// The health, defense and destruction bounty of a nest.
+#[derive(Bundle)]
+pub struct NestBundle {
+    pub health: Health,
+    pub combat_stats: CombatStats,
+    pub gold: GoldReward,
+    pub xp: XpReward,
+}
+
+impl NestBundle {
+    /// A nest for the mob described by `spec`, usually already scaled for the floor.
+    pub fn from_spec(spec: &MobSpec) -> Self {
+        let health = (spec.max_health.end() as f32 * NEST_HEALTH_MULTIPLIER).round() as i32;
+        Self {
+            health: Health::new(health),
+            combat_stats: CombatStats {
+                attack: 0,
+                defense: spec.defense.end(),
+            },
+            gold: GoldReward(spec.dropped_gold.end() * NEST_REWARD_MULTIPLIER),
+            xp: XpReward(spec.dropped_xp.end() * NEST_REWARD_MULTIPLIER),
+        }
+    }
+}
+
+type HatchingNests<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static NestEntity,
+        &'static mut NestTimer,
+        &'static Transform,
+        Option<&'static ChildOf>,
+    ),
+    Without<DyingMob>,
+>;
+
+pub fn hatch_nests(
+    mut commands: Commands,
+    time: Res<Time>,
+    tile_size: Res<TileWorldSize>,
+    mut nests: HatchingNests,
+    brood: Query<&NestBrood, Without<DyingMob>>,
+) {
+    for (nest, nest_entity, mut timer, transform, parent) in &mut nests {
+        if !timer.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let living = brood.iter().filter(|b| b.0 == nest).count();
+        if living >= NEST_BROOD_CAP {
+            continue;
+        }
+
+        let mob_id = nest_entity.mob_id;
+        let spec = mob_id.spec();
+        let marker = DungeonEntityMarker {
+            pos: transform.translation.truncate(),
+            size: EntitySize::new(tile_size.0, tile_size.0),
+        };
+        let mob = commands
+            .spawn((
+                marker,
+                MobEntity { mob_id },
+                Roaming::for_behavior(spec.behavior),
+                MobTactics::for_spec(spec),
+                NestBrood(nest),
+            ))
+            .id();
+        if let Some(parent) = parent {
+            commands.entity(mob).insert(ChildOf(parent.parent()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mob::MobId;
+
+    #[test]
+    fn nest_outlasts_and_outpays_its_mob() {
+        crate::mob::data::init();
+        let spec = MobId::Goblin.spec();
+        let nest = NestBundle::from_spec(spec);
+
+        assert!(nest.health.max > spec.max_health.end());
+        assert_eq!(nest.combat_stats.defense, spec.defense.end());
+        assert_eq!(
+            nest.gold.0,
+            spec.dropped_gold.end() * NEST_REWARD_MULTIPLIER
+        );
+        assert_eq!(nest.xp.0, spec.dropped_xp.end() * NEST_REWARD_MULTIPLIER);
+    }
+}
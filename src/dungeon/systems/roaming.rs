@@ -0,0 +1,213 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::dungeon::constants::DEFAULT_TILE_SIZE;
+use crate::dungeon::{MobEntity, TileWorldSize};
+use crate::mob::{EliteModifier, MobBehavior};
+use crate::ui::DyingMob;
+
+/// How far from its spawn point a roaming mob wanders, in tiles.
+const ROAM_RADIUS_TILES: f32 = 1.5;
+
+/// Roaming speed, in tiles per second.
+const ROAM_SPEED_TILES: f32 = 0.8;
+
+/// How long a mob idles between walks.
+const ROAM_REST_SECS: f32 = 2.0;
+
+/// How long a patrolling mob stops at each waypoint.
+const PATROL_REST_SECS: f32 = 0.5;
+
+/// Waypoints of a patrol loop, as fractions of the roam radius around home.
+const PATROL_ROUTE: [Vec2; 4] = [
+    Vec2::new(1.0, 0.0),
+    Vec2::new(0.0, 1.0),
+    Vec2::new(-1.0, 0.0),
+    Vec2::new(0.0, -1.0),
+];
+
+/// Distance from its target, in pixels, at which a mob counts as arrived.
+const ARRIVE_DISTANCE: f32 = 1.0;
+
+/// Makes a mob wander between random spots near where it spawned, resting in between.
+/// Patrolling mobs walk the same loop of waypoints instead.
+#[derive(Component, Debug)]
+pub struct Roaming {
+    home: Option<Vec2>,
+    target: Option<Vec2>,
+    rest: Timer,
+    /// Index of the next patrol waypoint, for mobs that patrol.
+    patrol: Option<usize>,
+}
+
+impl Default for Roaming {
+    fn default() -> Self {
+        Self {
+            home: None,
+            target: None,
+            rest: Timer::from_seconds(ROAM_REST_SECS, TimerMode::Once),
+            patrol: None,
+        }
+    }
+}
+
+impl Roaming {
+    pub fn patrol() -> Self {
+        Self {
+            rest: Timer::from_seconds(PATROL_REST_SECS, TimerMode::Once),
+            patrol: Some(0),
+            ..default()
+        }
+    }
+
+    pub fn for_behavior(behavior: MobBehavior) -> Self {
+        match behavior {
+            MobBehavior::Patrol => Self::patrol(),
+            MobBehavior::Roam | MobBehavior::Ranged => Self::default(),
+        }
+    }
+
+    /// The direction to walk in from `pos`, or zero while resting. The first position seen
+    /// becomes the mob's home, and new targets are picked within `radius` of it, or on the
+    /// patrol loop at that distance.
+    pub fn heading(&mut self, pos: Vec2, radius: f32, delta: Duration, rng: &mut impl Rng) -> Vec2 {
+        let home = *self.home.get_or_insert(pos);
+
+        if let Some(target) = self.target {
+            let to_target = target - pos;
+            if to_target.length() > ARRIVE_DISTANCE {
+                return to_target.normalize();
+            }
+            self.pause();
+        }
+
+        self.rest.tick(delta);
+        if self.rest.is_finished() {
+            let offset = match &mut self.patrol {
+                Some(next) => {
+                    let waypoint = PATROL_ROUTE[*next];
+                    *next = (*next + 1) % PATROL_ROUTE.len();
+                    waypoint * radius
+                }
+                None => Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(0.0..radius),
+            };
+            self.target = Some(home + offset);
+        }
+        Vec2::ZERO
+    }
+
+    /// Where the mob is walking to, or `None` while it rests.
+    pub fn target(&self) -> Option<Vec2> {
+        self.target
+    }
+
+    pub fn is_patrolling(&self) -> bool {
+        self.patrol.is_some()
+    }
+
+    /// Stops the mob where it is and starts a fresh rest.
+    pub fn pause(&mut self) {
+        self.target = None;
+        self.rest.reset();
+    }
+}
+
+type RoamingMobs<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Roaming,
+        &'static Transform,
+        &'static mut LinearVelocity,
+        Option<&'static EliteModifier>,
+        Has<DyingMob>,
+    ),
+    With<MobEntity>,
+>;
+
+pub fn roam_mobs(
+    time: Res<Time>,
+    tile_size: Option<Res<TileWorldSize>>,
+    mut mobs: RoamingMobs,
+) {
+    let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+    let mut rng = rand::thread_rng();
+
+    for (mut roaming, transform, mut velocity, elite, dying) in &mut mobs {
+        if dying {
+            velocity.0 = Vec2::ZERO;
+            continue;
+        }
+        let heading = roaming.heading(
+            transform.translation.truncate(),
+            ROAM_RADIUS_TILES * tile_size,
+            time.delta(),
+            &mut rng,
+        );
+        let speed = elite.map_or(1.0, |modifier| modifier.speed_multiplier());
+        velocity.0 = heading * ROAM_SPEED_TILES * speed * tile_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roams_near_home_after_resting() {
+        let mut rng = rand::thread_rng();
+        let mut roaming = Roaming::default();
+        let home = Vec2::new(100.0, 50.0);
+
+        assert_eq!(
+            roaming.heading(home, 24.0, Duration::ZERO, &mut rng),
+            Vec2::ZERO
+        );
+        assert!(roaming.target.is_none());
+
+        let rest = Duration::from_secs_f32(ROAM_REST_SECS);
+        roaming.heading(home, 24.0, rest, &mut rng);
+        let target = roaming.target.expect("a target once rested");
+        assert!(target.distance(home) <= 24.0);
+
+        let far = home + Vec2::new(-100.0, 0.0);
+        let heading = roaming.heading(far, 24.0, Duration::ZERO, &mut rng);
+        assert!((heading.length() - 1.0).abs() < 1e-4);
+        assert!(heading.x > 0.0);
+    }
+
+    #[test]
+    fn patrols_walk_the_loop_in_order() {
+        let mut rng = rand::thread_rng();
+        let mut roaming = Roaming::patrol();
+        let home = Vec2::ZERO;
+        let rest = Duration::from_secs_f32(PATROL_REST_SECS);
+
+        roaming.heading(home, 10.0, Duration::ZERO, &mut rng);
+        for waypoint in PATROL_ROUTE.iter().chain(PATROL_ROUTE.iter().take(1)) {
+            roaming.pause();
+            roaming.heading(home, 10.0, rest, &mut rng);
+            assert_eq!(roaming.target, Some(*waypoint * 10.0));
+        }
+    }
+
+    #[test]
+    fn arriving_starts_a_new_rest() {
+        let mut rng = rand::thread_rng();
+        let mut roaming = Roaming {
+            home: Some(Vec2::ZERO),
+            target: Some(Vec2::new(0.5, 0.0)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            roaming.heading(Vec2::ZERO, 24.0, Duration::ZERO, &mut rng),
+            Vec2::ZERO
+        );
+        assert!(roaming.target.is_none());
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use randr_core::floor::{generate_floor, GeneratedFloor, Placement};
+use tracing::instrument;
+
+use crate::crafting_station::CraftingStationType;
+use crate::dungeon::spawn::SpawnTable;
+use crate::dungeon::systems::{MobTactics, Roaming, TransitionInProgress};
+use crate::dungeon::{
+    ChestEntity, CraftingStationEntity, HerbEntity, MobEntity, NestEntity, NpcEntity, RockEntity,
+    ShrineEntity, StairsEntity, TrainingDummyEntity,
+};
+use crate::mob::MobId;
+use crate::rock::RockType;
+
+use super::context::SpawnContext;
+
+/// Spawns the entity for one of the generator's placements.
+fn spawn_placement(
+    commands: &mut Commands,
+    ctx: &SpawnContext,
+    pos: Vec2,
+    placement: Placement<MobId>,
+) {
+    match placement {
+        Placement::Chest => ctx.spawn_entity(commands, pos, ChestEntity),
+        Placement::Stairs => ctx.spawn_entity(commands, pos, StairsEntity),
+        Placement::Rock {
+            kind,
+            sprite_variant,
+        } => {
+            let rock_type = RockType::ALL.get(kind).copied().unwrap_or(RockType::Coal);
+            ctx.spawn_entity(commands, pos, RockEntity { rock_type, sprite_variant })
+        }
+        Placement::Herb => ctx.spawn_entity(commands, pos, HerbEntity),
+        Placement::Shrine => ctx.spawn_entity(commands, pos, ShrineEntity),
+        Placement::TrainingDummy => ctx.spawn_entity(commands, pos, TrainingDummyEntity),
+        Placement::Forge => ctx.spawn_entity(
+            commands,
+            pos,
+            CraftingStationEntity {
+                station_type: CraftingStationType::Forge,
+            },
+        ),
+        Placement::Anvil => ctx.spawn_entity(
+            commands,
+            pos,
+            CraftingStationEntity {
+                station_type: CraftingStationType::Anvil,
+            },
+        ),
+        Placement::Npc(mob_id) => ctx.spawn_entity(commands, pos, NpcEntity { mob_id }),
+        Placement::Mob(mob_id) => ctx.spawn_entity(commands, pos, MobEntity { mob_id }),
+        Placement::RoamingMob(mob_id) => {
+            let spec = mob_id.spec();
+            let roaming = Roaming::for_behavior(spec.behavior);
+            let tactics = MobTactics::for_spec(spec);
+            ctx.spawn_entity(commands, pos, (MobEntity { mob_id }, roaming, tactics))
+        }
+        Placement::Nest(mob_id) => ctx.spawn_entity(commands, pos, NestEntity { mob_id }),
+    }
+}
+
+/// A floor being generated on the async compute pool. The floor stays in transition until
+/// it is spawned.
+#[derive(Resource)]
+pub struct PendingFloor {
+    task: Task<GeneratedFloor<MobId>>,
+    ctx: SpawnContext,
+}
+
+impl PendingFloor {
+    /// Starts generating a floor off the main thread.
+    pub fn start(
+        config: SpawnTable,
+        available: Vec<Vec2>,
+        used: Vec<Vec2>,
+        seed: u64,
+        ctx: SpawnContext,
+    ) -> Self {
+        let rules = config.rules();
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { generate_floor(&rules, &available, used, seed) });
+        Self { task, ctx }
+    }
+}
+
+/// Spawns the pending floor's entities once its task has finished, ending the transition.
+#[instrument(level = "debug", skip_all)]
+pub fn spawn_generated_floor(
+    mut commands: Commands,
+    mut pending: ResMut<PendingFloor>,
+    entities: Query<Entity>,
+) {
+    let Some(floor) = block_on(future::poll_once(&mut pending.task)) else {
+        return;
+    };
+    commands.remove_resource::<PendingFloor>();
+    commands.remove_resource::<TransitionInProgress>();
+
+    // The floor may have been left while it was being generated.
+    let root = pending.ctx.floor_root;
+    if root.is_some_and(|root| !entities.contains(root)) {
+        return;
+    }
+    for (pos, placement) in floor.placements {
+        spawn_placement(&mut commands, &pending.ctx, pos, placement);
+    }
+}
@@ -1,87 +1,83 @@
 mod context;
-mod crafting;
 mod entities;
-mod mobs;
-mod npcs;
+mod generate;
 
 use bevy::prelude::*;
-use bevy_ecs_tiled::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use tracing::instrument;
 
+use crate::dungeon::map::FloorMapReady;
 use crate::dungeon::spawn::SpawnTable;
-use crate::dungeon::tile_components::{can_have_entity, is_door};
-use crate::dungeon::TileWorldSize;
+use crate::dungeon::systems::TransitionInProgress;
+use crate::dungeon::{
+    DepthSorting, DungeonState, FloorEvent, FloorSeed, FloorSeedQueue, TileWorldSize,
+};
 use crate::ui::screens::FloorRoot;
 
-use context::{compute_depth_sorting, compute_tilemap_info, SpawnContext, TilemapData, TilemapQuery};
-use crafting::spawn_crafting_stations;
-use entities::{spawn_chests, spawn_doors, spawn_rocks, spawn_stairs};
-use mobs::spawn_mobs;
-use npcs::spawn_npcs;
+use context::SpawnContext;
+use entities::spawn_doors;
 
-#[instrument(level = "debug", skip_all, fields(spawn_count = spawn_tiles.iter().count(), door_count = door_tiles.iter().count()))]
-pub fn on_map_created(
-    _trigger: On<TiledEvent<MapCreated>>,
+pub use generate::{spawn_generated_floor, PendingFloor};
+
+#[instrument(level = "debug", skip_all, fields(spawn_count = trigger.spawn_tiles.len(), door_count = trigger.door_tiles.len()))]
+pub fn on_floor_map_ready(
+    trigger: On<FloorMapReady>,
     mut commands: Commands,
-    spawn_tiles: Query<(&TilePos, &can_have_entity)>,
-    door_tiles: Query<(&TilePos, &is_door)>,
-    tilemap_query: TilemapQuery,
     floor_root_query: Query<Entity, With<FloorRoot>>,
     config: Option<Res<SpawnTable>>,
+    seed_queue: Option<ResMut<FloorSeedQueue>>,
+    mut dungeon: ResMut<DungeonState>,
 ) {
-    let Some((map_size, grid_size, tilemap_tile_size, map_type, anchor, transform)) =
-        tilemap_query.single().ok()
-    else {
-        return;
-    };
-
-    let tile_size = tilemap_tile_size.x;
+    let info = trigger.info;
+    let tile_size = info.tile_size.x;
     commands.insert_resource(TileWorldSize(tile_size));
 
     let floor_root = floor_root_query.single().ok();
     let ctx = SpawnContext { tile_size, floor_root };
 
-    let tilemap = TilemapData {
-        map_size,
-        grid_size,
-        tile_size: tilemap_tile_size,
-        map_type,
-        anchor,
-        transform,
-    };
-
-    let info = compute_tilemap_info(map_size, tilemap_tile_size, transform);
     commands.insert_resource(info);
-
-    let depth_sorting = compute_depth_sorting(map_size, tilemap_tile_size);
-    commands.insert_resource(depth_sorting);
+    commands.insert_resource(DepthSorting::from_map(
+        info.world_size.y / info.tile_size.y,
+        info.tile_size.y,
+    ));
 
     let mut used_positions: Vec<Vec2> = Vec::new();
 
-    spawn_doors(&mut commands, &door_tiles, &mut used_positions, &ctx, &tilemap);
+    spawn_doors(&mut commands, &trigger.door_tiles, &mut used_positions, &ctx);
 
     let Some(config) = config else {
+        dungeon.set_floor_event(None);
+        commands.remove_resource::<TransitionInProgress>();
         return;
     };
 
-    let mut rng = rand::thread_rng();
+    let seed = seed_queue
+        .and_then(|mut queue| queue.0.pop_front())
+        .unwrap_or_else(rand::random);
+    commands.insert_resource(FloorSeed(seed));
 
-    let available: Vec<Vec2> = spawn_tiles
-        .iter()
-        .filter(|(_, can_spawn)| can_spawn.0)
-        .map(|(tile_pos, _)| tilemap.tile_to_world(tile_pos))
-        .collect();
+    // Rolled from its own stream so a seed's layout is the same with or without an event.
+    let event = if dungeon.depth() > 0 {
+        FloorEvent::roll(&mut StdRng::seed_from_u64(seed.rotate_left(32)))
+    } else {
+        None
+    };
+    dungeon.set_floor_event(event);
+    let mut config = (*config).clone();
+    if let Some(event) = event {
+        event.apply_to_spawn_table(&mut config);
+    }
+    commands.remove_resource::<SpawnTable>();
+
+    let available = trigger.spawn_tiles.clone();
 
     if available.is_empty() {
+        commands.remove_resource::<TransitionInProgress>();
         return;
     }
 
-    spawn_chests(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-    spawn_stairs(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-    spawn_rocks(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-    spawn_crafting_stations(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-    spawn_npcs(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-    spawn_mobs(&mut commands, &config, &available, &mut used_positions, &ctx, &mut rng);
-
-    commands.remove_resource::<SpawnTable>();
+    // Placing entities checks every free tile per entity, which stalls a frame on big
+    // floors, so it runs on the task pool and `spawn_generated_floor` spawns the result.
+    commands.insert_resource(PendingFloor::start(config, available, used_positions, seed, ctx));
 }
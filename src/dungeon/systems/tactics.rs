@@ -0,0 +1,164 @@
+//! What roaming mobs do once the player is near: badly hurt ones run away, and ranged ones
+//! back off to a safe distance and shoot.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::combat::{Projectile, ProjectileBundle};
+use crate::dungeon::constants::DEFAULT_TILE_SIZE;
+use crate::dungeon::{MobEntity, TileWorldSize};
+use crate::mob::definitions::MobSpec;
+use crate::mob::{CombatStats, Health, MobBehavior};
+use crate::ui::screens::FloorRoot;
+use crate::ui::{DungeonPlayer, DyingMob};
+
+/// How close, in tiles, the player has to be to scare off a fleeing mob.
+const FLEE_RANGE_TILES: f32 = 4.0;
+
+const FLEE_SPEED_TILES: f32 = 1.6;
+
+/// How close, in tiles, the player has to be for a ranged mob to start shooting.
+const SHOOT_RANGE_TILES: f32 = 5.0;
+
+/// Ranged mobs back away from a player closer than this, in tiles.
+const KEEP_DISTANCE_TILES: f32 = 3.0;
+
+const BACK_OFF_SPEED_TILES: f32 = 1.0;
+
+const SHOT_SPEED_TILES: f32 = 4.0;
+
+const RELOAD_SECS: f32 = 1.8;
+
+/// Tactics a roaming mob takes from its spec.
+#[derive(Component, Debug)]
+pub struct MobTactics {
+    flee_below: Option<f32>,
+    /// Time between shots, for ranged mobs.
+    reload: Option<Timer>,
+}
+
+impl MobTactics {
+    pub fn for_spec(spec: &MobSpec) -> Self {
+        Self {
+            flee_below: spec.flee_below,
+            reload: (spec.behavior == MobBehavior::Ranged)
+                .then(|| Timer::from_seconds(RELOAD_SECS, TimerMode::Repeating)),
+        }
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        self.reload.is_some()
+    }
+
+    pub fn is_fleeing(&self, health: &Health) -> bool {
+        self.flee_below
+            .is_some_and(|below| (health.current as f32) < health.max as f32 * below)
+    }
+}
+
+/// Direction a ranged mob at `pos` moves in: away from a player closer than
+/// `keep_distance`, otherwise nowhere.
+pub fn keep_distance_heading(pos: Vec2, player: Vec2, keep_distance: f32) -> Vec2 {
+    let from_player = pos - player;
+    if from_player.length() >= keep_distance {
+        return Vec2::ZERO;
+    }
+    from_player.normalize_or_zero()
+}
+
+type TacticalMobs<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut MobTactics,
+        &'static Transform,
+        &'static mut LinearVelocity,
+        &'static Health,
+        &'static CombatStats,
+    ),
+    (With<MobEntity>, Without<DyingMob>, Without<DungeonPlayer>),
+>;
+
+/// Runs after roaming and takes over a mob's velocity while the player is close.
+pub fn apply_mob_tactics(
+    mut commands: Commands,
+    time: Res<Time>,
+    tile_size: Option<Res<TileWorldSize>>,
+    dungeon_player: Query<&Transform, With<DungeonPlayer>>,
+    floor_root: Query<Entity, With<FloorRoot>>,
+    mut mobs: TacticalMobs,
+) {
+    let Ok(player) = dungeon_player.single() else {
+        return;
+    };
+    let player = player.translation.truncate();
+    let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+
+    for (mut tactics, transform, mut velocity, health, combat_stats) in &mut mobs {
+        let pos = transform.translation.truncate();
+        let distance = pos.distance(player);
+
+        if tactics.is_fleeing(health) && distance <= FLEE_RANGE_TILES * tile_size {
+            velocity.0 = (pos - player).normalize_or_zero() * FLEE_SPEED_TILES * tile_size;
+            continue;
+        }
+
+        let Some(reload) = tactics.reload.as_mut() else {
+            continue;
+        };
+        if distance > SHOOT_RANGE_TILES * tile_size {
+            reload.reset();
+            continue;
+        }
+
+        velocity.0 = keep_distance_heading(pos, player, KEEP_DISTANCE_TILES * tile_size)
+            * BACK_OFF_SPEED_TILES
+            * tile_size;
+        if !reload.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let aim = (player - pos).normalize_or_zero() * SHOT_SPEED_TILES * tile_size;
+        let mut shot = commands.spawn(ProjectileBundle::new(
+            Projectile::Mob(combat_stats.clone()),
+            pos,
+            aim,
+        ));
+        if let Ok(root) = floor_root.single() {
+            shot.insert(ChildOf(root));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranged_mobs_only_back_off_when_crowded() {
+        let player = Vec2::ZERO;
+        let heading = keep_distance_heading(Vec2::new(0.0, 10.0), player, 48.0);
+        assert!((heading - Vec2::Y).length() < 1e-4);
+        assert_eq!(
+            keep_distance_heading(Vec2::new(60.0, 0.0), player, 48.0),
+            Vec2::ZERO
+        );
+    }
+
+    #[test]
+    fn mobs_flee_only_below_their_threshold() {
+        let tactics = MobTactics {
+            flee_below: Some(0.25),
+            reload: None,
+        };
+        let mut health = Health::new(40);
+        assert!(!tactics.is_fleeing(&health));
+        health.take_damage(31);
+        assert!(tactics.is_fleeing(&health));
+
+        let brave = MobTactics {
+            flee_below: None,
+            reload: None,
+        };
+        assert!(!brave.is_fleeing(&health));
+    }
+}
@@ -1,18 +1,37 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::dungeon::events::FloorTransition;
 use crate::dungeon::{DungeonRegistry, DungeonState, FloorId, SpawnFloor};
-use crate::location::LocationId;
+use crate::location::CurrentTown;
 
 #[derive(Resource, Default)]
 pub struct TransitionInProgress;
 
+/// Floor transitions for systems that settle a run or contract before the dungeon moves
+/// on. Nothing is read while a transition is still in progress.
+#[derive(SystemParam)]
+pub struct FloorTransitions<'w, 's> {
+    events: MessageReader<'w, 's, FloorTransition>,
+    in_progress: Option<Res<'w, TransitionInProgress>>,
+}
+
+impl FloorTransitions<'_, '_> {
+    pub fn read(&mut self) -> impl Iterator<Item = &FloorTransition> {
+        if self.in_progress.is_some() {
+            self.events.clear();
+        }
+        self.events.read()
+    }
+}
+
 pub fn handle_floor_transition(
     mut commands: Commands,
     mut events: MessageReader<FloorTransition>,
     mut spawn_events: MessageWriter<SpawnFloor>,
     mut state: ResMut<DungeonState>,
     registry: Res<DungeonRegistry>,
+    town: Res<CurrentTown>,
     transition_in_progress: Option<Res<TransitionInProgress>>,
 ) {
     if transition_in_progress.is_some() {
@@ -24,16 +43,22 @@ pub fn handle_floor_transition(
         commands.insert_resource(TransitionInProgress);
         match event {
             FloorTransition::AdvanceFloor => {
-                state.advance_floor(&registry);
+                // Moving on from the last floor of an escort or challenge leads home. A
+                // registered dungeon's last floor stays cleared, as it always has.
+                if state.advance_floor().is_none() && state.is_on_route() {
+                    state.reset_dungeon();
+                    state.exit_dungeon();
+                    state.enter_dungeon(town.0.spec().home, &registry);
+                }
             }
             FloorTransition::EnterDoor => {
                 state.exit_dungeon();
-                state.enter_dungeon(LocationId::MainDungeon, &registry);
+                state.enter_dungeon(town.0.spec().dungeon, &registry);
             }
             FloorTransition::ReturnToHome => {
                 state.reset_dungeon();
                 state.exit_dungeon();
-                state.enter_dungeon(LocationId::Home, &registry);
+                state.enter_dungeon(town.0.spec().home, &registry);
             }
         }
 
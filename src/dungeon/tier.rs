@@ -0,0 +1,110 @@
+//! Dungeon tiers and floor depth. Every floor has a depth, and harder tiers push a
+//! dungeon's floors deeper still. Depth makes mobs stronger, tilts loot toward better
+//! quality and, past a point, swaps in the deep cave tiles.
+
+/// Share mob stats grow by with each level of depth past the first.
+const DEPTH_STAT_STEP: f32 = 0.1;
+
+/// Magic Find each level of depth past the first adds to loot rolls.
+const DEPTH_MAGIC_FIND: i32 = 5;
+
+/// Depth from which cave floors use the deep cave tiles.
+pub const DEEP_TILE_DEPTH: u32 = 4;
+
+/// How hard a tiered dungeon is run. Harder tiers open up as the player levels.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum DungeonTier {
+    #[default]
+    Normal,
+    Veteran,
+    Nightmare,
+}
+
+impl DungeonTier {
+    pub const ALL: [DungeonTier; 3] = [
+        DungeonTier::Normal,
+        DungeonTier::Veteran,
+        DungeonTier::Nightmare,
+    ];
+
+    /// Localization key for the tier's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            DungeonTier::Normal => "dungeon_tier.normal",
+            DungeonTier::Veteran => "dungeon_tier.veteran",
+            DungeonTier::Nightmare => "dungeon_tier.nightmare",
+        }
+    }
+
+    /// Player level needed to pick the tier.
+    pub fn required_level(self) -> i32 {
+        match self {
+            DungeonTier::Normal => 1,
+            DungeonTier::Veteran => 8,
+            DungeonTier::Nightmare => 15,
+        }
+    }
+
+    /// Levels of depth the tier adds to every floor of a tiered dungeon.
+    pub fn depth_bonus(self) -> u32 {
+        match self {
+            DungeonTier::Normal => 0,
+            DungeonTier::Veteran => 3,
+            DungeonTier::Nightmare => 6,
+        }
+    }
+
+    /// The next tier a player of `level` may pick, wrapping back to Normal.
+    pub fn next_unlocked(self, level: i32) -> Self {
+        let index = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL
+            .iter()
+            .cycle()
+            .skip(index + 1)
+            .take(Self::ALL.len())
+            .copied()
+            .find(|tier| level >= tier.required_level())
+            .unwrap_or(DungeonTier::Normal)
+    }
+}
+
+/// What mob stats are multiplied by at `depth`. Town floors and the first floor down are
+/// left as designed.
+pub fn depth_stat_multiplier(depth: u32) -> f32 {
+    1.0 + DEPTH_STAT_STEP * depth.saturating_sub(1) as f32
+}
+
+/// Magic Find added to loot rolls at `depth`.
+pub fn depth_magic_find(depth: u32) -> i32 {
+    DEPTH_MAGIC_FIND * depth.saturating_sub(1) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_tiers_are_skipped_when_cycling() {
+        assert_eq!(DungeonTier::Normal.next_unlocked(1), DungeonTier::Normal);
+        assert_eq!(DungeonTier::Normal.next_unlocked(8), DungeonTier::Veteran);
+        assert_eq!(DungeonTier::Veteran.next_unlocked(8), DungeonTier::Normal);
+        assert_eq!(
+            DungeonTier::Veteran.next_unlocked(20),
+            DungeonTier::Nightmare
+        );
+        assert_eq!(
+            DungeonTier::Nightmare.next_unlocked(20),
+            DungeonTier::Normal
+        );
+    }
+
+    #[test]
+    fn deeper_floors_scale_stats_and_loot() {
+        assert_eq!(depth_stat_multiplier(0), 1.0);
+        assert_eq!(depth_stat_multiplier(1), 1.0);
+        assert!((depth_stat_multiplier(4) - 1.3).abs() < 1e-6);
+
+        assert_eq!(depth_magic_find(1), 0);
+        assert_eq!(depth_magic_find(3), 2 * DEPTH_MAGIC_FIND);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::faction::{FactionId, FactionRank, Reputation};
+use crate::inventory::Inventory;
+use crate::stats::{StatSheet, StatType};
+
+use super::WorthGold;
+
+/// Price swing per point of Charisma, in percent.
+const PERCENT_PER_CHARISMA: i32 = 1;
+
+/// Largest swing haggling can reach either way, in percent.
+pub const MAX_HAGGLE_PERCENT: i32 = 25;
+
+/// How far merchants bend their prices for the player. Positive values mean cheaper
+/// purchases and better offers when selling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Haggle {
+    percent: i32,
+}
+
+impl Haggle {
+    /// Haggling from `charisma` and the player's standing with the Merchants' League.
+    pub fn new(charisma: i32, standing: FactionRank) -> Self {
+        let rank_bonus = match standing {
+            FactionRank::Disliked => -10,
+            FactionRank::Stranger => 0,
+            FactionRank::Associate => 2,
+            FactionRank::Member => 5,
+            FactionRank::Trusted => 8,
+            FactionRank::Exalted => 12,
+        };
+        let percent = charisma * PERCENT_PER_CHARISMA + rank_bonus;
+        Self {
+            percent: percent.clamp(-MAX_HAGGLE_PERCENT, MAX_HAGGLE_PERCENT),
+        }
+    }
+
+    /// Haggling for the player, counting Charisma from their base stats and equipment.
+    pub fn for_player(stats: &StatSheet, inventory: &Inventory, reputation: &Reputation) -> Self {
+        let charisma =
+            stats.value(StatType::Charisma) + inventory.sum_equipment_stats(StatType::Charisma);
+        Self::new(charisma, reputation.rank(FactionId::MerchantsLeague))
+    }
+
+    pub fn percent(self) -> i32 {
+        self.percent
+    }
+
+    /// What a merchant asks for `item`.
+    pub fn buy_price(self, item: &impl WorthGold) -> i32 {
+        let base = item.purchase_price();
+        let price = base * (100 - self.percent) / 100;
+        if base > 0 { price.max(1) } else { price }
+    }
+
+    /// What a merchant pays for `item`.
+    pub fn sell_price(self, item: &impl WorthGold) -> i32 {
+        item.sell_price() * (100 + self.percent) / 100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Priced(i32);
+
+    impl WorthGold for Priced {
+        fn gold_value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn no_haggling_keeps_list_prices() {
+        let haggle = Haggle::default();
+        assert_eq!(haggle.buy_price(&Priced(100)), 100);
+        assert_eq!(haggle.sell_price(&Priced(100)), 50);
+    }
+
+    #[test]
+    fn charisma_and_standing_bend_prices_both_ways() {
+        let haggle = Haggle::new(10, FactionRank::Member);
+        assert_eq!(haggle.percent(), 15);
+        assert_eq!(haggle.buy_price(&Priced(100)), 85);
+        assert_eq!(haggle.sell_price(&Priced(100)), 57);
+
+        let disliked = Haggle::new(0, FactionRank::Disliked);
+        assert_eq!(disliked.buy_price(&Priced(100)), 110);
+        assert_eq!(disliked.sell_price(&Priced(100)), 45);
+    }
+
+    #[test]
+    fn haggling_is_capped_and_never_makes_items_free() {
+        let haggle = Haggle::new(500, FactionRank::Exalted);
+        assert_eq!(haggle.percent(), MAX_HAGGLE_PERCENT);
+        assert_eq!(haggle.buy_price(&Priced(1)), 1);
+        assert!(haggle.sell_price(&Priced(100)) < haggle.buy_price(&Priced(100)));
+    }
+}
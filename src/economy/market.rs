@@ -0,0 +1,153 @@
+//! Supply-driven material prices. Every unit the player sells floods the market a little,
+//! and the glut drains away as in-game days pass.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::item::{Item, ItemId, ItemType};
+
+/// Units sold that knock one percent off a material's price.
+const UNITS_PER_PERCENT: u32 = 4;
+
+/// The lowest a material's price can be driven, in percent of its list price.
+pub const MIN_PRICE_PERCENT: i32 = 40;
+
+/// Share of the glut that clears each day, in percent.
+const RECOVERY_PERCENT_PER_DAY: u32 = 40;
+
+/// Daily price samples kept per material.
+pub const HISTORY_DAYS: usize = 14;
+
+/// What merchants currently pay for materials, relative to list price.
+#[derive(Resource, Debug, Default)]
+pub struct CommodityMarket {
+    glut: HashMap<ItemId, u32>,
+    history: HashMap<ItemId, VecDeque<i32>>,
+    /// The last day the market was caught up to.
+    pub last_day: Option<u32>,
+}
+
+impl CommodityMarket {
+    /// Whether `item`'s price moves with supply.
+    pub fn is_commodity(item: &Item) -> bool {
+        matches!(item.item_type, ItemType::Material(_))
+    }
+
+    /// Today's price for `item_id`, in percent of its list price.
+    pub fn price_percent(&self, item_id: ItemId) -> i32 {
+        let glut = self.glut.get(&item_id).copied().unwrap_or(0);
+        let drop = (glut / UNITS_PER_PERCENT).min(100) as i32;
+        (100 - drop).max(MIN_PRICE_PERCENT)
+    }
+
+    /// `price` scaled to today's market for `item`. Non-commodities pass through untouched.
+    pub fn adjust(&self, item: &Item, price: i32) -> i32 {
+        if !Self::is_commodity(item) {
+            return price;
+        }
+        let adjusted = price * self.price_percent(item.item_id) / 100;
+        if price > 0 { adjusted.max(1) } else { adjusted }
+    }
+
+    /// Floods the market with `quantity` of `item`, if it's a commodity.
+    pub fn record_sale(&mut self, item: &Item, quantity: u32) {
+        if Self::is_commodity(item) {
+            self.add_supply(item.item_id, quantity);
+        }
+    }
+
+    fn add_supply(&mut self, item_id: ItemId, quantity: u32) {
+        *self.glut.entry(item_id).or_insert(0) += quantity;
+    }
+
+    /// Closes out a day: samples every moving price into its history, then lets part of
+    /// each glut clear. Materials drop out once their whole history is back at list price.
+    pub fn end_day(&mut self) {
+        let ids: HashSet<ItemId> = self
+            .glut
+            .keys()
+            .chain(self.history.keys())
+            .copied()
+            .collect();
+        for item_id in ids {
+            let percent = self.price_percent(item_id);
+            let samples = self.history.entry(item_id).or_default();
+            samples.push_back(percent);
+            if samples.len() > HISTORY_DAYS {
+                samples.pop_front();
+            }
+        }
+
+        for glut in self.glut.values_mut() {
+            *glut -= *glut * RECOVERY_PERCENT_PER_DAY / 100;
+            // Small gluts would never clear by percentage alone.
+            *glut = glut.saturating_sub(1);
+        }
+        self.glut.retain(|_, glut| *glut > 0);
+        let glut = &self.glut;
+        self.history.retain(|item_id, samples| {
+            glut.contains_key(item_id) || samples.iter().any(|&percent| percent < 100)
+        });
+    }
+
+    /// Past daily prices for `item_id`, oldest first, followed by today's.
+    pub fn history(&self, item_id: ItemId) -> Vec<i32> {
+        self.history
+            .get(&item_id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain(std::iter::once(self.price_percent(item_id)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selling_in_bulk_drives_the_price_down_to_the_floor() {
+        let mut market = CommodityMarket::default();
+        assert_eq!(market.price_percent(ItemId::CopperOre), 100);
+
+        market.add_supply(ItemId::CopperOre, 200);
+        assert_eq!(market.price_percent(ItemId::CopperOre), 50);
+        assert_eq!(market.price_percent(ItemId::IronOre), 100);
+
+        market.add_supply(ItemId::CopperOre, 10_000);
+        assert_eq!(market.price_percent(ItemId::CopperOre), MIN_PRICE_PERCENT);
+    }
+
+    #[test]
+    fn prices_recover_over_days_and_leave_a_history() {
+        let mut market = CommodityMarket::default();
+        market.add_supply(ItemId::CopperOre, 200);
+
+        market.end_day();
+        let next_day = market.price_percent(ItemId::CopperOre);
+        assert!(next_day > 50 && next_day < 100);
+        assert_eq!(market.history(ItemId::CopperOre), vec![50, next_day]);
+
+        for _ in 0..HISTORY_DAYS * 2 {
+            market.end_day();
+        }
+        assert_eq!(market.price_percent(ItemId::CopperOre), 100);
+        assert_eq!(market.history(ItemId::CopperOre), vec![100]);
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut market = CommodityMarket::default();
+        market.add_supply(ItemId::CopperOre, 400);
+        for _ in 0..5 {
+            market.end_day();
+        }
+        market.add_supply(ItemId::CopperOre, 400);
+        for _ in 0..HISTORY_DAYS {
+            market.end_day();
+        }
+        assert_eq!(market.history(ItemId::CopperOre).len(), HISTORY_DAYS + 1);
+    }
+}
@@ -1,3 +1,6 @@
-mod worth;
+mod haggle;
+mod market;
 
-pub use worth::WorthGold;
+pub use haggle::{Haggle, MAX_HAGGLE_PERCENT};
+pub use market::{CommodityMarket, HISTORY_DAYS, MIN_PRICE_PERCENT};
+pub use randr_core::economy::{Currency, Wallet, WalletError, WorthGold};
@@ -0,0 +1,90 @@
+//! The factions the player can earn standing with.
+//!
+//! Each faction rewards its own kind of work, sells extra wares through town merchants and
+//! teaches recipes once the player is ranked high enough, and resents its rival's gains.
+
+use std::ops::RangeInclusive;
+
+use crate::item::recipe::RecipeId;
+use crate::item::ItemId;
+
+use super::enums::FactionRank;
+
+entity_macros::define_data! {
+    spec FactionSpec {
+        pub name: &'static str,
+        // The faction that loses standing when this one's contracts are taken
+        pub rival: FactionId,
+        // Extra merchant stock offered from the given rank up
+        pub wares: Vec<(FactionRank, ItemId, RangeInclusive<i32>)>,
+        // Anvil and alchemy recipes that stay locked until the given rank
+        pub recipes: Vec<(FactionRank, RecipeId)>,
+    }
+
+    id FactionId;
+
+    variants {
+        MinersGuild {
+            name: "Miners' Guild",
+            rival: FactionId::MagesCircle,
+            wares: vec![
+                (FactionRank::Associate, ItemId::Coal, 10..=20),
+                (FactionRank::Member, ItemId::CopperPickaxe, 1..=1),
+                (FactionRank::Trusted, ItemId::GoldOre, 10..=20),
+            ],
+            recipes: vec![
+                (FactionRank::Member, RecipeId::GoldHelmet),
+                (FactionRank::Member, RecipeId::GoldGauntlets),
+                (FactionRank::Trusted, RecipeId::GoldGreaves),
+                (FactionRank::Trusted, RecipeId::GoldLeggings),
+                (FactionRank::Exalted, RecipeId::GoldChestplate),
+            ],
+        }
+        MagesCircle {
+            name: "Mages' Circle",
+            rival: FactionId::MinersGuild,
+            wares: vec![
+                (FactionRank::Associate, ItemId::BasicHPPotion, 3..=6),
+                (FactionRank::Member, ItemId::BlueCrystal, 1..=3),
+                (FactionRank::Member, ItemId::GreenCrystal, 1..=3),
+                (FactionRank::Trusted, ItemId::WhiteCrystal, 1..=2),
+                (FactionRank::Exalted, ItemId::ImbaRing, 1..=1),
+            ],
+            recipes: vec![(FactionRank::Member, RecipeId::FireFlask)],
+        }
+        MerchantsLeague {
+            name: "Merchants' League",
+            rival: FactionId::MinersGuild,
+            wares: vec![
+                (FactionRank::Associate, ItemId::GoldRing, 1..=1),
+                (FactionRank::Member, ItemId::QualityUpgradeStone, 1..=2),
+                (FactionRank::Trusted, ItemId::QualityUpgradeStone, 2..=4),
+            ],
+            recipes: vec![],
+        }
+    }
+}
+
+impl FactionId {
+    /// Localization key for the faction's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            FactionId::MinersGuild => "faction.miners_guild",
+            FactionId::MagesCircle => "faction.mages_circle",
+            FactionId::MerchantsLeague => "faction.merchants_league",
+        }
+    }
+
+    /// The faction whose rank gates `recipe`, and that rank. Recipes no faction teaches
+    /// are always known.
+    pub fn recipe_requirement(recipe: RecipeId) -> Option<(FactionId, FactionRank)> {
+        FactionId::ALL.iter().find_map(|&faction| {
+            faction
+                .spec()
+                .recipes
+                .iter()
+                .find(|(_, id)| *id == recipe)
+                .map(|(rank, _)| (faction, *rank))
+        })
+    }
+}
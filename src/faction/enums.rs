@@ -0,0 +1,62 @@
+use serde::Deserialize;
+
+/// Standing with a faction, derived from reputation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize)]
+pub enum FactionRank {
+    Disliked,
+    #[default]
+    Stranger,
+    Associate,
+    Member,
+    Trusted,
+    Exalted,
+}
+
+impl FactionRank {
+    pub const ALL: [FactionRank; 6] = [
+        FactionRank::Disliked,
+        FactionRank::Stranger,
+        FactionRank::Associate,
+        FactionRank::Member,
+        FactionRank::Trusted,
+        FactionRank::Exalted,
+    ];
+
+    /// Reputation needed to hold this rank.
+    pub fn threshold(self) -> i32 {
+        match self {
+            FactionRank::Disliked => i32::MIN,
+            FactionRank::Stranger => 0,
+            FactionRank::Associate => 100,
+            FactionRank::Member => 300,
+            FactionRank::Trusted => 700,
+            FactionRank::Exalted => 1500,
+        }
+    }
+
+    pub fn for_reputation(reputation: i32) -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|rank| reputation >= rank.threshold())
+            .unwrap_or(FactionRank::Disliked)
+    }
+
+    /// The rank above this one, if any.
+    pub fn next(self) -> Option<Self> {
+        let index = Self::ALL.iter().position(|r| *r == self)?;
+        Self::ALL.get(index + 1).copied()
+    }
+
+    /// Localization key for the rank's title.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            FactionRank::Disliked => "faction.rank_disliked",
+            FactionRank::Stranger => "faction.rank_stranger",
+            FactionRank::Associate => "faction.rank_associate",
+            FactionRank::Member => "faction.rank_member",
+            FactionRank::Trusted => "faction.rank_trusted",
+            FactionRank::Exalted => "faction.rank_exalted",
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use super::definitions::FactionId;
+use super::enums::FactionRank;
+
+/// Reputation earned (or, when negative, lost) with a faction.
+#[derive(Message, Debug, Clone)]
+pub struct ReputationGained {
+    pub faction: FactionId,
+    pub amount: i32,
+}
+
+#[derive(Message, Debug, Clone)]
+pub struct FactionRankChanged {
+    pub faction: FactionId,
+    pub old_rank: FactionRank,
+    pub new_rank: FactionRank,
+}
@@ -0,0 +1,11 @@
+pub mod definitions;
+pub mod enums;
+pub mod events;
+pub mod plugin;
+pub mod reputation;
+
+pub use definitions::{FactionId, FactionSpec};
+pub use enums::FactionRank;
+pub use events::{FactionRankChanged, ReputationGained};
+pub use plugin::FactionPlugin;
+pub use reputation::{Reputation, MIN_REPUTATION, REPUTATION_SAVE_PATH};
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+use crate::dungeon::{MineableEntityType, MiningResult};
+use crate::game::{BrewingResult, CharacterDataExt, MerchantTransactionResult};
+use crate::mob::MobId;
+use crate::plugins::MobDefeated;
+
+use super::definitions::FactionId;
+use super::events::{FactionRankChanged, ReputationGained};
+use super::reputation::Reputation;
+
+/// Miners' Guild reputation per rock mined.
+const ROCK_REPUTATION: i32 = 2;
+
+/// The guild does not take kindly to its dwarves being cut down.
+const DWARF_MINER_PENALTY: i32 = -5;
+
+/// Mages' Circle reputation per slime slain; the Circle studies their gel.
+const SLIME_REPUTATION: i32 = 3;
+
+/// Merchants' League reputation per goblin slain, for keeping the trade roads clear.
+const GOBLIN_REPUTATION: i32 = 2;
+
+const BREW_REPUTATION: i32 = 10;
+
+const PURCHASE_REPUTATION: i32 = 2;
+const SALE_REPUTATION: i32 = 1;
+
+pub struct FactionPlugin;
+
+impl Plugin for FactionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_character_resource::<Reputation>()
+            .add_message::<ReputationGained>()
+            .add_message::<FactionRankChanged>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        reputation_from_mining.run_if(on_message::<MiningResult>),
+                        reputation_from_kills.run_if(on_message::<MobDefeated>),
+                        reputation_from_brewing.run_if(on_message::<BrewingResult>),
+                        reputation_from_trade.run_if(on_message::<MerchantTransactionResult>),
+                    ),
+                    process_reputation_gained.run_if(on_message::<ReputationGained>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn process_reputation_gained(
+    mut events: MessageReader<ReputationGained>,
+    mut reputation: ResMut<Reputation>,
+    mut rank_events: MessageWriter<FactionRankChanged>,
+) {
+    for event in events.read() {
+        let old_rank = reputation.rank(event.faction);
+        if let Some(new_rank) = reputation.adjust(event.faction, event.amount) {
+            rank_events.write(FactionRankChanged {
+                faction: event.faction,
+                old_rank,
+                new_rank,
+            });
+        }
+    }
+}
+
+fn reputation_from_mining(
+    mut events: MessageReader<MiningResult>,
+    mut reputation_events: MessageWriter<ReputationGained>,
+) {
+    for event in events.read() {
+        if let MineableEntityType::Rock { .. } = event.mineable_type {
+            reputation_events.write(ReputationGained {
+                faction: FactionId::MinersGuild,
+                amount: ROCK_REPUTATION,
+            });
+        }
+    }
+}
+
+fn reputation_from_kills(
+    mut events: MessageReader<MobDefeated>,
+    mut reputation_events: MessageWriter<ReputationGained>,
+) {
+    for event in events.read() {
+        let (faction, amount) = match event.mob_id {
+            MobId::Slime => (FactionId::MagesCircle, SLIME_REPUTATION),
+            MobId::Goblin => (FactionId::MerchantsLeague, GOBLIN_REPUTATION),
+            MobId::DwarfMiner => (FactionId::MinersGuild, DWARF_MINER_PENALTY),
+            _ => continue,
+        };
+        reputation_events.write(ReputationGained { faction, amount });
+    }
+}
+
+fn reputation_from_brewing(
+    mut events: MessageReader<BrewingResult>,
+    mut reputation_events: MessageWriter<ReputationGained>,
+) {
+    for event in events.read() {
+        if let BrewingResult::Success { .. } = event {
+            reputation_events.write(ReputationGained {
+                faction: FactionId::MagesCircle,
+                amount: BREW_REPUTATION,
+            });
+        }
+    }
+}
+
+fn reputation_from_trade(
+    mut events: MessageReader<MerchantTransactionResult>,
+    mut reputation_events: MessageWriter<ReputationGained>,
+) {
+    for event in events.read() {
+        let amount = match event {
+            MerchantTransactionResult::BuySuccess { .. } => PURCHASE_REPUTATION,
+            MerchantTransactionResult::SellSuccess { .. }
+            | MerchantTransactionResult::SellJunkSuccess { .. } => SALE_REPUTATION,
+            _ => continue,
+        };
+        reputation_events.write(ReputationGained {
+            faction: FactionId::MerchantsLeague,
+            amount,
+        });
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CharacterResource;
+use crate::item::recipe::RecipeId;
+use crate::item::ItemId;
+
+use super::definitions::FactionId;
+use super::enums::FactionRank;
+
+/// Where reputation is saved in each character's slot.
+pub const REPUTATION_SAVE_PATH: &str = "reputation.ron";
+
+/// Lowest reputation a faction can fall to, so a grudge can always be worked off.
+pub const MIN_REPUTATION: i32 = -500;
+
+/// The player's reputation with every faction.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reputation {
+    standing: HashMap<FactionId, i32>,
+}
+
+impl CharacterResource for Reputation {
+    const FILE: &'static str = REPUTATION_SAVE_PATH;
+}
+
+impl Reputation {
+    pub fn get(&self, faction: FactionId) -> i32 {
+        self.standing.get(&faction).copied().unwrap_or(0)
+    }
+
+    pub fn rank(&self, faction: FactionId) -> FactionRank {
+        FactionRank::for_reputation(self.get(faction))
+    }
+
+    /// Adjusts reputation with `faction` and returns the new rank if it changed.
+    pub fn adjust(&mut self, faction: FactionId, amount: i32) -> Option<FactionRank> {
+        let before = self.rank(faction);
+        let standing = self.standing.entry(faction).or_insert(0);
+        *standing = standing.saturating_add(amount).max(MIN_REPUTATION);
+        let after = self.rank(faction);
+        (after != before).then_some(after)
+    }
+
+    /// Reputation needed for the next rank with `faction`, or `None` at the top.
+    pub fn next_rank_at(&self, faction: FactionId) -> Option<i32> {
+        self.rank(faction).next().map(FactionRank::threshold)
+    }
+
+    /// Whether the player may forge or brew `recipe`.
+    pub fn recipe_unlocked(&self, recipe: RecipeId) -> bool {
+        FactionId::recipe_requirement(recipe)
+            .is_none_or(|(faction, rank)| self.rank(faction) >= rank)
+    }
+
+    /// Faction wares merchants may stock for a player of this standing.
+    pub fn unlocked_wares(&self) -> impl Iterator<Item = (ItemId, RangeInclusive<i32>)> + '_ {
+        FactionId::ALL.iter().flat_map(move |&faction| {
+            let rank = self.rank(faction);
+            faction
+                .spec()
+                .wares
+                .iter()
+                .filter(move |(required, _, _)| rank >= *required)
+                .map(|(_, item_id, quantity)| (*item_id, quantity.clone()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_follow_thresholds() {
+        assert_eq!(FactionRank::for_reputation(-1), FactionRank::Disliked);
+        assert_eq!(FactionRank::for_reputation(0), FactionRank::Stranger);
+        assert_eq!(FactionRank::for_reputation(299), FactionRank::Associate);
+        assert_eq!(FactionRank::for_reputation(300), FactionRank::Member);
+        assert_eq!(FactionRank::for_reputation(i32::MAX), FactionRank::Exalted);
+        assert_eq!(FactionRank::Exalted.next(), None);
+    }
+
+    #[test]
+    fn adjust_reports_rank_changes_and_floors_reputation() {
+        let mut reputation = Reputation::default();
+
+        assert_eq!(reputation.adjust(FactionId::MinersGuild, 50), None);
+        assert_eq!(
+            reputation.adjust(FactionId::MinersGuild, 60),
+            Some(FactionRank::Associate)
+        );
+        reputation.adjust(FactionId::MagesCircle, -10_000);
+        assert_eq!(reputation.get(FactionId::MagesCircle), MIN_REPUTATION);
+        assert_eq!(reputation.rank(FactionId::MagesCircle), FactionRank::Disliked);
+    }
+
+    #[test]
+    fn faction_recipes_and_wares_unlock_with_rank() {
+        let mut reputation = Reputation::default();
+        assert!(reputation.recipe_unlocked(RecipeId::IronSword));
+        assert!(!reputation.recipe_unlocked(RecipeId::GoldHelmet));
+        assert_eq!(reputation.unlocked_wares().count(), 0);
+
+        reputation.adjust(FactionId::MinersGuild, FactionRank::Member.threshold());
+        assert!(reputation.recipe_unlocked(RecipeId::GoldHelmet));
+        assert!(!reputation.recipe_unlocked(RecipeId::GoldChestplate));
+
+        let wares: Vec<ItemId> = reputation.unlocked_wares().map(|(id, _)| id).collect();
+        assert_eq!(wares, [ItemId::Coal, ItemId::CopperPickaxe]);
+
+        assert!(!reputation.recipe_unlocked(RecipeId::FireFlask));
+        reputation.adjust(FactionId::MagesCircle, FactionRank::Member.threshold());
+        assert!(reputation.recipe_unlocked(RecipeId::FireFlask));
+    }
+
+    #[test]
+    fn next_rank_threshold_tracks_standing() {
+        let mut reputation = Reputation::default();
+        assert_eq!(
+            reputation.next_rank_at(FactionId::MerchantsLeague),
+            Some(FactionRank::Associate.threshold())
+        );
+        reputation.adjust(FactionId::MerchantsLeague, -1);
+        assert_eq!(reputation.next_rank_at(FactionId::MerchantsLeague), Some(0));
+        reputation.adjust(FactionId::MerchantsLeague, i32::MAX);
+        assert_eq!(reputation.next_rank_at(FactionId::MerchantsLeague), None);
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let mut reputation = Reputation::default();
+        reputation.adjust(FactionId::MinersGuild, 120);
+        reputation.adjust(FactionId::MagesCircle, -40);
+
+        let ron = crate::save::to_ron(&reputation).unwrap();
+        assert_eq!(crate::save::from_ron::<Reputation>(&ron).unwrap(), reputation);
+    }
+}
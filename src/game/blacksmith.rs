@@ -1,10 +1,17 @@
 use bevy::prelude::*;
+use rand::Rng;
 use uuid::Uuid;
 
+use crate::economy::{Currency, Wallet};
+use crate::game::ItemFound;
 use crate::inventory::{FindsItems, Inventory, ManagesItems};
 use crate::item::recipe::{Recipe, RecipeId};
-use crate::item::ItemRegistry;
-use crate::player::{PlayerGold, PlayerMarker};
+use crate::item::enums::ItemQuality;
+use crate::item::{Item, ItemId, ItemRegistry};
+use crate::player::PlayerMarker;
+
+/// Success chance added to quality upgrades for every failure in a row.
+pub const STREAK_BONUS_PER_FAILURE: f64 = 0.1;
 
 #[derive(Message, Debug, Clone)]
 pub struct UpgradeItemEvent {
@@ -45,6 +52,13 @@ pub enum BlacksmithResult {
         new_quality: String,
     },
     QualityUpgradeFailedNoStone,
+    /// The Magic Rock was spent but the quality didn't take. `downgraded_to` is set if the
+    /// item dropped a tier; `warded` if a Warding Rune was used up to stop that.
+    QualityUpgradeFailed {
+        item_name: String,
+        downgraded_to: Option<String>,
+        warded: bool,
+    },
     QualityUpgradeFailedMaxQuality {
         item_name: String,
     },
@@ -77,6 +91,7 @@ impl Plugin for BlacksmithPlugin {
             .add_message::<SmeltRecipeEvent>()
             .add_message::<ForgeRecipeEvent>()
             .add_message::<BlacksmithResult>()
+            .init_resource::<UpgradeStreak>()
             .add_systems(
                 Update,
                 (
@@ -186,9 +201,9 @@ pub fn calculate_upgrade_cost(item: &crate::item::Item) -> i32 {
 fn handle_upgrade_item(
     mut upgrade_events: MessageReader<UpgradeItemEvent>,
     mut result_events: MessageWriter<BlacksmithResult>,
-    mut player: Query<(&mut PlayerGold, &mut Inventory), With<PlayerMarker>>,
+    mut player: Query<(&mut Wallet, &mut Inventory), With<PlayerMarker>>,
 ) {
-    let Ok((mut gold, mut inventory)) = player.single_mut() else {
+    let Ok((mut wallet, mut inventory)) = player.single_mut() else {
         return;
     };
 
@@ -207,16 +222,16 @@ fn handle_upgrade_item(
             continue;
         }
 
-        if gold.0 < upgrade_cost {
+        if wallet.gold() < upgrade_cost {
             result_events.write(BlacksmithResult::UpgradeFailedNotEnoughGold {
                 need: upgrade_cost,
-                have: gold.0,
+                have: wallet.gold(),
             });
             info!("Not enough gold to upgrade");
             continue;
         }
 
-        gold.0 -= upgrade_cost;
+        wallet.subtract(Currency::Gold, upgrade_cost);
 
         if let Some(inv_item_mut) = inventory.find_item_by_uuid_mut(event.item_uuid) {
             if let Ok(result) = inv_item_mut.item.upgrade() {
@@ -234,10 +249,75 @@ fn handle_upgrade_item(
     }
 }
 
+/// Quality upgrades failed in a row since the last one that took. Each failure makes the
+/// next attempt likelier to land.
+#[derive(Resource, Default, Debug)]
+pub struct UpgradeStreak {
+    failures: u32,
+}
+
+impl UpgradeStreak {
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Success chance added by the current streak.
+    pub fn bonus(&self) -> f64 {
+        self.failures as f64 * STREAK_BONUS_PER_FAILURE
+    }
+
+    /// Chance the next quality upgrade on an item of `quality` succeeds.
+    pub fn success_chance(&self, quality: ItemQuality) -> f64 {
+        if quality.next_quality().is_none() {
+            return 0.0;
+        }
+        (quality.upgrade_success_chance() + self.bonus()).min(1.0)
+    }
+}
+
+/// How a quality upgrade attempt went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityAttempt {
+    Upgraded(ItemQuality),
+    /// Failed with nothing lower to drop to.
+    Failed,
+    /// Failed, and a Warding Rune kept the item's quality.
+    Warded,
+    Downgraded(ItemQuality),
+}
+
+/// Rolls a quality upgrade on `item`, which must be below Mythic. A failure drops the item a
+/// tier unless `warded`, and lengthens the streak; a success resets it.
+pub fn attempt_quality_upgrade(
+    item: &mut Item,
+    streak: &mut UpgradeStreak,
+    warded: bool,
+    rng: &mut impl Rng,
+) -> QualityAttempt {
+    if rng.gen_bool(streak.success_chance(item.quality)) {
+        streak.failures = 0;
+        return match item.upgrade_quality() {
+            Ok(quality) => QualityAttempt::Upgraded(quality),
+            Err(_) => QualityAttempt::Failed,
+        };
+    }
+
+    streak.failures += 1;
+    if item.quality.previous_quality().is_none() {
+        QualityAttempt::Failed
+    } else if warded {
+        QualityAttempt::Warded
+    } else {
+        item.downgrade_quality()
+            .map_or(QualityAttempt::Failed, QualityAttempt::Downgraded)
+    }
+}
+
 fn handle_upgrade_quality(
     mut quality_events: MessageReader<UpgradeQualityEvent>,
     mut result_events: MessageWriter<BlacksmithResult>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    mut streak: ResMut<UpgradeStreak>,
 ) {
     let Ok(mut inventory) = player.single_mut() else {
         return;
@@ -249,42 +329,65 @@ fn handle_upgrade_quality(
         };
         let item_name = inv_item.item.name.clone();
 
-        if inventory
-            .find_item_by_id(crate::item::ItemId::QualityUpgradeStone)
-            .is_none()
-        {
+        if inv_item.item.quality.next_quality().is_none() {
+            result_events.write(BlacksmithResult::QualityUpgradeFailedMaxQuality { item_name });
+            info!("Item is already at max quality");
+            continue;
+        }
+
+        if inventory.find_item_by_id(ItemId::QualityUpgradeStone).is_none() {
             result_events.write(BlacksmithResult::QualityUpgradeFailedNoStone);
             info!("You need a Magic Rock (Quality Upgrade Stone) to improve quality");
             continue;
         }
 
-        if let Some(inv_item_mut) = inventory.find_item_by_uuid_mut(event.item_uuid) {
-            match inv_item_mut.item.upgrade_quality() {
-                Ok(new_quality) => {
-                    let quality_name = format!("{:?}", new_quality);
-
-                    inventory.decrease_item_quantity(crate::item::ItemId::QualityUpgradeStone, 1);
-
-                    result_events.write(BlacksmithResult::QualityUpgradeSuccess {
-                        item_name: item_name.clone(),
-                        new_quality: quality_name.clone(),
-                    });
-                    info!("Upgraded {} to {} quality", item_name, quality_name);
+        let warded = inventory.find_item_by_id(ItemId::WardingRune).is_some();
+        let Some(inv_item_mut) = inventory.find_item_by_uuid_mut(event.item_uuid) else {
+            continue;
+        };
+        let attempt = attempt_quality_upgrade(
+            &mut inv_item_mut.item,
+            &mut streak,
+            warded,
+            &mut rand::thread_rng(),
+        );
+        inventory.decrease_item_quantity(ItemId::QualityUpgradeStone, 1);
+
+        let result = match attempt {
+            QualityAttempt::Upgraded(quality) => {
+                info!("Upgraded {} to {:?} quality", item_name, quality);
+                BlacksmithResult::QualityUpgradeSuccess {
+                    item_name,
+                    new_quality: quality.display_name().to_string(),
                 }
-                Err(_) => {
-                    result_events.write(BlacksmithResult::QualityUpgradeFailedMaxQuality {
-                        item_name,
-                    });
-                    info!("Item is already at max quality");
+            }
+            QualityAttempt::Failed => BlacksmithResult::QualityUpgradeFailed {
+                item_name,
+                downgraded_to: None,
+                warded: false,
+            },
+            QualityAttempt::Warded => {
+                inventory.decrease_item_quantity(ItemId::WardingRune, 1);
+                BlacksmithResult::QualityUpgradeFailed {
+                    item_name,
+                    downgraded_to: None,
+                    warded: true,
                 }
             }
-        }
+            QualityAttempt::Downgraded(quality) => BlacksmithResult::QualityUpgradeFailed {
+                item_name,
+                downgraded_to: Some(quality.display_name().to_string()),
+                warded: false,
+            },
+        };
+        result_events.write(result);
     }
 }
 
 fn handle_smelt_recipe(
     mut smelt_events: MessageReader<SmeltRecipeEvent>,
     mut result_events: MessageWriter<BlacksmithResult>,
+    mut found_events: MessageWriter<ItemFound>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     registry: Res<ItemRegistry>,
 ) {
@@ -293,19 +396,25 @@ fn handle_smelt_recipe(
     };
 
     for event in smelt_events.read() {
-        process_crafting_recipe(
+        if process_crafting_recipe(
             event.recipe_id,
             CraftingOperation::Smelt,
             &mut result_events,
             &mut inventory,
             &registry,
-        );
+        ) {
+            found_events.write(ItemFound {
+                item_id: event.recipe_id.spec().output,
+                quantity: 1,
+            });
+        }
     }
 }
 
 fn handle_forge_recipe(
     mut forge_events: MessageReader<ForgeRecipeEvent>,
     mut result_events: MessageWriter<BlacksmithResult>,
+    mut found_events: MessageWriter<ItemFound>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     registry: Res<ItemRegistry>,
 ) {
@@ -314,12 +423,78 @@ fn handle_forge_recipe(
     };
 
     for event in forge_events.read() {
-        process_crafting_recipe(
+        if process_crafting_recipe(
             event.recipe_id,
             CraftingOperation::Forge,
             &mut result_events,
             &mut inventory,
             &registry,
-        );
+        ) {
+            found_events.write(ItemFound {
+                item_id: event.recipe_id.spec().output,
+                quantity: 1,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::item::enums::{EquipmentType, ItemType};
+
+    fn sword(quality: ItemQuality) -> Item {
+        Item {
+            max_upgrades: 5,
+            quality,
+            ..Item::test(ItemId::Sword, ItemType::Equipment(EquipmentType::Weapon))
+        }
+    }
+
+    #[test]
+    fn failures_raise_the_odds_up_to_certain() {
+        let mut streak = UpgradeStreak::default();
+        assert_eq!(streak.success_chance(ItemQuality::Masterworked), 0.3);
+        streak.failures = 3;
+        assert!((streak.success_chance(ItemQuality::Masterworked) - 0.6).abs() < 1e-9);
+        assert_eq!(streak.success_chance(ItemQuality::Poor), 1.0);
+        assert_eq!(streak.success_chance(ItemQuality::Mythic), 0.0);
+    }
+
+    #[test]
+    fn a_long_enough_streak_always_lands_and_resets() {
+        let mut streak = UpgradeStreak { failures: 7 };
+        let mut item = sword(ItemQuality::Masterworked);
+        let attempt =
+            attempt_quality_upgrade(&mut item, &mut streak, false, &mut StdRng::seed_from_u64(1));
+        assert_eq!(attempt, QualityAttempt::Upgraded(ItemQuality::Mythic));
+        assert_eq!(streak.failures(), 0);
+    }
+
+    #[test]
+    fn failures_drop_a_tier_unless_warded() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for warded in [false, true] {
+            let failure = (0..100).find_map(|_| {
+                let mut streak = UpgradeStreak::default();
+                let mut item = sword(ItemQuality::Masterworked);
+                let attempt = attempt_quality_upgrade(&mut item, &mut streak, warded, &mut rng);
+                (attempt != QualityAttempt::Upgraded(ItemQuality::Mythic))
+                    .then_some((attempt, item.quality, streak.failures()))
+            });
+            let expected = if warded {
+                (QualityAttempt::Warded, ItemQuality::Masterworked, 1)
+            } else {
+                (
+                    QualityAttempt::Downgraded(ItemQuality::WellForged),
+                    ItemQuality::WellForged,
+                    1,
+                )
+            };
+            assert_eq!(failure, Some(expected));
+        }
     }
 }
@@ -0,0 +1,256 @@
+//! Temporary stat changes picked up during a run, such as shrine blessings and curses.
+//! Each lasts a number of floors and is undone when it runs out or the player heads home.
+
+use bevy::prelude::*;
+
+use crate::dungeon::{DungeonState, FloorReady};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::player::PlayerMarker;
+use crate::stats::{StatSheet, StatType};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BuffKind {
+    Might,
+    Warding,
+    Fortune,
+    /// Curse that saps attack.
+    Weakness,
+    /// Curse that wears down defense.
+    Frailty,
+}
+
+impl BuffKind {
+    pub const BLESSINGS: [BuffKind; 3] = [BuffKind::Might, BuffKind::Warding, BuffKind::Fortune];
+    pub const CURSES: [BuffKind; 2] = [BuffKind::Weakness, BuffKind::Frailty];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            BuffKind::Might => "buffs.might",
+            BuffKind::Warding => "buffs.warding",
+            BuffKind::Fortune => "buffs.fortune",
+            BuffKind::Weakness => "buffs.weakness",
+            BuffKind::Frailty => "buffs.frailty",
+        }
+    }
+
+    pub fn stat(self) -> StatType {
+        match self {
+            BuffKind::Might | BuffKind::Weakness => StatType::Attack,
+            BuffKind::Warding | BuffKind::Frailty => StatType::Defense,
+            BuffKind::Fortune => StatType::GoldFind,
+        }
+    }
+
+    /// How far the buff moves its stat; negative for curses.
+    pub fn amount(self) -> i32 {
+        match self {
+            BuffKind::Might => 4,
+            BuffKind::Warding => 3,
+            BuffKind::Fortune => 50,
+            BuffKind::Weakness => -3,
+            BuffKind::Frailty => -2,
+        }
+    }
+
+    pub fn is_curse(self) -> bool {
+        self.amount() < 0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Buff {
+    pub kind: BuffKind,
+    /// Floors left including the current one.
+    pub floors_left: u32,
+    /// What was actually added to the stat. A curse can be cut short by a stat bottoming
+    /// out at zero, so this is what gets taken back rather than `kind.amount()`.
+    applied: i32,
+}
+
+/// Buffs and curses the player carries on the current run.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveBuffs {
+    buffs: Vec<Buff>,
+}
+
+impl ActiveBuffs {
+    pub fn iter(&self) -> impl Iterator<Item = &Buff> {
+        self.buffs.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffs.is_empty()
+    }
+
+    pub fn has_curse(&self) -> bool {
+        self.buffs.iter().any(|buff| buff.kind.is_curse())
+    }
+
+    /// How much the held buffs and curses have moved `stat`, net.
+    pub fn stat_bonus(&self, stat: StatType) -> i32 {
+        self.buffs
+            .iter()
+            .filter(|buff| buff.kind.stat() == stat)
+            .map(|buff| buff.applied)
+            .sum()
+    }
+
+    /// Applies a buff for `floors` floors. Gaining one already held only tops its
+    /// duration back up; the stat change does not stack.
+    pub fn grant(&mut self, kind: BuffKind, floors: u32, stats: &mut StatSheet) {
+        if let Some(buff) = self.buffs.iter_mut().find(|buff| buff.kind == kind) {
+            buff.floors_left = buff.floors_left.max(floors);
+            return;
+        }
+
+        let stat = kind.stat();
+        let before = stats.value(stat);
+        match kind.amount() {
+            amount if amount >= 0 => stats.increase_stat(stat, amount),
+            amount => stats.decrease_stat(stat, -amount),
+        }
+        self.buffs.push(Buff {
+            kind,
+            floors_left: floors,
+            applied: stats.value(stat) - before,
+        });
+    }
+
+    /// Counts a floor off every buff, removing the ones that run out. Returns those.
+    pub fn tick_floor(&mut self, stats: &mut StatSheet) -> Vec<BuffKind> {
+        for buff in &mut self.buffs {
+            buff.floors_left = buff.floors_left.saturating_sub(1);
+        }
+        self.remove_where(stats, |buff| buff.floors_left == 0)
+    }
+
+    /// Lifts every curse. Returns how many there were.
+    pub fn cleanse(&mut self, stats: &mut StatSheet) -> usize {
+        self.remove_where(stats, |buff| buff.kind.is_curse()).len()
+    }
+
+    pub fn clear(&mut self, stats: &mut StatSheet) {
+        self.remove_where(stats, |_| true);
+    }
+
+    fn remove_where(
+        &mut self,
+        stats: &mut StatSheet,
+        predicate: impl Fn(&Buff) -> bool,
+    ) -> Vec<BuffKind> {
+        let (removed, kept) = self
+            .buffs
+            .drain(..)
+            .partition::<Vec<_>, _>(|buff| predicate(buff));
+        self.buffs = kept;
+        for buff in &removed {
+            match buff.applied {
+                applied if applied >= 0 => stats.decrease_stat(buff.kind.stat(), applied),
+                applied => stats.increase_stat(buff.kind.stat(), -applied),
+            }
+        }
+        removed.into_iter().map(|buff| buff.kind).collect()
+    }
+}
+
+pub struct BuffPlugin;
+
+impl Plugin for BuffPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveBuffs>().add_systems(
+            Update,
+            tick_buffs
+                .run_if(on_message::<FloorReady>)
+                .run_if(|buffs: Res<ActiveBuffs>| !buffs.is_empty()),
+        );
+    }
+}
+
+/// Each new floor counts down the buffs; getting back to town ends them all.
+fn tick_buffs(
+    mut floor_ready: MessageReader<FloorReady>,
+    dungeon: Res<DungeonState>,
+    mut buffs: ResMut<ActiveBuffs>,
+    mut player: Query<&mut StatSheet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    floor_ready.clear();
+    let Ok(mut stats) = player.single_mut() else {
+        return;
+    };
+
+    if dungeon.depth() == 0 {
+        buffs.clear(&mut stats);
+        return;
+    }
+
+    for kind in buffs.tick_floor(&mut stats) {
+        toast_writer.write(ShowToast::new(
+            loc.format("buffs.expired", &[("buff", &loc.get(kind.label_key()))]),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> StatSheet {
+        StatSheet::new()
+            .with(StatType::Attack, 8)
+            .with(StatType::Defense, 1)
+    }
+
+    #[test]
+    fn buffs_wear_off_after_their_floors() {
+        let mut stats = stats();
+        let mut buffs = ActiveBuffs::default();
+        buffs.grant(BuffKind::Might, 2, &mut stats);
+        assert_eq!(stats.value(StatType::Attack), 8 + BuffKind::Might.amount());
+
+        assert!(buffs.tick_floor(&mut stats).is_empty());
+        assert_eq!(buffs.tick_floor(&mut stats), [BuffKind::Might]);
+        assert_eq!(stats.value(StatType::Attack), 8);
+        assert!(buffs.is_empty());
+    }
+
+    #[test]
+    fn regaining_a_buff_refreshes_instead_of_stacking() {
+        let mut stats = stats();
+        let mut buffs = ActiveBuffs::default();
+        buffs.grant(BuffKind::Might, 1, &mut stats);
+        buffs.grant(BuffKind::Might, 3, &mut stats);
+
+        assert_eq!(stats.value(StatType::Attack), 8 + BuffKind::Might.amount());
+        assert_eq!(
+            buffs
+                .iter()
+                .map(|buff| buff.floors_left)
+                .collect::<Vec<_>>(),
+            [3]
+        );
+    }
+
+    #[test]
+    fn cleansing_restores_what_a_curse_took() {
+        let mut stats = stats();
+        let mut buffs = ActiveBuffs::default();
+        buffs.grant(BuffKind::Frailty, 2, &mut stats);
+        buffs.grant(BuffKind::Warding, 2, &mut stats);
+        // Frailty could only take the one point of defense there was.
+        assert_eq!(stats.value(StatType::Defense), BuffKind::Warding.amount());
+        assert!(buffs.has_curse());
+
+        assert_eq!(buffs.cleanse(&mut stats), 1);
+        assert_eq!(
+            stats.value(StatType::Defense),
+            1 + BuffKind::Warding.amount()
+        );
+        assert!(!buffs.has_curse());
+
+        buffs.clear(&mut stats);
+        assert_eq!(stats.value(StatType::Defense), 1);
+    }
+}
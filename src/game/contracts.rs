@@ -0,0 +1,352 @@
+//! Crafting contracts from the blacksmith.
+//!
+//! The blacksmith hands over the materials for an anvil recipe and pays a fee, plus some
+//! Blacksmith XP, once the finished item is delivered. The offers rotate every day.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::economy::{Currency, Wallet};
+use crate::faction::{FactionId, Reputation, ReputationGained};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::inventory::{FindsItems, Inventory, ManagesItems};
+use crate::item::recipe::{Recipe, RecipeId};
+use crate::item::{ItemId, ItemRegistry};
+use crate::location::{CurrentTown, TownState};
+use crate::player::PlayerMarker;
+use crate::skills::{SkillType, SkillXpGained};
+use crate::states::ScreenSet;
+use crate::world_event::GameClock;
+
+/// Contracts on offer at once.
+pub const CONTRACT_OFFERS: usize = 3;
+
+/// Share of the commissioned item's value paid as the crafting fee.
+pub const FEE_PERCENT: i32 = 30;
+
+/// Blacksmith XP per ingredient on a delivered contract, on top of what the anvil grants.
+pub const XP_PER_INGREDIENT: u64 = 20;
+
+/// Miners' Guild reputation for each delivered contract; the guild sells the blacksmith
+/// their metal.
+pub const CONTRACT_REPUTATION: i32 = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ContractError {
+    #[error("A contract is already underway")]
+    AlreadyActive,
+    #[error("No contract offer at index {0}")]
+    NoSuchOffer(usize),
+    #[error("No contract is underway")]
+    NoActiveContract,
+}
+
+/// An order to forge one item at the anvil from materials the blacksmith supplies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CraftingContract {
+    pub recipe: RecipeId,
+    pub fee: i32,
+    pub xp: u64,
+}
+
+impl CraftingContract {
+    /// A contract for `recipe`, whose output is worth `item_value` gold.
+    pub fn new(recipe: RecipeId, item_value: i32) -> Self {
+        let ingredients: u32 = recipe.spec().ingredients.values().sum();
+        Self {
+            recipe,
+            fee: (item_value * FEE_PERCENT / 100).max(1),
+            xp: ingredients as u64 * XP_PER_INGREDIENT,
+        }
+    }
+
+    pub fn output(&self) -> ItemId {
+        self.recipe.spec().output
+    }
+}
+
+/// Today's offers and the contract the player is working on.
+#[derive(Resource, Debug, Default)]
+pub struct BlacksmithContracts {
+    pub offers: Vec<CraftingContract>,
+    pub active: Option<CraftingContract>,
+    refreshed_on: Option<u32>,
+}
+
+impl BlacksmithContracts {
+    /// Whether the offers were already rolled for `day`.
+    pub fn is_fresh(&self, day: u32) -> bool {
+        self.refreshed_on == Some(day)
+    }
+
+    /// Replaces the offers with up to [`CONTRACT_OFFERS`] picked from `recipes`.
+    pub fn refresh(
+        &mut self,
+        day: u32,
+        recipes: &[RecipeId],
+        item_value: impl Fn(ItemId) -> i32,
+        rng: &mut impl Rng,
+    ) {
+        self.refreshed_on = Some(day);
+        self.offers = recipes
+            .choose_multiple(rng, CONTRACT_OFFERS)
+            .map(|&recipe| CraftingContract::new(recipe, item_value(recipe.spec().output)))
+            .collect();
+    }
+
+    /// Takes on the offer at `index`. Only one contract can be underway at a time.
+    pub fn accept(&mut self, index: usize) -> Result<CraftingContract, ContractError> {
+        if self.active.is_some() {
+            return Err(ContractError::AlreadyActive);
+        }
+        if index >= self.offers.len() {
+            return Err(ContractError::NoSuchOffer(index));
+        }
+        let contract = self.offers.remove(index);
+        self.active = Some(contract);
+        Ok(contract)
+    }
+
+    /// Ends the contract underway, whether delivered or abandoned.
+    pub fn finish(&mut self) -> Result<CraftingContract, ContractError> {
+        self.active.take().ok_or(ContractError::NoActiveContract)
+    }
+}
+
+/// Something the player asked the blacksmith for.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractRequest {
+    /// Take on the offer at this index and receive its materials.
+    Accept(usize),
+    /// Hand over the finished item for the fee.
+    Deliver,
+    /// Give up the contract, handing the materials back.
+    Abandon,
+}
+
+pub struct ContractsPlugin;
+
+impl Plugin for ContractsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BlacksmithContracts>()
+            .add_message::<ContractRequest>()
+            .add_systems(
+                Update,
+                (
+                    rotate_contracts,
+                    handle_contract_requests.run_if(on_message::<ContractRequest>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// Rolls new offers each day from the anvil recipes the player's standing and the town's
+/// blacksmith allow.
+fn rotate_contracts(
+    clock: Res<GameClock>,
+    mut contracts: ResMut<BlacksmithContracts>,
+    reputation: Res<Reputation>,
+    town_state: Res<TownState>,
+    town: Res<CurrentTown>,
+    registry: Option<Res<ItemRegistry>>,
+) {
+    let Some(registry) = registry else {
+        return;
+    };
+    if contracts.is_fresh(clock.day()) {
+        return;
+    }
+
+    let recipes: Vec<RecipeId> = RecipeId::all_forging_recipes()
+        .iter()
+        .copied()
+        .filter(|&recipe| {
+            reputation.recipe_unlocked(recipe) && town_state.recipe_unlocked(town.0, recipe)
+        })
+        .collect();
+    contracts.refresh(
+        clock.day(),
+        &recipes,
+        |item_id| registry.get(item_id).gold_value,
+        &mut rand::thread_rng(),
+    );
+}
+
+/// The Blacksmith XP and Miners' Guild standing a delivered contract pays out.
+#[derive(SystemParam)]
+struct ContractRewards<'w> {
+    xp_events: MessageWriter<'w, SkillXpGained>,
+    reputation_writer: MessageWriter<'w, ReputationGained>,
+}
+
+fn handle_contract_requests(
+    mut requests: MessageReader<ContractRequest>,
+    mut contracts: ResMut<BlacksmithContracts>,
+    mut player: Query<(&mut Wallet, &mut Inventory), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    rewards: ContractRewards,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let ContractRewards {
+        mut xp_events,
+        mut reputation_writer,
+    } = rewards;
+    let Ok((mut wallet, mut inventory)) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        let message = match *request {
+            ContractRequest::Accept(index) => {
+                let contract = match contracts.accept(index) {
+                    Ok(contract) => contract,
+                    Err(ContractError::AlreadyActive) => {
+                        toast_writer.write(ShowToast::new(loc.get("contracts.already_active")));
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("{e}");
+                        continue;
+                    }
+                };
+
+                // Hand over every material or none, so a full backpack can't strand half.
+                let mut supplied = inventory.clone();
+                let fits = contract.recipe.spec().ingredients.iter().all(|(&id, &qty)| {
+                    (0..qty).all(|_| supplied.add_to_inv(registry.spawn(id)).is_ok())
+                });
+                if !fits {
+                    contracts.active = None;
+                    contracts.offers.insert(index, contract);
+                    toast_writer.write(ShowToast::new(loc.get("contracts.inventory_full")));
+                    continue;
+                }
+                *inventory = supplied;
+
+                let name = output_name(&loc, &registry, contract.output());
+                loc.format("contracts.accepted", &[("item", &name), ("fee", &contract.fee)])
+            }
+            ContractRequest::Deliver => {
+                let Some(contract) = contracts.active else {
+                    continue;
+                };
+                let output = contract.output();
+                let name = output_name(&loc, &registry, output);
+                let crafted = inventory
+                    .get_inventory_items()
+                    .iter()
+                    .find(|inv| inv.item.item_id == output && !inv.item.is_locked)
+                    .map(|inv| inv.uuid());
+                let Some(uuid) = crafted else {
+                    toast_writer.write(ShowToast::new(
+                        loc.format("contracts.not_crafted", &[("item", &name)]),
+                    ));
+                    continue;
+                };
+
+                inventory.remove_item(uuid);
+                let _ = contracts.finish();
+                wallet.add(Currency::Gold, contract.fee);
+                xp_events.write(SkillXpGained {
+                    skill: SkillType::Blacksmith,
+                    amount: contract.xp,
+                });
+                reputation_writer.write(ReputationGained {
+                    faction: FactionId::MinersGuild,
+                    amount: CONTRACT_REPUTATION,
+                });
+                loc.format(
+                    "contracts.delivered",
+                    &[
+                        ("item", &name),
+                        ("fee", &contract.fee),
+                        ("xp", &contract.xp),
+                        ("reputation", &CONTRACT_REPUTATION),
+                        ("faction", &loc.get(FactionId::MinersGuild.name_key())),
+                    ],
+                )
+            }
+            ContractRequest::Abandon => {
+                let Some(contract) = contracts.active else {
+                    continue;
+                };
+                let Ok(recipe) = Recipe::new(contract.recipe) else {
+                    continue;
+                };
+                if !recipe.can_craft(&*inventory) {
+                    toast_writer.write(ShowToast::new(loc.get("contracts.materials_missing")));
+                    continue;
+                }
+
+                for (&item_id, &qty) in recipe.ingredients() {
+                    inventory.decrease_item_quantity(item_id, qty);
+                }
+                let _ = contracts.finish();
+                let name = output_name(&loc, &registry, contract.output());
+                loc.format("contracts.abandoned", &[("item", &name)])
+            }
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+fn output_name(loc: &Localization, registry: &ItemRegistry, item_id: ItemId) -> String {
+    loc.item_name(item_id, &registry.get(item_id).name).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn fee_and_xp_scale_with_the_commission() {
+        let contract = CraftingContract::new(RecipeId::CopperSword, 200);
+
+        assert_eq!(contract.fee, 200 * FEE_PERCENT / 100);
+        assert_eq!(contract.xp, 4 * XP_PER_INGREDIENT);
+        assert_eq!(contract.output(), ItemId::CopperSword);
+        assert_eq!(CraftingContract::new(RecipeId::CopperSword, 0).fee, 1);
+    }
+
+    #[test]
+    fn refresh_offers_distinct_recipes_once_per_day() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut contracts = BlacksmithContracts::default();
+        contracts.refresh(2, RecipeId::all_forging_recipes(), |_| 100, &mut rng);
+
+        assert!(contracts.is_fresh(2));
+        assert!(!contracts.is_fresh(3));
+        assert_eq!(contracts.offers.len(), CONTRACT_OFFERS);
+        for offer in &contracts.offers {
+            assert_eq!(contracts.offers.iter().filter(|c| c.recipe == offer.recipe).count(), 1);
+        }
+    }
+
+    #[test]
+    fn only_one_contract_at_a_time() {
+        let mut contracts = BlacksmithContracts {
+            offers: vec![
+                CraftingContract::new(RecipeId::CopperSword, 100),
+                CraftingContract::new(RecipeId::IronSword, 100),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(contracts.accept(5), Err(ContractError::NoSuchOffer(5)));
+        assert_eq!(contracts.accept(0).map(|c| c.recipe), Ok(RecipeId::CopperSword));
+        assert_eq!(contracts.accept(0), Err(ContractError::AlreadyActive));
+        assert_eq!(contracts.offers.len(), 1);
+
+        assert!(contracts.finish().is_ok());
+        assert_eq!(contracts.finish(), Err(ContractError::NoActiveContract));
+    }
+}
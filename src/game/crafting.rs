@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
+use crate::faction::{FactionId, FactionRank, Reputation};
 use crate::inventory::{Inventory, ManagesItems};
 use crate::item::recipe::{Recipe, RecipeId};
 use crate::item::ItemRegistry;
+use crate::location::{CurrentTown, TownState};
 use crate::player::PlayerMarker;
 
 #[derive(Message, Debug, Clone)]
@@ -14,6 +16,14 @@ pub struct BrewPotionEvent {
 pub enum BrewingResult {
     Success { item_name: String },
     InsufficientIngredients { recipe_name: String },
+    /// The town's alchemist hasn't been upgraded far enough for this potion.
+    Locked { recipe_name: String, level: u32 },
+    /// The faction that teaches this potion doesn't rank the player highly enough yet.
+    FactionLocked {
+        recipe_name: String,
+        faction: FactionId,
+        rank: FactionRank,
+    },
     InventoryFull { item_name: String },
     CraftingFailed { recipe_name: String },
 }
@@ -33,6 +43,9 @@ fn handle_brew_potion(
     mut result_events: MessageWriter<BrewingResult>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     registry: Res<ItemRegistry>,
+    town_state: Res<TownState>,
+    town: Res<CurrentTown>,
+    reputation: Res<Reputation>,
 ) {
     let Ok(mut inventory) = player.single_mut() else {
         return;
@@ -45,6 +58,25 @@ fn handle_brew_potion(
 
         let recipe_name = recipe.name().to_string();
 
+        if !town_state.recipe_unlocked(town.0, event.recipe_id) {
+            result_events.write(BrewingResult::Locked {
+                recipe_name,
+                level: event.recipe_id.tier(),
+            });
+            continue;
+        }
+
+        let faction_lock = FactionId::recipe_requirement(event.recipe_id)
+            .filter(|_| !reputation.recipe_unlocked(event.recipe_id));
+        if let Some((faction, rank)) = faction_lock {
+            result_events.write(BrewingResult::FactionLocked {
+                recipe_name,
+                faction,
+                rank,
+            });
+            continue;
+        }
+
         if !recipe.can_craft(&*inventory) {
             result_events.write(BrewingResult::InsufficientIngredients { recipe_name });
             continue;
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use tracing::instrument;
 
 use crate::crafting_station::{AnvilCraftingState, ForgeCraftingState};
+use crate::game::ItemFound;
 use crate::inventory::{Inventory, ManagesItems};
 use crate::item::ItemRegistry;
 use crate::player::PlayerMarker;
@@ -39,6 +40,7 @@ fn handle_forge_crafting_complete(
     mut xp_events: MessageWriter<SkillXpGained>,
     skills: Res<Skills>,
     mut forge_query: Query<&mut ForgeCraftingState>,
+    registry: Res<ItemRegistry>,
 ) {
     let blacksmith_level = skills
         .skill(SkillType::Blacksmith)
@@ -52,6 +54,7 @@ fn handle_forge_crafting_complete(
             bonus_chance,
             &mut forge_query,
             &mut xp_events,
+            &registry,
         );
     }
 }
@@ -68,6 +71,7 @@ fn process_forge_complete_event(
     bonus_chance: f32,
     forge_query: &mut Query<&mut ForgeCraftingState>,
     xp_events: &mut MessageWriter<SkillXpGained>,
+    registry: &ItemRegistry,
 ) {
     let Ok(mut state) = forge_query.get_mut(entity) else {
         return;
@@ -80,7 +84,7 @@ fn process_forge_complete_event(
     tracing::Span::current().record("coal_qty", coal_qty);
     tracing::Span::current().record("ore_qty", ore_qty);
 
-    state.complete_crafting_with_bonus(bonus_chance);
+    state.complete_crafting_with_bonus(bonus_chance, registry);
 
     tracing::Span::current().record("product_slot_after", state.product_slot.is_some());
 
@@ -95,6 +99,7 @@ fn process_forge_complete_event(
 fn handle_anvil_crafting_complete(
     mut events: MessageReader<AnvilCraftingCompleteEvent>,
     mut xp_events: MessageWriter<SkillXpGained>,
+    mut found_events: MessageWriter<ItemFound>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     skills: Res<Skills>,
     mut anvil_query: Query<&mut AnvilCraftingState>,
@@ -119,7 +124,12 @@ fn handle_anvil_crafting_complete(
 
         let spec = recipe_id.spec();
         let item = registry.spawn(spec.output);
-        let _ = inventory.add_to_inv(item);
+        if inventory.add_to_inv(item).is_ok() {
+            found_events.write(ItemFound {
+                item_id: spec.output,
+                quantity: 1,
+            });
+        }
 
         let ingredient_count: u32 = spec.ingredients.values().sum();
         let xp_amount = 75 + (ingredient_count.saturating_sub(1) * 25);
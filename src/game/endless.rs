@@ -0,0 +1,193 @@
+//! Endless mode: a run down the Endless Depths, one floor after another until the player
+//! leaves or is knocked out. Every run is rolled from a single seed so a record run's
+//! floors can be generated again.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::combat::EntityDied;
+use crate::dungeon::systems::FloorTransitions;
+use crate::dungeon::{
+    handle_floor_transition, DungeonState, FloorId, FloorSeedQueue, FloorTransition,
+    SeededFloors,
+};
+use crate::entities::Progression;
+use crate::game::game_stats::{EndlessRecord, GameStats};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment};
+use crate::location::{CurrentTown, LocationId};
+use crate::player::{PlayerMarker, PlayerName};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+
+/// Request to start an endless run from town.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StartEndlessRun;
+
+/// The endless run in progress.
+#[derive(Resource, Debug)]
+pub struct EndlessRun(pub SeededFloors);
+
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<StartEndlessRun>()
+            .add_systems(
+                Update,
+                start_endless_run
+                    .run_if(on_message::<StartEndlessRun>)
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(
+                Update,
+                (
+                    end_run_on_defeat.run_if(on_message::<EntityDied>),
+                    follow_endless_run
+                        .run_if(on_message::<FloorTransition>)
+                        .before(handle_floor_transition)
+                        .run_if(resource_exists::<EndlessRun>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Dungeon)),
+            );
+    }
+}
+
+/// What starting a run sets up: the town it leaves from, the dungeon it enters, and the
+/// first floor's seed.
+#[derive(SystemParam)]
+struct EndlessStart<'w> {
+    town: Res<'w, CurrentTown>,
+    dungeon: ResMut<'w, DungeonState>,
+    seed_queue: ResMut<'w, FloorSeedQueue>,
+}
+
+fn start_endless_run(
+    mut commands: Commands,
+    mut requests: MessageReader<StartEndlessRun>,
+    run: Option<Res<EndlessRun>>,
+    start: EndlessStart,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let EndlessStart {
+        town,
+        mut dungeon,
+        mut seed_queue,
+    } = start;
+    if requests.read().last().is_none() || run.is_some() {
+        return;
+    }
+    if dungeon.current_location != Some(town.0.spec().home) {
+        toast_writer.write(ShowToast::new(loc.get("endless.not_in_town")));
+        return;
+    }
+
+    let mut run = EndlessRun(SeededFloors::new(rand::random()));
+    run.0.queue_next(&mut seed_queue);
+    dungeon.reset_dungeon();
+    dungeon.exit_dungeon();
+    dungeon.enter_endless(LocationId::EndlessDepths, FloorId::EndlessDepths);
+    toast_writer.write(ShowToast::new(
+        loc.format("endless.started", &[("seed", &run.0.seed())]),
+    ));
+    commands.insert_resource(run);
+    state_requests.write(StateTransitionRequest::Dungeon);
+}
+
+/// Falling on an endless run or challenge ends it: the player is sent home and the run
+/// recorded.
+fn end_run_on_defeat(
+    mut deaths: MessageReader<EntityDied>,
+    dungeon: Res<DungeonState>,
+    mut transition_writer: MessageWriter<FloorTransition>,
+) {
+    if deaths.read().any(|death| death.is_player) && dungeon.is_descending() {
+        transition_writer.write(FloorTransition::ReturnToHome);
+    }
+}
+
+/// The run under way, the floor it has reached, and the leaderboard it ends up on.
+#[derive(SystemParam)]
+struct EndlessProgress<'w, 's> {
+    run: ResMut<'w, EndlessRun>,
+    dungeon: Res<'w, DungeonState>,
+    seed_queue: ResMut<'w, FloorSeedQueue>,
+    stats: ResMut<'w, GameStats>,
+    player: Query<
+        'w,
+        's,
+        (&'static PlayerName, &'static Progression, &'static Inventory),
+        With<PlayerMarker>,
+    >,
+}
+
+/// Queues the next floor's seed on the way down, and records the run once the player
+/// leaves the depths. Runs before the dungeon handles the move so the depth reached is
+/// still the current floor's.
+fn follow_endless_run(
+    mut commands: Commands,
+    mut transitions: FloorTransitions,
+    progress: EndlessProgress,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let EndlessProgress {
+        mut run,
+        dungeon,
+        mut seed_queue,
+        mut stats,
+        player,
+    } = progress;
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if !dungeon.is_endless() {
+        commands.remove_resource::<EndlessRun>();
+        return;
+    }
+    if matches!(transition, FloorTransition::AdvanceFloor) {
+        run.0.queue_next(&mut seed_queue);
+        return;
+    }
+
+    let Ok((name, progression, inventory)) = player.single() else {
+        return;
+    };
+    let depth = dungeon.depth();
+    let weapon = equipped_weapon_name(inventory, &loc);
+    let previous_best = stats.best_depth;
+    let place = stats.record_endless(EndlessRecord {
+        depth,
+        seed: run.0.seed(),
+        name: name.0.clone(),
+        level: progression.level,
+        weapon,
+    });
+    seed_queue.0.clear();
+    commands.remove_resource::<EndlessRun>();
+
+    let key = if depth > previous_best {
+        "endless.new_best"
+    } else if place.is_some() {
+        "endless.ranked"
+    } else {
+        "endless.ended"
+    };
+    let place = place.map_or(0, |place| place + 1);
+    toast_writer.write(ShowToast::new(
+        loc.format(key, &[("depth", &depth), ("place", &place)]),
+    ));
+}
+
+/// Display name of the player's weapon, for run records.
+pub(crate) fn equipped_weapon_name(inventory: &Inventory, loc: &Localization) -> Option<String> {
+    inventory
+        .get_equipped_item(EquipmentSlot::Weapon)
+        .map(|equipped| {
+            loc.item_name(equipped.item.item_id, &equipped.item.name)
+                .to_string()
+        })
+}
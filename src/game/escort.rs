@@ -0,0 +1,366 @@
+//! Caravan escort contracts: the player walks a caravan to another town across a
+//! generated road of ambush floors, protecting its cargo for a payout.
+
+use std::collections::HashMap;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::dungeon::systems::{FloorTransitions, TransitionInProgress};
+use crate::dungeon::{handle_floor_transition, DungeonState, FloorId, FloorTransition, MobEntity};
+use crate::economy::{Currency, Wallet};
+use crate::faction::{FactionId, ReputationGained};
+use crate::game::{IrreversibleAction, ShowToast, Storage, TownStorages, UndoHistory};
+use crate::i18n::Localization;
+use crate::location::{CurrentTown, LocationId, TownId, TravelRoute};
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::DyingMob;
+
+/// Road covered by each ambush leg.
+pub const LEAGUES_PER_LEG: f32 = 10.0;
+
+/// Chance that a leg is a warband rather than a small ambush.
+pub const WARBAND_CHANCE: f64 = 0.35;
+
+/// Gold paid per point of route danger for delivering undamaged cargo.
+pub const PAY_PER_DANGER: i32 = 40;
+
+pub const CARGO_HP: f32 = 100.0;
+
+/// Cargo lost each second to every raider still standing on the current leg.
+pub const RAIDER_DAMAGE_PER_SECOND: f32 = 1.5;
+
+/// Cargo lost for every raider left standing when the caravan moves on.
+pub const RAIDER_LEFT_BEHIND_DAMAGE: f32 = 10.0;
+
+/// Patron reputation per point of route danger on delivery. The patron's rival loses a
+/// third as much.
+pub const REPUTATION_PER_DANGER: i32 = 15;
+
+/// A caravan run offered between two towns on behalf of a faction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscortContract {
+    pub route: TravelRoute,
+    pub legs: Vec<FloorId>,
+    pub patron: FactionId,
+}
+
+impl EscortContract {
+    /// Rolls a patron and one ambush floor per [`LEAGUES_PER_LEG`] of road, at least one.
+    pub fn generate(route: TravelRoute, rng: &mut impl Rng) -> Self {
+        let patron = FactionId::ALL[rng.gen_range(0..FactionId::ALL.len())];
+        let leg_count = (route.distance / LEAGUES_PER_LEG).ceil().max(1.0) as usize;
+        let legs = (0..leg_count)
+            .map(|_| {
+                if rng.gen_bool(WARBAND_CHANCE) {
+                    FloorId::RoadWarband
+                } else {
+                    FloorId::RoadAmbush
+                }
+            })
+            .collect();
+        Self {
+            route,
+            legs,
+            patron,
+        }
+    }
+
+    pub fn danger(&self) -> u32 {
+        self.legs
+            .iter()
+            .map(|leg| match leg {
+                FloorId::RoadWarband => 2,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    /// Full pay for delivering undamaged cargo.
+    pub fn payout(&self) -> i32 {
+        self.danger() as i32 * PAY_PER_DANGER
+    }
+
+    /// Pay for delivering with `cargo` hit points left, scaled down with the damage taken.
+    pub fn payout_for(&self, cargo: f32) -> i32 {
+        (self.payout() as f32 * (cargo / CARGO_HP).clamp(0.0, 1.0)).round() as i32
+    }
+
+    /// Standing changes on delivery: the patron's gain and its rival's loss.
+    pub fn reputation_changes(&self) -> [ReputationGained; 2] {
+        let gain = self.danger() as i32 * REPUTATION_PER_DANGER;
+        [
+            ReputationGained {
+                faction: self.patron,
+                amount: gain,
+            },
+            ReputationGained {
+                faction: self.patron.spec().rival,
+                amount: -gain / 3,
+            },
+        ]
+    }
+}
+
+/// The escort in progress. The leg being walked is the dungeon's floor index.
+#[derive(Resource, Debug)]
+pub struct Escort {
+    pub contract: EscortContract,
+    pub cargo: f32,
+}
+
+impl Escort {
+    pub fn new(contract: EscortContract) -> Self {
+        Self {
+            contract,
+            cargo: CARGO_HP,
+        }
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.cargo = (self.cargo - amount).max(0.0);
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.cargo <= 0.0
+    }
+}
+
+/// Contracts on offer from the current town, keyed by destination.
+#[derive(Resource, Debug, Default)]
+pub struct EscortOffers(pub HashMap<TownId, EscortContract>);
+
+impl EscortOffers {
+    /// Rolls a fresh contract to every other town.
+    pub fn refresh(&mut self, from: TownId) {
+        let mut rng = rand::thread_rng();
+        self.0 = TownId::ALL
+            .iter()
+            .filter(|&&to| to != from)
+            .map(|&to| (to, EscortContract::generate(TravelRoute::new(from, to), &mut rng)))
+            .collect();
+    }
+}
+
+/// Request to take the offered contract to `to`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EscortRequest {
+    pub to: TownId,
+}
+
+pub struct EscortPlugin;
+
+impl Plugin for EscortPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EscortOffers>()
+            .add_message::<EscortRequest>()
+            .add_systems(
+                Update,
+                start_escort
+                    .run_if(on_message::<EscortRequest>)
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(
+                Update,
+                (
+                    follow_escort_route
+                        .run_if(on_message::<FloorTransition>)
+                        .before(handle_floor_transition),
+                    raid_cargo.run_if(not(resource_exists::<TransitionInProgress>)),
+                )
+                    .chain()
+                    .run_if(resource_exists::<Escort>)
+                    .run_if(in_state(AppState::Dungeon)),
+            );
+    }
+}
+
+/// The contracts on offer, and the one under way if any.
+#[derive(SystemParam)]
+struct EscortContracts<'w> {
+    offers: Res<'w, EscortOffers>,
+    active: Option<Res<'w, Escort>>,
+}
+
+fn start_escort(
+    mut commands: Commands,
+    mut requests: MessageReader<EscortRequest>,
+    contracts: EscortContracts,
+    mut dungeon: ResMut<DungeonState>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+    if contracts.active.is_some() {
+        return;
+    }
+    let Some(contract) = contracts.offers.0.get(&request.to) else {
+        return;
+    };
+
+    dungeon.enter_route(LocationId::CaravanRoad, contract.legs.clone());
+    commands.insert_resource(Escort::new(contract.clone()));
+    toast_writer.write(ShowToast::new(loc.format(
+        "escort.departed",
+        &[("town", &loc.get(request.to.name_key()))],
+    )));
+    state_requests.write(StateTransitionRequest::Dungeon);
+}
+
+/// Settles each move along the road. Runs before the dungeon handles the transition so an
+/// arrival has already moved the player's home when the last leg leads "home".
+/// The escort under way, and what delivering its cargo pays out and moves.
+#[derive(SystemParam)]
+struct EscortDelivery<'w, 's> {
+    escort: ResMut<'w, Escort>,
+    player: Query<'w, 's, &'static mut Wallet, With<PlayerMarker>>,
+    town: ResMut<'w, CurrentTown>,
+    storage: ResMut<'w, Storage>,
+    town_storages: ResMut<'w, TownStorages>,
+    history: ResMut<'w, UndoHistory>,
+    reputation_writer: MessageWriter<'w, ReputationGained>,
+}
+
+fn follow_escort_route(
+    mut commands: Commands,
+    mut transitions: FloorTransitions,
+    dungeon: Res<DungeonState>,
+    raiders: Query<(), (With<MobEntity>, Without<DyingMob>)>,
+    delivery: EscortDelivery,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let EscortDelivery {
+        mut escort,
+        mut player,
+        mut town,
+        mut storage,
+        mut town_storages,
+        mut history,
+        mut reputation_writer,
+    } = delivery;
+
+    for transition in transitions.read() {
+        match transition {
+            FloorTransition::AdvanceFloor => {
+                escort.damage(raiders.iter().count() as f32 * RAIDER_LEFT_BEHIND_DAMAGE);
+                if !dungeon.is_current_floor_final() || escort.is_lost() {
+                    continue;
+                }
+
+                let payout = escort.contract.payout_for(escort.cargo);
+                if let Ok(mut wallet) = player.single_mut() {
+                    wallet.add(Currency::Gold, payout);
+                }
+                let to = escort.contract.route.to;
+                town_storages.move_to(to, &mut town, &mut storage);
+                history.record_irreversible(IrreversibleAction::Travel);
+                reputation_writer.write_batch(escort.contract.reputation_changes());
+                commands.remove_resource::<Escort>();
+                toast_writer.write(ShowToast::new(loc.format(
+                    "escort.delivered",
+                    &[("town", &loc.get(to.name_key())), ("gold", &payout)],
+                )));
+                return;
+            }
+            FloorTransition::EnterDoor | FloorTransition::ReturnToHome => {
+                commands.remove_resource::<Escort>();
+                toast_writer.write(ShowToast::new(loc.get("escort.abandoned")));
+                return;
+            }
+        }
+    }
+}
+
+/// Raiders left standing wear the cargo down. When it is gone the contract fails and the
+/// current leg becomes the last, so its stairs lead back home.
+fn raid_cargo(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut escort: ResMut<Escort>,
+    mut dungeon: ResMut<DungeonState>,
+    raiders: Query<(), (With<MobEntity>, Without<DyingMob>)>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let raider_count = raiders.iter().count() as f32;
+    escort.damage(raider_count * RAIDER_DAMAGE_PER_SECOND * time.delta_secs());
+    if !escort.is_lost() {
+        return;
+    }
+
+    let leg = dungeon.floor_index;
+    dungeon.floor_sequence.truncate(leg + 1);
+    commands.remove_resource::<Escort>();
+    toast_writer.write(ShowToast::new(loc.get("escort.failed")));
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn legs_scale_with_route_length() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let route = TravelRoute::new(TownId::Village, TownId::Outpost);
+        let contract = EscortContract::generate(route, &mut rng);
+
+        assert_eq!(contract.legs.len(), 3);
+        assert!((3..=6).contains(&contract.danger()));
+    }
+
+    #[test]
+    fn payout_scales_with_danger_and_cargo() {
+        let route = TravelRoute::new(TownId::Village, TownId::Outpost);
+        let contract = EscortContract {
+            route,
+            legs: vec![FloorId::RoadAmbush, FloorId::RoadWarband],
+            patron: FactionId::MerchantsLeague,
+        };
+
+        assert_eq!(contract.danger(), 3);
+        assert_eq!(contract.payout(), 3 * PAY_PER_DANGER);
+        assert_eq!(contract.payout_for(CARGO_HP / 2.0), 3 * PAY_PER_DANGER / 2);
+        assert_eq!(contract.payout_for(0.0), 0);
+    }
+
+    #[test]
+    fn cargo_never_drops_below_zero() {
+        let route = TravelRoute::new(TownId::Village, TownId::Outpost);
+        let mut escort = Escort::new(EscortContract {
+            route,
+            legs: vec![FloorId::RoadAmbush],
+            patron: FactionId::MinersGuild,
+        });
+
+        escort.damage(40.0);
+        assert!(!escort.is_lost());
+        escort.damage(500.0);
+        assert_eq!(escort.cargo, 0.0);
+        assert!(escort.is_lost());
+    }
+
+    #[test]
+    fn delivery_favors_the_patron_over_its_rival() {
+        let route = TravelRoute::new(TownId::Village, TownId::Outpost);
+        let contract = EscortContract {
+            route,
+            legs: vec![FloorId::RoadWarband],
+            patron: FactionId::MagesCircle,
+        };
+
+        let [patron, rival] = contract.reputation_changes();
+        assert_eq!(patron.faction, FactionId::MagesCircle);
+        assert_eq!(patron.amount, 2 * REPUTATION_PER_DANGER);
+        assert_eq!(rival.faction, FactionId::MinersGuild);
+        assert_eq!(rival.amount, -(2 * REPUTATION_PER_DANGER) / 3);
+    }
+}
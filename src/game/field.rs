@@ -0,0 +1,50 @@
+//! Exploring the open fields around a town: the player walks the field like a dungeon
+//! floor, gathering herbs and fighting roaming mobs when they cross paths.
+
+use bevy::prelude::*;
+
+use crate::dungeon::{DungeonRegistry, DungeonState};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::location::CurrentTown;
+use crate::states::{ScreenSet, StateTransitionRequest};
+
+/// Request to head out into the current town's field.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ExploreFieldRequest;
+
+pub struct FieldPlugin;
+
+impl Plugin for FieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ExploreFieldRequest>().add_systems(
+            Update,
+            explore_field
+                .run_if(on_message::<ExploreFieldRequest>)
+                .in_set(ScreenSet::Logic),
+        );
+    }
+}
+
+fn explore_field(
+    mut requests: MessageReader<ExploreFieldRequest>,
+    town: Res<CurrentTown>,
+    registry: Res<DungeonRegistry>,
+    mut dungeon: ResMut<DungeonState>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    if requests.read().last().is_none() {
+        return;
+    }
+    let Some(field) = town.0.spec().field else {
+        toast_writer.write(ShowToast::new(loc.get("field.none")));
+        return;
+    };
+
+    dungeon.reset_dungeon();
+    dungeon.exit_dungeon();
+    dungeon.enter_dungeon(field, &registry);
+    state_requests.write(StateTransitionRequest::Dungeon);
+}
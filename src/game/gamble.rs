@@ -0,0 +1,196 @@
+//! Gambling at the merchant.
+//!
+//! The player pays a flat fee for an unidentified item. The merchant first picks an item
+//! category, then an item from it, and Magic Find and Gold Find tilt the quality roll.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::combat::{player_effective_goldfind, player_effective_magicfind};
+use crate::economy::{Currency, Wallet, WorthGold};
+use crate::game::{IrreversibleAction, ShowToast, UndoHistory};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ItemCategory, ManagesItems};
+use crate::item::enums::ItemQuality;
+use crate::item::{ItemId, ItemRegistry};
+use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::stats::StatSheet;
+
+/// Gold paid per gamble.
+pub const GAMBLE_COST: i32 = 100;
+
+/// Most a gamble's quality roll can be raised by, out of 100.
+pub const MAX_QUALITY_BONUS: i32 = 30;
+
+/// Categories a gamble can land on, each equally likely.
+const GAMBLE_CATEGORIES: [ItemCategory; 3] = [
+    ItemCategory::Equipment,
+    ItemCategory::Materials,
+    ItemCategory::Consumables,
+];
+
+/// How far Magic Find and Gold Find raise a gamble's quality roll.
+pub fn quality_bonus(magic_find: i32, gold_find: i32) -> i32 {
+    (magic_find / 2 + gold_find / 4).clamp(0, MAX_QUALITY_BONUS)
+}
+
+/// Lifetime gambling totals, with items counted at their list value.
+#[derive(Resource, Debug, Default)]
+pub struct GambleLedger {
+    pub plays: u32,
+    pub spent: i32,
+    pub won: i32,
+}
+
+impl GambleLedger {
+    pub fn record(&mut self, cost: i32, value: i32) {
+        self.plays += 1;
+        self.spent += cost;
+        self.won += value;
+    }
+
+    /// What gambling has earned, or lost when negative.
+    pub fn net(&self) -> i32 {
+        self.won - self.spent
+    }
+}
+
+/// The player asked the merchant for a mystery item.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GambleRequest;
+
+/// A paid-for gamble and the item it turned up.
+#[derive(Message, Debug, Clone)]
+pub struct GambleOutcome {
+    pub item_id: ItemId,
+    pub quality: ItemQuality,
+    pub value: i32,
+}
+
+pub struct GamblePlugin;
+
+impl Plugin for GamblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GambleLedger>()
+            .add_message::<GambleRequest>()
+            .add_message::<GambleOutcome>()
+            .add_systems(
+                Update,
+                handle_gamble_requests
+                    .run_if(on_message::<GambleRequest>)
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// Picks a category, then an item from it. Categories with no items are skipped.
+fn roll_item(registry: &ItemRegistry, rng: &mut impl Rng) -> Option<ItemId> {
+    let mut categories = GAMBLE_CATEGORIES;
+    categories.shuffle(rng);
+    categories.iter().find_map(|category| {
+        let items: Vec<ItemId> = ItemId::ALL
+            .iter()
+            .copied()
+            .filter(|&id| {
+                registry
+                    .try_get(id)
+                    .is_some_and(|spec| category.contains(spec.item_type))
+            })
+            .collect();
+        items.choose(rng).copied()
+    })
+}
+
+/// Where a gamble is recorded: the running ledger, and the undo history it can't be taken
+/// back from.
+#[derive(SystemParam)]
+struct GambleRecords<'w> {
+    ledger: ResMut<'w, GambleLedger>,
+    history: ResMut<'w, UndoHistory>,
+}
+
+fn handle_gamble_requests(
+    mut requests: MessageReader<GambleRequest>,
+    mut player: Query<(&mut Wallet, &mut Inventory, &StatSheet), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    records: GambleRecords,
+    loc: Res<Localization>,
+    mut outcome_writer: MessageWriter<GambleOutcome>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let GambleRecords {
+        mut ledger,
+        mut history,
+    } = records;
+    let Ok((mut wallet, mut inventory, stats)) = player.single_mut() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+
+    for _ in requests.read() {
+        if inventory.get_inventory_items().len() >= inventory.max_slots() {
+            toast_writer.write(ShowToast::new(loc.get("gamble.inventory_full")));
+            continue;
+        }
+        if wallet.gold() < GAMBLE_COST {
+            toast_writer.write(ShowToast::new(
+                loc.format("gamble.not_enough_gold", &[("cost", &GAMBLE_COST)]),
+            ));
+            continue;
+        }
+        let Some(item_id) = roll_item(&registry, &mut rng) else {
+            continue;
+        };
+
+        let bonus = quality_bonus(
+            player_effective_magicfind(stats, &inventory),
+            player_effective_goldfind(stats, &inventory),
+        );
+        let item = registry
+            .spawn_with_quality(item_id, ItemQuality::from_roll(rng.gen_range(0..100) + bonus));
+        let outcome = GambleOutcome {
+            item_id,
+            quality: item.quality,
+            value: item.gold_value(),
+        };
+
+        wallet.subtract(Currency::Gold, GAMBLE_COST);
+        let _ = inventory.add_to_inv(item);
+        ledger.record(GAMBLE_COST, outcome.value);
+        history.record_irreversible(IrreversibleAction::Purchase);
+        outcome_writer.write(outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magic_find_counts_double_and_the_bonus_is_capped() {
+        assert_eq!(quality_bonus(0, 0), 0);
+        assert_eq!(quality_bonus(10, 0), 5);
+        assert_eq!(quality_bonus(10, 10), 7);
+        assert_eq!(quality_bonus(500, 500), MAX_QUALITY_BONUS);
+        assert_eq!(quality_bonus(-20, 0), 0);
+    }
+
+    #[test]
+    fn the_bonus_can_lift_a_roll_into_a_better_quality() {
+        assert_eq!(ItemQuality::from_roll(80), ItemQuality::Improved);
+        assert_eq!(ItemQuality::from_roll(80 + MAX_QUALITY_BONUS), ItemQuality::Mythic);
+    }
+
+    #[test]
+    fn ledger_tracks_net_winnings() {
+        let mut ledger = GambleLedger::default();
+        ledger.record(GAMBLE_COST, 40);
+        ledger.record(GAMBLE_COST, 210);
+
+        assert_eq!(ledger.plays, 2);
+        assert_eq!(ledger.net(), 250 - 2 * GAMBLE_COST);
+    }
+}
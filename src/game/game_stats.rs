@@ -0,0 +1,219 @@
+//! Records kept across sessions: the deepest endless run, the local leaderboard and this
+//! week's challenge.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::ghost::RunTrace;
+use crate::game::weekly::IsoWeek;
+use crate::save::{load_ron, save_ron};
+
+/// Where stats are saved, relative to the save directory.
+pub const GAME_STATS_SAVE_PATH: &str = "stats.ron";
+
+/// Runs the endless leaderboard keeps.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// One endless run on the leaderboard: how deep it got, the seed its floors were rolled
+/// from, and the character who ran it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndlessRecord {
+    pub depth: u32,
+    pub seed: u64,
+    pub name: String,
+    pub level: i32,
+    /// Name of the weapon the run was finished with, if any.
+    pub weapon: Option<String>,
+}
+
+/// The best attempt at one week's challenge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyRecord {
+    pub week: IsoWeek,
+    pub floors_cleared: u32,
+    pub completed: bool,
+    pub seed: u64,
+    pub name: String,
+    pub level: i32,
+    pub weapon: Option<String>,
+    /// Per-floor trace of the attempt, raced as a ghost by later attempts.
+    #[serde(default)]
+    pub trace: RunTrace,
+}
+
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameStats {
+    #[serde(default)]
+    pub best_depth: u32,
+    /// Best endless runs, deepest first.
+    #[serde(default)]
+    pub endless_records: Vec<EndlessRecord>,
+    /// Best attempt at the most recently played weekly challenge.
+    #[serde(default)]
+    pub weekly: Option<WeeklyRecord>,
+    /// Latest week whose challenge reward chest has been claimed.
+    #[serde(default)]
+    pub weekly_reward_week: Option<IsoWeek>,
+}
+
+impl GameStats {
+    /// Records a finished endless run. Returns its place on the leaderboard, counting from
+    /// 0, or `None` if it didn't make the board. Ties go to the earlier run.
+    pub fn record_endless(&mut self, record: EndlessRecord) -> Option<usize> {
+        self.best_depth = self.best_depth.max(record.depth);
+
+        let place = self
+            .endless_records
+            .iter()
+            .position(|kept| kept.depth < record.depth)
+            .unwrap_or(self.endless_records.len());
+        if place >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.endless_records.insert(place, record);
+        self.endless_records.truncate(LEADERBOARD_SIZE);
+        Some(place)
+    }
+
+    /// Best attempt at `week`'s challenge, if it has been played.
+    pub fn weekly_best(&self, week: IsoWeek) -> Option<&WeeklyRecord> {
+        self.weekly.as_ref().filter(|record| record.week == week)
+    }
+
+    /// Records an attempt at a weekly challenge, replacing last week's entry. Returns
+    /// whether it beat the week's best: getting further wins, and among attempts that got
+    /// as far, the faster one.
+    pub fn record_weekly(&mut self, record: WeeklyRecord) -> bool {
+        let better = self.weekly_best(record.week).is_none_or(|best| {
+            let reached = (record.completed, record.floors_cleared);
+            let best_reached = (best.completed, best.floors_cleared);
+            reached > best_reached
+                || (reached == best_reached
+                    && !record.trace.is_empty()
+                    && (best.trace.is_empty()
+                        || record.trace.total_seconds() < best.trace.total_seconds()))
+        });
+        if better {
+            self.weekly = Some(record);
+        }
+        better
+    }
+
+    pub fn weekly_reward_claimed(&self, week: IsoWeek) -> bool {
+        self.weekly_reward_week == Some(week)
+    }
+
+    pub fn claim_weekly_reward(&mut self, week: IsoWeek) {
+        self.weekly_reward_week = Some(week);
+    }
+}
+
+pub struct GameStatsPlugin;
+
+impl Plugin for GameStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<GameStats>(GAME_STATS_SAVE_PATH))
+            .add_systems(
+                Update,
+                save_stats
+                    .run_if(resource_changed::<GameStats>.and(not(resource_added::<GameStats>))),
+            );
+    }
+}
+
+fn save_stats(stats: Res<GameStats>) {
+    if let Err(e) = save_ron(GAME_STATS_SAVE_PATH, &*stats) {
+        warn!("{e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ghost::FloorSplit;
+    use crate::save::{from_ron, to_ron};
+
+    fn run(depth: u32) -> EndlessRecord {
+        EndlessRecord {
+            depth,
+            seed: depth as u64,
+            name: "Drew".to_string(),
+            level: 5,
+            weapon: Some("Sword".to_string()),
+        }
+    }
+
+    #[test]
+    fn leaderboard_stays_sorted_and_capped() {
+        let mut stats = GameStats::default();
+        for depth in 1..=LEADERBOARD_SIZE as u32 {
+            stats.record_endless(run(depth));
+        }
+        assert_eq!(stats.best_depth, LEADERBOARD_SIZE as u32);
+        assert_eq!(stats.endless_records[0].depth, LEADERBOARD_SIZE as u32);
+
+        assert_eq!(stats.record_endless(run(0)), None);
+        assert_eq!(stats.record_endless(run(3)), Some(LEADERBOARD_SIZE - 2));
+        assert_eq!(stats.endless_records.len(), LEADERBOARD_SIZE);
+        assert_eq!(stats.endless_records.last().unwrap().depth, 2);
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_run() {
+        let mut stats = GameStats::default();
+        stats.record_endless(run(4));
+        let mut later = run(4);
+        later.seed = 99;
+        assert_eq!(stats.record_endless(later), Some(1));
+        assert_eq!(stats.endless_records[0].seed, 4);
+    }
+
+    fn attempt(week: u32, floors_cleared: u32, completed: bool) -> WeeklyRecord {
+        WeeklyRecord {
+            week: IsoWeek { year: 2026, week },
+            floors_cleared,
+            completed,
+            seed: 1,
+            name: "Drew".to_string(),
+            level: 5,
+            weapon: None,
+            trace: RunTrace::default(),
+        }
+    }
+
+    #[test]
+    fn weekly_entry_keeps_the_best_attempt_of_the_week() {
+        let mut stats = GameStats::default();
+        assert!(stats.record_weekly(attempt(10, 2, false)));
+        assert!(!stats.record_weekly(attempt(10, 1, false)));
+        assert!(stats.record_weekly(attempt(10, 5, true)));
+        assert!(!stats.record_weekly(attempt(10, 5, false)));
+
+        // As far but faster takes the entry, with its trace.
+        let mut faster = attempt(10, 5, true);
+        faster.trace.splits.push(FloorSplit {
+            seconds: 90.0,
+            hp_on_arrival: 100,
+            hp_on_exit: 80,
+            mobs_defeated: 6,
+        });
+        assert!(stats.record_weekly(faster.clone()));
+        faster.trace.splits[0].seconds = 120.0;
+        assert!(!stats.record_weekly(faster));
+
+        // A new week starts the entry over.
+        assert!(stats.record_weekly(attempt(11, 1, false)));
+        assert_eq!(stats.weekly_best(IsoWeek { year: 2026, week: 10 }), None);
+    }
+
+    #[test]
+    fn stats_round_trip_through_ron() {
+        let mut stats = GameStats::default();
+        stats.record_endless(run(7));
+        stats.record_weekly(attempt(3, 4, false));
+        stats.claim_weekly_reward(IsoWeek { year: 2026, week: 3 });
+        let ron = to_ron(&stats).unwrap();
+        assert_eq!(from_ron::<GameStats>(&ron).unwrap(), stats);
+        assert_eq!(from_ron::<GameStats>("()").unwrap(), GameStats::default());
+    }
+}
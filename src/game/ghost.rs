@@ -0,0 +1,188 @@
+//! Ghost data for the weekly challenge: a per-floor trace of each attempt, kept with the
+//! week's best so the next attempt on the same seed can be measured against it.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::dungeon::FloorReady;
+use crate::player::PlayerMarker;
+use crate::plugins::MobDefeated;
+use crate::states::AppState;
+use crate::stats::{StatSheet, StatType};
+
+/// How one floor of a run went.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FloorSplit {
+    pub seconds: f32,
+    pub hp_on_arrival: i32,
+    pub hp_on_exit: i32,
+    pub mobs_defeated: u32,
+}
+
+/// Splits for every floor a run got through, in order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunTrace {
+    pub splits: Vec<FloorSplit>,
+}
+
+impl RunTrace {
+    pub fn is_empty(&self) -> bool {
+        self.splits.is_empty()
+    }
+
+    /// Time spent on the first `floors` floors.
+    pub fn seconds_through(&self, floors: usize) -> f32 {
+        self.splits
+            .iter()
+            .take(floors)
+            .map(|split| split.seconds)
+            .sum()
+    }
+
+    pub fn total_seconds(&self) -> f32 {
+        self.seconds_through(self.splits.len())
+    }
+
+    /// HP the run reached the last of its floors with.
+    pub fn final_floor_hp(&self) -> Option<i32> {
+        self.splits.last().map(|split| split.hp_on_arrival)
+    }
+}
+
+/// The trace of the attempt in progress.
+#[derive(Resource, Debug, Default)]
+pub struct GhostRecorder {
+    pub trace: RunTrace,
+    seconds: f32,
+    hp_on_arrival: i32,
+    mobs_defeated: u32,
+}
+
+impl GhostRecorder {
+    /// Seconds spent on the current floor so far.
+    pub fn floor_seconds(&self) -> f32 {
+        self.seconds
+    }
+
+    pub fn hp_on_arrival(&self) -> i32 {
+        self.hp_on_arrival
+    }
+
+    pub fn start_floor(&mut self, hp: i32) {
+        self.seconds = 0.0;
+        self.hp_on_arrival = hp;
+        self.mobs_defeated = 0;
+    }
+
+    /// Closes the current floor's split and returns it.
+    pub fn finish_floor(&mut self, hp: i32) -> FloorSplit {
+        let split = FloorSplit {
+            seconds: self.seconds,
+            hp_on_arrival: self.hp_on_arrival,
+            hp_on_exit: hp,
+            mobs_defeated: self.mobs_defeated,
+        };
+        self.trace.splits.push(split);
+        split
+    }
+}
+
+/// The best earlier attempt on the same seed, raced against.
+#[derive(Resource, Debug, Clone)]
+pub struct Ghost(pub RunTrace);
+
+impl Ghost {
+    /// How far ahead of the ghost a run is after `ours`, in seconds: positive when ahead,
+    /// `None` once the run has gone further than the ghost did.
+    pub fn lead(&self, ours: &RunTrace) -> Option<f32> {
+        let floors = ours.splits.len();
+        if floors == 0 || floors > self.0.splits.len() {
+            return None;
+        }
+        Some(self.0.seconds_through(floors) - ours.seconds_through(floors))
+    }
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                start_floor_split.run_if(on_message::<FloorReady>),
+                count_split_kills.run_if(on_message::<MobDefeated>),
+                tick_floor_split,
+            )
+                .run_if(resource_exists::<GhostRecorder>)
+                .run_if(in_state(AppState::Dungeon)),
+        );
+    }
+}
+
+fn start_floor_split(
+    mut floors: MessageReader<FloorReady>,
+    mut recorder: ResMut<GhostRecorder>,
+    player: Query<&StatSheet, With<PlayerMarker>>,
+) {
+    if floors.read().last().is_none() {
+        return;
+    }
+    let hp = player
+        .single()
+        .map_or(0, |stats| stats.value(StatType::Health));
+    recorder.start_floor(hp);
+}
+
+fn count_split_kills(mut kills: MessageReader<MobDefeated>, mut recorder: ResMut<GhostRecorder>) {
+    recorder.mobs_defeated += kills.read().count() as u32;
+}
+
+fn tick_floor_split(time: Res<Time>, mut recorder: ResMut<GhostRecorder>) {
+    recorder.seconds += time.delta_secs();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(seconds: &[f32]) -> RunTrace {
+        RunTrace {
+            splits: seconds
+                .iter()
+                .map(|&seconds| FloorSplit {
+                    seconds,
+                    hp_on_arrival: 100,
+                    hp_on_exit: 90,
+                    mobs_defeated: 3,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn recorder_keeps_a_split_per_floor() {
+        let mut recorder = GhostRecorder::default();
+        recorder.start_floor(100);
+        recorder.seconds = 12.5;
+        recorder.mobs_defeated = 4;
+        let split = recorder.finish_floor(70);
+        assert_eq!(split.hp_on_arrival, 100);
+        assert_eq!(split.hp_on_exit, 70);
+
+        recorder.start_floor(70);
+        assert_eq!(recorder.floor_seconds(), 0.0);
+        recorder.finish_floor(50);
+        assert_eq!(recorder.trace.splits.len(), 2);
+        assert_eq!(recorder.trace.final_floor_hp(), Some(70));
+    }
+
+    #[test]
+    fn lead_compares_the_same_floors() {
+        let ghost = Ghost(trace(&[30.0, 40.0]));
+        assert_eq!(ghost.lead(&trace(&[])), None);
+        assert_eq!(ghost.lead(&trace(&[25.0])), Some(5.0));
+        assert_eq!(ghost.lead(&trace(&[25.0, 50.0])), Some(-5.0));
+        assert_eq!(ghost.lead(&trace(&[25.0, 50.0, 10.0])), None);
+    }
+}
@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 use uuid::Uuid;
 
-use crate::inventory::EquipmentSlot;
-use crate::item::ItemId;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::inventory::{EquipmentSlot, Inventory, InventoryError, InventorySort, ManagesItems};
+use crate::loot::LootDrop;
+use crate::item::{ItemDiscovery, ItemId};
+use crate::player::PlayerMarker;
 
 /// Event fired when an item is equipped
 #[derive(Message, Debug, Clone)]
@@ -45,6 +49,37 @@ pub struct ItemPickedUp {
     pub was_stacked: bool,
 }
 
+/// Event fired when the player obtains an item from loot or crafting.
+///
+/// Feeds [`ItemDiscovery`], which the compendium uses to track found items.
+#[derive(Message, Debug, Clone)]
+pub struct ItemFound {
+    pub item_id: ItemId,
+    pub quantity: u32,
+}
+
+impl ItemFound {
+    pub fn from_loot(drops: &[LootDrop]) -> impl Iterator<Item = ItemFound> + '_ {
+        drops.iter().map(|drop| ItemFound {
+            item_id: drop.item.item_id,
+            quantity: drop.quantity.max(0) as u32,
+        })
+    }
+}
+
+/// Command to reorder the player's backpack.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SortInventory {
+    pub sort: InventorySort,
+}
+
+/// Command to move `amount` items off a backpack stack into a new stack.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SplitStack {
+    pub inventory_index: usize,
+    pub amount: u32,
+}
+
 /// Plugin that registers item-related events
 ///
 /// The inventory system is accessed through Player (player.inventory).
@@ -57,6 +92,60 @@ impl Plugin for ItemPlugin {
             .add_message::<ItemUnequipped>()
             .add_message::<ItemUsed>()
             .add_message::<ItemDropped>()
-            .add_message::<ItemPickedUp>();
+            .add_message::<ItemPickedUp>()
+            .add_message::<ItemFound>()
+            .add_message::<SortInventory>()
+            .add_message::<SplitStack>()
+            .init_resource::<ItemDiscovery>()
+            .add_systems(
+                Update,
+                (
+                    record_found_items.run_if(on_message::<ItemFound>),
+                    handle_sort_inventory.run_if(on_message::<SortInventory>),
+                    handle_split_stack.run_if(on_message::<SplitStack>),
+                ),
+            );
+    }
+}
+
+fn record_found_items(mut events: MessageReader<ItemFound>, mut discovery: ResMut<ItemDiscovery>) {
+    for event in events.read() {
+        discovery.record(event.item_id, event.quantity);
+    }
+}
+
+fn handle_sort_inventory(
+    mut events: MessageReader<SortInventory>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+) {
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        inventory.sort_items(event.sort);
+    }
+}
+
+fn handle_split_stack(
+    mut events: MessageReader<SplitStack>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        match inventory.split_stack(event.inventory_index, event.amount) {
+            Ok(_) => {}
+            Err(InventoryError::Full) => {
+                toast_writer.write(ShowToast::new(loc.get("inventory.split_full")));
+            }
+            Err(InventoryError::InvalidSplit) => {
+                warn!("Ignoring invalid split of slot {}", event.inventory_index);
+            }
+        }
     }
 }
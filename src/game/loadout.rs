@@ -0,0 +1,128 @@
+//! Named equipment loadouts on the player, switched with the number keys.
+//!
+//! Switching pulls each item from the backpack, or from storage while the player is home
+//! where the merchant keeps it. A loadout whose gear cannot all be found is not equipped.
+
+use bevy::prelude::*;
+
+use crate::game::{ShowToast, Storage};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::inventory::{Inventory, LoadoutError, Loadouts};
+use crate::location::PlayerLocation;
+use crate::player::PlayerMarker;
+use crate::ui::screens::modal::{ActiveModal, ModalType};
+use crate::ui::UiFeedback;
+
+/// Something the player asked of their loadouts.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadoutRequest {
+    /// Equip the loadout at this index.
+    SwitchLoadout { index: usize },
+    /// Save the equipped gear as the loadout at this index.
+    SaveLoadout { index: usize },
+}
+
+pub struct LoadoutPlugin;
+
+impl Plugin for LoadoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<LoadoutRequest>().add_systems(
+            Update,
+            (
+                request_loadouts.run_if(on_message::<GameAction>),
+                handle_loadout_requests.run_if(on_message::<LoadoutRequest>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// The number keys work outside modals and in the inventory, where the loadouts are shown.
+fn request_loadouts(
+    mut actions: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    mut requests: MessageWriter<LoadoutRequest>,
+) {
+    if !matches!(active_modal.modal, None | Some(ModalType::Inventory)) {
+        return;
+    }
+    for action in actions.read() {
+        match *action {
+            GameAction::SwitchLoadout(index) => {
+                requests.write(LoadoutRequest::SwitchLoadout { index });
+            }
+            GameAction::SaveLoadout(index) => {
+                requests.write(LoadoutRequest::SaveLoadout { index });
+            }
+            _ => {}
+        }
+    }
+}
+
+fn handle_loadout_requests(
+    mut requests: MessageReader<LoadoutRequest>,
+    mut player: Query<(&mut Inventory, &mut Loadouts), With<PlayerMarker>>,
+    mut storage: ResMut<Storage>,
+    location: PlayerLocation,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let Ok((mut inventory, mut loadouts)) = player.single_mut() else {
+        return;
+    };
+    // Storage is kept by the town merchant, so gear can only come out of it at home.
+    let at_home = location.in_town();
+
+    for request in requests.read() {
+        let (LoadoutRequest::SwitchLoadout { index } | LoadoutRequest::SaveLoadout { index }) =
+            *request;
+        let Some(name) = loadouts.get(index).map(|loadout| loadout.name.clone()) else {
+            continue;
+        };
+
+        let result = match *request {
+            LoadoutRequest::SaveLoadout { .. } => loadouts
+                .save(index, &inventory)
+                .map(|()| loc.format("loadout.saved", &[("name", &name)])),
+            LoadoutRequest::SwitchLoadout { .. } => {
+                let stored = if at_home {
+                    Some(&mut storage.inventory)
+                } else {
+                    None
+                };
+                loadouts.switch(index, &mut inventory, stored).map(|taken| {
+                    for uuid in &taken {
+                        storage.forget(*uuid);
+                    }
+                    loc.format("loadout.switched", &[("name", &name)])
+                })
+            }
+        };
+        let message = match result {
+            Ok(message) => message,
+            Err(e) => {
+                feedback.write(UiFeedback::Error);
+                error_text(&loc, &name, &e)
+            }
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+fn error_text(loc: &Localization, name: &str, error: &LoadoutError) -> String {
+    match error {
+        LoadoutError::Unknown(_) | LoadoutError::Empty(_) => {
+            loc.format("loadout.empty", &[("name", &name)])
+        }
+        LoadoutError::Missing(slots) => {
+            let slots: Vec<_> = slots.iter().map(|slot| loc.get(slot.label_key())).collect();
+            loc.format(
+                "loadout.missing",
+                &[("name", &name), ("slots", &slots.join(", "))],
+            )
+        }
+        LoadoutError::BackpackFull => loc.format("loadout.backpack_full", &[("name", &name)]),
+    }
+}
@@ -1,8 +1,16 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::economy::WorthGold;
-use crate::inventory::{Inventory, ManagesItems};
-use crate::player::{PlayerGold, PlayerMarker};
+use crate::economy::{CommodityMarket, Currency, Haggle, Wallet};
+use crate::faction::Reputation;
+use crate::game::{IrreversibleAction, ShowToast, UndoHistory, UndoableAction};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, InventoryItem, ManagesItems};
+use crate::location::PlayerLocation;
+use crate::loot::LootFilter;
+use crate::player::{BankedGold, PlayerMarker};
+use crate::states::ScreenSet;
+use crate::stats::StatSheet;
 use crate::ui::screens::merchant_modal::MerchantStock;
 
 #[derive(Message, Debug, Clone)]
@@ -15,6 +23,18 @@ pub struct SellItemEvent {
     pub inventory_index: usize,
 }
 
+/// Sells every unlocked backpack item below the loot filter's junk threshold.
+#[derive(Message, Debug, Clone)]
+pub struct SellJunkEvent;
+
+/// Moves all carried gold into the bank.
+#[derive(Message, Debug, Clone)]
+pub struct DepositGoldEvent;
+
+/// Moves all banked gold back into the player's pouch.
+#[derive(Message, Debug, Clone)]
+pub struct WithdrawGoldEvent;
+
 #[derive(Message, Debug, Clone)]
 pub enum MerchantTransactionResult {
     BuySuccess { item_name: String, price: i32 },
@@ -24,6 +44,12 @@ pub enum MerchantTransactionResult {
     SellSuccess { item_name: String, price: i32 },
     SellFailedItemLocked,
     SellFailedNoItem,
+    SellJunkSuccess { count: u32, price: i32 },
+    SellJunkFailedNoJunk,
+    DepositSuccess { amount: i32 },
+    WithdrawSuccess { amount: i32 },
+    BankFailedNoGold,
+    BankFailedNotInTown,
 }
 
 pub struct MerchantPlugin;
@@ -32,13 +58,20 @@ impl Plugin for MerchantPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<BuyItemEvent>()
             .add_message::<SellItemEvent>()
+            .add_message::<SellJunkEvent>()
+            .add_message::<DepositGoldEvent>()
+            .add_message::<WithdrawGoldEvent>()
             .add_message::<MerchantTransactionResult>()
             .add_systems(
                 Update,
                 (
                     handle_buy_item.run_if(on_message::<BuyItemEvent>),
                     handle_sell_item.run_if(on_message::<SellItemEvent>),
-                ),
+                    handle_sell_junk.run_if(on_message::<SellJunkEvent>),
+                    handle_deposit_gold.run_if(on_message::<DepositGoldEvent>),
+                    handle_withdraw_gold.run_if(on_message::<WithdrawGoldEvent>),
+                )
+                    .in_set(ScreenSet::Logic),
             );
     }
 }
@@ -46,34 +79,36 @@ impl Plugin for MerchantPlugin {
 fn handle_buy_item(
     mut buy_events: MessageReader<BuyItemEvent>,
     mut result_events: MessageWriter<MerchantTransactionResult>,
-    mut player: Query<(&mut PlayerGold, &mut Inventory), With<PlayerMarker>>,
+    mut player: Query<(&mut Wallet, &mut Inventory, &StatSheet), With<PlayerMarker>>,
     mut stock: Option<ResMut<MerchantStock>>,
+    reputation: Res<Reputation>,
+    mut history: ResMut<UndoHistory>,
 ) {
-    let Ok((mut player_gold, mut inventory)) = player.single_mut() else {
+    let Ok((mut wallet, mut inventory, stats)) = player.single_mut() else {
         return;
     };
+    let haggle = Haggle::for_player(stats, &inventory, &reputation);
     let Some(ref mut stock) = stock else {
         return;
     };
 
     for event in buy_events.read() {
-        let Some(store_item) = stock.items.get_mut(event.stock_index) else {
+        let Some(item) = stock
+            .items
+            .get(event.stock_index)
+            .and_then(|store_item| store_item.display_item())
+        else {
             result_events.write(MerchantTransactionResult::BuyFailedNoItem);
             continue;
         };
 
-        let Some(item) = store_item.display_item() else {
-            result_events.write(MerchantTransactionResult::BuyFailedNoItem);
-            continue;
-        };
-
-        let price = item.purchase_price();
+        let price = stock.buy_price(haggle, item);
         let item_name = item.name.clone();
 
-        if player_gold.0 < price {
+        if wallet.gold() < price {
             result_events.write(MerchantTransactionResult::BuyFailedNotEnoughGold {
                 need: price,
-                have: player_gold.0,
+                have: wallet.gold(),
             });
             continue;
         }
@@ -83,13 +118,14 @@ fn handle_buy_item(
             continue;
         }
 
-        let Some(purchased_item) = store_item.take_item() else {
+        let Some(purchased_item) = stock.items[event.stock_index].take_item() else {
             result_events.write(MerchantTransactionResult::BuyFailedNoItem);
             continue;
         };
 
-        player_gold.subtract(price);
+        wallet.subtract(Currency::Gold, price);
         let _ = inventory.add_to_inv(purchased_item);
+        history.record_irreversible(IrreversibleAction::Purchase);
         result_events.write(MerchantTransactionResult::BuySuccess { item_name, price });
     }
 }
@@ -97,11 +133,15 @@ fn handle_buy_item(
 fn handle_sell_item(
     mut sell_events: MessageReader<SellItemEvent>,
     mut result_events: MessageWriter<MerchantTransactionResult>,
-    mut player: Query<(&mut PlayerGold, &mut Inventory), With<PlayerMarker>>,
+    mut player: Query<(&mut Wallet, &mut Inventory, &StatSheet), With<PlayerMarker>>,
+    reputation: Res<Reputation>,
+    mut market: ResMut<CommodityMarket>,
+    mut history: ResMut<UndoHistory>,
 ) {
-    let Ok((mut player_gold, mut inventory)) = player.single_mut() else {
+    let Ok((mut wallet, mut inventory, stats)) = player.single_mut() else {
         return;
     };
+    let haggle = Haggle::for_player(stats, &inventory, &reputation);
 
     for event in sell_events.read() {
         let inv_items = inventory.get_inventory_items();
@@ -115,15 +155,147 @@ fn handle_sell_item(
             continue;
         }
 
-        let sell_price = inv_item.item.sell_price();
+        let sell_price = market.adjust(&inv_item.item, haggle.sell_price(&inv_item.item));
         let item_name = inv_item.item.name.clone();
         let item_id = inv_item.item.item_id;
+        let sold = inv_item.item.clone();
 
-        player_gold.add(sell_price);
+        wallet.add(Currency::Gold, sell_price);
         inventory.decrease_item_quantity(item_id, 1);
+        market.record_sale(&sold, 1);
+        history.record(UndoableAction::Sell {
+            item: sold,
+            price: sell_price,
+        });
         result_events.write(MerchantTransactionResult::SellSuccess {
             item_name,
             price: sell_price,
         });
     }
 }
+
+/// Item count and total sell price of `items` at the haggled rate and today's market.
+pub fn junk_totals<'a>(
+    items: impl IntoIterator<Item = &'a InventoryItem>,
+    haggle: Haggle,
+    market: &CommodityMarket,
+) -> (u32, i32) {
+    items.into_iter().fold((0, 0), |(count, price), inv_item| {
+        let unit_price = market.adjust(&inv_item.item, haggle.sell_price(&inv_item.item));
+        (
+            count + inv_item.quantity,
+            price + unit_price * inv_item.quantity as i32,
+        )
+    })
+}
+
+/// What a junk sale is priced from: the loot filter's junk threshold, the standing the player
+/// haggles with, and the market the junk is sold into.
+#[derive(SystemParam)]
+struct JunkSale<'w> {
+    filter: Res<'w, LootFilter>,
+    reputation: Res<'w, Reputation>,
+    market: ResMut<'w, CommodityMarket>,
+}
+
+fn handle_sell_junk(
+    mut events: MessageReader<SellJunkEvent>,
+    mut result_events: MessageWriter<MerchantTransactionResult>,
+    mut player: Query<(&mut Wallet, &mut Inventory, &StatSheet), With<PlayerMarker>>,
+    sale: JunkSale,
+    mut history: ResMut<UndoHistory>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let JunkSale {
+        filter,
+        reputation,
+        mut market,
+    } = sale;
+    let Ok((mut wallet, mut inventory, stats)) = player.single_mut() else {
+        return;
+    };
+    let haggle = Haggle::for_player(stats, &inventory, &reputation);
+
+    for _ in events.read() {
+        let junk = inventory.take_junk(filter.junk_threshold());
+        if junk.is_empty() {
+            toast_writer.write(ShowToast::new(loc.get("merchant.no_junk")));
+            result_events.write(MerchantTransactionResult::SellJunkFailedNoJunk);
+            continue;
+        }
+
+        let (count, price) = junk_totals(&junk, haggle, &market);
+        for inv_item in &junk {
+            market.record_sale(&inv_item.item, inv_item.quantity);
+        }
+        wallet.add(Currency::Gold, price);
+        history.record_irreversible(IrreversibleAction::BulkSale);
+        toast_writer.write(ShowToast::new(
+            loc.format("merchant.sold_junk", &[("count", &count), ("gold", &price)]),
+        ));
+        result_events.write(MerchantTransactionResult::SellJunkSuccess { count, price });
+    }
+}
+
+fn handle_deposit_gold(
+    mut deposit_events: MessageReader<DepositGoldEvent>,
+    mut result_events: MessageWriter<MerchantTransactionResult>,
+    mut player: Query<(&mut Wallet, &mut BankedGold), With<PlayerMarker>>,
+    mut history: ResMut<UndoHistory>,
+    location: PlayerLocation,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok((mut wallet, mut banked)) = player.single_mut() else {
+        return;
+    };
+
+    for _ in deposit_events.read() {
+        // Dungeon and traveling merchants trade, but only the town keeps a bank.
+        if !location.in_town() {
+            toast_writer.write(ShowToast::new(loc.get("merchant.bank_not_in_town")));
+            result_events.write(MerchantTransactionResult::BankFailedNotInTown);
+            continue;
+        }
+        let amount = wallet.gold();
+        match banked.deposit(&mut wallet, amount) {
+            0 => result_events.write(MerchantTransactionResult::BankFailedNoGold),
+            amount => {
+                history.record_irreversible(IrreversibleAction::BankGold);
+                result_events.write(MerchantTransactionResult::DepositSuccess { amount })
+            }
+        };
+    }
+}
+
+fn handle_withdraw_gold(
+    mut withdraw_events: MessageReader<WithdrawGoldEvent>,
+    mut result_events: MessageWriter<MerchantTransactionResult>,
+    mut player: Query<(&mut Wallet, &mut BankedGold), With<PlayerMarker>>,
+    mut history: ResMut<UndoHistory>,
+    location: PlayerLocation,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok((mut wallet, mut banked)) = player.single_mut() else {
+        return;
+    };
+
+    for _ in withdraw_events.read() {
+        // Dungeon and traveling merchants trade, but only the town keeps a bank.
+        if !location.in_town() {
+            toast_writer.write(ShowToast::new(loc.get("merchant.bank_not_in_town")));
+            result_events.write(MerchantTransactionResult::BankFailedNotInTown);
+            continue;
+        }
+        let amount = banked.0;
+        match banked.withdraw(&mut wallet, amount) {
+            0 => result_events.write(MerchantTransactionResult::BankFailedNoGold),
+            amount => {
+                history.record_irreversible(IrreversibleAction::BankGold);
+                result_events.write(MerchantTransactionResult::WithdrawSuccess { amount })
+            }
+        };
+    }
+}
@@ -1,11 +1,16 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::chest::Chest;
-use crate::dungeon::events::{ChestMined, MineableEntityType, MiningResult, RockMined};
-use crate::dungeon::DungeonCommands;
+use crate::data::StatRange;
+use crate::dungeon::events::{
+    ChestMined, HerbGathered, MineableEntityType, MiningResult, RockMined,
+};
+use crate::dungeon::{DungeonCommands, DungeonState, FloorId};
+use crate::game::ItemFound;
 use crate::inventory::Inventory;
-use crate::item::ItemRegistry;
-use crate::loot::{collect_loot_drops, HasLoot};
+use crate::item::{ItemId, ItemRegistry};
+use crate::loot::{collect_loot_drops, HasLoot, LootTable};
 use crate::player::PlayerMarker;
 use crate::rock::Rock;
 use crate::skills::{SkillType, SkillXpGained};
@@ -16,7 +21,8 @@ pub struct MiningPlugin;
 impl Plugin for MiningPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(on_chest_mined)
-            .add_observer(on_rock_mined);
+            .add_observer(on_rock_mined)
+            .add_observer(on_herb_gathered);
     }
 }
 
@@ -24,8 +30,10 @@ fn on_chest_mined(
     trigger: On<ChestMined>,
     mut commands: Commands,
     mut result_events: MessageWriter<MiningResult>,
+    mut found_events: MessageWriter<ItemFound>,
     mut player: Query<(&StatSheet, &mut Inventory), With<PlayerMarker>>,
     registry: Res<ItemRegistry>,
+    dungeon: Res<DungeonState>,
 ) {
     let Ok((stats, mut inventory)) = player.single_mut() else {
         return;
@@ -33,8 +41,14 @@ fn on_chest_mined(
     let event = trigger.event();
     let magic_find = stats.value(StatType::MagicFind);
 
-    let loot_drops = Chest::default().roll_drops(magic_find, &registry);
+    let chest = if dungeon.current_floor() == Some(FloorId::ChallengeVault) {
+        Chest::reward()
+    } else {
+        Chest::default()
+    };
+    let loot_drops = chest.roll_drops(magic_find, &registry);
 
+    found_events.write_batch(ItemFound::from_loot(&loot_drops));
     collect_loot_drops(&mut *inventory, &loot_drops);
     commands.despawn_dungeon_entity(event.entity);
 
@@ -44,14 +58,27 @@ fn on_chest_mined(
     });
 }
 
+/// What mining a rock reports: the result, the ore found, and the Mining XP earned.
+#[derive(SystemParam)]
+struct RockMinedWriters<'w> {
+    result_events: MessageWriter<'w, MiningResult>,
+    found_events: MessageWriter<'w, ItemFound>,
+    xp_events: MessageWriter<'w, SkillXpGained>,
+}
+
 fn on_rock_mined(
     trigger: On<RockMined>,
     mut commands: Commands,
-    mut result_events: MessageWriter<MiningResult>,
-    mut xp_events: MessageWriter<SkillXpGained>,
+    writers: RockMinedWriters,
     mut player: Query<(&StatSheet, &mut Inventory), With<PlayerMarker>>,
     registry: Res<ItemRegistry>,
+    dungeon: Res<DungeonState>,
 ) {
+    let RockMinedWriters {
+        mut result_events,
+        mut found_events,
+        mut xp_events,
+    } = writers;
     let Ok((stats, mut inventory)) = player.single_mut() else {
         return;
     };
@@ -63,8 +90,14 @@ fn on_rock_mined(
         amount: event.rock_type.mining_xp(),
     });
 
-    let loot_drops = Rock::new(event.rock_type).roll_drops(magic_find, &registry);
+    let mut loot_drops = Rock::new(event.rock_type).roll_drops(magic_find, &registry);
+    if let Some(floor_event) = dungeon.floor_event() {
+        for drop in &mut loot_drops {
+            drop.quantity *= floor_event.ore_multiplier();
+        }
+    }
 
+    found_events.write_batch(ItemFound::from_loot(&loot_drops));
     collect_loot_drops(&mut *inventory, &loot_drops);
     commands.despawn_dungeon_entity(event.entity);
 
@@ -73,3 +106,32 @@ fn on_rock_mined(
         loot_drops,
     });
 }
+
+fn on_herb_gathered(
+    trigger: On<HerbGathered>,
+    mut commands: Commands,
+    mut result_events: MessageWriter<MiningResult>,
+    mut found_events: MessageWriter<ItemFound>,
+    mut player: Query<(&StatSheet, &mut Inventory), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+) {
+    let Ok((stats, mut inventory)) = player.single_mut() else {
+        return;
+    };
+    let event = trigger.event();
+    let magic_find = stats.value(StatType::MagicFind);
+
+    let loot_drops = LootTable::new()
+        .with(ItemId::Herb, 1, 1, StatRange(1, 3))
+        .build()
+        .roll_drops(magic_find, &registry);
+
+    found_events.write_batch(ItemFound::from_loot(&loot_drops));
+    collect_loot_drops(&mut *inventory, &loot_drops);
+    commands.despawn_dungeon_entity(event.entity);
+
+    result_events.write(MiningResult {
+        mineable_type: MineableEntityType::Herb,
+        loot_drops,
+    });
+}
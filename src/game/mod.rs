@@ -1,36 +1,87 @@
 pub mod blacksmith;
+pub mod buffs;
+pub mod contracts;
 pub mod crafting;
 pub mod crafting_complete;
+pub mod endless;
+pub mod escort;
+pub mod field;
+pub mod gamble;
+pub mod game_stats;
+pub mod ghost;
 pub mod items;
+pub mod loadout;
 pub mod merchant;
 pub mod mining;
 pub mod npc_interactions;
+pub mod persona;
 pub mod player;
+pub mod rescue;
+pub mod run_planner;
+pub mod save_slots;
+pub mod shared_profile;
+pub mod shrine;
 pub mod storage;
 pub mod store_transactions;
 pub mod toast;
+pub mod town_upgrades;
+pub mod training;
+pub mod travel;
+pub mod undo;
+pub mod weekly;
 
 // Re-export combat types from the combat module
 pub use crate::combat::CombatPlugin;
-pub use items::{ItemDropped, ItemEquipped, ItemPickedUp, ItemPlugin, ItemUnequipped, ItemUsed};
-pub use crate::player::{PlayerGold, PlayerName};
+pub use items::{
+    ItemDropped, ItemEquipped, ItemFound, ItemPickedUp, ItemPlugin, ItemUnequipped, ItemUsed,
+    SortInventory, SplitStack,
+};
+pub use loadout::{LoadoutPlugin, LoadoutRequest};
+pub use escort::{Escort, EscortContract, EscortOffers, EscortPlugin, EscortRequest};
+pub use endless::{EndlessPlugin, EndlessRun, StartEndlessRun};
+pub use field::{ExploreFieldRequest, FieldPlugin};
+pub use gamble::{GambleLedger, GambleOutcome, GamblePlugin, GambleRequest, GAMBLE_COST};
+pub use game_stats::{EndlessRecord, GameStats, GameStatsPlugin, WeeklyRecord, LEADERBOARD_SIZE};
+pub use ghost::{FloorSplit, Ghost, GhostPlugin, GhostRecorder, RunTrace};
+pub use crate::player::{BankedGold, PlayerName};
 pub use crate::storage::Storage;
 pub use player::{GoldChanged, PlayerDamaged, PlayerHealed, PlayerLeveledUp, PlayerPlugin};
 pub use storage::{ItemDeposited, ItemWithdrawn, StoragePlugin};
+pub use rescue::{Rescue, RescuePlugin};
+pub use run_planner::{RunGap, RECOMMENDED_POTIONS};
+pub use save_slots::{
+    CharacterDataExt, CharacterResource, CharacterSave, PlayedAgo, SaveSlotRequest, SaveSlots,
+    SaveSlotsPlugin, MAX_SAVE_SLOTS,
+};
+pub use shared_profile::{ImportedProfile, SharedProfile};
+pub use shrine::{ShrineOffer, ShrinePlugin, UseShrine};
 pub use toast::{ShowToast, ToastPlugin};
+pub use town_upgrades::{TownUpgradeRequest, TownUpgradesPlugin};
+pub use training::{DpsMeter, DpsSummary, TrainingPlugin};
+pub use travel::{Journey, TownStorages, TravelPlugin, TravelRequest, TravelResult};
+pub use undo::{IrreversibleAction, UndoError, UndoHistory, UndoPlugin, UndoableAction};
+pub use weekly::{
+    IsoWeek, StartWeeklyChallenge, WeeklyChallenge, WeeklyPlugin, WeeklyRun, CHALLENGE_FLOORS,
+};
+pub use buffs::{ActiveBuffs, Buff, BuffKind, BuffPlugin};
 pub use blacksmith::{
     calculate_upgrade_cost, BlacksmithPlugin, BlacksmithResult, ForgeRecipeEvent,
-    SmeltRecipeEvent, UpgradeItemEvent, UpgradeQualityEvent,
+    SmeltRecipeEvent, UpgradeItemEvent, UpgradeQualityEvent, UpgradeStreak,
+};
+pub use contracts::{
+    BlacksmithContracts, ContractError, ContractRequest, ContractsPlugin, CraftingContract,
 };
 pub use crafting::{BrewPotionEvent, BrewingResult, CraftingPlugin};
 pub use store_transactions::{
-    StorageDepositEvent, StorageTransactionResult, StorageTransactionsPlugin, StorageWithdrawEvent,
+    StorageDepositAllEvent, StorageDepositEvent, StorageTransactionResult,
+    StorageTransactionsPlugin, StorageWithdrawEvent, StorageWithdrawForRecipeEvent,
 };
 pub use merchant::{
-    BuyItemEvent, MerchantPlugin, MerchantTransactionResult, SellItemEvent,
+    junk_totals, BuyItemEvent, DepositGoldEvent, MerchantPlugin, MerchantTransactionResult,
+    SellItemEvent, SellJunkEvent, WithdrawGoldEvent,
 };
 pub use crafting_complete::{
     AnvilCraftingCompleteEvent, CraftingCompletePlugin, ForgeCraftingCompleteEvent,
 };
 pub use mining::MiningPlugin;
-pub use npc_interactions::NpcInteractionsPlugin;
+pub use npc_interactions::{NpcInteractionsPlugin, OpenTownShop};
@@ -1,19 +1,141 @@
+use bevy::asset::LoadedFolder;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::dungeon::MerchantInteraction;
+use crate::dialogue::{ActiveDialogue, DialogueLibrary, DialogueTree};
+use crate::dungeon::{DungeonState, MerchantInteraction, TravelingMerchantInteraction};
+use crate::faction::Reputation;
+use crate::game::persona::Persona;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
 use crate::item::ItemRegistry;
-use crate::ui::screens::merchant_modal::MerchantStock;
+use crate::location::{CurrentTown, TownState};
+use crate::mob::MobId;
+use crate::ui::screens::merchant_modal::{MerchantStock, TownStocks};
 use crate::ui::screens::modal::{ModalType, OpenModal};
+use crate::world_event::GameClock;
 
 pub struct NpcInteractionsPlugin;
 
 impl Plugin for NpcInteractionsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(on_merchant_interaction);
+        app.add_observer(on_merchant_interaction)
+            .add_observer(on_open_town_shop)
+            .add_observer(on_traveling_merchant_interaction);
     }
 }
 
-fn on_merchant_interaction(_trigger: On<MerchantInteraction>, mut commands: Commands, registry: Res<ItemRegistry>) {
-    commands.insert_resource(MerchantStock::generate(&registry));
+/// Open the town merchant's shop, skipping any conversation.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OpenTownShop;
+
+/// Marks a traveling merchant the player has already traded with. They only open shop once
+/// per floor, and leave with the floor's entities.
+#[derive(Component)]
+struct TradedWith;
+
+/// Talks to the town merchant if they have dialogue, and opens the shop straight away if not.
+fn on_merchant_interaction(
+    _trigger: On<MerchantInteraction>,
+    mut commands: Commands,
+    library: Option<Res<DialogueLibrary>>,
+    folders: Res<Assets<LoadedFolder>>,
+    trees: Res<Assets<DialogueTree>>,
+) {
+    let tree = library.and_then(|library| library.for_npc(MobId::Merchant, &folders, &trees));
+    match tree {
+        Some(tree) => {
+            commands.insert_resource(ActiveDialogue::new(tree));
+            commands.trigger(OpenModal(ModalType::Dialogue));
+        }
+        None => commands.trigger(OpenTownShop),
+    }
+}
+
+/// What widens the town merchant's stock: the player's standing and the town's upgrades.
+#[derive(SystemParam)]
+struct StockTerms<'w> {
+    reputation: Res<'w, Reputation>,
+    town_state: Res<'w, TownState>,
+}
+
+fn on_open_town_shop(
+    _trigger: On<OpenTownShop>,
+    mut commands: Commands,
+    registry: Res<ItemRegistry>,
+    town: Res<CurrentTown>,
+    terms: StockTerms,
+    clock: Res<GameClock>,
+    mut town_stocks: ResMut<TownStocks>,
+) {
+    let StockTerms {
+        reputation,
+        town_state,
+    } = terms;
+    let today = clock.day();
+    let extra_slots = town_state.extra_stock_slots(town.0);
+    let stock = town_stocks.take_fresh(town.0, today).unwrap_or_else(|| {
+        MerchantStock::generate(&registry, town.0, &reputation, today, extra_slots)
+    });
+    commands.insert_resource(stock);
+    commands.trigger(OpenModal(ModalType::MerchantModal));
+}
+
+/// Seed for the traveling merchant standing at `position`, so the same merchant on the same
+/// floor and day is always the same person.
+fn merchant_seed(day: u32, floor_index: usize, position: Vec2) -> u64 {
+    let position = position.round().as_ivec2();
+    (day as u64) << 48
+        ^ (floor_index as u64) << 32
+        ^ (position.x as u16 as u64) << 16
+        ^ position.y as u16 as u64
+}
+
+/// Who a traveling merchant is and whether they have traded yet: where they stand, and the
+/// floor and day they are met on.
+#[derive(SystemParam)]
+struct MerchantVisit<'w, 's> {
+    traded: Query<'w, 's, (), With<TradedWith>>,
+    transforms: Query<'w, 's, &'static Transform>,
+    dungeon: Res<'w, DungeonState>,
+    clock: Res<'w, GameClock>,
+}
+
+fn on_traveling_merchant_interaction(
+    trigger: On<TravelingMerchantInteraction>,
+    mut commands: Commands,
+    registry: Res<ItemRegistry>,
+    visit: MerchantVisit,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let MerchantVisit {
+        traded,
+        transforms,
+        dungeon,
+        clock,
+    } = visit;
+    let entity = trigger.event().entity;
+    let position = transforms
+        .get(entity)
+        .map(|t| t.translation.truncate())
+        .unwrap_or_default();
+    let persona = Persona::from_seed(merchant_seed(clock.day(), dungeon.floor_index, position));
+    if traded.contains(entity) {
+        toast_writer.write(ShowToast::new(loc.format(
+            "traveling_merchant.packed_up",
+            &[("name", &persona.name)],
+        )));
+        return;
+    }
+
+    commands.entity(entity).insert(TradedWith);
+    let mut stock = MerchantStock::generate_traveling(&registry, &mut rand::thread_rng());
+    stock.premium_percent = persona.temperament.apply(stock.premium_percent);
+    toast_writer.write(ShowToast::new(loc.format(
+        &format!("traveling_merchant.greeting.{}", persona.greeting.key()),
+        &[("name", &persona.name), ("premium", &stock.premium_percent)],
+    )));
+    commands.insert_resource(stock);
     commands.trigger(OpenModal(ModalType::MerchantModal));
 }
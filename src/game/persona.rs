@@ -0,0 +1,119 @@
+//! Names and personalities for generated characters. A persona is rolled from a seed, so the
+//! same seed always gives the same person: their name, how they greet the player and how hard
+//! they bargain.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const NAME_STARTS: &[&str] = &[
+    "Al", "Bre", "Cor", "Dag", "El", "Fen", "Gar", "Hol", "Ir", "Jas", "Kel", "Lor", "Mir",
+    "Nor", "Os", "Pel", "Quin", "Ros", "Sel", "Tam", "Ul", "Var", "Wen", "Yor",
+];
+
+const NAME_ENDS: &[&str] = &[
+    "ric", "wyn", "da", "mund", "ira", "an", "el", "o", "wick", "ette", "bert", "is",
+];
+
+/// How a character opens a conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GreetingStyle {
+    Warm,
+    Gruff,
+    Sly,
+    Weary,
+}
+
+impl GreetingStyle {
+    pub const ALL: [GreetingStyle; 4] = [
+        GreetingStyle::Warm,
+        GreetingStyle::Gruff,
+        GreetingStyle::Sly,
+        GreetingStyle::Weary,
+    ];
+
+    /// Suffix for the localization keys of this style's lines.
+    pub fn key(self) -> &'static str {
+        match self {
+            GreetingStyle::Warm => "warm",
+            GreetingStyle::Gruff => "gruff",
+            GreetingStyle::Sly => "sly",
+            GreetingStyle::Weary => "weary",
+        }
+    }
+}
+
+/// How hard a trader bargains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceTemperament {
+    Generous,
+    Fair,
+    Shrewd,
+    Greedy,
+}
+
+impl PriceTemperament {
+    pub const ALL: [PriceTemperament; 4] = [
+        PriceTemperament::Generous,
+        PriceTemperament::Fair,
+        PriceTemperament::Shrewd,
+        PriceTemperament::Greedy,
+    ];
+
+    /// Points added to a trader's markup over list price.
+    pub fn premium_adjustment(self) -> i32 {
+        match self {
+            PriceTemperament::Generous => -20,
+            PriceTemperament::Fair => 0,
+            PriceTemperament::Shrewd => 15,
+            PriceTemperament::Greedy => 30,
+        }
+    }
+
+    /// `premium_percent` as this trader would ask it. Never below list price.
+    pub fn apply(self, premium_percent: i32) -> i32 {
+        (premium_percent + self.premium_adjustment()).max(0)
+    }
+}
+
+/// A generated character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Persona {
+    pub name: String,
+    pub greeting: GreetingStyle,
+    pub temperament: PriceTemperament,
+}
+
+impl Persona {
+    pub fn from_seed(seed: u64) -> Self {
+        Self::generate(&mut StdRng::seed_from_u64(seed))
+    }
+
+    pub fn generate(rng: &mut impl Rng) -> Self {
+        let start = NAME_STARTS[rng.gen_range(0..NAME_STARTS.len())];
+        let end = NAME_ENDS[rng.gen_range(0..NAME_ENDS.len())];
+        Self {
+            name: format!("{start}{end}"),
+            greeting: GreetingStyle::ALL[rng.gen_range(0..GreetingStyle::ALL.len())],
+            temperament: PriceTemperament::ALL[rng.gen_range(0..PriceTemperament::ALL.len())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_rolls_the_same_person() {
+        assert_eq!(Persona::from_seed(42), Persona::from_seed(42));
+        let everyone: Vec<_> = (0..32).map(Persona::from_seed).collect();
+        assert!(everyone.iter().any(|p| p.name != everyone[0].name));
+    }
+
+    #[test]
+    fn temperament_never_prices_below_list() {
+        assert_eq!(PriceTemperament::Greedy.apply(50), 80);
+        assert_eq!(PriceTemperament::Generous.apply(50), 30);
+        assert_eq!(PriceTemperament::Generous.apply(10), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::entities::Progression;
+use crate::game::save_slots::SaveSlots;
 use crate::item::ItemRegistry;
 use crate::player::{PlayerBundle, PlayerMarker};
 use crate::states::AppState;
@@ -51,8 +52,27 @@ impl Plugin for PlayerPlugin {
     }
 }
 
-fn spawn_player_entity(mut commands: Commands, registry: Res<ItemRegistry>) {
-    commands.spawn(PlayerBundle::new(&registry));
+/// Spawns the character in the active save slot, or a new one if the slot is empty.
+fn spawn_player_entity(
+    mut commands: Commands,
+    registry: Res<ItemRegistry>,
+    slots: Res<SaveSlots>,
+) {
+    let bundle = match slots.active_save() {
+        Some(save) => save.bundle(&registry),
+        None => PlayerBundle::new(&registry),
+    };
+    commands.spawn(bundle);
+}
+
+/// The stats gained on reaching `level`.
+pub fn apply_level_gains(stats: &mut StatSheet, level: i32) {
+    if level % 10 == 0 {
+        stats.increase_stat(crate::stats::StatType::Defense, 1);
+    }
+    stats.increase_stat(crate::stats::StatType::Health, 5);
+    stats.increase_stat_max(crate::stats::StatType::Health, 5);
+    stats.increase_stat(crate::stats::StatType::Attack, 1);
 }
 
 fn handle_level_up(
@@ -69,12 +89,7 @@ fn handle_level_up(
     let current_level = progression.level;
     if current_level > prev_level.0 {
         for level in (prev_level.0 + 1)..=current_level {
-            if level % 10 == 0 {
-                stats.increase_stat(crate::stats::StatType::Defense, 1);
-            }
-            stats.increase_stat(crate::stats::StatType::Health, 5);
-            stats.increase_stat_max(crate::stats::StatType::Health, 5);
-            stats.increase_stat(crate::stats::StatType::Attack, 1);
+            apply_level_gains(&mut stats, level);
 
             level_up_events.write(PlayerLeveledUp {
                 new_level: level as u32,
@@ -0,0 +1,248 @@
+//! Captives held on dungeon floors. Once freed, a captive follows the player, and leading
+//! it to the stairs alive pays out in gold, experience and standing with its faction.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat::XpGained;
+use crate::dungeon::systems::{FloorTransitions, TransitionInProgress};
+use crate::dungeon::{
+    handle_floor_transition, CaptiveFreed, DungeonCommands, DungeonState, Following,
+    FloorTransition, MobEntity,
+};
+use crate::economy::{Currency, Wallet};
+use crate::faction::{FactionId, ReputationGained};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::mob::MobId;
+use crate::player::PlayerMarker;
+use crate::states::AppState;
+use crate::ui::{DungeonPlayer, DyingMob};
+use crate::world_state::WorldFlags;
+
+pub const CAPTIVE_HP: f32 = 40.0;
+
+/// How close, in pixels, a mob has to be to hurt the captive.
+pub const THREAT_RANGE: f32 = 48.0;
+
+/// Health the captive loses each second to every mob in range.
+pub const THREAT_DAMAGE_PER_SECOND: f32 = 5.0;
+
+/// How close to the player, in pixels, the captive must be when the stairs are taken.
+pub const ESCORT_RANGE: f32 = 96.0;
+
+pub const GOLD_PER_DEPTH: i32 = 30;
+
+pub const XP_PER_DEPTH: i32 = 25;
+
+/// Standing gained with the captive's faction.
+pub const RESCUE_REPUTATION: i32 = 10;
+
+/// The captive being led out.
+#[derive(Resource, Debug)]
+pub struct Rescue {
+    pub captive: Entity,
+    pub faction: FactionId,
+    pub health: f32,
+}
+
+impl Rescue {
+    pub fn new(captive: Entity, faction: FactionId) -> Self {
+        Self {
+            captive,
+            faction,
+            health: CAPTIVE_HP,
+        }
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.health = (self.health - amount).max(0.0);
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.health <= 0.0
+    }
+}
+
+/// World flag counting the captives brought out for `faction`, so its people remember.
+pub fn rescued_flag(faction: FactionId) -> String {
+    format!("rescue.{faction:?}")
+}
+
+/// Gold and experience for bringing a captive out from `depth`.
+pub fn rescue_reward(depth: u32) -> (i32, i32) {
+    let depth = depth.max(1) as i32;
+    (GOLD_PER_DEPTH * depth, XP_PER_DEPTH * depth)
+}
+
+pub struct RescuePlugin;
+
+impl Plugin for RescuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(free_captive).add_systems(
+            Update,
+            (
+                settle_rescue
+                    .run_if(on_message::<FloorTransition>)
+                    .before(handle_floor_transition),
+                threaten_captive.run_if(not(resource_exists::<TransitionInProgress>)),
+            )
+                .chain()
+                .run_if(resource_exists::<Rescue>)
+                .run_if(in_state(AppState::Dungeon)),
+        );
+    }
+}
+
+fn free_captive(
+    trigger: On<CaptiveFreed>,
+    mut commands: Commands,
+    rescue: Option<Res<Rescue>>,
+    flags: Res<WorldFlags>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let entity = trigger.event().entity;
+    if let Some(rescue) = rescue {
+        if rescue.captive != entity {
+            toast_writer.write(ShowToast::new(loc.get("rescue.one_at_a_time")));
+        }
+        return;
+    }
+
+    let faction = FactionId::ALL[rand::thread_rng().gen_range(0..FactionId::ALL.len())];
+    commands.entity(entity).insert(Following);
+    commands.insert_resource(Rescue::new(entity, faction));
+    let key = if flags.is_set(&rescued_flag(faction)) {
+        "rescue.freed_again"
+    } else {
+        "rescue.freed"
+    };
+    toast_writer.write(ShowToast::new(
+        loc.format(key, &[("faction", &loc.get(faction.name_key()))]),
+    ));
+}
+
+/// Where the player and the captive stand when the floor is left.
+#[derive(SystemParam)]
+struct RescuePositions<'w, 's> {
+    dungeon_player: Query<'w, 's, &'static Transform, With<DungeonPlayer>>,
+    captives: Query<'w, 's, &'static Transform, Without<DungeonPlayer>>,
+}
+
+/// What a completed rescue pays out: gold and XP scaled by depth, standing with the
+/// captive's faction, and a lasting record of the rescue.
+#[derive(SystemParam)]
+struct RescueReward<'w, 's> {
+    dungeon: Res<'w, DungeonState>,
+    player: Query<'w, 's, &'static mut Wallet, With<PlayerMarker>>,
+    flags: ResMut<'w, WorldFlags>,
+    xp_writer: MessageWriter<'w, XpGained>,
+    reputation_writer: MessageWriter<'w, ReputationGained>,
+}
+
+/// Settles the rescue when the player leaves the floor: taking the stairs with the
+/// captive close behind completes it, any other way out leaves the captive behind.
+fn settle_rescue(
+    mut commands: Commands,
+    mut transitions: FloorTransitions,
+    rescue: Res<Rescue>,
+    positions: RescuePositions,
+    reward: RescueReward,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let RescuePositions {
+        dungeon_player,
+        captives,
+    } = positions;
+    let RescueReward {
+        dungeon,
+        mut player,
+        mut flags,
+        mut xp_writer,
+        mut reputation_writer,
+    } = reward;
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    commands.remove_resource::<Rescue>();
+
+    let close_behind = match (dungeon_player.single(), captives.get(rescue.captive)) {
+        (Ok(player), Ok(captive)) => {
+            player.translation.truncate().distance(captive.translation.truncate()) <= ESCORT_RANGE
+        }
+        _ => false,
+    };
+    if !matches!(transition, FloorTransition::AdvanceFloor) || !close_behind {
+        toast_writer.write(ShowToast::new(loc.get("rescue.left_behind")));
+        return;
+    }
+
+    let (gold, xp) = rescue_reward(dungeon.depth());
+    if let Ok(mut wallet) = player.single_mut() {
+        wallet.add(Currency::Gold, gold);
+    }
+    xp_writer.write(XpGained {
+        amount: xp,
+        source: MobId::Captive.spec().name.clone(),
+    });
+    reputation_writer.write(ReputationGained {
+        faction: rescue.faction,
+        amount: RESCUE_REPUTATION,
+    });
+    flags.add(rescued_flag(rescue.faction), 1);
+    toast_writer.write(ShowToast::new(
+        loc.format("rescue.complete", &[("gold", &gold), ("xp", &xp)]),
+    ));
+}
+
+/// Mobs near the captive wear its health down. If it falls, the rescue fails.
+fn threaten_captive(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rescue: ResMut<Rescue>,
+    captives: Query<&Transform, Without<MobEntity>>,
+    mobs: Query<&Transform, (With<MobEntity>, Without<DyingMob>)>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(captive) = captives.get(rescue.captive) else {
+        return;
+    };
+    let pos = captive.translation.truncate();
+    let threats = mobs
+        .iter()
+        .filter(|transform| transform.translation.truncate().distance(pos) <= THREAT_RANGE)
+        .count();
+    rescue.damage(threats as f32 * THREAT_DAMAGE_PER_SECOND * time.delta_secs());
+    if !rescue.is_lost() {
+        return;
+    }
+
+    commands.despawn_dungeon_entity(rescue.captive);
+    commands.remove_resource::<Rescue>();
+    toast_writer.write(ShowToast::new(loc.get("rescue.failed")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewards_scale_with_depth() {
+        assert_eq!(rescue_reward(0), (GOLD_PER_DEPTH, XP_PER_DEPTH));
+        assert_eq!(rescue_reward(3), (3 * GOLD_PER_DEPTH, 3 * XP_PER_DEPTH));
+    }
+
+    #[test]
+    fn captive_health_never_drops_below_zero() {
+        let mut rescue = Rescue::new(Entity::PLACEHOLDER, FactionId::MinersGuild);
+        rescue.damage(15.0);
+        assert!(!rescue.is_lost());
+        rescue.damage(500.0);
+        assert_eq!(rescue.health, 0.0);
+        assert!(rescue.is_lost());
+    }
+}
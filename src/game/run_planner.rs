@@ -0,0 +1,124 @@
+//! The pre-run checklist: what the player is carrying into the dungeon, and the gaps
+//! worth fixing before they head down.
+
+use crate::economy::WorthGold;
+use crate::inventory::{EquipmentSlot, Inventory, InventoryItem, ManagesEquipment, ManagesItems};
+use crate::item::enums::{ConsumableType, ItemType};
+
+/// Slots a run shouldn't start with empty.
+pub const ESSENTIAL_SLOTS: [EquipmentSlot; 3] = [
+    EquipmentSlot::Weapon,
+    EquipmentSlot::Head,
+    EquipmentSlot::Chest,
+];
+
+/// Potions worth carrying into a run.
+pub const RECOMMENDED_POTIONS: u32 = 3;
+
+/// Something the player may want to sort out before a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunGap {
+    EmptySlot(EquipmentSlot),
+    FewPotions { have: u32 },
+}
+
+pub fn is_potion_type(item_type: ItemType) -> bool {
+    matches!(item_type, ItemType::Consumable(ConsumableType::Potion))
+}
+
+pub fn is_potion(item: &InventoryItem) -> bool {
+    is_potion_type(item.item.item_type)
+}
+
+/// Potions in the backpack, counting every stack.
+pub fn potion_count(inventory: &Inventory) -> u32 {
+    inventory
+        .get_inventory_items()
+        .iter()
+        .filter(|item| is_potion(item))
+        .map(|item| item.quantity)
+        .sum()
+}
+
+/// Gaps in the player's loadout, empty slots first.
+pub fn run_gaps(inventory: &Inventory) -> Vec<RunGap> {
+    let mut gaps: Vec<RunGap> = ESSENTIAL_SLOTS
+        .iter()
+        .filter(|slot| inventory.get_equipped_item(**slot).is_none())
+        .map(|slot| RunGap::EmptySlot(*slot))
+        .collect();
+
+    let have = potion_count(inventory);
+    if have < RECOMMENDED_POTIONS {
+        gaps.push(RunGap::FewPotions { have });
+    }
+    gaps
+}
+
+/// Index of the most valuable backpack item that fits `slot`.
+pub fn best_for_slot(inventory: &Inventory, slot: EquipmentSlot) -> Option<usize> {
+    inventory
+        .get_inventory_items()
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.item.item_type.fits_slot(slot))
+        .max_by_key(|(_, item)| item.item.gold_value())
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::enums::EquipmentType;
+    use crate::item::{Item, ItemId};
+
+    fn weapon(id: ItemId, gold_value: i32) -> Item {
+        Item {
+            gold_value,
+            ..Item::test(id, ItemType::Equipment(EquipmentType::Weapon))
+        }
+    }
+
+    fn potion() -> Item {
+        Item::test(ItemId::BasicHPPotion, ItemType::Consumable(ConsumableType::Potion))
+    }
+
+    #[test]
+    fn an_empty_loadout_lists_every_gap() {
+        let inventory = Inventory::new();
+        assert_eq!(
+            run_gaps(&inventory),
+            vec![
+                RunGap::EmptySlot(EquipmentSlot::Weapon),
+                RunGap::EmptySlot(EquipmentSlot::Head),
+                RunGap::EmptySlot(EquipmentSlot::Chest),
+                RunGap::FewPotions { have: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn stacked_potions_count_toward_the_recommendation() {
+        let mut inventory = Inventory::new();
+        for _ in 0..RECOMMENDED_POTIONS {
+            let _ = inventory.add_to_inv(potion());
+        }
+        assert_eq!(potion_count(&inventory), RECOMMENDED_POTIONS);
+        assert!(
+            run_gaps(&inventory)
+                .iter()
+                .all(|gap| matches!(gap, RunGap::EmptySlot(_)))
+        );
+    }
+
+    #[test]
+    fn the_most_valuable_fitting_item_is_picked() {
+        let mut inventory = Inventory::new();
+        let _ = inventory.add_to_inv(weapon(ItemId::Sword, 50));
+        let _ = inventory.add_to_inv(potion());
+        let _ = inventory.add_to_inv(weapon(ItemId::Dagger, 80));
+
+        assert_eq!(best_for_slot(&inventory, EquipmentSlot::Weapon), Some(2));
+        assert_eq!(best_for_slot(&inventory, EquipmentSlot::Head), None);
+    }
+}
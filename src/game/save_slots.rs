@@ -0,0 +1,516 @@
+//! Characters kept in numbered save slots, picked from the roster on the main menu.
+//!
+//! A slot keeps the character's name, level, experience, currencies, inventory and
+//! loadouts. Everything else that belongs to one character, like its storage, skills and
+//! town, is a [`CharacterResource`] or other registered character data kept in the slot's
+//! directory. The character being played is written back to its slot when leaving the
+//! dungeon, on exit and before switching to another one.
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::economy::{Currency, Wallet};
+use crate::entities::Progression;
+use crate::game::player::{apply_level_gains, PlayerPreviousLevel};
+use crate::inventory::{Inventory, Loadouts, SavedInventory};
+use crate::item::ItemRegistry;
+use crate::player::{BankedGold, PlayerBundle, PlayerMarker, PlayerName};
+use crate::save::{read_ron, save_ron};
+use crate::states::{AppState, ScreenSet};
+
+/// Where slot files are kept, relative to the save directory.
+pub const SAVE_SLOT_DIR: &str = "slots";
+
+/// How many characters the roster holds.
+pub const MAX_SAVE_SLOTS: usize = 4;
+
+/// The player components a [`CharacterSave`] is made from.
+pub type PlayerParts<'a> = (
+    &'a PlayerName,
+    &'a Progression,
+    &'a Wallet,
+    &'a BankedGold,
+    &'a Inventory,
+    &'a Loadouts,
+);
+
+/// One character as written to its slot file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterSave {
+    pub name: String,
+    pub level: i32,
+    pub xp: i32,
+    pub total_xp: i32,
+    pub gold: i32,
+    #[serde(default)]
+    pub banked_gold: i32,
+    /// Balances in every currency but gold.
+    #[serde(default)]
+    pub currencies: Vec<(Currency, i32)>,
+    /// The backpack and equipped gear. Characters saved before inventories were start
+    /// with the starting kit.
+    #[serde(default)]
+    pub inventory: Option<SavedInventory>,
+    #[serde(default)]
+    pub loadouts: Loadouts,
+    /// Unix seconds when the character was last saved.
+    pub last_played: u64,
+}
+
+impl CharacterSave {
+    pub fn new(player: PlayerParts, last_played: u64) -> Self {
+        let (name, progression, wallet, banked, inventory, loadouts) = player;
+        Self {
+            name: name.0.clone(),
+            level: progression.level,
+            xp: progression.xp,
+            total_xp: progression.total_xp,
+            gold: wallet.gold(),
+            banked_gold: banked.0,
+            currencies: wallet
+                .held()
+                .filter(|(currency, _)| *currency != Currency::Gold)
+                .collect(),
+            inventory: Some(SavedInventory::from(inventory)),
+            loadouts: loadouts.clone(),
+            last_played,
+        }
+    }
+
+    /// A player carrying this character's progress and belongings, with the stats its
+    /// level earned.
+    pub fn bundle(&self, registry: &ItemRegistry) -> PlayerBundle {
+        let mut bundle = PlayerBundle::new(registry);
+        bundle.name = PlayerName(self.name.clone());
+        bundle.progression = Progression {
+            level: self.level,
+            xp: self.xp,
+            total_xp: self.total_xp,
+        };
+        let _ = bundle.wallet.set(Currency::Gold, self.gold.max(0));
+        for &(currency, amount) in &self.currencies {
+            let _ = bundle.wallet.set(currency, amount.max(0));
+        }
+        bundle.banked_gold = BankedGold(self.banked_gold);
+        if let Some(inventory) = &self.inventory {
+            bundle.inventory = inventory.restore(registry);
+        }
+        bundle.loadouts = self.loadouts.clone();
+        for level in 2..=self.level {
+            apply_level_gains(&mut bundle.stats, level);
+        }
+        bundle.previous_level = PlayerPreviousLevel(self.level.max(1));
+        bundle
+    }
+}
+
+/// The file slot `index` is saved in, counting slots from 0.
+pub fn slot_path(index: usize) -> PathBuf {
+    PathBuf::from(SAVE_SLOT_DIR).join(format!("slot_{}.ron", index + 1))
+}
+
+/// The directory slot `index` keeps its character data in.
+pub fn slot_dir(index: usize) -> PathBuf {
+    PathBuf::from(SAVE_SLOT_DIR).join(format!("slot_{}", index + 1))
+}
+
+/// Reads `file` from slot `index`'s directory. `None` if it is missing or unreadable.
+pub fn read_character_file<T: DeserializeOwned>(index: usize, file: &str) -> Option<T> {
+    let path = slot_dir(index).join(file);
+    match read_ron(&path) {
+        Ok(value) => Some(value),
+        // Slot 1 picks up what was saved before each character kept its own.
+        Err(e) if e.is_not_found() && index == 0 => read_ron(file).ok(),
+        Err(e) if e.is_not_found() => None,
+        Err(e) => {
+            warn!("Starting {} fresh: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Writes `value` as `file` in slot `index`'s directory.
+pub fn save_character_file<T: Serialize>(index: usize, file: &str, value: &T) {
+    if let Err(e) = save_ron(slot_dir(index).join(file), value) {
+        warn!("{e}");
+    }
+}
+
+/// A resource that belongs to the character being played. It is saved as `FILE` in the
+/// active slot's directory and swapped out when another character is played.
+pub trait CharacterResource: Resource + Default + Serialize + DeserializeOwned {
+    const FILE: &'static str;
+}
+
+/// How one piece of character data is saved to and loaded from a slot.
+#[derive(Clone, Copy)]
+struct CharacterData {
+    file: &'static str,
+    save: fn(&World, usize),
+    load: fn(&mut World, usize),
+}
+
+/// Every piece of character data, saved and swapped along with the player.
+#[derive(Resource, Default, Clone)]
+struct CharacterDataRegistry(Vec<CharacterData>);
+
+/// Extension trait for registering state that belongs to one character.
+pub trait CharacterDataExt {
+    /// Loads `R` from the active slot and saves it back whenever it changes.
+    fn init_character_resource<R: CharacterResource>(&mut self) -> &mut Self;
+
+    /// Registers character data kept in `file` that needs more than serde to save or load,
+    /// like anything holding items. The caller loads it at startup and saves its changes.
+    fn register_character_data(
+        &mut self,
+        file: &'static str,
+        save: fn(&World, usize),
+        load: fn(&mut World, usize),
+    ) -> &mut Self;
+}
+
+impl CharacterDataExt for App {
+    fn init_character_resource<R: CharacterResource>(&mut self) -> &mut Self {
+        let index = self
+            .world()
+            .get_resource::<SaveSlots>()
+            .map_or(0, |slots| slots.active);
+        self.insert_resource(read_character_file::<R>(index, R::FILE).unwrap_or_default())
+            .register_character_data(R::FILE, save_resource::<R>, load_resource::<R>)
+            .add_systems(
+                Update,
+                save_changed_resource::<R>
+                    .run_if(resource_changed::<R>.and(not(resource_added::<R>)))
+                    .in_set(ScreenSet::Logic),
+            )
+    }
+
+    fn register_character_data(
+        &mut self,
+        file: &'static str,
+        save: fn(&World, usize),
+        load: fn(&mut World, usize),
+    ) -> &mut Self {
+        self.init_resource::<CharacterDataRegistry>();
+        self.world_mut()
+            .resource_mut::<CharacterDataRegistry>()
+            .0
+            .push(CharacterData { file, save, load });
+        self
+    }
+}
+
+fn save_resource<R: CharacterResource>(world: &World, index: usize) {
+    save_character_file(index, R::FILE, world.resource::<R>());
+}
+
+fn load_resource<R: CharacterResource>(world: &mut World, index: usize) {
+    world.insert_resource(read_character_file::<R>(index, R::FILE).unwrap_or_default());
+}
+
+fn save_changed_resource<R: CharacterResource>(resource: Res<R>, slots: Res<SaveSlots>) {
+    save_character_file(slots.active, R::FILE, &*resource);
+}
+
+/// How long ago a character was last played, rounded down to the largest whole unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayedAgo {
+    JustNow,
+    Minutes(u64),
+    Hours(u64),
+    Days(u64),
+}
+
+impl PlayedAgo {
+    pub fn since(last_played: u64, now: u64) -> Self {
+        let seconds = now.saturating_sub(last_played);
+        match seconds {
+            0..60 => PlayedAgo::JustNow,
+            60..3_600 => PlayedAgo::Minutes(seconds / 60),
+            3_600..86_400 => PlayedAgo::Hours(seconds / 3_600),
+            _ => PlayedAgo::Days(seconds / 86_400),
+        }
+    }
+}
+
+/// Unix seconds now.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Every slot's character, and which one is being played.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SaveSlots {
+    pub slots: Vec<Option<CharacterSave>>,
+    pub active: usize,
+}
+
+impl Default for SaveSlots {
+    fn default() -> Self {
+        Self {
+            slots: vec![None; MAX_SAVE_SLOTS],
+            active: 0,
+        }
+    }
+}
+
+impl SaveSlots {
+    pub fn get(&self, index: usize) -> Option<&CharacterSave> {
+        self.slots.get(index)?.as_ref()
+    }
+
+    pub fn active_save(&self) -> Option<&CharacterSave> {
+        self.get(self.active)
+    }
+}
+
+/// Something the player asked of the roster.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveSlotRequest {
+    /// Play the character in this slot, starting a new one if it is empty.
+    Play { index: usize },
+    /// Erase the character in this slot. The character being played can't be erased.
+    Delete { index: usize },
+}
+
+pub struct SaveSlotsPlugin;
+
+impl Plugin for SaveSlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_slots())
+            .init_resource::<CharacterDataRegistry>()
+            .add_message::<SaveSlotRequest>()
+            .add_systems(OnExit(AppState::Dungeon), save_active_character)
+            .add_systems(Last, save_active_character.run_if(on_message::<AppExit>))
+            .add_systems(
+                Update,
+                handle_save_slot_requests
+                    .run_if(on_message::<SaveSlotRequest>)
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// Reads every slot file, leaving a slot empty if its file is missing or unreadable.
+fn load_slots() -> SaveSlots {
+    let slots = (0..MAX_SAVE_SLOTS)
+        .map(|index| match read_ron::<CharacterSave>(slot_path(index)) {
+            Ok(save) => Some(save),
+            Err(e) if e.is_not_found() => None,
+            Err(e) => {
+                warn!("Save slot {} is unreadable: {e}", index + 1);
+                None
+            }
+        })
+        .collect();
+    SaveSlots { slots, active: 0 }
+}
+
+type PlayerSaveQuery<'w, 's> = Query<'w, 's, PlayerParts<'static>, With<PlayerMarker>>;
+
+/// Writes the character being played to its slot.
+fn save_active(slots: &mut SaveSlots, player: &PlayerSaveQuery) {
+    let Ok(parts) = player.single() else {
+        return;
+    };
+    let save = CharacterSave::new(parts, now_secs());
+    if let Err(e) = save_ron(slot_path(slots.active), &save) {
+        warn!("{e}");
+    }
+    if let Some(slot) = slots.slots.get_mut(slots.active) {
+        *slot = Some(save);
+    }
+}
+
+fn save_active_character(mut slots: ResMut<SaveSlots>, player: PlayerSaveQuery) {
+    save_active(&mut slots, &player);
+}
+
+fn handle_save_slot_requests(
+    mut commands: Commands,
+    mut requests: MessageReader<SaveSlotRequest>,
+    mut slots: ResMut<SaveSlots>,
+    player: PlayerSaveQuery,
+    registry: Res<ItemRegistry>,
+    character_data: Res<CharacterDataRegistry>,
+) {
+    for request in requests.read() {
+        match *request {
+            SaveSlotRequest::Play { index } => {
+                if index >= MAX_SAVE_SLOTS || index == slots.active {
+                    continue;
+                }
+                save_active(&mut slots, &player);
+
+                let save = slots.get(index).cloned().unwrap_or_else(|| {
+                    let new = PlayerBundle::new(&registry);
+                    CharacterSave::new(
+                        (
+                            &new.name,
+                            &new.progression,
+                            &new.wallet,
+                            &new.banked_gold,
+                            &new.inventory,
+                            &new.loadouts,
+                        ),
+                        now_secs(),
+                    )
+                });
+                if let Err(e) = save_ron(slot_path(index), &save) {
+                    warn!("{e}");
+                }
+                slots.slots[index] = Some(save);
+                commands.queue(SwitchCharacter { index });
+            }
+            SaveSlotRequest::Delete { index } => {
+                if index >= MAX_SAVE_SLOTS || index == slots.active {
+                    continue;
+                }
+                match crate::save::remove(&slot_path(index)) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        warn!("Failed to erase save slot {}: {e}", index + 1);
+                        continue;
+                    }
+                }
+                for data in &character_data.0 {
+                    let path = slot_dir(index).join(data.file);
+                    match crate::save::remove(&path) {
+                        Ok(()) => {}
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(e) => warn!("Failed to erase {}: {e}", path.display()),
+                    }
+                }
+                slots.slots[index] = None;
+            }
+        }
+    }
+}
+
+/// Makes slot `index` the one being played. The old slot's character data is saved
+/// before the new slot's is loaded, and the player entity is kept and given the new
+/// character's components.
+struct SwitchCharacter {
+    index: usize,
+}
+
+impl Command for SwitchCharacter {
+    fn apply(self, world: &mut World) {
+        let old = world.resource::<SaveSlots>().active;
+        let character_data = world.resource::<CharacterDataRegistry>().clone();
+        for data in &character_data.0 {
+            (data.save)(world, old);
+        }
+        for data in &character_data.0 {
+            (data.load)(world, self.index);
+        }
+
+        let mut slots = world.resource_mut::<SaveSlots>();
+        slots.active = self.index;
+        let Some(save) = slots.get(self.index).cloned() else {
+            return;
+        };
+        let bundle = save.bundle(world.resource::<ItemRegistry>());
+        let player = world
+            .query_filtered::<Entity, With<PlayerMarker>>()
+            .single(world);
+        match player {
+            Ok(entity) => {
+                world.entity_mut(entity).insert(bundle);
+            }
+            Err(_) => {
+                world.spawn(bundle);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::inventory::ManagesItems;
+    use crate::item::ItemId;
+    use crate::save::{from_ron, to_ron};
+
+    fn save() -> CharacterSave {
+        CharacterSave {
+            name: "Ada".to_string(),
+            level: 12,
+            xp: 40,
+            total_xp: 2_300,
+            gold: 815,
+            banked_gold: 2_000,
+            currencies: vec![(Currency::BossSigil, 3)],
+            inventory: None,
+            loadouts: Loadouts::default(),
+            last_played: 1_790_000_000,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let save = save();
+        assert_eq!(
+            from_ron::<CharacterSave>(&to_ron(&save).unwrap()).unwrap(),
+            save
+        );
+    }
+
+    #[test]
+    fn bundle_restores_belongings() {
+        let registry = ItemRegistry::from_assets();
+        let mut player = PlayerBundle::new(&registry);
+        player.wallet.add(Currency::WarToken, 7);
+        let _ = player.inventory.add_to_inv(registry.spawn(ItemId::IronOre));
+        let saved = CharacterSave::new(
+            (
+                &player.name,
+                &player.progression,
+                &player.wallet,
+                &player.banked_gold,
+                &player.inventory,
+                &player.loadouts,
+            ),
+            0,
+        );
+
+        let saved: CharacterSave = from_ron(&to_ron(&saved).unwrap()).unwrap();
+        let restored = saved.bundle(&registry);
+
+        assert_eq!(restored.wallet, player.wallet);
+        assert_eq!(restored.inventory.items.len(), player.inventory.items.len());
+        for (restored, item) in restored.inventory.items.iter().zip(&player.inventory.items) {
+            assert_eq!(restored.uuid(), item.uuid());
+            assert_eq!(restored.quantity, item.quantity);
+        }
+    }
+
+    #[test]
+    fn played_ago_rounds_down_to_the_largest_unit() {
+        let then = 1_000_000;
+        assert_eq!(PlayedAgo::since(then, then + 59), PlayedAgo::JustNow);
+        assert_eq!(PlayedAgo::since(then, then + 60), PlayedAgo::Minutes(1));
+        assert_eq!(PlayedAgo::since(then, then + 7_199), PlayedAgo::Hours(1));
+        assert_eq!(
+            PlayedAgo::since(then, then + 3 * 86_400),
+            PlayedAgo::Days(3)
+        );
+        assert_eq!(PlayedAgo::since(then, then - 10), PlayedAgo::JustNow);
+    }
+
+    #[test]
+    fn slots_are_numbered_from_one_on_disk() {
+        assert_eq!(slot_path(0), Path::new("slots/slot_1.ron"));
+        assert_eq!(slot_dir(1), Path::new("slots/slot_2"));
+    }
+}
@@ -0,0 +1,253 @@
+//! Character summaries players can share: stats, gear, skills and depth record, exported as
+//! a JSON file or a compact code and viewed read-only once imported.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment};
+use crate::item::enums::ItemQuality;
+use crate::item::ItemId;
+use crate::skills::{SkillType, Skills};
+use crate::stats::{HasStats, StatSheet};
+
+/// Where exported profiles are written.
+pub const PROFILE_EXPORT_DIR: &str = "exports";
+
+/// Bumped when the summary's fields change, so old codes are turned away instead of
+/// misread.
+const SHARED_PROFILE_VERSION: u32 = 1;
+
+/// URL-safe base64, so codes survive being pasted into chat and links.
+const CODE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SharedProfileError {
+    #[error("Failed to access profile file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Not a profile: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Not a profile code")]
+    BadCode,
+    #[error("Profile is from version {0}, which this game can't read")]
+    Version(u32),
+}
+
+/// One equipped item as it appears in a shared profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedGear {
+    pub slot: EquipmentSlot,
+    pub item: ItemId,
+    pub name: String,
+    pub quality: ItemQuality,
+    pub upgrades: i32,
+}
+
+/// A snapshot of a character's build.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedProfile {
+    pub version: u32,
+    pub name: String,
+    pub level: i32,
+    pub max_hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub gear: Vec<SharedGear>,
+    pub skills: Vec<(SkillType, u32)>,
+    pub best_depth: u32,
+}
+
+/// The last profile imported from the console, shown beside the player's own.
+#[derive(Resource, Debug, Default)]
+pub struct ImportedProfile(pub Option<SharedProfile>);
+
+impl SharedProfile {
+    pub fn new(
+        name: &str,
+        level: i32,
+        stats: &StatSheet,
+        inventory: &Inventory,
+        skills: &Skills,
+        best_depth: u32,
+    ) -> Self {
+        let gear = EquipmentSlot::all()
+            .iter()
+            .filter_map(|&slot| {
+                let item = &inventory.get_equipped_item(slot)?.item;
+                Some(SharedGear {
+                    slot,
+                    item: item.item_id,
+                    name: item.name.clone(),
+                    quality: item.quality,
+                    upgrades: item.num_upgrades,
+                })
+            })
+            .collect();
+        let skills = SkillType::all()
+            .iter()
+            .filter_map(|&skill| Some((skill, skills.skill(skill)?.level)))
+            .collect();
+        Self {
+            version: SHARED_PROFILE_VERSION,
+            name: name.to_string(),
+            level,
+            max_hp: stats.max_hp(),
+            attack: stats.attack(),
+            defense: stats.defense(),
+            gear,
+            skills,
+            best_depth,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, SharedProfileError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(source: &str) -> Result<Self, SharedProfileError> {
+        let profile: Self = serde_json::from_str(source)?;
+        if profile.version != SHARED_PROFILE_VERSION {
+            return Err(SharedProfileError::Version(profile.version));
+        }
+        Ok(profile)
+    }
+
+    /// The profile as a single line of text to paste elsewhere.
+    pub fn to_code(&self) -> Result<String, SharedProfileError> {
+        Ok(encode(serde_json::to_string(self)?.as_bytes()))
+    }
+
+    pub fn from_code(code: &str) -> Result<Self, SharedProfileError> {
+        let bytes = decode(code.trim()).ok_or(SharedProfileError::BadCode)?;
+        let json = String::from_utf8(bytes).map_err(|_| SharedProfileError::BadCode)?;
+        Self::from_json(&json)
+    }
+
+    /// Reads a profile from either a `.json` file path or a code.
+    pub fn import(source: &str) -> Result<Self, SharedProfileError> {
+        if source.ends_with(".json") {
+            Self::from_json(&std::fs::read_to_string(source)?)
+        } else {
+            Self::from_code(source)
+        }
+    }
+
+    /// Writes the profile to the export directory, named after the character.
+    pub fn export(&self) -> Result<PathBuf, SharedProfileError> {
+        let path = export_path(&self.name);
+        self.save(&path)?;
+        Ok(path)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SharedProfileError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+pub fn export_path(name: &str) -> PathBuf {
+    let file: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    PathBuf::from(PROFILE_EXPORT_DIR).join(format!("{file}.profile.json"))
+}
+
+fn encode(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let word = chunk.iter().enumerate().fold(0u32, |word, (i, byte)| {
+            word | ((*byte as u32) << (16 - 8 * i))
+        });
+        for i in 0..=chunk.len() {
+            let index = (word >> (18 - 6 * i)) & 0x3f;
+            code.push(CODE_ALPHABET[index as usize] as char);
+        }
+    }
+    code
+}
+
+fn decode(code: &str) -> Option<Vec<u8>> {
+    let values = code
+        .bytes()
+        .map(|c| CODE_ALPHABET.iter().position(|a| *a == c))
+        .collect::<Option<Vec<_>>>()?;
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        if chunk.len() < 2 {
+            return None;
+        }
+        let word = chunk.iter().enumerate().fold(0u32, |word, (i, value)| {
+            word | ((*value as u32) << (18 - 6 * i))
+        });
+        for i in 0..chunk.len() - 1 {
+            bytes.push((word >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> SharedProfile {
+        SharedProfile {
+            version: SHARED_PROFILE_VERSION,
+            name: "Ada".to_string(),
+            level: 7,
+            max_hp: 140,
+            attack: 32,
+            defense: 18,
+            gear: vec![SharedGear {
+                slot: EquipmentSlot::Weapon,
+                item: ItemId::IronSword,
+                name: "Iron Sword".to_string(),
+                quality: ItemQuality::Improved,
+                upgrades: 2,
+            }],
+            skills: vec![(SkillType::Mining, 12), (SkillType::Combat, 9)],
+            best_depth: 14,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_codes_and_json() {
+        let profile = profile();
+
+        let code = profile.to_code().unwrap();
+        assert!(code.bytes().all(|c| CODE_ALPHABET.contains(&c)));
+        assert_eq!(SharedProfile::from_code(&code).unwrap(), profile);
+        assert_eq!(
+            SharedProfile::from_json(&profile.to_json().unwrap()).unwrap(),
+            profile
+        );
+    }
+
+    #[test]
+    fn codes_pad_nothing_and_decode_every_length() {
+        for bytes in [&b""[..], b"a", b"ab", b"abc", b"abcd"] {
+            assert_eq!(decode(&encode(bytes)).unwrap(), bytes);
+        }
+        assert_eq!(encode(b"abc"), "YWJj");
+        assert!(decode("Y").is_none());
+        assert!(decode("not a code!").is_none());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut profile = profile();
+        profile.version = SHARED_PROFILE_VERSION + 1;
+        let code = profile.to_code().unwrap();
+
+        assert!(matches!(
+            SharedProfile::from_code(&code),
+            Err(SharedProfileError::Version(_))
+        ));
+    }
+}
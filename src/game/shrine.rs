@@ -0,0 +1,269 @@
+//! Shrines found on dungeon floors. Each takes a single offering: gold buys a blessing, a
+//! prayer leaves it to chance, and a cleansing lifts any curses.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::dungeon::{DungeonCommands, DungeonState, ShrineEntity};
+use crate::economy::{Currency, Wallet};
+use crate::game::buffs::{ActiveBuffs, BuffKind};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::stats::{HasStats, Healable, StatSheet};
+use crate::ui::UiFeedback;
+use crate::world_state::WorldFlags;
+
+/// Floors a blessing lasts, counting the one it was granted on.
+pub const BLESSING_FLOORS: u32 = 3;
+
+/// Floors a curse from a prayer lasts.
+pub const CURSE_FLOORS: u32 = 2;
+
+/// Gold an offering costs for each floor of depth.
+pub const OFFERING_GOLD_PER_DEPTH: i32 = 40;
+
+/// Gold a cleansing costs for each floor of depth.
+pub const CLEANSE_GOLD_PER_DEPTH: i32 = 15;
+
+/// World flag counting every gold offering the player has made at a shrine.
+pub const SACRIFICES_FLAG: &str = "shrine.sacrifices";
+
+/// Offerings after which shrines look kindly on the player's prayers for good.
+pub const DEVOTION_SACRIFICES: i32 = 5;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShrineOffer {
+    /// Gold for a random blessing.
+    Sacrifice,
+    /// Free, but the shrine may bless, heal, curse or ignore the player.
+    Pray,
+    /// Gold to lift every curse.
+    Cleanse,
+}
+
+impl ShrineOffer {
+    pub const ALL: [ShrineOffer; 3] = [
+        ShrineOffer::Sacrifice,
+        ShrineOffer::Pray,
+        ShrineOffer::Cleanse,
+    ];
+
+    /// Gold the offer costs at `depth`.
+    pub fn gold_cost(self, depth: u32) -> i32 {
+        let depth = depth.max(1) as i32;
+        match self {
+            ShrineOffer::Sacrifice => OFFERING_GOLD_PER_DEPTH * depth,
+            ShrineOffer::Pray => 0,
+            ShrineOffer::Cleanse => CLEANSE_GOLD_PER_DEPTH * depth,
+        }
+    }
+}
+
+/// What a prayer brings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrayerAnswer {
+    Blessing(BuffKind),
+    Healing,
+    Curse(BuffKind),
+    Silence,
+}
+
+/// Rolls a prayer's answer: usually kind, sometimes a curse. A devoted player gets a second
+/// roll when the first would curse them.
+pub fn answer_prayer(devoted: bool, rng: &mut impl Rng) -> PrayerAnswer {
+    let answer = roll_prayer(rng);
+    if devoted && matches!(answer, PrayerAnswer::Curse(_)) {
+        return roll_prayer(rng);
+    }
+    answer
+}
+
+fn roll_prayer(rng: &mut impl Rng) -> PrayerAnswer {
+    match rng.gen_range(0..100) {
+        0..40 => PrayerAnswer::Blessing(random_kind(&BuffKind::BLESSINGS, rng)),
+        40..60 => PrayerAnswer::Healing,
+        60..85 => PrayerAnswer::Curse(random_kind(&BuffKind::CURSES, rng)),
+        _ => PrayerAnswer::Silence,
+    }
+}
+
+fn random_kind(kinds: &[BuffKind], rng: &mut impl Rng) -> BuffKind {
+    kinds[rng.gen_range(0..kinds.len())]
+}
+
+/// The player made an offering at a shrine.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct UseShrine {
+    pub shrine: Entity,
+    pub offer: ShrineOffer,
+}
+
+pub struct ShrinePlugin;
+
+impl Plugin for ShrinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<UseShrine>()
+            .add_systems(
+                Update,
+                use_shrine
+                    .run_if(on_message::<UseShrine>)
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// What a shrine's answer touches: the player's buffs and curses, and the world flags that
+/// remember their sacrifices.
+#[derive(SystemParam)]
+struct ShrineFavor<'w> {
+    buffs: ResMut<'w, ActiveBuffs>,
+    flags: ResMut<'w, WorldFlags>,
+}
+
+/// How a shrine replies: a toast, and an error cue when it turns the player away.
+#[derive(SystemParam)]
+struct ShrineReplies<'w> {
+    loc: Res<'w, Localization>,
+    toast_writer: MessageWriter<'w, ShowToast>,
+    feedback: MessageWriter<'w, UiFeedback>,
+}
+
+fn use_shrine(
+    mut commands: Commands,
+    mut requests: MessageReader<UseShrine>,
+    dungeon: Res<DungeonState>,
+    shrines: Query<(), With<ShrineEntity>>,
+    mut player: Query<(&mut StatSheet, &mut Wallet), With<PlayerMarker>>,
+    favor: ShrineFavor,
+    replies: ShrineReplies,
+) {
+    let ShrineFavor {
+        mut buffs,
+        mut flags,
+    } = favor;
+    let ShrineReplies {
+        loc,
+        mut toast_writer,
+        mut feedback,
+    } = replies;
+    let Ok((mut stats, mut wallet)) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        if shrines.get(request.shrine).is_err() {
+            continue;
+        }
+        if request.offer == ShrineOffer::Cleanse && !buffs.has_curse() {
+            toast_writer.write(ShowToast::new(loc.get("shrine.no_curses")));
+            feedback.write(UiFeedback::Error);
+            continue;
+        }
+        let cost = request.offer.gold_cost(dungeon.depth());
+        if cost > 0 && wallet.spend(Currency::Gold, cost).is_err() {
+            toast_writer.write(ShowToast::new(
+                loc.format("shrine.cannot_afford", &[("gold", &cost)]),
+            ));
+            feedback.write(UiFeedback::Error);
+            continue;
+        }
+
+        let mut rng = rand::thread_rng();
+        let message = match request.offer {
+            ShrineOffer::Sacrifice => {
+                flags.add(SACRIFICES_FLAG, 1);
+                let kind = random_kind(&BuffKind::BLESSINGS, &mut rng);
+                buffs.grant(kind, BLESSING_FLOORS, &mut stats);
+                blessed_message(&loc, kind)
+            }
+            ShrineOffer::Pray => {
+                let devoted = flags.count(SACRIFICES_FLAG) >= DEVOTION_SACRIFICES;
+                match answer_prayer(devoted, &mut rng) {
+                    PrayerAnswer::Blessing(kind) => {
+                        buffs.grant(kind, BLESSING_FLOORS, &mut stats);
+                        blessed_message(&loc, kind)
+                    }
+                    PrayerAnswer::Healing => {
+                        let max_hp = stats.max_hp();
+                        stats.heal(max_hp);
+                        loc.get("shrine.healed").to_string()
+                    }
+                    PrayerAnswer::Curse(kind) => {
+                        buffs.grant(kind, CURSE_FLOORS, &mut stats);
+                        let buff = loc.get(kind.label_key());
+                        loc.format(
+                            "shrine.cursed",
+                            &[("buff", &buff), ("floors", &CURSE_FLOORS)],
+                        )
+                    }
+                    PrayerAnswer::Silence => loc.get("shrine.silence").to_string(),
+                }
+            }
+            ShrineOffer::Cleanse => {
+                let lifted = buffs.cleanse(&mut stats);
+                loc.format("shrine.cleansed", &[("count", &lifted)])
+            }
+        };
+
+        // Shrines answer once; the spent one crumbles.
+        commands.despawn_dungeon_entity(request.shrine);
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+fn blessed_message(loc: &Localization, kind: BuffKind) -> String {
+    loc.format(
+        "shrine.blessed",
+        &[
+            ("buff", &loc.get(kind.label_key())),
+            ("floors", &BLESSING_FLOORS),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn costs_scale_with_depth() {
+        assert_eq!(ShrineOffer::Sacrifice.gold_cost(0), OFFERING_GOLD_PER_DEPTH);
+        assert_eq!(
+            ShrineOffer::Sacrifice.gold_cost(3),
+            3 * OFFERING_GOLD_PER_DEPTH
+        );
+        assert_eq!(
+            ShrineOffer::Cleanse.gold_cost(2),
+            2 * CLEANSE_GOLD_PER_DEPTH
+        );
+        assert_eq!(ShrineOffer::Pray.gold_cost(5), 0);
+    }
+
+    #[test]
+    fn prayers_only_curse_with_curses() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            match answer_prayer(false, &mut rng) {
+                PrayerAnswer::Blessing(kind) => assert!(!kind.is_curse()),
+                PrayerAnswer::Curse(kind) => assert!(kind.is_curse()),
+                PrayerAnswer::Healing | PrayerAnswer::Silence => {}
+            }
+        }
+    }
+
+    #[test]
+    fn devotion_makes_curses_rarer() {
+        let curses = |devoted: bool| {
+            let mut rng = StdRng::seed_from_u64(11);
+            (0..1000)
+                .filter(|_| matches!(answer_prayer(devoted, &mut rng), PrayerAnswer::Curse(_)))
+                .count()
+        };
+        assert!(curses(true) < curses(false));
+    }
+}
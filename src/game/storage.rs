@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::storage::Storage;
+use crate::game::save_slots::{read_character_file, save_character_file};
+use crate::game::{CharacterDataExt, SaveSlots};
+use crate::item::ItemRegistry;
+use crate::states::{AppState, ScreenSet};
+use crate::storage::{SavedStorage, Storage, STORAGE_SAVE_PATH};
 
 /// Event fired when an item is deposited into storage
 #[derive(Message, Debug, Clone)]
@@ -14,13 +18,49 @@ pub struct ItemWithdrawn {
     pub item_name: String,
 }
 
-/// Plugin that initializes the Storage and registers storage-related events
+/// Plugin that initializes the Storage, keeps it in the active save slot and registers
+/// storage-related events
 pub struct StoragePlugin;
 
 impl Plugin for StoragePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Storage>()
+            .register_character_data(STORAGE_SAVE_PATH, save_storage, load_storage)
             .add_message::<ItemDeposited>()
-            .add_message::<ItemWithdrawn>();
+            .add_message::<ItemWithdrawn>()
+            .add_systems(OnExit(AppState::Loading), load_active_storage)
+            .add_systems(
+                Update,
+                save_changed_storage
+                    .run_if(resource_changed::<Storage>.and(not(resource_added::<Storage>)))
+                    .in_set(ScreenSet::Logic),
+            );
     }
 }
+
+fn save_storage(world: &World, index: usize) {
+    save_character_file(
+        index,
+        STORAGE_SAVE_PATH,
+        &world.resource::<Storage>().to_saved(),
+    );
+}
+
+/// Storage holds items, so it waits for the item registry instead of loading with the
+/// plain character resources.
+fn load_storage(world: &mut World, index: usize) {
+    let storage = read_character_file::<SavedStorage>(index, STORAGE_SAVE_PATH)
+        .map_or_else(Storage::default, |saved| {
+            Storage::from_saved(&saved, world.resource::<ItemRegistry>())
+        });
+    world.insert_resource(storage);
+}
+
+fn load_active_storage(world: &mut World) {
+    let index = world.resource::<SaveSlots>().active;
+    load_storage(world, index);
+}
+
+fn save_changed_storage(storage: Res<Storage>, slots: Res<SaveSlots>) {
+    save_character_file(slots.active, STORAGE_SAVE_PATH, &storage.to_saved());
+}
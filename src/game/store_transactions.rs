@@ -1,8 +1,15 @@
 use bevy::prelude::*;
 
-use crate::game::{ItemDeposited, ItemWithdrawn, Storage};
-use crate::inventory::{FindsItems, HasInventory, Inventory, InventoryError, ManagesItems};
+use crate::game::{
+    IrreversibleAction, ItemDeposited, ItemWithdrawn, ShowToast, Storage, UndoHistory,
+    UndoableAction,
+};
+use crate::i18n::Localization;
+use crate::inventory::{FindsItems, HasInventory, Inventory, ManagesItems};
+use crate::item::recipe::RecipeId;
 use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::storage::DepositFilter;
 
 #[derive(Message, Debug, Clone)]
 pub struct StorageWithdrawEvent {
@@ -14,6 +21,18 @@ pub struct StorageDepositEvent {
     pub inventory_index: usize,
 }
 
+/// Stores every backpack stack the filter accepts in one go.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StorageDepositAllEvent {
+    pub filter: DepositFilter,
+}
+
+/// Withdraws whatever the backpack is missing to craft `recipe`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StorageWithdrawForRecipeEvent {
+    pub recipe: RecipeId,
+}
+
 #[derive(Message, Debug, Clone)]
 pub enum StorageTransactionResult {
     WithdrawSuccess { item_name: String },
@@ -28,13 +47,19 @@ impl Plugin for StorageTransactionsPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<StorageWithdrawEvent>()
             .add_message::<StorageDepositEvent>()
+            .add_message::<StorageDepositAllEvent>()
+            .add_message::<StorageWithdrawForRecipeEvent>()
             .add_message::<StorageTransactionResult>()
             .add_systems(
                 Update,
                 (
                     handle_storage_withdraw.run_if(on_message::<StorageWithdrawEvent>),
                     handle_storage_deposit.run_if(on_message::<StorageDepositEvent>),
-                ),
+                    handle_storage_deposit_all.run_if(on_message::<StorageDepositAllEvent>),
+                    handle_withdraw_for_recipe
+                        .run_if(on_message::<StorageWithdrawForRecipeEvent>),
+                )
+                    .in_set(ScreenSet::Logic),
             );
     }
 }
@@ -45,6 +70,7 @@ fn handle_storage_withdraw(
     mut withdrawn_events: MessageWriter<ItemWithdrawn>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     mut storage: ResMut<Storage>,
+    mut history: ResMut<UndoHistory>,
 ) {
     let Ok(mut inventory) = player.single_mut() else {
         return;
@@ -66,8 +92,11 @@ fn handle_storage_withdraw(
         let Some(inv_item) = storage.remove_item(item_uuid) else {
             continue;
         };
+        storage.forget(item_uuid);
 
+        let item = inv_item.item.clone();
         if inventory.add_to_inv(inv_item.item).is_ok() {
+            history.record(UndoableAction::Withdraw { item });
             result_events.write(StorageTransactionResult::WithdrawSuccess {
                 item_name: item_name.clone(),
             });
@@ -85,6 +114,7 @@ fn handle_storage_deposit(
     mut deposited_events: MessageWriter<ItemDeposited>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
     mut storage: ResMut<Storage>,
+    mut history: ResMut<UndoHistory>,
 ) {
     let Ok(mut inventory) = player.single_mut() else {
         return;
@@ -109,8 +139,9 @@ fn handle_storage_deposit(
             continue;
         };
 
-        match storage.add_to_inv(inv_item.item.clone()) {
+        match storage.deposit(inv_item.item.clone()) {
             Ok(_) => {
+                history.record(UndoableAction::Deposit { item: inv_item.item });
                 result_events.write(StorageTransactionResult::DepositSuccess {
                     item_name: item_name.clone(),
                 });
@@ -119,7 +150,7 @@ fn handle_storage_deposit(
                 });
                 info!("Deposited {} into storage", item_name);
             }
-            Err(InventoryError::Full) => {
+            Err(_) => {
                 result_events.write(StorageTransactionResult::DepositFailed {
                     reason: "Storage is full (unexpected)".to_string(),
                 });
@@ -129,3 +160,55 @@ fn handle_storage_deposit(
         }
     }
 }
+
+fn handle_storage_deposit_all(
+    mut events: MessageReader<StorageDepositAllEvent>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    mut storage: ResMut<Storage>,
+    mut history: ResMut<UndoHistory>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let stored = storage.deposit_all(&mut inventory, event.filter);
+        let message = if stored == 0 {
+            loc.get("storage.nothing_to_deposit").to_string()
+        } else {
+            history.record_irreversible(IrreversibleAction::BulkTransfer);
+            loc.format("storage.deposited_all", &[("count", &stored)])
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+fn handle_withdraw_for_recipe(
+    mut events: MessageReader<StorageWithdrawForRecipeEvent>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    mut storage: ResMut<Storage>,
+    mut history: ResMut<UndoHistory>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let spec = event.recipe.spec();
+        let taken = storage.withdraw_ingredients(&mut inventory, &spec.ingredients);
+        let message = if taken == 0 {
+            loc.get("storage.nothing_to_withdraw").to_string()
+        } else {
+            history.record_irreversible(IrreversibleAction::BulkTransfer);
+            loc.format(
+                "storage.withdrew_for_recipe",
+                &[("count", &taken), ("recipe", &spec.name)],
+            )
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
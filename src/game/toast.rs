@@ -1,9 +1,12 @@
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
 use std::time::Duration;
 
 const TOAST_DURATION: Duration = Duration::from_secs(3);
 
+/// The toast background. Builds without `gui` show toasts as plain text.
+#[cfg(feature = "gui")]
 #[derive(Resource)]
 struct ToastSprite {
     aseprite: Handle<Aseprite>,
@@ -29,7 +32,6 @@ pub struct ToastPlugin;
 impl Plugin for ToastPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<ShowToast>()
-            .add_systems(PreStartup, load_toast_sprite)
             .add_systems(Startup, spawn_toast_container)
             .add_systems(
                 Update,
@@ -38,9 +40,13 @@ impl Plugin for ToastPlugin {
                     tick_toast_timers.run_if(any_with_component::<ToastTimer>),
                 ),
             );
+
+        #[cfg(feature = "gui")]
+        app.add_systems(PreStartup, load_toast_sprite);
     }
 }
 
+#[cfg(feature = "gui")]
 fn load_toast_sprite(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(ToastSprite {
         aseprite: asset_server.load("sprites/toast_1.aseprite"),
@@ -65,20 +71,23 @@ fn spawn_toast_container(mut commands: Commands) {
 fn spawn_toast(
     mut commands: Commands,
     mut events: MessageReader<ShowToast>,
-    toast_sprite: Res<ToastSprite>,
-    aseprites: Res<Assets<Aseprite>>,
+    #[cfg(feature = "gui")] toast_sprite: Res<ToastSprite>,
+    #[cfg(feature = "gui")] aseprites: Res<Assets<Aseprite>>,
     container: Query<Entity, With<ToastContainer>>,
 ) {
     let Ok(container) = container.single() else {
         return;
     };
+    #[cfg(feature = "gui")]
     let Some(aseprite) = aseprites.get(&toast_sprite.aseprite) else {
         return;
     };
+    #[cfg(feature = "gui")]
     let Some(slice) = aseprite.slices.get("Slice 1") else {
         return;
     };
 
+    #[cfg(feature = "gui")]
     let image_mode = match slice.nine_patch {
         Some(borders) => NodeImageMode::Sliced(TextureSlicer {
             border: BorderRect {
@@ -94,6 +103,19 @@ fn spawn_toast(
 
     for event in events.read() {
         commands.entity(container).with_children(|parent| {
+            #[cfg(feature = "gui")]
+            let background = (
+                ImageNode {
+                    image_mode: image_mode.clone(),
+                    ..default()
+                },
+                AseSlice {
+                    name: "Slice 1".into(),
+                    aseprite: toast_sprite.aseprite.clone(),
+                },
+            );
+            #[cfg(not(feature = "gui"))]
+            let background = ();
             parent
                 .spawn((
                     ToastTimer(Timer::new(TOAST_DURATION, TimerMode::Once)),
@@ -101,14 +123,7 @@ fn spawn_toast(
                         padding: UiRect::all(Val::Px(8.0)),
                         ..default()
                     },
-                    ImageNode {
-                        image_mode: image_mode.clone(),
-                        ..default()
-                    },
-                    AseSlice {
-                        name: "Slice 1".into(),
-                        aseprite: toast_sprite.aseprite.clone(),
-                    },
+                    background,
                 ))
                 .with_children(|toast| {
                     toast.spawn((
@@ -0,0 +1,99 @@
+//! Spending gold and materials on the current town's buildings.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::economy::{Currency, Wallet};
+use crate::game::{CharacterDataExt, ShowToast};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::location::{CurrentTown, TownBuilding, TownState};
+use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::ui::UiFeedback;
+
+/// Upgrade this building in the current town.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TownUpgradeRequest(pub TownBuilding);
+
+pub struct TownUpgradesPlugin;
+
+impl Plugin for TownUpgradesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_character_resource::<TownState>()
+            .add_message::<TownUpgradeRequest>()
+            .add_systems(
+                Update,
+                handle_upgrade_requests
+                    .run_if(on_message::<TownUpgradeRequest>)
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+/// The upgrade levels of every town, and which town the player is in.
+#[derive(SystemParam)]
+struct TownBuildings<'w> {
+    state: ResMut<'w, TownState>,
+    town: Res<'w, CurrentTown>,
+}
+
+fn handle_upgrade_requests(
+    mut requests: MessageReader<TownUpgradeRequest>,
+    buildings: TownBuildings,
+    mut player: Query<(&mut Inventory, &mut Wallet), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let TownBuildings { mut state, town } = buildings;
+    let Ok((mut inventory, mut wallet)) = player.single_mut() else {
+        return;
+    };
+
+    for &TownUpgradeRequest(building) in requests.read() {
+        let name = loc.get(building.label_key()).to_string();
+        let Some(cost) = state.upgrade_cost(town.0, building) else {
+            feedback.write(UiFeedback::Error);
+            toast_writer.write(ShowToast::new(
+                loc.format("town_upgrades.max_level", &[("building", &name)]),
+            ));
+            continue;
+        };
+
+        let missing = cost
+            .materials
+            .iter()
+            .find(|&&(item_id, quantity)| inventory.count_item(item_id) < quantity);
+        if let Some(&(item_id, quantity)) = missing {
+            feedback.write(UiFeedback::Error);
+            let item = loc.item_name(item_id, &registry.get(item_id).name);
+            toast_writer.write(ShowToast::new(loc.format(
+                "town_upgrades.missing_materials",
+                &[("item", &item), ("quantity", &quantity)],
+            )));
+            continue;
+        }
+        if wallet.spend(Currency::Gold, cost.gold).is_err() {
+            feedback.write(UiFeedback::Error);
+            toast_writer.write(ShowToast::new(
+                loc.format("town_upgrades.cannot_afford", &[("gold", &cost.gold)]),
+            ));
+            continue;
+        }
+
+        for &(item_id, quantity) in &cost.materials {
+            inventory.decrease_item_quantity(item_id, quantity);
+        }
+        state.upgrade(town.0, building);
+        toast_writer.write(ShowToast::new(loc.format(
+            "town_upgrades.upgraded",
+            &[
+                ("building", &name),
+                ("level", &state.level(town.0, building)),
+            ],
+        )));
+    }
+}
@@ -0,0 +1,170 @@
+//! The training dummy in town. Hits on it go through the same damage pipeline as real
+//! combat; the meter here adds them up and, once the player stops swinging, toasts how
+//! much damage per second they managed.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::combat::HitLanded;
+use crate::dungeon::TrainingDummyEntity;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+
+/// Seconds of hits the live damage per second is taken over.
+pub const DPS_WINDOW_SECS: f32 = 5.0;
+
+/// Seconds without a hit after which a session at the dummy counts as over.
+pub const DUMMY_IDLE_SECS: f32 = 2.0;
+
+/// How a session at the dummy went.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DpsSummary {
+    pub damage: i32,
+    pub hits: u32,
+    /// From the first hit to the last.
+    pub secs: f32,
+    /// Damage over the whole session, per second.
+    pub average: f32,
+    /// Best damage per second over any rolling window in the session.
+    pub peak: f32,
+}
+
+/// Damage dealt to the training dummy in the current session.
+#[derive(Resource, Debug, Default)]
+pub struct DpsMeter {
+    /// Hits within the rolling window, as (elapsed seconds, damage).
+    window: VecDeque<(f32, i32)>,
+    started: Option<f32>,
+    last_hit: f32,
+    damage: i32,
+    hits: u32,
+    peak: f32,
+}
+
+impl DpsMeter {
+    pub fn is_active(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn record(&mut self, now: f32, damage: i32) {
+        let started = *self.started.get_or_insert(now);
+        self.last_hit = now;
+        self.damage += damage;
+        self.hits += 1;
+        self.window.push_back((now, damage));
+        while self
+            .window
+            .front()
+            .is_some_and(|&(at, _)| at < now - DPS_WINDOW_SECS)
+        {
+            self.window.pop_front();
+        }
+
+        // A session shorter than the window is measured over the time it has run, but never
+        // less than a second, so a single opening hit doesn't read as a huge spike.
+        let span = (now - started).clamp(1.0, DPS_WINDOW_SECS);
+        let in_window: i32 = self.window.iter().map(|&(_, damage)| damage).sum();
+        self.peak = self.peak.max(in_window as f32 / span);
+    }
+
+    /// Ends the session if the player has stopped hitting, returning how it went.
+    pub fn finish_if_idle(&mut self, now: f32) -> Option<DpsSummary> {
+        let started = self.started?;
+        if now - self.last_hit < DUMMY_IDLE_SECS {
+            return None;
+        }
+        let secs = self.last_hit - started;
+        let summary = DpsSummary {
+            damage: self.damage,
+            hits: self.hits,
+            secs,
+            average: self.damage as f32 / secs.max(1.0),
+            peak: self.peak,
+        };
+        *self = Self::default();
+        Some(summary)
+    }
+}
+
+pub struct TrainingPlugin;
+
+impl Plugin for TrainingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DpsMeter>().add_systems(
+            Update,
+            (
+                record_dummy_hits.run_if(on_message::<HitLanded>),
+                report_dummy_dps.run_if(|meter: Res<DpsMeter>| meter.is_active()),
+            )
+                .chain(),
+        );
+    }
+}
+
+fn record_dummy_hits(
+    mut hits: MessageReader<HitLanded>,
+    dummies: Query<(), With<TrainingDummyEntity>>,
+    time: Res<Time>,
+    mut meter: ResMut<DpsMeter>,
+) {
+    let now = time.elapsed_secs();
+    for hit in hits.read() {
+        if dummies.contains(hit.target) {
+            meter.record(now, hit.damage);
+        }
+    }
+}
+
+fn report_dummy_dps(
+    time: Res<Time>,
+    loc: Res<Localization>,
+    mut meter: ResMut<DpsMeter>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Some(summary) = meter.finish_if_idle(time.elapsed_secs()) else {
+        return;
+    };
+    toast_writer.write(ShowToast::new(loc.format(
+        "training.summary",
+        &[
+            ("damage", &summary.damage),
+            ("hits", &summary.hits),
+            ("secs", &format!("{:.1}", summary.secs)),
+            ("dps", &format!("{:.1}", summary.average)),
+            ("peak", &format!("{:.1}", summary.peak)),
+        ],
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_waits_for_the_player_to_stop() {
+        let mut meter = DpsMeter::default();
+        for second in 0..4 {
+            meter.record(second as f32, 10);
+        }
+        assert_eq!(meter.finish_if_idle(4.0), None);
+
+        let summary = meter.finish_if_idle(3.0 + DUMMY_IDLE_SECS).unwrap();
+        assert_eq!(summary.damage, 40);
+        assert_eq!(summary.hits, 4);
+        assert_eq!(summary.secs, 3.0);
+        assert!(!meter.is_active());
+    }
+
+    #[test]
+    fn peak_is_taken_over_the_rolling_window() {
+        let mut meter = DpsMeter::default();
+        meter.record(0.0, 5);
+        // A burst well after the session started counts only the hits still in the window.
+        meter.record(10.0, 50);
+        meter.record(10.5, 50);
+        let summary = meter.finish_if_idle(20.0).unwrap();
+        assert_eq!(summary.peak, 100.0 / DPS_WINDOW_SECS);
+        assert_eq!(summary.average, 105.0 / 10.5);
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::economy::{Currency, Wallet};
+use crate::game::{IrreversibleAction, Storage, UndoHistory};
+use crate::location::{CurrentTown, TownId, TravelRoute};
+use crate::player::PlayerMarker;
+use crate::states::{ScreenSet, StateTransitionRequest};
+use crate::world_event::GameClock;
+
+/// In-game hours a journey between towns takes, whatever the route.
+const JOURNEY_HOURS: f32 = 8.0;
+
+/// Request to leave the current town for `to`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct TravelRequest {
+    pub to: TownId,
+}
+
+#[derive(Message, Debug, Clone)]
+pub enum TravelResult {
+    Departed { route: TravelRoute },
+    Arrived { town: TownId },
+    FailedNotEnoughGold { need: i32, have: i32 },
+    FailedAlreadyThere,
+    FailedAlreadyTraveling,
+}
+
+/// A journey in progress. The player arrives when the timer finishes.
+#[derive(Resource, Debug)]
+pub struct Journey {
+    pub route: TravelRoute,
+    pub timer: Timer,
+}
+
+/// Storage left behind in towns other than the current one. The active town's storage is
+/// always the [`Storage`] resource.
+#[derive(Resource, Debug, Default)]
+pub struct TownStorages(HashMap<TownId, Storage>);
+
+impl TownStorages {
+    /// Leaves `current` in `from` and returns the storage kept in `to`, creating an empty
+    /// one with the town's capacity on the first visit.
+    pub fn swap(&mut self, from: TownId, to: TownId, current: Storage) -> Storage {
+        self.0.insert(from, current);
+        self.0
+            .remove(&to)
+            .unwrap_or_else(|| Storage::with_slots(to.spec().storage_slots))
+    }
+
+    /// Makes `to` the current town, leaving the active storage behind in the old one.
+    pub fn move_to(&mut self, to: TownId, town: &mut CurrentTown, storage: &mut Storage) {
+        let left_behind = std::mem::take(storage);
+        *storage = self.swap(town.0, to, left_behind);
+        town.0 = to;
+    }
+}
+
+pub struct TravelPlugin;
+
+impl Plugin for TravelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurrentTown>()
+            .init_resource::<TownStorages>()
+            .add_message::<TravelRequest>()
+            .add_message::<TravelResult>()
+            .add_systems(
+                Update,
+                (
+                    handle_travel_request.run_if(on_message::<TravelRequest>),
+                    tick_journey.run_if(resource_exists::<Journey>),
+                )
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+fn handle_travel_request(
+    mut commands: Commands,
+    mut requests: MessageReader<TravelRequest>,
+    mut results: MessageWriter<TravelResult>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    town: Res<CurrentTown>,
+    journey: Option<Res<Journey>>,
+) {
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    // Requests made in the same frame collapse into the last one.
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+
+    if journey.is_some() {
+        results.write(TravelResult::FailedAlreadyTraveling);
+        return;
+    }
+    if request.to == town.0 {
+        results.write(TravelResult::FailedAlreadyThere);
+        return;
+    }
+
+    let route = TravelRoute::new(town.0, request.to);
+    let cost = route.gold_cost();
+    if wallet.gold() < cost {
+        results.write(TravelResult::FailedNotEnoughGold {
+            need: cost,
+            have: wallet.gold(),
+        });
+        return;
+    }
+
+    wallet.subtract(Currency::Gold, cost);
+    commands.insert_resource(Journey {
+        route,
+        timer: Timer::new(route.duration(), TimerMode::Once),
+    });
+    results.write(TravelResult::Departed { route });
+}
+
+/// What a journey's end updates: the town and its storage, the dungeon, and the clock.
+#[derive(SystemParam)]
+struct Arrival<'w> {
+    town: ResMut<'w, CurrentTown>,
+    storage: ResMut<'w, Storage>,
+    town_storages: ResMut<'w, TownStorages>,
+    dungeon: ResMut<'w, DungeonState>,
+    clock: ResMut<'w, GameClock>,
+}
+
+fn tick_journey(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut journey: ResMut<Journey>,
+    arrival: Arrival,
+    mut history: ResMut<UndoHistory>,
+    mut results: MessageWriter<TravelResult>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    if !journey.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Arrival {
+        mut town,
+        mut storage,
+        mut town_storages,
+        mut dungeon,
+        mut clock,
+    } = arrival;
+
+    let route = journey.route;
+    town_storages.move_to(route.to, &mut town, &mut storage);
+    clock.pass_hours(JOURNEY_HOURS);
+
+    dungeon.reset_dungeon();
+    dungeon.exit_dungeon();
+    history.record_irreversible(IrreversibleAction::Travel);
+
+    commands.remove_resource::<Journey>();
+    results.write(TravelResult::Arrived { town: route.to });
+    state_requests.write(StateTransitionRequest::Dungeon);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_visit_gets_the_towns_storage_capacity() {
+        let mut storages = TownStorages::default();
+
+        let outpost = storages.swap(TownId::Village, TownId::Outpost, Storage::new());
+        assert_eq!(outpost.inventory.max_slots(), 10);
+
+        let village = storages.swap(TownId::Outpost, TownId::Village, outpost);
+        assert_eq!(village.inventory.max_slots(), usize::MAX);
+    }
+}
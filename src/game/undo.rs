@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::economy::{Currency, Wallet};
+use crate::game::{ShowToast, Storage};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment, ManagesItems};
+use crate::item::Item;
+use crate::player::PlayerMarker;
+
+/// A player action that can be reversed with [`GameAction::Undo`].
+#[derive(Debug, Clone)]
+pub enum UndoableAction {
+    Deposit { item: Item },
+    Withdraw { item: Item },
+    Equip { slot: EquipmentSlot, previous: Option<Uuid> },
+    Unequip { slot: EquipmentSlot, item_uuid: Uuid },
+    Sell { item: Item, price: i32 },
+}
+
+/// An action that takes the undo slot but cannot be reversed itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IrreversibleAction {
+    Purchase,
+    BankGold,
+    Travel,
+    BulkTransfer,
+    BulkSale,
+    AuctionListing,
+}
+
+#[derive(Debug, Clone)]
+enum HistoryEntry {
+    Undoable(UndoableAction),
+    Irreversible(IrreversibleAction),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UndoError {
+    #[error("Nothing to undo")]
+    NothingToUndo,
+    #[error("{0:?} cannot be undone")]
+    Irreversible(IrreversibleAction),
+    #[error("Inventory is full")]
+    InventoryFull,
+    #[error("Not enough gold: need {need}, have {have}")]
+    NotEnoughGold { need: i32, have: i32 },
+    #[error("The item is no longer there")]
+    ItemMissing,
+}
+
+/// Single-level undo: only the most recent action is kept.
+#[derive(Resource, Debug, Default)]
+pub struct UndoHistory {
+    last: Option<HistoryEntry>,
+}
+
+impl UndoHistory {
+    pub fn record(&mut self, action: UndoableAction) {
+        self.last = Some(HistoryEntry::Undoable(action));
+    }
+
+    pub fn record_irreversible(&mut self, action: IrreversibleAction) {
+        self.last = Some(HistoryEntry::Irreversible(action));
+    }
+
+    /// Takes the last action out of the history. Irreversible actions stay recorded so
+    /// repeated undo attempts keep explaining why nothing happened.
+    pub fn take(&mut self) -> Result<UndoableAction, UndoError> {
+        match self.last.take() {
+            Some(HistoryEntry::Undoable(action)) => Ok(action),
+            Some(HistoryEntry::Irreversible(action)) => {
+                self.last = Some(HistoryEntry::Irreversible(action));
+                Err(UndoError::Irreversible(action))
+            }
+            None => Err(UndoError::NothingToUndo),
+        }
+    }
+}
+
+impl UndoableAction {
+    /// Reverses the action. On error nothing is changed.
+    pub fn revert(
+        &self,
+        inventory: &mut Inventory,
+        storage: &mut Storage,
+        wallet: &mut Wallet,
+    ) -> Result<(), UndoError> {
+        match self {
+            UndoableAction::Deposit { item } => move_one(&mut storage.inventory, inventory, item),
+            UndoableAction::Withdraw { item } => move_one(inventory, &mut storage.inventory, item),
+            UndoableAction::Equip { slot, previous: Some(previous) } => {
+                if inventory.find_item_index_by_uuid(*previous).is_none() {
+                    return Err(UndoError::ItemMissing);
+                }
+                inventory.equip_from_inventory(*previous, *slot);
+                Ok(())
+            }
+            UndoableAction::Equip { slot, previous: None } => {
+                inventory.unequip_item(*slot).map_err(|_| UndoError::InventoryFull)
+            }
+            UndoableAction::Unequip { slot, item_uuid } => {
+                if inventory.find_item_index_by_uuid(*item_uuid).is_none() {
+                    return Err(UndoError::ItemMissing);
+                }
+                inventory.equip_from_inventory(*item_uuid, *slot);
+                Ok(())
+            }
+            UndoableAction::Sell { item, price } => {
+                if wallet.gold() < *price {
+                    return Err(UndoError::NotEnoughGold {
+                        need: *price,
+                        have: wallet.gold(),
+                    });
+                }
+                inventory
+                    .add_to_inv(item.clone())
+                    .map_err(|_| UndoError::InventoryFull)?;
+                wallet.subtract(Currency::Gold, *price);
+                Ok(())
+            }
+        }
+    }
+
+    fn toast_key(&self) -> &'static str {
+        match self {
+            UndoableAction::Deposit { .. } => "undo.deposit",
+            UndoableAction::Withdraw { .. } => "undo.withdraw",
+            UndoableAction::Equip { .. } => "undo.equip",
+            UndoableAction::Unequip { .. } => "undo.unequip",
+            UndoableAction::Sell { .. } => "undo.sell",
+        }
+    }
+}
+
+/// Moves one unit of `item` from `from` to `to`, preferring the exact item by UUID and
+/// falling back to any stack of the same kind for stackable items.
+fn move_one(from: &mut Inventory, to: &mut Inventory, item: &Item) -> Result<(), UndoError> {
+    let index = from
+        .find_item_index_by_uuid(item.item_uuid)
+        .or_else(|| {
+            if item.item_type.is_equipment() {
+                return None;
+            }
+            from.items.iter().position(|i| i.item.item_id == item.item_id)
+        })
+        .ok_or(UndoError::ItemMissing)?;
+
+    let taken = if from.items[index].quantity > 1 {
+        from.items[index].decrease_quantity(1);
+        from.items[index].item.clone()
+    } else {
+        from.items.remove(index).item
+    };
+
+    if to.add_to_inv(taken.clone()).is_err() {
+        let _ = from.add_to_inv(taken);
+        return Err(UndoError::InventoryFull);
+    }
+    Ok(())
+}
+
+pub struct UndoPlugin;
+
+impl Plugin for UndoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UndoHistory>()
+            .add_systems(Update, handle_undo.run_if(on_message::<GameAction>));
+    }
+}
+
+fn handle_undo(
+    mut action_reader: MessageReader<GameAction>,
+    mut history: ResMut<UndoHistory>,
+    mut player: Query<(&mut Inventory, &mut Wallet), With<PlayerMarker>>,
+    mut storage: ResMut<Storage>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::Undo {
+            continue;
+        }
+        let Ok((mut inventory, mut wallet)) = player.single_mut() else {
+            return;
+        };
+
+        let result = history.take().and_then(|undo| {
+            match undo.revert(&mut inventory, &mut storage, &mut wallet) {
+                Ok(()) => Ok(undo),
+                Err(e) => {
+                    history.record(undo);
+                    Err(e)
+                }
+            }
+        });
+
+        let message = match result {
+            Ok(undo) => match &undo {
+                UndoableAction::Deposit { item }
+                | UndoableAction::Withdraw { item }
+                | UndoableAction::Sell { item, .. } => {
+                    loc.format(undo.toast_key(), &[("item", &item.name)])
+                }
+                UndoableAction::Equip { .. } | UndoableAction::Unequip { .. } => {
+                    loc.get(undo.toast_key()).to_string()
+                }
+            },
+            Err(UndoError::NothingToUndo) => loc.get("undo.nothing").to_string(),
+            Err(UndoError::Irreversible(IrreversibleAction::Purchase)) => {
+                loc.get("undo.irreversible_purchase").to_string()
+            }
+            Err(UndoError::Irreversible(IrreversibleAction::BankGold)) => {
+                loc.get("undo.irreversible_bank").to_string()
+            }
+            Err(UndoError::Irreversible(IrreversibleAction::Travel)) => {
+                loc.get("undo.irreversible_travel").to_string()
+            }
+            Err(UndoError::Irreversible(IrreversibleAction::BulkTransfer)) => {
+                loc.get("undo.irreversible_bulk").to_string()
+            }
+            Err(UndoError::Irreversible(IrreversibleAction::BulkSale)) => {
+                loc.get("undo.irreversible_bulk_sale").to_string()
+            }
+            Err(UndoError::Irreversible(IrreversibleAction::AuctionListing)) => {
+                loc.get("undo.irreversible_auction").to_string()
+            }
+            Err(UndoError::InventoryFull) => loc.get("undo.inventory_full").to_string(),
+            Err(UndoError::NotEnoughGold { need, .. }) => {
+                loc.format("undo.not_enough_gold", &[("need", &need)])
+            }
+            Err(UndoError::ItemMissing) => loc.get("undo.item_missing").to_string(),
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::enums::{EquipmentType, ItemType, MaterialType};
+    use crate::item::ItemId;
+
+    fn ore() -> Item {
+        Item::test(ItemId::Coal, ItemType::Material(MaterialType::Ore))
+    }
+
+    fn sword() -> Item {
+        Item::test(ItemId::Sword, ItemType::Equipment(EquipmentType::Weapon))
+    }
+
+    #[test]
+    fn undo_deposit_returns_one_unit_from_a_stack() {
+        let mut inventory = Inventory::new();
+        let mut storage = Storage::new();
+        let mut gold = Wallet::with_gold(0);
+        let coal = ore();
+        storage.add_to_inv(coal.clone()).unwrap();
+        storage.add_to_inv(ore()).unwrap();
+
+        UndoableAction::Deposit { item: coal }
+            .revert(&mut inventory, &mut storage, &mut gold)
+            .unwrap();
+
+        assert_eq!(storage.count_item(ItemId::Coal), 1);
+        assert_eq!(inventory.count_item(ItemId::Coal), 1);
+    }
+
+    #[test]
+    fn undo_sell_needs_the_gold_back() {
+        let mut inventory = Inventory::new();
+        let mut storage = Storage::new();
+        let mut gold = Wallet::with_gold(5);
+        let action = UndoableAction::Sell {
+            item: sword(),
+            price: 10,
+        };
+
+        assert_eq!(
+            action.revert(&mut inventory, &mut storage, &mut gold),
+            Err(UndoError::NotEnoughGold { need: 10, have: 5 })
+        );
+        assert!(inventory.items.is_empty());
+
+        gold.add(Currency::Gold, 7);
+        action.revert(&mut inventory, &mut storage, &mut gold).unwrap();
+        assert_eq!(gold.gold(), 2);
+        assert_eq!(inventory.count_item(ItemId::Sword), 1);
+    }
+
+    #[test]
+    fn undo_equip_restores_the_previous_item() {
+        let mut inventory = Inventory::new();
+        let mut storage = Storage::new();
+        let mut gold = Wallet::with_gold(0);
+        let old = sword();
+        let new = sword();
+        let (old_uuid, new_uuid) = (old.item_uuid, new.item_uuid);
+        inventory.add_to_inv(old).unwrap();
+        inventory.add_to_inv(new).unwrap();
+        inventory.equip_from_inventory(old_uuid, EquipmentSlot::Weapon);
+        inventory.equip_from_inventory(new_uuid, EquipmentSlot::Weapon);
+
+        UndoableAction::Equip {
+            slot: EquipmentSlot::Weapon,
+            previous: Some(old_uuid),
+        }
+        .revert(&mut inventory, &mut storage, &mut gold)
+        .unwrap();
+
+        let equipped = inventory.get_equipped_item(EquipmentSlot::Weapon).unwrap();
+        assert_eq!(equipped.uuid(), old_uuid);
+        assert!(inventory.find_item_index_by_uuid(new_uuid).is_some());
+    }
+
+    #[test]
+    fn irreversible_actions_block_undo() {
+        let mut history = UndoHistory::default();
+        assert_eq!(history.take().unwrap_err(), UndoError::NothingToUndo);
+
+        history.record(UndoableAction::Withdraw { item: ore() });
+        history.record_irreversible(IrreversibleAction::Purchase);
+
+        let blocked = Err(UndoError::Irreversible(IrreversibleAction::Purchase));
+        assert_eq!(history.take().map(|_| ()), blocked);
+        assert_eq!(history.take().map(|_| ()), blocked);
+    }
+}
@@ -0,0 +1,393 @@
+//! The weekly challenge: a short run down the Endless Depths that everyone plays with the
+//! same seed and modifiers until the ISO week turns over. Finishing it opens the vault,
+//! whose reward chest can be claimed once a week. The week's best attempt is kept as a
+//! ghost for later attempts to race.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::dungeon::systems::FloorTransitions;
+use crate::dungeon::{
+    handle_floor_transition, ChallengeModifier, DungeonState, FloorId, FloorSeedQueue,
+    FloorTransition, MobEntity, SeededFloors,
+};
+use crate::entities::Progression;
+use crate::game::endless::equipped_weapon_name;
+use crate::game::game_stats::{GameStats, WeeklyRecord};
+use crate::game::ghost::{FloorSplit, Ghost, GhostRecorder};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::inventory::Inventory;
+use crate::location::{CurrentTown, LocationId};
+use crate::player::{PlayerMarker, PlayerName};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::stats::{StatSheet, StatType};
+use crate::ui::DyingMob;
+
+/// Floors to clear before the vault opens.
+pub const CHALLENGE_FLOORS: usize = 5;
+
+/// Modifiers each week's challenge is played under.
+pub const CHALLENGE_MODIFIER_COUNT: usize = 2;
+
+/// A week of the ISO 8601 calendar: weeks start on Monday, and week 1 is the one with the
+/// year's first Thursday in it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct IsoWeek {
+    pub year: i32,
+    pub week: u32,
+}
+
+impl IsoWeek {
+    /// The week `days` after 1970-01-01.
+    pub fn from_unix_days(days: i64) -> Self {
+        // 1970-01-01 was a Thursday, so this puts Monday at 0.
+        let weekday = (days + 3).rem_euclid(7);
+        let thursday = days - weekday + 3;
+
+        let mut year = 1970 + (thursday as f64 / 365.2425).floor() as i64;
+        while days_from_civil(year + 1, 1, 1) <= thursday {
+            year += 1;
+        }
+        while days_from_civil(year, 1, 1) > thursday {
+            year -= 1;
+        }
+        let ordinal = thursday - days_from_civil(year, 1, 1);
+        Self {
+            year: year as i32,
+            week: (ordinal / 7 + 1) as u32,
+        }
+    }
+
+    /// The week it is now, going by the system clock.
+    pub fn current() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Self::from_unix_days((secs / 86_400) as i64)
+    }
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// One week's challenge. Everything about it follows from the week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyChallenge {
+    pub week: IsoWeek,
+    pub seed: u64,
+    pub modifiers: Vec<ChallengeModifier>,
+}
+
+impl WeeklyChallenge {
+    pub fn for_week(week: IsoWeek) -> Self {
+        let mut rng = StdRng::seed_from_u64(((week.year as u64) << 8) | week.week as u64);
+        Self {
+            week,
+            seed: rng.r#gen(),
+            modifiers: ChallengeModifier::roll(CHALLENGE_MODIFIER_COUNT, &mut rng),
+        }
+    }
+
+    /// The challenge's floors, followed by the vault when its reward is still unclaimed.
+    pub fn floors(&self, with_vault: bool) -> Vec<FloorId> {
+        let mut floors = vec![FloorId::EndlessDepths; CHALLENGE_FLOORS];
+        if with_vault {
+            floors.push(FloorId::ChallengeVault);
+        }
+        floors
+    }
+}
+
+/// Request to start this week's challenge from town.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StartWeeklyChallenge;
+
+/// The weekly challenge in progress.
+#[derive(Resource, Debug)]
+pub struct WeeklyRun {
+    pub challenge: WeeklyChallenge,
+    floors: SeededFloors,
+}
+
+pub struct WeeklyPlugin;
+
+impl Plugin for WeeklyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<StartWeeklyChallenge>()
+            .add_systems(
+                Update,
+                start_weekly_challenge
+                    .run_if(on_message::<StartWeeklyChallenge>)
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(
+                Update,
+                follow_weekly_run
+                    .run_if(on_message::<FloorTransition>)
+                    .before(handle_floor_transition)
+                    .run_if(resource_exists::<WeeklyRun>)
+                    .run_if(in_state(AppState::Dungeon)),
+            );
+    }
+}
+
+/// What starting a challenge sets up: the town it leaves from, the records that decide the
+/// vault and the ghost, the dungeon it enters, and the first floor's seed.
+#[derive(SystemParam)]
+struct WeeklyStart<'w> {
+    town: Res<'w, CurrentTown>,
+    stats: Res<'w, GameStats>,
+    dungeon: ResMut<'w, DungeonState>,
+    seed_queue: ResMut<'w, FloorSeedQueue>,
+}
+
+fn start_weekly_challenge(
+    mut commands: Commands,
+    mut requests: MessageReader<StartWeeklyChallenge>,
+    run: Option<Res<WeeklyRun>>,
+    start: WeeklyStart,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let WeeklyStart {
+        town,
+        stats,
+        mut dungeon,
+        mut seed_queue,
+    } = start;
+    if requests.read().last().is_none() || run.is_some() {
+        return;
+    }
+    if dungeon.current_location != Some(town.0.spec().home) {
+        toast_writer.write(ShowToast::new(loc.get("endless.not_in_town")));
+        return;
+    }
+
+    let challenge = WeeklyChallenge::for_week(IsoWeek::current());
+    let with_vault = !stats.weekly_reward_claimed(challenge.week);
+    let mut floors = SeededFloors::new(challenge.seed);
+    floors.queue_next(&mut seed_queue);
+
+    dungeon.reset_dungeon();
+    dungeon.exit_dungeon();
+    dungeon.enter_challenge(
+        LocationId::EndlessDepths,
+        challenge.floors(with_vault),
+        challenge.modifiers.clone(),
+    );
+    toast_writer.write(ShowToast::new(
+        loc.format("weekly.started", &[("week", &challenge.week.week)]),
+    ));
+    match stats
+        .weekly_best(challenge.week)
+        .filter(|best| !best.trace.is_empty())
+    {
+        Some(best) => commands.insert_resource(Ghost(best.trace.clone())),
+        None => commands.remove_resource::<Ghost>(),
+    }
+    commands.insert_resource(GhostRecorder::default());
+    commands.insert_resource(WeeklyRun { challenge, floors });
+    state_requests.write(StateTransitionRequest::Dungeon);
+}
+
+/// The challenge under way, the floor it has reached and what is left on it, and the
+/// records it ends up in.
+#[derive(SystemParam)]
+struct WeeklyProgress<'w, 's> {
+    run: ResMut<'w, WeeklyRun>,
+    dungeon: Res<'w, DungeonState>,
+    mobs: Query<'w, 's, (), (With<MobEntity>, Without<DyingMob>)>,
+    seed_queue: ResMut<'w, FloorSeedQueue>,
+    stats: ResMut<'w, GameStats>,
+    player: Query<
+        'w,
+        's,
+        (
+            &'static PlayerName,
+            &'static Progression,
+            &'static Inventory,
+            &'static StatSheet,
+        ),
+        With<PlayerMarker>,
+    >,
+}
+
+/// Queues floor seeds on the way down and records the attempt once the player finishes
+/// the last floor or leaves early. Clearing every mob on the last floor counts as
+/// finishing it when there is no vault to move on to. Each finished floor closes a split
+/// of the attempt's trace.
+fn follow_weekly_run(
+    mut commands: Commands,
+    mut transitions: FloorTransitions,
+    progress: WeeklyProgress,
+    mut recorder: ResMut<GhostRecorder>,
+    ghost: Option<Res<Ghost>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let WeeklyProgress {
+        mut run,
+        dungeon,
+        mobs,
+        mut seed_queue,
+        mut stats,
+        player,
+    } = progress;
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if dungeon.current_location != Some(LocationId::EndlessDepths) {
+        end_weekly_run(&mut commands);
+        return;
+    }
+    let Ok((name, progression, inventory, player_stats)) = player.single() else {
+        return;
+    };
+    let hp = player_stats.value(StatType::Health);
+
+    let on_last_floor = dungeon.floor_index + 1 >= CHALLENGE_FLOORS;
+    let advancing = matches!(transition, FloorTransition::AdvanceFloor);
+    let finished_floor = advancing || (on_last_floor && mobs.is_empty());
+    if finished_floor {
+        let split = recorder.finish_floor(hp);
+        if let Some(message) = split_message(&loc, &recorder, ghost.as_deref(), split) {
+            toast_writer.write(ShowToast::new(message));
+        }
+    }
+    if advancing && !on_last_floor {
+        run.floors.queue_next(&mut seed_queue);
+        return;
+    }
+    let completed = finished_floor;
+
+    let week = run.challenge.week;
+    let new_best = stats.record_weekly(WeeklyRecord {
+        week,
+        floors_cleared: if completed {
+            CHALLENGE_FLOORS as u32
+        } else {
+            dungeon.floor_index as u32
+        },
+        completed,
+        seed: run.challenge.seed,
+        name: name.0.clone(),
+        level: progression.level,
+        weapon: equipped_weapon_name(inventory, &loc),
+        trace: std::mem::take(&mut recorder.trace),
+    });
+
+    let vault_next = completed && dungeon.floor_sequence.last() == Some(&FloorId::ChallengeVault);
+    if vault_next {
+        run.floors.queue_next(&mut seed_queue);
+        stats.claim_weekly_reward(week);
+    } else {
+        seed_queue.0.clear();
+    }
+    end_weekly_run(&mut commands);
+
+    let message = if vault_next {
+        loc.get("weekly.vault_open").to_string()
+    } else if completed {
+        loc.get("weekly.completed").to_string()
+    } else {
+        let key = if new_best {
+            "weekly.new_best"
+        } else {
+            "weekly.ended"
+        };
+        loc.format(
+            key,
+            &[
+                ("floors", &dungeon.floor_index),
+                ("total", &CHALLENGE_FLOORS),
+            ],
+        )
+    };
+    toast_writer.write(ShowToast::new(message));
+}
+
+fn end_weekly_run(commands: &mut Commands) {
+    commands.remove_resource::<WeeklyRun>();
+    commands.remove_resource::<GhostRecorder>();
+    commands.remove_resource::<Ghost>();
+}
+
+/// Compares a finished floor against the ghost, while there is one that got this far.
+fn split_message(
+    loc: &Localization,
+    recorder: &GhostRecorder,
+    ghost: Option<&Ghost>,
+    split: FloorSplit,
+) -> Option<String> {
+    let lead = ghost?.lead(&recorder.trace)?;
+    let key = if lead >= 0.0 {
+        "ghost.split_ahead"
+    } else {
+        "ghost.split_behind"
+    };
+    Some(loc.format(
+        key,
+        &[
+            ("floor", &recorder.trace.splits.len()),
+            ("time", &format!("{:.1}", split.seconds)),
+            ("lead", &format!("{:.1}", lead.abs())),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iso_weeks_follow_the_first_thursday() {
+        let week = |year, week| IsoWeek { year, week };
+        assert_eq!(IsoWeek::from_unix_days(0), week(1970, 1));
+        // 2021-01-03, a Sunday, still belongs to the last week of 2020.
+        assert_eq!(IsoWeek::from_unix_days(18_630), week(2020, 53));
+        assert_eq!(IsoWeek::from_unix_days(18_631), week(2021, 1));
+        // 2024-12-30, a Monday, starts the first week of 2025.
+        assert_eq!(IsoWeek::from_unix_days(20_087), week(2025, 1));
+        assert_eq!(IsoWeek::from_unix_days(20_742), week(2026, 42));
+    }
+
+    #[test]
+    fn a_week_always_gets_the_same_challenge() {
+        let week = IsoWeek {
+            year: 2026,
+            week: 42,
+        };
+        let challenge = WeeklyChallenge::for_week(week);
+        assert_eq!(challenge, WeeklyChallenge::for_week(week));
+        assert_eq!(challenge.modifiers.len(), CHALLENGE_MODIFIER_COUNT);
+
+        let next = WeeklyChallenge::for_week(IsoWeek { week: 43, ..week });
+        assert_ne!(challenge.seed, next.seed);
+    }
+
+    #[test]
+    fn the_vault_follows_the_last_floor() {
+        let challenge = WeeklyChallenge::for_week(IsoWeek {
+            year: 2026,
+            week: 1,
+        });
+        let floors = challenge.floors(true);
+        assert_eq!(floors.len(), CHALLENGE_FLOORS + 1);
+        assert_eq!(floors.last(), Some(&FloorId::ChallengeVault));
+        assert_eq!(challenge.floors(false).len(), CHALLENGE_FLOORS);
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CharacterResource;
+use crate::inventory::ManagesItems;
+use crate::item::ItemId;
+
+use super::perks::{upgrade_cost, GuildPerk, MAX_HALL_LEVEL, XP_BONUS_PERCENT};
+
+/// Where the guild hall is saved in each character's slot. Every character joins and
+/// upgrades its own hall.
+pub const GUILD_SAVE_PATH: &str = "guild.ron";
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// Guild membership and the state of its hall.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuildHall {
+    pub joined: bool,
+    pub level: u32,
+    /// Materials donated toward the next level.
+    donated: HashMap<ItemId, u32>,
+    /// Day, counted from the Unix epoch, the free potion was last claimed on.
+    last_potion_day: Option<u64>,
+}
+
+impl CharacterResource for GuildHall {
+    const FILE: &'static str = GUILD_SAVE_PATH;
+}
+
+impl GuildHall {
+    pub fn has_perk(&self, perk: GuildPerk) -> bool {
+        self.joined && self.level >= perk.level()
+    }
+
+    pub fn is_max_level(&self) -> bool {
+        self.level >= MAX_HALL_LEVEL
+    }
+
+    pub fn donated(&self, item_id: ItemId) -> u32 {
+        self.donated.get(&item_id).copied().unwrap_or(0)
+    }
+
+    /// Each material the next upgrade needs, with how many have been donated so far.
+    pub fn requirements(&self) -> Vec<(ItemId, u32, u32)> {
+        upgrade_cost(self.level)
+            .unwrap_or_default()
+            .iter()
+            .map(|&(item_id, need)| (item_id, self.donated(item_id).min(need), need))
+            .collect()
+    }
+
+    /// Hands over as much of the next upgrade's materials as `inventory` holds, then
+    /// upgrades the hall if nothing is missing. Returns how many items were donated.
+    pub fn donate(&mut self, inventory: &mut impl ManagesItems) -> u32 {
+        if !self.joined {
+            return 0;
+        }
+
+        let mut total = 0;
+        for (item_id, have, need) in self.requirements() {
+            let amount = (need - have).min(inventory.count_item(item_id));
+            inventory.decrease_item_quantity(item_id, amount);
+            *self.donated.entry(item_id).or_insert(0) += amount;
+            total += amount;
+        }
+
+        let complete = self.requirements().iter().all(|(_, have, need)| have >= need);
+        if complete && !self.is_max_level() {
+            self.level += 1;
+            self.donated.clear();
+        }
+        total
+    }
+
+    /// Combat XP after the guild's bonus.
+    pub fn boost_xp(&self, amount: i32) -> i32 {
+        if self.has_perk(GuildPerk::XpBonus) {
+            amount + amount * XP_BONUS_PERCENT / 100
+        } else {
+            amount
+        }
+    }
+
+    /// Storage tabs granted on top of the usual limit.
+    pub fn extra_storage_tabs(&self) -> usize {
+        usize::from(self.has_perk(GuildPerk::ExtraStorageTab))
+    }
+
+    pub fn can_claim_potion(&self, day: u64) -> bool {
+        self.has_perk(GuildPerk::DailyPotion) && self.last_potion_day.is_none_or(|d| d < day)
+    }
+
+    /// Marks today's free potion as taken. Returns `false` if it was not available.
+    pub fn claim_potion(&mut self, day: u64) -> bool {
+        if !self.can_claim_potion(day) {
+            return false;
+        }
+        self.last_potion_day = Some(day);
+        true
+    }
+}
+
+/// Days since the Unix epoch, used to reset the daily potion.
+pub fn current_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::Inventory;
+    use crate::item::enums::{ItemType, MaterialType};
+    use crate::item::Item;
+    use crate::save::{from_ron, to_ron};
+
+    fn add(inventory: &mut Inventory, item_id: ItemId, quantity: u32) {
+        for _ in 0..quantity {
+            inventory
+                .add_to_inv(Item::test(item_id, ItemType::Material(MaterialType::Ore)))
+                .unwrap();
+        }
+    }
+
+    fn joined() -> GuildHall {
+        GuildHall {
+            joined: true,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn donations_accumulate_until_the_hall_upgrades() {
+        let mut hall = joined();
+        let mut inventory = Inventory::new();
+        add(&mut inventory, ItemId::Coal, 25);
+
+        assert_eq!(hall.donate(&mut inventory), 20);
+        assert_eq!(hall.level, 0);
+        assert_eq!(inventory.count_item(ItemId::Coal), 5);
+        assert_eq!(hall.donated(ItemId::Coal), 20);
+
+        add(&mut inventory, ItemId::CopperIngot, 10);
+        assert_eq!(hall.donate(&mut inventory), 10);
+        assert_eq!(hall.level, 1);
+        assert_eq!(hall.donated(ItemId::Coal), 0);
+        assert_eq!(inventory.count_item(ItemId::Coal), 5);
+        assert_eq!(hall.extra_storage_tabs(), 1);
+    }
+
+    #[test]
+    fn outsiders_cannot_donate_or_use_perks() {
+        let mut hall = GuildHall {
+            level: MAX_HALL_LEVEL,
+            ..default()
+        };
+        let mut inventory = Inventory::new();
+        add(&mut inventory, ItemId::Coal, 5);
+
+        assert_eq!(hall.donate(&mut inventory), 0);
+        assert_eq!(hall.boost_xp(100), 100);
+        assert!(!hall.can_claim_potion(1));
+    }
+
+    #[test]
+    fn potion_can_be_claimed_once_a_day() {
+        let mut hall = GuildHall {
+            level: MAX_HALL_LEVEL,
+            ..joined()
+        };
+
+        assert_eq!(hall.boost_xp(100), 110);
+        assert!(hall.claim_potion(10));
+        assert!(!hall.claim_potion(10));
+        assert!(hall.claim_potion(11));
+    }
+
+    #[test]
+    fn hall_round_trips_through_ron() {
+        let mut hall = joined();
+        let mut inventory = Inventory::new();
+        add(&mut inventory, ItemId::Coal, 3);
+        hall.donate(&mut inventory);
+
+        let loaded = from_ron::<GuildHall>(&to_ron(&hall).unwrap()).unwrap();
+        assert_eq!(loaded, hall);
+    }
+}
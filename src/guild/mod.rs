@@ -0,0 +1,7 @@
+pub mod hall;
+pub mod perks;
+pub mod plugin;
+
+pub use hall::{current_day, GuildHall, GUILD_SAVE_PATH};
+pub use perks::{upgrade_cost, GuildPerk, MAX_HALL_LEVEL, XP_BONUS_PERCENT};
+pub use plugin::{GuildPlugin, GuildRequest};
@@ -0,0 +1,52 @@
+use crate::item::ItemId;
+
+/// Highest level the guild hall can be upgraded to.
+pub const MAX_HALL_LEVEL: u32 = 3;
+
+/// Extra combat XP, in percent, once [`GuildPerk::XpBonus`] is unlocked.
+pub const XP_BONUS_PERCENT: i32 = 10;
+
+/// Account-wide bonuses the guild hall grants as it is upgraded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuildPerk {
+    ExtraStorageTab,
+    XpBonus,
+    DailyPotion,
+}
+
+impl GuildPerk {
+    pub const ALL: [GuildPerk; 3] = [
+        GuildPerk::ExtraStorageTab,
+        GuildPerk::XpBonus,
+        GuildPerk::DailyPotion,
+    ];
+
+    /// Hall level that unlocks the perk.
+    pub fn level(self) -> u32 {
+        match self {
+            GuildPerk::ExtraStorageTab => 1,
+            GuildPerk::XpBonus => 2,
+            GuildPerk::DailyPotion => 3,
+        }
+    }
+
+    /// Localization key for the perk's description.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            GuildPerk::ExtraStorageTab => "guild.perk_storage_tab",
+            GuildPerk::XpBonus => "guild.perk_xp",
+            GuildPerk::DailyPotion => "guild.perk_potion",
+        }
+    }
+}
+
+/// Materials that must be donated to raise the hall above `level`, or `None` once it is
+/// fully upgraded.
+pub fn upgrade_cost(level: u32) -> Option<&'static [(ItemId, u32)]> {
+    match level {
+        0 => Some(&[(ItemId::Coal, 20), (ItemId::CopperIngot, 10)]),
+        1 => Some(&[(ItemId::Coal, 30), (ItemId::IronIngot, 15)]),
+        2 => Some(&[(ItemId::GoldIngot, 10), (ItemId::BlueCrystal, 3)]),
+        _ => None,
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::game::{CharacterDataExt, ShowToast};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::{ItemId, ItemRegistry};
+use crate::player::PlayerMarker;
+use crate::states::ScreenSet;
+use crate::storage::{Storage, BASE_TAB_LIMIT};
+
+use super::hall::{current_day, GuildHall};
+
+/// Something the player asked the guild for.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuildRequest {
+    Join,
+    /// Donate whatever the hall's next upgrade still needs from the backpack.
+    Donate,
+    ClaimPotion,
+}
+
+pub struct GuildPlugin;
+
+impl Plugin for GuildPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_character_resource::<GuildHall>()
+            .add_message::<GuildRequest>()
+            .add_systems(
+                Update,
+                (
+                    handle_guild_requests.run_if(on_message::<GuildRequest>),
+                    apply_guild_storage_tabs.run_if(resource_changed::<GuildHall>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+fn apply_guild_storage_tabs(hall: Res<GuildHall>, mut storage: ResMut<Storage>) {
+    storage.max_tabs = BASE_TAB_LIMIT + hall.extra_storage_tabs();
+}
+
+fn handle_guild_requests(
+    mut requests: MessageReader<GuildRequest>,
+    mut hall: ResMut<GuildHall>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        let message = match request {
+            GuildRequest::Join if hall.joined => continue,
+            GuildRequest::Join => {
+                hall.joined = true;
+                loc.get("guild.joined_toast").to_string()
+            }
+            GuildRequest::Donate => {
+                let level = hall.level;
+                match hall.donate(&mut *inventory) {
+                    0 => loc.get("guild.nothing_to_donate").to_string(),
+                    _ if hall.level > level => {
+                        loc.format("guild.upgraded", &[("level", &hall.level)])
+                    }
+                    count => loc.format("guild.donated", &[("count", &count)]),
+                }
+            }
+            GuildRequest::ClaimPotion => {
+                let day = current_day();
+                if !hall.can_claim_potion(day) {
+                    loc.get("guild.potion_unavailable").to_string()
+                } else if inventory.add_to_inv(registry.spawn(ItemId::BasicHPPotion)).is_err() {
+                    loc.get("guild.inventory_full").to_string()
+                } else {
+                    hall.claim_potion(day);
+                    loc.get("guild.potion_claimed").to_string()
+                }
+            }
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
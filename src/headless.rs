@@ -0,0 +1,158 @@
+//! Runs the whole game without a window or GPU (`headless` feature only), so integration
+//! tests can drive it the way a player would.
+//!
+//! A [`Simulation`] builds the app with every game plugin but none of the windowing or
+//! rendering backends, steps it a fixed 1/60 s per frame, and feeds it
+//! [`GameAction`]s and console commands. Tests then assert on resources and components
+//! after a number of frames.
+//!
+//! ```ignore
+//! let mut sim = Simulation::new();
+//! sim.run_until(600, |sim| sim.state() == AppState::Menu);
+//! sim.run_script(&[SimStep::Action(GameAction::Select), SimStep::Wait(30)]);
+//! assert_eq!(sim.state(), AppState::Dungeon);
+//! ```
+
+use std::time::Duration;
+
+use bevy::app::{PluginGroupBuilder, PluginsState};
+use bevy::prelude::*;
+#[cfg(feature = "gui")]
+use bevy::render::{RenderPlugin, settings::WgpuSettings};
+use bevy::tasks::tick_global_task_pools_on_main_thread;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+#[cfg(feature = "gui")]
+use bevy::winit::WinitPlugin;
+
+use crate::console::ConsoleSubmit;
+use crate::input::GameAction;
+use crate::plugins::GamePlugin;
+use crate::save::{FileSaveBackend, set_save_backend};
+use crate::states::AppState;
+
+/// How far the clock moves each simulated frame.
+pub const SIMULATION_FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// Bevy's default plugins with no window, no event loop and no GPU. Asset types, UI and
+/// sprite components still exist, so game plugins build as usual; nothing is drawn.
+pub fn headless_plugins() -> PluginGroupBuilder {
+    let plugins = DefaultPlugins.build().set(WindowPlugin {
+        primary_window: None,
+        exit_condition: ExitCondition::DontExit,
+        ..default()
+    });
+    // Without `gui` Bevy is built with no renderer or event loop to turn off.
+    #[cfg(feature = "gui")]
+    let plugins = plugins.disable::<WinitPlugin>().set(RenderPlugin {
+        render_creation: WgpuSettings {
+            backends: None,
+            ..default()
+        }
+        .into(),
+        ..default()
+    });
+    plugins
+}
+
+/// One step of a scripted run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimStep {
+    /// Send an input action, as if its key were pressed, and run a frame.
+    Action(GameAction),
+    /// Submit a console line, as if typed and entered, and run a frame.
+    Command(String),
+    /// Run this many frames.
+    Wait(u32),
+}
+
+/// The game running headless.
+pub struct Simulation {
+    app: App,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulation {
+    /// Builds the full game. Saves go to a directory under the system temp dir, so runs
+    /// don't touch the player's saves.
+    pub fn new() -> Self {
+        let saves = std::env::temp_dir().join(format!("randr-headless-{}", std::process::id()));
+        // Tests in one binary share the backend; the first simulation installs it.
+        let _ = set_save_backend(FileSaveBackend::new(saves));
+
+        let mut app = App::new();
+        app.add_plugins(headless_plugins())
+            .add_plugins(GamePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(SIMULATION_FRAME));
+        // What `App::run` does before its first frame.
+        while app.plugins_state() == PluginsState::Adding {
+            tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        Self { app }
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn state(&self) -> AppState {
+        *self.world().resource::<State<AppState>>().get()
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.world().resource::<R>()
+    }
+
+    /// Runs `frames` frames.
+    pub fn run_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.app.update();
+        }
+        self
+    }
+
+    /// Runs frames until `done` holds, for at most `max_frames`. Returns whether it held.
+    pub fn run_until(&mut self, max_frames: u32, done: impl Fn(&Self) -> bool) -> bool {
+        for _ in 0..max_frames {
+            if done(self) {
+                return true;
+            }
+            self.app.update();
+        }
+        done(self)
+    }
+
+    /// Sends `action` and runs a frame.
+    pub fn action(&mut self, action: GameAction) -> &mut Self {
+        self.world_mut().write_message(action);
+        self.run_frames(1)
+    }
+
+    /// Submits a console line and runs a frame.
+    pub fn command(&mut self, line: impl Into<String>) -> &mut Self {
+        self.world_mut().write_message(ConsoleSubmit(line.into()));
+        self.run_frames(1)
+    }
+
+    pub fn run_script(&mut self, steps: &[SimStep]) -> &mut Self {
+        for step in steps {
+            match step {
+                SimStep::Action(action) => self.action(*action),
+                SimStep::Command(line) => self.command(line.clone()),
+                SimStep::Wait(frames) => self.run_frames(*frames),
+            };
+        }
+        self
+    }
+}
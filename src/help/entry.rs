@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Section a help entry is listed under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub enum HelpCategory {
+    Stat,
+    Quality,
+    StatusEffect,
+    Mechanic,
+}
+
+impl HelpCategory {
+    /// Localization key for the category label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            HelpCategory::Stat => "help.category.stat",
+            HelpCategory::Quality => "help.category.quality",
+            HelpCategory::StatusEffect => "help.category.status_effect",
+            HelpCategory::Mechanic => "help.category.mechanic",
+        }
+    }
+}
+
+/// One glossary entry, loaded from `assets/help/*.help.ron`.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Deserialize)]
+pub struct HelpEntry {
+    pub title: String,
+    pub category: HelpCategory,
+    pub body: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl HelpEntry {
+    fn title_matches(&self, query: &str) -> bool {
+        self.title.to_lowercase().contains(query)
+    }
+
+    fn text_matches(&self, query: &str) -> bool {
+        self.keywords.iter().any(|k| k.to_lowercase().contains(query))
+            || self.body.to_lowercase().contains(query)
+    }
+}
+
+/// Filters `entries` by a case-insensitive query. Title matches sort ahead of keyword and
+/// body matches; ties are ordered by category, then title.
+pub fn search<'a>(entries: &'a [HelpEntry], query: &str) -> Vec<&'a HelpEntry> {
+    let query = query.trim().to_lowercase();
+
+    let mut results: Vec<(bool, &HelpEntry)> = entries
+        .iter()
+        .filter_map(|entry| {
+            if query.is_empty() || entry.title_matches(&query) {
+                Some((true, entry))
+            } else if entry.text_matches(&query) {
+                Some((false, entry))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    results.sort_by(|(a_title, a), (b_title, b)| {
+        b_title
+            .cmp(a_title)
+            .then(a.category.cmp(&b.category))
+            .then_with(|| a.title.cmp(&b.title))
+    });
+
+    results.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(title: &str, category: HelpCategory, body: &str, keywords: &[&str]) -> HelpEntry {
+        HelpEntry {
+            title: title.to_string(),
+            category,
+            body: body.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    fn titles(results: &[&HelpEntry]) -> Vec<String> {
+        results.iter().map(|e| e.title.clone()).collect()
+    }
+
+    #[test]
+    fn empty_query_lists_everything_by_category() {
+        let entries = vec![
+            entry("Mining", HelpCategory::Mechanic, "", &[]),
+            entry("Attack", HelpCategory::Stat, "", &[]),
+            entry("Defense", HelpCategory::Stat, "", &[]),
+        ];
+
+        assert_eq!(titles(&search(&entries, "  ")), ["Attack", "Defense", "Mining"]);
+    }
+
+    #[test]
+    fn search_is_case_insensitive_over_keywords_and_body() {
+        let entries = vec![
+            entry("Gold Find", HelpCategory::Stat, "More gold from kills.", &["GF"]),
+            entry("Banking", HelpCategory::Mechanic, "Store GOLD with the merchant.", &[]),
+            entry("Defense", HelpCategory::Stat, "Reduces damage.", &[]),
+        ];
+
+        assert_eq!(titles(&search(&entries, "gf")), ["Gold Find"]);
+        assert_eq!(titles(&search(&entries, "merchant")), ["Banking"]);
+    }
+
+    #[test]
+    fn title_matches_rank_first() {
+        let entries = vec![
+            entry("Banking", HelpCategory::Mechanic, "Keeps gold safe.", &[]),
+            entry("Gold Find", HelpCategory::Stat, "", &[]),
+        ];
+
+        assert_eq!(titles(&search(&entries, "gold")), ["Gold Find", "Banking"]);
+    }
+}
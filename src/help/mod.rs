@@ -0,0 +1,5 @@
+mod entry;
+mod plugin;
+
+pub use entry::{search, HelpCategory, HelpEntry};
+pub use plugin::{HelpLibrary, HelpPlugin, HELP_DIR};
@@ -0,0 +1,49 @@
+use bevy::asset::LoadedFolder;
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use super::entry::HelpEntry;
+
+/// Directory under `assets/` that help entries are loaded from.
+pub const HELP_DIR: &str = "help";
+
+/// Handle to the loaded help folder. Entries are read on demand so edits hot-reload.
+#[derive(Resource)]
+pub struct HelpLibrary {
+    folder: Handle<LoadedFolder>,
+}
+
+impl HelpLibrary {
+    pub fn entries(
+        &self,
+        folders: &Assets<LoadedFolder>,
+        assets: &Assets<HelpEntry>,
+    ) -> Vec<HelpEntry> {
+        let Some(folder) = folders.get(&self.folder) else {
+            return Vec::new();
+        };
+
+        folder
+            .handles
+            .iter()
+            .filter_map(|h| h.id().try_typed::<HelpEntry>().ok())
+            .filter_map(|id| assets.get(id))
+            .cloned()
+            .collect()
+    }
+}
+
+pub struct HelpPlugin;
+
+impl Plugin for HelpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<HelpEntry>::new(&["help.ron"]))
+            .add_systems(Startup, load_help_folder);
+    }
+}
+
+fn load_help_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(HelpLibrary {
+        folder: asset_server.load_folder(HELP_DIR),
+    });
+}
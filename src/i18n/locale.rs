@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// English strings are compiled in so every lookup has a fallback.
+const ENGLISH_TABLE: &str = include_str!("../../assets/i18n/en.locale.ron");
+
+/// The language UI text is shown in, chosen in [`GameSettings`](crate::settings::GameSettings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    pub fn all() -> &'static [Locale] {
+        &[Locale::English, Locale::Spanish]
+    }
+
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let index = all.iter().position(|l| *l == self).unwrap_or(0);
+        all[(index + 1) % all.len()]
+    }
+
+    /// Localization key for the language's name, written in that language.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Locale::English => "language.en",
+            Locale::Spanish => "language.es",
+        }
+    }
+
+    /// Asset path of the locale's string table.
+    pub fn table_path(self) -> String {
+        format!("i18n/{}.locale.ron", self.code())
+    }
+}
+
+/// A locale's key to string table, loaded through the asset server.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct LocaleTable(pub HashMap<String, String>);
+
+/// Key to string lookup table for the active [`Locale`].
+///
+/// Missing keys fall back to English, then to the key itself.
+#[derive(Resource, Debug, Clone)]
+pub struct Localization {
+    strings: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            strings: HashMap::new(),
+            fallback: parse_table(ENGLISH_TABLE),
+        }
+    }
+}
+
+impl Localization {
+    /// Builds a table from the RON source for a locale. English needs no source.
+    pub fn from_ron(source: &str) -> Self {
+        Self {
+            strings: parse_table(source),
+            ..default()
+        }
+    }
+
+    pub fn from_table(table: &LocaleTable) -> Self {
+        Self {
+            strings: table.0.clone(),
+            ..default()
+        }
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Looks up `key` and replaces each `{name}` placeholder with its argument.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        text
+    }
+
+    /// Display name for an item, falling back to the name from its spec.
+    pub fn item_name<'a>(&'a self, id: ItemId, default: &'a str) -> &'a str {
+        self.lookup_optional(&format!("item.{id:?}")).unwrap_or(default)
+    }
+
+    /// Display name for a mob, falling back to the name from its spec.
+    pub fn mob_name<'a>(&'a self, id: MobId, default: &'a str) -> &'a str {
+        self.lookup_optional(&format!("mob.{id:?}")).unwrap_or(default)
+    }
+
+    fn lookup_optional(&self, key: &str) -> Option<&str> {
+        self.strings
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map(String::as_str)
+    }
+}
+
+fn parse_table(source: &str) -> HashMap<String, String> {
+    ron::from_str(source).unwrap_or_else(|e| {
+        warn!("Failed to parse localization table: {}", e);
+        HashMap::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_table_parses() {
+        let loc = Localization::default();
+        assert_eq!(loc.get("skills.title"), "Skills");
+    }
+
+    #[test]
+    fn missing_key_returns_key() {
+        let loc = Localization::default();
+        assert_eq!(loc.get("does.not.exist"), "does.not.exist");
+    }
+
+    #[test]
+    fn locale_table_falls_back_to_english() {
+        let loc = Localization::from_ron(r#"{ "skills.title": "Habilidades" }"#);
+        assert_eq!(loc.get("skills.title"), "Habilidades");
+        assert_eq!(loc.get("mods.title"), "Mods");
+    }
+
+    #[test]
+    fn format_replaces_placeholders() {
+        let loc = Localization::default();
+        let text = loc.format("toast.level_up", &[("level", &5)]);
+        assert_eq!(text, "Level Up! You are now level 5");
+    }
+
+    #[test]
+    fn item_name_falls_back_to_spec_name() {
+        let loc = Localization::from_ron(r#"{ "item.Sword": "Espada" }"#);
+        assert_eq!(loc.item_name(ItemId::Sword, "Sword"), "Espada");
+        assert_eq!(loc.item_name(ItemId::Dagger, "Dagger"), "Dagger");
+    }
+
+    #[test]
+    fn every_locale_table_parses() {
+        for locale in Locale::all() {
+            let path = format!("assets/{}", locale.table_path());
+            let source = std::fs::read_to_string(&path).expect("locale table exists");
+            let table: HashMap<String, String> =
+                ron::from_str(&source).expect("locale table parses");
+            assert!(!table.is_empty(), "{path} is empty");
+            assert!(table.contains_key(locale.label_key()), "{path} names no language");
+        }
+    }
+}
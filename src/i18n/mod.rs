@@ -0,0 +1,5 @@
+mod locale;
+mod plugin;
+
+pub use locale::{Locale, LocaleTable, Localization};
+pub use plugin::I18nPlugin;
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use crate::settings::GameSettings;
+use crate::states::AppState;
+
+use super::locale::{Locale, LocaleTable, Localization};
+
+pub struct I18nPlugin;
+
+impl Plugin for I18nPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<LocaleTable>::new(&["locale.ron"]))
+            .init_resource::<Localization>()
+            .add_systems(
+                PreUpdate,
+                (
+                    request_locale_table.run_if(resource_changed::<GameSettings>),
+                    apply_locale_table.run_if(resource_exists::<PendingLocale>),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                refresh_screen.run_if(
+                    resource_changed::<Localization>.and(not(resource_added::<Localization>)),
+                ),
+            );
+    }
+}
+
+/// A locale table that is still loading.
+#[derive(Resource)]
+struct PendingLocale {
+    locale: Locale,
+    handle: Handle<LocaleTable>,
+}
+
+/// Starts loading the table for the chosen locale whenever the setting changes.
+fn request_locale_table(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+    mut requested: Local<Option<Locale>>,
+) {
+    if requested.replace(settings.locale) == Some(settings.locale) {
+        return;
+    }
+
+    commands.remove_resource::<PendingLocale>();
+    if settings.locale == Locale::English {
+        commands.insert_resource(Localization::default());
+        return;
+    }
+    commands.insert_resource(PendingLocale {
+        locale: settings.locale,
+        handle: asset_server.load(settings.locale.table_path()),
+    });
+}
+
+fn apply_locale_table(
+    mut commands: Commands,
+    pending: Res<PendingLocale>,
+    tables: Res<Assets<LocaleTable>>,
+    asset_server: Res<AssetServer>,
+) {
+    if let Some(table) = tables.get(&pending.handle) {
+        info!("Loaded {} strings", pending.locale.code());
+        commands.insert_resource(Localization::from_table(table));
+    } else if asset_server.load_state(&pending.handle).is_failed() {
+        warn!("Missing locale table {}", pending.locale.table_path());
+        commands.insert_resource(Localization::default());
+    } else {
+        return;
+    }
+    commands.remove_resource::<PendingLocale>();
+}
+
+/// Rebuilds the screen on display so its text is drawn in the new language. The dungeon is
+/// left alone, since entering it again would generate a new floor; its modals pick the
+/// language up the next time they open.
+fn refresh_screen(state: Res<State<AppState>>, mut next_state: ResMut<NextState<AppState>>) {
+    match state.get() {
+        AppState::Loading | AppState::Dungeon => {}
+        screen => next_state.set(*screen),
+    }
+}
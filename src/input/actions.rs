@@ -1,7 +1,8 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Navigation directions for menu/list traversal.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Message)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Message, Serialize, Deserialize)]
 pub enum NavigationDirection {
     Up,
     Down,
@@ -11,7 +12,7 @@ pub enum NavigationDirection {
 
 /// Game actions that can be triggered by input.
 /// These map to the original keybinds from the terminal UI.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Message)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Message, Serialize, Deserialize)]
 pub enum GameAction {
     /// Navigation (Arrow keys)
     Navigate(NavigationDirection),
@@ -51,6 +52,163 @@ pub enum GameAction {
 
     /// Open skills modal (k)
     OpenSkills,
+
+    /// Deposit carried gold at the merchant (g)
+    DepositGold,
+
+    /// Withdraw banked gold at the merchant (Shift+G)
+    WithdrawGold,
+
+    /// Open the loaded mods list from the main menu (m)
+    OpenMods,
+
+    /// Open the loot filter settings from the main menu (l)
+    OpenLootFilter,
+
+    /// Open the character roster from the main menu (a)
+    OpenRoster,
+
+    /// Erase the selected roster save slot, pressed twice to confirm (Delete)
+    DeleteSaveSlot,
+
+    /// Step the combat log through its verbosity levels (Shift+L)
+    CycleCombatLog,
+
+    /// Step adaptive difficulty through its bands, or turn it off (Shift+M)
+    CycleAdaptiveDifficulty,
+
+    /// Pick the next unlocked dungeon tier while in town (Shift+T)
+    CycleDungeonTier,
+
+    /// Dismiss the current tutorial tip (x)
+    DismissTip,
+
+    /// Stop showing tutorial tips (Shift+X)
+    DisableTips,
+
+    /// Open the help and glossary modal (F1)
+    OpenHelp,
+
+    /// Undo the last storage, equipment, or sale action (z)
+    Undo,
+
+    /// Open the world map to travel between towns (t)
+    OpenWorldMap,
+
+    /// Open the guild hall screen (h)
+    OpenGuild,
+
+    /// Open the running world event's token shop (e)
+    OpenEventShop,
+
+    /// Open the endless mode leaderboard (Shift+E)
+    OpenLeaderboard,
+
+    /// Open the auction house (u)
+    OpenAuctionHouse,
+
+    /// Open the blacksmith's crafting contracts (n)
+    OpenContracts,
+
+    /// Open the pre-run checklist (Shift+P)
+    OpenRunPlanner,
+
+    /// Open item storage from the merchant (v)
+    OpenStorage,
+
+    /// Sell every unlocked item below the junk threshold at the merchant (j)
+    SellJunk,
+
+    /// Buy a mystery item from the merchant (Shift+B)
+    Gamble,
+
+    /// Sort the backpack, cycling through sort modes (r)
+    SortInventory,
+
+    /// Cycle the backpack's category filter (c)
+    FilterInventory,
+
+    /// Open the split dialog for the selected backpack stack (q)
+    SplitStack,
+
+    /// Star or unstar the selected backpack item (y)
+    ToggleFavorite,
+
+    /// Take the selected anvil recipe's, or the forge ore's, missing ingredients out of
+    /// storage (f)
+    FetchIngredients,
+
+    /// Spend a Magic Rock on the selected anvil bag item's quality (Shift+Q)
+    UpgradeQuality,
+
+    /// Hire the forge apprentice or change what they smelt (o)
+    CycleApprentice,
+
+    /// Open the action menu for the selected item (Shift+Enter)
+    OpenItemMenu,
+
+    /// Throw a capture orb at the closest weakened mob (Shift+C)
+    ThrowCaptureOrb,
+
+    /// Throw a fire flask in the facing direction (Shift+F)
+    ThrowFlask,
+
+    /// Cast the tome's active spell page (s)
+    Cast,
+
+    /// Ready the tome's next spell page (Shift+S)
+    NextSpellPage,
+
+    /// Open the spellcrafting screen to write new tome pages (w)
+    OpenSpellcraft,
+
+    /// Open the index of magic words found so far (Shift+W)
+    OpenWordIndex,
+
+    /// Open the mob ranch while in town (Shift+R)
+    OpenRanch,
+
+    /// Open the trophy room while in town (Shift+H)
+    OpenTrophyRoom,
+
+    /// Open the town workers screen while in town (Shift+O)
+    OpenWorkers,
+
+    /// Open the town building upgrades screen while in town (Shift+U)
+    OpenTownUpgrades,
+
+    /// Equip the loadout with this index (1-4)
+    SwitchLoadout(usize),
+
+    /// Save the equipped gear as the loadout with this index (Shift+1-4)
+    SaveLoadout(usize),
+
+    /// Turn menu sounds on or off (F2)
+    ToggleUiSounds,
+
+    /// Turn gamepad rumble on or off (Shift+F2)
+    ToggleRumble,
+
+    /// Turn floating damage numbers on or off (F4)
+    ToggleDamageNumbers,
+
+    /// Turn the screen reader text log on or off (Shift+F4)
+    ToggleScreenReader,
+
+    /// Step UI text through its sizes (F7)
+    CycleTextScale,
+
+    /// Step through the color-blind palettes (Shift+F7)
+    CyclePalette,
+
+    /// Switch to the next UI language (F8, main menu)
+    CycleLanguage,
+
+    /// Save a screenshot of the current frame (F12)
+    Screenshot,
+
+    /// Save a screenshot with the UI hidden (Shift+F12)
+    CleanScreenshot,
 }
 
 /// Tracks the currently-held navigation direction (if any).
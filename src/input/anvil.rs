@@ -1,10 +1,14 @@
 use bevy::prelude::*;
 
-use crate::crafting_station::{AnvilCraftingState, TryStartAnvilCrafting};
+use crate::crafting_station::{AnvilCraftingState, ItemEvolved, TryStartAnvilCrafting};
+use crate::faction::Reputation;
+use crate::game::{StorageWithdrawForRecipeEvent, UpgradeQualityEvent};
 use crate::input::GameAction;
 use crate::inventory::{Inventory, ManagesItems};
+use crate::item::evolution::evolve_item;
 use crate::item::recipe::RecipeId;
 use crate::item::ItemRegistry;
+use crate::location::{CurrentTown, TownState};
 use crate::player::PlayerMarker;
 use crate::ui::focus::{FocusPanel, FocusState};
 use crate::ui::modal_registry::ModalCommands;
@@ -48,6 +52,9 @@ pub fn craft_anvil_recipe(
     focus_state: Option<Res<FocusState>>,
     active_anvil: Option<Res<ActiveAnvilEntity>>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    reputation: Res<Reputation>,
+    town_state: Res<TownState>,
+    town: Res<CurrentTown>,
     mut anvil_state_query: Query<&mut AnvilCraftingState>,
     recipe_grids: Query<&ItemGridSelection, (With<AnvilRecipeGrid>, Without<AnvilPlayerGrid>)>,
     mut player_grids: Query<
@@ -86,6 +93,12 @@ pub fn craft_anvil_recipe(
             continue;
         };
 
+        if !reputation.recipe_unlocked(*recipe_id)
+            || !town_state.recipe_unlocked(town.0, *recipe_id)
+        {
+            continue;
+        }
+
         let spec = recipe_id.spec();
 
         let can_craft = spec
@@ -115,16 +128,117 @@ pub fn craft_anvil_recipe(
     }
 }
 
+/// Evolves the highlighted bag item when it is fully upgraded and the materials are at
+/// hand, then closes the anvil so the reveal can take the screen.
+pub fn evolve_anvil_item(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    focus_state: Option<Res<FocusState>>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    player_grids: Query<&ItemGridSelection, With<AnvilPlayerGrid>>,
+    registry: Res<ItemRegistry>,
+    mut evolved_writer: MessageWriter<ItemEvolved>,
+) {
+    let Some(focus_state) = focus_state else { return };
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        if *action != GameAction::Select || !focus_state.is_focused(FocusPanel::AnvilInventory) {
+            continue;
+        }
+        let Ok(selection) = player_grids.single() else {
+            continue;
+        };
+        let items = inventory.get_inventory_items();
+        let Some(inv_item) = items.get(selection.selected_index) else {
+            continue;
+        };
+        let from = inv_item.item.item_id;
+        let uuid = inv_item.uuid();
+
+        // Items that can't evolve yet say why in the detail pane.
+        let Ok(item) = evolve_item(&mut inventory, uuid, &registry) else {
+            continue;
+        };
+        evolved_writer.write(ItemEvolved { from, item });
+        commands.close_modal::<AnvilModal>();
+    }
+}
+
+/// Spends a Magic Rock on the highlighted bag item's quality. The blacksmith rolls the
+/// outcome; the detail pane shows the odds beforehand.
+pub fn upgrade_anvil_item_quality(
+    mut action_reader: MessageReader<GameAction>,
+    focus_state: Option<Res<FocusState>>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    player_grids: Query<&ItemGridSelection, With<AnvilPlayerGrid>>,
+    mut upgrade_writer: MessageWriter<UpgradeQualityEvent>,
+) {
+    let Some(focus_state) = focus_state else { return };
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        if *action != GameAction::UpgradeQuality
+            || !focus_state.is_focused(FocusPanel::AnvilInventory)
+        {
+            continue;
+        }
+        let Ok(selection) = player_grids.single() else {
+            continue;
+        };
+        let items = inventory.get_inventory_items();
+        let Some(inv_item) = items.get(selection.selected_index) else {
+            continue;
+        };
+        if inv_item.item.item_type.is_equipment() {
+            upgrade_writer.write(UpgradeQualityEvent {
+                item_uuid: inv_item.uuid(),
+            });
+        }
+    }
+}
+
+/// Pulls the highlighted recipe's missing ingredients out of storage.
+pub fn fetch_anvil_ingredients(
+    mut action_reader: MessageReader<GameAction>,
+    focus_state: Option<Res<FocusState>>,
+    recipe_grids: Query<&ItemGridSelection, With<AnvilRecipeGrid>>,
+    mut withdraw_events: MessageWriter<StorageWithdrawForRecipeEvent>,
+) {
+    let Some(focus_state) = focus_state else { return };
+
+    for action in action_reader.read() {
+        if *action != GameAction::FetchIngredients
+            || !focus_state.is_focused(FocusPanel::RecipeGrid)
+        {
+            continue;
+        }
+        let Ok(selection) = recipe_grids.single() else {
+            continue;
+        };
+        if let Some(&recipe) = RecipeId::all_forging_recipes().get(selection.selected_index) {
+            withdraw_events.write(StorageWithdrawForRecipeEvent { recipe });
+        }
+    }
+}
+
 pub fn sync_anvil_recipes(
     player: Query<&Inventory, (With<PlayerMarker>, Changed<Inventory>)>,
     mut recipe_grids: Query<&mut ItemGrid, With<AnvilRecipeGrid>>,
     registry: Res<ItemRegistry>,
+    reputation: Res<Reputation>,
+    town_state: Res<TownState>,
+    town: Res<CurrentTown>,
 ) {
     let Ok(inventory) = player.single() else {
         return;
     };
 
     if let Ok(mut grid) = recipe_grids.single_mut() {
-        grid.items = get_recipe_entries(inventory, &registry);
+        grid.items = get_recipe_entries(inventory, &registry, &reputation, &town_state, town.0);
     }
 }
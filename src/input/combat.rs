@@ -1,37 +1,145 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
 
 use crate::combat::hitbox::{AttackHitboxBundle, Attacking};
+use crate::combat::{Projectile, ProjectileBundle};
+use crate::dungeon::constants::DEFAULT_TILE_SIZE;
+use crate::dungeon::TileWorldSize;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
 use crate::input::GameAction;
+use crate::inventory::{EquipmentSlot, FindsItems, Inventory, ManagesEquipment, ManagesItems};
+use crate::item::enums::EquipmentType;
+use crate::item::{ItemId, ItemType};
+use crate::player::PlayerMarker;
+use crate::stats::StatType;
+#[cfg(feature = "gui")]
 use crate::ui::player_sprite::{PLAYER_ATTACK_TAG, PLAYER_IDLE_TAG};
+use crate::ui::screens::{ActiveModal, FloorRoot};
 use crate::ui::{DungeonPlayer, FacingDirection, PlayerSpriteSheet};
 
+const ARROW_SPEED_TILES: f32 = 8.0;
+
+const FLASK_SPEED_TILES: f32 = 5.0;
+
+/// Swings the player's weapon, or looses an arrow in the facing direction if a bow is
+/// equipped.
 pub fn trigger_player_attack(
     mut commands: Commands,
     mut actions: MessageReader<GameAction>,
-    mut player: Query<
-        (Entity, &Transform, &FacingDirection, &mut AseAnimation),
+    player: Query<
+        (Entity, &Transform, &FacingDirection),
         (With<DungeonPlayer>, Without<Attacking>),
     >,
+    inventory: Query<&Inventory, With<PlayerMarker>>,
     sprite_sheet: Res<PlayerSpriteSheet>,
+    tile_size: Option<Res<TileWorldSize>>,
+    floor_root: Query<Entity, With<FloorRoot>>,
 ) {
     if !actions.read().any(|a| matches!(a, GameAction::Attack)) {
         return;
     }
 
-    let Ok((entity, transform, facing, mut ase_anim)) = player.single_mut() else {
+    let Ok((entity, transform, facing)) = player.single() else {
         return;
     };
 
-    commands.entity(entity).insert(Attacking);
-    commands.spawn(AttackHitboxBundle::new(
-        entity,
-        transform.translation.truncate(),
-        *facing,
-        sprite_sheet.frame_size.as_vec2(),
-    ));
-
-    ase_anim.animation = Animation::tag(PLAYER_ATTACK_TAG)
-        .with_repeat(AnimationRepeat::Count(1))
-        .with_then(PLAYER_IDLE_TAG, AnimationRepeat::Loop);
+    let position = transform.translation.truncate();
+    if inventory.single().is_ok_and(has_bow_equipped) {
+        let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+        let velocity = facing.to_offset(ARROW_SPEED_TILES * tile_size);
+        let mut arrow = commands.spawn(ProjectileBundle::new(
+            Projectile::Arrow,
+            position,
+            velocity,
+        ));
+        if let Ok(root) = floor_root.single() {
+            arrow.insert(ChildOf(root));
+        }
+    } else {
+        commands.entity(entity).insert(Attacking);
+        commands.spawn(AttackHitboxBundle::new(
+            entity,
+            position,
+            *facing,
+            sprite_sheet.frame_size.as_vec2(),
+        ));
+    }
+
+    #[cfg(feature = "gui")]
+    commands
+        .entity(entity)
+        .entry::<AseAnimation>()
+        .and_modify(|mut ase_anim| {
+            ase_anim.animation = Animation::tag(PLAYER_ATTACK_TAG)
+                .with_repeat(AnimationRepeat::Count(1))
+                .with_then(PLAYER_IDLE_TAG, AnimationRepeat::Loop);
+        });
+}
+
+/// Where the thrower stands and faces, the flasks they carry, and the floor a thrown flask
+/// flies across.
+#[derive(SystemParam)]
+pub struct FlaskThrow<'w, 's> {
+    dungeon_player:
+        Query<'w, 's, (&'static Transform, &'static FacingDirection), With<DungeonPlayer>>,
+    player: Query<'w, 's, &'static mut Inventory, With<PlayerMarker>>,
+    tile_size: Option<Res<'w, TileWorldSize>>,
+    floor_root: Query<'w, 's, Entity, With<FloorRoot>>,
+}
+
+/// Throws a fire flask in the facing direction. It bursts on the first mob or wall it hits.
+pub fn throw_flask(
+    mut commands: Commands,
+    mut actions: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    throw: FlaskThrow,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let FlaskThrow {
+        dungeon_player,
+        mut player,
+        tile_size,
+        floor_root,
+    } = throw;
+    for action in actions.read() {
+        if *action != GameAction::ThrowFlask || active_modal.modal.is_some() {
+            continue;
+        }
+        let (Ok((transform, facing)), Ok(mut inventory)) =
+            (dungeon_player.single(), player.single_mut())
+        else {
+            return;
+        };
+
+        let Some(power) = inventory
+            .find_item_by_id(ItemId::FireFlask)
+            .map(|flask| flask.item.stats.value(StatType::Attack))
+        else {
+            toast_writer.write(ShowToast::new(loc.get("combat.no_flasks")));
+            continue;
+        };
+        inventory.decrease_item_quantity(ItemId::FireFlask, 1);
+
+        let tile_size = tile_size.as_ref().map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+        let mut flask = commands.spawn(ProjectileBundle::new(
+            Projectile::flask(power),
+            transform.translation.truncate(),
+            facing.to_offset(FLASK_SPEED_TILES * tile_size),
+        ));
+        if let Ok(root) = floor_root.single() {
+            flask.insert(ChildOf(root));
+        }
+    }
+}
+
+fn has_bow_equipped(inventory: &Inventory) -> bool {
+    inventory
+        .get_equipped_item(EquipmentSlot::Weapon)
+        .is_some_and(|weapon| {
+            weapon.item.item_type == ItemType::Equipment(EquipmentType::Bow)
+        })
 }
@@ -1,10 +1,11 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::input::{GameAction, NavigationDirection};
 use crate::ui::screens::modal::{ActiveModal, ModalType};
 use crate::ui::screens::monster_compendium::state::{
-    CompendiumDetailView, CompendiumListState, CompendiumMonsters, CompendiumViewState,
-    DropsListState,
+    CompendiumDetailView, CompendiumItems, CompendiumListState, CompendiumMonsters,
+    CompendiumSection, CompendiumViewState, DropsListState, ItemListState, ItemSourcesState,
 };
 use crate::ui::focus::SelectionState;
 use crate::ui::{FocusPanel, FocusState};
@@ -13,6 +14,7 @@ pub fn switch_compendium_panel(
     mut action_reader: MessageReader<GameAction>,
     active_modal: Res<ActiveModal>,
     mut focus_state: Option<ResMut<FocusState>>,
+    mut view_state: ResMut<CompendiumViewState>,
 ) {
     if active_modal.modal != Some(ModalType::MonsterCompendium) {
         return;
@@ -21,33 +23,66 @@ pub fn switch_compendium_panel(
     let Some(ref mut focus_state) = focus_state else { return };
 
     for action in action_reader.read() {
-        if *action == GameAction::NextTab {
-            focus_state.toggle_between(
-                FocusPanel::CompendiumMonsterList,
-                FocusPanel::CompendiumDropsList,
-            );
+        match (action, view_state.section) {
+            (GameAction::NextTab, CompendiumSection::Monsters) => {
+                focus_state.toggle_between(
+                    FocusPanel::CompendiumMonsterList,
+                    FocusPanel::CompendiumDropsList,
+                );
+            }
+            (GameAction::NextTab, CompendiumSection::Items) => {
+                focus_state.toggle_between(
+                    FocusPanel::CompendiumItemList,
+                    FocusPanel::CompendiumItemSources,
+                );
+            }
+            (GameAction::PrevTab, CompendiumSection::Monsters) => {
+                view_state.section = CompendiumSection::Items;
+                focus_state.set_focus(FocusPanel::CompendiumItemList);
+            }
+            (GameAction::PrevTab, CompendiumSection::Items) => {
+                view_state.section = CompendiumSection::Monsters;
+                focus_state.set_focus(FocusPanel::CompendiumMonsterList);
+            }
+            _ => {}
         }
     }
 }
 
+/// The compendium's monster and item entries, once the modal has built them.
+#[derive(SystemParam)]
+pub struct CompendiumEntries<'w> {
+    monsters: Option<Res<'w, CompendiumMonsters>>,
+    items: Option<Res<'w, CompendiumItems>>,
+}
+
+/// The selected monster, item, and item source.
+#[derive(SystemParam)]
+pub struct CompendiumCursors<'w> {
+    monsters: ResMut<'w, CompendiumListState>,
+    items: ResMut<'w, ItemListState>,
+    sources: ResMut<'w, ItemSourcesState>,
+}
+
 pub fn navigate_compendium(
     mut action_reader: MessageReader<GameAction>,
     active_modal: Res<ActiveModal>,
     focus_state: Option<Res<FocusState>>,
-    mut list_state: ResMut<CompendiumListState>,
+    mut cursors: CompendiumCursors,
     mut drops_state: Option<ResMut<DropsListState>>,
     mut view_state: ResMut<CompendiumViewState>,
-    monsters: Option<Res<CompendiumMonsters>>,
+    entries: CompendiumEntries,
 ) {
     if active_modal.modal != Some(ModalType::MonsterCompendium) {
         return;
     }
 
-    let Some(monsters) = monsters else { return };
+    let Some(monsters) = entries.monsters else { return };
+    let Some(items) = entries.items else { return };
     let Some(focus_state) = focus_state else { return };
 
-    if list_state.count != monsters.len() {
-        list_state.count = monsters.len();
+    if cursors.monsters.count != monsters.len() {
+        cursors.monsters.count = monsters.len();
     }
 
     for action in action_reader.read() {
@@ -56,8 +91,8 @@ pub fn navigate_compendium(
                 NavigationDirection::Up | NavigationDirection::Down => {
                     if focus_state.is_focused(FocusPanel::CompendiumMonsterList) {
                         match dir {
-                            NavigationDirection::Up => list_state.up(),
-                            NavigationDirection::Down => list_state.down(),
+                            NavigationDirection::Up => cursors.monsters.up(),
+                            NavigationDirection::Down => cursors.monsters.down(),
                             _ => {}
                         }
                     } else if focus_state.is_focused(FocusPanel::CompendiumDropsList) {
@@ -68,15 +103,90 @@ pub fn navigate_compendium(
                                 _ => {}
                             }
                         }
+                    } else if focus_state.is_focused(FocusPanel::CompendiumItemList) {
+                        match dir {
+                            NavigationDirection::Up => cursors.items.up(),
+                            NavigationDirection::Down => cursors.items.down(),
+                            _ => {}
+                        }
+                        cursors.sources.reset();
+                    } else if focus_state.is_focused(FocusPanel::CompendiumItemSources) {
+                        cursors.sources.count = items
+                            .get(cursors.items.selected)
+                            .filter(|entry| entry.is_discovered())
+                            .map_or(0, |entry| entry.dropped_by.len());
+                        match dir {
+                            NavigationDirection::Up => cursors.sources.up(),
+                            NavigationDirection::Down => cursors.sources.down(),
+                            _ => {}
+                        }
                     }
                 }
                 NavigationDirection::Left | NavigationDirection::Right => {
-                    view_state.view = match view_state.view {
-                        CompendiumDetailView::Stats => CompendiumDetailView::Drops,
-                        CompendiumDetailView::Drops => CompendiumDetailView::Stats,
-                    };
+                    if view_state.section == CompendiumSection::Monsters {
+                        view_state.view = match view_state.view {
+                            CompendiumDetailView::Stats => CompendiumDetailView::Drops,
+                            CompendiumDetailView::Drops => CompendiumDetailView::Stats,
+                        };
+                    }
                 }
             }
         }
     }
 }
+
+/// Follows cross-links with Select: a monster's drop opens that item's page, and an item's
+/// "dropped by" entry opens that monster's page.
+pub fn follow_compendium_link(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    focus_state: Option<ResMut<FocusState>>,
+    mut cursors: CompendiumCursors,
+    drops_state: Res<DropsListState>,
+    mut view_state: ResMut<CompendiumViewState>,
+    entries: CompendiumEntries,
+) {
+    if active_modal.modal != Some(ModalType::MonsterCompendium) {
+        return;
+    }
+
+    let (Some(mut focus_state), Some(monsters), Some(items)) =
+        (focus_state, entries.monsters, entries.items)
+    else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        if *action != GameAction::Select {
+            continue;
+        }
+
+        if focus_state.is_focused(FocusPanel::CompendiumDropsList) {
+            let Some(item_index) = monsters
+                .get(cursors.monsters.selected)
+                .and_then(|monster| monster.drops.get(drops_state.selected))
+                .and_then(|drop| items.index_of(drop.item_id()))
+            else {
+                continue;
+            };
+
+            cursors.items.selected = item_index;
+            cursors.sources.reset();
+            view_state.section = CompendiumSection::Items;
+            focus_state.set_focus(FocusPanel::CompendiumItemList);
+        } else if focus_state.is_focused(FocusPanel::CompendiumItemSources) {
+            let Some(mob_index) = items
+                .get(cursors.items.selected)
+                .and_then(|item| item.dropped_by.get(cursors.sources.selected))
+                .and_then(|source| monsters.index_of(source.mob_id))
+            else {
+                continue;
+            };
+
+            cursors.monsters.selected = mob_index;
+            view_state.section = CompendiumSection::Monsters;
+            view_state.view = CompendiumDetailView::Drops;
+            focus_state.set_focus(FocusPanel::CompendiumMonsterList);
+        }
+    }
+}
@@ -1,15 +1,19 @@
 use bevy::prelude::*;
 use tracing::instrument;
 
-use crate::crafting_station::ForgeCraftingState;
+use crate::crafting_station::{can_hire, Apprentice, ForgeCraftingState, APPRENTICE_LEVEL};
+use crate::game::{ShowToast, StorageWithdrawForRecipeEvent};
+use crate::i18n::Localization;
 use crate::input::{GameAction, NavigationDirection};
-use crate::inventory::{Inventory, ManagesItems};
-use crate::item::enums::MaterialType;
-use crate::item::{Item, ItemId, ItemRegistry, ItemType};
+use crate::inventory::{FindsItems, Inventory, ManagesItems};
+use crate::item::recipe::RecipeId;
+use crate::item::{Item, ItemId, ItemRegistry};
 use crate::player::PlayerMarker;
+use crate::skills::Skills;
 use crate::ui::focus::{FocusPanel, FocusState};
 use crate::ui::screens::forge_modal::{ActiveForgeEntity, ForgeModalState, ForgePlayerGrid, ForgeSlotIndex};
 use crate::ui::widgets::{ItemGrid, ItemGridEntry, ItemGridSelection};
+use crate::world_event::GameClock;
 
 pub fn navigate_forge_ui(
     mut action_reader: MessageReader<GameAction>,
@@ -81,6 +85,94 @@ pub fn transfer_forge_items(
     }
 }
 
+/// Pulls what smelting the loaded ore, or else the highlighted bag item, needs out of storage.
+pub fn fetch_forge_ingredients(
+    mut action_reader: MessageReader<GameAction>,
+    active_forge: Option<Res<ActiveForgeEntity>>,
+    forge_states: Query<&ForgeCraftingState>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    player_grids: Query<&ItemGridSelection, With<ForgePlayerGrid>>,
+    registry: Res<ItemRegistry>,
+    mut withdraw_events: MessageWriter<StorageWithdrawForRecipeEvent>,
+) {
+    let Some(active_forge) = active_forge else { return };
+    let Ok(inventory) = player.single() else { return };
+
+    for action in action_reader.read() {
+        if *action != GameAction::FetchIngredients {
+            continue;
+        }
+        let loaded = forge_states
+            .get(active_forge.0)
+            .ok()
+            .and_then(|state| state.ore_slot.map(|(id, _)| id));
+        let selected = player_grids.single().ok().and_then(|selection| {
+            let items = inventory.get_inventory_items();
+            items
+                .get(selection.selected_index)
+                .map(|inv_item| inv_item.item.item_id)
+        });
+        let recipe = loaded
+            .or(selected)
+            .and_then(|ore| smelting_recipe(ore, &registry));
+        if let Some(recipe) = recipe {
+            withdraw_events.write(StorageWithdrawForRecipeEvent { recipe });
+        }
+    }
+}
+
+/// The smelting recipe that makes what `ore` smelts into.
+fn smelting_recipe(ore: ItemId, registry: &ItemRegistry) -> Option<RecipeId> {
+    let output = registry.smelt_output(ore)?;
+    RecipeId::all_smelting_recipes()
+        .iter()
+        .copied()
+        .find(|recipe| recipe.spec().output == output)
+}
+
+/// Hires the apprentice, moves them to the next smelting recipe, or lets them go after the
+/// last one.
+pub fn cycle_apprentice(
+    mut action_reader: MessageReader<GameAction>,
+    mut apprentice: ResMut<Apprentice>,
+    skills: Res<Skills>,
+    clock: Res<GameClock>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleApprentice {
+            continue;
+        }
+        if !can_hire(&skills) {
+            toast_writer.write(ShowToast::new(
+                loc.format("apprentice.locked", &[("level", &APPRENTICE_LEVEL)]),
+            ));
+            continue;
+        }
+
+        let was_hired = apprentice.recipe.is_some();
+        let next = apprentice.next_recipe();
+        apprentice.assign(next, clock.day());
+
+        let message = match next {
+            None => loc.get("apprentice.dismissed").to_string(),
+            Some(recipe) => {
+                let output = recipe.spec().output;
+                let item = loc.item_name(output, &registry.get(output).name).to_string();
+                let key = if was_hired {
+                    "apprentice.assigned"
+                } else {
+                    "apprentice.hired"
+                };
+                loc.format(key, &[("item", &item)])
+            }
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
 #[instrument(level = "debug", skip_all, fields(
     selected_slot = ?modal_state.selected_slot,
     has_forge_state = forge_state_query.contains(entity),
@@ -131,28 +223,30 @@ fn process_forge_select(
         if let Some(inv_item) = inv_items.get(selected) {
             let item_id = inv_item.item.item_id;
             let quantity = inv_item.quantity;
+            // The whole selected stack moves, not whichever stack of the item comes first.
+            let uuid = inv_item.uuid();
 
             if is_coal(&inv_item.item) {
                 if forge_state.coal_slot.is_none() {
                     forge_state.coal_slot = Some((item_id, quantity));
-                    inventory.decrease_item_quantity(item_id, quantity);
+                    inventory.remove_item(uuid);
                     return true;
                 } else if forge_state.coal_slot.as_ref().map(|(id, _)| *id) == Some(item_id) {
                     if let Some((_, existing_qty)) = forge_state.coal_slot.as_mut() {
                         *existing_qty += quantity;
-                        inventory.decrease_item_quantity(item_id, quantity);
+                        inventory.remove_item(uuid);
                         return true;
                     }
                 }
-            } else if is_ore(&inv_item.item) {
+            } else if registry.smelt_output(item_id).is_some() {
                 if forge_state.ore_slot.is_none() {
                     forge_state.ore_slot = Some((item_id, quantity));
-                    inventory.decrease_item_quantity(item_id, quantity);
+                    inventory.remove_item(uuid);
                     return true;
                 } else if forge_state.ore_slot.as_ref().map(|(id, _)| *id) == Some(item_id) {
                     if let Some((_, existing_qty)) = forge_state.ore_slot.as_mut() {
                         *existing_qty += quantity;
-                        inventory.decrease_item_quantity(item_id, quantity);
+                        inventory.remove_item(uuid);
                         return true;
                     }
                 }
@@ -167,13 +261,6 @@ fn is_coal(item: &Item) -> bool {
     matches!(item.item_id, ItemId::Coal)
 }
 
-fn is_ore(item: &Item) -> bool {
-    matches!(
-        item.item_type,
-        ItemType::Material(MaterialType::Ore)
-    )
-}
-
 fn add_items_to_inventory(inventory: &mut Inventory, item_id: ItemId, quantity: u32, registry: &ItemRegistry) {
     for _ in 0..quantity {
         let item = registry.spawn(item_id);
@@ -1,26 +1,56 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::input::GameAction;
-use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment};
+use crate::game::{SortInventory, SplitStack, StorageDepositEvent, UndoHistory, UndoableAction};
+use crate::input::{GameAction, NavigationDirection};
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment, ManagesItems};
+use crate::location::PlayerLocation;
 use crate::player::PlayerMarker;
 use crate::ui::focus::{FocusPanel, FocusState};
-use crate::ui::screens::inventory_modal::render::get_backpack_items;
-use crate::ui::screens::inventory_modal::{BackpackGrid, EquipmentGrid};
+use crate::ui::screens::inventory_modal::render::backpack_indices;
+use crate::ui::screens::inventory_modal::{
+    BackpackGrid, EquipmentGrid, InventoryView, ItemAction, ItemContextMenu, ItemMenuTarget,
+    SplitStackDialog,
+};
 use crate::ui::widgets::{ItemGrid, ItemGridSelection};
 
+/// The split dialog and the item menu, which take over the inventory's keys while open.
+#[derive(SystemParam)]
+pub struct InventoryPopups<'w> {
+    dialog: Option<Res<'w, SplitStackDialog>>,
+    menu: Option<Res<'w, ItemContextMenu>>,
+}
+
+impl InventoryPopups<'_> {
+    pub fn is_open(&self) -> bool {
+        self.dialog.is_some() || self.menu.is_some()
+    }
+}
+
+/// The focused inventory panel and the selection in each of its grids.
+#[derive(SystemParam)]
+pub struct InventorySelection<'w, 's> {
+    focus: Option<Res<'w, FocusState>>,
+    equipment: Query<'w, 's, &'static ItemGridSelection, EquipmentGridFilter>,
+    backpack: Query<'w, 's, &'static ItemGridSelection, BackpackGridFilter>,
+}
+
+type EquipmentGridFilter = (With<EquipmentGrid>, Without<BackpackGrid>);
+type BackpackGridFilter = (With<BackpackGrid>, Without<EquipmentGrid>);
+
 pub fn navigate_inventory_grid(
     mut action_reader: MessageReader<GameAction>,
     focus_state: Option<Res<FocusState>>,
-    mut equipment_grids: Query<
-        (&ItemGrid, &mut ItemGridSelection),
-        (With<EquipmentGrid>, Without<BackpackGrid>),
-    >,
-    mut backpack_grids: Query<
-        (&ItemGrid, &mut ItemGridSelection),
-        (With<BackpackGrid>, Without<EquipmentGrid>),
-    >,
+    mut equipment_grids: Query<(&ItemGrid, &mut ItemGridSelection), EquipmentGridFilter>,
+    mut backpack_grids: Query<(&ItemGrid, &mut ItemGridSelection), BackpackGridFilter>,
+    popups: InventoryPopups,
 ) {
     let Some(focus_state) = focus_state else { return };
+    // The split dialog and the item menu take the arrow keys while they are open.
+    if popups.is_open() {
+        action_reader.clear();
+        return;
+    }
 
     for action in action_reader.read() {
         if let GameAction::Navigate(direction) = action {
@@ -39,18 +69,26 @@ pub fn navigate_inventory_grid(
 
 pub fn toggle_equipment(
     mut action_reader: MessageReader<GameAction>,
-    focus_state: Option<Res<FocusState>>,
     mut player: Query<&mut Inventory, With<PlayerMarker>>,
-    equipment_grids: Query<&ItemGridSelection, (With<EquipmentGrid>, Without<BackpackGrid>)>,
-    backpack_grids: Query<
-        (&ItemGrid, &ItemGridSelection),
-        (With<BackpackGrid>, Without<EquipmentGrid>),
-    >,
+    selection: InventorySelection,
+    mut history: ResMut<UndoHistory>,
+    view: Res<InventoryView>,
+    popups: InventoryPopups,
 ) {
+    let InventorySelection {
+        focus: focus_state,
+        equipment: equipment_grids,
+        backpack: backpack_grids,
+    } = selection;
     let Some(focus_state) = focus_state else { return };
     let Ok(mut inventory) = player.single_mut() else {
         return;
     };
+    // Enter confirms the split dialog or the item menu instead of equipping.
+    if popups.is_open() {
+        action_reader.clear();
+        return;
+    }
 
     for action in action_reader.read() {
         if *action != GameAction::Select {
@@ -71,21 +109,345 @@ pub fn toggle_equipment(
                 .collect();
 
             if let Some(&slot) = equipped_slots.get(selected) {
-                let _ = inventory.unequip_item(slot);
+                unequip_slot(&mut inventory, slot, &mut history);
             }
         } else {
-            let Ok((_, selection)) = backpack_grids.single() else {
+            let Ok(selection) = backpack_grids.single() else {
                 continue;
             };
             let selected = selection.selected_index;
-            let backpack_items = get_backpack_items(&inventory);
 
-            if let Some(inv_item) = backpack_items.get(selected) {
-                if let Some(slot) = inv_item.item.item_type.equipment_slot() {
-                    let uuid = inv_item.uuid();
-                    inventory.equip_from_inventory(uuid, slot);
+            if let Some(&index) = backpack_indices(&inventory, view.filter).get(selected) {
+                equip_backpack_item(&mut inventory, index, &mut history);
+            }
+        }
+    }
+}
+
+/// Unequips whatever is in `slot`, recording it for undo.
+fn unequip_slot(inventory: &mut Inventory, slot: EquipmentSlot, history: &mut UndoHistory) {
+    let item_uuid = inventory.get_equipped_item(slot).map(|item| item.uuid());
+    if let (Some(item_uuid), Ok(())) = (item_uuid, inventory.unequip_item(slot)) {
+        history.record(UndoableAction::Unequip { slot, item_uuid });
+    }
+}
+
+/// Equips the backpack stack at `index` if it is equipment, recording it for undo.
+fn equip_backpack_item(inventory: &mut Inventory, index: usize, history: &mut UndoHistory) {
+    let Some(inv_item) = inventory.get_inventory_items().get(index) else {
+        return;
+    };
+    let Some(slot) = inventory.slot_for(inv_item.item.item_type) else {
+        return;
+    };
+    let uuid = inv_item.uuid();
+    let previous = inventory.get_equipped_item(slot).map(|item| item.uuid());
+    inventory.equip_from_inventory(uuid, slot);
+    history.record(UndoableAction::Equip { slot, previous });
+}
+
+/// Sort, filter, and split actions for the backpack.
+pub fn handle_inventory_view_actions(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    mut view: ResMut<InventoryView>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    selection: InventorySelection,
+    mut sort_writer: MessageWriter<SortInventory>,
+    popups: InventoryPopups,
+) {
+    let InventorySelection {
+        focus: focus_state,
+        backpack: backpack_grids,
+        ..
+    } = selection;
+    // Reordering the backpack mid-split or mid-menu would point them at the wrong stack.
+    if popups.is_open() {
+        action_reader.clear();
+        return;
+    }
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::SortInventory => {
+                sort_writer.write(SortInventory { sort: view.sort });
+                view.sort = view.sort.next();
+            }
+            GameAction::FilterInventory => view.filter = view.filter.next(),
+            GameAction::SplitStack => {
+                if !focus_state
+                    .as_ref()
+                    .is_some_and(|focus| focus.is_focused(FocusPanel::BackpackGrid))
+                {
+                    continue;
+                }
+                let (Ok(inventory), Ok(selection)) = (player.single(), backpack_grids.single())
+                else {
+                    continue;
+                };
+                let Some(&index) =
+                    backpack_indices(inventory, view.filter).get(selection.selected_index)
+                else {
+                    continue;
+                };
+                let quantity = inventory.get_inventory_items()[index].quantity;
+                if let Some(dialog) = SplitStackDialog::new(index, quantity) {
+                    commands.insert_resource(dialog);
                 }
             }
+            _ => {}
+        }
+    }
+}
+
+/// Index into the backpack of the stack selected in the backpack grid, if it has focus.
+fn selected_backpack_index(
+    focus_state: Option<&FocusState>,
+    inventory: &Inventory,
+    view: &InventoryView,
+    selection: &ItemGridSelection,
+) -> Option<usize> {
+    if !focus_state.is_some_and(|focus| focus.is_focused(FocusPanel::BackpackGrid)) {
+        return None;
+    }
+    backpack_indices(inventory, view.filter)
+        .get(selection.selected_index)
+        .copied()
+}
+
+/// Stars or unstars the selected backpack item.
+pub fn toggle_favorite(
+    mut action_reader: MessageReader<GameAction>,
+    focus_state: Option<Res<FocusState>>,
+    view: Res<InventoryView>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    backpack_grids: Query<&ItemGridSelection, With<BackpackGrid>>,
+) {
+    let (Ok(mut inventory), Ok(selection)) = (player.single_mut(), backpack_grids.single())
+    else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        if *action != GameAction::ToggleFavorite {
+            continue;
+        }
+        if let Some(index) =
+            selected_backpack_index(focus_state.as_deref(), &inventory, &view, selection)
+        {
+            let item = &mut inventory.items[index].item;
+            item.is_favorite = !item.is_favorite;
+        }
+    }
+}
+
+/// Clears the "new" marker from the backpack stack the player has selected.
+pub fn mark_selected_seen(
+    focus_state: Option<Res<FocusState>>,
+    view: Res<InventoryView>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    backpack_grids: Query<&ItemGridSelection, With<BackpackGrid>>,
+) {
+    let (Ok(mut inventory), Ok(selection)) = (player.single_mut(), backpack_grids.single())
+    else {
+        return;
+    };
+    let Some(index) =
+        selected_backpack_index(focus_state.as_deref(), &inventory, &view, selection)
+    else {
+        return;
+    };
+    // Only write when there is something to clear, so the grids aren't rebuilt every frame.
+    if inventory.items[index].is_new {
+        inventory.items[index].is_new = false;
+    }
+}
+
+/// Left/Right change the split by one, Up/Down by ten. Enter splits and Backspace cancels;
+/// Escape still closes the whole modal.
+pub fn handle_split_dialog(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    mut dialog: ResMut<SplitStackDialog>,
+    mut split_writer: MessageWriter<SplitStack>,
+) {
+    // Skip the key press that opened the dialog, which may have been Enter on the item menu.
+    if dialog.is_added() {
+        action_reader.clear();
+        return;
+    }
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Left) => dialog.adjust(-1),
+            GameAction::Navigate(NavigationDirection::Right) => dialog.adjust(1),
+            GameAction::Navigate(NavigationDirection::Down) => dialog.adjust(-10),
+            GameAction::Navigate(NavigationDirection::Up) => dialog.adjust(10),
+            GameAction::Select => {
+                split_writer.write(SplitStack {
+                    inventory_index: dialog.inventory_index,
+                    amount: dialog.amount,
+                });
+                commands.remove_resource::<SplitStackDialog>();
+                return;
+            }
+            GameAction::Back => {
+                commands.remove_resource::<SplitStackDialog>();
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Opens the context menu on the selected item, listing the actions that apply to it.
+pub fn open_item_menu(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    view: Res<InventoryView>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    selection: InventorySelection,
+    location: PlayerLocation,
+    popups: InventoryPopups,
+) {
+    let InventorySelection {
+        focus: focus_state,
+        equipment: equipment_grids,
+        backpack: backpack_grids,
+    } = selection;
+    if !action_reader.read().any(|action| *action == GameAction::OpenItemMenu) {
+        return;
+    }
+    let Some(focus_state) = focus_state else {
+        return;
+    };
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+    if popups.is_open() {
+        return;
+    }
+
+    let target = if focus_state.is_focused(FocusPanel::EquipmentGrid) {
+        let Ok(selection) = equipment_grids.single() else {
+            return;
+        };
+        EquipmentSlot::all()
+            .iter()
+            .copied()
+            .filter(|slot| inventory.get_equipped_item(*slot).is_some())
+            .nth(selection.selected_index)
+            .map(ItemMenuTarget::Equipped)
+    } else {
+        let Ok(selection) = backpack_grids.single() else {
+            return;
+        };
+        selected_backpack_index(Some(&*focus_state), inventory, &view, selection)
+            .map(ItemMenuTarget::Backpack)
+    };
+
+    let actions = match target {
+        Some(ItemMenuTarget::Equipped(slot)) => inventory
+            .get_equipped_item(slot)
+            .map(|inv_item| ItemAction::available(inv_item, true, false)),
+        Some(ItemMenuTarget::Backpack(index)) => {
+            // Storage is kept by the town merchant, so items can only be stored from home.
+            Some(ItemAction::available(&inventory.items[index], false, location.in_town()))
+        }
+        None => None,
+    };
+    if let Some(menu) = target.zip(actions).and_then(|(t, a)| ItemContextMenu::new(t, a)) {
+        commands.insert_resource(menu);
+    }
+}
+
+/// Left/Right (or Up/Down) pick an action, Enter carries it out and Backspace closes the menu.
+pub fn handle_item_menu(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    mut menu: ResMut<ItemContextMenu>,
+    mut player: Query<&mut Inventory, With<PlayerMarker>>,
+    mut history: ResMut<UndoHistory>,
+    mut deposit_writer: MessageWriter<StorageDepositEvent>,
+) {
+    // Skip the key press that opened the menu.
+    if menu.is_added() {
+        action_reader.clear();
+        return;
+    }
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Left | NavigationDirection::Up) => {
+                menu.step(-1)
+            }
+            GameAction::Navigate(NavigationDirection::Right | NavigationDirection::Down) => {
+                menu.step(1)
+            }
+            GameAction::Select => {
+                run_item_action(
+                    &mut commands,
+                    &mut inventory,
+                    &mut history,
+                    &mut deposit_writer,
+                    menu.target,
+                    menu.action(),
+                );
+                commands.remove_resource::<ItemContextMenu>();
+                return;
+            }
+            GameAction::Back | GameAction::OpenItemMenu => {
+                commands.remove_resource::<ItemContextMenu>();
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Carries out `action` on `target` through the same paths as the dedicated keys.
+fn run_item_action(
+    commands: &mut Commands,
+    inventory: &mut Inventory,
+    history: &mut UndoHistory,
+    deposit_writer: &mut MessageWriter<StorageDepositEvent>,
+    target: ItemMenuTarget,
+    action: ItemAction,
+) {
+    let inv_item = match target {
+        ItemMenuTarget::Backpack(index) => inventory.items.get_mut(index),
+        ItemMenuTarget::Equipped(slot) => inventory.equipment_mut().get_mut(&slot),
+    };
+    let Some(inv_item) = inv_item else {
+        return;
+    };
+
+    match (action, target) {
+        (ItemAction::Lock | ItemAction::Unlock, _) => {
+            inv_item.item.is_locked = action == ItemAction::Lock;
+        }
+        (ItemAction::Favorite | ItemAction::Unfavorite, _) => {
+            inv_item.item.is_favorite = action == ItemAction::Favorite;
+        }
+        (ItemAction::Equip, ItemMenuTarget::Backpack(index)) => {
+            equip_backpack_item(inventory, index, history);
+        }
+        (ItemAction::Unequip, ItemMenuTarget::Equipped(slot)) => {
+            unequip_slot(inventory, slot, history);
+        }
+        (ItemAction::Split, ItemMenuTarget::Backpack(index)) => {
+            if let Some(dialog) = SplitStackDialog::new(index, inv_item.quantity) {
+                commands.insert_resource(dialog);
+            }
+        }
+        (ItemAction::Deposit, ItemMenuTarget::Backpack(index)) => {
+            deposit_writer.write(StorageDepositEvent {
+                inventory_index: index,
+            });
         }
+        _ => {}
     }
 }
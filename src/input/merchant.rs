@@ -1,9 +1,24 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::game::{BuyItemEvent, SellItemEvent};
+use crate::economy::{CommodityMarket, Haggle};
+use crate::faction::Reputation;
+use crate::game::{
+    junk_totals, BuyItemEvent, DepositGoldEvent, GambleRequest, SellItemEvent, SellJunkEvent,
+    ShowToast, WithdrawGoldEvent,
+};
+use crate::i18n::Localization;
 use crate::input::GameAction;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::loot::LootFilter;
+use crate::player::PlayerMarker;
+use crate::stats::StatSheet;
 use crate::ui::focus::{FocusPanel, FocusState};
-use crate::ui::screens::merchant_modal::{MerchantPlayerGrid, MerchantStockGrid};
+use crate::ui::modal_registry::ModalCommands;
+use crate::ui::screens::merchant_modal::{
+    MerchantModal, MerchantPlayerGrid, MerchantStockGrid, SellJunkDialog,
+};
+use crate::ui::screens::modal::{ModalType, OpenModal};
 use crate::ui::widgets::{ItemGrid, ItemGridSelection};
 
 pub fn navigate_merchant_grid(
@@ -17,10 +32,15 @@ pub fn navigate_merchant_grid(
         (&ItemGrid, &mut ItemGridSelection),
         (With<MerchantPlayerGrid>, Without<MerchantStockGrid>),
     >,
+    dialog: Option<Res<SellJunkDialog>>,
 ) {
     let Some(focus_state) = focus_state else {
         return;
     };
+    if dialog.is_some() {
+        action_reader.clear();
+        return;
+    }
 
     for action in action_reader.read() {
         if let GameAction::Navigate(direction) = action {
@@ -44,10 +64,16 @@ pub fn process_transaction(
     mut sell_events: MessageWriter<SellItemEvent>,
     stock_grids: Query<&ItemGridSelection, (With<MerchantStockGrid>, Without<MerchantPlayerGrid>)>,
     player_grids: Query<&ItemGridSelection, (With<MerchantPlayerGrid>, Without<MerchantStockGrid>)>,
+    dialog: Option<Res<SellJunkDialog>>,
 ) {
     let Some(focus_state) = focus_state else {
         return;
     };
+    // Enter confirms the sell-junk dialog instead of trading the selected item.
+    if dialog.is_some() {
+        action_reader.clear();
+        return;
+    }
 
     for action in action_reader.read() {
         if *action != GameAction::Select {
@@ -71,3 +97,104 @@ pub fn process_transaction(
         }
     }
 }
+
+pub fn process_banking(
+    mut action_reader: MessageReader<GameAction>,
+    mut deposit_events: MessageWriter<DepositGoldEvent>,
+    mut withdraw_events: MessageWriter<WithdrawGoldEvent>,
+) {
+    for action in action_reader.read() {
+        match action {
+            GameAction::DepositGold => {
+                deposit_events.write(DepositGoldEvent);
+            }
+            GameAction::WithdrawGold => {
+                withdraw_events.write(WithdrawGoldEvent);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What the junk in the backpack is quoted from: the loot filter's junk threshold, the
+/// standing the player haggles with, and current market prices.
+#[derive(SystemParam)]
+pub struct JunkQuote<'w> {
+    filter: Res<'w, LootFilter>,
+    reputation: Res<'w, Reputation>,
+    market: Res<'w, CommodityMarket>,
+}
+
+/// Totals up the backpack's junk and asks for confirmation, or says there is none.
+pub fn open_sell_junk_dialog(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    player: Query<(&Inventory, &StatSheet), With<PlayerMarker>>,
+    quote: JunkQuote,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let JunkQuote {
+        filter,
+        reputation,
+        market,
+    } = quote;
+    if !action_reader.read().any(|action| *action == GameAction::SellJunk) {
+        return;
+    }
+    let Ok((inventory, stats)) = player.single() else {
+        return;
+    };
+
+    let haggle = Haggle::for_player(stats, inventory, &reputation);
+    let junk = inventory.junk_items(filter.junk_threshold());
+    let (count, gold) = junk_totals(junk, haggle, &market);
+    if count == 0 {
+        toast_writer.write(ShowToast::new(loc.get("merchant.no_junk")));
+    } else {
+        commands.insert_resource(SellJunkDialog { count, gold });
+    }
+}
+
+/// Enter sells the junk and Backspace cancels; Escape still closes the whole modal.
+pub fn handle_sell_junk_dialog(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    mut sell_writer: MessageWriter<SellJunkEvent>,
+) {
+    for action in action_reader.read() {
+        match action {
+            GameAction::Select => {
+                sell_writer.write(SellJunkEvent);
+                commands.remove_resource::<SellJunkDialog>();
+                return;
+            }
+            GameAction::Back => {
+                commands.remove_resource::<SellJunkDialog>();
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The merchant also keeps the player's item storage; this swaps the merchant modal for
+/// the storage modal.
+pub fn open_storage(mut commands: Commands, mut action_reader: MessageReader<GameAction>) {
+    if action_reader.read().any(|action| *action == GameAction::OpenStorage) {
+        commands.close_modal::<MerchantModal>();
+        commands.trigger(OpenModal(ModalType::Storage));
+    }
+}
+
+/// Asks the merchant for a mystery item.
+pub fn request_gamble(
+    mut action_reader: MessageReader<GameAction>,
+    mut gamble_writer: MessageWriter<GambleRequest>,
+) {
+    for action in action_reader.read() {
+        if *action == GameAction::Gamble {
+            gamble_writer.write(GambleRequest);
+        }
+    }
+}
@@ -11,10 +11,21 @@ mod systems;
 pub use actions::{GameAction, HeldDirection, NavigationDirection};
 pub use systems::{clear_game_action_events, InputPlugin};
 
-pub use anvil::{craft_anvil_recipe, navigate_anvil_grid, sync_anvil_recipes};
-pub use combat::trigger_player_attack;
-pub use compendium::{navigate_compendium, switch_compendium_panel};
-pub use forge::{navigate_forge_ui, transfer_forge_items};
-pub use inventory::{navigate_inventory_grid, toggle_equipment};
-pub use merchant::{navigate_merchant_grid, process_transaction};
+pub use anvil::{
+    craft_anvil_recipe, evolve_anvil_item, fetch_anvil_ingredients, navigate_anvil_grid,
+    sync_anvil_recipes, upgrade_anvil_item_quality,
+};
+pub use combat::{throw_flask, trigger_player_attack};
+pub use compendium::{follow_compendium_link, navigate_compendium, switch_compendium_panel};
+pub use forge::{
+    cycle_apprentice, fetch_forge_ingredients, navigate_forge_ui, transfer_forge_items,
+};
+pub use inventory::{
+    handle_inventory_view_actions, handle_item_menu, handle_split_dialog, mark_selected_seen,
+    navigate_inventory_grid, open_item_menu, toggle_equipment, toggle_favorite,
+};
+pub use merchant::{
+    handle_sell_junk_dialog, navigate_merchant_grid, open_sell_junk_dialog, open_storage,
+    process_banking, process_transaction, request_gamble,
+};
 pub use navigation::{emit_move_intent, request_menu_transition};
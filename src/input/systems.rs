@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
+use crate::console::console_closed;
 use crate::dungeon::InteractableNearby;
+use crate::replay::replay_inactive;
+use crate::ui::screens::modal::{in_help_modal, in_storage_modal};
 
 use super::actions::{GameAction, HeldDirection, NavigationDirection};
 
@@ -29,7 +32,15 @@ impl Plugin for InputPlugin {
             .add_message::<NavigationDirection>()
             .init_resource::<NavigationRepeatState>()
             .init_resource::<HeldDirection>()
-            .add_systems(PreUpdate, translate_keyboard_input);
+            .add_systems(
+                PreUpdate,
+                translate_keyboard_input.run_if(
+                    console_closed
+                        .and(not(in_help_modal))
+                        .and(not(in_storage_modal))
+                        .and(replay_inactive),
+                ),
+            );
     }
 }
 
@@ -83,7 +94,11 @@ fn translate_keyboard_input(
     }
 
     if keyboard.just_pressed(KeyCode::Enter) {
-        action_writer.write(GameAction::Select);
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenItemMenu);
+        } else {
+            action_writer.write(GameAction::Select);
+        }
     }
 
     if keyboard.just_pressed(KeyCode::Backspace) {
@@ -110,7 +125,11 @@ fn translate_keyboard_input(
         action_writer.write(GameAction::OpenInventory);
     }
     if keyboard.just_pressed(KeyCode::KeyP) {
-        action_writer.write(GameAction::OpenProfile);
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenRunPlanner);
+        } else {
+            action_writer.write(GameAction::OpenProfile);
+        }
     }
     if keyboard.just_pressed(KeyCode::Slash)
         && (keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight))
@@ -123,14 +142,217 @@ fn translate_keyboard_input(
     }
 
     if keyboard.just_pressed(KeyCode::KeyB) {
-        action_writer.write(GameAction::OpenCompendium);
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::Gamble);
+        } else {
+            action_writer.write(GameAction::OpenCompendium);
+        }
     }
 
     if keyboard.just_pressed(KeyCode::KeyK) {
         action_writer.write(GameAction::OpenSkills);
     }
 
-    held.0 = repeat.direction;
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::CycleAdaptiveDifficulty);
+        } else {
+            action_writer.write(GameAction::OpenMods);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyL) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::CycleCombatLog);
+        } else {
+            action_writer.write(GameAction::OpenLootFilter);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyA) {
+        action_writer.write(GameAction::OpenRoster);
+    }
+
+    if keyboard.just_pressed(KeyCode::Delete) {
+        action_writer.write(GameAction::DeleteSaveSlot);
+    }
+
+    if keyboard.just_pressed(KeyCode::F1) {
+        action_writer.write(GameAction::OpenHelp);
+    }
+
+    if keyboard.just_pressed(KeyCode::F2) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::ToggleRumble);
+        } else {
+            action_writer.write(GameAction::ToggleUiSounds);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::F4) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::ToggleScreenReader);
+        } else {
+            action_writer.write(GameAction::ToggleDamageNumbers);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::F7) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::CyclePalette);
+        } else {
+            action_writer.write(GameAction::CycleTextScale);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::F8) {
+        action_writer.write(GameAction::CycleLanguage);
+    }
+
+    if keyboard.just_pressed(KeyCode::F12) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::CleanScreenshot);
+        } else {
+            action_writer.write(GameAction::Screenshot);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::CycleDungeonTier);
+        } else {
+            action_writer.write(GameAction::OpenWorldMap);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyH) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenTrophyRoom);
+        } else {
+            action_writer.write(GameAction::OpenGuild);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyE) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenLeaderboard);
+        } else {
+            action_writer.write(GameAction::OpenEventShop);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyU) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenTownUpgrades);
+        } else {
+            action_writer.write(GameAction::OpenAuctionHouse);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyN) {
+        action_writer.write(GameAction::OpenContracts);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyV) {
+        action_writer.write(GameAction::OpenStorage);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyJ) {
+        action_writer.write(GameAction::SellJunk);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenRanch);
+        } else {
+            action_writer.write(GameAction::SortInventory);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyC) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::ThrowCaptureOrb);
+        } else {
+            action_writer.write(GameAction::FilterInventory);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyQ) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::UpgradeQuality);
+        } else {
+            action_writer.write(GameAction::SplitStack);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::ThrowFlask);
+        } else {
+            action_writer.write(GameAction::FetchIngredients);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyS) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::NextSpellPage);
+        } else {
+            action_writer.write(GameAction::Cast);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyW) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenWordIndex);
+        } else {
+            action_writer.write(GameAction::OpenSpellcraft);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyY) {
+        action_writer.write(GameAction::ToggleFavorite);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyO) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::OpenWorkers);
+        } else {
+            action_writer.write(GameAction::CycleApprentice);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyZ) {
+        action_writer.write(GameAction::Undo);
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyX) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::DisableTips);
+        } else {
+            action_writer.write(GameAction::DismissTip);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+            action_writer.write(GameAction::WithdrawGold);
+        } else {
+            action_writer.write(GameAction::DepositGold);
+        }
+    }
+
+    let loadout_keys = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4];
+    for (index, key) in loadout_keys.into_iter().enumerate() {
+        if keyboard.just_pressed(key) {
+            if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) {
+                action_writer.write(GameAction::SaveLoadout(index));
+            } else {
+                action_writer.write(GameAction::SwitchLoadout(index));
+            }
+        }
+    }
+
+    held.set_if_neq(HeldDirection(repeat.direction));
 }
 
 pub fn clear_game_action_events(mut events: ResMut<Messages<GameAction>>) {
@@ -1,11 +1,4 @@
-mod definition;
-mod enums;
-mod equipment;
-#[cfg(test)]
-mod tests;
-mod traits;
+mod saved;
 
-pub use definition::{AddItemResult, EquipmentMap, Inventory, InventoryItem};
-pub use enums::{EquipmentSlot, InventoryError};
-pub use equipment::HasEquipment;
-pub use traits::{FindsItems, HasInventory, ManagesEquipment, ManagesItems};
+pub use randr_core::inventory::*;
+pub use saved::SavedInventory;
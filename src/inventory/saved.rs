@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::{ItemRegistry, SavedItem};
+
+use super::{EquipmentSlot, Inventory, InventoryItem};
+
+/// An [`Inventory`] as written to a save: its stacks, what is equipped and how many slots
+/// it has.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedInventory {
+    pub items: Vec<(SavedItem, u32)>,
+    #[serde(default)]
+    pub equipment: Vec<(EquipmentSlot, SavedItem)>,
+    pub max_slots: usize,
+}
+
+impl From<&Inventory> for SavedInventory {
+    fn from(inventory: &Inventory) -> Self {
+        Self {
+            items: inventory
+                .items
+                .iter()
+                .map(|inv_item| (SavedItem::from(&inv_item.item), inv_item.quantity))
+                .collect(),
+            equipment: inventory
+                .equipment()
+                .iter()
+                .map(|(slot, inv_item)| (*slot, SavedItem::from(&inv_item.item)))
+                .collect(),
+            max_slots: inventory.max_slots(),
+        }
+    }
+}
+
+impl SavedInventory {
+    /// Rebuilds the inventory. Items whose spec is no longer registered are dropped.
+    pub fn restore(&self, registry: &ItemRegistry) -> Inventory {
+        let mut inventory = Inventory::with_max_slots(self.max_slots);
+        inventory.items = self
+            .items
+            .iter()
+            .filter_map(|(item, quantity)| {
+                let mut inv_item = InventoryItem::new(item.restore(registry)?);
+                inv_item.quantity = *quantity;
+                Some(inv_item)
+            })
+            .collect();
+        for (slot, item) in &self.equipment {
+            if let Some(item) = item.restore(registry) {
+                inventory
+                    .equipment_mut()
+                    .insert(*slot, InventoryItem::new(item));
+            }
+        }
+        inventory
+    }
+}
@@ -1,105 +1,15 @@
 use bevy::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
-use crate::assets::SpriteSheetKey;
 use crate::stats::StatSheet;
 
+use super::evolution::Evolution;
+use super::material::MaterialTag;
+
 pub use super::enums::{
     ConsumableType, EquipmentType, ItemQuality, ItemType, MaterialType, ToolKind,
 };
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-pub enum ItemId {
-    Sword,
-    Dagger,
-    GoldSword,
-    IronSword,
-    CopperSword,
-    BonkStick,
-    BasicShield,
-    IronHelmet,
-    IronChestplate,
-    IronGauntlets,
-    IronGreaves,
-    IronLeggings,
-    GoldHelmet,
-    GoldChestplate,
-    GoldGauntlets,
-    GoldGreaves,
-    GoldLeggings,
-    CopperHelmet,
-    CopperChestplate,
-    CopperGauntlets,
-    CopperGreaves,
-    CopperLeggings,
-    CopperPickaxe,
-    GoldRing,
-    ImbaRing,
-    Coal,
-    CopperOre,
-    IronOre,
-    GoldOre,
-    GoldIngot,
-    IronIngot,
-    CopperIngot,
-    BlueCrystal,
-    RedCrystal,
-    GreenCrystal,
-    WhiteCrystal,
-    OrangeCrystal,
-    YellowCrystal,
-    Cowhide,
-    SlimeGel,
-    BasicHPPotion,
-    QualityUpgradeStone,
-}
-
-impl ItemId {
-    pub const ALL: &'static [ItemId] = &[
-        ItemId::Sword,
-        ItemId::Dagger,
-        ItemId::GoldSword,
-        ItemId::IronSword,
-        ItemId::CopperSword,
-        ItemId::BonkStick,
-        ItemId::BasicShield,
-        ItemId::IronHelmet,
-        ItemId::IronChestplate,
-        ItemId::IronGauntlets,
-        ItemId::IronGreaves,
-        ItemId::IronLeggings,
-        ItemId::GoldHelmet,
-        ItemId::GoldChestplate,
-        ItemId::GoldGauntlets,
-        ItemId::GoldGreaves,
-        ItemId::GoldLeggings,
-        ItemId::CopperHelmet,
-        ItemId::CopperChestplate,
-        ItemId::CopperGauntlets,
-        ItemId::CopperGreaves,
-        ItemId::CopperLeggings,
-        ItemId::CopperPickaxe,
-        ItemId::GoldRing,
-        ItemId::ImbaRing,
-        ItemId::Coal,
-        ItemId::CopperOre,
-        ItemId::IronOre,
-        ItemId::GoldOre,
-        ItemId::GoldIngot,
-        ItemId::IronIngot,
-        ItemId::CopperIngot,
-        ItemId::BlueCrystal,
-        ItemId::RedCrystal,
-        ItemId::GreenCrystal,
-        ItemId::WhiteCrystal,
-        ItemId::OrangeCrystal,
-        ItemId::YellowCrystal,
-        ItemId::Cowhide,
-        ItemId::SlimeGel,
-        ItemId::BasicHPPotion,
-        ItemId::QualityUpgradeStone,
-    ];
-}
+pub use randr_core::item::ItemId;
 
 #[derive(Debug, Clone, Deserialize, Asset, TypePath)]
 pub struct ItemSpec {
@@ -113,16 +23,24 @@ pub struct ItemSpec {
     pub gold_value: i32,
     pub sprite_name: String,
     #[serde(default)]
-    pub sprite_sheet: Option<SpriteSheetKey>,
+    pub sprite_sheet: Option<ItemSheet>,
+    /// Where the item sits in the smelting chain, for ores and ingots.
+    #[serde(default)]
+    pub tags: Vec<MaterialTag>,
+    /// The named item this one becomes at the anvil once fully upgraded.
+    #[serde(default)]
+    pub evolution: Option<Evolution>,
 }
 
 use uuid::Uuid;
-use super::definition::Item;
-use super::sprite_info::SpriteInfo;
+use super::{Item, ItemSheet, SpriteInfo};
 
 impl ItemSpec {
     pub(super) fn to_item(&self) -> Item {
-        let quality = self.quality.unwrap_or_else(ItemQuality::roll);
+        self.to_item_with_quality(self.quality.unwrap_or_else(ItemQuality::roll))
+    }
+
+    pub(super) fn to_item_with_quality(&self, quality: ItemQuality) -> Item {
         let base_stats = self.stats.clone();
         let stats = quality.multiply_stats(&base_stats);
 
@@ -133,6 +51,7 @@ impl ItemSpec {
             name: self.name.clone(),
             is_equipped: false,
             is_locked: false,
+            is_favorite: false,
             num_upgrades: 0,
             max_upgrades: self.max_upgrades,
             max_stack_quantity: self.max_stack_quantity,
@@ -142,7 +61,7 @@ impl ItemSpec {
             quality,
             sprite: SpriteInfo {
                 name: self.sprite_name.clone(),
-                sheet_key: self.sprite_sheet.unwrap_or(SpriteSheetKey::IconItems),
+                sheet_key: self.sprite_sheet.unwrap_or_default(),
             },
         }
     }
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::ItemId;
+
+/// Running totals of every item the player has obtained from loot or crafting.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ItemDiscovery {
+    found: HashMap<ItemId, u32>,
+}
+
+impl ItemDiscovery {
+    pub fn record(&mut self, item_id: ItemId, quantity: u32) {
+        if quantity == 0 {
+            return;
+        }
+        *self.found.entry(item_id).or_default() += quantity;
+    }
+
+    /// Total number of `item_id` found so far.
+    pub fn count(&self, item_id: ItemId) -> u32 {
+        self.found.get(&item_id).copied().unwrap_or(0)
+    }
+
+    pub fn is_discovered(&self, item_id: ItemId) -> bool {
+        self.count(item_id) > 0
+    }
+
+    /// Number of distinct items found at least once.
+    pub fn discovered_count(&self) -> usize {
+        self.found.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_accumulates_per_item() {
+        let mut discovery = ItemDiscovery::default();
+        discovery.record(ItemId::IronOre, 2);
+        discovery.record(ItemId::IronOre, 3);
+        discovery.record(ItemId::GoldOre, 1);
+
+        assert_eq!(discovery.count(ItemId::IronOre), 5);
+        assert_eq!(discovery.count(ItemId::GoldOre), 1);
+        assert_eq!(discovery.discovered_count(), 2);
+    }
+
+    #[test]
+    fn zero_quantity_does_not_discover() {
+        let mut discovery = ItemDiscovery::default();
+        discovery.record(ItemId::IronOre, 0);
+
+        assert!(!discovery.is_discovered(ItemId::IronOre));
+        assert_eq!(discovery.discovered_count(), 0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Item evolution: a fully upgraded item plus rare materials becomes a named successor,
+//! worked at the anvil.
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::inventory::{FindsItems, Inventory, ManagesItems};
+
+use super::Item;
+use super::definitions::{ItemId, ItemSpec};
+use super::registry::ItemRegistry;
+
+/// What an item evolves into, and the materials it takes on top of the item itself.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Evolution {
+    pub into: ItemId,
+    pub materials: Vec<(ItemId, u32)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum EvolutionError {
+    #[error("Item not found in the bag")]
+    ItemNotFound,
+    #[error("{0:?} does not evolve")]
+    NoEvolution(ItemId),
+    #[error("Item must be fully upgraded to evolve")]
+    NotFullyUpgraded,
+    #[error("Not enough materials to evolve")]
+    MissingMaterials,
+}
+
+impl ItemSpec {
+    pub fn evolution(&self) -> Option<&Evolution> {
+        self.evolution.as_ref()
+    }
+}
+
+/// The evolution `item` is ready for, or why it isn't ready yet.
+pub fn evolution_for<'a>(
+    item: &Item,
+    inventory: &Inventory,
+    registry: &'a ItemRegistry,
+) -> Result<&'a Evolution, EvolutionError> {
+    let evolution = registry
+        .try_get(item.item_id)
+        .and_then(ItemSpec::evolution)
+        .ok_or(EvolutionError::NoEvolution(item.item_id))?;
+    if item.num_upgrades < item.max_upgrades {
+        return Err(EvolutionError::NotFullyUpgraded);
+    }
+    let has_materials = evolution
+        .materials
+        .iter()
+        .all(|(item_id, required)| inventory.count_item(*item_id) >= *required);
+    if !has_materials {
+        return Err(EvolutionError::MissingMaterials);
+    }
+    Ok(evolution)
+}
+
+/// Evolves the bag item `uuid`, using up the materials. The successor keeps the item's
+/// quality and whether it was locked or starred, and starts again from no upgrades.
+pub fn evolve_item(
+    inventory: &mut Inventory,
+    uuid: Uuid,
+    registry: &ItemRegistry,
+) -> Result<Item, EvolutionError> {
+    let item = inventory
+        .get_inventory_items()
+        .iter()
+        .find(|inv_item| inv_item.uuid() == uuid)
+        .map(|inv_item| inv_item.item.clone())
+        .ok_or(EvolutionError::ItemNotFound)?;
+    let evolution = evolution_for(&item, inventory, registry)?;
+
+    let mut successor = registry.spawn_with_quality(evolution.into, item.quality);
+    successor.is_locked = item.is_locked;
+    successor.is_favorite = item.is_favorite;
+
+    inventory.remove_item(uuid);
+    for (item_id, required) in &evolution.materials {
+        inventory.decrease_item_quantity(*item_id, *required);
+    }
+    // The evolved item's own slot was just freed, so there is always room for this.
+    let _ = inventory.add_to_inv(successor.clone());
+    Ok(successor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::enums::ItemQuality;
+
+    fn registry() -> ItemRegistry {
+        ItemRegistry::from_assets()
+    }
+
+    fn maxed(registry: &ItemRegistry, id: ItemId) -> Item {
+        let mut item = registry.spawn_with_quality(id, ItemQuality::Masterworked);
+        item.num_upgrades = item.max_upgrades;
+        item.is_favorite = true;
+        item
+    }
+
+    fn add_materials(inventory: &mut Inventory, registry: &ItemRegistry, evolution: &Evolution) {
+        for (item_id, count) in &evolution.materials {
+            for _ in 0..*count {
+                inventory.add_to_inv(registry.spawn(*item_id)).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn evolutions_lead_to_equipment_of_the_same_kind() {
+        let registry = registry();
+        let evolving: Vec<_> = ItemId::ALL
+            .iter()
+            .filter_map(|id| registry.try_get(*id))
+            .filter_map(|spec| spec.evolution().map(|evolution| (spec, evolution)))
+            .collect();
+        assert!(!evolving.is_empty());
+
+        for (spec, evolution) in evolving {
+            let successor = registry.get(evolution.into);
+            assert_eq!(spec.item_type, successor.item_type, "{:?}", spec.id);
+            assert!(!evolution.materials.is_empty(), "{:?}", spec.id);
+        }
+    }
+
+    #[test]
+    fn a_maxed_item_with_materials_evolves_and_keeps_its_quality() {
+        let registry = registry();
+        let mut inventory = Inventory::new();
+        let sword = maxed(&registry, ItemId::GoldSword);
+        let uuid = sword.item_uuid;
+        inventory.add_to_inv(sword).unwrap();
+
+        assert_eq!(
+            evolve_item(&mut inventory, uuid, &registry).unwrap_err(),
+            EvolutionError::MissingMaterials
+        );
+
+        let evolution = registry.get(ItemId::GoldSword).evolution().unwrap().clone();
+        add_materials(&mut inventory, &registry, &evolution);
+        let successor = evolve_item(&mut inventory, uuid, &registry).unwrap();
+
+        assert_eq!(successor.item_id, evolution.into);
+        assert_eq!(successor.quality, ItemQuality::Masterworked);
+        assert_eq!(successor.num_upgrades, 0);
+        assert!(successor.is_favorite);
+        assert_eq!(inventory.count_item(ItemId::GoldSword), 0);
+        assert_eq!(inventory.count_item(evolution.into), 1);
+        for (item_id, _) in &evolution.materials {
+            assert_eq!(inventory.count_item(*item_id), 0);
+        }
+    }
+
+    #[test]
+    fn an_unfinished_item_cannot_evolve() {
+        let registry = registry();
+        let mut inventory = Inventory::new();
+        let sword = registry.spawn(ItemId::GoldSword);
+        let uuid = sword.item_uuid;
+        inventory.add_to_inv(sword).unwrap();
+
+        assert_eq!(
+            evolve_item(&mut inventory, uuid, &registry).unwrap_err(),
+            EvolutionError::NotFullyUpgraded
+        );
+        assert_eq!(
+            evolve_item(&mut inventory, Uuid::new_v4(), &registry).unwrap_err(),
+            EvolutionError::ItemNotFound
+        );
+    }
+}
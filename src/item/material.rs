@@ -0,0 +1,87 @@
+//! Tags that tie raw materials into the smelting chain. An ore names the ingot it smelts
+//! into and both carry a tier, so a new metal only needs its item files to work at a forge.
+
+use serde::Deserialize;
+
+use super::definitions::{ItemId, ItemSpec};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum MaterialTag {
+    Ore,
+    Ingot,
+    /// Rank of the metal, from 1 for the softest.
+    Tier(u8),
+    /// The ingot this ore smelts into.
+    SmeltsInto(ItemId),
+}
+
+impl ItemSpec {
+    pub fn has_tag(&self, tag: MaterialTag) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    pub fn tier(&self) -> Option<u8> {
+        self.tags.iter().find_map(|tag| match tag {
+            MaterialTag::Tier(tier) => Some(*tier),
+            _ => None,
+        })
+    }
+
+    /// The ingot this item smelts into, if it is an ore.
+    pub fn smelts_into(&self) -> Option<ItemId> {
+        if !self.has_tag(MaterialTag::Ore) {
+            return None;
+        }
+        self.tags.iter().find_map(|tag| match tag {
+            MaterialTag::SmeltsInto(ingot) => Some(*ingot),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use super::*;
+
+    fn item_specs() -> HashMap<ItemId, ItemSpec> {
+        fs::read_dir("assets/data/items")
+            .unwrap()
+            .map(|entry| {
+                let text = fs::read_to_string(entry.unwrap().path()).unwrap();
+                let spec: ItemSpec = ron::from_str(&text).unwrap();
+                (spec.id, spec)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_ore_smelts_into_an_ingot_of_its_tier() {
+        let specs = item_specs();
+        let ores: Vec<_> = specs
+            .values()
+            .filter(|spec| spec.has_tag(MaterialTag::Ore))
+            .collect();
+        assert!(ores.len() >= 3);
+
+        for ore in ores {
+            let ingot = &specs[&ore.smelts_into().expect("ore without an ingot")];
+            assert!(ingot.has_tag(MaterialTag::Ingot), "{:?}", ingot.id);
+            assert_eq!(ore.tier(), ingot.tier(), "{:?}", ore.id);
+            assert!(ore.tier().is_some());
+        }
+    }
+
+    #[test]
+    fn only_ores_smelt() {
+        let specs = item_specs();
+        assert_eq!(
+            specs[&ItemId::CopperOre].smelts_into(),
+            Some(ItemId::CopperIngot)
+        );
+        assert_eq!(specs[&ItemId::CopperIngot].smelts_into(), None);
+        assert_eq!(specs[&ItemId::Coal].smelts_into(), None);
+    }
+}
@@ -1,14 +1,18 @@
-pub mod definition;
 pub mod definitions;
+pub mod discovery;
 pub mod enums;
+pub mod evolution;
+pub mod material;
 pub mod recipe;
 pub mod registry;
-pub mod sprite_info;
-mod traits;
+pub mod saved;
 
-pub use definition::Item;
 pub use definitions::ItemId;
+pub use discovery::ItemDiscovery;
 pub use enums::ItemType;
 pub use enums::UpgradeResult;
+pub use evolution::{Evolution, EvolutionError};
+pub use material::MaterialTag;
+pub use randr_core::item::{Item, ItemSheet, SpriteInfo};
 pub use registry::ItemRegistry;
-pub use sprite_info::SpriteInfo;
+pub use saved::SavedItem;
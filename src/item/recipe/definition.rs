@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::{
     item::ItemId,
-    inventory::{FindsItems, ManagesItems},
+    inventory::ManagesItems,
 };
 
 use super::specs::{RecipeId, RecipeSpec};
@@ -30,26 +30,23 @@ impl Recipe {
         self.spec.output
     }
 
-    pub fn can_craft<T: FindsItems>(&self, inventory: &T) -> bool {
-        self.spec.ingredients.iter().all(|(&item_id, &qty)| {
-            inventory
-                .find_item_by_id(item_id)
-                .map(|inv| inv.quantity >= qty)
-                .unwrap_or(false)
-        })
+    /// Whether `inventory` holds enough of every ingredient, counting split stacks together.
+    pub fn can_craft<T: ManagesItems>(&self, inventory: &T) -> bool {
+        self.spec
+            .ingredients
+            .iter()
+            .all(|(&item_id, &qty)| inventory.count_item(item_id) >= qty)
     }
 
     /// Consumes ingredients from inventory and returns the ItemId to spawn.
     /// The caller is responsible for spawning the item using an ItemRegistry.
-    pub fn craft<T: FindsItems + ManagesItems>(&self, inventory: &mut T) -> Result<ItemId, RecipeError> {
+    pub fn craft<T: ManagesItems>(&self, inventory: &mut T) -> Result<ItemId, RecipeError> {
         if !self.can_craft(inventory) {
             return Err(RecipeError::NotEnoughIngredients);
         }
 
         for (&item_id, &qty) in &self.spec.ingredients {
-            if inventory.find_item_by_id(item_id).is_some() {
-                inventory.decrease_item_quantity(item_id, qty);
-            }
+            inventory.decrease_item_quantity(item_id, qty);
         }
 
         Ok(self.spec.output)
@@ -24,6 +24,8 @@ entity_macros::define_data! {
 
     id RecipeId;
 
+    overridable;
+
     variants {
         // ─────────────────────────────────────────────────────────────────────
         // Smelting Recipes
@@ -85,6 +87,20 @@ entity_macros::define_data! {
             output_quantity: 1,
             recipe_type: RecipeType::Alchemy,
         }
+        HerbalHPPotion {
+            name: "Herbal HP Potion",
+            ingredients: HashMap::from([(ItemId::Herb, 3), (ItemId::SlimeGel, 2)]),
+            output: ItemId::BasicHPPotion,
+            output_quantity: 1,
+            recipe_type: RecipeType::Alchemy,
+        }
+        FireFlask {
+            name: "Fire Flask",
+            ingredients: HashMap::from([(ItemId::SlimeGel, 4), (ItemId::Coal, 2)]),
+            output: ItemId::FireFlask,
+            output_quantity: 1,
+            recipe_type: RecipeType::Alchemy,
+        }
 
         // ─────────────────────────────────────────────────────────────────────
         // Iron Armor Recipes
@@ -277,7 +293,23 @@ impl RecipeId {
             | RecipeId::CopperGreaves
             | RecipeId::CopperLeggings => ForgeMaterial::Bronze,
 
-            RecipeId::BasicHPPotion => ForgeMaterial::Other,
+            RecipeId::BasicHPPotion | RecipeId::HerbalHPPotion | RecipeId::FireFlask => {
+                ForgeMaterial::Other
+            }
+        }
+    }
+
+    /// Level the town's blacksmith (for forging) or alchemist (for brewing) needs before
+    /// this recipe can be made. Copper gear and the basic potion need no upgrades.
+    pub fn tier(&self) -> u32 {
+        match self {
+            RecipeId::HerbalHPPotion => 1,
+            RecipeId::FireFlask => 2,
+            _ => match self.material() {
+                ForgeMaterial::Bronze | ForgeMaterial::Other => 0,
+                ForgeMaterial::Iron => 1,
+                ForgeMaterial::Gold => 2,
+            },
         }
     }
 }
@@ -3,11 +3,10 @@ use uuid::Uuid;
 
 #[cfg(test)]
 use crate::{
-    assets::SpriteSheetKey,
     inventory::{FindsItems, Inventory, ManagesItems},
     item::enums::{ItemQuality, MaterialType},
     item::recipe::{Recipe, RecipeError, RecipeId},
-    item::{Item, ItemId, ItemType, SpriteInfo},
+    item::{Item, ItemId, ItemSheet, ItemType, SpriteInfo},
     stats::StatSheet,
 };
 
@@ -20,6 +19,7 @@ fn create_test_material(id: ItemId) -> Item {
         name: format!("{:?}", id),
         is_equipped: false,
         is_locked: false,
+        is_favorite: false,
         num_upgrades: 0,
         max_upgrades: 0,
         max_stack_quantity: 99,
@@ -27,7 +27,7 @@ fn create_test_material(id: ItemId) -> Item {
         stats: StatSheet::new(),
         gold_value: 10,
         quality: ItemQuality::Normal,
-        sprite: SpriteInfo { name: String::new(), sheet_key: SpriteSheetKey::IconItems },
+        sprite: SpriteInfo { name: String::new(), sheet_key: ItemSheet::IconItems },
     }
 }
 
@@ -337,3 +337,17 @@ fn user_flow_crafting_consumes_exact_quantities() {
 
     assert!(!recipe.can_craft(&inventory));
 }
+
+#[test]
+fn user_flow_crafting_takes_from_every_split_stack() {
+    let mut inventory = Inventory::new();
+    add_materials(&mut inventory, ItemId::SlimeGel, 10);
+    inventory.split_stack(0, 5).unwrap();
+
+    let recipe = Recipe::new(RecipeId::BasicHPPotion).unwrap();
+    assert!(recipe.can_craft(&inventory));
+    assert!(recipe.craft(&mut inventory).is_ok());
+
+    assert_eq!(inventory.count_item(ItemId::SlimeGel), 0);
+    assert!(inventory.get_inventory_items().is_empty());
+}
@@ -2,8 +2,9 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
-use super::definition::Item;
+use super::Item;
 use super::definitions::{ItemId, ItemSpec};
+use super::enums::ItemQuality;
 
 #[derive(Clone, Resource)]
 pub struct ItemRegistry(HashMap<ItemId, ItemSpec>);
@@ -23,10 +24,37 @@ impl ItemRegistry {
             .unwrap_or_else(|| panic!("No item spec for {id:?}"))
     }
 
+    pub fn try_get(&self, id: ItemId) -> Option<&ItemSpec> {
+        self.0.get(&id)
+    }
+
+    /// The ingot a forge makes from `ore`, or `None` if it doesn't smelt.
+    pub fn smelt_output(&self, ore: ItemId) -> Option<ItemId> {
+        self.try_get(ore).and_then(ItemSpec::smelts_into)
+    }
+
     pub fn spawn(&self, id: ItemId) -> Item {
         self.0
             .get(&id)
             .unwrap_or_else(|| panic!("No item spec for {id:?}"))
             .to_item()
     }
+
+    /// Spawns `id` at `quality` instead of a random one. Items whose spec fixes a quality
+    /// keep it.
+    pub fn spawn_with_quality(&self, id: ItemId, quality: ItemQuality) -> Item {
+        let spec = self.get(id);
+        spec.to_item_with_quality(spec.quality.unwrap_or(quality))
+    }
+
+    /// Registers every spec under `assets/data/items`, as loading does.
+    #[cfg(test)]
+    pub fn from_assets() -> Self {
+        let mut registry = Self::new();
+        for entry in std::fs::read_dir("assets/data/items").unwrap() {
+            let text = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            registry.register(ron::from_str(&text).unwrap());
+        }
+        registry
+    }
 }
@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Item;
+use super::definitions::ItemId;
+use super::enums::ItemQuality;
+use super::registry::ItemRegistry;
+use crate::registry::{ContentKey, ContentRegistry};
+
+/// The parts of an [`Item`] that can change after it is spawned. Everything else is rebuilt
+/// from its spec, so saves pick up rebalanced stats and prices. The ID is written as its
+/// content key (`base:iron_sword`) rather than its enum name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "SavedItemFile", into = "SavedItemFile")]
+pub struct SavedItem {
+    pub uuid: Uuid,
+    pub id: ItemId,
+    pub quality: ItemQuality,
+    #[serde(default)]
+    pub upgrades: i32,
+    #[serde(default)]
+    pub equipped: bool,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// How a [`SavedItem`] is written. Saves from before content keys only have `id`.
+#[derive(Serialize, Deserialize)]
+struct SavedItemFile {
+    uuid: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "bare")]
+    key: Option<ContentKey>,
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "bare")]
+    id: Option<ItemId>,
+    quality: ItemQuality,
+    #[serde(default)]
+    upgrades: i32,
+    #[serde(default)]
+    equipped: bool,
+    #[serde(default)]
+    locked: bool,
+    #[serde(default)]
+    favorite: bool,
+}
+
+/// Writes optional fields as `id: IronOre` rather than `id: Some(IronOre)`, the way saves
+/// from before content keys have it.
+mod bare {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        T::deserialize(deserializer).map(Some)
+    }
+}
+
+impl From<SavedItem> for SavedItemFile {
+    fn from(item: SavedItem) -> Self {
+        let key = ContentRegistry::installed().key_of(item.id).cloned();
+        Self {
+            uuid: item.uuid,
+            id: key.is_none().then_some(item.id),
+            key,
+            quality: item.quality,
+            upgrades: item.upgrades,
+            equipped: item.equipped,
+            locked: item.locked,
+            favorite: item.favorite,
+        }
+    }
+}
+
+impl TryFrom<SavedItemFile> for SavedItem {
+    type Error = String;
+
+    fn try_from(file: SavedItemFile) -> Result<Self, Self::Error> {
+        let id = match (&file.key, file.id) {
+            (Some(key), _) => ContentRegistry::installed()
+                .resolve(&key.to_string())
+                .map_err(|e| e.to_string())?,
+            (None, Some(id)) => id,
+            (None, None) => return Err(format!("Saved item {} has no key", file.uuid)),
+        };
+        Ok(Self {
+            uuid: file.uuid,
+            id,
+            quality: file.quality,
+            upgrades: file.upgrades,
+            equipped: file.equipped,
+            locked: file.locked,
+            favorite: file.favorite,
+        })
+    }
+}
+
+impl From<&Item> for SavedItem {
+    fn from(item: &Item) -> Self {
+        Self {
+            uuid: item.item_uuid,
+            id: item.item_id,
+            quality: item.quality,
+            upgrades: item.num_upgrades,
+            equipped: item.is_equipped,
+            locked: item.is_locked,
+            favorite: item.is_favorite,
+        }
+    }
+}
+
+impl SavedItem {
+    /// Rebuilds the item, replaying its upgrades. `None` if its spec is no longer registered.
+    pub fn restore(&self, registry: &ItemRegistry) -> Option<Item> {
+        let mut item = registry.try_get(self.id)?.to_item_with_quality(self.quality);
+        for _ in 0..self.upgrades {
+            if item.upgrade().is_err() {
+                break;
+            }
+        }
+        item.item_uuid = self.uuid;
+        item.is_equipped = self.equipped;
+        item.is_locked = self.locked;
+        item.is_favorite = self.favorite;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn restored_items_keep_upgrades_and_flags() {
+        let registry = ItemRegistry::from_assets();
+        let id = ItemId::ALL
+            .iter()
+            .copied()
+            .find(|id| registry.try_get(*id).is_some_and(|spec| spec.max_upgrades > 1))
+            .unwrap();
+        let mut item = registry.spawn_with_quality(id, ItemQuality::Improved);
+        item.upgrade().unwrap();
+        item.is_locked = true;
+
+        let saved: SavedItem = from_ron(&to_ron(&SavedItem::from(&item)).unwrap()).unwrap();
+        let restored = saved.restore(&registry).unwrap();
+
+        assert_eq!(restored.item_uuid, item.item_uuid);
+        assert_eq!(restored.quality, item.quality);
+        assert_eq!(restored.num_upgrades, 1);
+        assert_eq!(restored.stats, item.stats);
+        assert!(restored.is_locked);
+    }
+
+    #[test]
+    fn saved_items_are_written_by_content_key() {
+        let registry = ItemRegistry::from_assets();
+        let saved = SavedItem::from(&registry.spawn(ItemId::IronOre));
+
+        let written = to_ron(&saved).unwrap();
+
+        assert!(written.contains("key: \"base:iron_ore\""));
+        assert_eq!(from_ron::<SavedItem>(&written).unwrap(), saved);
+    }
+
+    #[test]
+    fn saves_from_before_content_keys_still_load() {
+        let saved: SavedItem = from_ron(
+            "(uuid: \"67e55044-10b1-426f-9247-bb680e5fe0c8\", id: IronOre, quality: Normal)",
+        )
+        .unwrap();
+
+        assert_eq!(saved.id, ItemId::IronOre);
+    }
+}
@@ -1,19 +1,28 @@
 pub mod assets;
+pub mod auction;
+pub mod bot;
 pub mod camera;
 pub mod chest;
 pub mod crafting_station;
+pub mod dialogue;
 pub mod rock;
 pub mod storage;
 pub mod economy;
+pub mod faction;
+pub mod guild;
 pub mod location;
 pub mod loot;
+pub mod magic;
 pub mod mob;
 pub mod navigation;
+pub mod ranch;
 pub mod player;
 pub mod ui;
 pub mod entities;
 pub mod combat;
+pub mod console;
 pub mod data;
+pub mod difficulty;
 pub mod dungeon;
 pub mod item;
 pub mod registry;
@@ -21,8 +30,24 @@ pub mod inventory;
 pub mod skills;
 pub mod stats;
 pub mod game;
+pub mod help;
+#[cfg(feature = "headless")]
+pub mod headless;
+pub mod i18n;
 pub mod input;
+pub mod replay;
+pub mod save;
+pub mod settings;
 pub mod states;
+#[cfg(feature = "tui")]
+pub mod terminal;
+pub mod trophy;
+pub mod tutorial;
+pub mod workers;
+pub mod world_event;
+pub mod world_state;
+pub mod presence;
+pub mod utils;
 pub mod plugins;
 
 #[cfg(test)]
@@ -1,5 +1,12 @@
 pub mod spec;
 pub mod store;
+pub mod town;
+pub mod upgrades;
 
-pub use spec::{LocationData, LocationId, LocationSpec, LocationType, StoreData};
+pub use spec::{CombatSubtype, LocationData, LocationId, LocationSpec, LocationType, StoreData};
 pub use store::{Store, StoreItem};
+pub use town::{CurrentTown, PlayerLocation, TownId, TownSpec, TravelRoute};
+pub use upgrades::{
+    BuildingCost, TownBuilding, TownState, MAX_BUILDING_LEVEL, STOCK_SLOTS_PER_LEVEL,
+    TOWN_SAVE_PATH,
+};
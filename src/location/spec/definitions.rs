@@ -48,6 +48,20 @@ pub struct FieldData {
     pub mob_weights: HashMap<MobId, i32>,
 }
 
+impl FieldData {
+    /// The field's mobs and their spawn weights, in [`MobId::ALL`] order so spawns stay
+    /// reproducible from a floor seed.
+    pub fn weighted_mobs(&self) -> Vec<(MobId, u32)> {
+        MobId::ALL
+            .iter()
+            .filter_map(|id| {
+                let weight = *self.mob_weights.get(id)?;
+                (weight > 0).then_some((*id, weight as u32))
+            })
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct DungeonData {
     // Dungeon-specific data is managed by DungeonRegistry/DungeonPlugin
@@ -122,6 +136,19 @@ entity_macros::define_data! {
                 ]),
             }),
         }
+        CaravanRoad {
+            name: "Caravan Road",
+            description: "The road between towns, where raiders prey on caravans",
+            refresh_interval: None,
+            min_level: None,
+            data: LocationData::Field(FieldData {
+                mob_weights: HashMap::from([
+                    (MobId::Goblin, 5),
+                    (MobId::Slime, 2),
+                    (MobId::DwarfWarrior, 2),
+                ]),
+            }),
+        }
 
         Home {
             name: "Home",
@@ -137,6 +164,27 @@ entity_macros::define_data! {
             min_level: None,
             data: LocationData::Dungeon(DungeonData {}),
         }
+        Outpost {
+            name: "Frostpeak Outpost",
+            description: "A mining camp in the mountains with its own merchant and forge",
+            refresh_interval: None,
+            min_level: None,
+            data: LocationData::Dungeon(DungeonData {}),
+        }
+        DeepMine {
+            name: "Deep Mine",
+            description: "Old dwarven tunnels beneath the outpost",
+            refresh_interval: None,
+            min_level: Some(5),
+            data: LocationData::Dungeon(DungeonData {}),
+        }
+        EndlessDepths {
+            name: "Endless Depths",
+            description: "Caves that go down further than anyone has come back from",
+            refresh_interval: None,
+            min_level: None,
+            data: LocationData::Dungeon(DungeonData {}),
+        }
     }
 }
 
@@ -177,8 +225,14 @@ impl LocationId {
             LocationId::VillageStore => LocationType::Commerce(CommerceSubtype::Store),
             LocationId::VillageBlacksmith => LocationType::Crafting(CraftingSubtype::Blacksmith),
             LocationId::VillageAlchemist => LocationType::Crafting(CraftingSubtype::Alchemist),
-            LocationId::VillageField => LocationType::Combat(CombatSubtype::Field),
-            LocationId::Home | LocationId::MainDungeon => {
+            LocationId::VillageField | LocationId::CaravanRoad => {
+                LocationType::Combat(CombatSubtype::Field)
+            }
+            LocationId::Home
+            | LocationId::MainDungeon
+            | LocationId::Outpost
+            | LocationId::DeepMine
+            | LocationId::EndlessDepths => {
                 LocationType::Combat(CombatSubtype::Dungeon)
             }
         }
@@ -1,6 +1,6 @@
 pub mod definitions;
 
 pub use definitions::{
-    AlchemistData, BlacksmithData, FieldData, LocationData, LocationId, LocationSpec, LocationType,
-    StoreData,
+    AlchemistData, BlacksmithData, CombatSubtype, FieldData, LocationData, LocationId, LocationSpec,
+    LocationType, StoreData,
 };
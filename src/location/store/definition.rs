@@ -3,11 +3,10 @@ use std::fmt::Display;
 use bevy::prelude::Resource;
 
 use crate::{
-    economy::WorthGold,
+    economy::{Currency, Haggle, Wallet},
     inventory::{Inventory, ManagesItems},
     item::{Item, ItemId, ItemRegistry},
     location::{LocationId, LocationSpec, StoreData},
-    player::PlayerGold,
 };
 
 use super::store_item::StoreItem;
@@ -50,9 +49,10 @@ impl Store {
 
     pub fn purchase_item(
         &mut self,
-        gold: &mut PlayerGold,
+        wallet: &mut Wallet,
         inventory: &mut Inventory,
         index: usize,
+        haggle: Haggle,
     ) -> Result<Item, super::StoreError> {
         use super::StoreError;
 
@@ -61,9 +61,9 @@ impl Store {
         }
 
         let item = self.inventory[index].take_item().ok_or(StoreError::OutOfStock)?;
-        let cost = item.purchase_price();
+        let cost = haggle.buy_price(&item);
 
-        if gold.0 < cost {
+        if wallet.gold() < cost {
             self.inventory[index].items.push(item);
             return Err(StoreError::NotEnoughGold);
         }
@@ -73,7 +73,7 @@ impl Store {
             return Err(StoreError::InventoryFull);
         }
 
-        gold.subtract(cost);
+        wallet.subtract(Currency::Gold, cost);
         Ok(item)
     }
 
@@ -0,0 +1,217 @@
+//! Towns the player can live in and travel between.
+//!
+//! Each town pairs a safe home location (merchant, forge, anvil) with the dungeon its door
+//! leads to, may have fields around it to explore, and has its own storage and merchant
+//! stock.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::item::ItemId;
+use crate::location::LocationId;
+
+/// Gold charged per league of road travelled.
+pub const GOLD_PER_LEAGUE: i32 = 3;
+
+/// Real seconds spent on the road per league.
+pub const SECONDS_PER_LEAGUE: f32 = 0.25;
+
+entity_macros::define_data! {
+    spec TownSpec {
+        pub name: &'static str,
+        pub home: LocationId,
+        pub dungeon: LocationId,
+        // Open fields outside town, reached from the world map
+        pub field: Option<LocationId>,
+        // World map position, in leagues
+        pub map_position: Vec2,
+        // Storage capacity in slots; None is unlimited
+        pub storage_slots: Option<usize>,
+        // Items the town merchant can roll into stock, with quantity ranges
+        pub merchant_pool: Vec<(ItemId, RangeInclusive<i32>)>,
+    }
+
+    id TownId;
+
+    variants {
+        Village {
+            name: "Village",
+            home: LocationId::Home,
+            dungeon: LocationId::MainDungeon,
+            field: Some(LocationId::VillageField),
+            map_position: Vec2::new(0.0, 0.0),
+            storage_slots: None,
+            merchant_pool: vec![
+                (ItemId::BasicHPPotion, 3..=8),
+                (ItemId::CaptureOrb, 2..=5),
+                (ItemId::FireFlask, 3..=6),
+                (ItemId::Sword, 1..=1),
+                (ItemId::Dagger, 1..=1),
+                (ItemId::Shortbow, 1..=1),
+                (ItemId::GoldSword, 1..=1),
+                (ItemId::IronSword, 1..=1),
+                (ItemId::CopperSword, 1..=1),
+                (ItemId::BasicShield, 1..=1),
+                (ItemId::IronHelmet, 1..=1),
+                (ItemId::IronChestplate, 1..=1),
+                (ItemId::IronGauntlets, 1..=1),
+                (ItemId::IronGreaves, 1..=1),
+                (ItemId::IronLeggings, 1..=1),
+                (ItemId::GoldHelmet, 1..=1),
+                (ItemId::GoldChestplate, 1..=1),
+                (ItemId::GoldGauntlets, 1..=1),
+                (ItemId::GoldGreaves, 1..=1),
+                (ItemId::GoldLeggings, 1..=1),
+                (ItemId::CopperHelmet, 1..=1),
+                (ItemId::CopperChestplate, 1..=1),
+                (ItemId::CopperGauntlets, 1..=1),
+                (ItemId::CopperGreaves, 1..=1),
+                (ItemId::CopperLeggings, 1..=1),
+                (ItemId::CopperPickaxe, 1..=1),
+                (ItemId::GoldRing, 1..=1),
+                (ItemId::SilverAmulet, 1..=1),
+                (ItemId::TravelersCape, 1..=1),
+                (ItemId::IronOre, 5..=15),
+                (ItemId::GoldOre, 5..=15),
+                (ItemId::Coal, 5..=15),
+                (ItemId::IronIngot, 2..=5),
+                (ItemId::GoldIngot, 2..=5),
+                (ItemId::CopperIngot, 1..=3),
+                (ItemId::Cowhide, 3..=8),
+                (ItemId::SlimeGel, 3..=8),
+            ],
+        }
+        Outpost {
+            name: "Frostpeak Outpost",
+            home: LocationId::Outpost,
+            dungeon: LocationId::DeepMine,
+            field: None,
+            map_position: Vec2::new(24.0, 18.0),
+            storage_slots: Some(10),
+            merchant_pool: vec![
+                (ItemId::BasicHPPotion, 5..=12),
+                (ItemId::CopperPickaxe, 1..=1),
+                (ItemId::IronSword, 1..=1),
+                (ItemId::IronHelmet, 1..=1),
+                (ItemId::IronChestplate, 1..=1),
+                (ItemId::IronGauntlets, 1..=1),
+                (ItemId::IronGreaves, 1..=1),
+                (ItemId::IronLeggings, 1..=1),
+                (ItemId::IronOre, 15..=30),
+                (ItemId::GoldOre, 10..=20),
+                (ItemId::Coal, 15..=30),
+                (ItemId::IronIngot, 4..=8),
+                (ItemId::GoldIngot, 3..=6),
+                (ItemId::CopperIngot, 3..=6),
+            ],
+        }
+    }
+}
+
+impl TownId {
+    /// The town whose home, dungeon or field is `location`.
+    pub fn for_location(location: LocationId) -> Option<TownId> {
+        TownId::ALL.iter().copied().find(|town| {
+            let spec = town.spec();
+            spec.home == location || spec.dungeon == location || spec.field == Some(location)
+        })
+    }
+
+    /// Localization key for the town's display name.
+    pub fn name_key(self) -> &'static str {
+        match self {
+            TownId::Village => "town.village",
+            TownId::Outpost => "town.outpost",
+        }
+    }
+}
+
+/// The town the player currently lives in.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentTown(pub TownId);
+
+impl Default for CurrentTown {
+    fn default() -> Self {
+        Self(TownId::Village)
+    }
+}
+
+/// Where the player is: at the current town's home location, or out in its dungeon or
+/// fields.
+#[derive(SystemParam)]
+pub struct PlayerLocation<'w> {
+    dungeon: Res<'w, DungeonState>,
+    town: Res<'w, CurrentTown>,
+}
+
+impl PlayerLocation<'_> {
+    pub fn town(&self) -> TownId {
+        self.town.0
+    }
+
+    pub fn in_town(&self) -> bool {
+        self.dungeon.current_location == Some(self.town.0.spec().home)
+    }
+}
+
+/// The road between two towns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TravelRoute {
+    pub from: TownId,
+    pub to: TownId,
+    /// Straight-line distance in leagues.
+    pub distance: f32,
+}
+
+impl TravelRoute {
+    pub fn new(from: TownId, to: TownId) -> Self {
+        Self {
+            from,
+            to,
+            distance: from.spec().map_position.distance(to.spec().map_position),
+        }
+    }
+
+    pub fn gold_cost(&self) -> i32 {
+        (self.distance * GOLD_PER_LEAGUE as f32).round() as i32
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(self.distance * SECONDS_PER_LEAGUE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn towns_are_found_by_home_dungeon_or_field() {
+        assert_eq!(TownId::for_location(LocationId::Home), Some(TownId::Village));
+        assert_eq!(TownId::for_location(LocationId::DeepMine), Some(TownId::Outpost));
+        assert_eq!(TownId::for_location(LocationId::VillageField), Some(TownId::Village));
+        assert_eq!(TownId::for_location(LocationId::VillageStore), None);
+    }
+
+    #[test]
+    fn routes_are_symmetric_and_scale_with_distance() {
+        let there = TravelRoute::new(TownId::Village, TownId::Outpost);
+        let back = TravelRoute::new(TownId::Outpost, TownId::Village);
+
+        assert_eq!(there.distance, 30.0);
+        assert_eq!(there.gold_cost(), back.gold_cost());
+        assert_eq!(there.gold_cost(), 90);
+        assert_eq!(there.duration(), Duration::from_secs_f32(7.5));
+    }
+
+    #[test]
+    fn staying_put_is_free() {
+        let route = TravelRoute::new(TownId::Village, TownId::Village);
+        assert_eq!(route.gold_cost(), 0);
+        assert_eq!(route.duration(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,190 @@
+//! Town buildings the player pays to improve. Each town keeps its own levels: the
+//! blacksmith's unlocks higher-tier forging recipes, the store's adds stock slots, and the
+//! alchemist's unlocks new potions.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CharacterResource;
+use crate::item::recipe::enums::RecipeType;
+use crate::item::recipe::RecipeId;
+use crate::item::ItemId;
+
+use super::TownId;
+
+/// Where building levels are saved in each character's slot.
+pub const TOWN_SAVE_PATH: &str = "town.ron";
+
+pub const MAX_BUILDING_LEVEL: u32 = 2;
+
+/// Extra items the town merchant stocks per store level.
+pub const STOCK_SLOTS_PER_LEVEL: usize = 3;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TownBuilding {
+    Blacksmith,
+    Store,
+    Alchemist,
+}
+
+impl TownBuilding {
+    pub const ALL: [TownBuilding; 3] = [
+        TownBuilding::Blacksmith,
+        TownBuilding::Store,
+        TownBuilding::Alchemist,
+    ];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            TownBuilding::Blacksmith => "town_upgrades.blacksmith",
+            TownBuilding::Store => "town_upgrades.store",
+            TownBuilding::Alchemist => "town_upgrades.alchemist",
+        }
+    }
+
+    /// The building whose level gates `recipe`, if any. Smelting is never gated.
+    pub fn for_recipe(recipe: RecipeId) -> Option<TownBuilding> {
+        match recipe.spec().recipe_type {
+            RecipeType::Forging => Some(TownBuilding::Blacksmith),
+            RecipeType::Alchemy => Some(TownBuilding::Alchemist),
+            RecipeType::Smelting => None,
+        }
+    }
+
+    /// Recipes this building unlocks on reaching `level`.
+    pub fn recipes_unlocked_at(self, level: u32) -> impl Iterator<Item = RecipeId> {
+        RecipeId::ALL.iter().copied().filter(move |&recipe| {
+            recipe.tier() == level && TownBuilding::for_recipe(recipe) == Some(self)
+        })
+    }
+
+    /// Gold and materials that take this building from `level` to the next.
+    pub fn upgrade_cost(self, level: u32) -> Option<BuildingCost> {
+        if level >= MAX_BUILDING_LEVEL {
+            return None;
+        }
+        let step = level as i32 + 1;
+        let (gold, material, per_level) = match self {
+            TownBuilding::Blacksmith => (300, ItemId::CopperIngot, 10),
+            TownBuilding::Store => (200, ItemId::Cowhide, 5),
+            TownBuilding::Alchemist => (250, ItemId::Herb, 6),
+        };
+        Some(BuildingCost {
+            gold: gold * step,
+            materials: vec![(material, per_level * step as u32)],
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildingCost {
+    pub gold: i32,
+    pub materials: Vec<(ItemId, u32)>,
+}
+
+/// Building levels in every town.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TownState {
+    levels: HashMap<(TownId, TownBuilding), u32>,
+}
+
+impl CharacterResource for TownState {
+    const FILE: &'static str = TOWN_SAVE_PATH;
+}
+
+impl TownState {
+    pub fn level(&self, town: TownId, building: TownBuilding) -> u32 {
+        self.levels.get(&(town, building)).copied().unwrap_or(0)
+    }
+
+    pub fn upgrade_cost(&self, town: TownId, building: TownBuilding) -> Option<BuildingCost> {
+        building.upgrade_cost(self.level(town, building))
+    }
+
+    pub fn upgrade(&mut self, town: TownId, building: TownBuilding) {
+        let level = self.levels.entry((town, building)).or_insert(0);
+        *level = (*level + 1).min(MAX_BUILDING_LEVEL);
+    }
+
+    /// Whether `town`'s buildings are good enough to make `recipe`.
+    pub fn recipe_unlocked(&self, town: TownId, recipe: RecipeId) -> bool {
+        TownBuilding::for_recipe(recipe)
+            .is_none_or(|building| recipe.tier() <= self.level(town, building))
+    }
+
+    /// Extra items `town`'s merchant stocks.
+    pub fn extra_stock_slots(&self, town: TownId) -> usize {
+        self.level(town, TownBuilding::Store) as usize * STOCK_SLOTS_PER_LEVEL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn blacksmith_levels_unlock_forging_tiers() {
+        let mut town = TownState::default();
+        let village = TownId::Village;
+        assert!(town.recipe_unlocked(village, RecipeId::CopperSword));
+        assert!(!town.recipe_unlocked(village, RecipeId::IronSword));
+        // Smelting is never gated.
+        assert!(town.recipe_unlocked(village, RecipeId::GoldIngot));
+
+        town.upgrade(village, TownBuilding::Blacksmith);
+        assert!(town.recipe_unlocked(village, RecipeId::IronSword));
+        assert!(!town.recipe_unlocked(village, RecipeId::GoldSword));
+        // Other towns and buildings keep their own levels.
+        assert!(!town.recipe_unlocked(TownId::Outpost, RecipeId::IronSword));
+        assert!(!town.recipe_unlocked(village, RecipeId::HerbalHPPotion));
+    }
+
+    #[test]
+    fn each_level_lists_what_it_unlocks() {
+        let alchemy: Vec<_> = TownBuilding::Alchemist.recipes_unlocked_at(2).collect();
+        assert_eq!(alchemy, vec![RecipeId::FireFlask]);
+        assert!(TownBuilding::Blacksmith
+            .recipes_unlocked_at(1)
+            .any(|recipe| recipe == RecipeId::IronSword));
+        assert_eq!(TownBuilding::Store.recipes_unlocked_at(1).count(), 0);
+    }
+
+    #[test]
+    fn upgrades_stop_at_the_max_level() {
+        let mut town = TownState::default();
+        for _ in 0..5 {
+            town.upgrade(TownId::Village, TownBuilding::Store);
+        }
+        assert_eq!(
+            town.level(TownId::Village, TownBuilding::Store),
+            MAX_BUILDING_LEVEL
+        );
+        assert_eq!(
+            town.upgrade_cost(TownId::Village, TownBuilding::Store),
+            None
+        );
+        assert_eq!(
+            town.extra_stock_slots(TownId::Village),
+            MAX_BUILDING_LEVEL as usize * STOCK_SLOTS_PER_LEVEL
+        );
+    }
+
+    #[test]
+    fn costs_grow_with_each_level() {
+        let first = TownBuilding::Alchemist.upgrade_cost(0).unwrap();
+        let second = TownBuilding::Alchemist.upgrade_cost(1).unwrap();
+        assert_eq!(second.gold, first.gold * 2);
+        assert_eq!(second.materials[0].1, first.materials[0].1 * 2);
+    }
+
+    #[test]
+    fn saves_round_trip() {
+        let mut town = TownState::default();
+        town.upgrade(TownId::Outpost, TownBuilding::Blacksmith);
+        let ron = to_ron(&town).unwrap();
+        assert_eq!(from_ron::<TownState>(&ron).unwrap(), town);
+    }
+}
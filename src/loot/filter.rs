@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::inventory::ItemCategory;
+use crate::item::enums::{ItemQuality, ItemType};
+use crate::item::Item;
+
+/// Where the loot filter is saved, relative to the save directory.
+pub const LOOT_FILTER_SAVE_PATH: &str = "loot_filter.ron";
+
+/// Categories the filter has a rule for. Quest items are always picked up.
+pub const FILTERED_CATEGORIES: [ItemCategory; 3] = [
+    ItemCategory::Equipment,
+    ItemCategory::Materials,
+    ItemCategory::Consumables,
+];
+
+/// What happens to a drop. Ordered from most to least wanted, so when an item's category
+/// and quality rules disagree the stricter one wins.
+#[derive(
+    Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum LootRule {
+    #[default]
+    Pickup,
+    /// Converted straight to gold at the merchant's sell price.
+    Sell,
+    Ignore,
+}
+
+impl LootRule {
+    pub const ALL: [LootRule; 3] = [LootRule::Pickup, LootRule::Sell, LootRule::Ignore];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|r| *r == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let index = Self::ALL.iter().position(|r| *r == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the rule's label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            LootRule::Pickup => "loot_filter.rule_pickup",
+            LootRule::Sell => "loot_filter.rule_sell",
+            LootRule::Ignore => "loot_filter.rule_ignore",
+        }
+    }
+}
+
+/// The player's auto-pickup rules for dungeon drops, plus what the store treats as junk.
+/// Drops without a rule are picked up.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootFilter {
+    #[serde(default)]
+    categories: HashMap<ItemCategory, LootRule>,
+    #[serde(default)]
+    qualities: HashMap<ItemQuality, LootRule>,
+    /// Unlocked items below this quality are sold by the store's sell-all-junk action.
+    #[serde(default = "default_junk_threshold")]
+    junk_threshold: ItemQuality,
+}
+
+impl Default for LootFilter {
+    fn default() -> Self {
+        Self {
+            categories: HashMap::new(),
+            qualities: HashMap::new(),
+            junk_threshold: default_junk_threshold(),
+        }
+    }
+}
+
+fn default_junk_threshold() -> ItemQuality {
+    ItemQuality::Normal
+}
+
+impl LootFilter {
+    pub fn junk_threshold(&self) -> ItemQuality {
+        self.junk_threshold
+    }
+
+    pub fn set_junk_threshold(&mut self, threshold: ItemQuality) {
+        self.junk_threshold = threshold;
+    }
+
+    pub fn category_rule(&self, category: ItemCategory) -> LootRule {
+        self.categories.get(&category).copied().unwrap_or_default()
+    }
+
+    pub fn quality_rule(&self, quality: ItemQuality) -> LootRule {
+        self.qualities.get(&quality).copied().unwrap_or_default()
+    }
+
+    pub fn set_category_rule(&mut self, category: ItemCategory, rule: LootRule) {
+        self.categories.insert(category, rule);
+    }
+
+    pub fn set_quality_rule(&mut self, quality: ItemQuality, rule: LootRule) {
+        self.qualities.insert(quality, rule);
+    }
+
+    /// The rule for `item`: the stricter of its category's and its quality's.
+    pub fn rule_for(&self, item: &Item) -> LootRule {
+        if item.item_type == ItemType::QuestItem {
+            return LootRule::Pickup;
+        }
+
+        let category = FILTERED_CATEGORIES
+            .into_iter()
+            .find(|category| category.contains(item.item_type))
+            .map_or(LootRule::Pickup, |category| self.category_rule(category));
+        category.max(self.quality_rule(item.quality))
+    }
+}
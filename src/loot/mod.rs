@@ -1,10 +1,14 @@
 mod collection;
 pub(crate) mod definition;
 pub(crate) mod enums;
+mod filter;
+mod plugin;
 pub(crate) mod traits;
 #[cfg(test)]
 mod tests;
 
 pub use collection::collect_loot_drops;
 pub(crate) use definition::{LootDrop, LootTable};
+pub use filter::{LootFilter, LootRule, FILTERED_CATEGORIES, LOOT_FILTER_SAVE_PATH};
+pub use plugin::LootFilterPlugin;
 pub(crate) use traits::HasLoot;
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::save::{load_ron, save_ron};
+
+use super::filter::{LootFilter, LOOT_FILTER_SAVE_PATH};
+
+pub struct LootFilterPlugin;
+
+impl Plugin for LootFilterPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<LootFilter>(LOOT_FILTER_SAVE_PATH))
+            .add_systems(
+                Update,
+                save_loot_filter
+                    .run_if(resource_changed::<LootFilter>.and(not(resource_added::<LootFilter>))),
+            );
+    }
+}
+
+fn save_loot_filter(filter: Res<LootFilter>) {
+    if let Err(e) = save_ron(LOOT_FILTER_SAVE_PATH, &*filter) {
+        warn!("{e}");
+    }
+}
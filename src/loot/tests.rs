@@ -1,12 +1,10 @@
 #[cfg(test)]
 use uuid::Uuid;
 
-#[cfg(test)]
-use crate::assets::SpriteSheetKey;
 #[cfg(test)]
 use crate::item::enums::{ItemQuality, ItemType, MaterialType};
 #[cfg(test)]
-use crate::item::{Item, ItemId, SpriteInfo};
+use crate::item::{Item, ItemId, ItemSheet, SpriteInfo};
 #[cfg(test)]
 use crate::stats::StatSheet;
 #[cfg(test)]
@@ -18,6 +16,10 @@ use crate::data::StatRange;
 use super::definition::{LootItem, LootTable};
 #[cfg(test)]
 use super::enums::LootError;
+#[cfg(test)]
+use super::filter::{LootFilter, LootRule};
+#[cfg(test)]
+use crate::inventory::ItemCategory;
 
 
 #[cfg(test)]
@@ -29,6 +31,7 @@ fn create_test_material(id: ItemId, gold_value: i32) -> Item {
         name: "Test Material".to_string(),
         is_equipped: false,
         is_locked: false,
+        is_favorite: false,
         num_upgrades: 0,
         max_upgrades: 0,
         max_stack_quantity: 99,
@@ -36,7 +39,7 @@ fn create_test_material(id: ItemId, gold_value: i32) -> Item {
         stats: StatSheet::new(),
         gold_value,
         quality: ItemQuality::Normal,
-        sprite: SpriteInfo { name: String::new(), sheet_key: SpriteSheetKey::IconItems },
+        sprite: SpriteInfo { name: String::new(), sheet_key: ItemSheet::IconItems },
     }
 }
 
@@ -419,3 +422,35 @@ fn loot_table_roll_drops_magic_find_increases_chances() {
         drops_with_mf, drops_no_mf
     );
 }
+
+#[test]
+fn loot_filter_picks_up_everything_by_default() {
+    let filter = LootFilter::default();
+    assert_eq!(filter.rule_for(&create_test_material(ItemId::IronOre, 10)), LootRule::Pickup);
+}
+
+#[test]
+fn loot_filter_applies_the_stricter_of_category_and_quality_rules() {
+    let mut filter = LootFilter::default();
+    filter.set_category_rule(ItemCategory::Materials, LootRule::Sell);
+    filter.set_quality_rule(ItemQuality::Poor, LootRule::Ignore);
+
+    let mut ore = create_test_material(ItemId::IronOre, 10);
+    assert_eq!(filter.rule_for(&ore), LootRule::Sell);
+
+    ore.quality = ItemQuality::Poor;
+    assert_eq!(filter.rule_for(&ore), LootRule::Ignore);
+
+    ore.item_type = ItemType::QuestItem;
+    assert_eq!(filter.rule_for(&ore), LootRule::Pickup);
+}
+
+#[test]
+fn loot_filter_round_trips_through_ron() {
+    let mut filter = LootFilter::default();
+    filter.set_quality_rule(ItemQuality::Normal, LootRule::Sell);
+
+    let ron = crate::save::to_ron(&filter).unwrap();
+    let restored = crate::save::from_ron::<LootFilter>(&ron).unwrap();
+    assert_eq!(restored, filter);
+}
@@ -0,0 +1,253 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use randr_core::combat::Attack;
+
+use crate::combat::{roll_hit, AttackSources, CritProfile, DamageEntity, HitLanded};
+use crate::dungeon::{Following, MobEntity};
+use crate::game::{ActiveBuffs, ShowToast};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::mob::CombatStats;
+use crate::player::PlayerMarker;
+use crate::stats::{Healable, StatSheet, StatType};
+use crate::ui::screens::ActiveModal;
+use crate::ui::{DungeonPlayer, DyingMob};
+
+use super::spell::{SpellResult, WordRegistry};
+use super::summon::SummonAlly;
+use super::tome::Tome;
+
+/// Floors a buff granted by a spell lasts.
+pub const SPELL_BUFF_FLOORS: u32 = 1;
+
+/// Seconds for one point of mana to come back.
+pub const MANA_REGEN_SECS: f32 = 2.0;
+
+/// Mobs a spell or a summon can go after: alive, and not following the player.
+pub(super) type HostileMobs = (With<MobEntity>, Without<DyingMob>, Without<Following>);
+
+/// The tome's pages and the words they are read with.
+#[derive(SystemParam)]
+pub struct Spellbook<'w> {
+    tome: Res<'w, Tome>,
+    registry: Res<'w, WordRegistry>,
+}
+
+/// Where the caster stands, the stats a cast draws mana from, and the mobs in reach.
+#[derive(SystemParam)]
+pub struct SpellReach<'w, 's> {
+    dungeon_player: Query<'w, 's, &'static Transform, With<DungeonPlayer>>,
+    player: Query<'w, 's, &'static mut StatSheet, With<PlayerMarker>>,
+    mobs: Query<'w, 's, (Entity, &'static CombatStats, &'static Transform), HostileMobs>,
+}
+
+/// What a cast can do: hurt mobs, grant buffs, and call up summons.
+#[derive(SystemParam)]
+pub struct SpellEffects<'w> {
+    buffs: ResMut<'w, ActiveBuffs>,
+    damage_writer: MessageWriter<'w, DamageEntity>,
+    hit_writer: MessageWriter<'w, HitLanded>,
+    summon_writer: MessageWriter<'w, SummonAlly>,
+}
+
+/// Casts the tome's active page. Nothing is spent when the page is unreadable, the mana
+/// is short, or a damaging spell has nothing in reach.
+pub fn cast_spell(
+    mut actions: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    spellbook: Spellbook,
+    reach: SpellReach,
+    effects: SpellEffects,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Spellbook { tome, registry } = spellbook;
+    let SpellReach {
+        dungeon_player,
+        mut player,
+        mobs,
+    } = reach;
+    let SpellEffects {
+        mut buffs,
+        mut damage_writer,
+        mut hit_writer,
+        mut summon_writer,
+    } = effects;
+    for action in actions.read() {
+        if *action != GameAction::Cast || active_modal.modal.is_some() {
+            continue;
+        }
+        let (Ok(player_transform), Ok(mut stats)) = (dungeon_player.single(), player.single_mut())
+        else {
+            return;
+        };
+        let Some(page) = tome.active_page() else {
+            toast_writer.write(ShowToast::new(loc.get("magic.empty_tome")));
+            continue;
+        };
+        let spell = match registry.compute(page) {
+            Ok(spell) => spell,
+            Err(e) => {
+                toast_writer.write(ShowToast::new(loc.format(
+                    "magic.unreadable",
+                    &[("spell", &page.name), ("reason", &loc.get(e.message_key()))],
+                )));
+                continue;
+            }
+        };
+        if stats.value(StatType::Mana) < spell.mana_cost {
+            toast_writer.write(ShowToast::new(loc.format(
+                "magic.no_mana",
+                &[
+                    ("spell", &page.name),
+                    ("mana", &spell.mana_cost.to_string()),
+                ],
+            )));
+            continue;
+        }
+
+        let origin = player_transform.translation.truncate();
+        let targets = spell.targets(
+            origin,
+            mobs.iter().map(|(entity, stats, transform)| {
+                ((entity, stats), transform.translation.truncate())
+            }),
+        );
+        if spell.damage > 0 && targets.is_empty() {
+            toast_writer.write(ShowToast::new(
+                loc.format("magic.no_target", &[("spell", &page.name)]),
+            ));
+            continue;
+        }
+
+        stats.decrease_stat(StatType::Mana, spell.mana_cost);
+        let mut result = SpellResult::default();
+        let sources = AttackSources::flat(spell.damage);
+        let attack = Attack::new(spell.damage, spell.damage);
+        for (target, target_stats) in targets {
+            // Spells land for their exact damage and never crit.
+            let breakdown = roll_hit(
+                sources,
+                &attack,
+                CritProfile::default(),
+                target_stats.defense,
+                &mut rand::thread_rng(),
+            );
+            let damage = breakdown.damage;
+            damage_writer.write(DamageEntity {
+                target,
+                amount: damage,
+            });
+            hit_writer.write(HitLanded {
+                target,
+                damage,
+                breakdown,
+            });
+            result.hits += 1;
+            result.damage += damage;
+        }
+        if spell.heal > 0 {
+            result.healed = stats.heal(spell.heal);
+        }
+        for &kind in &spell.buffs {
+            buffs.grant(kind, SPELL_BUFF_FLOORS, &mut stats);
+            result.buffs.push(kind);
+        }
+        for &mob_id in &spell.summons {
+            summon_writer.write(SummonAlly { mob_id, at: origin });
+            result.summoned += 1;
+        }
+
+        toast_writer.write(ShowToast::new(describe(&loc, &page.name, &result)));
+    }
+}
+
+/// One toast line summing up what a cast did.
+fn describe(loc: &Localization, spell: &str, result: &SpellResult) -> String {
+    let mut parts = Vec::new();
+    if result.hits > 0 {
+        parts.push(loc.format(
+            "magic.result_damage",
+            &[
+                ("hits", &result.hits.to_string()),
+                ("damage", &result.damage.to_string()),
+            ],
+        ));
+    }
+    if result.healed > 0 {
+        parts.push(loc.format(
+            "magic.result_heal",
+            &[("healed", &result.healed.to_string())],
+        ));
+    }
+    parts.extend(
+        result
+            .buffs
+            .iter()
+            .map(|kind| loc.get(kind.label_key()).to_string()),
+    );
+    if result.summoned > 0 {
+        parts.push(loc.format(
+            "magic.result_summon",
+            &[("count", &result.summoned.to_string())],
+        ));
+    }
+    loc.format(
+        "magic.cast",
+        &[("spell", &spell), ("result", &parts.join(", "))],
+    )
+}
+
+/// Readies the tome's next page.
+pub fn cycle_spell_page(
+    mut actions: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    mut tome: ResMut<Tome>,
+    registry: Res<WordRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in actions.read() {
+        if *action != GameAction::NextSpellPage || active_modal.modal.is_some() {
+            continue;
+        }
+        let Some(page) = tome.next_page() else {
+            toast_writer.write(ShowToast::new(loc.get("magic.empty_tome")));
+            continue;
+        };
+        let message = match registry.compute(page) {
+            Ok(spell) => loc.format(
+                "magic.page_ready",
+                &[
+                    ("spell", &page.name),
+                    ("mana", &spell.mana_cost.to_string()),
+                ],
+            ),
+            Err(e) => loc.format(
+                "magic.unreadable",
+                &[("spell", &page.name), ("reason", &loc.get(e.message_key()))],
+            ),
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+/// Mana trickles back while in the dungeon.
+pub fn regenerate_mana(
+    time: Res<Time>,
+    mut since_regen: Local<f32>,
+    mut player: Query<&mut StatSheet, With<PlayerMarker>>,
+) {
+    *since_regen += time.delta_secs();
+    if *since_regen < MANA_REGEN_SECS {
+        return;
+    }
+    *since_regen -= MANA_REGEN_SECS;
+
+    let Ok(mut stats) = player.single_mut() else {
+        return;
+    };
+    if stats.value(StatType::Mana) < stats.max_value(StatType::Mana) {
+        stats.increase_stat(StatType::Mana, 1);
+    }
+}
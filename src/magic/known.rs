@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::word::WordId;
+
+/// Where learned words are saved. They outlast runs, like the trophy case.
+pub const KNOWN_WORDS_SAVE_PATH: &str = "known_words.ron";
+
+/// Every word the player can write with: the starting words plus those learned from
+/// scrolls.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownWords {
+    #[serde(deserialize_with = "with_starting_words")]
+    words: BTreeSet<WordId>,
+}
+
+/// Saved words plus the starting ones, so a save never loses them.
+fn with_starting_words<'de, D>(deserializer: D) -> Result<BTreeSet<WordId>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let mut words = BTreeSet::deserialize(deserializer)?;
+    words.extend(WordId::STARTING);
+    Ok(words)
+}
+
+impl Default for KnownWords {
+    fn default() -> Self {
+        Self {
+            words: WordId::STARTING.into_iter().collect(),
+        }
+    }
+}
+
+impl KnownWords {
+    pub fn knows(&self, word: WordId) -> bool {
+        self.words.contains(&word)
+    }
+
+    /// Learns `word`. Returns false if it was already known.
+    pub fn learn(&mut self, word: WordId) -> bool {
+        self.words.insert(word)
+    }
+
+    /// Known words in `WordId::ALL` order.
+    pub fn words(&self) -> Vec<WordId> {
+        WordId::ALL
+            .into_iter()
+            .filter(|word| self.knows(*word))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn starts_with_the_starting_words() {
+        let mut known = KnownWords::default();
+
+        assert_eq!(
+            known.words(),
+            [WordId::Bolt, WordId::Aura, WordId::Fire, WordId::Life]
+        );
+        assert!(known.learn(WordId::Frost));
+        assert!(!known.learn(WordId::Frost));
+        assert!(known.knows(WordId::Frost));
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let mut known = KnownWords::default();
+        known.learn(WordId::Amplify);
+
+        let ron = to_ron(&known).unwrap();
+        assert_eq!(from_ron::<KnownWords>(&ron).unwrap(), known);
+        assert_eq!(
+            from_ron::<KnownWords>("(words: [])").unwrap(),
+            KnownWords::default()
+        );
+    }
+}
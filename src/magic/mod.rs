@@ -0,0 +1,24 @@
+//! Spells written as pages of magic words in the player's tome, cast with mana.
+
+pub mod cast;
+pub mod known;
+pub mod plugin;
+pub mod scroll;
+pub mod spell;
+pub mod summon;
+pub mod tome;
+pub mod word;
+
+pub use cast::{MANA_REGEN_SECS, SPELL_BUFF_FLOORS};
+pub use known::{KnownWords, KNOWN_WORDS_SAVE_PATH};
+pub use plugin::MagicPlugin;
+pub use scroll::{roll_word_scroll, scroll_depth, scroll_weights, SCROLL_DROP_ODDS};
+pub use spell::{
+    ComputedSpell, Page, SpellError, SpellResult, WordRegistry, BOLT_RANGE, MAX_PAGE_WORDS,
+    NOVA_RADIUS,
+};
+pub use summon::{
+    SummonAlly, Summoned, MAX_SUMMONS, SUMMON_REACH, SUMMON_SECS, SUMMON_SIGHT, SUMMON_STRIKE_SECS,
+};
+pub use tome::Tome;
+pub use word::{SpellForm, WordEffect, WordId, WordSpec};
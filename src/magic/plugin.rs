@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::dungeon::systems::{follow_player, TransitionInProgress};
+use crate::game::ItemFound;
+use crate::input::GameAction;
+use crate::save::{load_ron, save_ron};
+use crate::states::AppState;
+
+use super::cast::{cast_spell, cycle_spell_page, regenerate_mana};
+use super::known::{KnownWords, KNOWN_WORDS_SAVE_PATH};
+use super::scroll::learn_words_from_scrolls;
+use super::spell::WordRegistry;
+use super::summon::{
+    expire_summons, hunt_with_summons, spawn_summons, summons_strike, SummonAlly, Summoned,
+};
+use super::tome::Tome;
+
+pub struct MagicPlugin;
+
+impl Plugin for MagicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WordRegistry>()
+            .init_resource::<Tome>()
+            .insert_resource(load_ron::<KnownWords>(KNOWN_WORDS_SAVE_PATH))
+            .add_message::<SummonAlly>()
+            .add_systems(
+                Update,
+                (
+                    (cast_spell, cycle_spell_page).run_if(on_message::<GameAction>),
+                    regenerate_mana,
+                    spawn_summons
+                        .after(cast_spell)
+                        .run_if(on_message::<SummonAlly>),
+                    (summons_strike, expire_summons)
+                        .chain()
+                        .run_if(any_with_component::<Summoned>),
+                )
+                    .run_if(not(resource_exists::<TransitionInProgress>))
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                hunt_with_summons
+                    .after(follow_player)
+                    .run_if(any_with_component::<Summoned>)
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                Update,
+                (
+                    learn_words_from_scrolls.run_if(on_message::<ItemFound>),
+                    save_known_words.run_if(
+                        resource_changed::<KnownWords>.and(not(resource_added::<KnownWords>)),
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn save_known_words(known: Res<KnownWords>) {
+    if let Err(e) = save_ron(KNOWN_WORDS_SAVE_PATH, &*known) {
+        warn!("{e}");
+    }
+}
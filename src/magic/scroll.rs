@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::game::{ItemFound, ShowToast};
+use crate::i18n::Localization;
+
+use super::known::KnownWords;
+use super::word::WordId;
+
+/// One kill in this many drops a word scroll, on floors deep enough to have any.
+pub const SCROLL_DROP_ODDS: u32 = 30;
+
+/// The shallowest floor each word's scroll drops on. Rarer words sit deeper.
+const SCROLL_DEPTHS: [(WordId, u32); 6] = [
+    (WordId::Frost, 1),
+    (WordId::Might, 2),
+    (WordId::Nova, 3),
+    (WordId::Stone, 3),
+    (WordId::Summon, 4),
+    (WordId::Amplify, 5),
+];
+
+/// The shallowest floor `word`'s scroll drops on, or `None` for starting words.
+pub fn scroll_depth(word: WordId) -> Option<u32> {
+    SCROLL_DEPTHS
+        .into_iter()
+        .find_map(|(scroll_word, depth)| (scroll_word == word).then_some(depth))
+}
+
+/// The words whose scrolls can drop at `depth`. Each is weighted by how far past its
+/// shallowest floor the player is, so a word stays uncommon just below where it first drops.
+pub fn scroll_weights(depth: u32) -> Vec<(WordId, u32)> {
+    SCROLL_DEPTHS
+        .into_iter()
+        .filter(|&(_, min_depth)| depth >= min_depth)
+        .map(|(word, min_depth)| (word, (depth - min_depth).min(3) + 1))
+        .collect()
+}
+
+/// Rolls for a word scroll from a kill at `depth`.
+pub fn roll_word_scroll(depth: u32, rng: &mut impl Rng) -> Option<WordId> {
+    let weights = scroll_weights(depth);
+    let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+    if total == 0 || rng.gen_range(0..SCROLL_DROP_ODDS) != 0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0..total);
+    weights.into_iter().find_map(|(word, weight)| {
+        if roll < weight {
+            Some(word)
+        } else {
+            roll -= weight;
+            None
+        }
+    })
+}
+
+/// Finding a scroll teaches its word.
+pub fn learn_words_from_scrolls(
+    mut found: MessageReader<ItemFound>,
+    mut known: ResMut<KnownWords>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for event in found.read() {
+        let Some(word) = WordId::from_scroll(event.item_id) else {
+            continue;
+        };
+        if known.knows(word) {
+            continue;
+        }
+        known.learn(word);
+        toast_writer.write(ShowToast::new(
+            loc.format("magic.word_learned", &[("word", &loc.get(word.name_key()))]),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn deeper_floors_drop_rarer_words() {
+        assert!(scroll_weights(0).is_empty());
+        assert_eq!(scroll_depth(WordId::Amplify), Some(5));
+        assert_eq!(scroll_depth(WordId::Fire), None);
+        assert_eq!(scroll_weights(1), [(WordId::Frost, 1)]);
+        assert_eq!(
+            scroll_weights(5),
+            [
+                (WordId::Frost, 4),
+                (WordId::Might, 4),
+                (WordId::Nova, 3),
+                (WordId::Stone, 3),
+                (WordId::Summon, 2),
+                (WordId::Amplify, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn scrolls_are_rare_and_only_drop_where_allowed() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let drops: Vec<_> = (0..3000)
+            .filter_map(|_| roll_word_scroll(1, &mut rng))
+            .collect();
+
+        assert!(drops.iter().all(|word| *word == WordId::Frost));
+        assert!((50..150).contains(&drops.len()), "{}", drops.len());
+        assert!((0..100).all(|_| roll_word_scroll(0, &mut rng).is_none()));
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::BuffKind;
+use crate::mob::MobId;
+
+use super::word::{SpellForm, WordEffect, WordId, WordSpec};
+
+/// Most words a page holds.
+pub const MAX_PAGE_WORDS: usize = 4;
+
+/// How far from the caster, in pixels, a bolt reaches.
+pub const BOLT_RANGE: f32 = 192.0;
+
+/// How far from the caster, in pixels, a nova spreads.
+pub const NOVA_RADIUS: f32 = 96.0;
+
+/// A named spell written into a tome, as the words that make it up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    pub name: String,
+    pub words: Vec<WordId>,
+}
+
+impl Page {
+    pub fn new(name: impl Into<String>, words: impl Into<Vec<WordId>>) -> Self {
+        Self {
+            name: name.into(),
+            words: words.into(),
+        }
+    }
+}
+
+/// Why a page does not make a spell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SpellError {
+    #[error("The page is empty")]
+    EmptyPage,
+    #[error("A page holds at most {0} words")]
+    TooManyWords(usize),
+    #[error("The page needs a form word")]
+    MissingForm,
+    #[error("A page can only have one form word")]
+    ExtraForm,
+    #[error("The page has no effect words")]
+    NoEffect,
+    #[error("An aura cannot deal damage")]
+    SelfDamage,
+}
+
+impl SpellError {
+    /// Localization key for the message shown to the player.
+    pub fn message_key(self) -> &'static str {
+        match self {
+            SpellError::EmptyPage => "spell_error.empty_page",
+            SpellError::TooManyWords(_) => "spell_error.too_many_words",
+            SpellError::MissingForm => "spell_error.missing_form",
+            SpellError::ExtraForm => "spell_error.extra_form",
+            SpellError::NoEffect => "spell_error.no_effect",
+            SpellError::SelfDamage => "spell_error.self_damage",
+        }
+    }
+}
+
+/// The numbers a page works out to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComputedSpell {
+    pub form: SpellForm,
+    /// Damage to each target, before their defense.
+    pub damage: i32,
+    /// Health restored to the caster.
+    pub heal: i32,
+    /// Buffs granted to the caster.
+    pub buffs: Vec<BuffKind>,
+    /// Allies called up beside the caster, one per summoning word.
+    pub summons: Vec<MobId>,
+    pub mana_cost: i32,
+}
+
+impl ComputedSpell {
+    /// The targets this spell strikes from `origin`, out of `candidates` and their positions.
+    /// Auras strike nothing.
+    pub fn targets<T: Copy>(
+        &self,
+        origin: Vec2,
+        candidates: impl IntoIterator<Item = (T, Vec2)>,
+    ) -> Vec<T> {
+        let in_reach = |radius: f32| {
+            candidates
+                .into_iter()
+                .map(move |(target, at)| (target, origin.distance(at)))
+                .filter(move |&(_, distance)| distance <= radius)
+        };
+        match self.form {
+            SpellForm::Bolt => in_reach(BOLT_RANGE)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(target, _)| target)
+                .into_iter()
+                .collect(),
+            SpellForm::Nova => in_reach(NOVA_RADIUS).map(|(target, _)| target).collect(),
+            SpellForm::Aura => Vec::new(),
+        }
+    }
+}
+
+/// What a cast actually did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpellResult {
+    pub hits: usize,
+    /// Damage dealt across every target, after defense.
+    pub damage: i32,
+    pub healed: i32,
+    pub buffs: Vec<BuffKind>,
+    pub summoned: usize,
+}
+
+/// How every word behaves. Pages are checked and worked out against this.
+#[derive(Resource, Debug, Clone)]
+pub struct WordRegistry {
+    specs: HashMap<WordId, WordSpec>,
+}
+
+impl Default for WordRegistry {
+    fn default() -> Self {
+        Self {
+            specs: WordId::ALL
+                .into_iter()
+                .map(|word| (word, WordSpec::builtin(word)))
+                .collect(),
+        }
+    }
+}
+
+impl WordRegistry {
+    pub fn spec(&self, word: WordId) -> WordSpec {
+        self.specs
+            .get(&word)
+            .copied()
+            .unwrap_or_else(|| WordSpec::builtin(word))
+    }
+
+    /// Works out the spell `page` makes, or why it makes none.
+    pub fn compute(&self, page: &Page) -> Result<ComputedSpell, SpellError> {
+        if page.words.is_empty() {
+            return Err(SpellError::EmptyPage);
+        }
+        if page.words.len() > MAX_PAGE_WORDS {
+            return Err(SpellError::TooManyWords(MAX_PAGE_WORDS));
+        }
+
+        let mut form = None;
+        let mut damage = 0;
+        let mut heal = 0;
+        let mut buffs = Vec::new();
+        let mut summons = Vec::new();
+        let mut amplify = 100;
+        let mut mana_cost = 0;
+        for &word in &page.words {
+            let spec = self.spec(word);
+            mana_cost += spec.mana_cost;
+            match spec.effect {
+                WordEffect::Form(_) if form.is_some() => return Err(SpellError::ExtraForm),
+                WordEffect::Form(f) => form = Some(f),
+                WordEffect::Damage(amount) => damage += amount,
+                WordEffect::Heal(amount) => heal += amount,
+                WordEffect::Buff(kind) if !buffs.contains(&kind) => buffs.push(kind),
+                WordEffect::Buff(_) => {}
+                WordEffect::Summon(mob_id) => summons.push(mob_id),
+                WordEffect::Amplify(percent) => amplify = amplify * percent / 100,
+            }
+        }
+
+        let form = form.ok_or(SpellError::MissingForm)?;
+        if damage == 0 && heal == 0 && buffs.is_empty() && summons.is_empty() {
+            return Err(SpellError::NoEffect);
+        }
+        if form == SpellForm::Aura && damage > 0 {
+            return Err(SpellError::SelfDamage);
+        }
+        Ok(ComputedSpell {
+            form,
+            damage: damage * amplify / 100,
+            heal: heal * amplify / 100,
+            buffs,
+            summons,
+            mana_cost,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compute(words: &[WordId]) -> Result<ComputedSpell, SpellError> {
+        WordRegistry::default().compute(&Page::new("test", words))
+    }
+
+    #[test]
+    fn words_add_up_into_a_spell() {
+        let spell = compute(&[WordId::Bolt, WordId::Fire, WordId::Amplify]).unwrap();
+
+        assert_eq!(spell.form, SpellForm::Bolt);
+        assert_eq!(spell.damage, 18);
+        assert_eq!(spell.mana_cost, 2 + 4 + 4);
+    }
+
+    #[test]
+    fn malformed_pages_are_rejected() {
+        assert_eq!(compute(&[]), Err(SpellError::EmptyPage));
+        assert_eq!(compute(&[WordId::Fire]), Err(SpellError::MissingForm));
+        assert_eq!(
+            compute(&[WordId::Bolt, WordId::Nova, WordId::Fire]),
+            Err(SpellError::ExtraForm)
+        );
+        assert_eq!(
+            compute(&[WordId::Nova, WordId::Amplify]),
+            Err(SpellError::NoEffect)
+        );
+        assert_eq!(
+            compute(&[WordId::Aura, WordId::Fire]),
+            Err(SpellError::SelfDamage)
+        );
+        assert_eq!(
+            compute(&[
+                WordId::Bolt,
+                WordId::Fire,
+                WordId::Fire,
+                WordId::Fire,
+                WordId::Fire
+            ]),
+            Err(SpellError::TooManyWords(MAX_PAGE_WORDS))
+        );
+    }
+
+    #[test]
+    fn bolts_strike_the_closest_and_novas_everything_near() {
+        let mobs = [
+            (1, Vec2::new(50.0, 0.0)),
+            (2, Vec2::new(20.0, 0.0)),
+            (3, Vec2::new(500.0, 0.0)),
+        ];
+        let bolt = compute(&[WordId::Bolt, WordId::Frost]).unwrap();
+        let nova = compute(&[WordId::Nova, WordId::Frost]).unwrap();
+        let aura = compute(&[WordId::Aura, WordId::Life]).unwrap();
+
+        assert_eq!(bolt.targets(Vec2::ZERO, mobs), [2]);
+        assert_eq!(nova.targets(Vec2::ZERO, mobs), [1, 2]);
+        assert!(aura.targets(Vec2::ZERO, mobs).is_empty());
+    }
+
+    #[test]
+    fn summoning_words_call_up_one_ally_each() {
+        let spell = compute(&[WordId::Aura, WordId::Summon, WordId::Summon]).unwrap();
+
+        assert_eq!(spell.summons, [MobId::DwarfWarrior, MobId::DwarfWarrior]);
+        assert_eq!(spell.mana_cost, 1 + 8 + 8);
+        assert!(compute(&[WordId::Bolt, WordId::Summon]).is_ok());
+    }
+}
@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat::{apply_defense, DamageEntity};
+use crate::dungeon::constants::DEFAULT_TILE_SIZE;
+use crate::dungeon::physics::mob_layers;
+use crate::dungeon::systems::follow_heading;
+use crate::dungeon::{DepthSorting, Following, MovementConfig, TileWorldSize, MOB_COLLIDER};
+use crate::mob::{CombatStats, MobId};
+use crate::ui::screens::FloorRoot;
+use crate::ui::AseMobSheets;
+
+use super::cast::HostileMobs;
+
+/// Most allies that can be summoned at once. Summoning past the cap dismisses the oldest.
+pub const MAX_SUMMONS: usize = 2;
+
+/// Seconds a summoned ally stays before fading.
+pub const SUMMON_SECS: f32 = 30.0;
+
+/// How far, in pixels, a summoned ally spots a mob and goes after it.
+pub const SUMMON_SIGHT: f32 = 160.0;
+
+/// How close, in pixels, a summoned ally has to be to strike.
+pub const SUMMON_REACH: f32 = 40.0;
+
+/// Seconds between a summoned ally's strikes.
+pub const SUMMON_STRIKE_SECS: f32 = 1.5;
+
+/// Tint that sets an ally apart from the mobs it fights.
+const SUMMON_TINT: Color = Color::srgb(0.6, 0.8, 1.0);
+
+/// Asks for an allied `mob_id` to appear at `at`.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct SummonAlly {
+    pub mob_id: MobId,
+    pub at: Vec2,
+}
+
+/// A mob fighting for the player until its time runs out. It follows the player like a
+/// freed captive and leaves with the floor.
+#[derive(Component, Debug)]
+#[require(RigidBody::Kinematic, CollisionLayers = mob_layers(), Following)]
+pub struct Summoned {
+    pub mob_id: MobId,
+    pub lifetime: Timer,
+    pub strike: Timer,
+}
+
+impl Summoned {
+    pub fn new(mob_id: MobId) -> Self {
+        Self {
+            mob_id,
+            lifetime: Timer::from_seconds(SUMMON_SECS, TimerMode::Once),
+            strike: Timer::from_seconds(SUMMON_STRIKE_SECS, TimerMode::Once),
+        }
+    }
+}
+
+/// The summons to dismiss, oldest first, so `incoming` more fit under `MAX_SUMMONS`.
+/// `summons` pairs each summon with how long it has been out.
+pub fn summons_to_dismiss<T>(
+    summons: impl IntoIterator<Item = (T, Duration)>,
+    incoming: usize,
+) -> Vec<T> {
+    let mut summons: Vec<_> = summons.into_iter().collect();
+    let excess = (summons.len() + incoming).saturating_sub(MAX_SUMMONS);
+    summons.sort_by_key(|s| std::cmp::Reverse(s.1));
+    summons
+        .into_iter()
+        .take(excess)
+        .map(|(summon, _)| summon)
+        .collect()
+}
+
+/// The closest of `candidates` within `radius` of `origin`.
+fn closest_within<T>(
+    origin: Vec2,
+    radius: f32,
+    candidates: impl IntoIterator<Item = (T, Vec2)>,
+) -> Option<(T, Vec2)> {
+    candidates
+        .into_iter()
+        .filter(|(_, at)| origin.distance(*at) <= radius)
+        .min_by(|a, b| origin.distance(a.1).total_cmp(&origin.distance(b.1)))
+}
+
+pub fn spawn_summons(
+    mut commands: Commands,
+    mut requests: MessageReader<SummonAlly>,
+    summons: Query<(Entity, &Summoned)>,
+    ase_sheets: Res<AseMobSheets>,
+    depth_sorting: Option<Res<DepthSorting>>,
+    floor_root: Query<Entity, With<FloorRoot>>,
+) {
+    let requests: Vec<SummonAlly> = requests.read().copied().collect();
+    // Only the newest summons of a single cast can stay.
+    let requests = &requests[requests.len().saturating_sub(MAX_SUMMONS)..];
+    let out = summons
+        .iter()
+        .map(|(entity, summon)| (entity, summon.lifetime.elapsed()));
+    for entity in summons_to_dismiss(out, requests.len()) {
+        commands.entity(entity).despawn();
+    }
+
+    let Ok(root) = floor_root.single() else {
+        return;
+    };
+    let depth = depth_sorting.map(|d| *d).unwrap_or_default();
+    for (i, request) in requests.iter().enumerate() {
+        let Some(sheet) = ase_sheets.get(request.mob_id) else {
+            continue;
+        };
+        let frame = sheet.frame_size.as_vec2();
+        // Side by side when several arrive at once.
+        let at = request.at + Vec2::new((i as f32 - 0.5) * frame.x, 0.0);
+        #[cfg(feature = "gui")]
+        let animation = sheet.idle_animation();
+        #[cfg(not(feature = "gui"))]
+        let animation = ();
+        commands.spawn((
+            Summoned::new(request.mob_id),
+            animation,
+            Sprite {
+                color: SUMMON_TINT,
+                ..default()
+            },
+            Transform::from_translation(at.extend(depth.entity_z(at.y))),
+            MOB_COLLIDER.create_collider(frame),
+            ChildOf(root),
+        ));
+    }
+}
+
+/// Summons leave the player's side to close in on a mob they spot. Runs after
+/// `follow_player`, so a summon with nothing in sight keeps following.
+pub fn hunt_with_summons(
+    tile_size: Option<Res<TileWorldSize>>,
+    movement: Res<MovementConfig>,
+    mut summons: Query<(&Transform, &mut LinearVelocity), With<Summoned>>,
+    mobs: Query<&Transform, HostileMobs>,
+) {
+    let tile_size = tile_size.map(|t| t.0).unwrap_or(DEFAULT_TILE_SIZE);
+    let speed = movement.tiles_per_second * tile_size;
+    for (transform, mut velocity) in &mut summons {
+        let origin = transform.translation.truncate();
+        let positions = mobs.iter().map(|t| ((), t.translation.truncate()));
+        if let Some((_, target)) = closest_within(origin, SUMMON_SIGHT, positions) {
+            velocity.0 = follow_heading(origin, target, SUMMON_REACH * 0.75) * speed;
+        }
+    }
+}
+
+/// Each summon strikes the closest mob in reach with its own attack, then waits out its
+/// strike timer. It holds its strike until something comes in reach.
+pub fn summons_strike(
+    time: Res<Time>,
+    mut summons: Query<(&mut Summoned, &Transform)>,
+    mobs: Query<(Entity, &CombatStats, &Transform), HostileMobs>,
+    mut damage_writer: MessageWriter<DamageEntity>,
+) {
+    let mut rng = rand::thread_rng();
+    for (mut summon, transform) in &mut summons {
+        if !summon.strike.tick(time.delta()).is_finished() {
+            continue;
+        }
+        let origin = transform.translation.truncate();
+        let candidates = mobs
+            .iter()
+            .map(|(entity, stats, t)| ((entity, stats), t.translation.truncate()));
+        let Some(((target, stats), _)) = closest_within(origin, SUMMON_REACH, candidates) else {
+            continue;
+        };
+
+        let attack = summon.mob_id.spec().attack;
+        let raw = rng.gen_range(attack.start()..=attack.end().max(attack.start()));
+        damage_writer.write(DamageEntity {
+            target,
+            amount: apply_defense(raw, stats.defense),
+        });
+        summon.strike.reset();
+    }
+}
+
+/// Summons fade once their time is up.
+pub fn expire_summons(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut summons: Query<(Entity, &mut Summoned)>,
+) {
+    for (entity, mut summon) in &mut summons {
+        if summon.lifetime.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_oldest_summons_make_room_under_the_cap() {
+        let out = [
+            ("young", Duration::from_secs(2)),
+            ("old", Duration::from_secs(20)),
+        ];
+
+        assert!(summons_to_dismiss(out, 0).is_empty());
+        assert_eq!(summons_to_dismiss(out, 1), ["old"]);
+        assert_eq!(summons_to_dismiss(out, 2), ["old", "young"]);
+        assert!(summons_to_dismiss([("young", Duration::ZERO)], 1).is_empty());
+    }
+
+    #[test]
+    fn only_mobs_in_reach_are_struck() {
+        let mobs = [(1, Vec2::new(30.0, 0.0)), (2, Vec2::new(10.0, 0.0))];
+
+        assert_eq!(
+            closest_within(Vec2::ZERO, SUMMON_REACH, mobs),
+            Some(mobs[1])
+        );
+        assert_eq!(
+            closest_within(Vec2::new(500.0, 0.0), SUMMON_REACH, mobs),
+            None
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+
+use super::spell::Page;
+use super::word::WordId;
+
+/// The player's spellbook: the pages they have written and the one ready to cast.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Tome {
+    pages: Vec<Page>,
+    active: usize,
+}
+
+impl Default for Tome {
+    /// Every adventurer starts out knowing a firebolt and a mending charm.
+    fn default() -> Self {
+        Self {
+            pages: vec![
+                Page::new("Firebolt", [WordId::Bolt, WordId::Fire]),
+                Page::new("Mend", [WordId::Aura, WordId::Life]),
+            ],
+            active: 0,
+        }
+    }
+}
+
+impl Tome {
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    pub fn active_page(&self) -> Option<&Page> {
+        self.pages.get(self.active)
+    }
+
+    /// Writes `page` into the tome and readies it. A page with the same name is written over.
+    pub fn write(&mut self, page: Page) {
+        match self.pages.iter().position(|p| p.name == page.name) {
+            Some(index) => {
+                self.pages[index] = page;
+                self.active = index;
+            }
+            None => {
+                self.pages.push(page);
+                self.active = self.pages.len() - 1;
+            }
+        }
+    }
+
+    /// Readies the next page, wrapping round to the first.
+    pub fn next_page(&mut self) -> Option<&Page> {
+        if !self.pages.is_empty() {
+            self.active = (self.active + 1) % self.pages.len();
+        }
+        self.active_page()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paging_wraps_round() {
+        let mut tome = Tome::default();
+
+        assert_eq!(tome.active_page().unwrap().name, "Firebolt");
+        assert_eq!(tome.next_page().unwrap().name, "Mend");
+        assert_eq!(tome.next_page().unwrap().name, "Firebolt");
+    }
+
+    #[test]
+    fn writing_a_page_readies_it_and_replaces_its_namesake() {
+        let mut tome = Tome::default();
+
+        tome.write(Page::new("Frost Nova", [WordId::Nova, WordId::Frost]));
+        assert_eq!(tome.pages().len(), 3);
+        assert_eq!(tome.active_page().unwrap().name, "Frost Nova");
+
+        tome.write(Page::new("Firebolt", [WordId::Bolt, WordId::Fire, WordId::Amplify]));
+        assert_eq!(tome.pages().len(), 3);
+        assert_eq!(tome.active_page().unwrap().words.len(), 3);
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::BuffKind;
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// A magic word. A spell is a page of words: one form saying where it goes and one or more
+/// effects saying what it does, optionally amplified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WordId {
+    Bolt,
+    Nova,
+    Aura,
+    Fire,
+    Frost,
+    Life,
+    Might,
+    Stone,
+    Summon,
+    Amplify,
+}
+
+impl WordId {
+    pub const ALL: [WordId; 10] = [
+        WordId::Bolt,
+        WordId::Nova,
+        WordId::Aura,
+        WordId::Fire,
+        WordId::Frost,
+        WordId::Life,
+        WordId::Might,
+        WordId::Stone,
+        WordId::Summon,
+        WordId::Amplify,
+    ];
+
+    /// Words every adventurer knows from the start; the rest are learned from scrolls.
+    pub const STARTING: [WordId; 4] = [WordId::Bolt, WordId::Aura, WordId::Fire, WordId::Life];
+
+    /// The scroll that teaches this word, if it is not a starting word.
+    pub fn scroll(self) -> Option<ItemId> {
+        match self {
+            WordId::Nova => Some(ItemId::NovaScroll),
+            WordId::Frost => Some(ItemId::FrostScroll),
+            WordId::Might => Some(ItemId::MightScroll),
+            WordId::Stone => Some(ItemId::StoneScroll),
+            WordId::Summon => Some(ItemId::SummonScroll),
+            WordId::Amplify => Some(ItemId::AmplifyScroll),
+            WordId::Bolt | WordId::Aura | WordId::Fire | WordId::Life => None,
+        }
+    }
+
+    /// The word a scroll item teaches.
+    pub fn from_scroll(item: ItemId) -> Option<WordId> {
+        WordId::ALL
+            .into_iter()
+            .find(|word| word.scroll() == Some(item))
+    }
+
+    pub fn name_key(self) -> &'static str {
+        match self {
+            WordId::Bolt => "words.bolt",
+            WordId::Nova => "words.nova",
+            WordId::Aura => "words.aura",
+            WordId::Fire => "words.fire",
+            WordId::Frost => "words.frost",
+            WordId::Life => "words.life",
+            WordId::Might => "words.might",
+            WordId::Stone => "words.stone",
+            WordId::Summon => "words.summon",
+            WordId::Amplify => "words.amplify",
+        }
+    }
+}
+
+/// Where a spell goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpellForm {
+    /// The closest mob in range.
+    Bolt,
+    /// Every mob around the caster.
+    Nova,
+    /// The caster only.
+    Aura,
+}
+
+impl SpellForm {
+    pub fn name_key(self) -> &'static str {
+        match self {
+            SpellForm::Bolt => WordId::Bolt.name_key(),
+            SpellForm::Nova => WordId::Nova.name_key(),
+            SpellForm::Aura => WordId::Aura.name_key(),
+        }
+    }
+}
+
+/// What a word adds to a spell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordEffect {
+    Form(SpellForm),
+    Damage(i32),
+    Heal(i32),
+    Buff(BuffKind),
+    /// Calls up an allied mob beside the caster for a while.
+    Summon(MobId),
+    /// Multiplies every damage and heal number on the page by this many percent.
+    Amplify(i32),
+}
+
+/// How a word behaves and what it costs to speak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordSpec {
+    pub effect: WordEffect,
+    pub mana_cost: i32,
+}
+
+impl WordSpec {
+    pub(super) fn builtin(word: WordId) -> Self {
+        let (effect, mana_cost) = match word {
+            WordId::Bolt => (WordEffect::Form(SpellForm::Bolt), 2),
+            WordId::Nova => (WordEffect::Form(SpellForm::Nova), 6),
+            WordId::Aura => (WordEffect::Form(SpellForm::Aura), 1),
+            WordId::Fire => (WordEffect::Damage(12), 4),
+            WordId::Frost => (WordEffect::Damage(8), 3),
+            WordId::Life => (WordEffect::Heal(15), 5),
+            WordId::Might => (WordEffect::Buff(BuffKind::Might), 6),
+            WordId::Stone => (WordEffect::Buff(BuffKind::Warding), 6),
+            WordId::Summon => (WordEffect::Summon(MobId::DwarfWarrior), 8),
+            WordId::Amplify => (WordEffect::Amplify(150), 4),
+        };
+        Self { effect, mana_cost }
+    }
+}
@@ -8,9 +8,19 @@ use tracing_subscriber::{
     EnvFilter,
 };
 
+use game::bot::BotConfig;
 use game::plugins::GamePlugin;
 
-fn main() {
+fn main() -> AppExit {
+    let bot = match BotConfig::from_args(std::env::args().skip(1)) {
+        Ok(bot) => bot,
+        Err(e) => {
+            eprintln!("{e}");
+            return AppExit::error();
+        }
+    };
+    let headless = bot.as_ref().is_some_and(|bot| bot.headless);
+
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
@@ -32,20 +42,34 @@ fn main() {
         )
         .init();
 
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(ImagePlugin::default_nearest()) // Pixel-perfect rendering for sprites
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "R&R".into(),
-                        resolution: (1280u32, 720u32).into(),
-                        present_mode: bevy::window::PresentMode::AutoVsync,
-                        ..default()
-                    }),
-                    ..default()
-                }),
-        )
-        .add_plugins(GamePlugin)
-        .run();
+    let window = if headless {
+        // Bot runs on CI have no display; keep the app alive without a window.
+        WindowPlugin {
+            primary_window: None,
+            exit_condition: bevy::window::ExitCondition::DontExit,
+            ..default()
+        }
+    } else {
+        WindowPlugin {
+            primary_window: Some(Window {
+                title: "R&R".into(),
+                resolution: (1280u32, 720u32).into(),
+                present_mode: bevy::window::PresentMode::AutoVsync,
+                ..default()
+            }),
+            ..default()
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(ImagePlugin::default_nearest()) // Pixel-perfect rendering for sprites
+            .set(window),
+    )
+    .add_plugins(GamePlugin);
+    if let Some(bot) = bot {
+        app.insert_resource(bot);
+    }
+    app.run()
 }
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 use rand::Rng;
 
 use super::components::{CombatStats, DeathProcessed, GoldReward, Health, MobLootTable, MobMarker, XpReward};
+use super::definitions::MobSpec;
 use super::MobId;
 
 /// Bundle containing all combat-related components for a mob entity.
@@ -25,7 +26,12 @@ impl MobCombatBundle {
     /// Create a new combat bundle from a MobId.
     /// Stats are randomized within the spec's ranges.
     pub fn from_mob_id(mob_id: MobId) -> Self {
-        let spec = mob_id.spec();
+        Self::from_spec(mob_id, mob_id.spec())
+    }
+
+    /// Create a combat bundle for `mob_id` with stats rolled from `spec`, such as a
+    /// scaled copy of the mob's own spec.
+    pub fn from_spec(mob_id: MobId, spec: &MobSpec) -> Self {
         let mut rng = rand::thread_rng();
 
         let hp_min = spec.max_health.start();
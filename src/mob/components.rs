@@ -33,6 +33,10 @@ impl Health {
         self.current > 0
     }
 
+    pub fn heal(&mut self, amount: i32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
 }
 
 /// Combat stats for attack and defense calculations.
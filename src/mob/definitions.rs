@@ -5,6 +5,8 @@ use crate::data::StatRange;
 use crate::dungeon::EntitySize;
 use crate::loot::LootTable;
 
+use super::parts::PartKind;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum MobId {
     Slime,
@@ -13,7 +15,11 @@ pub enum MobId {
     DwarfWarrior,
     DwarfMiner,
     DwarfKing,
+    Colossus,
     Merchant,
+    TravelingMerchant,
+    /// A villager held on a dungeon floor, waiting to be freed and led out.
+    Captive,
 }
 
 impl MobId {
@@ -24,7 +30,10 @@ impl MobId {
         MobId::DwarfWarrior,
         MobId::DwarfMiner,
         MobId::DwarfKing,
+        MobId::Colossus,
         MobId::Merchant,
+        MobId::TravelingMerchant,
+        MobId::Captive,
     ];
 
     pub fn spec(&self) -> &'static MobSpec {
@@ -38,6 +47,18 @@ pub enum MobQuality {
     Boss,
 }
 
+/// How a mob gets around a roaming floor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum MobBehavior {
+    /// Wanders between random spots near where it spawned.
+    #[default]
+    Roam,
+    /// Walks a fixed loop around where it spawned.
+    Patrol,
+    /// Keeps its distance from the player and shoots.
+    Ranged,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MobSpriteData {
     pub aseprite_path: String,
@@ -61,6 +82,14 @@ pub struct MobSpec {
     pub loot: LootTable,
     #[serde(default)]
     pub entity_size: EntitySize,
+    /// Targetable parts, for colossal bosses.
+    #[serde(default)]
+    pub parts: Vec<PartKind>,
+    #[serde(default)]
+    pub behavior: MobBehavior,
+    /// Share of its health below which the mob runs from the player.
+    #[serde(default)]
+    pub flee_below: Option<f32>,
     pub sprite: MobSpriteData,
 }
 
@@ -77,6 +106,9 @@ impl MobSpec {
             quality: self.quality.clone(),
             loot: self.loot.clone(),
             entity_size: self.entity_size,
+            parts: self.parts.clone(),
+            behavior: self.behavior,
+            flee_below: self.flee_below,
             sprite: self.sprite.clone(),
         }
     }
@@ -93,6 +125,9 @@ impl MobSpec {
             quality: self.quality.clone(),
             loot: self.loot.clone(),
             entity_size: self.entity_size,
+            parts: self.parts.clone(),
+            behavior: self.behavior,
+            flee_below: self.flee_below,
             sprite: self.sprite.clone(),
         }
     }
@@ -109,6 +144,9 @@ impl MobSpec {
             quality,
             loot: self.loot.clone(),
             entity_size: self.entity_size,
+            parts: self.parts.clone(),
+            behavior: self.behavior,
+            flee_below: self.flee_below,
             sprite: self.sprite.clone(),
         }
     }
@@ -221,6 +259,29 @@ mod tests {
         assert_eq!(spec.dropped_xp.start(), 0);
     }
 
+    #[test]
+    fn traveling_merchant_has_zero_combat_stats() {
+        init();
+        let spec = MobId::TravelingMerchant.spec();
+        assert_eq!(spec.attack.start(), 0);
+        assert_eq!(spec.attack.end(), 0);
+        assert_eq!(spec.defense.start(), 0);
+        assert_eq!(spec.defense.end(), 0);
+        assert_eq!(spec.dropped_gold.end(), 0);
+        assert_eq!(spec.dropped_xp.end(), 0);
+        assert!(spec.loot.is_empty());
+    }
+
+    #[test]
+    fn colossus_is_a_boss_with_parts() {
+        init();
+        let spec = MobId::Colossus.spec();
+        assert!(matches!(spec.quality, MobQuality::Boss));
+        assert!(!spec.parts.is_empty());
+        assert_eq!(spec.with_multiplier(1.5).parts, spec.parts);
+        assert!(MobId::Goblin.spec().parts.is_empty());
+    }
+
     #[test]
     fn all_mobs_have_entity_size() {
         init();
@@ -0,0 +1,151 @@
+//! Elite variants of ordinary mobs. An elite rolls one modifier when it spawns, which
+//! toughens its stats and gives it a trick of its own; it pays out more when killed.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::data::StatRange;
+
+use super::definitions::{MobQuality, MobSpec};
+
+/// Chance for an ordinary mob to spawn as an elite.
+pub const ELITE_CHANCE: f64 = 0.08;
+
+/// Gold and XP an elite gives, as a multiple of what the plain mob would.
+pub const ELITE_REWARD_MULTIPLIER: f32 = 2.0;
+
+/// Magic find added to an elite's loot rolls.
+pub const ELITE_MAGIC_FIND: i32 = 50;
+
+/// Flat defense an armored elite gains on top of doubling its own.
+const ARMORED_DEFENSE_BONUS: i32 = 2;
+
+/// Share of the damage a vampiric elite deals that it heals.
+pub const VAMPIRIC_LIFESTEAL: f32 = 0.5;
+
+#[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EliteModifier {
+    /// Hits harder and moves faster.
+    Frenzied,
+    /// Tougher all round, and its armor soaks up the first blow it takes.
+    Armored,
+    /// Heals itself with the damage it deals.
+    Vampiric,
+}
+
+/// The armor an [`EliteModifier::Armored`] mob spawns with. The first hit breaks it
+/// instead of doing damage.
+#[derive(Component, Debug, Default)]
+pub struct ArmorShell;
+
+impl EliteModifier {
+    pub const ALL: [EliteModifier; 3] = [
+        EliteModifier::Frenzied,
+        EliteModifier::Armored,
+        EliteModifier::Vampiric,
+    ];
+
+    /// Rolls whether a mob spawns as an elite, and with which modifier. Bosses never do.
+    pub fn roll(spec: &MobSpec, rng: &mut impl Rng) -> Option<Self> {
+        if matches!(spec.quality, MobQuality::Boss) || !rng.gen_bool(ELITE_CHANCE) {
+            return None;
+        }
+        Some(Self::ALL[rng.gen_range(0..Self::ALL.len())])
+    }
+
+    /// Localization key for the name prefix, e.g. "Frenzied {mob}".
+    pub fn label_key(self) -> &'static str {
+        match self {
+            EliteModifier::Frenzied => "elite.frenzied",
+            EliteModifier::Armored => "elite.armored",
+            EliteModifier::Vampiric => "elite.vampiric",
+        }
+    }
+
+    pub fn tint(self) -> Color {
+        match self {
+            EliteModifier::Frenzied => Color::srgb(1.0, 0.55, 0.45),
+            EliteModifier::Armored => Color::srgb(0.7, 0.8, 1.0),
+            EliteModifier::Vampiric => Color::srgb(0.8, 0.5, 1.0),
+        }
+    }
+
+    /// How much faster than usual the mob gets around.
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            EliteModifier::Frenzied => 1.75,
+            EliteModifier::Armored | EliteModifier::Vampiric => 1.0,
+        }
+    }
+
+    /// A copy of `spec` with this modifier's stat changes applied.
+    pub fn apply(self, spec: &MobSpec) -> MobSpec {
+        let mut elite = spec.clone();
+        match self {
+            EliteModifier::Frenzied => {
+                elite.attack = spec.attack.scale(1.5);
+            }
+            EliteModifier::Armored => {
+                elite.max_health = spec.max_health.scale(1.5);
+                elite.defense = StatRange(
+                    spec.defense.start() * 2 + ARMORED_DEFENSE_BONUS,
+                    spec.defense.end() * 2 + ARMORED_DEFENSE_BONUS,
+                );
+            }
+            EliteModifier::Vampiric => {
+                elite.max_health = spec.max_health.scale(1.25);
+            }
+        }
+        elite
+    }
+}
+
+/// Gold or XP for a kill, boosted if the mob was an elite.
+pub fn kill_reward(amount: i32, elite: bool) -> i32 {
+    if !elite {
+        return amount;
+    }
+    (amount as f32 * ELITE_REWARD_MULTIPLIER).round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mob::MobId;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn init() {
+        crate::mob::data::init();
+    }
+
+    #[test]
+    fn modifiers_toughen_the_right_stats() {
+        init();
+        let base = MobId::Goblin.spec();
+
+        let frenzied = EliteModifier::Frenzied.apply(base);
+        assert!(frenzied.attack.end() >= base.attack.end());
+        assert_eq!(frenzied.max_health, base.max_health);
+
+        let armored = EliteModifier::Armored.apply(base);
+        assert_eq!(
+            armored.defense.start(),
+            base.defense.start() * 2 + ARMORED_DEFENSE_BONUS
+        );
+        assert!(armored.max_health.end() > base.max_health.end());
+        assert_eq!(armored.attack, base.attack);
+    }
+
+    #[test]
+    fn only_ordinary_mobs_roll_elite_and_they_pay_double() {
+        init();
+        let mut rng = StdRng::seed_from_u64(3);
+        let boss = MobId::Colossus.spec();
+        assert!((0..500).all(|_| EliteModifier::roll(boss, &mut rng).is_none()));
+        let goblin = MobId::Goblin.spec();
+        assert!((0..500).any(|_| EliteModifier::roll(goblin, &mut rng).is_some()));
+        assert_eq!(kill_reward(7, false), 7);
+        assert_eq!(kill_reward(7, true), 14);
+    }
+}
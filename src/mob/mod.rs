@@ -2,7 +2,11 @@ pub mod bundle;
 pub mod components;
 pub mod data;
 pub mod definitions;
+pub mod elite;
+pub mod parts;
 
 pub use bundle::MobCombatBundle;
 pub use components::{CombatStats, DeathProcessed, GoldReward, Health, MobLootTable, MobMarker, XpReward};
-pub use definitions::MobId;
+pub use definitions::{MobBehavior, MobId};
+pub use elite::{ArmorShell, EliteModifier};
+pub use parts::{BossPart, BossParts, PartKind, PartStrike};
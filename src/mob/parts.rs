@@ -0,0 +1,213 @@
+//! Targetable parts of colossal bosses. The boss's own [`Health`] is its core; each part
+//! has its own pool on top of that and weakens the boss when it breaks.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::components::{CombatStats, Health};
+
+/// Share of core damage that gets through while a part still guards the core.
+pub const GUARDED_CORE_DAMAGE: f32 = 0.5;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+pub enum PartKind {
+    LeftClaw,
+    RightClaw,
+    Carapace,
+}
+
+impl PartKind {
+    /// Localization key for the part's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            PartKind::LeftClaw => "boss_part.left_claw",
+            PartKind::RightClaw => "boss_part.right_claw",
+            PartKind::Carapace => "boss_part.carapace",
+        }
+    }
+
+    /// The part's HP as a share of the core's.
+    pub fn health_share(self) -> f32 {
+        match self {
+            PartKind::LeftClaw | PartKind::RightClaw => 0.35,
+            PartKind::Carapace => 0.5,
+        }
+    }
+
+    /// Whether the core only takes part of its damage while this part stands.
+    pub fn guards_core(self) -> bool {
+        matches!(self, PartKind::LeftClaw | PartKind::RightClaw)
+    }
+
+    /// Weakens the boss once the part is destroyed: a lost claw takes a third off its
+    /// attack, a broken carapace halves its defense.
+    pub fn on_destroyed(self, stats: &mut CombatStats) {
+        match self {
+            PartKind::LeftClaw | PartKind::RightClaw => {
+                stats.attack = (stats.attack as f32 * 2.0 / 3.0).round() as i32;
+            }
+            PartKind::Carapace => stats.defense /= 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BossPart {
+    pub kind: PartKind,
+    pub health: Health,
+}
+
+/// Where a strike on a boss landed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStrike {
+    /// A part took the hit, and broke if `destroyed`.
+    Part { kind: PartKind, destroyed: bool },
+    /// The hit went through to the core for this much damage.
+    Core(i32),
+}
+
+/// A boss's targetable parts and which one the player is aiming at.
+#[derive(Component, Debug, Clone)]
+pub struct BossParts {
+    pub parts: Vec<BossPart>,
+    /// Index of the targeted part, or `None` for the core.
+    pub targeted: Option<usize>,
+}
+
+impl BossParts {
+    pub fn new(kinds: &[PartKind], core_max_hp: i32) -> Self {
+        let parts = kinds
+            .iter()
+            .map(|&kind| BossPart {
+                kind,
+                health: Health::new(
+                    ((core_max_hp as f32 * kind.health_share()).round() as i32).max(1),
+                ),
+            })
+            .collect();
+        Self {
+            parts,
+            targeted: None,
+        }
+    }
+
+    /// Whether a part still stands that keeps most damage off the core.
+    pub fn core_guarded(&self) -> bool {
+        self.parts
+            .iter()
+            .any(|part| part.kind.guards_core() && part.health.is_alive())
+    }
+
+    /// Moves the target to the next standing part, or back one with `forward` unset. The
+    /// core comes after the last part.
+    pub fn cycle_target(&mut self, forward: bool) {
+        // Slots are the parts in order followed by the core.
+        let slots = self.parts.len() + 1;
+        let mut slot = self.targeted.unwrap_or(self.parts.len());
+        for _ in 0..slots {
+            slot = if forward {
+                (slot + 1) % slots
+            } else {
+                (slot + slots - 1) % slots
+            };
+            if slot == self.parts.len() {
+                self.targeted = None;
+                return;
+            }
+            if self.parts[slot].health.is_alive() {
+                self.targeted = Some(slot);
+                return;
+            }
+        }
+    }
+
+    /// Lands `amount` damage on the targeted part, or on the core through whatever guard
+    /// is left. A part that breaks hands the target back to the core.
+    pub fn strike(&mut self, amount: i32) -> PartStrike {
+        if let Some(part) = self.targeted.and_then(|index| self.parts.get_mut(index))
+            && part.health.is_alive()
+        {
+            part.health.take_damage(amount);
+            let destroyed = !part.health.is_alive();
+            let kind = part.kind;
+            if destroyed {
+                self.targeted = None;
+            }
+            return PartStrike::Part { kind, destroyed };
+        }
+
+        if self.core_guarded() {
+            PartStrike::Core((amount as f32 * GUARDED_CORE_DAMAGE).round() as i32)
+        } else {
+            PartStrike::Core(amount)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colossus() -> BossParts {
+        BossParts::new(
+            &[PartKind::LeftClaw, PartKind::RightClaw, PartKind::Carapace],
+            100,
+        )
+    }
+
+    #[test]
+    fn parts_get_a_share_of_core_hp() {
+        let parts = colossus();
+        assert_eq!(parts.parts[0].health.max, 35);
+        assert_eq!(parts.parts[2].health.max, 50);
+        assert_eq!(parts.targeted, None);
+    }
+
+    #[test]
+    fn claws_guard_the_core_until_broken() {
+        let mut parts = colossus();
+        assert_eq!(parts.strike(10), PartStrike::Core(5));
+
+        parts.cycle_target(true);
+        assert_eq!(parts.targeted, Some(0));
+        assert_eq!(
+            parts.strike(40),
+            PartStrike::Part {
+                kind: PartKind::LeftClaw,
+                destroyed: true
+            }
+        );
+        assert_eq!(parts.targeted, None);
+
+        parts.parts[1].health.take_damage(100);
+        assert!(!parts.core_guarded());
+        assert_eq!(parts.strike(10), PartStrike::Core(10));
+    }
+
+    #[test]
+    fn targeting_skips_broken_parts() {
+        let mut parts = colossus();
+        parts.parts[0].health.take_damage(100);
+
+        parts.cycle_target(true);
+        assert_eq!(parts.targeted, Some(1));
+        parts.cycle_target(true);
+        assert_eq!(parts.targeted, Some(2));
+        parts.cycle_target(true);
+        assert_eq!(parts.targeted, None);
+        parts.cycle_target(false);
+        assert_eq!(parts.targeted, Some(2));
+    }
+
+    #[test]
+    fn breaking_parts_weakens_the_boss() {
+        let mut stats = CombatStats {
+            attack: 30,
+            defense: 20,
+        };
+        PartKind::LeftClaw.on_destroyed(&mut stats);
+        PartKind::Carapace.on_destroyed(&mut stats);
+        assert_eq!(stats.attack, 20);
+        assert_eq!(stats.defense, 10);
+    }
+}
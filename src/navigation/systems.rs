@@ -8,10 +8,12 @@ use crate::ui::ModalCommands;
 use super::table::{NavigationTable, NavigationTarget};
 
 use crate::ui::screens::forge_modal::ForgeModal;
+use crate::ui::screens::help_modal::HelpModal;
 use crate::ui::screens::inventory_modal::InventoryModal;
 use crate::ui::screens::merchant_modal::MerchantModal;
 use crate::ui::screens::monster_compendium::MonsterCompendiumModal;
 use crate::ui::screens::skills_modal::SkillsModal;
+use crate::ui::screens::storage_modal::StorageModal;
 
 pub fn handle_navigation(
     mut commands: Commands,
@@ -45,7 +47,14 @@ fn handle_modal_toggle(commands: &mut Commands, modal_type: ModalType) {
         ModalType::MerchantModal => commands.toggle_modal::<MerchantModal>(),
         ModalType::ForgeModal => commands.toggle_modal::<ForgeModal>(),
         ModalType::SkillsModal => commands.toggle_modal::<SkillsModal>(),
-        ModalType::Profile | ModalType::Keybinds | ModalType::AnvilModal => {
-        }
+        ModalType::Help => commands.toggle_modal::<HelpModal>(),
+        ModalType::Storage => commands.toggle_modal::<StorageModal>(),
+        ModalType::Profile
+        | ModalType::Keybinds
+        | ModalType::AnvilModal
+        | ModalType::Shrine
+        | ModalType::Evolution
+        | ModalType::Away
+        | ModalType::Dialogue => {}
     }
 }
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 
 use super::components::PlayerMarker;
-use super::{default_player_stats, PlayerGold, PlayerName};
+use super::{default_player_stats, BankedGold, PlayerName};
+use crate::economy::Wallet;
 use crate::entities::Progression;
 use crate::game::player::PlayerPreviousLevel;
-use crate::inventory::{Inventory, ManagesItems};
+use crate::inventory::{Inventory, Loadouts, ManagesItems};
 use crate::item::{ItemId, ItemRegistry};
 use crate::stats::StatSheet;
 
@@ -12,10 +13,12 @@ use crate::stats::StatSheet;
 pub struct PlayerBundle {
     pub marker: PlayerMarker,
     pub name: PlayerName,
-    pub gold: PlayerGold,
+    pub banked_gold: BankedGold,
+    pub wallet: Wallet,
     pub progression: Progression,
     pub stats: StatSheet,
     pub inventory: Inventory,
+    pub loadouts: Loadouts,
     pub previous_level: PlayerPreviousLevel,
 }
 
@@ -29,10 +32,12 @@ impl PlayerBundle {
         Self {
             marker: PlayerMarker,
             name: PlayerName::default(),
-            gold: PlayerGold(100),
+            banked_gold: BankedGold::default(),
+            wallet: Wallet::with_gold(100),
             progression: Progression::new(),
             stats: default_player_stats(),
             inventory,
+            loadouts: Loadouts::default(),
             previous_level: PlayerPreviousLevel(1),
         }
     }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use bevy::prelude::*;
 
+use crate::economy::{Currency, Wallet};
 use crate::stats::{StatInstance, StatSheet, StatType};
 
 #[derive(Resource, Component, Debug, Clone)]
@@ -13,16 +14,26 @@ impl Default for PlayerName {
     }
 }
 
-#[derive(Resource, Component, Debug, Clone, Default)]
-pub struct PlayerGold(pub i32);
-
-impl PlayerGold {
-    pub fn add(&mut self, amount: i32) {
-        self.0 += amount;
+/// Gold held safely in the bank. Only reachable through the merchant.
+#[derive(Component, Debug, Clone, Default)]
+pub struct BankedGold(pub i32);
+
+impl BankedGold {
+    /// Moves up to `amount` carried gold from `wallet` into the bank. Returns the amount
+    /// moved.
+    pub fn deposit(&mut self, wallet: &mut Wallet, amount: i32) -> i32 {
+        let moved = amount.clamp(0, wallet.gold());
+        wallet.subtract(Currency::Gold, moved);
+        self.0 += moved;
+        moved
     }
 
-    pub fn subtract(&mut self, amount: i32) {
-        self.0 = (self.0 - amount).max(0);
+    /// Moves up to `amount` gold from the bank into `wallet`. Returns the amount moved.
+    pub fn withdraw(&mut self, wallet: &mut Wallet, amount: i32) -> i32 {
+        let moved = amount.clamp(0, self.0);
+        self.0 -= moved;
+        wallet.add(Currency::Gold, moved);
+        moved
     }
 }
 
@@ -33,6 +44,11 @@ pub fn default_player_stats() -> StatSheet {
     sheet.insert(StatType::Defense.instance(3));
     sheet.insert(StatType::GoldFind.instance(0));
     sheet.insert(StatType::Mining.instance(100));
+    sheet.insert(StatType::Charisma.instance(0));
+    sheet.insert(StatType::CritChance.instance(5));
+    sheet.insert(StatType::CritDamage.instance(0));
+    sheet.insert(StatType::Dodge.instance(0));
+    sheet.insert(StatType::Mana.instance(30));
     sheet.insert(StatType::Health.instance(100));
     sheet
 }
@@ -48,44 +64,71 @@ mod tests {
     }
 
     #[test]
-    fn player_gold_default() {
-        let gold = PlayerGold::default();
-        assert_eq!(gold.0, 0);
+    fn wallet_gold_default() {
+        let gold = Wallet::default();
+        assert_eq!(gold.gold(), 0);
+    }
+
+    #[test]
+    fn wallet_gold_add() {
+        let mut gold = Wallet::with_gold(100);
+        gold.add(Currency::Gold, 50);
+        assert_eq!(gold.gold(), 150);
+    }
+
+    #[test]
+    fn wallet_gold_add_negative() {
+        let mut gold = Wallet::with_gold(100);
+        gold.add(Currency::Gold, -30);
+        assert_eq!(gold.gold(), 70);
+    }
+
+    #[test]
+    fn wallet_gold_subtract() {
+        let mut gold = Wallet::with_gold(100);
+        gold.subtract(Currency::Gold, 30);
+        assert_eq!(gold.gold(), 70);
     }
 
     #[test]
-    fn player_gold_add() {
-        let mut gold = PlayerGold(100);
-        gold.add(50);
-        assert_eq!(gold.0, 150);
+    fn wallet_gold_subtract_does_not_go_negative() {
+        let mut gold = Wallet::with_gold(50);
+        gold.subtract(Currency::Gold, 100);
+        assert_eq!(gold.gold(), 0);
     }
 
     #[test]
-    fn player_gold_add_negative() {
-        let mut gold = PlayerGold(100);
-        gold.add(-30);
-        assert_eq!(gold.0, 70);
+    fn wallet_gold_subtract_exactly_to_zero() {
+        let mut gold = Wallet::with_gold(50);
+        gold.subtract(Currency::Gold, 50);
+        assert_eq!(gold.gold(), 0);
     }
 
     #[test]
-    fn player_gold_subtract() {
-        let mut gold = PlayerGold(100);
-        gold.subtract(30);
-        assert_eq!(gold.0, 70);
+    fn banked_gold_deposit_moves_carried_gold() {
+        let mut carried = Wallet::with_gold(100);
+        let mut banked = BankedGold::default();
+        assert_eq!(banked.deposit(&mut carried, 60), 60);
+        assert_eq!(carried.gold(), 40);
+        assert_eq!(banked.0, 60);
     }
 
     #[test]
-    fn player_gold_subtract_does_not_go_negative() {
-        let mut gold = PlayerGold(50);
-        gold.subtract(100);
-        assert_eq!(gold.0, 0);
+    fn banked_gold_deposit_caps_at_carried() {
+        let mut carried = Wallet::with_gold(30);
+        let mut banked = BankedGold(10);
+        assert_eq!(banked.deposit(&mut carried, 100), 30);
+        assert_eq!(carried.gold(), 0);
+        assert_eq!(banked.0, 40);
     }
 
     #[test]
-    fn player_gold_subtract_exactly_to_zero() {
-        let mut gold = PlayerGold(50);
-        gold.subtract(50);
-        assert_eq!(gold.0, 0);
+    fn banked_gold_withdraw_caps_at_balance() {
+        let mut carried = Wallet::with_gold(5);
+        let mut banked = BankedGold(20);
+        assert_eq!(banked.withdraw(&mut carried, 50), 20);
+        assert_eq!(carried.gold(), 25);
+        assert_eq!(banked.0, 0);
     }
 
     #[test]
@@ -96,6 +139,10 @@ mod tests {
         assert_eq!(stats.value(StatType::GoldFind), 0);
         assert_eq!(stats.value(StatType::Mining), 100);
         assert_eq!(stats.value(StatType::Health), 100);
+        assert_eq!(stats.value(StatType::Charisma), 0);
+        assert_eq!(stats.value(StatType::CritChance), 5);
+        assert_eq!(stats.value(StatType::Dodge), 0);
+        assert_eq!(stats.value(StatType::Mana), 30);
     }
 }
 
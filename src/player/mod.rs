@@ -4,4 +4,4 @@ mod definition;
 
 pub use bundle::PlayerBundle;
 pub use components::PlayerMarker;
-pub use definition::{default_player_stats, PlayerGold, PlayerName};
+pub use definition::{default_player_stats, BankedGold, PlayerName};
@@ -0,0 +1,211 @@
+//! Spawn-tuning debug overlay (`debug_overlay` feature only). Toggle with F9.
+//!
+//! Also reports how many sprites and UI images the current screen draws, and the fewest
+//! draw calls they could batch into, to spot screens that switch textures too often.
+
+#[cfg(feature = "debug_overlay")]
+mod overlay_impl {
+    use std::collections::HashSet;
+    use std::fmt::Write;
+
+    use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+    use bevy::prelude::*;
+
+    use crate::assets::PackedAtlas;
+    use crate::dungeon::{ChestEntity, DungeonState, FloorSeed, RockEntity, SpawnTable};
+    use crate::inventory::Inventory;
+    use crate::mob::components::MobMarker;
+    use crate::player::PlayerMarker;
+    use crate::states::AppState;
+    use crate::stats::{StatSheet, StatType};
+
+    const OVERLAY_Z_INDEX: i32 = 400;
+
+    pub struct DebugOverlayPlugin;
+
+    impl Plugin for DebugOverlayPlugin {
+        fn build(&self, app: &mut App) {
+            if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+                app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+            }
+            app.add_systems(Startup, spawn_debug_overlay)
+                .add_systems(Update, (toggle_debug_overlay, update_debug_overlay).chain());
+        }
+    }
+
+    #[derive(Component)]
+    struct DebugOverlayText;
+
+    fn spawn_debug_overlay(mut commands: Commands) {
+        commands.spawn((
+            DebugOverlayText,
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 1.0, 0.6)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            ZIndex(OVERLAY_Z_INDEX),
+            Visibility::Hidden,
+        ));
+    }
+
+    fn toggle_debug_overlay(
+        keyboard: Res<ButtonInput<KeyCode>>,
+        mut overlay: Query<&mut Visibility, With<DebugOverlayText>>,
+    ) {
+        if !keyboard.just_pressed(KeyCode::F9) {
+            return;
+        }
+        if let Ok(mut visibility) = overlay.single_mut() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Visible,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+
+    fn update_debug_overlay(
+        diagnostics: Res<DiagnosticsStore>,
+        dungeon: Res<DungeonState>,
+        seed: Option<Res<FloorSeed>>,
+        mobs: Query<(), With<MobMarker>>,
+        chests: Query<(), With<ChestEntity>>,
+        rocks: Query<(), With<RockEntity>>,
+        player: Query<(&StatSheet, &Inventory), With<PlayerMarker>>,
+        state: Res<State<AppState>>,
+        sprites: Query<(&Sprite, &ViewVisibility)>,
+        ui_images: Query<(&ImageNode, &ViewVisibility)>,
+        packed: Option<Res<PackedAtlas>>,
+        mut overlay: Query<(&mut Text, &Visibility), With<DebugOverlayText>>,
+    ) {
+        let Ok((mut text, visibility)) = overlay.single_mut() else {
+            return;
+        };
+        if *visibility == Visibility::Hidden {
+            return;
+        }
+
+        let mut out = String::new();
+
+        let fps = diagnostics
+            .get(&FrameTimeDiagnosticsPlugin::FPS)
+            .and_then(|fps| fps.smoothed())
+            .map_or_else(|| "--".to_string(), |fps| format!("{fps:.0}"));
+        _ = writeln!(out, "FPS: {fps}");
+
+        _ = writeln!(
+            out,
+            "Mobs: {}  Chests: {}  Rocks: {}",
+            mobs.iter().count(),
+            chests.iter().count(),
+            rocks.iter().count()
+        );
+
+        let sprites = visible_textures(sprites.iter().map(|(s, v)| (&s.image, v)));
+        let ui_images = visible_textures(ui_images.iter().map(|(i, v)| (&i.image, v)));
+        _ = writeln!(
+            out,
+            "Screen: {:?}  Draws: {} sprites / {} textures, {} UI images / {} textures",
+            state.get(),
+            sprites.0,
+            sprites.1,
+            ui_images.0,
+            ui_images.1
+        );
+        if let Some(packed) = packed {
+            _ = writeln!(
+                out,
+                "Item atlas: {} sprites from {} sheets, {}x{}",
+                packed.sprites, packed.sheets, packed.size.x, packed.size.y
+            );
+        }
+
+        let floor = dungeon
+            .current_floor()
+            .map_or_else(|| "home".to_string(), |f| format!("{f:?} (#{})", dungeon.floor_index));
+        let seed = seed.map_or_else(|| "--".to_string(), |s| s.0.to_string());
+        _ = writeln!(out, "Floor: {floor}  Seed: {seed}");
+
+        if let Some(table) = dungeon.get_spawn_config() {
+            write_spawn_table(&mut out, &table);
+        }
+
+        if let Ok((stats, inventory)) = player.single() {
+            _ = writeln!(out, "Stats (base + equip):");
+            for &stat in StatType::all() {
+                let base = stats.value(stat);
+                let equip = inventory.sum_equipment_stats(stat);
+                _ = writeln!(
+                    out,
+                    "  {}: {} + {} = {}",
+                    stat.display_name(),
+                    base,
+                    equip,
+                    base + equip
+                );
+            }
+        }
+
+        **text = out;
+    }
+
+    /// How many of the images are visible, and how many distinct textures they use. Each
+    /// texture is at least one draw call per pass.
+    fn visible_textures<'a>(
+        images: impl Iterator<Item = (&'a Handle<Image>, &'a ViewVisibility)>,
+    ) -> (usize, usize) {
+        let mut count = 0;
+        let mut textures = HashSet::new();
+        for (image, visibility) in images {
+            if visibility.get() {
+                count += 1;
+                textures.insert(image.id());
+            }
+        }
+        (count, textures.len())
+    }
+
+    fn write_spawn_table(out: &mut String, table: &SpawnTable) {
+        _ = writeln!(out, "Spawn table:");
+        _ = writeln!(
+            out,
+            "  mobs {:?}  chests {:?}  rocks {:?}  stairs {:?}",
+            table.mob_count(),
+            table.chest(),
+            table.rock(),
+            table.stairs()
+        );
+        for entry in table.weighted_mobs() {
+            _ = writeln!(out, "  {:?} w{}", entry.mob_id, entry.weight);
+        }
+        for (mob_id, count) in table.guaranteed_mobs() {
+            _ = writeln!(out, "  {mob_id:?} x{count} (guaranteed)");
+        }
+        for (mob_id, range) in table.npc_spawns() {
+            _ = writeln!(out, "  {mob_id:?} {range:?} (npc)");
+        }
+    }
+}
+
+#[cfg(feature = "debug_overlay")]
+pub use overlay_impl::DebugOverlayPlugin;
+
+/// No-op plugin when the `debug_overlay` feature is disabled.
+#[cfg(not(feature = "debug_overlay"))]
+pub struct DebugOverlayPlugin;
+
+#[cfg(not(feature = "debug_overlay"))]
+impl bevy::prelude::Plugin for DebugOverlayPlugin {
+    fn build(&self, _app: &mut bevy::prelude::App) {
+        // No-op without the debug_overlay feature
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
+use crate::economy::{CommodityMarket, Currency, Wallet};
 use crate::item::Item;
+use crate::player::PlayerMarker;
+use crate::world_event::GameClock;
 
 /// Event fired when loot is dropped from a source (mob, chest, mining, etc.)
 #[derive(Message, Debug, Clone)]
@@ -56,6 +59,15 @@ pub struct TransactionCompleted {
     pub is_purchase: bool,
 }
 
+/// Event fired when a balance in the player's wallet changes, for any reason
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CurrencyChanged {
+    pub currency: Currency,
+    /// Signed change in the balance
+    pub amount: i32,
+    pub new_total: i32,
+}
+
 /// Plugin that registers all economy and loot events for UI feedback
 pub struct EconomyPlugin;
 
@@ -65,6 +77,48 @@ impl Plugin for EconomyPlugin {
             .add_message::<LootCollected>()
             .add_message::<GoldEarned>()
             .add_message::<GoldSpent>()
-            .add_message::<TransactionCompleted>();
+            .add_message::<TransactionCompleted>()
+            .add_message::<CurrencyChanged>()
+            .init_resource::<CommodityMarket>()
+            .add_systems(Update, (track_wallet_changes, advance_commodity_market));
+    }
+}
+
+/// Diffs the player's wallet against the last seen balances, so every earn and spend is
+/// reported without each system that touches the wallet announcing it.
+fn track_wallet_changes(
+    player: Query<&Wallet, (With<PlayerMarker>, Changed<Wallet>)>,
+    mut previous: Local<Wallet>,
+    mut changed_writer: MessageWriter<CurrencyChanged>,
+) {
+    let Ok(wallet) = player.single() else {
+        return;
+    };
+
+    for currency in Currency::ALL {
+        let new_total = wallet.balance(currency);
+        let amount = new_total - previous.balance(currency);
+        if amount != 0 {
+            changed_writer.write(CurrencyChanged {
+                currency,
+                amount,
+                new_total,
+            });
+        }
+    }
+    *previous = wallet.clone();
+}
+
+/// Catches material prices up to the calendar, one day of recovery at a time.
+fn advance_commodity_market(clock: Res<GameClock>, mut market: ResMut<CommodityMarket>) {
+    let today = clock.day();
+    let days_passed = match market.last_day {
+        Some(day) if day >= today => return,
+        Some(day) => today - day,
+        None => 0,
+    };
+    for _ in 0..days_passed {
+        market.end_day();
     }
+    market.last_day = Some(today);
 }
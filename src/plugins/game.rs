@@ -16,7 +16,7 @@ pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        // Infrastructure: assets, states, input, camera, tiled
+        // Infrastructure: assets, states, input, camera
         app.add_plugins(InfrastructurePlugins);
 
         // Navigation (builder-based, stays inline)
@@ -27,8 +27,13 @@ impl Plugin for GamePlugin {
                     .on(GameAction::OpenProfile, ModalType::Profile)
                     .on(GameAction::OpenCompendium, ModalType::MonsterCompendium)
                     .on(GameAction::OpenSkills, ModalType::SkillsModal)
+                .state(AppState::Menu)
+                    .on(GameAction::OpenMods, AppState::Mods)
+                    .on(GameAction::OpenLootFilter, AppState::LootFilter)
+                    .on(GameAction::OpenRoster, AppState::Roster)
                 .global()
                     .on(GameAction::OpenKeybinds, AppState::Keybinds)
+                    .on(GameAction::OpenHelp, ModalType::Help)
                 .build(),
         );
 
@@ -38,9 +43,19 @@ impl Plugin for GamePlugin {
                 .location(LocationId::Home)
                     .floor(FloorId::HomeFloor)
                 .location(LocationId::MainDungeon)
+                    .tiered()
                     .floor(FloorId::MainDungeon1)
                     .floor(FloorId::MainDungeon2)
                     .floor(FloorId::MainDungeon3)
+                .location(LocationId::Outpost)
+                    .floor(FloorId::OutpostFloor)
+                .location(LocationId::DeepMine)
+                    .tiered()
+                    .floor(FloorId::DeepMine1)
+                    .floor(FloorId::DeepMine2)
+                    .floor(FloorId::ColossusLair)
+                .location(LocationId::VillageField)
+                    .floor(FloorId::VillageField)
                 .build(),
         );
 
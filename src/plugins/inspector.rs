@@ -0,0 +1,319 @@
+//! Dungeon entity inspector (`inspector` feature only). Left-click an entity, or press F10
+//! for the mob nearest the player, to list its components in a side panel. Clicking empty
+//! floor clears the selection.
+
+#[cfg(feature = "inspector")]
+mod inspector_impl {
+    use std::fmt::Write;
+
+    use bevy::prelude::*;
+    use bevy::window::PrimaryWindow;
+
+    use crate::combat::{Invulnerable, Knockback};
+    use crate::dungeon::systems::MobTactics;
+    use crate::dungeon::{
+        ChestEntity, Following, NestBrood, NestEntity, NpcEntity, RockEntity, Roaming,
+    };
+    use crate::mob::{
+        BossParts, CombatStats, DeathProcessed, EliteModifier, GoldReward, Health, MobLootTable,
+        MobMarker, XpReward,
+    };
+    use crate::states::AppState;
+    use crate::ui::{DungeonPlayer, DyingMob};
+
+    const PANEL_Z_INDEX: i32 = 400;
+    const PANEL_WIDTH: f32 = 280.0;
+
+    /// How far from the cursor, in pixels, a click still picks an entity.
+    const PICK_RADIUS: f32 = 16.0;
+
+    /// Anything the inspector can pick.
+    type Inspectable = Or<(
+        With<MobMarker>,
+        With<NestEntity>,
+        With<NpcEntity>,
+        With<ChestEntity>,
+        With<RockEntity>,
+    )>;
+
+    pub struct InspectorPlugin;
+
+    impl Plugin for InspectorPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<Inspected>()
+                .add_systems(Startup, spawn_inspector_panel)
+                .add_systems(
+                    Update,
+                    (select_clicked_entity, select_nearest_mob, update_inspector_panel)
+                        .chain()
+                        .run_if(in_state(AppState::Dungeon)),
+                )
+                .add_systems(OnExit(AppState::Dungeon), clear_selection);
+        }
+    }
+
+    /// The entity being inspected, if any.
+    #[derive(Resource, Default)]
+    struct Inspected(Option<Entity>);
+
+    #[derive(Component)]
+    struct InspectorPanel;
+
+    fn spawn_inspector_panel(mut commands: Commands) {
+        commands.spawn((
+            InspectorPanel,
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.6, 0.85, 1.0)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            ZIndex(PANEL_Z_INDEX),
+            Visibility::Hidden,
+        ));
+    }
+
+    fn select_clicked_entity(
+        mouse: Res<ButtonInput<MouseButton>>,
+        window: Query<&Window, With<PrimaryWindow>>,
+        camera: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+        candidates: Query<(Entity, &GlobalTransform), Inspectable>,
+        mut inspected: ResMut<Inspected>,
+    ) {
+        if !mouse.just_pressed(MouseButton::Left) {
+            return;
+        }
+        let (Ok(window), Ok((camera, camera_transform))) = (window.single(), camera.single())
+        else {
+            return;
+        };
+        let Some(point) = window
+            .cursor_position()
+            .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        else {
+            return;
+        };
+        inspected.0 = nearest(point, &candidates)
+            .filter(|(_, distance)| *distance <= PICK_RADIUS)
+            .map(|(entity, _)| entity);
+    }
+
+    fn select_nearest_mob(
+        keyboard: Res<ButtonInput<KeyCode>>,
+        player: Query<&GlobalTransform, With<DungeonPlayer>>,
+        candidates: Query<(Entity, &GlobalTransform), Inspectable>,
+        mobs: Query<(), With<MobMarker>>,
+        mut inspected: ResMut<Inspected>,
+    ) {
+        if !keyboard.just_pressed(KeyCode::F10) {
+            return;
+        }
+        let Ok(player) = player.single() else {
+            return;
+        };
+        let at = player.translation().truncate();
+        inspected.0 = candidates
+            .iter()
+            .filter(|(entity, _)| mobs.contains(*entity))
+            .map(|(entity, transform)| (entity, transform.translation().truncate().distance(at)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity);
+    }
+
+    fn nearest(
+        point: Vec2,
+        candidates: &Query<(Entity, &GlobalTransform), Inspectable>,
+    ) -> Option<(Entity, f32)> {
+        candidates
+            .iter()
+            .map(|(entity, transform)| (entity, transform.translation().truncate().distance(point)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    fn update_inspector_panel(
+        mut inspected: ResMut<Inspected>,
+        identity: Query<(
+            &GlobalTransform,
+            Option<&MobMarker>,
+            Option<&NestEntity>,
+            Option<&NpcEntity>,
+            Option<&ChestEntity>,
+            Option<&RockEntity>,
+        )>,
+        combat: Query<(
+            Option<&Health>,
+            Option<&CombatStats>,
+            Option<&GoldReward>,
+            Option<&XpReward>,
+            Option<&MobLootTable>,
+            Option<&EliteModifier>,
+            Option<&BossParts>,
+        )>,
+        ai: Query<(
+            Option<&Roaming>,
+            Option<&MobTactics>,
+            Option<&Following>,
+            Option<&Knockback>,
+            Option<&Invulnerable>,
+            Option<&NestBrood>,
+            Option<&DyingMob>,
+            Option<&DeathProcessed>,
+        )>,
+        mut panel: Query<(&mut Text, &mut Visibility), With<InspectorPanel>>,
+    ) {
+        let Ok((mut text, mut visibility)) = panel.single_mut() else {
+            return;
+        };
+        let Some(entity) = inspected.0 else {
+            *visibility = Visibility::Hidden;
+            return;
+        };
+        let Ok((transform, mob, nest, npc, chest, rock)) = identity.get(entity) else {
+            // Despawned since it was picked.
+            inspected.0 = None;
+            *visibility = Visibility::Hidden;
+            return;
+        };
+        *visibility = Visibility::Visible;
+
+        let mut out = String::new();
+        let kind = match (mob, nest, npc, chest, rock) {
+            (Some(mob), ..) => format!("Mob {:?}", mob.0),
+            (_, Some(nest), ..) => format!("Nest of {:?}", nest.mob_id),
+            (_, _, Some(npc), ..) => format!("NPC {:?}", npc.mob_id),
+            (_, _, _, Some(_), _) => "Chest".to_string(),
+            (.., Some(rock)) => format!("Rock {:?}", rock.rock_type),
+            _ => "Entity".to_string(),
+        };
+        let at = transform.translation();
+        _ = writeln!(out, "{kind}  [{entity}]");
+        _ = writeln!(out, "Position: ({:.0}, {:.0})", at.x, at.y);
+
+        if let Ok((health, stats, gold, xp, loot, elite, boss)) = combat.get(entity) {
+            if let Some(health) = health {
+                _ = writeln!(out, "Health: {}/{}", health.current, health.max);
+            }
+            if let Some(stats) = stats {
+                _ = writeln!(out, "Attack: {}  Defense: {}", stats.attack, stats.defense);
+            }
+            if let (Some(gold), Some(xp)) = (gold, xp) {
+                _ = writeln!(out, "Rewards: {}g  {}xp", gold.0, xp.0);
+            }
+            if let Some(elite) = elite {
+                _ = writeln!(out, "Elite: {elite:?}");
+            }
+            if let Some(boss) = boss {
+                _ = writeln!(out, "Boss parts (target {:?}):", boss.targeted);
+                for part in &boss.parts {
+                    _ = writeln!(
+                        out,
+                        "  {:?} {}/{}",
+                        part.kind, part.health.current, part.health.max
+                    );
+                }
+            }
+            if let Some(loot) = loot {
+                _ = writeln!(out, "Loot table:");
+                for item in loot.0.iter() {
+                    let range = item.quantity_range();
+                    _ = writeln!(
+                        out,
+                        "  {:?} {:.1}% x{}-{}",
+                        item.item_id(),
+                        item.drop_chance_percent(),
+                        range.0,
+                        range.1
+                    );
+                }
+            }
+        }
+
+        if let Ok((roaming, tactics, following, knockback, invulnerable, brood, dying, processed)) =
+            ai.get(entity)
+        {
+            let mut state = Vec::new();
+            if let Some(roaming) = roaming {
+                state.push(match roaming.target() {
+                    Some(target) => format!("walking to ({:.0}, {:.0})", target.x, target.y),
+                    None => "resting".to_string(),
+                });
+                if roaming.is_patrolling() {
+                    state.push("patrol".to_string());
+                }
+            }
+            if let Some(tactics) = tactics {
+                if tactics.is_ranged() {
+                    state.push("ranged".to_string());
+                }
+                if combat
+                    .get(entity)
+                    .ok()
+                    .and_then(|(health, ..)| health)
+                    .is_some_and(|health| tactics.is_fleeing(health))
+                {
+                    state.push("fleeing".to_string());
+                }
+            }
+            if following.is_some() {
+                state.push("following player".to_string());
+            }
+            if knockback.is_some() {
+                state.push("knocked back".to_string());
+            }
+            if invulnerable.is_some() {
+                state.push("invulnerable".to_string());
+            }
+            if dying.is_some() {
+                state.push("dying".to_string());
+            }
+            if processed.is_some_and(|p| p.0) {
+                state.push("death processed".to_string());
+            }
+            if !state.is_empty() {
+                _ = writeln!(out, "AI: {}", state.join(", "));
+            }
+
+            let source = match brood {
+                Some(brood) => format!("hatched by nest [{}]", brood.0),
+                None if following.is_some() => "rescued captive".to_string(),
+                None => "floor spawn".to_string(),
+            };
+            _ = writeln!(out, "Spawn: {source}");
+        }
+
+        **text = out;
+    }
+
+    fn clear_selection(
+        mut inspected: ResMut<Inspected>,
+        mut panel: Query<&mut Visibility, With<InspectorPanel>>,
+    ) {
+        inspected.0 = None;
+        if let Ok(mut visibility) = panel.single_mut() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+#[cfg(feature = "inspector")]
+pub use inspector_impl::InspectorPlugin;
+
+/// No-op plugin when the `inspector` feature is disabled.
+#[cfg(not(feature = "inspector"))]
+pub struct InspectorPlugin;
+
+#[cfg(not(feature = "inspector"))]
+impl bevy::prelude::Plugin for InspectorPlugin {
+    fn build(&self, _app: &mut bevy::prelude::App) {
+        // No-op without the inspector feature
+    }
+}
@@ -0,0 +1,166 @@
+//! Entity leak detection (debug builds only). After every app state transition it counts
+//! the entities carrying each marker (zero-sized) component. It warns when a marker's count
+//! has gone up on each of the last few visits to the same state, which is what a screen that
+//! spawns UI roots or health bars without despawning them looks like. It also warns when the
+//! world grows past an overall entity budget.
+
+#[cfg(debug_assertions)]
+mod leak_impl {
+    use std::collections::{HashMap, VecDeque};
+
+    use bevy::prelude::*;
+    use bevy::state::state::StateTransitionEvent;
+
+    use crate::states::AppState;
+
+    /// Visits to a state compared when looking for growth. A marker has to grow on each of
+    /// the last `LEAK_WINDOW - 1` visits to be reported.
+    pub(super) const LEAK_WINDOW: usize = 4;
+
+    /// Entities alive at once before the census warns about the overall count.
+    const ENTITY_BUDGET: usize = 20_000;
+
+    pub(super) type Census = HashMap<String, usize>;
+
+    pub struct LeakDetectorPlugin;
+
+    impl Plugin for LeakDetectorPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<EntityCensus>().add_systems(
+                Last,
+                take_census.run_if(on_message::<StateTransitionEvent<AppState>>),
+            );
+        }
+    }
+
+    /// Marker counts from the most recent visits to each state, oldest first.
+    #[derive(Resource, Default)]
+    struct EntityCensus(HashMap<AppState, VecDeque<Census>>);
+
+    fn take_census(world: &mut World) {
+        let Some(state) = world.get_resource::<State<AppState>>().map(|s| *s.get()) else {
+            return;
+        };
+        let census = marker_counts(world);
+        let total: usize = world.archetypes().iter().map(|a| a.len() as usize).sum();
+        if total > ENTITY_BUDGET {
+            warn!(
+                total,
+                budget = ENTITY_BUDGET,
+                ?state,
+                "Entity count is over budget"
+            );
+        }
+
+        let mut history = world.resource_mut::<EntityCensus>();
+        let visits = history.0.entry(state).or_default();
+        visits.push_back(census);
+        if visits.len() > LEAK_WINDOW {
+            visits.pop_front();
+        }
+        for (marker, counts) in growing_markers(visits) {
+            warn!(
+                ?state,
+                marker,
+                ?counts,
+                "Marker count grew on every recent visit; possible entity leak"
+            );
+        }
+    }
+
+    /// Entities per zero-sized component, keyed by the component's short type name.
+    fn marker_counts(world: &World) -> Census {
+        world
+            .components()
+            .iter_registered()
+            .filter(|info| info.layout().size() == 0)
+            .filter_map(|info| {
+                let count: usize = world
+                    .archetypes()
+                    .iter()
+                    .filter(|archetype| archetype.contains(info.id()))
+                    .map(|archetype| archetype.len() as usize)
+                    .sum();
+                let name = info.name().to_string();
+                let short = name.rsplit("::").next().unwrap_or(&name).to_string();
+                (count > 0).then_some((short, count))
+            })
+            .collect()
+    }
+
+    /// Markers whose count rose on every visit in a full window, with their counts.
+    pub(super) fn growing_markers(visits: &VecDeque<Census>) -> Vec<(&str, Vec<usize>)> {
+        if visits.len() < LEAK_WINDOW {
+            return Vec::new();
+        }
+        let Some(latest) = visits.back() else {
+            return Vec::new();
+        };
+        let mut growing: Vec<_> = latest
+            .keys()
+            .filter_map(|marker| {
+                let counts: Vec<usize> = visits
+                    .iter()
+                    .map(|census| census.get(marker).copied().unwrap_or(0))
+                    .collect();
+                counts
+                    .windows(2)
+                    .all(|pair| pair[1] > pair[0])
+                    .then_some((marker.as_str(), counts))
+            })
+            .collect();
+        growing.sort();
+        growing
+    }
+}
+
+#[cfg(debug_assertions)]
+pub use leak_impl::LeakDetectorPlugin;
+
+/// No-op plugin in release builds.
+#[cfg(not(debug_assertions))]
+pub struct LeakDetectorPlugin;
+
+#[cfg(not(debug_assertions))]
+impl bevy::prelude::Plugin for LeakDetectorPlugin {
+    fn build(&self, _app: &mut bevy::prelude::App) {
+        // No-op in release builds
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::leak_impl::{growing_markers, Census, LEAK_WINDOW};
+
+    fn visits(counts: &[(&str, &[usize])]) -> VecDeque<Census> {
+        (0..LEAK_WINDOW)
+            .map(|visit| {
+                counts
+                    .iter()
+                    .map(|(marker, counts)| (marker.to_string(), counts[visit]))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_markers_growing_every_visit_are_reported() {
+        let visits = visits(&[
+            ("ShopRoot", &[1, 2, 3, 4]),
+            ("MobHealthBar", &[5, 9, 2, 8]),
+            ("MainMenuRoot", &[1, 1, 1, 1]),
+        ]);
+
+        assert_eq!(growing_markers(&visits), [("ShopRoot", vec![1, 2, 3, 4])]);
+    }
+
+    #[test]
+    fn needs_a_full_window_of_visits() {
+        let mut visits = visits(&[("ShopRoot", &[1, 2, 3, 4])]);
+        visits.pop_front();
+
+        assert!(growing_markers(&visits).is_empty());
+    }
+}
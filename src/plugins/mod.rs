@@ -1,19 +1,25 @@
+mod debug_overlay;
 mod economy;
 mod game;
+mod inspector;
+mod leak_detector;
 mod mobs;
 mod physics_debug;
 mod plugin_groups;
 mod toast_listeners;
 
+pub use debug_overlay::DebugOverlayPlugin;
 pub use economy::{
-    EconomyPlugin, GoldEarned, GoldSpent, LootCollected, LootDrop, LootDropped,
+    CurrencyChanged, EconomyPlugin, GoldEarned, GoldSpent, LootCollected, LootDrop, LootDropped,
     TransactionCompleted,
 };
 pub use game::GamePlugin;
+pub use inspector::InspectorPlugin;
+pub use leak_detector::LeakDetectorPlugin;
 pub use mobs::{MobDefeated, MobPlugin};
 pub use physics_debug::PhysicsDebugTogglePlugin;
 pub use plugin_groups::{
     CoreGamePlugins, GameMechanicsPlugins, InfrastructurePlugins, ScreenPlugins,
     UiInfrastructurePlugins, UiWidgetPlugins,
 };
-pub use toast_listeners::{ToastListenersPlugin, ToastThresholds};
+pub use toast_listeners::ToastListenersPlugin;
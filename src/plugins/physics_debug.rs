@@ -1,6 +1,6 @@
-//! Physics debug visualization plugin (debug builds only).
+//! Physics debug visualization plugin (debug builds with the `gui` feature only).
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, feature = "gui"))]
 mod debug_impl {
     use avian2d::prelude::{PhysicsDebugPlugin, PhysicsGizmos};
     use bevy::prelude::*;
@@ -31,16 +31,16 @@ mod debug_impl {
     }
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, feature = "gui"))]
 pub use debug_impl::PhysicsDebugTogglePlugin;
 
-/// No-op plugin for release builds.
-#[cfg(not(debug_assertions))]
+/// No-op plugin for release and `tui` builds.
+#[cfg(not(all(debug_assertions, feature = "gui")))]
 pub struct PhysicsDebugTogglePlugin;
 
-#[cfg(not(debug_assertions))]
+#[cfg(not(all(debug_assertions, feature = "gui")))]
 impl bevy::prelude::Plugin for PhysicsDebugTogglePlugin {
     fn build(&self, _app: &mut bevy::prelude::App) {
-        // No-op in release builds
+        // No-op in release and `tui` builds
     }
 }
@@ -2,96 +2,187 @@
 
 use bevy::app::PluginGroupBuilder;
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::AsepriteUltraPlugin;
-use bevy_ecs_tiled::prelude::TiledPlugin;
 
 use crate::assets::AssetPlugin as GameAssetPlugin;
+use crate::auction::AuctionPlugin;
+use crate::bot::BotPlugin;
 use crate::camera::CameraPlugin;
+use crate::console::ConsolePlugin;
 use crate::crafting_station::CraftingStationPlugin;
 use crate::data::DataPlugin;
+use crate::dialogue::DialoguePlugin;
+use crate::difficulty::DifficultyPlugin;
+use crate::faction::FactionPlugin;
+use crate::guild::GuildPlugin;
 use crate::combat::ActionCombatPlugin;
+use crate::help::HelpPlugin;
+use crate::i18n::I18nPlugin;
 use crate::game::{
-    BlacksmithPlugin, CombatPlugin, CraftingCompletePlugin, CraftingPlugin, ItemPlugin,
-    MerchantPlugin, MiningPlugin, NpcInteractionsPlugin, PlayerPlugin, StoragePlugin,
-    StorageTransactionsPlugin, ToastPlugin,
+    BlacksmithPlugin, BuffPlugin, CombatPlugin, ContractsPlugin, CraftingCompletePlugin,
+    CraftingPlugin, EndlessPlugin, EscortPlugin, FieldPlugin, GamblePlugin, GameStatsPlugin,
+    GhostPlugin, ItemPlugin, LoadoutPlugin, MerchantPlugin, MiningPlugin, NpcInteractionsPlugin,
+    PlayerPlugin, RescuePlugin, SaveSlotsPlugin, ShrinePlugin, StoragePlugin,
+    StorageTransactionsPlugin, ToastPlugin, TownUpgradesPlugin, TrainingPlugin, TravelPlugin,
+    UndoPlugin, WeeklyPlugin,
 };
 use crate::input::InputPlugin;
+use crate::loot::LootFilterPlugin;
+use crate::magic::MagicPlugin;
+use crate::presence::PresencePlugin;
+use crate::ranch::RanchPlugin;
+use crate::replay::ReplayPlugin;
+use crate::save::SavePlugin;
+use crate::settings::SettingsPlugin;
 use crate::skills::SkillsPlugin;
 use crate::states::StateTransitionPlugin;
+use crate::trophy::TrophyPlugin;
+use crate::tutorial::TutorialPlugin;
+use crate::workers::WorkersPlugin;
+use crate::world_event::WorldEventPlugin;
+use crate::world_state::WorldStatePlugin;
 use crate::ui::screens::{
-    AnvilModalPlugin, DungeonScreenPlugin, ForgeModalPlugin, InventoryModalPlugin, KeybindsPlugin,
-    MainMenuPlugin, MerchantModalPlugin, ModalPlugin, MonsterCompendiumPlugin, ProfilePlugin,
-    SkillsModalPlugin,
+    AnvilModalPlugin, DungeonScreenPlugin, ForgeModalPlugin, HelpModalPlugin, InventoryModalPlugin,
+    KeybindsPlugin, MainMenuPlugin, MerchantModalPlugin, ModalPlugin, ModsPlugin,
+    MonsterCompendiumPlugin, ProfilePlugin, SkillsModalPlugin, StorageModalPlugin, WorldMapPlugin,
+    GuildScreenPlugin, EventShopPlugin, LootFilterScreenPlugin, AuctionHouseScreenPlugin,
+    ContractsScreenPlugin, RunPlannerPlugin, LeaderboardPlugin, RanchScreenPlugin,
+    ShrineModalPlugin, TrophyRoomScreenPlugin, EvolutionModalPlugin, SpellcraftScreenPlugin,
+    WordIndexPlugin, RosterScreenPlugin, AwayModalPlugin, WorkersScreenPlugin,
+    TownUpgradesScreenPlugin, DialogueModalPlugin,
 };
 use crate::ui::widgets::{
-    ColumnPlugin, GoldDisplayPlugin, IconValueRowPlugin, ItemDetailDisplayPlugin,
-    ItemDetailPanePlugin, ItemGridPlugin, ItemStatsDisplayPlugin, OutlinedTextPlugin,
-    PlayerStatsPlugin, RowPlugin, SelectorPlugin, StackPlugin, StatRowPlugin,
+    ColumnPlugin, CurrencyDisplayPlugin, GoldDisplayPlugin, IconValueRowPlugin,
+    ItemDetailDisplayPlugin, ItemDetailPanePlugin, ItemGridPlugin, ItemStatsDisplayPlugin,
+    OutlinedTextPlugin, PlayerStatsPlugin, RichTextPlugin, RowPlugin, SelectorPlugin, StackPlugin,
+    StatRowPlugin, TextRowsPlugin,
+};
+#[cfg(feature = "gui")]
+use crate::ui::ScreenshotPlugin;
+use crate::ui::{
+    AnnouncePlugin, DamageNumbersPlugin, MobAnimationPlugin, PlayerSpritePlugin, ThemePlugin,
+    UiFeedbackPlugin,
 };
-use crate::ui::{MobAnimationPlugin, PlayerSpritePlugin};
 
-use super::{EconomyPlugin, MobPlugin, PhysicsDebugTogglePlugin, ToastListenersPlugin};
+use super::{
+    DebugOverlayPlugin, EconomyPlugin, InspectorPlugin, LeakDetectorPlugin, MobPlugin,
+    PhysicsDebugTogglePlugin, ToastListenersPlugin,
+};
 
-/// Infrastructure plugins: assets, states, input, camera, tiled maps.
+/// Infrastructure plugins: Aseprite sprites (`gui` only), assets, saves, states, input and
+/// replay, camera.
 pub struct InfrastructurePlugins;
 
 impl PluginGroup for InfrastructurePlugins {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
-            .add(AsepriteUltraPlugin)
-            .add(TiledPlugin::default())
+        let group = PluginGroupBuilder::start::<Self>();
+        #[cfg(feature = "gui")]
+        let group = group.add(AsepriteUltraPlugin);
+        group
+            .add(SavePlugin)
             .add(StateTransitionPlugin)
             .add(DataPlugin)
+            .add(I18nPlugin)
+            .add(HelpPlugin)
             .add(GameAssetPlugin)
             .add(InputPlugin)
+            .add(ReplayPlugin)
+            .add(BotPlugin)
             .add(CameraPlugin)
     }
 }
 
-/// Core game plugins: player, storage, items, combat, crafting.
+/// Core game plugins: player and save slots, storage, items, loot filter, settings and stats, combat, magic,
+/// crafting, skills, factions, guild, ranch, trophies, world flags.
 pub struct CoreGamePlugins;
 
 impl PluginGroup for CoreGamePlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(PlayerPlugin)
+            .add(SaveSlotsPlugin)
             .add(StoragePlugin)
             .add(ItemPlugin)
+            .add(LoadoutPlugin)
+            .add(LootFilterPlugin)
+            .add(SettingsPlugin)
+            .add(GameStatsPlugin)
             .add(CombatPlugin)
             .add(ActionCombatPlugin)
+            .add(MagicPlugin)
             .add(CraftingPlugin)
             .add(SkillsPlugin)
+            .add(FactionPlugin)
+            .add(GuildPlugin)
+            .add(RanchPlugin)
+            .add(WorkersPlugin)
+            .add(TrophyPlugin)
+            .add(WorldStatePlugin)
     }
 }
 
-/// Game mechanics plugins: NPCs, merchants, mining, economy.
+/// Game mechanics plugins: NPCs, merchants, mining, shrines and buffs, economy, world events,
+/// adaptive difficulty.
 pub struct GameMechanicsPlugins;
 
 impl PluginGroup for GameMechanicsPlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(BlacksmithPlugin)
+            .add(ContractsPlugin)
             .add(CraftingCompletePlugin)
             .add(CraftingStationPlugin)
+            .add(GamblePlugin)
             .add(MerchantPlugin)
             .add(MiningPlugin)
+            .add(ShrinePlugin)
+            .add(BuffPlugin)
+            .add(TrainingPlugin)
             .add(NpcInteractionsPlugin)
+            .add(DialoguePlugin)
             .add(StorageTransactionsPlugin)
+            .add(TravelPlugin)
+            .add(TownUpgradesPlugin)
+            .add(EscortPlugin)
+            .add(RescuePlugin)
+            .add(FieldPlugin)
+            .add(EndlessPlugin)
+            .add(WeeklyPlugin)
+            .add(GhostPlugin)
+            .add(AuctionPlugin)
+            .add(UndoPlugin)
             .add(MobPlugin)
             .add(EconomyPlugin)
+            .add(WorldEventPlugin)
+            .add(DifficultyPlugin)
             .add(PhysicsDebugTogglePlugin)
+            .add(DebugOverlayPlugin)
+            .add(InspectorPlugin)
+            .add(PresencePlugin)
+            .add(LeakDetectorPlugin)
     }
 }
 
-/// UI infrastructure plugins: toasts, modals.
+/// UI infrastructure plugins: toasts, sound and rumble cues, screenshots (`gui` only), modals,
+/// dev console, tutorial tips.
 pub struct UiInfrastructurePlugins;
 
 impl PluginGroup for UiInfrastructurePlugins {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
+        let group = PluginGroupBuilder::start::<Self>()
             .add(ToastPlugin)
             .add(ToastListenersPlugin)
+            .add(UiFeedbackPlugin)
+            .add(DamageNumbersPlugin);
+        #[cfg(feature = "gui")]
+        let group = group.add(ScreenshotPlugin);
+        group
+            .add(AnnouncePlugin)
+            .add(ThemePlugin)
             .add(ModalPlugin)
+            .add(ConsolePlugin)
+            .add(TutorialPlugin)
     }
 }
 
@@ -102,6 +193,7 @@ impl PluginGroup for UiWidgetPlugins {
     fn build(self) -> PluginGroupBuilder {
         PluginGroupBuilder::start::<Self>()
             .add(ColumnPlugin)
+            .add(CurrencyDisplayPlugin)
             .add(GoldDisplayPlugin)
             .add(IconValueRowPlugin)
             .add(ItemDetailDisplayPlugin)
@@ -112,10 +204,12 @@ impl PluginGroup for UiWidgetPlugins {
             .add(OutlinedTextPlugin)
             .add(PlayerSpritePlugin)
             .add(PlayerStatsPlugin)
+            .add(RichTextPlugin)
             .add(RowPlugin)
             .add(SelectorPlugin)
             .add(StackPlugin)
             .add(StatRowPlugin)
+            .add(TextRowsPlugin)
     }
 }
 
@@ -129,11 +223,33 @@ impl PluginGroup for ScreenPlugins {
             .add(ProfilePlugin)
             .add(InventoryModalPlugin)
             .add(MerchantModalPlugin)
+            .add(StorageModalPlugin)
             .add(ForgeModalPlugin)
             .add(AnvilModalPlugin)
             .add(MonsterCompendiumPlugin)
             .add(KeybindsPlugin)
+            .add(ModsPlugin)
             .add(DungeonScreenPlugin)
             .add(SkillsModalPlugin)
+            .add(HelpModalPlugin)
+            .add(ShrineModalPlugin)
+            .add(EvolutionModalPlugin)
+            .add(AwayModalPlugin)
+            .add(DialogueModalPlugin)
+            .add(WorldMapPlugin)
+            .add(GuildScreenPlugin)
+            .add(EventShopPlugin)
+            .add(AuctionHouseScreenPlugin)
+            .add(ContractsScreenPlugin)
+            .add(RunPlannerPlugin)
+            .add(LeaderboardPlugin)
+            .add(RanchScreenPlugin)
+            .add(WorkersScreenPlugin)
+            .add(TownUpgradesScreenPlugin)
+            .add(TrophyRoomScreenPlugin)
+            .add(SpellcraftScreenPlugin)
+            .add(WordIndexPlugin)
+            .add(LootFilterScreenPlugin)
+            .add(RosterScreenPlugin)
     }
 }
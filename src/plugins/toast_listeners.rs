@@ -2,33 +2,45 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 
 use crate::combat::{GoldGained, LootDropped, XpGained};
 use crate::dungeon::{MineableEntityType, MiningResult};
+use crate::economy::WorthGold;
+use crate::faction::FactionRankChanged;
 use crate::game::{
     BrewingResult, GoldChanged, ItemDeposited, ItemDropped, ItemEquipped,
     ItemPickedUp, ItemUnequipped, ItemUsed, ItemWithdrawn, PlayerHealed,
     PlayerLeveledUp, ShowToast,
 };
+use crate::i18n::Localization;
+use crate::loot::{LootFilter, LootRule};
+use crate::settings::GameSettings;
 use crate::skills::SkillLeveledUp;
 use super::{GoldEarned, GoldSpent, LootCollected, MobDefeated, TransactionCompleted};
 
-#[derive(Resource, Clone, Debug)]
-pub struct ToastThresholds {
-    pub heal_min: i32,
-    pub gold_change: i32,
-    pub gold_earned: i32,
-    pub gold_spent: i32,
-    pub loot_items: i32,
-    pub transaction: i32,
+/// Pickup toasts held back so a burst of pickups shows as one toast.
+#[derive(Resource, Debug, Default)]
+struct PickupBatch {
+    messages: Vec<String>,
+    items: u32,
+    timer: Option<Timer>,
 }
 
-impl Default for ToastThresholds {
-    fn default() -> Self {
-        Self {
-            heal_min: 20,
-            gold_change: 50,
-            gold_earned: 50,
-            gold_spent: 50,
-            loot_items: 3,
-            transaction: 100,
+impl PickupBatch {
+    /// Holds `message` until `window` seconds after the first pickup of the batch.
+    fn push(&mut self, message: String, quantity: u32, window: f32) {
+        self.messages.push(message);
+        self.items += quantity;
+        self.timer
+            .get_or_insert_with(|| Timer::from_seconds(window.max(0.0), TimerMode::Once));
+    }
+
+    /// The toast for everything held back: the pickup's own message if there was only one,
+    /// otherwise a count.
+    fn take(&mut self, loc: &Localization) -> Option<String> {
+        self.timer = None;
+        let items = std::mem::take(&mut self.items);
+        match std::mem::take(&mut self.messages).as_mut_slice() {
+            [] => None,
+            [message] => Some(std::mem::take(message)),
+            _ => Some(loc.format("toast.picked_up_batch", &[("count", &items)])),
         }
     }
 }
@@ -44,11 +56,18 @@ struct ItemEventReaders<'w, 's> {
     withdrawn: MessageReader<'w, 's, ItemWithdrawn>,
 }
 
+#[derive(SystemParam)]
+struct CombatEventReaders<'w, 's> {
+    gold: MessageReader<'w, 's, GoldGained>,
+    xp: MessageReader<'w, 's, XpGained>,
+    loot: MessageReader<'w, 's, LootDropped>,
+}
+
 pub struct ToastListenersPlugin;
 
 impl Plugin for ToastListenersPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ToastThresholds>()
+        app.init_resource::<PickupBatch>()
             .add_systems(
             Update,
             (
@@ -81,6 +100,11 @@ impl Plugin for ToastListenersPlugin {
                 listen_mining_events.run_if(on_message::<MiningResult>),
                 listen_brewing_events.run_if(on_message::<BrewingResult>),
                 listen_skill_events.run_if(on_message::<SkillLeveledUp>),
+                listen_faction_events.run_if(on_message::<FactionRankChanged>),
+                flush_pickup_batch
+                    .after(listen_item_events)
+                    .after(listen_action_combat_events)
+                    .run_if(|batch: Res<PickupBatch>| batch.timer.is_some()),
             ),
         );
     }
@@ -90,95 +114,117 @@ fn listen_player_events(
     mut level_up_events: MessageReader<PlayerLeveledUp>,
     mut healed_events: MessageReader<PlayerHealed>,
     mut gold_changed_events: MessageReader<GoldChanged>,
-    thresholds: Res<ToastThresholds>,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
+    let thresholds = &settings.toasts;
     for event in level_up_events.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "Level Up! You are now level {}",
-            event.new_level
-        )));
+        if !thresholds.level_ups {
+            continue;
+        }
+        toast_writer.write(ShowToast::new(
+            loc.format("toast.level_up", &[("level", &event.new_level)]),
+        ));
     }
 
     for event in healed_events.read() {
         if event.amount >= thresholds.heal_min {
-            toast_writer.write(ShowToast::new(format!("Healed {} HP", event.amount)));
+            toast_writer.write(ShowToast::new(
+                loc.format("toast.healed", &[("amount", &event.amount)]),
+            ));
         }
     }
 
     for event in gold_changed_events.read() {
         if event.amount.abs() >= thresholds.gold_change {
             if event.amount > 0 {
-                toast_writer.write(ShowToast::new(format!("Gained {} gold", event.amount)));
+                toast_writer.write(ShowToast::new(
+                    loc.format("toast.gold_gained", &[("amount", &event.amount)]),
+                ));
             } else {
-                toast_writer.write(ShowToast::new(format!("Spent {} gold", -event.amount)));
+                toast_writer.write(ShowToast::new(
+                    loc.format("toast.gold_spent", &[("amount", &-event.amount)]),
+                ));
             }
         }
     }
 }
 
-fn listen_item_events(mut events: ItemEventReaders, mut toast_writer: MessageWriter<ShowToast>) {
+fn listen_item_events(
+    mut events: ItemEventReaders,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
+    mut batch: ResMut<PickupBatch>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
     for event in events.picked_up.read() {
-        if event.quantity > 1 {
-            toast_writer.write(ShowToast::new(format!(
-                "Picked up {} x{}",
-                event.item_name, event.quantity
-            )));
+        let message = if event.quantity > 1 {
+            loc.format(
+                "toast.picked_up_many",
+                &[("item", &event.item_name), ("quantity", &event.quantity)],
+            )
         } else {
-            toast_writer.write(ShowToast::new(format!("Picked up {}", event.item_name)));
-        }
+            loc.format("toast.picked_up", &[("item", &event.item_name)])
+        };
+        batch.push(message, event.quantity, settings.toasts.batch_secs);
     }
 
     for event in events.equipped.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "Equipped {} to {:?}",
-            event.item_name, event.slot
+        toast_writer.write(ShowToast::new(loc.format(
+            "toast.equipped",
+            &[("item", &event.item_name), ("slot", &format!("{:?}", event.slot))],
         )));
     }
 
     for event in events.unequipped.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "Unequipped {} from {:?}",
-            event.item_name, event.slot
+        toast_writer.write(ShowToast::new(loc.format(
+            "toast.unequipped",
+            &[("item", &event.item_name), ("slot", &format!("{:?}", event.slot))],
         )));
     }
 
     for event in events.used.read() {
-        toast_writer.write(ShowToast::new(format!("Used {}", event.item_name)));
+        toast_writer.write(ShowToast::new(
+            loc.format("toast.used", &[("item", &event.item_name)]),
+        ));
     }
 
     for event in events.dropped.read() {
         if event.quantity > 1 {
-            toast_writer.write(ShowToast::new(format!(
-                "Dropped {} x{}",
-                event.item_name, event.quantity
+            toast_writer.write(ShowToast::new(loc.format(
+                "toast.dropped_many",
+                &[("item", &event.item_name), ("quantity", &event.quantity)],
             )));
         } else {
-            toast_writer.write(ShowToast::new(format!(
-                "Dropped {}",
-                event.item_name
-            )));
+            toast_writer.write(ShowToast::new(
+                loc.format("toast.dropped", &[("item", &event.item_name)]),
+            ));
         }
     }
 
     for event in events.deposited.read() {
-        toast_writer.write(ShowToast::new(format!("Deposited {}", event.item_name)));
+        toast_writer.write(ShowToast::new(
+            loc.format("toast.deposited", &[("item", &event.item_name)]),
+        ));
     }
 
     for event in events.withdrawn.read() {
-        toast_writer.write(ShowToast::new(format!("Withdrew {}", event.item_name)));
+        toast_writer.write(ShowToast::new(
+            loc.format("toast.withdrew", &[("item", &event.item_name)]),
+        ));
     }
 }
 
 fn listen_combat_events(
     mut mob_defeated_events: MessageReader<MobDefeated>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
     for event in mob_defeated_events.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "Defeated {}!",
-            event.mob_id.spec().name
-        )));
+        let spec = event.mob_id.spec();
+        let mob = loc.mob_name(event.mob_id, &spec.name);
+        toast_writer.write(ShowToast::new(loc.format("toast.mob_defeated", &[("mob", &mob)])));
     }
 }
 
@@ -187,103 +233,167 @@ fn listen_economy_events(
     mut gold_spent_events: MessageReader<GoldSpent>,
     mut loot_collected_events: MessageReader<LootCollected>,
     mut transaction_completed_events: MessageReader<TransactionCompleted>,
-    thresholds: Res<ToastThresholds>,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
+    let thresholds = &settings.toasts;
     for event in gold_earned_events.read() {
         if event.amount >= thresholds.gold_earned {
-            toast_writer.write(ShowToast::new(format!("Earned {} gold", event.amount)));
+            toast_writer.write(ShowToast::new(
+                loc.format("toast.gold_earned", &[("amount", &event.amount)]),
+            ));
         }
     }
 
     for event in gold_spent_events.read() {
         if event.amount >= thresholds.gold_spent {
-            toast_writer.write(ShowToast::new(format!("Spent {} gold", event.amount)));
+            toast_writer.write(ShowToast::new(
+                loc.format("toast.gold_spent", &[("amount", &event.amount)]),
+            ));
         }
     }
 
     for event in loot_collected_events.read() {
         if event.total_items >= thresholds.loot_items {
-            toast_writer.write(ShowToast::new(format!(
-                "Collected {} items",
-                event.total_items
-            )));
+            toast_writer.write(ShowToast::new(
+                loc.format("toast.collected", &[("count", &event.total_items)]),
+            ));
         }
     }
 
     for event in transaction_completed_events.read() {
         if event.price >= thresholds.transaction {
-            let action = if event.is_purchase { "Purchased" } else { "Sold" };
-            toast_writer.write(ShowToast::new(format!(
-                "{} {} for {} gold",
-                action, event.item.name, event.price
-            )));
+            let key = if event.is_purchase { "toast.purchased" } else { "toast.sold" };
+            let item = loc.item_name(event.item.item_id, &event.item.name);
+            toast_writer.write(ShowToast::new(
+                loc.format(key, &[("item", &item), ("price", &event.price)]),
+            ));
         }
     }
 }
 
 fn listen_brewing_events(
     mut brewing_events: MessageReader<BrewingResult>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
     for event in brewing_events.read() {
-        match event {
+        let message = match event {
             BrewingResult::Success { item_name } => {
-                toast_writer.write(ShowToast::new(format!("Crafted {}!", item_name)));
+                loc.format("toast.crafted", &[("item", item_name)])
             }
             BrewingResult::InsufficientIngredients { recipe_name } => {
-                toast_writer.write(ShowToast::new(format!(
-                    "Missing ingredients for {}",
-                    recipe_name
-                )));
+                loc.format("toast.missing_ingredients", &[("recipe", recipe_name)])
             }
+            BrewingResult::Locked { recipe_name, level } => loc.format(
+                "toast.brew_locked",
+                &[("recipe", recipe_name), ("level", level)],
+            ),
+            BrewingResult::FactionLocked {
+                recipe_name,
+                faction,
+                rank,
+            } => loc.format(
+                "toast.brew_faction_locked",
+                &[
+                    ("recipe", recipe_name),
+                    ("rank", &loc.get(rank.label_key())),
+                    ("faction", &loc.get(faction.name_key())),
+                ],
+            ),
             BrewingResult::InventoryFull { item_name } => {
-                toast_writer.write(ShowToast::new(format!(
-                    "Inventory full - could not add {}",
-                    item_name
-                )));
+                loc.format("toast.craft_inventory_full", &[("item", item_name)])
             }
             BrewingResult::CraftingFailed { recipe_name } => {
-                toast_writer.write(ShowToast::new(format!(
-                    "Failed to craft {}",
-                    recipe_name
-                )));
+                loc.format("toast.craft_failed", &[("recipe", recipe_name)])
             }
-        }
+        };
+        toast_writer.write(ShowToast::new(message));
     }
 }
 
 fn listen_skill_events(
     mut skill_events: MessageReader<SkillLeveledUp>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
     for event in skill_events.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "{} Level Up! Now level {}",
-            event.skill.display_name(),
-            event.new_level
+        toast_writer.write(ShowToast::new(loc.format(
+            "toast.skill_level_up",
+            &[("skill", &event.skill.display_name()), ("level", &event.new_level)],
+        )));
+    }
+}
+
+fn listen_faction_events(
+    mut rank_events: MessageReader<FactionRankChanged>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for event in rank_events.read() {
+        let key = if event.new_rank > event.old_rank {
+            "toast.faction_rank_up"
+        } else {
+            "toast.faction_rank_down"
+        };
+        toast_writer.write(ShowToast::new(loc.format(
+            key,
+            &[
+                ("faction", &loc.get(event.faction.name_key())),
+                ("rank", &loc.get(event.new_rank.label_key())),
+            ],
         )));
+
+        let taught: Vec<&str> = event
+            .faction
+            .spec()
+            .recipes
+            .iter()
+            .filter(|(rank, _)| *rank > event.old_rank && *rank <= event.new_rank)
+            .map(|(_, recipe)| recipe.spec().name)
+            .collect();
+        if !taught.is_empty() {
+            toast_writer.write(ShowToast::new(loc.format(
+                "toast.faction_recipes",
+                &[
+                    ("faction", &loc.get(event.faction.name_key())),
+                    ("recipes", &taught.join(", ")),
+                ],
+            )));
+        }
     }
 }
 
 fn listen_mining_events(
     mut events: MessageReader<MiningResult>,
+    loc: Res<Localization>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
     for event in events.read() {
         let title = match &event.mineable_type {
-            MineableEntityType::Chest => "Chest Opened!".to_string(),
-            MineableEntityType::Rock { rock_type } => format!("{} Mined!", rock_type.display_name()),
+            MineableEntityType::Chest => loc.get("toast.chest_opened").to_string(),
+            MineableEntityType::Rock { rock_type } => {
+                loc.format("toast.rock_mined", &[("rock", &rock_type.display_name())])
+            }
+            MineableEntityType::Herb => loc.get("toast.herb_gathered").to_string(),
         };
 
         if event.loot_drops.is_empty() {
             toast_writer.write(ShowToast::new(title));
         } else {
             for drop in &event.loot_drops {
+                let item = loc.item_name(drop.item.item_id, &drop.item.name);
                 if drop.quantity > 1 {
-                    toast_writer.write(ShowToast::new(format!("{}: {} x{}", title, drop.item.name, drop.quantity)));
+                    toast_writer.write(ShowToast::new(loc.format(
+                        "toast.mining_loot_many",
+                        &[("title", &title), ("item", &item), ("quantity", &drop.quantity)],
+                    )));
                 } else {
-                    toast_writer.write(ShowToast::new(format!("{}: {}", title, drop.item.name)));
+                    toast_writer.write(ShowToast::new(loc.format(
+                        "toast.mining_loot",
+                        &[("title", &title), ("item", &item)],
+                    )));
                 }
             }
         }
@@ -291,23 +401,82 @@ fn listen_mining_events(
 }
 
 fn listen_action_combat_events(
-    mut gold_events: MessageReader<GoldGained>,
-    mut xp_events: MessageReader<XpGained>,
-    mut loot_events: MessageReader<LootDropped>,
+    mut events: CombatEventReaders,
+    filter: Res<LootFilter>,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
+    mut batch: ResMut<PickupBatch>,
     mut toast_writer: MessageWriter<ShowToast>,
 ) {
-    for event in gold_events.read() {
-        toast_writer.write(ShowToast::new(format!(
-            "{} defeated! +{}g",
-            event.source, event.amount
+    let thresholds = &settings.toasts;
+    for event in events.gold.read() {
+        if event.amount < thresholds.kill_gold {
+            continue;
+        }
+        toast_writer.write(ShowToast::new(loc.format(
+            "toast.kill_gold",
+            &[("mob", &event.source), ("amount", &event.amount)],
         )));
     }
 
-    for event in xp_events.read() {
-        toast_writer.write(ShowToast::new(format!("+{} xp", event.amount)));
+    for event in events.xp.read() {
+        toast_writer.write(ShowToast::new(
+            loc.format("toast.xp", &[("amount", &event.amount)]),
+        ));
     }
 
-    for event in loot_events.read() {
-        toast_writer.write(ShowToast::new(format!("Found: {}", event.item_name)));
+    for event in events.loot.read() {
+        let item = &event.item.name;
+        let message = match filter.rule_for(&event.item) {
+            LootRule::Pickup if event.item.quality < thresholds.drop_quality => continue,
+            LootRule::Pickup => {
+                let message = loc.format("toast.found", &[("item", item)]);
+                batch.push(message, event.quantity.max(0) as u32, thresholds.batch_secs);
+                continue;
+            }
+            LootRule::Sell => loc.format(
+                "toast.auto_sold",
+                &[("item", item), ("amount", &(event.item.sell_price() * event.quantity))],
+            ),
+            LootRule::Ignore => continue,
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+fn flush_pickup_batch(
+    time: Res<Time>,
+    loc: Res<Localization>,
+    mut batch: ResMut<PickupBatch>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Some(timer) = &mut batch.timer else {
+        return;
+    };
+    if !timer.tick(time.delta()).is_finished() {
+        return;
+    }
+    if let Some(message) = batch.take(&loc) {
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_burst_of_pickups_becomes_one_toast() {
+        let loc = Localization::from_ron(r#"{"toast.picked_up_batch": "Picked up {count} items"}"#);
+        let mut batch = PickupBatch::default();
+
+        batch.push("Picked up Coal".to_string(), 1, 1.0);
+        assert_eq!(batch.take(&loc).as_deref(), Some("Picked up Coal"));
+
+        for _ in 0..20 {
+            batch.push("Picked up Coal".to_string(), 2, 1.0);
+        }
+        assert_eq!(batch.take(&loc).as_deref(), Some("Picked up 40 items"));
+        assert_eq!(batch.take(&loc), None);
     }
 }
@@ -0,0 +1,86 @@
+use crate::i18n::Localization;
+use crate::states::AppState;
+use crate::ui::screens::modal::ModalType;
+
+/// A crafting station the player can be working at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CraftingStation {
+    Forge,
+    Anvil,
+}
+
+/// What the player is doing, as far as presence is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    InMenu,
+    InTown,
+    Exploring { depth: u32 },
+    FightingBoss { depth: u32 },
+    Crafting(CraftingStation),
+    Trading,
+}
+
+impl Activity {
+    /// The line shown to others, e.g. "Fighting the Floor 5 Boss".
+    pub fn text(self, loc: &Localization) -> String {
+        match self {
+            Activity::InMenu => loc.get("presence.menu").to_string(),
+            Activity::InTown => loc.get("presence.town").to_string(),
+            Activity::Exploring { depth } => loc.format("presence.exploring", &[("depth", &depth)]),
+            Activity::FightingBoss { depth } => loc.format("presence.boss", &[("depth", &depth)]),
+            Activity::Crafting(CraftingStation::Forge) => loc.get("presence.forge").to_string(),
+            Activity::Crafting(CraftingStation::Anvil) => loc.get("presence.anvil").to_string(),
+            Activity::Trading => loc.get("presence.trading").to_string(),
+        }
+    }
+}
+
+/// Works out the activity from the screen, the open modal, whether a boss is alive on the
+/// floor and how deep the floor is. Town floors are depth 0.
+pub fn current_activity(
+    state: AppState,
+    modal: Option<ModalType>,
+    boss_alive: bool,
+    depth: u32,
+) -> Activity {
+    if state != AppState::Dungeon {
+        return Activity::InMenu;
+    }
+    match modal {
+        Some(ModalType::ForgeModal) => Activity::Crafting(CraftingStation::Forge),
+        Some(ModalType::AnvilModal) => Activity::Crafting(CraftingStation::Anvil),
+        Some(ModalType::MerchantModal) => Activity::Trading,
+        _ if boss_alive => Activity::FightingBoss { depth },
+        _ if depth == 0 => Activity::InTown,
+        _ => Activity::Exploring { depth },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stations_and_bosses_win_over_the_floor() {
+        assert_eq!(
+            current_activity(AppState::Dungeon, Some(ModalType::ForgeModal), false, 0),
+            Activity::Crafting(CraftingStation::Forge)
+        );
+        assert_eq!(
+            current_activity(AppState::Dungeon, Some(ModalType::Inventory), true, 5),
+            Activity::FightingBoss { depth: 5 }
+        );
+        assert_eq!(
+            current_activity(AppState::Dungeon, None, false, 3),
+            Activity::Exploring { depth: 3 }
+        );
+        assert_eq!(
+            current_activity(AppState::Dungeon, None, false, 0),
+            Activity::InTown
+        );
+        assert_eq!(
+            current_activity(AppState::Guild, None, true, 5),
+            Activity::InMenu
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Rich presence (`presence` feature only): what the player is doing, published to a
+//! presence provider such as a launcher or chat client so friends can see it.
+
+#[cfg(feature = "presence")]
+mod activity;
+#[cfg(feature = "presence")]
+mod plugin;
+
+#[cfg(feature = "presence")]
+pub use activity::{current_activity, Activity, CraftingStation};
+#[cfg(feature = "presence")]
+pub use plugin::{LogPresence, Presence, PresencePlugin, PresenceProvider};
+
+/// No-op plugin when the `presence` feature is disabled.
+#[cfg(not(feature = "presence"))]
+pub struct PresencePlugin;
+
+#[cfg(not(feature = "presence"))]
+impl bevy::prelude::Plugin for PresencePlugin {
+    fn build(&self, _app: &mut bevy::prelude::App) {
+        // No-op without the presence feature
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::i18n::Localization;
+use crate::mob::BossParts;
+use crate::states::AppState;
+use crate::ui::screens::modal::ActiveModal;
+use crate::ui::DyingMob;
+
+use super::activity::{current_activity, Activity};
+
+/// Somewhere presence is published, such as a launcher's rich presence or a chat client.
+pub trait PresenceProvider: Send + Sync + 'static {
+    /// Shows `activity`, described by `text`, in place of whatever was shown before.
+    fn publish(&mut self, activity: Activity, text: &str);
+
+    /// Stops showing anything, as the game closes.
+    fn clear(&mut self) {}
+}
+
+/// Writes presence changes to the log. Used when no other provider is set.
+#[derive(Debug, Default)]
+pub struct LogPresence;
+
+impl PresenceProvider for LogPresence {
+    fn publish(&mut self, _activity: Activity, text: &str) {
+        info!("Presence: {text}");
+    }
+}
+
+/// The provider presence goes to, and what it was last shown.
+#[derive(Resource)]
+pub struct Presence {
+    provider: Box<dyn PresenceProvider>,
+    shown: Option<Activity>,
+}
+
+impl Presence {
+    pub fn new(provider: impl PresenceProvider) -> Self {
+        Self {
+            provider: Box::new(provider),
+            shown: None,
+        }
+    }
+
+    /// Publishes `activity` unless it is already shown.
+    pub fn show(&mut self, activity: Activity, loc: &Localization) {
+        if self.shown == Some(activity) {
+            return;
+        }
+        self.provider.publish(activity, &activity.text(loc));
+        self.shown = Some(activity);
+    }
+
+    pub fn clear(&mut self) {
+        self.provider.clear();
+        self.shown = None;
+    }
+}
+
+/// Keeps the presence provider up to date. Insert a [`Presence`] before adding the plugin
+/// to publish somewhere other than the log.
+pub struct PresencePlugin;
+
+impl Plugin for PresencePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world().contains_resource::<Presence>() {
+            app.insert_resource(Presence::new(LogPresence));
+        }
+        app.add_systems(Update, update_presence)
+            .add_systems(Last, clear_presence.run_if(on_message::<AppExit>));
+    }
+}
+
+fn update_presence(
+    mut presence: ResMut<Presence>,
+    state: Res<State<AppState>>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    bosses: Query<(), (With<BossParts>, Without<DyingMob>)>,
+    loc: Res<Localization>,
+) {
+    let activity = current_activity(
+        *state.get(),
+        active_modal.modal,
+        !bosses.is_empty(),
+        dungeon.depth(),
+    );
+    presence.show(activity, &loc);
+}
+
+fn clear_presence(mut presence: ResMut<Presence>) {
+    presence.clear();
+}
@@ -0,0 +1,64 @@
+//! The ranch mob picked as an ally follows the player into the dungeon and strikes the
+//! closest mob every so often.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::combat::{apply_defense, DamageEntity};
+use crate::dungeon::MobEntity;
+use crate::mob::CombatStats;
+use crate::ui::{DungeonPlayer, DyingMob};
+
+use super::state::Ranch;
+
+/// How far from the player, in pixels, the ally reaches.
+pub const ALLY_RANGE: f32 = 128.0;
+
+/// Seconds between the ally's strikes.
+pub const ALLY_STRIKE_SECS: f32 = 2.0;
+
+pub fn has_ally(ranch: Res<Ranch>) -> bool {
+    ranch.ally().is_some()
+}
+
+/// Mobs the ally can strike: any that are still alive.
+type LivingMobs = (With<MobEntity>, Without<DyingMob>);
+
+pub fn ally_strikes(
+    time: Res<Time>,
+    ranch: Res<Ranch>,
+    mut since_strike: Local<f32>,
+    dungeon_player: Query<&Transform, With<DungeonPlayer>>,
+    mobs: Query<(Entity, &CombatStats, &Transform), LivingMobs>,
+    mut damage_writer: MessageWriter<DamageEntity>,
+) {
+    *since_strike += time.delta_secs();
+    if *since_strike < ALLY_STRIKE_SECS {
+        return;
+    }
+    let (Some(ally), Ok(player_transform)) = (ranch.ally(), dungeon_player.single()) else {
+        return;
+    };
+
+    let origin = player_transform.translation.truncate();
+    let target = mobs
+        .iter()
+        .map(|(entity, stats, transform)| {
+            let distance = origin.distance(transform.translation.truncate());
+            (entity, stats, distance)
+        })
+        .filter(|&(.., distance)| distance <= ALLY_RANGE)
+        .min_by(|a, b| a.2.total_cmp(&b.2));
+    // The ally holds its strike until something comes in reach.
+    let Some((target, stats, _)) = target else {
+        return;
+    };
+
+    let attack = ally.spec().attack;
+    let raw = rand::thread_rng().gen_range(attack.start()..=attack.end().max(attack.start()));
+    damage_writer.write(DamageEntity {
+        target,
+        amount: apply_defense(raw, stats.defense),
+    });
+    *since_strike = 0.0;
+}
@@ -0,0 +1,146 @@
+//! Capture orbs: thrown at a weakened mob near the player, they may trap it for the ranch.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::dungeon::MobEntity;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemId;
+use crate::mob::definitions::MobQuality;
+use crate::mob::{BossParts, Health};
+use crate::player::PlayerMarker;
+use crate::ui::screens::ActiveModal;
+use crate::ui::{DungeonPlayer, DyingMob};
+
+use super::state::Ranch;
+
+/// How far from the player, in pixels, an orb can be thrown.
+pub const CAPTURE_RANGE: f32 = 96.0;
+
+/// Share of its health a mob has to be down to before an orb can hold it.
+pub const WEAKENED_SHARE: f32 = 0.3;
+
+/// Capture chance for a mob only just weakened enough. It rises to certain as the mob's
+/// health runs out.
+pub const BASE_CAPTURE_CHANCE: f32 = 0.4;
+
+/// Chance an orb holds a mob at `health`, or `None` if it is not weakened enough yet.
+pub fn capture_chance(health: &Health) -> Option<f32> {
+    let share = health.current as f32 / health.max.max(1) as f32;
+    if share > WEAKENED_SHARE {
+        return None;
+    }
+    let weakness = 1.0 - share / WEAKENED_SHARE;
+    Some(BASE_CAPTURE_CHANCE + (1.0 - BASE_CAPTURE_CHANCE) * weakness)
+}
+
+/// Mobs an orb can be thrown at: alive, and not a boss with targetable parts.
+type CapturableMobs = (Without<DyingMob>, Without<BossParts>);
+
+/// Where the thrower stands, the orbs they carry, and the mobs an orb could reach.
+#[derive(SystemParam)]
+pub struct OrbThrow<'w, 's> {
+    dungeon_player: Query<'w, 's, &'static Transform, With<DungeonPlayer>>,
+    player: Query<'w, 's, &'static mut Inventory, With<PlayerMarker>>,
+    mobs: Query<
+        'w,
+        's,
+        (Entity, &'static MobEntity, &'static Health, &'static Transform),
+        CapturableMobs,
+    >,
+}
+
+/// Throws an orb at the closest capturable mob in range. The orb is only used up once
+/// there is a weakened mob to throw it at.
+pub fn throw_capture_orb(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    throw: OrbThrow,
+    mut ranch: ResMut<Ranch>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let OrbThrow {
+        dungeon_player,
+        mut player,
+        mobs,
+    } = throw;
+    for action in action_reader.read() {
+        if *action != GameAction::ThrowCaptureOrb || active_modal.modal.is_some() {
+            continue;
+        }
+        let (Ok(player_transform), Ok(mut inventory)) =
+            (dungeon_player.single(), player.single_mut())
+        else {
+            return;
+        };
+
+        if inventory.count_item(ItemId::CaptureOrb) == 0 {
+            toast_writer.write(ShowToast::new(loc.get("ranch.no_orbs")));
+            continue;
+        }
+        if ranch.is_full() {
+            toast_writer.write(ShowToast::new(loc.get("ranch.full")));
+            continue;
+        }
+
+        let origin = player_transform.translation.truncate();
+        let target = mobs
+            .iter()
+            .filter(|(_, mob, ..)| !matches!(mob.mob_id.spec().quality, MobQuality::Boss))
+            .map(|(entity, mob, health, transform)| {
+                let distance = origin.distance(transform.translation.truncate());
+                (entity, mob.mob_id, health, distance)
+            })
+            .filter(|&(.., distance)| distance <= CAPTURE_RANGE)
+            .min_by(|a, b| a.3.total_cmp(&b.3));
+        let Some((entity, mob_id, health, _)) = target else {
+            toast_writer.write(ShowToast::new(loc.get("ranch.no_target")));
+            continue;
+        };
+        let name = loc.mob_name(mob_id, &mob_id.spec().name);
+        let Some(chance) = capture_chance(health) else {
+            toast_writer.write(ShowToast::new(
+                loc.format("ranch.not_weakened", &[("mob", &name)]),
+            ));
+            continue;
+        };
+
+        inventory.decrease_item_quantity(ItemId::CaptureOrb, 1);
+        if rand::thread_rng().gen_bool(chance as f64) {
+            commands.entity(entity).despawn();
+            ranch.add(mob_id);
+            toast_writer.write(ShowToast::new(
+                loc.format("ranch.captured", &[("mob", &name)]),
+            ));
+        } else {
+            toast_writer.write(ShowToast::new(
+                loc.format("ranch.broke_free", &[("mob", &name)]),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(current: i32, max: i32) -> Health {
+        let mut health = Health::new(max);
+        health.take_damage(max - current);
+        health
+    }
+
+    #[test]
+    fn only_weakened_mobs_can_be_captured() {
+        assert_eq!(capture_chance(&health(40, 100)), None);
+        assert_eq!(capture_chance(&health(30, 100)), Some(BASE_CAPTURE_CHANCE));
+        let nearly_down = capture_chance(&health(3, 100)).unwrap();
+        assert!(nearly_down > 0.9 && nearly_down <= 1.0);
+    }
+}
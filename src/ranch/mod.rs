@@ -0,0 +1,9 @@
+pub mod ally;
+pub mod capture;
+pub mod plugin;
+pub mod state;
+
+pub use ally::{ALLY_RANGE, ALLY_STRIKE_SECS};
+pub use capture::{capture_chance, CAPTURE_RANGE, WEAKENED_SHARE};
+pub use plugin::{RanchPlugin, RanchRequest};
+pub use state::{product, Ranch, RanchMob, RanchRole, MAX_RANCH_LEVEL, MAX_STOCK, RANCH_SAVE_PATH};
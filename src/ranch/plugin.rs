@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+
+use crate::dungeon::systems::TransitionInProgress;
+use crate::economy::{Currency, Wallet};
+use crate::game::{CharacterDataExt, ShowToast};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet};
+use crate::ui::UiFeedback;
+use crate::world_event::GameClock;
+
+use super::ally::{ally_strikes, has_ally};
+use super::capture::throw_capture_orb;
+use super::state::Ranch;
+
+/// Something the player asked of the ranch.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RanchRequest {
+    /// Buy more pens.
+    Upgrade,
+    /// Move what the producers have made into the backpack.
+    Collect,
+    /// Switch the mob at this index between producing and fighting.
+    ToggleRole(usize),
+    Release(usize),
+}
+
+pub struct RanchPlugin;
+
+impl Plugin for RanchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_character_resource::<Ranch>()
+            .add_message::<RanchRequest>()
+            .add_systems(
+                Update,
+                (
+                    handle_ranch_requests.run_if(on_message::<RanchRequest>),
+                    produce_daily,
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(
+                Update,
+                (
+                    throw_capture_orb.run_if(on_message::<GameAction>),
+                    ally_strikes.run_if(has_ally),
+                )
+                    .run_if(not(resource_exists::<TransitionInProgress>))
+                    .run_if(in_state(AppState::Dungeon)),
+            );
+    }
+}
+
+/// Producers work once for every day that passes on the game clock.
+fn produce_daily(
+    clock: Res<GameClock>,
+    mut last_day: Local<Option<u32>>,
+    mut ranch: ResMut<Ranch>,
+) {
+    let day = clock.day();
+    let Some(last) = last_day.replace(day) else {
+        return;
+    };
+    for _ in last..day {
+        ranch.produce();
+    }
+}
+
+fn handle_ranch_requests(
+    mut requests: MessageReader<RanchRequest>,
+    mut ranch: ResMut<Ranch>,
+    mut player: Query<(&mut Inventory, &mut Wallet), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let Ok((mut inventory, mut wallet)) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        let message = match *request {
+            RanchRequest::Upgrade => match ranch.upgrade_cost() {
+                None => {
+                    feedback.write(UiFeedback::Error);
+                    loc.get("ranch.max_level").to_string()
+                }
+                Some(cost) if wallet.spend(Currency::Gold, cost).is_err() => {
+                    feedback.write(UiFeedback::Error);
+                    loc.format("ranch.cannot_afford", &[("gold", &cost)])
+                }
+                Some(_) => {
+                    ranch.upgrade();
+                    loc.format("ranch.upgraded", &[("pens", &ranch.capacity())])
+                }
+            },
+            RanchRequest::Collect => {
+                let stock: Vec<_> = ranch.stock().collect();
+                if stock.is_empty() {
+                    loc.get("ranch.nothing_to_collect").to_string()
+                } else {
+                    let mut collected = 0;
+                    for (item_id, quantity) in stock {
+                        let added = (0..quantity)
+                            .take_while(|_| inventory.add_to_inv(registry.spawn(item_id)).is_ok())
+                            .count() as u32;
+                        ranch.take_stock(item_id, added);
+                        collected += added;
+                    }
+                    if collected == 0 {
+                        feedback.write(UiFeedback::Error);
+                        loc.get("ranch.inventory_full").to_string()
+                    } else {
+                        loc.format("ranch.collected", &[("count", &collected)])
+                    }
+                }
+            }
+            RanchRequest::ToggleRole(index) => {
+                ranch.toggle_role(index);
+                continue;
+            }
+            RanchRequest::Release(index) => match ranch.release(index) {
+                Some(mob) => loc.format(
+                    "ranch.released",
+                    &[("mob", &loc.mob_name(mob.mob_id, &mob.mob_id.spec().name))],
+                ),
+                None => continue,
+            },
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
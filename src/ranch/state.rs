@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CharacterResource;
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// Where the ranch is saved in each character's slot, next to the guild hall.
+pub const RANCH_SAVE_PATH: &str = "ranch.ron";
+
+/// Pens before any upgrade.
+pub const BASE_CAPACITY: usize = 2;
+
+/// Pens added by each upgrade.
+pub const CAPACITY_PER_LEVEL: usize = 2;
+
+pub const MAX_RANCH_LEVEL: u32 = 3;
+
+/// Gold for the first upgrade; each one after costs this much more than the last.
+pub const UPGRADE_COST_STEP: i32 = 250;
+
+/// Most of any one material the ranch holds before its producers stop adding to it.
+pub const MAX_STOCK: u32 = 20;
+
+/// What a ranched mob spends its days doing.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RanchRole {
+    /// Produces its usual drop once a day.
+    #[default]
+    Producer,
+    /// Comes along on dungeon runs and fights beside the player.
+    Ally,
+}
+
+impl RanchRole {
+    pub fn label_key(self) -> &'static str {
+        match self {
+            RanchRole::Producer => "ranch.role_producer",
+            RanchRole::Ally => "ranch.role_ally",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RanchMob {
+    pub mob_id: MobId,
+    pub role: RanchRole,
+}
+
+/// The town ranch: captured mobs and the materials they have produced.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ranch {
+    pub level: u32,
+    mobs: Vec<RanchMob>,
+    stock: HashMap<ItemId, u32>,
+}
+
+impl CharacterResource for Ranch {
+    const FILE: &'static str = RANCH_SAVE_PATH;
+}
+
+impl Ranch {
+    pub fn capacity(&self) -> usize {
+        BASE_CAPACITY + self.level as usize * CAPACITY_PER_LEVEL
+    }
+
+    pub fn mobs(&self) -> &[RanchMob] {
+        &self.mobs
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.mobs.len() >= self.capacity()
+    }
+
+    pub fn is_max_level(&self) -> bool {
+        self.level >= MAX_RANCH_LEVEL
+    }
+
+    /// Gold the next capacity upgrade costs, if there is one.
+    pub fn upgrade_cost(&self) -> Option<i32> {
+        (!self.is_max_level()).then(|| (self.level as i32 + 1) * UPGRADE_COST_STEP)
+    }
+
+    pub fn upgrade(&mut self) {
+        if !self.is_max_level() {
+            self.level += 1;
+        }
+    }
+
+    /// Pens a captured mob as a producer. Returns `false` if there is no room.
+    pub fn add(&mut self, mob_id: MobId) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.mobs.push(RanchMob {
+            mob_id,
+            role: RanchRole::Producer,
+        });
+        true
+    }
+
+    pub fn release(&mut self, index: usize) -> Option<RanchMob> {
+        (index < self.mobs.len()).then(|| self.mobs.remove(index))
+    }
+
+    /// Switches a mob between producing and fighting. Only one mob fights at a time, so
+    /// making one the ally sends the previous ally back to producing.
+    pub fn toggle_role(&mut self, index: usize) {
+        let Some(role) = self.mobs.get(index).map(|mob| mob.role) else {
+            return;
+        };
+        let role = match role {
+            RanchRole::Producer => RanchRole::Ally,
+            RanchRole::Ally => RanchRole::Producer,
+        };
+        if role == RanchRole::Ally {
+            for mob in &mut self.mobs {
+                mob.role = RanchRole::Producer;
+            }
+        }
+        self.mobs[index].role = role;
+    }
+
+    /// The mob that comes along on runs, if one has been picked.
+    pub fn ally(&self) -> Option<MobId> {
+        self.mobs
+            .iter()
+            .find(|mob| mob.role == RanchRole::Ally)
+            .map(|mob| mob.mob_id)
+    }
+
+    /// Materials waiting to be collected.
+    pub fn stock(&self) -> impl Iterator<Item = (ItemId, u32)> + '_ {
+        self.stock
+            .iter()
+            .map(|(&item_id, &quantity)| (item_id, quantity))
+    }
+
+    /// Takes up to `quantity` of a material out of the stock.
+    pub fn take_stock(&mut self, item_id: ItemId, quantity: u32) {
+        if let Some(held) = self.stock.get_mut(&item_id) {
+            *held = held.saturating_sub(quantity);
+            if *held == 0 {
+                self.stock.remove(&item_id);
+            }
+        }
+    }
+
+    /// A day's work: every producer adds its material to the stock. Returns whether
+    /// anything was added.
+    pub fn produce(&mut self) -> bool {
+        let mut produced = false;
+        for mob in self
+            .mobs
+            .iter()
+            .filter(|mob| mob.role == RanchRole::Producer)
+        {
+            let Some((item_id, quantity)) = product(mob.mob_id) else {
+                continue;
+            };
+            let held = self.stock.entry(item_id).or_insert(0);
+            let added = quantity.min(MAX_STOCK.saturating_sub(*held));
+            *held += added;
+            produced |= added > 0;
+        }
+        produced
+    }
+}
+
+/// What a mob produces on the ranch each day: its most common drop, in the smallest
+/// quantity it drops.
+pub fn product(mob_id: MobId) -> Option<(ItemId, u32)> {
+    mob_id
+        .spec()
+        .loot
+        .iter()
+        .max_by(|a, b| a.drop_chance_percent().total_cmp(&b.drop_chance_percent()))
+        .map(|item| (item.item_id(), item.quantity_range().start().max(1) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn upgrades_add_pens() {
+        let mut ranch = Ranch::default();
+        assert!(ranch.add(MobId::Slime));
+        assert!(ranch.add(MobId::Goblin));
+        assert!(!ranch.add(MobId::Slime));
+        assert_eq!(ranch.upgrade_cost(), Some(UPGRADE_COST_STEP));
+
+        ranch.upgrade();
+        assert_eq!(ranch.capacity(), BASE_CAPACITY + CAPACITY_PER_LEVEL);
+        assert!(ranch.add(MobId::Slime));
+        assert_eq!(ranch.upgrade_cost(), Some(2 * UPGRADE_COST_STEP));
+
+        ranch.level = MAX_RANCH_LEVEL;
+        assert_eq!(ranch.upgrade_cost(), None);
+    }
+
+    #[test]
+    fn only_one_mob_fights_at_a_time() {
+        let mut ranch = Ranch::default();
+        ranch.add(MobId::Slime);
+        ranch.add(MobId::Goblin);
+
+        ranch.toggle_role(0);
+        assert_eq!(ranch.ally(), Some(MobId::Slime));
+        ranch.toggle_role(1);
+        assert_eq!(ranch.ally(), Some(MobId::Goblin));
+        assert_eq!(ranch.mobs()[0].role, RanchRole::Producer);
+
+        ranch.toggle_role(1);
+        assert_eq!(ranch.ally(), None);
+        assert_eq!(ranch.release(0).map(|mob| mob.mob_id), Some(MobId::Slime));
+        assert_eq!(ranch.release(5), None);
+    }
+
+    #[test]
+    fn producers_fill_the_stock_up_to_its_cap() {
+        crate::mob::data::init();
+        let mut ranch = Ranch::default();
+        ranch.add(MobId::Slime);
+        ranch.add(MobId::Slime);
+        ranch.toggle_role(1);
+
+        assert!(ranch.produce());
+        assert_eq!(ranch.stock().collect::<Vec<_>>(), [(ItemId::SlimeGel, 1)]);
+
+        for _ in 0..MAX_STOCK {
+            ranch.produce();
+        }
+        assert!(!ranch.produce());
+        ranch.take_stock(ItemId::SlimeGel, 5);
+        assert_eq!(
+            ranch.stock().collect::<Vec<_>>(),
+            [(ItemId::SlimeGel, MAX_STOCK - 5)]
+        );
+    }
+
+    #[test]
+    fn ranch_round_trips_through_ron() {
+        let mut ranch = Ranch::default();
+        ranch.add(MobId::Goblin);
+        ranch.toggle_role(0);
+
+        let loaded = from_ron::<Ranch>(&to_ron(&ranch).unwrap()).unwrap();
+        assert_eq!(loaded, ranch);
+    }
+}
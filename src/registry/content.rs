@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::dungeon::FloorId;
+use crate::item::recipe::RecipeId;
+use crate::item::ItemId;
+use crate::magic::WordId;
+use crate::mob::MobId;
+
+use super::key::{ContentKey, ContentKeyError};
+
+/// Bumped when the way content keys are written changes, so old saves can be migrated.
+pub const CONTENT_FORMAT_VERSION: u32 = 1;
+
+static INSTALLED: OnceLock<ContentRegistry> = OnceLock::new();
+
+/// The kinds of content the registry names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentKind {
+    Item,
+    Mob,
+    Recipe,
+    Floor,
+    Word,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RegistryError {
+    #[error("{kind:?} {key} is already registered")]
+    DuplicateKey { kind: ContentKind, key: ContentKey },
+    #[error("No {kind:?} is registered as {key}")]
+    Unknown { kind: ContentKind, key: ContentKey },
+    #[error(transparent)]
+    InvalidKey(#[from] ContentKeyError),
+}
+
+/// A value the registry can name. Built-in values are registered under `base` with their
+/// variant name in snake case, e.g. `ItemId::IronSword` as `base:iron_sword`.
+pub trait Content: Copy + Eq + Hash + Debug + Send + Sync + 'static {
+    const KIND: ContentKind;
+
+    /// Every built-in value, in the order it is registered.
+    fn builtin() -> &'static [Self];
+
+    fn table(registry: &ContentRegistry) -> &ContentTable<Self>;
+
+    fn table_mut(registry: &mut ContentRegistry) -> &mut ContentTable<Self>;
+
+    fn base_path(self) -> String {
+        let name = format!("{self:?}");
+        let mut path = String::with_capacity(name.len() + 4);
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                path.push('_');
+            }
+            path.push(c.to_ascii_lowercase());
+        }
+        path
+    }
+}
+
+macro_rules! impl_content {
+    ($id:ty, $kind:ident, $field:ident, $all:expr) => {
+        impl Content for $id {
+            const KIND: ContentKind = ContentKind::$kind;
+
+            fn builtin() -> &'static [Self] {
+                $all
+            }
+
+            fn table(registry: &ContentRegistry) -> &ContentTable<Self> {
+                &registry.$field
+            }
+
+            fn table_mut(registry: &mut ContentRegistry) -> &mut ContentTable<Self> {
+                &mut registry.$field
+            }
+        }
+    };
+}
+
+impl_content!(ItemId, Item, items, ItemId::ALL);
+impl_content!(MobId, Mob, mobs, MobId::ALL);
+impl_content!(RecipeId, Recipe, recipes, RecipeId::ALL);
+impl_content!(FloorId, Floor, floors, FloorId::ALL);
+impl_content!(WordId, Word, words, &WordId::ALL);
+
+/// A registered entry, valid for the registry that handed it out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContentHandle<T> {
+    index: u32,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ContentHandle<T> {
+    pub fn index(self) -> u32 {
+        self.index
+    }
+}
+
+/// Every key registered for one kind of content, in registration order.
+#[derive(Debug, Clone)]
+pub struct ContentTable<T> {
+    keys: Vec<ContentKey>,
+    values: Vec<T>,
+    by_key: HashMap<ContentKey, u32>,
+    /// The first key each value was registered under.
+    by_value: HashMap<T, u32>,
+}
+
+impl<T> Default for ContentTable<T> {
+    fn default() -> Self {
+        Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+            by_key: HashMap::new(),
+            by_value: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Content> ContentTable<T> {
+    pub fn keys(&self) -> &[ContentKey] {
+        &self.keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Identifies the content a save or data file was written against. A different fingerprint
+/// means content was added, removed or renamed since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentVersion {
+    pub format: u32,
+    pub fingerprint: u64,
+}
+
+/// Namespaced keys for every item, mob, recipe, floor and word. Several keys may resolve to
+/// the same value (a mod can alias built-in content), but each key is registered once.
+#[derive(Resource, Debug, Clone)]
+pub struct ContentRegistry {
+    items: ContentTable<ItemId>,
+    mobs: ContentTable<MobId>,
+    recipes: ContentTable<RecipeId>,
+    floors: ContentTable<FloorId>,
+    words: ContentTable<WordId>,
+}
+
+impl Default for ContentRegistry {
+    /// A registry holding the built-in content.
+    fn default() -> Self {
+        let mut registry = Self {
+            items: ContentTable::default(),
+            mobs: ContentTable::default(),
+            recipes: ContentTable::default(),
+            floors: ContentTable::default(),
+            words: ContentTable::default(),
+        };
+        registry.register_builtin::<ItemId>();
+        registry.register_builtin::<MobId>();
+        registry.register_builtin::<RecipeId>();
+        registry.register_builtin::<FloorId>();
+        registry.register_builtin::<WordId>();
+        registry
+    }
+}
+
+impl ContentRegistry {
+    fn register_builtin<T: Content>(&mut self) {
+        for &value in T::builtin() {
+            let registered = ContentKey::base(&value.base_path())
+                .map_err(RegistryError::from)
+                .and_then(|key| self.register(key, value));
+            if let Err(e) = registered {
+                panic!("Built-in {value:?} cannot be registered: {e}");
+            }
+        }
+    }
+
+    pub fn register<T: Content>(
+        &mut self,
+        key: ContentKey,
+        value: T,
+    ) -> Result<ContentHandle<T>, RegistryError> {
+        let table = T::table_mut(self);
+        if table.by_key.contains_key(&key) {
+            return Err(RegistryError::DuplicateKey { kind: T::KIND, key });
+        }
+        let index = table.keys.len() as u32;
+        table.by_key.insert(key.clone(), index);
+        table.by_value.entry(value).or_insert(index);
+        table.keys.push(key);
+        table.values.push(value);
+        Ok(ContentHandle {
+            index,
+            marker: PhantomData,
+        })
+    }
+
+    /// Registers `value` in a mod's namespace under its built-in path, e.g. `mymod:iron_sword`.
+    pub fn register_mod<T: Content>(
+        &mut self,
+        mod_name: &str,
+        value: T,
+    ) -> Result<ContentHandle<T>, RegistryError> {
+        let key = ContentKey::new(mod_name, &value.base_path())?;
+        self.register(key, value)
+    }
+
+    /// Makes this the registry saves read and write keys through. Only the first call counts,
+    /// so it is made once mods have loaded.
+    pub fn install(&self) {
+        INSTALLED.set(self.clone()).ok();
+    }
+
+    /// The installed registry, or the built-in content if none was installed.
+    pub fn installed() -> &'static ContentRegistry {
+        INSTALLED.get_or_init(ContentRegistry::default)
+    }
+
+    pub fn table<T: Content>(&self) -> &ContentTable<T> {
+        T::table(self)
+    }
+
+    pub fn handle<T: Content>(&self, key: &ContentKey) -> Option<ContentHandle<T>> {
+        T::table(self).by_key.get(key).map(|&index| ContentHandle {
+            index,
+            marker: PhantomData,
+        })
+    }
+
+    pub fn get<T: Content>(&self, handle: ContentHandle<T>) -> T {
+        T::table(self).values[handle.index as usize]
+    }
+
+    /// The value `source` (`namespace:path`, or a bare `path` in `base`) names.
+    pub fn resolve<T: Content>(&self, source: &str) -> Result<T, RegistryError> {
+        let key = ContentKey::parse(source)?;
+        match self.handle::<T>(&key) {
+            Some(handle) => Ok(self.get(handle)),
+            None => Err(RegistryError::Unknown { kind: T::KIND, key }),
+        }
+    }
+
+    /// The key `value` was first registered under, for writing it to a save or data file.
+    pub fn key_of<T: Content>(&self, value: T) -> Option<&ContentKey> {
+        let table = T::table(self);
+        table
+            .by_value
+            .get(&value)
+            .map(|&index| &table.keys[index as usize])
+    }
+
+    pub fn version(&self) -> ContentVersion {
+        // FNV-1a, so the fingerprint is the same on every build and platform.
+        let mut fingerprint: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                fingerprint ^= u64::from(byte);
+                fingerprint = fingerprint.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        let kinds = [
+            (ContentKind::Item, self.items.keys()),
+            (ContentKind::Mob, self.mobs.keys()),
+            (ContentKind::Recipe, self.recipes.keys()),
+            (ContentKind::Floor, self.floors.keys()),
+            (ContentKind::Word, self.words.keys()),
+        ];
+        for (kind, keys) in kinds {
+            feed(format!("{kind:?}").as_bytes());
+            for key in keys {
+                feed(key.to_string().as_bytes());
+                feed(&[0]);
+            }
+        }
+        ContentVersion {
+            format: CONTENT_FORMAT_VERSION,
+            fingerprint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_content_resolves_under_base() {
+        let registry = ContentRegistry::default();
+
+        assert_eq!(
+            registry.resolve::<ItemId>("base:iron_sword"),
+            Ok(ItemId::IronSword)
+        );
+        assert_eq!(registry.resolve::<MobId>("goblin"), Ok(MobId::Goblin));
+        assert_eq!(
+            registry.resolve::<WordId>("base:amplify"),
+            Ok(WordId::Amplify)
+        );
+        assert_eq!(
+            registry.table::<FloorId>().len(),
+            <FloorId as Content>::builtin().len()
+        );
+        assert_eq!(
+            registry
+                .key_of(RecipeId::IronIngot)
+                .map(ToString::to_string),
+            Some("base:iron_ingot".to_string())
+        );
+    }
+
+    #[test]
+    fn mods_register_their_own_namespace() {
+        let mut registry = ContentRegistry::default();
+        let key = ContentKey::parse("mymod:flame_axe").unwrap();
+
+        let handle = registry.register(key.clone(), ItemId::IronSword).unwrap();
+
+        assert_eq!(registry.get(handle), ItemId::IronSword);
+        assert_eq!(
+            registry.resolve::<ItemId>("mymod:flame_axe"),
+            Ok(ItemId::IronSword)
+        );
+        assert_eq!(
+            registry.key_of(ItemId::IronSword).map(ToString::to_string),
+            Some("base:iron_sword".to_string())
+        );
+        assert_eq!(
+            registry.register(key.clone(), ItemId::Sword),
+            Err(RegistryError::DuplicateKey {
+                kind: ContentKind::Item,
+                key
+            })
+        );
+    }
+
+    #[test]
+    fn mod_keys_use_the_builtin_path() {
+        let mut registry = ContentRegistry::default();
+
+        registry.register_mod("mymod", MobId::Goblin).unwrap();
+
+        assert_eq!(registry.resolve::<MobId>("mymod:goblin"), Ok(MobId::Goblin));
+        assert!(matches!(
+            registry.register_mod("My Mod", MobId::Goblin),
+            Err(RegistryError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_keys_are_errors() {
+        let registry = ContentRegistry::default();
+
+        assert!(matches!(
+            registry.resolve::<MobId>("mymod:dragon"),
+            Err(RegistryError::Unknown {
+                kind: ContentKind::Mob,
+                ..
+            })
+        ));
+        assert!(matches!(
+            registry.resolve::<MobId>("Goblin"),
+            Err(RegistryError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn version_changes_with_registered_content() {
+        let mut registry = ContentRegistry::default();
+        let before = registry.version();
+
+        assert_eq!(before, ContentRegistry::default().version());
+        registry
+            .register(ContentKey::parse("mymod:ogre").unwrap(), MobId::Goblin)
+            .unwrap();
+        assert_ne!(registry.version().fingerprint, before.fingerprint);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Namespace of everything the game ships with.
+pub const BASE_NAMESPACE: &str = "base";
+
+/// Why a string is not a content key.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContentKeyError {
+    #[error("Content key {0:?} has an empty namespace or path")]
+    Empty(String),
+    #[error("Content key {key:?} contains {found:?}; only a-z, 0-9 and _ are allowed")]
+    InvalidCharacter { key: String, found: char },
+}
+
+/// A namespaced content id such as `base:sword`. The namespace is `base` for built-in
+/// content and the mod's name for modded content.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContentKey {
+    namespace: String,
+    path: String,
+}
+
+impl ContentKey {
+    pub fn new(namespace: &str, path: &str) -> Result<Self, ContentKeyError> {
+        let key = || format!("{namespace}:{path}");
+        if namespace.is_empty() || path.is_empty() {
+            return Err(ContentKeyError::Empty(key()));
+        }
+        if let Some(found) = namespace
+            .chars()
+            .chain(path.chars())
+            .find(|c| !matches!(c, 'a'..='z' | '0'..='9' | '_'))
+        {
+            return Err(ContentKeyError::InvalidCharacter { key: key(), found });
+        }
+        Ok(Self {
+            namespace: namespace.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// A key in the `base` namespace.
+    pub fn base(path: &str) -> Result<Self, ContentKeyError> {
+        Self::new(BASE_NAMESPACE, path)
+    }
+
+    /// Parses `namespace:path`. A bare `path` is read as `base:path`.
+    pub fn parse(source: &str) -> Result<Self, ContentKeyError> {
+        match source.split_once(':') {
+            Some((namespace, path)) => Self::new(namespace, path),
+            None => Self::base(source),
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for ContentKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+impl FromStr for ContentKey {
+    type Err = ContentKeyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl TryFrom<String> for ContentKey {
+    type Error = ContentKeyError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<ContentKey> for String {
+    fn from(key: ContentKey) -> Self {
+        key.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_namespaced_and_bare_keys() {
+        let modded = ContentKey::parse("mymod:flame_axe").unwrap();
+        let base = ContentKey::parse("sword").unwrap();
+
+        assert_eq!(modded.namespace(), "mymod");
+        assert_eq!(modded.path(), "flame_axe");
+        assert_eq!(base.to_string(), "base:sword");
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert_eq!(
+            ContentKey::parse("base:"),
+            Err(ContentKeyError::Empty("base:".to_string()))
+        );
+        assert_eq!(
+            ContentKey::parse("base:Flame Axe"),
+            Err(ContentKeyError::InvalidCharacter {
+                key: "base:Flame Axe".to_string(),
+                found: 'F'
+            })
+        );
+        assert!(ContentKey::parse("a:b:c").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_ron_as_a_string() {
+        let key = ContentKey::parse("mymod:flame_axe").unwrap();
+        let text = ron::to_string(&key).unwrap();
+
+        assert_eq!(text, "\"mymod:flame_axe\"");
+        assert_eq!(ron::from_str::<ContentKey>(&text).unwrap(), key);
+    }
+}
@@ -0,0 +1,36 @@
+//! Lookup tables for game data.
+//!
+//! [`Registry`] maps an id to its spec. [`ContentRegistry`] names every item, mob, recipe,
+//! floor and magic word with a namespaced string key (`base:sword`, `mymod:flame_axe`) and
+//! resolves those keys to runtime handles, so saves, data files and mods can refer to content
+//! without depending on enum layout.
+
+mod content;
+mod key;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use bevy::prelude::*;
+
+pub use content::{
+    Content, ContentHandle, ContentKind, ContentRegistry, ContentTable, ContentVersion,
+    RegistryError, CONTENT_FORMAT_VERSION,
+};
+pub use key::{ContentKey, ContentKeyError, BASE_NAMESPACE};
+
+#[derive(Resource)]
+pub struct Registry<K: Eq + Hash, V>(HashMap<K, V>);
+
+impl<K: Eq + Hash + Debug, V> Registry<K, V> {
+    pub fn new(map: HashMap<K, V>) -> Self {
+        Self(map)
+    }
+
+    pub fn get(&self, id: K) -> &V {
+        self.0
+            .get(&id)
+            .unwrap_or_else(|| panic!("No spec for {id:?}"))
+    }
+}
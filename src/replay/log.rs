@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::input::{GameAction, NavigationDirection};
+
+/// One recorded input. Held directions are logged separately from actions because player
+/// movement reads [`HeldDirection`](crate::input::HeldDirection) every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReplayInput {
+    Action(GameAction),
+    Held(Option<NavigationDirection>),
+}
+
+/// An input and the time, in seconds since recording started, it was issued at.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub time: f32,
+    pub input: ReplayInput,
+}
+
+/// Most inputs one recording holds. Inputs past this are dropped rather than letting a long
+/// session grow the log without bound.
+pub const MAX_REPLAY_ENTRIES: usize = 50_000;
+
+/// Recorded inputs plus the seed of every floor generated while recording.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    pub entries: Vec<ReplayEntry>,
+    pub floor_seeds: Vec<u64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Failed to access replay file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse replay: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Failed to serialize replay: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl ReplayLog {
+    /// Appends `input`, returning false once the log already holds [`MAX_REPLAY_ENTRIES`].
+    pub fn record(&mut self, time: f32, input: ReplayInput) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.entries.push(ReplayEntry { time, input });
+        true
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= MAX_REPLAY_ENTRIES
+    }
+
+    /// The most recently recorded held direction, if any was recorded.
+    pub fn last_held(&self) -> Option<Option<NavigationDirection>> {
+        self.entries.iter().rev().find_map(|entry| match entry.input {
+            ReplayInput::Held(direction) => Some(direction),
+            ReplayInput::Action(_) => None,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.entries.last().map_or(0.0, |entry| entry.time)
+    }
+
+    /// Entries from `cursor` onward whose time is at or before `time`.
+    pub fn due(&self, cursor: usize, time: f32) -> &[ReplayEntry] {
+        let pending = self.entries.get(cursor..).unwrap_or_default();
+        let count = pending.iter().take_while(|entry| entry.time <= time).count();
+        &pending[..count]
+    }
+
+    pub fn to_ron(&self) -> Result<String, ReplayError> {
+        Ok(ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?)
+    }
+
+    pub fn from_ron(source: &str) -> Result<Self, ReplayError> {
+        Ok(ron::from_str(source)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::from_ron(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log() -> ReplayLog {
+        let mut log = ReplayLog {
+            floor_seeds: vec![7, 42],
+            ..default()
+        };
+        log.record(0.0, ReplayInput::Held(Some(NavigationDirection::Left)));
+        log.record(0.5, ReplayInput::Action(GameAction::Attack));
+        log.record(0.5, ReplayInput::Held(None));
+        log.record(1.25, ReplayInput::Action(GameAction::OpenInventory));
+        log
+    }
+
+    #[test]
+    fn due_returns_entries_up_to_time() {
+        let log = log();
+
+        assert_eq!(log.due(0, 0.4).len(), 1);
+        assert_eq!(log.due(1, 0.5).len(), 2);
+        assert!(log.due(3, 1.0).is_empty());
+        assert!(log.due(10, 5.0).is_empty());
+    }
+
+    #[test]
+    fn last_held_skips_actions() {
+        let log = log();
+
+        assert_eq!(log.last_held(), Some(None));
+        assert_eq!(ReplayLog::default().last_held(), None);
+    }
+
+    #[test]
+    fn record_stops_at_the_cap() {
+        let mut log = ReplayLog::default();
+        for _ in 0..MAX_REPLAY_ENTRIES {
+            assert!(log.record(0.0, ReplayInput::Action(GameAction::Attack)));
+        }
+
+        assert!(!log.record(1.0, ReplayInput::Action(GameAction::Attack)));
+        assert_eq!(log.entries.len(), MAX_REPLAY_ENTRIES);
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let log = log();
+        let source = log.to_ron().unwrap();
+
+        assert_eq!(ReplayLog::from_ron(&source).unwrap(), log);
+        assert_eq!(log.duration(), 1.25);
+    }
+}
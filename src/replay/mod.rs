@@ -0,0 +1,5 @@
+mod log;
+mod plugin;
+
+pub use log::{ReplayEntry, ReplayError, ReplayInput, ReplayLog, MAX_REPLAY_ENTRIES};
+pub use plugin::{replay_inactive, replay_path, ReplayPlugin, ReplayState, REPLAY_DIR};
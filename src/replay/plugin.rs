@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::dungeon::{FloorSeed, FloorSeedQueue};
+use crate::input::{GameAction, HeldDirection};
+
+use super::log::{ReplayError, ReplayInput, ReplayLog, MAX_REPLAY_ENTRIES};
+
+/// Directory replays are saved to and loaded from, relative to the working directory.
+pub const REPLAY_DIR: &str = "replays";
+
+/// Path of the replay file called `name`.
+pub fn replay_path(name: &str) -> PathBuf {
+    PathBuf::from(REPLAY_DIR).join(format!("{name}.replay.ron"))
+}
+
+struct Playback {
+    log: ReplayLog,
+    cursor: usize,
+    started_at: f32,
+}
+
+/// Session recording and, while a replay is running, its playback position.
+///
+/// Recording starts at launch and captures every [`GameAction`], held-direction change, and
+/// floor seed, up to [`MAX_REPLAY_ENTRIES`] inputs. Playback feeds the inputs back at their original times and queues the seeds so
+/// the same floors are generated. Anything else rolled with a thread RNG (loot, item quality)
+/// is not reproduced, and playback runs against the game as it currently is, so start it from
+/// the same point the recording was started from.
+#[derive(Resource, Default)]
+pub struct ReplayState {
+    recording: ReplayLog,
+    recording_started_at: f32,
+    playback: Option<Playback>,
+}
+
+impl ReplayState {
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    pub fn recording(&self) -> &ReplayLog {
+        &self.recording
+    }
+
+    /// Discards the current recording and starts a new one at `now`.
+    pub fn restart_recording(&mut self, now: f32) {
+        self.recording = ReplayLog::default();
+        self.recording_started_at = now;
+    }
+
+    /// Writes the current recording to [`replay_path`]`(name)`.
+    pub fn save(&self, name: &str) -> Result<PathBuf, ReplayError> {
+        let path = replay_path(name);
+        self.recording.save(&path)?;
+        Ok(path)
+    }
+
+    /// Starts playing `log` from `now`, queueing its floor seeds for the next floors.
+    pub fn play(&mut self, log: ReplayLog, now: f32, seeds: &mut FloorSeedQueue) {
+        seeds.0 = log.floor_seeds.iter().copied().collect();
+        self.playback = Some(Playback {
+            log,
+            cursor: 0,
+            started_at: now,
+        });
+    }
+
+    /// Stops playback, returning whether a replay was running.
+    pub fn stop(&mut self, seeds: &mut FloorSeedQueue) -> bool {
+        seeds.0.clear();
+        self.playback.take().is_some()
+    }
+}
+
+/// Run condition: true unless a replay is feeding inputs.
+pub fn replay_inactive(replay: Option<Res<ReplayState>>) -> bool {
+    replay.is_none_or(|r| !r.is_playing())
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayState>()
+            .init_resource::<FloorSeedQueue>()
+            .add_systems(PreUpdate, play_inputs.run_if(not(replay_inactive)))
+            .add_systems(
+                Update,
+                (
+                    record_inputs,
+                    record_floor_seed.run_if(resource_exists_and_changed::<FloorSeed>),
+                )
+                    .run_if(replay_inactive),
+            );
+    }
+}
+
+fn record_inputs(
+    time: Res<Time>,
+    held: Res<HeldDirection>,
+    mut actions: MessageReader<GameAction>,
+    mut replay: ResMut<ReplayState>,
+) {
+    if replay.recording.is_full() {
+        return;
+    }
+    let elapsed = time.elapsed_secs() - replay.recording_started_at;
+
+    if held.is_changed() && replay.recording.last_held() != Some(held.0) {
+        replay.recording.record(elapsed, ReplayInput::Held(held.0));
+    }
+    for action in actions.read() {
+        replay.recording.record(elapsed, ReplayInput::Action(*action));
+    }
+
+    if replay.recording.is_full() {
+        warn!(
+            "Replay recording hit {} inputs and stopped; `replay restart` starts a new one",
+            MAX_REPLAY_ENTRIES
+        );
+    }
+}
+
+fn record_floor_seed(seed: Res<FloorSeed>, mut replay: ResMut<ReplayState>) {
+    replay.recording.floor_seeds.push(seed.0);
+}
+
+fn play_inputs(
+    time: Res<Time>,
+    mut replay: ResMut<ReplayState>,
+    mut held: ResMut<HeldDirection>,
+    mut action_writer: MessageWriter<GameAction>,
+) {
+    let Some(playback) = replay.playback.as_mut() else {
+        return;
+    };
+
+    let elapsed = time.elapsed_secs() - playback.started_at;
+    let due = playback.log.due(playback.cursor, elapsed);
+    playback.cursor += due.len();
+
+    for entry in due {
+        match entry.input {
+            ReplayInput::Action(action) => {
+                action_writer.write(action);
+            }
+            ReplayInput::Held(direction) => held.0 = direction,
+        }
+    }
+
+    if playback.cursor >= playback.log.entries.len() {
+        info!(duration = playback.log.duration(), "Replay finished");
+        held.0 = None;
+        replay.playback = None;
+    }
+}
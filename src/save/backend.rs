@@ -0,0 +1,171 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Saves go here unless `SAVE_DIR_ENV` names another directory.
+pub const DEFAULT_SAVE_DIR: &str = "saves";
+
+/// Environment variable that moves the save directory.
+pub const SAVE_DIR_ENV: &str = "RANDR_SAVE_DIR";
+
+/// Somewhere saves are kept. Paths are relative to the backend's save root.
+///
+/// `pull` and `push` are no-ops here; a backend that syncs saves elsewhere, such as a
+/// platform's cloud storage, fetches newer copies in `pull` and uploads in `push`.
+pub trait SaveBackend: Send + Sync + 'static {
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Replaces the save at `path`. A reader sees the old save or the new one, never a
+    /// mix of the two, even if the game dies partway through.
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()>;
+
+    fn remove(&self, path: &Path) -> io::Result<()>;
+
+    /// Runs once, before any save is read.
+    fn pull(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Runs when the game exits, after the last save is written.
+    fn push(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Saves as files under a directory on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSaveBackend {
+    root: PathBuf,
+}
+
+impl FileSaveBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Uses the directory named by `SAVE_DIR_ENV`, or `DEFAULT_SAVE_DIR`.
+    pub fn from_env() -> Self {
+        match std::env::var_os(SAVE_DIR_ENV) {
+            Some(dir) if !dir.is_empty() => Self::new(dir),
+            _ => Self::new(DEFAULT_SAVE_DIR),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+impl SaveBackend for FileSaveBackend {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(self.resolve(path))
+    }
+
+    /// Writes beside the save first and renames over it once the new contents are on disk.
+    fn write(&self, path: &Path, contents: &str) -> io::Result<()> {
+        let path = self.resolve(path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        let result = (|| {
+            use std::io::Write;
+            let mut file = std::fs::File::create(&temp)?;
+            file.write_all(contents.as_bytes())?;
+            file.sync_all()?;
+            std::fs::rename(&temp, &path)
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        result
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        std::fs::remove_file(self.resolve(path))
+    }
+}
+
+static SAVE_BACKEND: OnceLock<Box<dyn SaveBackend>> = OnceLock::new();
+
+/// Makes `backend` the one every save goes through. Call it before the game's plugins are
+/// built, since they read their saves as they are added. Hands the backend back if one is
+/// already in use.
+pub fn set_save_backend(backend: impl SaveBackend) -> Result<(), Box<dyn SaveBackend>> {
+    SAVE_BACKEND.set(Box::new(backend))?;
+    pull(save_backend());
+    Ok(())
+}
+
+/// The backend saves go through, a `FileSaveBackend::from_env` unless another was set.
+pub fn save_backend() -> &'static dyn SaveBackend {
+    SAVE_BACKEND
+        .get_or_init(|| {
+            let backend = Box::new(FileSaveBackend::from_env());
+            pull(backend.as_ref());
+            backend
+        })
+        .as_ref()
+}
+
+fn pull(backend: &dyn SaveBackend) {
+    if let Err(e) = backend.pull() {
+        bevy::log::warn!("Failed to fetch saves: {e}");
+    }
+}
+
+/// Reads the save at `path` through the active backend.
+pub fn read(path: &Path) -> io::Result<String> {
+    save_backend().read(path)
+}
+
+/// Replaces the save at `path` through the active backend.
+pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+    save_backend().write(path, contents)
+}
+
+/// Deletes the save at `path` through the active backend.
+pub fn remove(path: &Path) -> io::Result<()> {
+    save_backend().remove(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_backend() -> FileSaveBackend {
+        FileSaveBackend::new(
+            std::env::temp_dir().join(format!("randr-saves-{}", uuid::Uuid::new_v4())),
+        )
+    }
+
+    #[test]
+    fn writes_replace_saves_and_leave_no_temp_file() {
+        let backend = temp_backend();
+        let path = Path::new("slots/slot_1.ron");
+
+        backend.write(path, "first").unwrap();
+        backend.write(path, "second").unwrap();
+
+        assert_eq!(backend.read(path).unwrap(), "second");
+        let files: Vec<_> = std::fs::read_dir(backend.root().join("slots"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(files, ["slot_1.ron"]);
+
+        backend.remove(path).unwrap();
+        assert_eq!(
+            backend.read(path).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        std::fs::remove_dir_all(backend.root()).unwrap();
+    }
+}
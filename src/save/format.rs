@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::backend::{read, write};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SaveError {
+    #[error("Failed to access save: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to parse save: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Failed to serialize save: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl SaveError {
+    /// Whether the save simply hasn't been written yet.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, SaveError::Io(e) if e.kind() == std::io::ErrorKind::NotFound)
+    }
+}
+
+pub fn to_ron<T: Serialize>(value: &T) -> Result<String, SaveError> {
+    Ok(ron::ser::to_string_pretty(
+        value,
+        ron::ser::PrettyConfig::default(),
+    )?)
+}
+
+pub fn from_ron<T: DeserializeOwned>(source: &str) -> Result<T, SaveError> {
+    Ok(ron::from_str(source)?)
+}
+
+/// Writes `value` to the save at `path` through the active backend.
+pub fn save_ron<T: Serialize>(path: impl AsRef<Path>, value: &T) -> Result<(), SaveError> {
+    write(path.as_ref(), &to_ron(value)?)?;
+    Ok(())
+}
+
+/// Reads the save at `path` through the active backend.
+pub fn read_ron<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, SaveError> {
+    from_ron(&read(path.as_ref())?)
+}
+
+/// Reads the save at `path`, starting from the default if there is none yet or it can't be
+/// read.
+pub fn load_ron<T: DeserializeOwned + Default>(path: impl AsRef<Path>) -> T {
+    let path = path.as_ref();
+    match read_ron(path) {
+        Ok(value) => value,
+        Err(e) if e.is_not_found() => T::default(),
+        Err(e) => {
+            bevy::log::warn!("Starting {} fresh: {e}", path.display());
+            T::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_saves_are_not_found() {
+        let error = read_ron::<Vec<u32>>("no/such/save.ron").unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(load_ron::<Vec<u32>>("no/such/save.ron"), Vec::<u32>::new());
+        assert!(!from_ron::<Vec<u32>>("[oops").unwrap_err().is_not_found());
+    }
+}
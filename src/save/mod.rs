@@ -0,0 +1,14 @@
+//! Where saves live. Every save in the game goes through one `SaveBackend`, so the save
+//! directory can be moved and a synced store swapped in without touching the code that
+//! saves. Saves are RON, written with `save_ron` and read back with `load_ron`.
+
+mod backend;
+mod format;
+mod plugin;
+
+pub use backend::{
+    read, remove, save_backend, set_save_backend, write, FileSaveBackend, SaveBackend,
+    DEFAULT_SAVE_DIR, SAVE_DIR_ENV,
+};
+pub use format::{from_ron, load_ron, read_ron, save_ron, to_ron, SaveError};
+pub use plugin::SavePlugin;
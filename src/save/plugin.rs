@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use super::backend::save_backend;
+
+/// Hands the saves to the backend's `push` when the game exits.
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, push_saves.run_if(on_message::<AppExit>));
+    }
+}
+
+fn push_saves() {
+    if let Err(e) = save_backend().push() {
+        warn!("Failed to sync saves: {e}");
+    }
+}
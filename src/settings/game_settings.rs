@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::combat::CombatLogVerbosity;
+use crate::difficulty::AdaptiveDifficultyBand;
+use crate::i18n::Locale;
+use crate::item::enums::ItemQuality;
+
+/// Where settings are saved, relative to the save directory.
+pub const SETTINGS_SAVE_PATH: &str = "settings.ron";
+
+/// Player preferences that outlive a session.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSettings {
+    #[serde(default)]
+    pub combat_log: CombatLogVerbosity,
+    #[serde(default)]
+    pub adaptive_difficulty: AdaptiveDifficultyBand,
+    /// Tones for menu navigation, confirmations and errors.
+    #[serde(default = "enabled")]
+    pub ui_sounds: bool,
+    /// Gamepad rumble on hits and level-ups.
+    #[serde(default = "enabled")]
+    pub rumble: bool,
+    /// Numbers floating over whoever takes damage in the dungeon.
+    #[serde(default = "enabled")]
+    pub damage_numbers: bool,
+    /// Mirror toasts, menu selections and combat results to stdout and a text log.
+    #[serde(default)]
+    pub screen_reader: bool,
+    /// How large text and the layout around it are drawn on every screen.
+    #[serde(default)]
+    pub text_scale: TextScale,
+    /// Colors for health bars, item qualities and selection highlights.
+    #[serde(default)]
+    pub color_palette: ColorPalette,
+    /// Which events pop a toast.
+    #[serde(default)]
+    pub toasts: ToastThresholds,
+    /// Set once the player asks never to see tutorial tips again.
+    #[serde(default)]
+    pub never_show_tips: bool,
+    /// The language UI text is shown in.
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            combat_log: CombatLogVerbosity::default(),
+            adaptive_difficulty: AdaptiveDifficultyBand::default(),
+            ui_sounds: true,
+            rumble: true,
+            damage_numbers: true,
+            screen_reader: false,
+            text_scale: TextScale::default(),
+            color_palette: ColorPalette::default(),
+            toasts: ToastThresholds::default(),
+            never_show_tips: false,
+            locale: Locale::default(),
+        }
+    }
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// Which events pop a toast and how pickups are grouped. Events under a threshold happen
+/// quietly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToastThresholds {
+    pub heal_min: i32,
+    pub gold_change: i32,
+    pub gold_earned: i32,
+    pub gold_spent: i32,
+    /// Gold from a single kill.
+    pub kill_gold: i32,
+    pub loot_items: i32,
+    pub transaction: i32,
+    /// Dungeon drops below this quality are picked up without a toast.
+    pub drop_quality: ItemQuality,
+    pub level_ups: bool,
+    /// Pickups this many seconds after the first are summed into one toast. 0 shows each
+    /// pickup on its own.
+    pub batch_secs: f32,
+}
+
+impl Default for ToastThresholds {
+    fn default() -> Self {
+        Self {
+            heal_min: 20,
+            gold_change: 50,
+            gold_earned: 50,
+            gold_spent: 50,
+            kill_gold: 0,
+            loot_items: 3,
+            transaction: 100,
+            drop_quality: ItemQuality::Poor,
+            level_ups: true,
+            batch_secs: 1.0,
+        }
+    }
+}
+
+/// Sizes UI text can be drawn at. Applied through Bevy's `UiScale`, so every screen and
+/// widget grows its layout with its text without knowing about the setting.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum TextScale {
+    Small,
+    #[default]
+    Normal,
+    Large,
+    Larger,
+    Largest,
+}
+
+impl TextScale {
+    pub const ALL: [TextScale; 5] = [
+        TextScale::Small,
+        TextScale::Normal,
+        TextScale::Large,
+        TextScale::Larger,
+        TextScale::Largest,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// What UI sizes are multiplied by.
+    pub fn factor(self) -> f32 {
+        match self {
+            TextScale::Small => 0.85,
+            TextScale::Normal => 1.0,
+            TextScale::Large => 1.25,
+            TextScale::Larger => 1.5,
+            TextScale::Largest => 2.0,
+        }
+    }
+
+    /// Localization key for the size's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            TextScale::Small => "text_scale.small",
+            TextScale::Normal => "text_scale.normal",
+            TextScale::Large => "text_scale.large",
+            TextScale::Larger => "text_scale.larger",
+            TextScale::Largest => "text_scale.largest",
+        }
+    }
+}
+
+/// Color schemes the UI can be drawn in, each kept apart for one kind of color blindness.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+    /// Red-green, weak green.
+    Deuteranopia,
+    /// Red-green, weak red.
+    Protanopia,
+    /// Blue-yellow.
+    Tritanopia,
+}
+
+impl ColorPalette {
+    pub const ALL: [ColorPalette; 4] = [
+        ColorPalette::Standard,
+        ColorPalette::Deuteranopia,
+        ColorPalette::Protanopia,
+        ColorPalette::Tritanopia,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the palette's name.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            ColorPalette::Standard => "palette.standard",
+            ColorPalette::Deuteranopia => "palette.deuteranopia",
+            ColorPalette::Protanopia => "palette.protanopia",
+            ColorPalette::Tritanopia => "palette.tritanopia",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn settings_round_trip_through_ron() {
+        let settings = GameSettings {
+            combat_log: CombatLogVerbosity::Detailed,
+            adaptive_difficulty: AdaptiveDifficultyBand::Wide,
+            ui_sounds: false,
+            rumble: true,
+            damage_numbers: false,
+            screen_reader: true,
+            text_scale: TextScale::Larger,
+            color_palette: ColorPalette::Tritanopia,
+            toasts: ToastThresholds {
+                drop_quality: ItemQuality::Masterworked,
+                level_ups: false,
+                ..default()
+            },
+            never_show_tips: true,
+            locale: Locale::Spanish,
+        };
+        let ron = to_ron(&settings).unwrap();
+        assert_eq!(from_ron::<GameSettings>(&ron).unwrap(), settings);
+    }
+
+    #[test]
+    fn text_scale_cycles_back_to_the_smallest() {
+        assert_eq!(TextScale::default().factor(), 1.0);
+        assert_eq!(TextScale::Normal.next(), TextScale::Large);
+        assert_eq!(TextScale::Largest.next(), TextScale::Small);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        assert_eq!(
+            from_ron::<GameSettings>("()").unwrap(),
+            GameSettings::default()
+        );
+    }
+
+    #[test]
+    fn toast_thresholds_fill_in_missing_fields() {
+        let settings =
+            from_ron::<GameSettings>("(toasts: (gold_earned: 500, batch_secs: 0.0))").unwrap();
+        assert_eq!(settings.toasts.gold_earned, 500);
+        assert_eq!(settings.toasts.batch_secs, 0.0);
+        assert_eq!(settings.toasts.heal_min, ToastThresholds::default().heal_min);
+    }
+}
@@ -0,0 +1,7 @@
+mod game_settings;
+mod plugin;
+
+pub use game_settings::{
+    ColorPalette, GameSettings, TextScale, ToastThresholds, SETTINGS_SAVE_PATH,
+};
+pub use plugin::SettingsPlugin;
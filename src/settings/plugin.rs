@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::save::{load_ron, save_ron};
+use crate::states::AppState;
+
+use super::game_settings::{GameSettings, SETTINGS_SAVE_PATH};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<GameSettings>(SETTINGS_SAVE_PATH))
+            .add_systems(
+                Update,
+                (
+                    cycle_combat_log.run_if(on_message::<GameAction>),
+                    cycle_adaptive_difficulty.run_if(on_message::<GameAction>),
+                    toggle_feedback.run_if(on_message::<GameAction>),
+                    cycle_text_scale.run_if(on_message::<GameAction>),
+                    cycle_color_palette.run_if(on_message::<GameAction>),
+                    cycle_locale
+                        .run_if(in_state(AppState::Menu))
+                        .run_if(on_message::<GameAction>),
+                    apply_text_scale.run_if(resource_changed::<GameSettings>),
+                    save_settings.run_if(
+                        resource_changed::<GameSettings>.and(not(resource_added::<GameSettings>)),
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn save_settings(settings: Res<GameSettings>) {
+    if let Err(e) = save_ron(SETTINGS_SAVE_PATH, &*settings) {
+        warn!("{e}");
+    }
+}
+
+fn cycle_combat_log(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleCombatLog {
+            continue;
+        }
+        settings.combat_log = settings.combat_log.next();
+        toast_writer.write(ShowToast::new(loc.format(
+            "combat_log.verbosity_changed",
+            &[("level", &loc.get(settings.combat_log.label_key()))],
+        )));
+    }
+}
+
+fn cycle_adaptive_difficulty(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleAdaptiveDifficulty {
+            continue;
+        }
+        settings.adaptive_difficulty = settings.adaptive_difficulty.next();
+        toast_writer.write(ShowToast::new(loc.format(
+            "difficulty.changed",
+            &[("band", &loc.get(settings.adaptive_difficulty.label_key()))],
+        )));
+    }
+}
+
+fn toggle_feedback(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        let (enabled, key) = match action {
+            GameAction::ToggleUiSounds => {
+                settings.ui_sounds = !settings.ui_sounds;
+                (settings.ui_sounds, "feedback.ui_sounds")
+            }
+            GameAction::ToggleRumble => {
+                settings.rumble = !settings.rumble;
+                (settings.rumble, "feedback.rumble")
+            }
+            GameAction::ToggleDamageNumbers => {
+                settings.damage_numbers = !settings.damage_numbers;
+                (settings.damage_numbers, "feedback.damage_numbers")
+            }
+            GameAction::ToggleScreenReader => {
+                settings.screen_reader = !settings.screen_reader;
+                (settings.screen_reader, "feedback.screen_reader")
+            }
+            _ => continue,
+        };
+        let state = if enabled {
+            "feedback.on"
+        } else {
+            "feedback.off"
+        };
+        toast_writer.write(ShowToast::new(
+            loc.format(key, &[("state", &loc.get(state))]),
+        ));
+    }
+}
+
+fn cycle_text_scale(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleTextScale {
+            continue;
+        }
+        settings.text_scale = settings.text_scale.next();
+        toast_writer.write(ShowToast::new(loc.format(
+            "text_scale.changed",
+            &[("size", &loc.get(settings.text_scale.label_key()))],
+        )));
+    }
+}
+
+fn cycle_color_palette(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CyclePalette {
+            continue;
+        }
+        settings.color_palette = settings.color_palette.next();
+        toast_writer.write(ShowToast::new(loc.format(
+            "palette.changed",
+            &[("palette", &loc.get(settings.color_palette.label_key()))],
+        )));
+    }
+}
+
+/// Only offered on the main menu, where the whole screen can be rebuilt in the new language.
+fn cycle_locale(
+    mut action_reader: MessageReader<GameAction>,
+    mut settings: ResMut<GameSettings>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleLanguage {
+            continue;
+        }
+        settings.locale = settings.locale.next();
+        toast_writer.write(ShowToast::new(loc.format(
+            "language.changed",
+            &[("language", &loc.get(settings.locale.label_key()))],
+        )));
+    }
+}
+
+/// Scales every UI node, and the text in it, by the chosen text size.
+fn apply_text_scale(settings: Res<GameSettings>, mut ui_scale: ResMut<UiScale>) {
+    let factor = settings.text_scale.factor();
+    if ui_scale.0 != factor {
+        ui_scale.0 = factor;
+    }
+}
@@ -1,19 +1,24 @@
-pub mod bonuses;
 pub mod events;
 pub mod plugin;
 
 use std::collections::HashMap;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-pub use bonuses::{
+use crate::game::CharacterResource;
+
+pub use randr_core::skills::{
     blacksmith_bonus_item_chance, blacksmith_quality_bonus, blacksmith_speed_multiplier,
     combat_attack_bonus, combat_defense_bonus, BlacksmithBonuses, CombatBonuses, MiningBonuses,
 };
 pub use events::{SkillLeveledUp, SkillXpGained};
 pub use plugin::SkillsPlugin;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Where skill levels are saved in each character's slot.
+pub const SKILLS_SAVE_PATH: &str = "skills.ron";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SkillType {
     Blacksmith,
     Mining,
@@ -34,7 +39,7 @@ impl SkillType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skill {
     pub skill_type: SkillType,
     pub level: u32,
@@ -51,7 +56,7 @@ impl Skill {
     }
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct Skills {
     skills: HashMap<SkillType, Skill>,
 }
@@ -62,6 +67,10 @@ impl Default for Skills {
     }
 }
 
+impl CharacterResource for Skills {
+    const FILE: &'static str = SKILLS_SAVE_PATH;
+}
+
 impl Skills {
     pub fn new() -> Self {
         let mut skills = HashMap::new();
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::game::CharacterDataExt;
+
 use super::events::{SkillLeveledUp, SkillXpGained};
 use super::{xp_for_level, Skills};
 
@@ -7,7 +9,7 @@ pub struct SkillsPlugin;
 
 impl Plugin for SkillsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Skills::new())
+        app.init_character_resource::<Skills>()
             .add_message::<SkillXpGained>()
             .add_message::<SkillLeveledUp>()
             .add_systems(Update, process_xp_gained.run_if(on_message::<SkillXpGained>));
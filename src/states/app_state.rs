@@ -1,12 +1,30 @@
 use bevy::prelude::*;
 use bevy::state::state::StateTransitionEvent;
 
+use super::ScreenSet;
+
 #[derive(Message, Debug, Clone, Copy)]
 pub enum StateTransitionRequest {
     Menu,
     Dungeon,
     Profile,
     Keybinds,
+    Mods,
+    WorldMap,
+    Guild,
+    EventShop,
+    LootFilter,
+    AuctionHouse,
+    Contracts,
+    RunPlanner,
+    Leaderboard,
+    Ranch,
+    TrophyRoom,
+    Spellcraft,
+    WordIndex,
+    Roster,
+    Workers,
+    TownUpgrades,
 }
 
 impl StateTransitionRequest {
@@ -16,6 +34,22 @@ impl StateTransitionRequest {
             StateTransitionRequest::Dungeon => AppState::Dungeon,
             StateTransitionRequest::Profile => AppState::Profile,
             StateTransitionRequest::Keybinds => AppState::Keybinds,
+            StateTransitionRequest::Mods => AppState::Mods,
+            StateTransitionRequest::WorldMap => AppState::WorldMap,
+            StateTransitionRequest::Guild => AppState::Guild,
+            StateTransitionRequest::EventShop => AppState::EventShop,
+            StateTransitionRequest::LootFilter => AppState::LootFilter,
+            StateTransitionRequest::AuctionHouse => AppState::AuctionHouse,
+            StateTransitionRequest::Contracts => AppState::Contracts,
+            StateTransitionRequest::RunPlanner => AppState::RunPlanner,
+            StateTransitionRequest::Leaderboard => AppState::Leaderboard,
+            StateTransitionRequest::Ranch => AppState::Ranch,
+            StateTransitionRequest::TrophyRoom => AppState::TrophyRoom,
+            StateTransitionRequest::Spellcraft => AppState::Spellcraft,
+            StateTransitionRequest::WordIndex => AppState::WordIndex,
+            StateTransitionRequest::Roster => AppState::Roster,
+            StateTransitionRequest::Workers => AppState::Workers,
+            StateTransitionRequest::TownUpgrades => AppState::TownUpgrades,
         }
     }
 }
@@ -28,6 +62,22 @@ impl From<AppState> for StateTransitionRequest {
             AppState::Dungeon => StateTransitionRequest::Dungeon,
             AppState::Profile => StateTransitionRequest::Profile,
             AppState::Keybinds => StateTransitionRequest::Keybinds,
+            AppState::Mods => StateTransitionRequest::Mods,
+            AppState::WorldMap => StateTransitionRequest::WorldMap,
+            AppState::Guild => StateTransitionRequest::Guild,
+            AppState::EventShop => StateTransitionRequest::EventShop,
+            AppState::LootFilter => StateTransitionRequest::LootFilter,
+            AppState::AuctionHouse => StateTransitionRequest::AuctionHouse,
+            AppState::Contracts => StateTransitionRequest::Contracts,
+            AppState::RunPlanner => StateTransitionRequest::RunPlanner,
+            AppState::Leaderboard => StateTransitionRequest::Leaderboard,
+            AppState::Ranch => StateTransitionRequest::Ranch,
+            AppState::TrophyRoom => StateTransitionRequest::TrophyRoom,
+            AppState::Spellcraft => StateTransitionRequest::Spellcraft,
+            AppState::WordIndex => StateTransitionRequest::WordIndex,
+            AppState::Roster => StateTransitionRequest::Roster,
+            AppState::Workers => StateTransitionRequest::Workers,
+            AppState::TownUpgrades => StateTransitionRequest::TownUpgrades,
         }
     }
 }
@@ -40,6 +90,22 @@ pub enum AppState {
     Dungeon,
     Profile,
     Keybinds,
+    Mods,
+    WorldMap,
+    Guild,
+    EventShop,
+    LootFilter,
+    AuctionHouse,
+    Contracts,
+    RunPlanner,
+    Leaderboard,
+    Ranch,
+    TrophyRoom,
+    Spellcraft,
+    WordIndex,
+    Roster,
+    Workers,
+    TownUpgrades,
 }
 
 #[derive(Resource, Default)]
@@ -54,6 +120,10 @@ impl Plugin for StateTransitionPlugin {
         app.init_state::<AppState>()
             .init_resource::<PreviousState>()
             .add_message::<StateTransitionRequest>()
+            .configure_sets(
+                Update,
+                (ScreenSet::Input, ScreenSet::Logic, ScreenSet::Render).chain(),
+            )
             .add_systems(StateTransition, track_state_transitions)
             .add_systems(
                 PreUpdate,
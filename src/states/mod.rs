@@ -1,3 +1,5 @@
 mod app_state;
+mod screen_set;
 
 pub use app_state::{AppState, PreviousState, StateTransitionPlugin, StateTransitionRequest};
+pub use screen_set::ScreenSet;
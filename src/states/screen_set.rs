@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+/// The order screen systems run in during `Update`.
+///
+/// Screens read the player's actions in [`ScreenSet::Input`] and turn them into requests,
+/// the game plugins apply those requests in [`ScreenSet::Logic`], and screens redraw from
+/// the result in [`ScreenSet::Render`]. Everything a key press changes is on screen the
+/// same frame.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScreenSet {
+    Input,
+    Logic,
+    Render,
+}
@@ -1,7 +1 @@
-pub mod definition;
-pub mod traits;
-pub use definition::*;
-pub use traits::*;
-
-#[cfg(test)]
-mod tests;
+pub use randr_core::stats::*;
@@ -1,18 +1,309 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::inventory::{
+    AddItemResult, Inventory, InventoryError, InventoryItem, ManagesItems, SavedInventory,
+};
+use crate::item::{Item, ItemId, ItemRegistry};
+
+use super::enums::{DepositFilter, StorageSort};
+
+/// Name of the tab every storage starts with. Items that were never filed anywhere else
+/// show up here.
+pub const DEFAULT_TAB_NAME: &str = "General";
 
-use crate::inventory::Inventory;
+/// Where storage is saved in each character's slot.
+pub const STORAGE_SAVE_PATH: &str = "storage.ron";
+
+/// Tabs a storage may hold before perks raise the limit.
+pub const BASE_TAB_LIMIT: usize = 4;
+
+/// Where a stored stack has been filed, and when it was last added to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Placement {
+    tab: usize,
+    deposited: u64,
+}
 
 #[derive(Resource, Debug)]
 pub struct Storage {
     pub inventory: Inventory,
+    pub tabs: Vec<String>,
+    pub active_tab: usize,
+    pub max_tabs: usize,
+    /// Case-insensitive filter on item names; empty shows everything in the tab.
+    pub search: String,
+    pub sort: StorageSort,
+    placements: HashMap<Uuid, Placement>,
+    deposits: u64,
+}
+
+/// A [`Storage`] as written to a save. The search and active tab start fresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedStorage {
+    inventory: SavedInventory,
+    tabs: Vec<String>,
+    max_tabs: usize,
+    #[serde(default)]
+    sort: StorageSort,
+    #[serde(default)]
+    placements: HashMap<Uuid, Placement>,
+    #[serde(default)]
+    deposits: u64,
 }
 
 impl Storage {
     pub fn new() -> Self {
+        Self::from_inventory(Inventory::new_unlimited())
+    }
+
+    /// Empty storage with `slots` capacity, or unlimited when `None`.
+    pub fn with_slots(slots: Option<usize>) -> Self {
+        Self::from_inventory(
+            slots.map_or_else(Inventory::new_unlimited, Inventory::with_max_slots),
+        )
+    }
+
+    fn from_inventory(inventory: Inventory) -> Self {
         Self {
-            inventory: Inventory::new_unlimited(),
+            inventory,
+            tabs: vec![DEFAULT_TAB_NAME.to_string()],
+            active_tab: 0,
+            max_tabs: BASE_TAB_LIMIT,
+            search: String::new(),
+            sort: StorageSort::default(),
+            placements: HashMap::new(),
+            deposits: 0,
+        }
+    }
+
+    pub fn to_saved(&self) -> SavedStorage {
+        SavedStorage {
+            inventory: SavedInventory::from(&self.inventory),
+            tabs: self.tabs.clone(),
+            max_tabs: self.max_tabs,
+            sort: self.sort,
+            placements: self.placements.clone(),
+            deposits: self.deposits,
+        }
+    }
+
+    /// Rebuilds a saved storage. Items whose spec is no longer registered are dropped.
+    pub fn from_saved(saved: &SavedStorage, registry: &ItemRegistry) -> Self {
+        let mut tabs = saved.tabs.clone();
+        if tabs.is_empty() {
+            tabs.push(DEFAULT_TAB_NAME.to_string());
+        }
+        Self {
+            tabs,
+            max_tabs: saved.max_tabs,
+            sort: saved.sort,
+            placements: saved.placements.clone(),
+            deposits: saved.deposits,
+            ..Self::from_inventory(saved.inventory.restore(registry))
+        }
+    }
+
+    /// Stores `item`, filing a new stack under the active tab. Stacking onto an existing
+    /// stack keeps its tab but counts as a fresh deposit for [`StorageSort::Recent`].
+    pub fn deposit(&mut self, item: Item) -> Result<AddItemResult, InventoryError> {
+        let result = self.add_to_inv(item)?;
+        // Storage has its own Recent sort; new badges are for the backpack.
+        self.inventory.items[result.slot_index].is_new = false;
+        let uuid = self.inventory.items[result.slot_index].uuid();
+
+        self.deposits += 1;
+        let active_tab = self.active_tab;
+        let placement = self.placements.entry(uuid).or_insert(Placement {
+            tab: active_tab,
+            deposited: 0,
+        });
+        placement.deposited = self.deposits;
+        Ok(result)
+    }
+
+    /// Drops the filing info for a stack that has left storage.
+    pub fn forget(&mut self, uuid: Uuid) {
+        self.placements.remove(&uuid);
+    }
+
+    /// The tab a stack is filed under. Unfiled stacks belong to the first tab.
+    pub fn tab_of(&self, uuid: Uuid) -> usize {
+        self.placements
+            .get(&uuid)
+            .map_or(0, |p| p.tab)
+            .min(self.tabs.len() - 1)
+    }
+
+    /// Adds a tab and makes it active. Blank names get a numbered default. Returns `None`
+    /// once `max_tabs` is reached.
+    pub fn add_tab(&mut self, name: &str) -> Option<usize> {
+        if self.tabs.len() >= self.max_tabs {
+            return None;
         }
+        let name = name.trim();
+        let name = if name.is_empty() {
+            format!("{} {}", DEFAULT_TAB_NAME, self.tabs.len() + 1)
+        } else {
+            name.to_string()
+        };
+        self.tabs.push(name);
+        self.active_tab = self.tabs.len() - 1;
+        Some(self.active_tab)
     }
+
+    pub fn cycle_tab(&mut self, forward: bool) {
+        let count = self.tabs.len();
+        self.active_tab = if forward {
+            (self.active_tab + 1) % count
+        } else {
+            (self.active_tab + count - 1) % count
+        };
+    }
+
+    /// Files a stack under the tab after the one it is in, wrapping to the first.
+    pub fn move_to_next_tab(&mut self, uuid: Uuid) {
+        let tab = (self.tab_of(uuid) + 1) % self.tabs.len();
+        let deposited = self.placements.get(&uuid).map_or(0, |p| p.deposited);
+        self.placements.insert(uuid, Placement { tab, deposited });
+    }
+
+    /// Stores every backpack stack `filter` accepts. Whatever does not fit stays in the
+    /// backpack. Returns how many items were stored.
+    pub fn deposit_all(&mut self, backpack: &mut Inventory, filter: DepositFilter) -> u32 {
+        let uuids: Vec<Uuid> = backpack
+            .items
+            .iter()
+            .filter(|inv_item| filter.matches(&inv_item.item))
+            .map(InventoryItem::uuid)
+            .collect();
+
+        let mut moved = 0;
+        for uuid in uuids {
+            let Some(index) = backpack.find_item_index_by_uuid(uuid) else {
+                continue;
+            };
+            while backpack.items[index].quantity > 0 {
+                if self.deposit(unit_of(&backpack.items[index])).is_err() {
+                    break;
+                }
+                backpack.items[index].decrease_quantity(1);
+                moved += 1;
+            }
+            if backpack.items[index].quantity == 0 {
+                backpack.items.remove(index);
+            }
+        }
+        moved
+    }
+
+    /// Takes out whatever the backpack is short of `ingredients`, as far as storage holds
+    /// it. Returns how many items were withdrawn.
+    pub fn withdraw_ingredients(
+        &mut self,
+        backpack: &mut Inventory,
+        ingredients: &HashMap<ItemId, u32>,
+    ) -> u32 {
+        let mut moved = 0;
+        for (&item_id, &required) in ingredients {
+            let mut missing = required.saturating_sub(backpack.count_item(item_id));
+            while missing > 0 {
+                let Some(index) =
+                    self.inventory.items.iter().position(|i| i.item.item_id == item_id)
+                else {
+                    break;
+                };
+                if backpack.add_to_inv(unit_of(&self.inventory.items[index])).is_err() {
+                    return moved;
+                }
+                let stack = &mut self.inventory.items[index];
+                stack.decrease_quantity(1);
+                if stack.quantity == 0 {
+                    let uuid = stack.uuid();
+                    self.inventory.items.remove(index);
+                    self.forget(uuid);
+                }
+                missing -= 1;
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Uses up `ingredients` straight from storage, all or nothing. Returns whether
+    /// storage held enough of everything.
+    pub fn consume(&mut self, ingredients: &HashMap<ItemId, u32>) -> bool {
+        if ingredients.iter().any(|(&item_id, &required)| self.count_item(item_id) < required) {
+            return false;
+        }
+
+        for (&item_id, &required) in ingredients {
+            let mut missing = required;
+            while missing > 0 {
+                let Some(index) =
+                    self.inventory.items.iter().position(|i| i.item.item_id == item_id)
+                else {
+                    break;
+                };
+                let stack = &mut self.inventory.items[index];
+                let taken = missing.min(stack.quantity);
+                stack.decrease_quantity(taken);
+                missing -= taken;
+                if stack.quantity == 0 {
+                    let uuid = stack.uuid();
+                    self.inventory.items.remove(index);
+                    self.forget(uuid);
+                }
+            }
+        }
+        true
+    }
+
+    /// Indices into `inventory.items` shown in the active tab, after the search filter and
+    /// sort are applied. UI grids list items in this order.
+    pub fn visible_items(&self) -> Vec<usize> {
+        let search = self.search.to_lowercase();
+        let mut indices: Vec<usize> = self
+            .inventory
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, inv_item)| self.tab_of(inv_item.uuid()) == self.active_tab)
+            .filter(|(_, inv_item)| inv_item.item.name.to_lowercase().contains(&search))
+            .map(|(index, _)| index)
+            .collect();
+
+        let items = &self.inventory.items;
+        match self.sort {
+            StorageSort::Name => {
+                indices.sort_by(|&a, &b| items[a].item.name.cmp(&items[b].item.name))
+            }
+            StorageSort::Value => indices.sort_by_key(|&i| Reverse(items[i].item.gold_value)),
+            StorageSort::Quantity => indices.sort_by_key(|&i| Reverse(items[i].quantity)),
+            StorageSort::Recent => indices.sort_by_key(|&i| {
+                Reverse(self.placements.get(&items[i].uuid()).map_or(0, |p| p.deposited))
+            }),
+            StorageSort::Favorites => indices.sort_by(|&a, &b| {
+                (!items[a].item.is_favorite, &items[a].item.name)
+                    .cmp(&(!items[b].item.is_favorite, &items[b].item.name))
+            }),
+        }
+        indices
+    }
+}
+
+/// One item taken from `stack`. Only the last one keeps the stack's UUID, so an item that
+/// starts a new stack elsewhere never shares it with the stack left behind.
+fn unit_of(stack: &InventoryItem) -> Item {
+    let mut item = stack.item.clone();
+    if stack.quantity > 1 {
+        item.item_uuid = Uuid::new_v4();
+    }
+    item
 }
 
 impl Default for Storage {
@@ -20,3 +311,175 @@ impl Default for Storage {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::enums::{EquipmentType, ItemType, MaterialType};
+
+    fn coal() -> Item {
+        Item {
+            gold_value: 2,
+            ..Item::test(ItemId::Coal, ItemType::Material(MaterialType::Ore))
+        }
+    }
+
+    fn sword() -> Item {
+        Item {
+            gold_value: 50,
+            ..Item::test(ItemId::Sword, ItemType::Equipment(EquipmentType::Weapon))
+        }
+    }
+
+    fn names(storage: &Storage) -> Vec<&str> {
+        storage
+            .visible_items()
+            .into_iter()
+            .map(|i| storage.inventory.items[i].item.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn sort_modes_order_the_visible_items() {
+        let mut storage = Storage::new();
+        storage.deposit(sword()).unwrap();
+        storage.deposit(coal()).unwrap();
+        storage.deposit(coal()).unwrap();
+
+        assert_eq!(names(&storage), ["Coal", "Sword"]);
+        storage.sort = StorageSort::Value;
+        assert_eq!(names(&storage), ["Sword", "Coal"]);
+        storage.sort = StorageSort::Quantity;
+        assert_eq!(names(&storage), ["Coal", "Sword"]);
+
+        storage.sort = StorageSort::Recent;
+        storage.deposit(sword()).unwrap();
+        assert_eq!(names(&storage), ["Sword", "Coal", "Sword"]);
+    }
+
+    #[test]
+    fn deposits_are_filed_under_the_active_tab() {
+        let mut storage = Storage::new();
+        storage.deposit(sword()).unwrap();
+        storage.add_tab("  Ores ");
+        storage.deposit(coal()).unwrap();
+
+        assert_eq!(storage.tabs, [DEFAULT_TAB_NAME, "Ores"]);
+        assert_eq!(names(&storage), ["Coal"]);
+
+        // Stacking onto a stack filed elsewhere keeps it where it was.
+        storage.cycle_tab(true);
+        storage.deposit(coal()).unwrap();
+        assert_eq!(names(&storage), ["Sword"]);
+
+        let sword_uuid = storage.inventory.items[0].uuid();
+        storage.move_to_next_tab(sword_uuid);
+        assert!(names(&storage).is_empty());
+        storage.cycle_tab(false);
+        assert_eq!(names(&storage), ["Coal", "Sword"]);
+    }
+
+    #[test]
+    fn search_is_case_insensitive() {
+        let mut storage = Storage::new();
+        storage.deposit(sword()).unwrap();
+        storage.deposit(coal()).unwrap();
+
+        storage.search = "SWO".to_string();
+        assert_eq!(names(&storage), ["Sword"]);
+        storage.search = "x".to_string();
+        assert!(names(&storage).is_empty());
+    }
+
+    #[test]
+    fn deposit_all_moves_whole_stacks_and_keeps_locked_items() {
+        let mut storage = Storage::new();
+        let mut backpack = Inventory::new();
+        for _ in 0..3 {
+            backpack.add_to_inv(coal()).unwrap();
+        }
+        backpack.add_to_inv(sword()).unwrap();
+        let mut locked = coal();
+        locked.item_id = ItemId::IronOre;
+        locked.is_locked = true;
+        backpack.add_to_inv(locked).unwrap();
+
+        assert_eq!(storage.deposit_all(&mut backpack, DepositFilter::Materials), 3);
+        assert_eq!(storage.count_item(ItemId::Coal), 3);
+        assert_eq!(backpack.items.len(), 2);
+
+        assert_eq!(storage.deposit_all(&mut backpack, DepositFilter::Unlocked), 1);
+        assert_eq!(backpack.count_item(ItemId::IronOre), 1);
+        assert_eq!(backpack.items.len(), 1);
+    }
+
+    #[test]
+    fn withdraw_ingredients_only_takes_what_is_missing() {
+        let mut storage = Storage::new();
+        let mut backpack = Inventory::new();
+        for _ in 0..5 {
+            storage.deposit(coal()).unwrap();
+        }
+        backpack.add_to_inv(coal()).unwrap();
+
+        let ingredients = HashMap::from([(ItemId::Coal, 3), (ItemId::IronOre, 2)]);
+        assert_eq!(storage.withdraw_ingredients(&mut backpack, &ingredients), 2);
+        assert_eq!(backpack.count_item(ItemId::Coal), 3);
+        assert_eq!(storage.count_item(ItemId::Coal), 3);
+
+        let ingredients = HashMap::from([(ItemId::Coal, 10)]);
+        assert_eq!(storage.withdraw_ingredients(&mut backpack, &ingredients), 3);
+        assert!(storage.inventory.items.is_empty());
+    }
+
+    #[test]
+    fn consume_takes_everything_or_nothing() {
+        let mut storage = Storage::new();
+        for _ in 0..3 {
+            storage.deposit(coal()).unwrap();
+        }
+
+        let short = HashMap::from([(ItemId::Coal, 2), (ItemId::IronOre, 1)]);
+        assert!(!storage.consume(&short));
+        assert_eq!(storage.count_item(ItemId::Coal), 3);
+
+        assert!(storage.consume(&HashMap::from([(ItemId::Coal, 2)])));
+        assert_eq!(storage.count_item(ItemId::Coal), 1);
+        assert!(storage.consume(&HashMap::from([(ItemId::Coal, 1)])));
+        assert!(storage.inventory.items.is_empty());
+    }
+
+    #[test]
+    fn tabs_stop_at_the_limit() {
+        let mut storage = Storage::new();
+        for _ in 1..BASE_TAB_LIMIT {
+            assert!(storage.add_tab("").is_some());
+        }
+        assert_eq!(storage.add_tab("One too many"), None);
+        assert_eq!(storage.tabs.len(), BASE_TAB_LIMIT);
+
+        storage.max_tabs += 1;
+        assert_eq!(storage.add_tab(""), Some(BASE_TAB_LIMIT));
+    }
+
+    #[test]
+    fn saved_storage_keeps_items_and_tabs() {
+        let registry = ItemRegistry::from_assets();
+        let mut storage = Storage::new();
+        storage.deposit(registry.spawn(ItemId::Coal)).unwrap();
+        storage.deposit(registry.spawn(ItemId::Coal)).unwrap();
+        storage.add_tab("Gear");
+        storage.deposit(registry.spawn(ItemId::Sword)).unwrap();
+        storage.sort = StorageSort::Recent;
+
+        let saved: SavedStorage =
+            crate::save::from_ron(&crate::save::to_ron(&storage.to_saved()).unwrap()).unwrap();
+        let restored = Storage::from_saved(&saved, &registry);
+
+        assert_eq!(restored.tabs, storage.tabs);
+        assert_eq!(restored.sort, StorageSort::Recent);
+        assert_eq!(restored.count_item(ItemId::Coal), 2);
+        let sword = storage.inventory.items[1].uuid();
+        assert_eq!(restored.tab_of(sword), 1);
+    }
+}
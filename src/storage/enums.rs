@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::{Item, ItemType};
+
+/// Order in which the active storage tab lists its items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum StorageSort {
+    #[default]
+    Name,
+    Value,
+    Quantity,
+    Recent,
+    Favorites,
+}
+
+impl StorageSort {
+    pub const ALL: [StorageSort; 5] = [
+        StorageSort::Name,
+        StorageSort::Value,
+        StorageSort::Quantity,
+        StorageSort::Recent,
+        StorageSort::Favorites,
+    ];
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Localization key for the sort mode's label.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            StorageSort::Name => "storage.sort_name",
+            StorageSort::Value => "storage.sort_value",
+            StorageSort::Quantity => "storage.sort_quantity",
+            StorageSort::Recent => "storage.sort_recent",
+            StorageSort::Favorites => "storage.sort_favorites",
+        }
+    }
+}
+
+/// Which backpack stacks [`Storage::deposit_all`](super::Storage::deposit_all) stores.
+/// Locked items are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepositFilter {
+    #[default]
+    Materials,
+    Unlocked,
+}
+
+impl DepositFilter {
+    pub fn matches(self, item: &Item) -> bool {
+        match self {
+            DepositFilter::Materials => {
+                !item.is_locked && matches!(item.item_type, ItemType::Material(_))
+            }
+            DepositFilter::Unlocked => !item.is_locked,
+        }
+    }
+}
@@ -2,4 +2,7 @@ pub mod definition;
 pub mod enums;
 pub mod traits;
 
-pub use definition::Storage;
+pub use definition::{
+    SavedStorage, Storage, BASE_TAB_LIMIT, DEFAULT_TAB_NAME, STORAGE_SAVE_PATH,
+};
+pub use enums::{DepositFilter, StorageSort};
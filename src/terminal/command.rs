@@ -0,0 +1,154 @@
+use crate::input::{GameAction, NavigationDirection};
+
+/// Words the terminal maps to input actions. The first word in each group is the one
+/// `help` lists.
+const ACTION_WORDS: &[(&[&str], GameAction)] = &[
+    (&["up", "u"], GameAction::Navigate(NavigationDirection::Up)),
+    (
+        &["down", "d"],
+        GameAction::Navigate(NavigationDirection::Down),
+    ),
+    (
+        &["left", "l"],
+        GameAction::Navigate(NavigationDirection::Left),
+    ),
+    (
+        &["right", "r"],
+        GameAction::Navigate(NavigationDirection::Right),
+    ),
+    (&["select", "enter", "ok"], GameAction::Select),
+    (&["back"], GameAction::Back),
+    (&["tab"], GameAction::NextTab),
+    (&["prevtab"], GameAction::PrevTab),
+    (&["interact", "use"], GameAction::Interact),
+    (&["attack", "hit"], GameAction::Attack),
+    (&["close", "esc"], GameAction::CloseModal),
+    (&["inventory", "inv"], GameAction::OpenInventory),
+    (&["profile"], GameAction::OpenProfile),
+    (&["skills"], GameAction::OpenSkills),
+    (&["map"], GameAction::OpenWorldMap),
+    (&["cast"], GameAction::Cast),
+    (&["undo"], GameAction::Undo),
+];
+
+const DIRECTIONS: &[(&str, NavigationDirection)] = &[
+    ("up", NavigationDirection::Up),
+    ("down", NavigationDirection::Down),
+    ("left", NavigationDirection::Left),
+    ("right", NavigationDirection::Right),
+];
+
+/// A line typed at the terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerminalCommand {
+    Help,
+    Quit,
+    Action(GameAction),
+    /// Start walking in a direction, or stop with `None`.
+    Walk(Option<NavigationDirection>),
+    /// A dev console line, typed after a leading backtick.
+    Console(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TerminalError {
+    #[error("Unknown command: {0} (type help for a list)")]
+    UnknownCommand(String),
+    #[error("Usage: go <up|down|left|right>")]
+    UnknownDirection,
+}
+
+impl TerminalCommand {
+    pub fn parse(line: &str) -> Result<Self, TerminalError> {
+        let line = line.trim();
+        if let Some(console) = line.strip_prefix('`') {
+            return Ok(Self::Console(console.trim().to_string()));
+        }
+
+        let mut words = line.split_whitespace().map(str::to_lowercase);
+        let Some(word) = words.next() else {
+            return Ok(Self::Help);
+        };
+        match word.as_str() {
+            "help" | "?" => Ok(Self::Help),
+            "quit" | "exit" => Ok(Self::Quit),
+            "stop" => Ok(Self::Walk(None)),
+            "go" => {
+                let direction = words.next().ok_or(TerminalError::UnknownDirection)?;
+                DIRECTIONS
+                    .iter()
+                    .find(|(name, _)| *name == direction)
+                    .map(|&(_, dir)| Self::Walk(Some(dir)))
+                    .ok_or(TerminalError::UnknownDirection)
+            }
+            _ => ACTION_WORDS
+                .iter()
+                .find(|(names, _)| names.contains(&word.as_str()))
+                .map(|&(_, action)| Self::Action(action))
+                .ok_or(TerminalError::UnknownCommand(word)),
+        }
+    }
+
+    pub fn help_lines() -> Vec<String> {
+        let mut lines = vec![
+            "go <up|down|left|right> - walk until stopped".to_string(),
+            "stop - stop walking".to_string(),
+            "`<command> - run a dev console command".to_string(),
+            "quit - leave the game".to_string(),
+        ];
+        let actions: Vec<&str> = ACTION_WORDS.iter().map(|(names, _)| names[0]).collect();
+        lines.push(format!("actions: {}", actions.join(", ")));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_actions_and_aliases() {
+        assert_eq!(
+            TerminalCommand::parse("Inv"),
+            Ok(TerminalCommand::Action(GameAction::OpenInventory))
+        );
+        assert_eq!(
+            TerminalCommand::parse(" u "),
+            Ok(TerminalCommand::Action(GameAction::Navigate(
+                NavigationDirection::Up
+            )))
+        );
+    }
+
+    #[test]
+    fn parses_walking() {
+        assert_eq!(
+            TerminalCommand::parse("go left"),
+            Ok(TerminalCommand::Walk(Some(NavigationDirection::Left)))
+        );
+        assert_eq!(
+            TerminalCommand::parse("stop"),
+            Ok(TerminalCommand::Walk(None))
+        );
+        assert_eq!(
+            TerminalCommand::parse("go sideways"),
+            Err(TerminalError::UnknownDirection)
+        );
+    }
+
+    #[test]
+    fn backtick_lines_go_to_the_console() {
+        assert_eq!(
+            TerminalCommand::parse("`give coal 5"),
+            Ok(TerminalCommand::Console("give coal 5".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_words() {
+        assert_eq!(
+            TerminalCommand::parse("dance"),
+            Err(TerminalError::UnknownCommand("dance".to_string()))
+        );
+    }
+}
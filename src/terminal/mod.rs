@@ -0,0 +1,8 @@
+//! Text-mode play (`tui` feature only), for terminals without a display such as an SSH
+//! session. Built into the `randr-terminal` binary on top of the headless app.
+
+mod command;
+mod plugin;
+
+pub use command::{TerminalCommand, TerminalError};
+pub use plugin::TerminalPlugin;
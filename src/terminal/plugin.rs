@@ -0,0 +1,112 @@
+use std::io::BufRead;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+
+use crate::console::ConsoleSubmit;
+use crate::input::{GameAction, HeldDirection};
+use crate::settings::GameSettings;
+use crate::states::AppState;
+use crate::ui::Announce;
+
+use super::command::TerminalCommand;
+
+/// Plays the game from stdin and stdout: typed lines become input actions and console
+/// commands, and announcements and screen changes are printed.
+pub struct TerminalPlugin;
+
+impl Plugin for TerminalPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TerminalInput::spawn())
+            .add_systems(Startup, print_help)
+            .add_systems(PreUpdate, read_terminal_lines)
+            .add_systems(
+                PostUpdate,
+                (
+                    print_state.run_if(state_changed::<AppState>),
+                    print_announcements.run_if(on_message::<Announce>),
+                ),
+            );
+    }
+}
+
+/// Lines read from stdin on a background thread.
+#[derive(Resource)]
+struct TerminalInput(Mutex<Receiver<String>>);
+
+impl TerminalInput {
+    fn spawn() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in std::io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self(Mutex::new(receiver))
+    }
+}
+
+fn print_help() {
+    for line in TerminalCommand::help_lines() {
+        println!("{line}");
+    }
+}
+
+fn read_terminal_lines(
+    input: Res<TerminalInput>,
+    mut held: ResMut<HeldDirection>,
+    mut actions: MessageWriter<GameAction>,
+    mut console: MessageWriter<ConsoleSubmit>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    let Ok(receiver) = input.0.lock() else {
+        return;
+    };
+    loop {
+        let line = match receiver.try_recv() {
+            Ok(line) => line,
+            Err(TryRecvError::Empty) => return,
+            // Stdin closed, as when a piped script runs out.
+            Err(TryRecvError::Disconnected) => {
+                exit.write(AppExit::Success);
+                return;
+            }
+        };
+        match TerminalCommand::parse(&line) {
+            Ok(TerminalCommand::Help) => print_help(),
+            Ok(TerminalCommand::Quit) => {
+                exit.write(AppExit::Success);
+            }
+            Ok(TerminalCommand::Action(action)) => {
+                actions.write(action);
+            }
+            Ok(TerminalCommand::Walk(direction)) => held.0 = direction,
+            Ok(TerminalCommand::Console(line)) => {
+                console.write(ConsoleSubmit(line));
+            }
+            Err(e) => println!("{e}"),
+        }
+    }
+}
+
+fn print_state(state: Res<State<AppState>>) {
+    println!("== {:?} ==", state.get());
+}
+
+/// Prints announcements, unless the screen reader setting already does.
+fn print_announcements(mut announcements: MessageReader<Announce>, settings: Res<GameSettings>) {
+    if settings.screen_reader {
+        announcements.clear();
+        return;
+    }
+    for Announce(text) in announcements.read() {
+        let text = text.trim();
+        if !text.is_empty() {
+            println!("{text}");
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod plugin;
+pub mod state;
+
+pub use plugin::{TrophyPlugin, TrophyRequest, TrophyUnlocked};
+pub use state::{Trophy, TrophyCase, DEEP_DELVER_DEPTH, PEDESTALS, TROPHY_SAVE_PATH};
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+
+use crate::combat::{EntityDied, LootDropped};
+use crate::dungeon::MiningResult;
+use crate::game::{GameStats, ShowToast};
+use crate::i18n::Localization;
+use crate::item::enums::ItemQuality;
+use crate::mob::definitions::MobQuality;
+use crate::mob::MobMarker;
+use crate::save::{load_ron, save_ron};
+use crate::states::ScreenSet;
+
+use super::state::{Trophy, TrophyCase, DEEP_DELVER_DEPTH, TROPHY_SAVE_PATH};
+
+/// A change the player made to the pedestals at home.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrophyRequest {
+    /// Show the next trophy on this pedestal, or the previous one.
+    Cycle {
+        pedestal: usize,
+        forward: bool,
+    },
+    Clear(usize),
+}
+
+/// A trophy was earned for the first time.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct TrophyUnlocked(pub Trophy);
+
+pub struct TrophyPlugin;
+
+impl Plugin for TrophyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<TrophyCase>(TROPHY_SAVE_PATH))
+            .add_message::<TrophyRequest>()
+            .add_message::<TrophyUnlocked>()
+            .add_systems(
+                Update,
+                (
+                    unlock_boss_heads.run_if(on_message::<EntityDied>),
+                    unlock_rare_finds
+                        .run_if(on_message::<LootDropped>.or(on_message::<MiningResult>)),
+                    unlock_achievements.run_if(resource_changed::<GameStats>),
+                    announce_unlocks.run_if(on_message::<TrophyUnlocked>),
+                    handle_trophy_requests.run_if(on_message::<TrophyRequest>),
+                    save_trophies.run_if(
+                        resource_changed::<TrophyCase>.and(not(resource_added::<TrophyCase>)),
+                    ),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+fn save_trophies(case: Res<TrophyCase>) {
+    if let Err(e) = save_ron(TROPHY_SAVE_PATH, &*case) {
+        warn!("{e}");
+    }
+}
+
+fn unlock(case: &mut TrophyCase, trophy: Trophy, unlocked: &mut MessageWriter<TrophyUnlocked>) {
+    // Only touch the case for new trophies, so it isn't saved on every kill.
+    if !case.is_unlocked(trophy) {
+        case.unlock(trophy);
+        unlocked.write(TrophyUnlocked(trophy));
+    }
+}
+
+fn unlock_boss_heads(
+    mut events: MessageReader<EntityDied>,
+    mobs: Query<&MobMarker>,
+    mut case: ResMut<TrophyCase>,
+    mut unlocked: MessageWriter<TrophyUnlocked>,
+) {
+    for event in events.read() {
+        let Ok(marker) = mobs.get(event.entity) else {
+            continue;
+        };
+        if event.is_player || !matches!(marker.0.spec().quality, MobQuality::Boss) {
+            continue;
+        }
+        unlock(&mut case, Trophy::BossHead(marker.0), &mut unlocked);
+    }
+}
+
+fn unlock_rare_finds(
+    mut loot: MessageReader<LootDropped>,
+    mut mining: MessageReader<MiningResult>,
+    mut case: ResMut<TrophyCase>,
+    mut unlocked: MessageWriter<TrophyUnlocked>,
+) {
+    let dropped = loot.read().map(|drop| drop.item.quality);
+    let mined: Vec<_> = mining
+        .read()
+        .flat_map(|result| result.loot_drops.iter().map(|drop| drop.item.quality))
+        .collect();
+    if dropped
+        .chain(mined)
+        .any(|quality| quality == ItemQuality::Mythic)
+    {
+        unlock(&mut case, Trophy::MythicFind, &mut unlocked);
+    }
+}
+
+fn unlock_achievements(
+    stats: Res<GameStats>,
+    mut case: ResMut<TrophyCase>,
+    mut unlocked: MessageWriter<TrophyUnlocked>,
+) {
+    if stats.best_depth >= DEEP_DELVER_DEPTH {
+        unlock(&mut case, Trophy::DeepDelver, &mut unlocked);
+    }
+    if stats.weekly.as_ref().is_some_and(|record| record.completed) {
+        unlock(&mut case, Trophy::WeeklyChampion, &mut unlocked);
+    }
+}
+
+fn announce_unlocks(
+    mut events: MessageReader<TrophyUnlocked>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for TrophyUnlocked(trophy) in events.read() {
+        toast_writer.write(ShowToast::new(
+            loc.format("trophy.unlocked", &[("trophy", &trophy.name(&loc))]),
+        ));
+    }
+}
+
+fn handle_trophy_requests(
+    mut requests: MessageReader<TrophyRequest>,
+    mut case: ResMut<TrophyCase>,
+) {
+    for request in requests.read() {
+        match *request {
+            TrophyRequest::Cycle { pedestal, forward } => case.cycle(pedestal, forward),
+            TrophyRequest::Clear(pedestal) => case.clear(pedestal),
+        }
+    }
+}
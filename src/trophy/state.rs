@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Localization;
+use crate::mob::MobId;
+
+/// Where the trophy case is saved, next to the ranch.
+pub const TROPHY_SAVE_PATH: &str = "trophies.ron";
+
+/// Pedestals in the player's house.
+pub const PEDESTALS: usize = 6;
+
+/// Endless depth that earns the delver's trophy.
+pub const DEEP_DELVER_DEPTH: u32 = 10;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Trophy {
+    /// Taken from a boss the first time it falls.
+    BossHead(MobId),
+    /// A mythic item found as loot.
+    MythicFind,
+    /// Reached [`DEEP_DELVER_DEPTH`] in endless mode.
+    DeepDelver,
+    /// Completed a weekly challenge.
+    WeeklyChampion,
+}
+
+impl Trophy {
+    pub fn name(self, loc: &Localization) -> String {
+        match self {
+            Trophy::BossHead(mob_id) => loc.format(
+                "trophy.boss_head",
+                &[("mob", &loc.mob_name(mob_id, &mob_id.spec().name))],
+            ),
+            Trophy::MythicFind => loc.get("trophy.mythic_find").to_string(),
+            Trophy::DeepDelver => loc.get("trophy.deep_delver").to_string(),
+            Trophy::WeeklyChampion => loc.get("trophy.weekly_champion").to_string(),
+        }
+    }
+}
+
+/// Trophies the player has earned and which of them stand on the pedestals at home.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrophyCase {
+    /// Earned trophies, oldest first.
+    #[serde(default)]
+    unlocked: Vec<Trophy>,
+    #[serde(default)]
+    pedestals: [Option<Trophy>; PEDESTALS],
+}
+
+impl TrophyCase {
+    pub fn unlocked(&self) -> &[Trophy] {
+        &self.unlocked
+    }
+
+    pub fn is_unlocked(&self, trophy: Trophy) -> bool {
+        self.unlocked.contains(&trophy)
+    }
+
+    /// Adds a trophy to the collection. Returns `false` if it was already there.
+    pub fn unlock(&mut self, trophy: Trophy) -> bool {
+        if self.is_unlocked(trophy) {
+            return false;
+        }
+        self.unlocked.push(trophy);
+        true
+    }
+
+    pub fn pedestals(&self) -> &[Option<Trophy>; PEDESTALS] {
+        &self.pedestals
+    }
+
+    /// Swaps the trophy on a pedestal for the next (or previous) one in the collection
+    /// that isn't already on show, passing through an empty pedestal on the way round.
+    pub fn cycle(&mut self, pedestal: usize, forward: bool) {
+        let Some(current) = self.pedestals.get(pedestal).copied() else {
+            return;
+        };
+        let mut choices: Vec<Option<Trophy>> = vec![None];
+        choices.extend(
+            self.unlocked
+                .iter()
+                .filter(|&&trophy| {
+                    Some(trophy) == current || !self.pedestals.contains(&Some(trophy))
+                })
+                .map(|&trophy| Some(trophy)),
+        );
+        let at = choices
+            .iter()
+            .position(|&choice| choice == current)
+            .unwrap_or(0);
+        let next = if forward {
+            (at + 1) % choices.len()
+        } else {
+            (at + choices.len() - 1) % choices.len()
+        };
+        self.pedestals[pedestal] = choices[next];
+    }
+
+    pub fn clear(&mut self, pedestal: usize) {
+        if let Some(slot) = self.pedestals.get_mut(pedestal) {
+            *slot = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn trophies_unlock_once() {
+        let mut case = TrophyCase::default();
+        assert!(case.unlock(Trophy::BossHead(MobId::Colossus)));
+        assert!(!case.unlock(Trophy::BossHead(MobId::Colossus)));
+        assert_eq!(case.unlocked(), [Trophy::BossHead(MobId::Colossus)]);
+    }
+
+    #[test]
+    fn cycling_skips_trophies_already_on_show() {
+        let mut case = TrophyCase::default();
+        case.unlock(Trophy::MythicFind);
+        case.unlock(Trophy::DeepDelver);
+
+        case.cycle(0, true);
+        assert_eq!(case.pedestals()[0], Some(Trophy::MythicFind));
+        case.cycle(1, true);
+        assert_eq!(case.pedestals()[1], Some(Trophy::DeepDelver));
+        case.cycle(1, true);
+        assert_eq!(case.pedestals()[1], None);
+
+        case.cycle(0, false);
+        assert_eq!(case.pedestals()[0], None);
+        case.cycle(0, false);
+        assert_eq!(case.pedestals()[0], Some(Trophy::DeepDelver));
+
+        case.clear(0);
+        case.cycle(PEDESTALS, true);
+        assert_eq!(case.pedestals(), &[None; PEDESTALS]);
+    }
+
+    #[test]
+    fn trophy_case_round_trips_through_ron() {
+        let mut case = TrophyCase::default();
+        case.unlock(Trophy::BossHead(MobId::Colossus));
+        case.cycle(2, true);
+        let ron = to_ron(&case).unwrap();
+        assert_eq!(from_ron::<TrophyCase>(&ron).unwrap(), case);
+        assert_eq!(from_ron::<TrophyCase>("()").unwrap(), TrophyCase::default());
+    }
+}
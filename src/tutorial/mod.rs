@@ -0,0 +1,5 @@
+mod plugin;
+mod progress;
+
+pub use plugin::TutorialPlugin;
+pub use progress::{TutorialProgress, TutorialStep, TUTORIAL_SAVE_PATH};
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use crate::combat::HitLanded;
+use crate::dungeon::{FloorId, FloorReady};
+use crate::game::{AnvilCraftingCompleteEvent, ForgeCraftingCompleteEvent};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::mob::MobMarker;
+use crate::save::{load_ron, save_ron};
+use crate::settings::GameSettings;
+
+use super::progress::{TutorialProgress, TutorialStep, TUTORIAL_SAVE_PATH};
+
+const TIP_Z_INDEX: i32 = 900;
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<TutorialProgress>(TUTORIAL_SAVE_PATH))
+            .add_systems(
+                Update,
+                (
+                    (
+                        (
+                            reach_floor_steps.run_if(on_message::<FloorReady>),
+                            reach_fight_step.run_if(on_message::<HitLanded>),
+                            reach_craft_step.run_if(
+                                on_message::<ForgeCraftingCompleteEvent>
+                                    .or(on_message::<AnvilCraftingCompleteEvent>),
+                            ),
+                        )
+                            .run_if(tips_enabled),
+                        handle_tip_actions.run_if(on_message::<GameAction>),
+                    ),
+                    sync_tip_popup.run_if(resource_changed::<TutorialProgress>),
+                    save_progress.run_if(
+                        resource_changed::<TutorialProgress>
+                            .and(not(resource_added::<TutorialProgress>)),
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Component)]
+struct TutorialTipRoot;
+
+fn tips_enabled(settings: Res<GameSettings>) -> bool {
+    !settings.never_show_tips
+}
+
+fn save_progress(progress: Res<TutorialProgress>) {
+    if let Err(e) = save_ron(TUTORIAL_SAVE_PATH, &*progress) {
+        warn!("{e}");
+    }
+}
+
+fn reach_floor_steps(
+    mut events: MessageReader<FloorReady>,
+    mut progress: ResMut<TutorialProgress>,
+) {
+    for event in events.read() {
+        match event.floor_id {
+            FloorId::HomeFloor | FloorId::OutpostFloor => progress.reach(TutorialStep::Town),
+            FloorId::MainDungeon1
+            | FloorId::MainDungeon2
+            | FloorId::MainDungeon3
+            | FloorId::DeepMine1
+            | FloorId::DeepMine2 => progress.reach(TutorialStep::FirstMineVisit),
+            _ => {}
+        }
+    }
+}
+
+/// The first time one of the player's attacks lands on a mob. Dummies and nests don't count.
+fn reach_fight_step(
+    mut events: MessageReader<HitLanded>,
+    mobs: Query<(), With<MobMarker>>,
+    mut progress: ResMut<TutorialProgress>,
+) {
+    if events.read().any(|hit| mobs.contains(hit.target))
+        && !progress.has_seen(TutorialStep::FirstFight)
+    {
+        progress.reach(TutorialStep::FirstFight);
+    }
+}
+
+fn reach_craft_step(
+    mut forge_events: MessageReader<ForgeCraftingCompleteEvent>,
+    mut anvil_events: MessageReader<AnvilCraftingCompleteEvent>,
+    mut progress: ResMut<TutorialProgress>,
+) {
+    let crafted = forge_events.read().count() + anvil_events.read().count() > 0;
+    if crafted && !progress.has_seen(TutorialStep::FirstCraft) {
+        progress.reach(TutorialStep::FirstCraft);
+    }
+}
+
+fn handle_tip_actions(
+    mut action_reader: MessageReader<GameAction>,
+    mut progress: ResMut<TutorialProgress>,
+    mut settings: ResMut<GameSettings>,
+) {
+    if progress.current().is_none() {
+        return;
+    }
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::DismissTip => progress.dismiss(),
+            GameAction::DisableTips => {
+                settings.never_show_tips = true;
+                progress.clear_pending();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sync_tip_popup(
+    mut commands: Commands,
+    progress: Res<TutorialProgress>,
+    loc: Res<Localization>,
+    existing: Query<Entity, With<TutorialTipRoot>>,
+) {
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+
+    let Some(step) = progress.current() else {
+        return;
+    };
+
+    commands
+        .spawn((
+            TutorialTipRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                left: Val::Percent(25.0),
+                width: Val::Percent(50.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.08, 0.05, 0.9)),
+            BorderColor::all(Color::srgb(0.6, 0.5, 0.3)),
+            ZIndex(TIP_Z_INDEX),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get(step.text_key())),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.95, 0.9, 0.7)),
+            ));
+            parent.spawn((
+                Text::new(loc.get("tutorial.hint")),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
@@ -0,0 +1,116 @@
+use std::collections::{HashSet, VecDeque};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where the tips already shown are saved.
+pub const TUTORIAL_SAVE_PATH: &str = "tutorial.ron";
+
+/// A one-time tutorial prompt shown the first time the player reaches a milestone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TutorialStep {
+    Town,
+    FirstMineVisit,
+    FirstFight,
+    FirstCraft,
+}
+
+impl TutorialStep {
+    /// Localization key for the tip text.
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            TutorialStep::Town => "tutorial.town",
+            TutorialStep::FirstMineVisit => "tutorial.first_mine_visit",
+            TutorialStep::FirstFight => "tutorial.first_fight",
+            TutorialStep::FirstCraft => "tutorial.first_craft",
+        }
+    }
+}
+
+/// Which tutorial tips have been shown and which are waiting. Turning tips off altogether is
+/// a [`GameSettings`](crate::settings::GameSettings) option.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct TutorialProgress {
+    #[serde(default)]
+    seen: HashSet<TutorialStep>,
+    // Tips still waiting when the game closes are dropped
+    #[serde(skip)]
+    pending: VecDeque<TutorialStep>,
+}
+
+impl TutorialProgress {
+    /// Queues `step` unless it was already shown.
+    pub fn reach(&mut self, step: TutorialStep) {
+        if !self.seen.insert(step) {
+            return;
+        }
+        self.pending.push_back(step);
+    }
+
+    pub fn has_seen(&self, step: TutorialStep) -> bool {
+        self.seen.contains(&step)
+    }
+
+    /// The tip that should currently be on screen.
+    pub fn current(&self) -> Option<TutorialStep> {
+        self.pending.front().copied()
+    }
+
+    pub fn dismiss(&mut self) {
+        self.pending.pop_front();
+    }
+
+    /// Drops any tips that are waiting, as when tips are turned off.
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn steps_are_shown_once() {
+        let mut progress = TutorialProgress::default();
+        progress.reach(TutorialStep::Town);
+        progress.dismiss();
+        progress.reach(TutorialStep::Town);
+        assert_eq!(progress.current(), None);
+        assert!(progress.has_seen(TutorialStep::Town));
+    }
+
+    #[test]
+    fn steps_queue_in_order() {
+        let mut progress = TutorialProgress::default();
+        progress.reach(TutorialStep::FirstMineVisit);
+        progress.reach(TutorialStep::FirstFight);
+        assert_eq!(progress.current(), Some(TutorialStep::FirstMineVisit));
+        progress.dismiss();
+        assert_eq!(progress.current(), Some(TutorialStep::FirstFight));
+    }
+
+    #[test]
+    fn clear_pending_drops_waiting_steps() {
+        let mut progress = TutorialProgress::default();
+        progress.reach(TutorialStep::Town);
+        progress.reach(TutorialStep::FirstCraft);
+        progress.clear_pending();
+        assert_eq!(progress.current(), None);
+    }
+
+    #[test]
+    fn saves_keep_seen_steps_only() {
+        let mut progress = TutorialProgress::default();
+        progress.reach(TutorialStep::Town);
+        progress.dismiss();
+        progress.reach(TutorialStep::FirstFight);
+
+        let mut loaded: TutorialProgress = from_ron(&to_ron(&progress).unwrap()).unwrap();
+        assert_eq!(loaded.current(), None);
+        loaded.reach(TutorialStep::Town);
+        assert_eq!(loaded.current(), None);
+        assert!(loaded.has_seen(TutorialStep::FirstFight));
+    }
+}
@@ -0,0 +1,99 @@
+//! Plain-text announcements of what changes on screen, for players using a screen reader.
+//!
+//! Anything worth saying aloud is sent as an [`Announce`]. While the screen reader setting
+//! is on, each one is printed to stdout and appended to a log file that external readers can
+//! follow. The file starts empty every session.
+
+use std::fs::File;
+use std::io::{LineWriter, Write};
+
+use bevy::prelude::*;
+
+use crate::game::ShowToast;
+use crate::settings::GameSettings;
+
+/// Where announcements are logged, relative to the working directory.
+pub const ANNOUNCEMENT_LOG_PATH: &str = "announcements.log";
+
+/// A line of text describing a change on screen.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct Announce(pub String);
+
+impl Announce {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+}
+
+pub struct AnnouncePlugin;
+
+impl Plugin for AnnouncePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<Announce>()
+            .init_resource::<AnnouncementLog>()
+            .add_systems(
+                PostUpdate,
+                (
+                    announce_toasts.run_if(on_message::<ShowToast>),
+                    write_announcements.run_if(on_message::<Announce>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The log file, opened on the first announcement of the session.
+#[derive(Resource, Default)]
+struct AnnouncementLog {
+    file: Option<LineWriter<File>>,
+    failed: bool,
+}
+
+impl AnnouncementLog {
+    fn write_line(&mut self, line: &str) {
+        if self.failed {
+            return;
+        }
+        if self.file.is_none() {
+            match File::create(ANNOUNCEMENT_LOG_PATH) {
+                Ok(file) => self.file = Some(LineWriter::new(file)),
+                Err(e) => {
+                    warn!("Failed to open {ANNOUNCEMENT_LOG_PATH}: {e}");
+                    self.failed = true;
+                    return;
+                }
+            }
+        }
+        if let Some(file) = &mut self.file
+            && let Err(e) = writeln!(file, "{line}")
+        {
+            warn!("Failed to write to {ANNOUNCEMENT_LOG_PATH}: {e}");
+            self.failed = true;
+        }
+    }
+}
+
+fn announce_toasts(mut toasts: MessageReader<ShowToast>, mut announce: MessageWriter<Announce>) {
+    for toast in toasts.read() {
+        announce.write(Announce::new(toast.0.clone()));
+    }
+}
+
+fn write_announcements(
+    mut announcements: MessageReader<Announce>,
+    settings: Res<GameSettings>,
+    mut log: ResMut<AnnouncementLog>,
+) {
+    if !settings.screen_reader {
+        announcements.clear();
+        return;
+    }
+    for Announce(text) in announcements.read() {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        println!("{text}");
+        log.write_line(text);
+    }
+}
@@ -0,0 +1,233 @@
+//! Damage numbers that pop up over whoever was hit, drift upwards and fade.
+//!
+//! The text entities are pooled: a finished number is hidden and handed back to
+//! [`DamageNumberPool`] rather than despawned, so a busy fight doesn't spawn an entity
+//! per hit. Numbers can be switched off in [`GameSettings`].
+
+use std::fmt::Write;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::combat::HitLanded;
+use crate::game::PlayerDamaged;
+use crate::settings::GameSettings;
+use crate::states::AppState;
+use crate::ui::DungeonPlayer;
+use crate::utils::EntityPool;
+
+const FONT_SIZE: f32 = 10.0;
+const LIFETIME_SECS: f32 = 0.8;
+/// How far a number drifts up over its lifetime, in pixels.
+const RISE: f32 = 18.0;
+/// Height above the struck entity's origin a number starts at.
+const START_OFFSET: f32 = 12.0;
+const Z: f32 = 5.0;
+
+/// What a number is showing, which decides its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageKind {
+    /// Damage the player dealt.
+    Dealt,
+    /// A critical hit the player dealt.
+    Critical,
+    /// Damage the player took.
+    Taken,
+}
+
+impl DamageKind {
+    pub fn color(self) -> Color {
+        match self {
+            DamageKind::Dealt => Color::srgb(1.0, 0.95, 0.8),
+            DamageKind::Critical => Color::srgb(1.0, 0.8, 0.1),
+            DamageKind::Taken => Color::srgb(1.0, 0.35, 0.3),
+        }
+    }
+
+    /// Marks criticals out from ordinary hits.
+    fn suffix(self) -> &'static str {
+        match self {
+            DamageKind::Critical => "!",
+            _ => "",
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct DamageNumber {
+    timer: Timer,
+    origin: Vec3,
+    color: Color,
+}
+
+impl DamageNumber {
+    fn new(origin: Vec3, color: Color) -> Self {
+        Self {
+            timer: Timer::from_seconds(LIFETIME_SECS, TimerMode::Once),
+            origin,
+            color,
+        }
+    }
+
+    /// Where the number sits and how opaque it is, `progress` of the way through its life.
+    fn pose(&self, progress: f32) -> (Vec3, f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        (self.origin + Vec3::Y * RISE * progress, 1.0 - progress)
+    }
+}
+
+/// Hidden damage number entities ready to be shown again.
+pub type DamageNumberPool = EntityPool<DamageNumber>;
+
+/// The pool of hidden numbers and the parts of each one that get reset on reuse.
+#[derive(SystemParam)]
+struct PooledNumbers<'w, 's> {
+    pool: ResMut<'w, DamageNumberPool>,
+    pooled: Query<
+        'w,
+        's,
+        (
+            &'static mut DamageNumber,
+            &'static mut Text2d,
+            &'static mut TextColor,
+            &'static mut Transform,
+            &'static mut Visibility,
+        ),
+    >,
+}
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberPool>()
+            .add_systems(
+                Update,
+                (
+                    show_damage_numbers
+                        .run_if(on_message::<HitLanded>.or(on_message::<PlayerDamaged>)),
+                    animate_damage_numbers,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(OnExit(AppState::Dungeon), hide_damage_numbers);
+    }
+}
+
+fn show_damage_numbers(
+    mut commands: Commands,
+    mut hits: MessageReader<HitLanded>,
+    mut damaged: MessageReader<PlayerDamaged>,
+    settings: Res<GameSettings>,
+    numbers: PooledNumbers,
+    targets: Query<&GlobalTransform>,
+    dungeon_player: Query<&GlobalTransform, With<DungeonPlayer>>,
+) {
+    let PooledNumbers {
+        mut pool,
+        mut pooled,
+    } = numbers;
+    if !settings.damage_numbers {
+        hits.clear();
+        damaged.clear();
+        return;
+    }
+
+    let dealt = hits.read().filter_map(|hit| {
+        let at = targets.get(hit.target).ok()?.translation();
+        let kind = if hit.breakdown.critical {
+            DamageKind::Critical
+        } else {
+            DamageKind::Dealt
+        };
+        Some((at, hit.damage, kind))
+    });
+    let player = dungeon_player.single().ok().map(|t| t.translation());
+    let taken: Vec<_> = damaged
+        .read()
+        .filter(|d| d.amount > 0)
+        .filter_map(|d| Some((player?, d.amount, DamageKind::Taken)))
+        .collect();
+
+    for (at, amount, kind) in dealt.chain(taken) {
+        let origin = at.truncate().extend(Z) + Vec3::Y * START_OFFSET;
+        let color = kind.color();
+        let reused = pool.acquire().and_then(|entity| pooled.get_mut(entity).ok());
+        match reused {
+            Some((mut number, mut text, mut text_color, mut transform, mut visibility)) => {
+                *number = DamageNumber::new(origin, color);
+                text.0.clear();
+                let _ = write!(text.0, "{amount}{}", kind.suffix());
+                text_color.0 = color;
+                transform.translation = origin;
+                *visibility = Visibility::Visible;
+            }
+            None => {
+                commands.spawn((
+                    DamageNumber::new(origin, color),
+                    Text2d::new(format!("{amount}{}", kind.suffix())),
+                    TextFont {
+                        font_size: FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(color),
+                    Transform::from_translation(origin),
+                    Visibility::Visible,
+                ));
+            }
+        }
+    }
+}
+
+fn animate_damage_numbers(
+    time: Res<Time>,
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<(
+        Entity,
+        &mut DamageNumber,
+        &mut Transform,
+        &mut TextColor,
+        &mut Visibility,
+    )>,
+) {
+    for (entity, mut number, mut transform, mut text_color, mut visibility) in &mut numbers {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        number.timer.tick(time.delta());
+        let (position, alpha) = number.pose(number.timer.fraction());
+        transform.translation = position;
+        text_color.0 = number.color.with_alpha(alpha);
+        if number.timer.is_finished() {
+            *visibility = Visibility::Hidden;
+            pool.release(entity);
+        }
+    }
+}
+
+/// Leaving the dungeon hides anything still on screen.
+fn hide_damage_numbers(
+    mut pool: ResMut<DamageNumberPool>,
+    mut numbers: Query<(Entity, &mut Visibility), With<DamageNumber>>,
+) {
+    for (entity, mut visibility) in &mut numbers {
+        *visibility = Visibility::Hidden;
+        pool.release(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_rise_and_fade() {
+        let number = DamageNumber::new(Vec3::new(4.0, 10.0, Z), Color::WHITE);
+        assert_eq!(number.pose(0.0), (Vec3::new(4.0, 10.0, Z), 1.0));
+        let (end, alpha) = number.pose(1.0);
+        assert_eq!(end, Vec3::new(4.0, 10.0 + RISE, Z));
+        assert_eq!(alpha, 0.0);
+        assert_eq!(number.pose(2.0), number.pose(1.0));
+    }
+}
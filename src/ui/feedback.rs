@@ -0,0 +1,256 @@
+//! Sound and rumble cues for the UI.
+//!
+//! Screens and gameplay systems write a [`UiFeedback`] message instead of playing effects
+//! themselves; this module turns it into a short tone and, for combat cues, a gamepad
+//! rumble. Both halves can be switched off in [`GameSettings`].
+
+use std::time::Duration;
+
+#[cfg(feature = "gui")]
+use bevy::audio::Volume;
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+
+use crate::combat::HitLanded;
+use crate::game::{BrewingResult, MerchantTransactionResult, PlayerDamaged, PlayerLeveledUp};
+use crate::input::GameAction;
+use crate::settings::GameSettings;
+use crate::states::AppState;
+use crate::ui::screens::modal::ActiveModal;
+
+#[cfg(feature = "gui")]
+const TONE_VOLUME: f32 = 0.25;
+
+/// Something the player should hear or feel.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiFeedback {
+    /// The highlight moved in a menu.
+    Navigate,
+    Select,
+    /// A menu was backed out of or closed.
+    Back,
+    /// The game refused what was asked, e.g. an offer the player can't afford.
+    Error,
+    /// A player attack connected.
+    Hit,
+    /// The player took damage.
+    Hurt,
+    LevelUp,
+}
+
+/// A sine tone: pitch in hertz and length in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    pub frequency: f32,
+    pub millis: u64,
+}
+
+/// Motor strengths between 0 and 1 and length in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rumble {
+    pub strong: f32,
+    pub weak: f32,
+    pub millis: u64,
+}
+
+impl UiFeedback {
+    pub fn tone(self) -> Option<Tone> {
+        let (frequency, millis) = match self {
+            UiFeedback::Navigate => (660.0, 30),
+            UiFeedback::Select => (880.0, 60),
+            UiFeedback::Back => (440.0, 50),
+            UiFeedback::Error => (180.0, 140),
+            UiFeedback::LevelUp => (1320.0, 220),
+            UiFeedback::Hit | UiFeedback::Hurt => return None,
+        };
+        Some(Tone { frequency, millis })
+    }
+
+    pub fn rumble(self) -> Option<Rumble> {
+        let (strong, weak, millis) = match self {
+            UiFeedback::Hit => (0.0, 0.4, 80),
+            UiFeedback::Hurt => (0.7, 0.3, 150),
+            UiFeedback::LevelUp => (0.5, 0.5, 300),
+            _ => return None,
+        };
+        Some(Rumble {
+            strong,
+            weak,
+            millis,
+        })
+    }
+
+    /// The cue a menu gives for `action`, if any.
+    pub fn for_menu_action(action: GameAction) -> Option<Self> {
+        match action {
+            GameAction::Navigate(_) | GameAction::NextTab | GameAction::PrevTab => {
+                Some(UiFeedback::Navigate)
+            }
+            GameAction::Select => Some(UiFeedback::Select),
+            GameAction::Back | GameAction::CloseModal => Some(UiFeedback::Back),
+            _ => None,
+        }
+    }
+}
+
+pub struct UiFeedbackPlugin;
+
+impl Plugin for UiFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<UiFeedback>().add_systems(
+            Update,
+            (
+                menu_feedback.run_if(on_message::<GameAction>),
+                refusal_feedback.run_if(
+                    on_message::<MerchantTransactionResult>.or(on_message::<BrewingResult>),
+                ),
+                combat_feedback.run_if(
+                    on_message::<HitLanded>
+                        .or(on_message::<PlayerDamaged>)
+                        .or(on_message::<PlayerLeveledUp>),
+                ),
+                play_feedback.run_if(on_message::<UiFeedback>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Menu input makes a sound on every screen except the dungeon itself, where the arrow
+/// keys walk the player around.
+fn menu_feedback(
+    mut action_reader: MessageReader<GameAction>,
+    state: Res<State<AppState>>,
+    active_modal: Res<ActiveModal>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let in_menu = active_modal.modal.is_some()
+        || !matches!(state.get(), AppState::Dungeon | AppState::Loading);
+    if !in_menu {
+        action_reader.clear();
+        return;
+    }
+
+    for action in action_reader.read() {
+        if let Some(cue) = UiFeedback::for_menu_action(*action) {
+            feedback.write(cue);
+        }
+    }
+}
+
+/// Merchant and crafting refusals get the error cue.
+fn refusal_feedback(
+    mut transactions: MessageReader<MerchantTransactionResult>,
+    mut brewing: MessageReader<BrewingResult>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let refused_sale = transactions.read().any(|result| {
+        !matches!(
+            result,
+            MerchantTransactionResult::BuySuccess { .. }
+                | MerchantTransactionResult::SellSuccess { .. }
+                | MerchantTransactionResult::SellJunkSuccess { .. }
+                | MerchantTransactionResult::DepositSuccess { .. }
+                | MerchantTransactionResult::WithdrawSuccess { .. }
+        )
+    });
+    let failed_brew = brewing
+        .read()
+        .any(|result| !matches!(result, BrewingResult::Success { .. }));
+    if refused_sale || failed_brew {
+        feedback.write(UiFeedback::Error);
+    }
+}
+
+fn combat_feedback(
+    mut hits: MessageReader<HitLanded>,
+    mut damaged: MessageReader<PlayerDamaged>,
+    mut level_ups: MessageReader<PlayerLeveledUp>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    if hits.read().count() > 0 {
+        feedback.write(UiFeedback::Hit);
+    }
+    if damaged.read().count() > 0 {
+        feedback.write(UiFeedback::Hurt);
+    }
+    if level_ups.read().count() > 0 {
+        feedback.write(UiFeedback::LevelUp);
+    }
+}
+
+fn play_feedback(
+    #[cfg(feature = "gui")] mut commands: Commands,
+    mut feedback: MessageReader<UiFeedback>,
+    settings: Res<GameSettings>,
+    #[cfg(feature = "gui")] mut pitches: ResMut<Assets<Pitch>>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut rumble_writer: MessageWriter<GamepadRumbleRequest>,
+) {
+    // Several cues in one frame would only blur together, so each kind plays once.
+    let mut played = Vec::new();
+    for cue in feedback.read() {
+        if played.contains(cue) {
+            continue;
+        }
+        played.push(*cue);
+
+        // Builds without `gui` have no audio output.
+        #[cfg(feature = "gui")]
+        if let Some(tone) = cue.tone().filter(|_| settings.ui_sounds) {
+            commands.spawn((
+                AudioPlayer(pitches.add(Pitch::new(
+                    tone.frequency,
+                    Duration::from_millis(tone.millis),
+                ))),
+                PlaybackSettings::DESPAWN.with_volume(Volume::Linear(TONE_VOLUME)),
+            ));
+        }
+
+        if let Some(rumble) = cue.rumble().filter(|_| settings.rumble) {
+            for gamepad in &gamepads {
+                rumble_writer.write(GamepadRumbleRequest::Add {
+                    gamepad,
+                    duration: Duration::from_millis(rumble.millis),
+                    intensity: GamepadRumbleIntensity {
+                        strong_motor: rumble.strong,
+                        weak_motor: rumble.weak,
+                    },
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::NavigationDirection;
+
+    #[test]
+    fn menu_cues_sound_and_combat_cues_rumble() {
+        for cue in [UiFeedback::Navigate, UiFeedback::Select, UiFeedback::Error] {
+            assert!(cue.tone().is_some());
+            assert!(cue.rumble().is_none());
+        }
+        for cue in [UiFeedback::Hit, UiFeedback::Hurt] {
+            assert!(cue.tone().is_none());
+            assert!(cue.rumble().is_some());
+        }
+        assert!(UiFeedback::LevelUp.tone().is_some());
+        assert!(UiFeedback::LevelUp.rumble().is_some());
+    }
+
+    #[test]
+    fn only_menu_actions_have_cues() {
+        assert_eq!(
+            UiFeedback::for_menu_action(GameAction::Navigate(NavigationDirection::Up)),
+            Some(UiFeedback::Navigate)
+        );
+        assert_eq!(
+            UiFeedback::for_menu_action(GameAction::CloseModal),
+            Some(UiFeedback::Back)
+        );
+        assert_eq!(UiFeedback::for_menu_action(GameAction::OpenInventory), None);
+    }
+}
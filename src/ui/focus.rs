@@ -20,6 +20,8 @@ pub enum FocusPanel {
     // Merchant modal
     MerchantStock,
     PlayerInventory,
+    // Storage modal (shares PlayerInventory with the merchant)
+    StorageItems,
     // Forge modal
     ForgeCraftingSlots,
     ForgeInventory,
@@ -29,6 +31,8 @@ pub enum FocusPanel {
     // Monster compendium
     CompendiumMonsterList,
     CompendiumDropsList,
+    CompendiumItemList,
+    CompendiumItemSources,
 }
 
 /// Resource tracking which panel is currently focused.
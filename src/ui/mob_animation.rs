@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
 
+#[cfg(feature = "gui")]
 use crate::combat::events::DamageEntity;
+#[cfg(feature = "gui")]
 use crate::mob::components::MobMarker;
 use crate::mob::MobId;
 use crate::states::AppState;
@@ -13,24 +16,35 @@ pub struct MobAnimationPlugin;
 impl Plugin for MobAnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<AseMobSheets>()
-            .add_systems(PreStartup, load_mob_sprite_sheets)
-            .add_systems(
-                Update,
-                (
-                    trigger_hurt_animation.run_if(on_message::<DamageEntity>),
-                    revert_hurt_animation
-                        .run_if(any_with_component::<PlayingHurtAnimation>),
-                    trigger_death_animation.run_if(any_with_component::<DyingMob>),
-                    despawn_after_death_animation
-                        .run_if(any_with_component::<DyingMob>),
-                )
-                    .chain()
-                    .run_if(in_state(AppState::Dungeon)),
-            );
+            .add_systems(PreStartup, load_mob_sprite_sheets);
+
+        #[cfg(feature = "gui")]
+        app.add_systems(
+            Update,
+            (
+                trigger_hurt_animation.run_if(on_message::<DamageEntity>),
+                revert_hurt_animation.run_if(any_with_component::<PlayingHurtAnimation>),
+                trigger_death_animation.run_if(any_with_component::<DyingMob>),
+                despawn_after_death_animation.run_if(any_with_component::<DyingMob>),
+            )
+                .chain()
+                .run_if(in_state(AppState::Dungeon)),
+        );
+        // Without sprites there is no death animation to wait for.
+        #[cfg(not(feature = "gui"))]
+        app.add_systems(
+            Update,
+            despawn_dying_mobs
+                .run_if(any_with_component::<DyingMob>)
+                .run_if(in_state(AppState::Dungeon)),
+        );
     }
 }
 
+/// A mob's sprite sheet. Builds without `gui` keep only the frame size, which sizes the
+/// mob's collider.
 pub struct AseMobSheet {
+    #[cfg(feature = "gui")]
     pub aseprite: Handle<Aseprite>,
     pub idle_tag: &'static str,
     pub hurt_tag: Option<&'static str>,
@@ -53,13 +67,25 @@ impl AseMobSheets {
     }
 }
 
+#[cfg(feature = "gui")]
+impl AseMobSheet {
+    /// The sheet's idle animation, looping.
+    pub fn idle_animation(&self) -> AseAnimation {
+        AseAnimation {
+            aseprite: self.aseprite.clone(),
+            animation: Animation::tag(self.idle_tag).with_repeat(AnimationRepeat::Loop),
+        }
+    }
+}
+
 fn load_mob_sprite_sheets(
-    asset_server: Res<AssetServer>,
+    #[cfg(feature = "gui")] asset_server: Res<AssetServer>,
     mut ase_sheets: ResMut<AseMobSheets>,
 ) {
     for &mob_id in MobId::ALL {
         let sprite = crate::mob::data::get_sprite(mob_id);
         ase_sheets.insert(mob_id, AseMobSheet {
+            #[cfg(feature = "gui")]
             aseprite: asset_server.load(&sprite.aseprite_path),
             idle_tag: sprite.idle_tag.as_str(),
             hurt_tag: sprite.hurt_tag.as_deref(),
@@ -69,12 +95,14 @@ fn load_mob_sprite_sheets(
     }
 }
 
+#[cfg(feature = "gui")]
 #[derive(Component)]
 pub struct PlayingHurtAnimation;
 
 #[derive(Component)]
 pub struct DyingMob;
 
+#[cfg(feature = "gui")]
 fn trigger_hurt_animation(
     mut commands: Commands,
     mut events: MessageReader<DamageEntity>,
@@ -102,6 +130,7 @@ fn trigger_hurt_animation(
     }
 }
 
+#[cfg(feature = "gui")]
 fn revert_hurt_animation(
     mut commands: Commands,
     query: Query<(Entity, &AseAnimation, &MobMarker), With<PlayingHurtAnimation>>,
@@ -117,6 +146,7 @@ fn revert_hurt_animation(
     }
 }
 
+#[cfg(feature = "gui")]
 fn trigger_death_animation(
     mut commands: Commands,
     mut query: Query<(Entity, &MobMarker, &mut AseAnimation), Added<DyingMob>>,
@@ -139,6 +169,7 @@ fn trigger_death_animation(
     }
 }
 
+#[cfg(feature = "gui")]
 fn despawn_after_death_animation(
     mut commands: Commands,
     query: Query<(Entity, &AseAnimation, &MobMarker), With<DyingMob>>,
@@ -154,3 +185,10 @@ fn despawn_after_death_animation(
         }
     }
 }
+
+#[cfg(not(feature = "gui"))]
+fn despawn_dying_mobs(mut commands: Commands, dying: Query<Entity, Added<DyingMob>>) {
+    for entity in &dying {
+        commands.entity(entity).despawn();
+    }
+}
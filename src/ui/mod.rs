@@ -1,4 +1,7 @@
 pub mod animation;
+pub mod announce;
+pub mod damage_numbers;
+pub mod feedback;
 pub mod focus;
 pub mod hints;
 pub mod info_panel_source;
@@ -9,11 +12,16 @@ pub mod modal_registry;
 pub mod nodes;
 pub mod player_sprite;
 pub mod screens;
+#[cfg(feature = "gui")]
+pub mod screenshot;
 pub mod text;
 pub mod theme;
 pub mod widgets;
 
 pub use animation::*;
+pub use announce::{Announce, AnnouncePlugin};
+pub use damage_numbers::{DamageNumberPool, DamageNumbersPlugin};
+pub use feedback::{UiFeedback, UiFeedbackPlugin};
 pub use focus::*;
 pub use hints::*;
 pub use info_panel_source::InfoPanelSource;
@@ -24,5 +32,7 @@ pub use modal_registry::{ModalCommands, RegisteredModal, RegisterModalExt};
 pub use nodes::*;
 pub use player_sprite::*;
 pub use screens::*;
+#[cfg(feature = "gui")]
+pub use screenshot::ScreenshotPlugin;
 pub use text::*;
 pub use theme::*;
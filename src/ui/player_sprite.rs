@@ -1,7 +1,10 @@
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
 
+#[cfg(feature = "gui")]
 use crate::combat::hitbox::Attacking;
+#[cfg(feature = "gui")]
 use crate::input::HeldDirection;
 
 use super::animation::{
@@ -26,17 +29,22 @@ impl Plugin for PlayerSpritePlugin {
                 Update,
                 (animate_sprites, animate_world_sprites)
                     .run_if(any_with_component::<SpriteAnimation>),
-            )
-            .add_systems(
-                Update,
-                sync_player_animation.run_if(any_with_component::<PlayerWalkTimer>),
-            )
-            .add_observer(revert_to_idle);
+            );
+
+        #[cfg(feature = "gui")]
+        app.add_systems(
+            Update,
+            sync_player_animation.run_if(any_with_component::<PlayerWalkTimer>),
+        )
+        .add_observer(revert_to_idle);
     }
 }
 
+/// The player's sprite sheet. Builds without `gui` keep only the frame size, which sizes
+/// the player's collider and attack hitbox.
 #[derive(Resource, Default)]
 pub struct PlayerSpriteSheet {
+    #[cfg(feature = "gui")]
     pub aseprite: Option<Handle<Aseprite>>,
     pub frame_size: UVec2,
 }
@@ -45,14 +53,19 @@ pub struct PlayerSpriteSheet {
 pub struct PlayerWalkTimer(pub Timer);
 
 fn load_player_sprite_sheet(
-    asset_server: Res<AssetServer>,
+    #[cfg(feature = "gui")] asset_server: Res<AssetServer>,
     mut player_sheet: ResMut<PlayerSpriteSheet>,
 ) {
-    player_sheet.aseprite = Some(asset_server.load("sprites/player/lightning_warrior.aseprite"));
+    #[cfg(feature = "gui")]
+    {
+        player_sheet.aseprite =
+            Some(asset_server.load("sprites/player/lightning_warrior.aseprite"));
+        info!("Loaded player sprite sheet: MiniLightningWarrior (aseprite)");
+    }
     player_sheet.frame_size = UVec2::splat(32);
-    info!("Loaded player sprite sheet: MiniLightningWarrior (aseprite)");
 }
 
+#[cfg(feature = "gui")]
 fn revert_to_idle(
     trigger: On<Remove, Attacking>,
     mut query: Query<&mut AseAnimation>,
@@ -63,6 +76,7 @@ fn revert_to_idle(
     }
 }
 
+#[cfg(feature = "gui")]
 fn sync_player_animation(
     time: Res<Time>,
     held_direction: Res<HeldDirection>,
@@ -1,13 +1,17 @@
 use bevy::prelude::*;
 
-use crate::input::{craft_anvil_recipe, navigate_anvil_grid, sync_anvil_recipes};
+use crate::input::{
+    craft_anvil_recipe, evolve_anvil_item, fetch_anvil_ingredients, navigate_anvil_grid,
+    sync_anvil_recipes, upgrade_anvil_item_quality,
+};
+use crate::states::ScreenSet;
 use crate::ui::focus::{tab_toggle_system, FocusPanel};
 use crate::ui::modal_registry::{modal_close_system, RegisterModalExt};
 use crate::ui::screens::modal::in_anvil_modal;
 use crate::ui::widgets::{update_detail_pane_source, ItemGridSelection};
 use crate::ui::FocusState;
 
-use super::render::populate_anvil_detail_pane_content;
+use super::render::{populate_anvil_detail_pane_content, show_quality_upgrade_results};
 use super::state::{AnvilDetailPane, AnvilModal};
 
 pub struct AnvilModalPlugin;
@@ -18,11 +22,21 @@ impl Plugin for AnvilModalPlugin {
             .add_systems(
                 Update,
                 (
-                    modal_close_system::<AnvilModal>.run_if(in_anvil_modal),
+                    modal_close_system::<AnvilModal>
+                        .run_if(in_anvil_modal)
+                        .in_set(ScreenSet::Input),
                     (
                         tab_toggle_system(FocusPanel::RecipeGrid, FocusPanel::AnvilInventory),
                         navigate_anvil_grid,
                         craft_anvil_recipe,
+                        evolve_anvil_item,
+                        upgrade_anvil_item_quality,
+                        fetch_anvil_ingredients,
+                    )
+                        .in_set(ScreenSet::Input)
+                        .run_if(in_anvil_modal),
+                    (
+                        show_quality_upgrade_results,
                         sync_anvil_recipes,
                         update_detail_pane_source::<AnvilDetailPane>.run_if(
                             resource_exists::<FocusState>
@@ -31,6 +45,7 @@ impl Plugin for AnvilModalPlugin {
                         ),
                         populate_anvil_detail_pane_content,
                     )
+                        .in_set(ScreenSet::Render)
                         .run_if(in_anvil_modal),
                 ),
             );
@@ -1,37 +1,54 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
-use crate::assets::{GameFonts, GameSprites};
-use crate::inventory::{Inventory, ManagesItems};
+use crate::assets::GameFonts;
+use crate::faction::{FactionId, Reputation};
+use crate::game::{BlacksmithResult, ShowToast, UpgradeStreak};
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesEquipment, ManagesItems};
+use crate::item::enums::ItemQuality;
 use crate::item::recipe::RecipeId;
-use crate::item::ItemRegistry;
+use crate::item::{ItemId, ItemRegistry};
+use crate::location::{CurrentTown, TownId, TownState};
 use crate::player::PlayerMarker;
 use crate::ui::focus::FocusPanel;
 use crate::ui::modal_content_row;
 use crate::ui::InfoPanelSource;
 use crate::ui::widgets::{
-    ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent, ItemGrid, ItemGridEntry,
+    DetailPaneQueries, ItemDetailDisplay, ItemDetailPane, ItemGrid, ItemGridEntry,
     ItemGridFocusPanel, ItemGridSelection, ItemStatsDisplay, OutlinedText,
 };
-use crate::ui::{FocusState, Modal, ModalBackground, SpawnModalExt};
+use crate::ui::{FocusState, Modal, ModalBackground, Palette, SpawnModalExt};
 
 use super::state::{AnvilModalRoot, AnvilPlayerGrid, AnvilRecipeGrid};
 
-/// Convert forging recipes to grid entries for display.
-pub fn get_recipe_entries(inventory: &Inventory, registry: &ItemRegistry) -> Vec<ItemGridEntry> {
+/// Convert forging recipes to grid entries for display. Recipes still locked behind a
+/// faction rank or the town's blacksmith level show as uncraftable.
+pub fn get_recipe_entries(
+    inventory: &Inventory,
+    registry: &ItemRegistry,
+    reputation: &Reputation,
+    town_state: &TownState,
+    town: TownId,
+) -> Vec<ItemGridEntry> {
     RecipeId::all_forging_recipes()
         .iter()
         .map(|recipe_id| {
             let spec = recipe_id.spec();
-            let can_craft = spec
-                .ingredients
-                .iter()
-                .all(|(item_id, required)| inventory.count_item(*item_id) >= *required);
+            let can_craft = reputation.recipe_unlocked(*recipe_id)
+                && town_state.recipe_unlocked(town, *recipe_id)
+                && spec
+                    .ingredients
+                    .iter()
+                    .all(|(item_id, required)| inventory.count_item(*item_id) >= *required);
 
             let output_item = registry.spawn(spec.output);
             ItemGridEntry {
-                sprite_sheet_key: output_item.sprite.sheet_key,
+                sprite_sheet_key: output_item.sprite.sheet_key.into(),
                 sprite_name: output_item.sprite.name,
                 quantity: if can_craft { 1 } else { 0 },
+                is_favorite: false,
+                is_new: false,
             }
         })
         .collect()
@@ -42,16 +59,17 @@ pub fn get_recipe_entries(inventory: &Inventory, registry: &ItemRegistry) -> Vec
 /// Called from RegisteredModal::spawn via run_system_cached.
 pub fn spawn_anvil_modal_impl(
     mut commands: Commands,
-    _game_sprites: &GameSprites,
-    _game_fonts: &GameFonts,
     inventory: &Inventory,
     registry: &ItemRegistry,
+    reputation: &Reputation,
+    town_state: &TownState,
+    town: TownId,
 ) {
     commands.insert_resource(FocusState {
         focused: Some(FocusPanel::RecipeGrid),
     });
 
-    let recipe_entries = get_recipe_entries(inventory, registry);
+    let recipe_entries = get_recipe_entries(inventory, registry, reputation, town_state, town);
     let player_entries = ItemGridEntry::from_inventory(inventory);
 
     commands.spawn_modal(
@@ -89,17 +107,39 @@ pub fn spawn_anvil_modal_impl(
     );
 }
 
+/// What the anvil's detail pane reads about recipes and quality upgrades.
+#[derive(SystemParam)]
+pub struct AnvilDetails<'w> {
+    registry: Res<'w, ItemRegistry>,
+    reputation: Res<'w, Reputation>,
+    town_state: Res<'w, TownState>,
+    town: Res<'w, CurrentTown>,
+    streak: Res<'w, UpgradeStreak>,
+}
+
 pub fn populate_anvil_detail_pane_content(
     mut commands: Commands,
     game_fonts: Res<GameFonts>,
     player: Query<&Inventory, With<PlayerMarker>>,
-    panes: Query<Ref<ItemDetailPane>>,
-    content_query: Query<(Entity, Option<&Children>), With<ItemDetailPaneContent>>,
-    registry: Res<ItemRegistry>,
+    detail_panes: DetailPaneQueries,
+    details: AnvilDetails,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
 ) {
     let Ok(inventory) = player.single() else {
         return;
     };
+    let DetailPaneQueries {
+        panes,
+        content: content_query,
+    } = detail_panes;
+    let AnvilDetails {
+        registry,
+        reputation,
+        town_state,
+        town,
+        streak,
+    } = details;
 
     for pane in &panes {
         if !pane.is_changed() {
@@ -121,10 +161,12 @@ pub fn populate_anvil_detail_pane_content(
                 let recipes = RecipeId::all_forging_recipes();
                 recipes.get(selected_index).map(|recipe_id| {
                     let spec = recipe_id.spec();
-                    let can_craft = spec
-                        .ingredients
-                        .iter()
-                        .all(|(item_id, required)| inventory.count_item(*item_id) >= *required);
+                    let can_craft = reputation.recipe_unlocked(*recipe_id)
+                        && town_state.recipe_unlocked(town.0, *recipe_id)
+                        && spec
+                            .ingredients
+                            .iter()
+                            .all(|(item_id, required)| inventory.count_item(*item_id) >= *required);
                     RecipeOrItem::Recipe {
                         recipe_id: *recipe_id,
                         can_craft,
@@ -137,6 +179,12 @@ pub fn populate_anvil_detail_pane_content(
                 .map(|inv_item| RecipeOrItem::Item {
                     item_id: inv_item.item.item_id,
                     quantity: inv_item.quantity,
+                    upgrades: (inv_item.item.num_upgrades, inv_item.item.max_upgrades),
+                    quality: inv_item
+                        .item
+                        .item_type
+                        .is_equipment()
+                        .then_some(inv_item.item.quality),
                 }),
             _ => None,
         };
@@ -166,6 +214,32 @@ pub fn populate_anvil_detail_pane_content(
                             .build(),
                     );
 
+                    let locked_behind = FactionId::recipe_requirement(recipe_id)
+                        .filter(|_| !reputation.recipe_unlocked(recipe_id));
+                    if let Some((faction, rank)) = locked_behind {
+                        parent.spawn((
+                            Text::new(loc.format(
+                                "anvil.recipe_locked",
+                                &[
+                                    ("rank", &loc.get(rank.label_key())),
+                                    ("faction", &loc.get(faction.name_key())),
+                                ],
+                            )),
+                            game_fonts.pixel_font(12.0),
+                            TextColor(Color::srgb(0.9, 0.5, 0.3)),
+                        ));
+                    }
+                    if !town_state.recipe_unlocked(town.0, recipe_id) {
+                        parent.spawn((
+                            Text::new(loc.format(
+                                "anvil.blacksmith_locked",
+                                &[("level", &recipe_id.tier())],
+                            )),
+                            game_fonts.pixel_font(12.0),
+                            TextColor(Color::srgb(0.9, 0.5, 0.3)),
+                        ));
+                    }
+
                     parent.spawn((
                         Text::new(format!("{}", output_item.item_type)),
                         game_fonts.pixel_font(14.0),
@@ -173,7 +247,7 @@ pub fn populate_anvil_detail_pane_content(
                     ));
 
                     parent.spawn((
-                        Text::new("Ingredients:"),
+                        Text::new(loc.get("anvil.ingredients")),
                         game_fonts.pixel_font(14.0),
                         TextColor(Color::srgb(0.8, 0.8, 0.8)),
                         Node {
@@ -196,6 +270,13 @@ pub fn populate_anvil_detail_pane_content(
                             TextColor(color),
                         ));
                     }
+                    if !can_craft {
+                        parent.spawn((
+                            Text::new(loc.get("anvil.fetch_hint")),
+                            game_fonts.pixel_font(12.0),
+                            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                        ));
+                    }
 
                     let stats: Vec<_> = output_item
                         .stats
@@ -205,7 +286,7 @@ pub fn populate_anvil_detail_pane_content(
                         .collect();
                     if !stats.is_empty() {
                         parent.spawn((
-                            Text::new("Stats:"),
+                            Text::new(loc.get("anvil.stats")),
                             game_fonts.pixel_font(14.0),
                             TextColor(Color::srgb(0.8, 0.8, 0.8)),
                             Node {
@@ -216,20 +297,213 @@ pub fn populate_anvil_detail_pane_content(
                         let display = ItemStatsDisplay::builder(stats)
                             .font_size(12.0)
                             .text_color(Color::srgb(0.85, 0.85, 0.85))
+                            .maybe_comparison(inventory.compare_to_equipped(&output_item))
                             .build();
                         parent.spawn(display);
                     }
                 }
-                RecipeOrItem::Item { item_id, quantity } => {
+                RecipeOrItem::Item {
+                    item_id,
+                    quantity,
+                    upgrades,
+                    quality,
+                } => {
                     let item = registry.spawn(item_id);
                     let display = ItemDetailDisplay::builder(&item).quantity(quantity).build();
                     parent.spawn(display);
+
+                    if let Some(quality) = quality {
+                        let lines =
+                            quality_upgrade_lines(quality, inventory, &streak, &loc, &palette);
+                        for (line, color) in lines {
+                            parent.spawn((
+                                Text::new(line),
+                                game_fonts.pixel_font(12.0),
+                                TextColor(color),
+                            ));
+                        }
+                    }
+
+                    let Some(evolution) = registry.get(item_id).evolution() else {
+                        return;
+                    };
+                    let successor = registry.get(evolution.into);
+                    parent.spawn((
+                        Text::new(loc.format(
+                            "evolution.evolves_into",
+                            &[("item", &loc.item_name(successor.id, &successor.name))],
+                        )),
+                        game_fonts.pixel_font(14.0),
+                        TextColor(EVOLUTION_COLOR),
+                        Node {
+                            margin: UiRect::top(Val::Px(8.0)),
+                            ..default()
+                        },
+                    ));
+
+                    let (have, max) = upgrades;
+                    let mut ready = have >= max;
+                    parent.spawn((
+                        Text::new(
+                            loc.format("evolution.upgrades", &[("have", &have), ("max", &max)]),
+                        ),
+                        game_fonts.pixel_font(12.0),
+                        TextColor(have_color(have >= max)),
+                    ));
+                    for (material, required) in &evolution.materials {
+                        let have = inventory.count_item(*material);
+                        let spec = registry.get(*material);
+                        ready &= have >= *required;
+                        parent.spawn((
+                            Text::new(format!(
+                                "  {} ({}/{})",
+                                loc.item_name(*material, &spec.name),
+                                have,
+                                required
+                            )),
+                            game_fonts.pixel_font(12.0),
+                            TextColor(have_color(have >= *required)),
+                        ));
+                    }
+                    if ready {
+                        parent.spawn((
+                            Text::new(loc.get("evolution.ready")),
+                            game_fonts.pixel_font(12.0),
+                            TextColor(EVOLUTION_COLOR),
+                        ));
+                    }
                 }
             }
         });
     }
 }
 
+/// Odds and costs of a quality upgrade on an item of `quality`, with the streak bonus
+/// spelled out so the player can see a failure run paying off.
+fn quality_upgrade_lines(
+    quality: ItemQuality,
+    inventory: &Inventory,
+    streak: &UpgradeStreak,
+    loc: &Localization,
+    palette: &Palette,
+) -> Vec<(String, Color)> {
+    let Some(next) = quality.next_quality() else {
+        return vec![(loc.get("anvil.quality_max").to_string(), QUALITY_HINT_COLOR)];
+    };
+
+    let chance = (streak.success_chance(quality) * 100.0).round() as i32;
+    let mut lines = vec![(
+        loc.format(
+            "anvil.quality_chance",
+            &[
+                ("from", &quality.display_name()),
+                ("to", &next.display_name()),
+                ("chance", &chance),
+            ],
+        ),
+        palette.quality(next),
+    )];
+    if streak.failures() > 0 {
+        let bonus = (streak.bonus() * 100.0).round() as i32;
+        lines.push((
+            loc.format(
+                "anvil.quality_streak",
+                &[("bonus", &bonus), ("failures", &streak.failures())],
+            ),
+            Color::srgb(0.3, 0.9, 0.3),
+        ));
+    }
+    if quality.previous_quality().is_some() {
+        let runes = inventory.count_item(ItemId::WardingRune);
+        let line = if runes > 0 {
+            loc.format("anvil.quality_warded", &[("runes", &runes)])
+        } else {
+            loc.get("anvil.quality_risk").to_string()
+        };
+        lines.push((line, QUALITY_HINT_COLOR));
+    }
+    let stones = inventory.count_item(ItemId::QualityUpgradeStone);
+    lines.push((
+        loc.format("anvil.quality_hint", &[("stones", &stones)]),
+        have_color(stones > 0),
+    ));
+    lines
+}
+
+/// Toasts how a quality upgrade went and refreshes the bag and detail pane to match.
+pub fn show_quality_upgrade_results(
+    mut results: MessageReader<BlacksmithResult>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    mut player_grids: Query<(&mut ItemGrid, &mut ItemGridSelection), With<AnvilPlayerGrid>>,
+    mut panes: Query<&mut ItemDetailPane>,
+    loc: Res<Localization>,
+) {
+    let mut refresh = false;
+    for result in results.read() {
+        let message = match result {
+            BlacksmithResult::QualityUpgradeSuccess {
+                item_name,
+                new_quality,
+            } => loc.format(
+                "blacksmith.quality_success",
+                &[("item", item_name), ("quality", new_quality)],
+            ),
+            BlacksmithResult::QualityUpgradeFailed {
+                item_name,
+                downgraded_to: Some(quality),
+                ..
+            } => loc.format(
+                "blacksmith.quality_downgraded",
+                &[("item", item_name), ("quality", quality)],
+            ),
+            BlacksmithResult::QualityUpgradeFailed {
+                item_name,
+                warded: true,
+                ..
+            } => loc.format("blacksmith.quality_warded", &[("item", item_name)]),
+            BlacksmithResult::QualityUpgradeFailed { item_name, .. } => {
+                loc.format("blacksmith.quality_failed", &[("item", item_name)])
+            }
+            BlacksmithResult::QualityUpgradeFailedNoStone => {
+                loc.get("blacksmith.quality_no_stone").to_string()
+            }
+            BlacksmithResult::QualityUpgradeFailedMaxQuality { item_name } => {
+                loc.format("blacksmith.quality_max", &[("item", item_name)])
+            }
+            _ => continue,
+        };
+        toast_writer.write(ShowToast::new(message));
+        refresh = true;
+    }
+
+    if !refresh {
+        return;
+    }
+    if let (Ok(inventory), Ok((mut grid, mut selection))) =
+        (player.single(), player_grids.single_mut())
+    {
+        grid.items = ItemGridEntry::from_inventory(inventory);
+        selection.clamp(grid.items.len());
+    }
+    for mut pane in &mut panes {
+        pane.set_changed();
+    }
+}
+
+const QUALITY_HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+/// Colour of evolution details in the detail pane.
+const EVOLUTION_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+fn have_color(enough: bool) -> Color {
+    if enough {
+        Color::srgb(0.3, 0.9, 0.3)
+    } else {
+        Color::srgb(0.9, 0.3, 0.3)
+    }
+}
+
 /// Helper enum for detail pane content.
 enum RecipeOrItem {
     Recipe {
@@ -239,5 +513,9 @@ enum RecipeOrItem {
     Item {
         item_id: crate::item::ItemId,
         quantity: u32,
+        /// Upgrades done and the most the item takes, for its evolution.
+        upgrades: (i32, i32),
+        /// The item's quality, if it is equipment whose quality can be upgraded.
+        quality: Option<ItemQuality>,
     },
 }
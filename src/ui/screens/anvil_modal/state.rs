@@ -68,10 +68,11 @@ impl RegisteredModal for AnvilModal {
 /// System that spawns the anvil modal UI.
 fn do_spawn_anvil_modal(
     commands: Commands,
-    game_sprites: Res<crate::assets::GameSprites>,
-    game_fonts: Res<crate::assets::GameFonts>,
     player_query: Query<&Inventory, With<PlayerMarker>>,
     registry: Res<crate::item::ItemRegistry>,
+    reputation: Res<crate::faction::Reputation>,
+    town_state: Res<crate::location::TownState>,
+    town: Res<crate::location::CurrentTown>,
 ) {
     let Ok(inventory) = player_query.single() else {
         tracing::error!("No player inventory found for anvil modal");
@@ -79,9 +80,10 @@ fn do_spawn_anvil_modal(
     };
     super::render::spawn_anvil_modal_impl(
         commands,
-        &game_sprites,
-        &game_fonts,
         inventory,
         &registry,
+        &reputation,
+        &town_state,
+        town.0,
     );
 }
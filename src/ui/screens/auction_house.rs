@@ -0,0 +1,442 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::auction::{
+    AuctionHouse, AuctionRequest, Listing, Seller, LISTING_DAYS, TRADER_NAMES,
+};
+use crate::dungeon::DungeonState;
+use crate::economy::{Wallet, WorthGold};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::Item;
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::widgets::TextRows;
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::modal::ActiveModal;
+use crate::world_event::GameClock;
+
+const TITLE_COLOR: Color = Color::srgb(0.85, 0.7, 1.0);
+const NORMAL_COLOR: Color = Color::WHITE;
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+/// Asking prices are set as a percentage of the market price, in these steps.
+const ASKING_STEP_PERCENT: i32 = 10;
+const ASKING_PERCENT_RANGE: (i32, i32) = (10, 300);
+
+pub struct AuctionHouseScreenPlugin;
+
+impl Plugin for AuctionHouseScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AuctionSelection>()
+            .add_systems(OnEnter(AppState::AuctionHouse), spawn_auction_house)
+            .add_systems(OnExit(AppState::AuctionHouse), despawn_auction_house)
+            .add_systems(
+                Update,
+                open_auction_house
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_auction_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    refresh_auction_house
+                        .run_if(
+                            resource_changed::<AuctionSelection>
+                                .or(resource_changed::<AuctionHouse>),
+                        )
+                        .in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::AuctionHouse)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum AuctionTab {
+    #[default]
+    Browse,
+    Sell,
+    Listings,
+}
+
+impl AuctionTab {
+    const ALL: [AuctionTab; 3] = [AuctionTab::Browse, AuctionTab::Sell, AuctionTab::Listings];
+
+    fn label_key(self) -> &'static str {
+        match self {
+            AuctionTab::Browse => "auction.tab_browse",
+            AuctionTab::Sell => "auction.tab_sell",
+            AuctionTab::Listings => "auction.tab_listings",
+        }
+    }
+
+    fn hint_key(self) -> &'static str {
+        match self {
+            AuctionTab::Browse => "auction.hint_browse",
+            AuctionTab::Sell => "auction.hint_sell",
+            AuctionTab::Listings => "auction.hint_listings",
+        }
+    }
+
+    fn next(self, forward: bool) -> Self {
+        let count = Self::ALL.len();
+        let index = Self::ALL.iter().position(|tab| *tab == self).unwrap_or(0);
+        let step = if forward { 1 } else { count - 1 };
+        Self::ALL[(index + step) % count]
+    }
+}
+
+/// The open tab, highlighted row, and the asking price being set on the sell tab as a
+/// percentage of market price.
+#[derive(Resource)]
+struct AuctionSelection {
+    tab: AuctionTab,
+    index: usize,
+    asking_percent: i32,
+}
+
+impl Default for AuctionSelection {
+    fn default() -> Self {
+        Self {
+            tab: AuctionTab::Browse,
+            index: 0,
+            asking_percent: 100,
+        }
+    }
+}
+
+#[derive(Component)]
+struct AuctionRoot;
+
+#[derive(Component)]
+struct AuctionTabs;
+
+#[derive(Component)]
+struct AuctionList;
+
+#[derive(Component)]
+struct AuctionDetail;
+
+#[derive(Component)]
+struct AuctionHint;
+
+/// The screen's list rows and its tab, detail, and hint lines.
+#[derive(SystemParam)]
+struct AuctionTexts<'w, 's> {
+    list: Query<'w, 's, &'static mut TextRows, With<AuctionList>>,
+    tabs: Query<'w, 's, &'static mut Text, (With<AuctionTabs>, Without<AuctionDetail>)>,
+    detail: Query<'w, 's, &'static mut Text, (With<AuctionDetail>, Without<AuctionHint>)>,
+    hint: Query<'w, 's, &'static mut Text, (With<AuctionHint>, Without<AuctionTabs>)>,
+}
+
+fn open_auction_house(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenAuctionHouse || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::AuctionHouse);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("auction.not_in_town")));
+        }
+    }
+}
+
+fn spawn_auction_house(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut selection: ResMut<AuctionSelection>,
+) {
+    *selection = AuctionSelection::default();
+
+    commands
+        .spawn((
+            AuctionRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("auction.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                AuctionTabs,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(palette.selection),
+            ));
+
+            parent.spawn((AuctionList, column_node(8.0), TextRows::new(22.0)));
+
+            parent.spawn((
+                AuctionDetail,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+
+            parent.spawn((
+                AuctionHint,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+/// Number of rows on the open tab.
+fn row_count(tab: AuctionTab, house: &AuctionHouse, inventory: &Inventory) -> usize {
+    match tab {
+        AuctionTab::Browse => house.for_sale().count(),
+        AuctionTab::Sell => inventory.get_inventory_items().len(),
+        AuctionTab::Listings => house.player_listings().count(),
+    }
+}
+
+fn handle_auction_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<AuctionSelection>,
+    house: Res<AuctionHouse>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    mut auction_writer: MessageWriter<AuctionRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        let count = row_count(selection.tab, &house, inventory).max(1);
+        let (min_percent, max_percent) = ASKING_PERCENT_RANGE;
+
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.index = (selection.index.min(count - 1) + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.index = (selection.index.min(count - 1) + 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Left)
+                if selection.tab == AuctionTab::Sell =>
+            {
+                selection.asking_percent =
+                    (selection.asking_percent - ASKING_STEP_PERCENT).max(min_percent);
+            }
+            GameAction::Navigate(NavigationDirection::Right)
+                if selection.tab == AuctionTab::Sell =>
+            {
+                selection.asking_percent =
+                    (selection.asking_percent + ASKING_STEP_PERCENT).min(max_percent);
+            }
+            GameAction::NextTab | GameAction::PrevTab => {
+                selection.tab = selection.tab.next(*action == GameAction::NextTab);
+                selection.index = 0;
+            }
+            GameAction::Select => match selection.tab {
+                AuctionTab::Browse => {
+                    if let Some(listing) = house.for_sale().nth(selection.index) {
+                        auction_writer.write(AuctionRequest::Buy {
+                            listing_id: listing.id,
+                        });
+                    }
+                }
+                AuctionTab::Sell => {
+                    let items = inventory.get_inventory_items();
+                    if let Some(inv_item) = items.get(selection.index) {
+                        auction_writer.write(AuctionRequest::List {
+                            inventory_index: selection.index,
+                            asking: asking_price(&house, &inv_item.item, selection.asking_percent),
+                        });
+                    }
+                }
+                AuctionTab::Listings => {}
+            },
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenAuctionHouse => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn asking_price(house: &AuctionHouse, item: &Item, percent: i32) -> i32 {
+    (house.market_price(item) * percent / 100).max(1)
+}
+
+fn refresh_auction_house(
+    selection: Res<AuctionSelection>,
+    house: Res<AuctionHouse>,
+    clock: Res<GameClock>,
+    player: Query<(&Inventory, &Wallet), With<PlayerMarker>>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    texts: AuctionTexts,
+) {
+    let AuctionTexts {
+        mut list,
+        tabs: mut tabs_text,
+        detail: mut detail_text,
+        hint: mut hint_text,
+    } = texts;
+    let (Ok((inventory, wallet)), Ok(mut list)) = (player.single(), list.single_mut()) else {
+        return;
+    };
+    let today = clock.day();
+    let count = row_count(selection.tab, &house, inventory);
+    let selected = selection.index.min(count.saturating_sub(1));
+
+    if let Ok(mut text) = tabs_text.single_mut() {
+        **text = AuctionTab::ALL
+            .iter()
+            .map(|tab| {
+                let label = loc.get(tab.label_key());
+                if *tab == selection.tab {
+                    format!("[{label}]")
+                } else {
+                    label.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("   ");
+    }
+    if let Ok(mut text) = hint_text.single_mut() {
+        **text = loc.format(selection.tab.hint_key(), &[("days", &LISTING_DAYS)]);
+    }
+
+    let rows: Vec<String> = match selection.tab {
+        AuctionTab::Browse => house
+            .for_sale()
+            .map(|listing| browse_row(&loc, listing, today))
+            .collect(),
+        AuctionTab::Sell => inventory
+            .get_inventory_items()
+            .iter()
+            .map(|inv_item| {
+                let item = &inv_item.item;
+                loc.format(
+                    "auction.sell_row",
+                    &[
+                        ("item", &loc.item_name(item.item_id, &item.name)),
+                        ("quantity", &inv_item.quantity),
+                        ("vendor", &item.sell_price()),
+                        ("market", &house.market_price(item)),
+                    ],
+                )
+            })
+            .collect(),
+        AuctionTab::Listings => house
+            .player_listings()
+            .map(|listing| own_listing_row(&loc, listing, today))
+            .collect(),
+    };
+
+    if let Ok(mut text) = detail_text.single_mut() {
+        let sell_item = inventory.get_inventory_items().get(selected);
+        **text = match (selection.tab, sell_item) {
+            (AuctionTab::Sell, Some(inv_item)) => {
+                let asking = asking_price(&house, &inv_item.item, selection.asking_percent);
+                loc.format(
+                    "auction.sell_detail",
+                    &[
+                        ("asking", &asking),
+                        ("percent", &selection.asking_percent),
+                        ("fee", &AuctionHouse::listing_fee(asking)),
+                        ("proceeds", &AuctionHouse::proceeds(asking)),
+                    ],
+                )
+            }
+            _ => loc.format("auction.balance", &[("gold", &wallet.gold()), ("day", &today)]),
+        };
+    }
+
+    if rows.is_empty() {
+        list.set([(loc.get("auction.empty").to_string(), HINT_COLOR)]);
+        return;
+    }
+    list.set(rows.into_iter().enumerate().map(|(index, row)| {
+        let color = if index == selected {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        };
+        (row, color)
+    }));
+}
+
+fn browse_row(loc: &Localization, listing: &Listing, today: u32) -> String {
+    let trader = match listing.seller {
+        Seller::Trader(index) => TRADER_NAMES[index % TRADER_NAMES.len()],
+        Seller::Player => "",
+    };
+    loc.format(
+        "auction.browse_row",
+        &[
+            ("item", &loc.item_name(listing.item.item_id, &listing.item.name)),
+            ("price", &listing.asking),
+            ("trader", &trader),
+            ("days", &listing.days_left(today)),
+        ],
+    )
+}
+
+fn own_listing_row(loc: &Localization, listing: &Listing, today: u32) -> String {
+    let name = loc.item_name(listing.item.item_id, &listing.item.name);
+    let days = listing.days_left(today);
+    match listing.best_bid {
+        Some(bid) => loc.format(
+            "auction.listing_row_bid",
+            &[("item", &name), ("asking", &listing.asking), ("bid", &bid), ("days", &days)],
+        ),
+        None => loc.format(
+            "auction.listing_row",
+            &[("item", &name), ("asking", &listing.asking), ("days", &days)],
+        ),
+    }
+}
+
+fn despawn_auction_house(mut commands: Commands, root: Query<Entity, With<AuctionRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
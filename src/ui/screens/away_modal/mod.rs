@@ -0,0 +1,8 @@
+//! The "while you were away" summary of crafts that kept going while the game was closed.
+
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::AwayModalPlugin;
+pub use state::AwayModal;
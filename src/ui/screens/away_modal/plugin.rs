@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::crafting_station::AwayReport;
+use crate::input::GameAction;
+use crate::states::{AppState, ScreenSet};
+use crate::ui::modal_registry::{modal_close_system, ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::{in_away_modal, ActiveModal, ModalType, OpenModal};
+
+use super::state::AwayModal;
+
+pub struct AwayModalPlugin;
+
+impl Plugin for AwayModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<AwayModal>().add_systems(
+            Update,
+            (
+                (
+                    modal_close_system::<AwayModal>,
+                    close_on_select.run_if(on_message::<GameAction>),
+                )
+                    .in_set(ScreenSet::Input)
+                    .run_if(in_away_modal),
+                open_away_summary
+                    .run_if(resource_exists::<AwayReport>.and(not(in_away_modal)))
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Logic),
+            ),
+        );
+    }
+}
+
+/// Shows the summary once the player is in the game and nothing else is open.
+fn open_away_summary(mut commands: Commands, active_modal: Res<ActiveModal>) {
+    if active_modal.modal.is_none() {
+        commands.trigger(OpenModal(ModalType::Away));
+    }
+}
+
+fn close_on_select(mut commands: Commands, mut action_reader: MessageReader<GameAction>) {
+    if action_reader
+        .read()
+        .any(|action| *action == GameAction::Select)
+    {
+        commands.close_modal::<AwayModal>();
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::crafting_station::{AwayCraft, AwayReport, CraftingStationType};
+use crate::i18n::Localization;
+use crate::ui::widgets::Column;
+use crate::ui::{Modal, SpawnModalExt};
+
+use super::state::AwayModalRoot;
+
+const MODAL_WIDTH: f32 = 380.0;
+const TEXT_SIZE: f32 = 16.0;
+
+const AWAY_COLOR: Color = Color::srgb(0.7, 0.7, 0.9);
+const FINISHED_COLOR: Color = Color::srgb(0.5, 0.9, 0.5);
+const PROGRESS_COLOR: Color = Color::WHITE;
+
+/// "2h 5m", or just minutes under an hour.
+fn away_time(secs: u64) -> String {
+    let minutes = secs / 60;
+    if minutes < 60 {
+        format!("{minutes}m")
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
+
+fn craft_line(craft: &AwayCraft, loc: &Localization) -> String {
+    let key = match (craft.kind, craft.is_finished()) {
+        (CraftingStationType::Forge, true) => "away.forge_finished",
+        (CraftingStationType::Forge, false) => "away.forge_progress",
+        (CraftingStationType::Anvil, true) => "away.anvil_finished",
+        (CraftingStationType::Anvil, false) => "away.anvil_progress",
+    };
+    loc.format(key, &[("secs", &(craft.remaining_secs.ceil() as u32))])
+}
+
+pub fn do_spawn_away_modal(
+    mut commands: Commands,
+    report: Option<Res<AwayReport>>,
+    loc: Res<Localization>,
+) {
+    let Some(report) = report else {
+        return;
+    };
+    let away = loc.format("away.time_away", &[("time", &away_time(report.secs_away))]);
+    let lines: Vec<(String, Color)> = report
+        .crafts
+        .iter()
+        .map(|craft| {
+            let color = if craft.is_finished() {
+                FINISHED_COLOR
+            } else {
+                PROGRESS_COLOR
+            };
+            (craft_line(craft, &loc), color)
+        })
+        .collect();
+
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get("away.title"))
+            .size((MODAL_WIDTH, 0.0))
+            .hint(loc.get("away.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(AwayModalRoot);
+            }))
+            .content(Box::new(move |c| {
+                c.spawn(Column::new().gap(8.0).align_center())
+                    .with_children(|col| {
+                        col.spawn((
+                            Text::new(away),
+                            TextFont {
+                                font_size: TEXT_SIZE,
+                                ..default()
+                            },
+                            TextColor(AWAY_COLOR),
+                        ));
+                        for (line, color) in lines {
+                            col.spawn((
+                                Text::new(line),
+                                TextFont {
+                                    font_size: TEXT_SIZE,
+                                    ..default()
+                                },
+                                TextColor(color),
+                            ));
+                        }
+                    });
+            }))
+            .build(),
+    );
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::crafting_station::AwayReport;
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+
+use super::render::do_spawn_away_modal;
+
+#[derive(Component)]
+pub struct AwayModalRoot;
+
+pub struct AwayModal;
+
+impl RegisteredModal for AwayModal {
+    type Root = AwayModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Away;
+
+    fn spawn(world: &mut World) {
+        world.run_system_cached(do_spawn_away_modal).ok();
+    }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<AwayReport>();
+    }
+}
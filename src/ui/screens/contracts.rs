@@ -0,0 +1,281 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::{BlacksmithContracts, ContractRequest, CraftingContract, ShowToast};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::{ItemId, ItemRegistry};
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::widgets::TextRows;
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.6, 0.3);
+const NORMAL_COLOR: Color = Color::WHITE;
+const ACTIVE_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+pub struct ContractsScreenPlugin;
+
+impl Plugin for ContractsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ContractSelection>()
+            .add_systems(OnEnter(AppState::Contracts), spawn_contracts_screen)
+            .add_systems(OnExit(AppState::Contracts), despawn_contracts_screen)
+            .add_systems(
+                Update,
+                open_contracts_screen
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_contracts_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_contracts_screen.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Contracts)),
+            );
+    }
+}
+
+/// Index of the highlighted offer.
+#[derive(Resource, Default)]
+struct ContractSelection(usize);
+
+#[derive(Component)]
+struct ContractsRoot;
+
+#[derive(Component)]
+struct ContractsActive;
+
+#[derive(Component)]
+struct ContractsOffers;
+
+#[derive(Component)]
+struct ContractsHint;
+
+/// The offer rows and the active-contract and hint lines, plus whether the rows were just
+/// spawned.
+#[derive(SystemParam)]
+struct ContractsTexts<'w, 's> {
+    offers: Query<'w, 's, &'static mut TextRows, With<ContractsOffers>>,
+    active: Query<'w, 's, &'static mut Text, (With<ContractsActive>, Without<ContractsHint>)>,
+    hint: Query<'w, 's, &'static mut Text, (With<ContractsHint>, Without<ContractsActive>)>,
+    added: Query<'w, 's, (), Added<ContractsOffers>>,
+}
+
+fn open_contracts_screen(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenContracts || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::Contracts);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("contracts.not_in_town")));
+        }
+    }
+}
+
+fn spawn_contracts_screen(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<ContractSelection>,
+) {
+    selection.0 = 0;
+
+    commands
+        .spawn((
+            ContractsRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("contracts.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                ContractsActive,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(ACTIVE_COLOR),
+            ));
+
+            parent.spawn((ContractsOffers, column_node(8.0), TextRows::new(22.0)));
+
+            parent.spawn((
+                ContractsHint,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+fn handle_contracts_input(
+    mut action_reader: MessageReader<GameAction>,
+    contracts: Res<BlacksmithContracts>,
+    mut selection: ResMut<ContractSelection>,
+    mut contract_writer: MessageWriter<ContractRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = contracts.offers.len().max(1);
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0.min(count - 1) + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0.min(count - 1) + 1) % count;
+            }
+            GameAction::Select if contracts.active.is_some() => {
+                contract_writer.write(ContractRequest::Deliver);
+            }
+            GameAction::Select => {
+                contract_writer.write(ContractRequest::Accept(selection.0));
+            }
+            GameAction::NextTab if contracts.active.is_some() => {
+                contract_writer.write(ContractRequest::Abandon);
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenContracts => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Formats `contract` with `key`, listing the materials the blacksmith supplies.
+fn contract_text(
+    loc: &Localization,
+    registry: &ItemRegistry,
+    key: &str,
+    contract: &CraftingContract,
+) -> String {
+    let name = |id: ItemId| loc.item_name(id, &registry.get(id).name).to_string();
+    let materials = contract
+        .recipe
+        .spec()
+        .ingredients
+        .iter()
+        .map(|(&id, qty)| format!("{qty}x {}", name(id)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    loc.format(
+        key,
+        &[
+            ("item", &name(contract.output())),
+            ("fee", &contract.fee),
+            ("xp", &contract.xp),
+            ("materials", &materials),
+        ],
+    )
+}
+
+fn update_contracts_screen(
+    contracts: Res<BlacksmithContracts>,
+    selection: Res<ContractSelection>,
+    player: Query<Ref<Inventory>, With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    texts: ContractsTexts,
+) {
+    let ContractsTexts {
+        offers: mut offers_list,
+        active: mut active_text,
+        hint: mut hint_text,
+        added,
+    } = texts;
+    let (Ok(inventory), Ok(mut list)) = (player.single(), offers_list.single_mut()) else {
+        return;
+    };
+    if !contracts.is_changed()
+        && !selection.is_changed()
+        && !inventory.is_changed()
+        && added.is_empty()
+    {
+        return;
+    }
+
+    if let Ok(mut text) = active_text.single_mut() {
+        **text = match &contracts.active {
+            Some(contract) => {
+                let line = contract_text(&loc, &registry, "contracts.active", contract);
+                let crafted = inventory.count_item(contract.output()) > 0;
+                let status = if crafted {
+                    loc.get("contracts.ready")
+                } else {
+                    loc.get("contracts.in_progress")
+                };
+                format!("{line}\n{status}")
+            }
+            None => loc.get("contracts.none_active").to_string(),
+        };
+    }
+
+    if let Ok(mut text) = hint_text.single_mut() {
+        **text = if contracts.active.is_some() {
+            loc.get("contracts.hint_active").to_string()
+        } else {
+            loc.get("contracts.hint").to_string()
+        };
+    }
+
+    let selected = selection.0.min(contracts.offers.len().saturating_sub(1));
+    if contracts.offers.is_empty() {
+        list.set([(loc.get("contracts.no_offers").to_string(), NORMAL_COLOR)]);
+        return;
+    }
+    list.set(contracts.offers.iter().enumerate().map(|(index, contract)| {
+        let color = if index == selected && contracts.active.is_none() {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        };
+        (contract_text(&loc, &registry, "contracts.offer", contract), color)
+    }));
+}
+
+fn despawn_contracts_screen(mut commands: Commands, root: Query<Entity, With<ContractsRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,8 @@
+//! A conversation with an NPC: their line, and the choices the player can answer with.
+
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::DialogueModalPlugin;
+pub use state::DialogueModal;
@@ -0,0 +1,107 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::dialogue::{ActiveDialogue, ChoiceConditions, DialogueChosen};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::states::ScreenSet;
+use crate::ui::modal_registry::{modal_close_system, ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::in_dialogue_modal;
+
+use super::render::update_dialogue;
+use super::state::{DialogueChoices, DialogueModal, DialogueModalState};
+
+pub struct DialogueModalPlugin;
+
+impl Plugin for DialogueModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<DialogueModal>().add_systems(
+            Update,
+            (
+                (
+                    modal_close_system::<DialogueModal>,
+                    handle_dialogue_input.run_if(on_message::<GameAction>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Input),
+                update_dialogue
+                    .run_if(
+                        resource_exists_and_changed::<ActiveDialogue>
+                            .or(resource_exists_and_changed::<DialogueModalState>)
+                            .or(any_match_filter::<Added<DialogueChoices>>),
+                    )
+                    .in_set(ScreenSet::Render),
+            )
+                .run_if(
+                    in_dialogue_modal
+                        .and(resource_exists::<ActiveDialogue>)
+                        .and(resource_exists::<DialogueModalState>),
+                ),
+        );
+    }
+}
+
+/// The conversation underway and the choice highlighted in it.
+#[derive(SystemParam)]
+struct Conversation<'w> {
+    dialogue: ResMut<'w, ActiveDialogue>,
+    state: ResMut<'w, DialogueModalState>,
+}
+
+/// Moves between the offered choices and picks one: its effects go out, then the
+/// conversation moves to the next node or ends. A choice whose items don't fit in the
+/// backpack is refused before any of its effects apply.
+fn handle_dialogue_input(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    conversation: Conversation,
+    conditions: ChoiceConditions,
+    loc: Res<Localization>,
+    mut chosen_writer: MessageWriter<DialogueChosen>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let Conversation {
+        mut dialogue,
+        mut state,
+    } = conversation;
+    let Some(context) = conditions.context() else {
+        return;
+    };
+
+    for action in action_reader.read() {
+        let available = dialogue.tree.available_choices(&dialogue.node, context);
+        let count = available.len().max(1);
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                state.selected = (state.selected + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                state.selected = (state.selected + 1) % count;
+            }
+            GameAction::Select => {
+                let Some(choice) = available.get(state.selected) else {
+                    continue;
+                };
+                if !conditions.fits(choice) {
+                    toast_writer.write(ShowToast::new(loc.get("dialogue.backpack_full")));
+                    continue;
+                }
+                // Close first so a shop opened by the choice doesn't find this modal
+                // still up.
+                let next = (!choice.ends()).then(|| choice.next.clone()).flatten();
+                if next.is_none() {
+                    commands.close_modal::<DialogueModal>();
+                }
+                chosen_writer.write(DialogueChosen {
+                    effects: choice.effects.clone(),
+                });
+                if let Some(next) = next {
+                    dialogue.node = next;
+                    state.selected = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::dialogue::{ActiveDialogue, ChoiceConditions};
+use crate::i18n::Localization;
+use crate::ui::widgets::Column;
+use crate::ui::{Modal, SpawnModalExt};
+
+use super::state::{DialogueChoices, DialogueLine, DialogueModalRoot, DialogueModalState};
+
+const MODAL_WIDTH: f32 = 460.0;
+const LINE_SIZE: f32 = 18.0;
+const CHOICE_SIZE: f32 = 18.0;
+
+const LINE_COLOR: Color = Color::srgb(0.9, 0.9, 0.8);
+const NORMAL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
+pub fn do_spawn_dialogue_modal(
+    mut commands: Commands,
+    dialogue: Option<Res<ActiveDialogue>>,
+    loc: Res<Localization>,
+) {
+    let Some(dialogue) = dialogue else {
+        return;
+    };
+
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get(&dialogue.tree.name))
+            .size((MODAL_WIDTH, 0.0))
+            .hint(loc.get("dialogue.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(DialogueModalRoot);
+            }))
+            .content(Box::new(|c| {
+                c.spawn(Column::new().gap(16.0)).with_children(|col| {
+                    col.spawn((
+                        DialogueLine,
+                        Text::new(""),
+                        TextFont {
+                            font_size: LINE_SIZE,
+                            ..default()
+                        },
+                        TextColor(LINE_COLOR),
+                    ));
+                    col.spawn((DialogueChoices, Column::new().gap(6.0)));
+                });
+            }))
+            .build(),
+    );
+}
+
+/// Shows the current node's line and rebuilds the list of choices the player qualifies for.
+pub fn update_dialogue(
+    mut commands: Commands,
+    dialogue: Res<ActiveDialogue>,
+    state: Res<DialogueModalState>,
+    conditions: ChoiceConditions,
+    loc: Res<Localization>,
+    mut lines: Query<&mut Text, With<DialogueLine>>,
+    choices: Query<(Entity, Option<&Children>), With<DialogueChoices>>,
+) {
+    let Some(context) = conditions.context() else {
+        return;
+    };
+    let Some(node) = dialogue.tree.node(&dialogue.node) else {
+        return;
+    };
+
+    if let Ok(mut line) = lines.single_mut() {
+        **line = loc.get(&node.text).to_string();
+    }
+
+    let Ok((container, children)) = choices.single() else {
+        return;
+    };
+    if let Some(children) = children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let available = dialogue.tree.available_choices(&dialogue.node, context);
+    commands.entity(container).with_children(|list| {
+        for (index, choice) in available.iter().enumerate() {
+            let color = if index == state.selected {
+                SELECTED_COLOR
+            } else {
+                NORMAL_COLOR
+            };
+            list.spawn((
+                Text::new(format!("{}. {}", index + 1, loc.get(&choice.text))),
+                TextFont {
+                    font_size: CHOICE_SIZE,
+                    ..default()
+                },
+                TextColor(color),
+            ));
+        }
+    });
+}
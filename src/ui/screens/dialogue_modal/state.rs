@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use crate::dialogue::ActiveDialogue;
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+
+use super::render::do_spawn_dialogue_modal;
+
+#[derive(Component)]
+pub struct DialogueModalRoot;
+
+/// What the NPC is saying.
+#[derive(Component)]
+pub struct DialogueLine;
+
+/// Holds the choice texts, rebuilt whenever the conversation moves on.
+#[derive(Component)]
+pub struct DialogueChoices;
+
+/// The highlighted choice, by its index among those currently offered.
+#[derive(Resource, Default)]
+pub struct DialogueModalState {
+    pub selected: usize,
+}
+
+pub struct DialogueModal;
+
+impl RegisteredModal for DialogueModal {
+    type Root = DialogueModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Dialogue;
+
+    fn spawn(world: &mut World) {
+        world.insert_resource(DialogueModalState::default());
+        world.run_system_cached(do_spawn_dialogue_modal).ok();
+    }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<ActiveDialogue>();
+        world.remove_resource::<DialogueModalState>();
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+
+use crate::combat::BossPartDestroyed;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::mob::{BossParts, Health, MobMarker};
+use crate::ui::DyingMob;
+
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.45, 0.3);
+const TARGET_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+const BROKEN_COLOR: Color = Color::srgb(0.45, 0.45, 0.45);
+const HUD_BACKGROUND: Color = Color::srgba(0.1, 0.02, 0.0, 0.8);
+
+#[derive(Component)]
+pub struct BossHud;
+
+/// Picks which part of a colossal boss the player's attacks land on.
+pub fn cycle_boss_target(
+    mut action_reader: MessageReader<GameAction>,
+    mut bosses: Query<&mut BossParts, Without<DyingMob>>,
+) {
+    for action in action_reader.read() {
+        let forward = match action {
+            GameAction::NextTab => true,
+            GameAction::PrevTab => false,
+            _ => continue,
+        };
+        for mut parts in &mut bosses {
+            parts.cycle_target(forward);
+        }
+    }
+}
+
+/// One line of the boss display.
+struct HudLine {
+    text: String,
+    color: Color,
+}
+
+fn hud_lines(
+    loc: &Localization,
+    marker: &MobMarker,
+    health: &Health,
+    parts: &BossParts,
+) -> Vec<HudLine> {
+    let spec = marker.0.spec();
+    let (total, total_max) = parts
+        .parts
+        .iter()
+        .fold((health.current, health.max), |(hp, max), part| {
+            (hp + part.health.current, max + part.health.max)
+        });
+    let mut lines = vec![HudLine {
+        text: loc.format(
+            "boss.hud_title",
+            &[
+                ("name", &loc.mob_name(marker.0, &spec.name)),
+                ("hp", &total),
+                ("max", &total_max),
+            ],
+        ),
+        color: TITLE_COLOR,
+    }];
+
+    let part_line = |name: &str, health: &Health, targeted: bool, note: Option<&str>| {
+        let mut text = loc.format(
+            "boss.part_row",
+            &[
+                ("part", &name),
+                ("hp", &health.current),
+                ("max", &health.max),
+            ],
+        );
+        if let Some(note) = note {
+            text = format!("{text}  {note}");
+        }
+        if targeted {
+            text = format!("> {text}");
+        }
+        let color = if !health.is_alive() {
+            BROKEN_COLOR
+        } else if targeted {
+            TARGET_COLOR
+        } else {
+            Color::WHITE
+        };
+        HudLine { text, color }
+    };
+
+    for (index, part) in parts.parts.iter().enumerate() {
+        let note = (!part.health.is_alive()).then(|| loc.get("boss.part_broken"));
+        lines.push(part_line(
+            loc.get(part.kind.label_key()),
+            &part.health,
+            parts.targeted == Some(index),
+            note,
+        ));
+    }
+    let note = parts.core_guarded().then(|| loc.get("boss.core_guarded"));
+    lines.push(part_line(
+        loc.get("boss_part.core"),
+        health,
+        parts.targeted.is_none(),
+        note,
+    ));
+
+    lines.push(HudLine {
+        text: loc.get("boss.hint").to_string(),
+        color: BROKEN_COLOR,
+    });
+    lines
+}
+
+/// Shows every part of the colossal boss on the floor with its own health, and which one
+/// is targeted. Rebuilt whenever a number on it changes.
+pub fn update_boss_hud(
+    mut commands: Commands,
+    bosses: Query<(&MobMarker, &Health, &BossParts), Without<DyingMob>>,
+    hud: Query<Entity, With<BossHud>>,
+    loc: Res<Localization>,
+    mut shown: Local<Vec<String>>,
+) {
+    let Some((marker, health, parts)) = bosses.iter().next() else {
+        for entity in &hud {
+            commands.entity(entity).despawn();
+        }
+        shown.clear();
+        return;
+    };
+
+    let lines = hud_lines(&loc, marker, health, parts);
+    let texts: Vec<String> = lines.iter().map(|line| line.text.clone()).collect();
+    if !hud.is_empty() && *shown == texts {
+        return;
+    }
+    *shown = texts;
+
+    for entity in &hud {
+        commands.entity(entity).despawn();
+    }
+    commands
+        .spawn((
+            BossHud,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(20.0),
+                left: Val::Px(0.0),
+                right: Val::Px(0.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    },
+                    BackgroundColor(HUD_BACKGROUND),
+                ))
+                .with_children(|panel| {
+                    for line in lines {
+                        panel.spawn((
+                            Text::new(line.text),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                            TextColor(line.color),
+                        ));
+                    }
+                });
+        });
+}
+
+pub fn announce_destroyed_parts(
+    mut events: MessageReader<BossPartDestroyed>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for event in events.read() {
+        toast_writer.write(ShowToast::new(loc.format(
+            "boss.part_destroyed",
+            &[("part", &loc.get(event.part.label_key()))],
+        )));
+    }
+}
+
+pub fn despawn_boss_hud(mut commands: Commands, hud: Query<Entity, With<BossHud>>) {
+    for entity in &hud {
+        commands.entity(entity).despawn();
+    }
+}
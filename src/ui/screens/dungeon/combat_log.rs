@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+use crate::combat::{CombatLog, CombatLogEntry, CombatLogVerbosity, DamageBreakdown};
+use crate::i18n::Localization;
+use crate::mob::MobId;
+use crate::settings::GameSettings;
+use crate::ui::Announce;
+
+const LOG_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.85);
+const LOG_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+
+#[derive(Component)]
+pub struct CombatLogHud;
+
+/// The formula behind a hit, one step per line: the attack's sources, the roll and any
+/// crit, then defense.
+fn breakdown_lines(breakdown: &DamageBreakdown, loc: &Localization) -> Vec<String> {
+    let sources = &breakdown.sources;
+    let mut lines = vec![
+        loc.format(
+            "combat_log.breakdown_attack",
+            &[
+                ("total", &sources.total()),
+                ("base", &sources.base),
+                ("equipment", &sources.equipment),
+                ("skill", &sources.skill),
+                ("buffs", &sources.buffs),
+            ],
+        ),
+        loc.format(
+            "combat_log.breakdown_roll",
+            &[
+                ("roll", &breakdown.roll),
+                ("min", &breakdown.min_damage),
+                ("max", &breakdown.max_damage),
+            ],
+        ),
+    ];
+    if breakdown.critical {
+        lines.push(loc.format(
+            "combat_log.breakdown_critical",
+            &[
+                ("multiplier", &breakdown.crit_multiplier_percent),
+                ("dealt", &breakdown.dealt),
+            ],
+        ));
+    }
+    lines.push(loc.format(
+        "combat_log.breakdown_defense",
+        &[
+            ("defense", &breakdown.defense),
+            ("mitigation", &breakdown.mitigation_percent()),
+            ("damage", &breakdown.damage),
+        ],
+    ));
+    lines
+}
+
+fn entry_text(entry: &CombatLogEntry, verbosity: CombatLogVerbosity, loc: &Localization) -> String {
+    let name = |id: MobId| loc.mob_name(id, &id.spec().name).to_string();
+    match entry {
+        CombatLogEntry::Hit {
+            target,
+            damage,
+            critical,
+            breakdown,
+        } => {
+            let line = match breakdown {
+                None => loc.format(
+                    "combat_log.hit",
+                    &[("target", &name(*target)), ("damage", damage)],
+                ),
+                Some(breakdown) => loc.format(
+                    "combat_log.hit_detailed",
+                    &[
+                        ("target", &name(*target)),
+                        ("damage", damage),
+                        ("roll", &breakdown.roll),
+                        ("min", &breakdown.min_damage),
+                        ("max", &breakdown.max_damage),
+                        ("defense", &breakdown.defense),
+                        ("mitigation", &breakdown.mitigation_percent()),
+                    ],
+                ),
+            };
+            let line = if *critical {
+                loc.format("combat_log.critical", &[("hit", &line)])
+            } else {
+                line
+            };
+            match breakdown {
+                Some(breakdown) if verbosity == CombatLogVerbosity::Verbose => {
+                    let mut lines = vec![line];
+                    lines.extend(breakdown_lines(breakdown, loc));
+                    lines.join("\n")
+                }
+                _ => line,
+            }
+        }
+        CombatLogEntry::Kill { target } => {
+            loc.format("combat_log.kill", &[("target", &name(*target))])
+        }
+        CombatLogEntry::Dodge => loc.get("combat_log.dodge").to_string(),
+    }
+}
+
+/// Lists the latest combat log entries in the corner of the dungeon view.
+pub fn update_combat_log_hud(
+    mut commands: Commands,
+    log: Res<CombatLog>,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
+    mut hud: Query<&mut Text, With<CombatLogHud>>,
+) {
+    let lines = log
+        .entries()
+        .map(|entry| entry_text(entry, settings.combat_log, &loc))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if let Ok(mut text) = hud.single_mut() {
+        **text = lines;
+        return;
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        CombatLogHud,
+        Text::new(lines),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(LOG_COLOR),
+        BackgroundColor(LOG_BACKGROUND),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            ..default()
+        },
+    ));
+}
+
+/// Reads out each new combat log entry once.
+pub fn announce_combat_log(
+    log: Res<CombatLog>,
+    settings: Res<GameSettings>,
+    loc: Res<Localization>,
+    mut seen: Local<usize>,
+    mut announce: MessageWriter<Announce>,
+) {
+    for entry in log.since(*seen) {
+        announce.write(Announce::new(entry_text(entry, settings.combat_log, &loc)));
+    }
+    *seen = log.pushed();
+}
+
+pub fn despawn_combat_log_hud(
+    mut commands: Commands,
+    mut log: ResMut<CombatLog>,
+    hud: Query<Entity, With<CombatLogHud>>,
+) {
+    log.clear();
+    for entity in &hud {
+        commands.entity(entity).despawn();
+    }
+}
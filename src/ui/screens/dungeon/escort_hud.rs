@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::escort::CARGO_HP;
+use crate::game::Escort;
+use crate::i18n::Localization;
+
+const HUD_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const CARGO_LOW_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+const CARGO_LOW_FRACTION: f32 = 0.3;
+
+#[derive(Component)]
+pub struct EscortHud;
+
+/// Shows the caravan's destination, leg, and cargo while an escort is under way.
+pub fn update_escort_hud(
+    mut commands: Commands,
+    escort: Option<Res<Escort>>,
+    dungeon: Res<DungeonState>,
+    loc: Res<Localization>,
+    mut hud: Query<(Entity, &mut Text, &mut TextColor), With<EscortHud>>,
+) {
+    let Some(escort) = escort else {
+        for (entity, ..) in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let contract = &escort.contract;
+    let label = loc.format(
+        "escort.hud",
+        &[
+            ("town", &loc.get(contract.route.to.name_key())),
+            ("leg", &(dungeon.floor_index + 1)),
+            ("legs", &contract.legs.len()),
+            ("cargo", &escort.cargo.ceil()),
+        ],
+    );
+    let color = if escort.cargo < CARGO_HP * CARGO_LOW_FRACTION {
+        CARGO_LOW_COLOR
+    } else {
+        HUD_COLOR
+    };
+
+    if let Ok((_, mut text, mut text_color)) = hud.single_mut() {
+        **text = label;
+        *text_color = TextColor(color);
+        return;
+    }
+
+    commands.spawn((
+        EscortHud,
+        Text::new(label),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::economy::Wallet;
+use crate::i18n::Localization;
+use crate::location::PlayerLocation;
+use crate::player::PlayerMarker;
+use crate::world_event::{ActiveWorldEvent, GameClock};
+
+const BANNER_COLOR: Color = Color::srgb(1.0, 0.55, 0.2);
+const BANNER_BACKGROUND: Color = Color::srgba(0.2, 0.05, 0.0, 0.8);
+const FLOOR_EVENT_COLOR: Color = Color::srgb(0.85, 0.6, 1.0);
+const FLOOR_EVENT_BACKGROUND: Color = Color::srgba(0.1, 0.0, 0.2, 0.8);
+
+#[derive(Component)]
+pub struct EventBanner;
+
+#[derive(Component)]
+pub struct FloorEventBanner;
+
+/// Announces the running world event while the player is in town.
+pub fn update_event_banner(
+    mut commands: Commands,
+    active: Res<ActiveWorldEvent>,
+    clock: Res<GameClock>,
+    player: Query<&Wallet, With<PlayerMarker>>,
+    location: PlayerLocation,
+    loc: Res<Localization>,
+    mut banner: Query<(Entity, &mut Text), With<EventBanner>>,
+) {
+    let Some(event) = active.0.as_ref().filter(|_| location.in_town()) else {
+        for (entity, _) in &banner {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let label = loc.format(
+        "event.banner",
+        &[
+            ("name", &event.name),
+            ("days", &(event.days_left(clock.day()).unwrap_or(0) + 1)),
+            ("amount", &player.single().map_or(0, |wallet| wallet.balance(event.currency))),
+            ("currency", &loc.get(event.currency.name_key())),
+        ],
+    );
+
+    if let Ok((_, mut text)) = banner.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        return;
+    }
+
+    commands.spawn((
+        EventBanner,
+        Text::new(label),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(BANNER_COLOR),
+        BackgroundColor(BANNER_BACKGROUND),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+            ..default()
+        },
+    ));
+}
+
+pub fn despawn_event_banner(mut commands: Commands, banner: Query<Entity, With<EventBanner>>) {
+    for entity in &banner {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Announces the current floor's event for as long as the player is on that floor.
+pub fn update_floor_event_banner(
+    mut commands: Commands,
+    dungeon: Res<DungeonState>,
+    loc: Res<Localization>,
+    mut banner: Query<(Entity, &mut Text), With<FloorEventBanner>>,
+) {
+    let Some(event) = dungeon.floor_event() else {
+        for (entity, _) in &banner {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let label = loc.format(
+        "floor_event.banner",
+        &[
+            ("name", &loc.get(event.label_key())),
+            ("effect", &loc.get(event.description_key())),
+        ],
+    );
+
+    if let Ok((_, mut text)) = banner.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        return;
+    }
+
+    commands.spawn((
+        FloorEventBanner,
+        Text::new(label),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(FLOOR_EVENT_COLOR),
+        BackgroundColor(FLOOR_EVENT_BACKGROUND),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+            ..default()
+        },
+    ));
+}
+
+pub fn despawn_floor_event_banner(
+    mut commands: Commands,
+    banner: Query<Entity, With<FloorEventBanner>>,
+) {
+    for entity in &banner {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::{Ghost, GhostRecorder};
+use crate::i18n::Localization;
+
+const AHEAD_COLOR: Color = Color::srgb(0.5, 1.0, 0.6);
+const BEHIND_COLOR: Color = Color::srgb(1.0, 0.5, 0.5);
+
+#[derive(Component)]
+pub struct GhostHud;
+
+/// Races the current floor against the ghost's split for it while a ghost is being
+/// replayed.
+pub fn update_ghost_hud(
+    mut commands: Commands,
+    ghost: Option<Res<Ghost>>,
+    recorder: Option<Res<GhostRecorder>>,
+    dungeon: Res<DungeonState>,
+    loc: Res<Localization>,
+    mut hud: Query<(Entity, &mut Text, &mut TextColor), With<GhostHud>>,
+) {
+    let (Some(ghost), Some(recorder)) = (ghost, recorder) else {
+        for (entity, ..) in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let floor = dungeon.floor_index + 1;
+    let seconds = recorder.floor_seconds();
+    let (label, color) = match ghost.0.splits.get(dungeon.floor_index) {
+        Some(split) => (
+            loc.format(
+                "ghost.hud",
+                &[
+                    ("floor", &floor),
+                    ("time", &format!("{seconds:.0}")),
+                    ("ghost_time", &format!("{:.0}", split.seconds)),
+                    ("hp", &recorder.hp_on_arrival()),
+                    ("ghost_hp", &split.hp_on_arrival),
+                ],
+            ),
+            if seconds <= split.seconds {
+                AHEAD_COLOR
+            } else {
+                BEHIND_COLOR
+            },
+        ),
+        None => (
+            loc.format(
+                "ghost.hud_past",
+                &[("floor", &floor), ("time", &format!("{seconds:.0}"))],
+            ),
+            AHEAD_COLOR,
+        ),
+    };
+
+    if let Ok((_, mut text, mut text_color)) = hud.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        *text_color = TextColor(color);
+        return;
+    }
+
+    commands.spawn((
+        GhostHud,
+        Text::new(label),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
@@ -3,13 +3,15 @@ use tracing::instrument;
 
 use crate::crafting_station::{AnvilActiveTimer, CraftingStationType, ForgeActiveTimer};
 use crate::dungeon::{
-    ChestEntity, ChestMined, CraftingStationEntity, CraftingStationInteraction,
-    DungeonEntityMarker, InteractableNearby, MerchantInteraction, NpcEntity, RockEntity, RockMined,
+    CaptiveFreed, ChestEntity, ChestMined, CraftingStationEntity, CraftingStationInteraction,
+    DungeonEntityMarker, HerbEntity, HerbGathered, InteractableNearby, MerchantInteraction,
+    NpcEntity, RockEntity, RockMined, ShrineEntity, TravelingMerchantInteraction,
 };
 use crate::mob::MobId;
 use crate::ui::screens::anvil_modal::ActiveAnvilEntity;
 use crate::ui::screens::forge_modal::ActiveForgeEntity;
 use crate::ui::screens::modal::{ModalType, OpenModal};
+use crate::ui::screens::shrine_modal::ActiveShrine;
 
 #[instrument(level = "debug", skip_all)]
 pub fn process_interaction(
@@ -22,6 +24,8 @@ pub fn process_interaction(
     crafting_query: Query<&CraftingStationEntity>,
     chest_query: Query<(), With<ChestEntity>>,
     rock_query: Query<&RockEntity>,
+    herb_query: Query<(), With<HerbEntity>>,
+    shrine_query: Query<(), With<ShrineEntity>>,
 ) {
     let is_interact = action_reader
         .read()
@@ -43,12 +47,21 @@ pub fn process_interaction(
     }
 
     if let Ok(npc) = npc_query.get(entity) {
-        if npc.mob_id == MobId::Merchant {
-            commands.trigger(MerchantInteraction { entity });
+        match npc.mob_id {
+            MobId::Merchant => commands.trigger(MerchantInteraction { entity }),
+            MobId::TravelingMerchant => commands.trigger(TravelingMerchantInteraction { entity }),
+            MobId::Captive => commands.trigger(CaptiveFreed { entity }),
+            _ => {}
         }
         return;
     }
 
+    if shrine_query.get(entity).is_ok() {
+        commands.insert_resource(ActiveShrine(entity));
+        commands.trigger(OpenModal(ModalType::Shrine));
+        return;
+    }
+
     let Ok(marker) = marker_query.get(entity) else {
         return;
     };
@@ -67,6 +80,14 @@ pub fn process_interaction(
             pos: marker.pos,
             rock_type: rock.rock_type,
         });
+        return;
+    }
+
+    if herb_query.get(entity).is_ok() {
+        commands.trigger(HerbGathered {
+            entity,
+            pos: marker.pos,
+        });
     }
 }
 
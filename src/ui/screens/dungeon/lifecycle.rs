@@ -1,11 +1,11 @@
 use bevy::prelude::*;
-use bevy_ecs_tiled::prelude::*;
 use tracing::instrument;
 
 use crate::dungeon::{
-    DepthSorting, DungeonRegistry, DungeonState, FloorReady, SpawnFloor, TilemapInfo,
+    DepthSorting, DungeonRegistry, DungeonState, FloorMapReady, FloorReady, SpawnFloor,
+    TilemapInfo,
 };
-use crate::location::LocationId;
+use crate::location::CurrentTown;
 use crate::ui::PlayerSpriteSheet;
 
 use super::components::{DungeonPlayer, FloorRoot, PendingPlayerSpawn};
@@ -15,10 +15,11 @@ pub fn enter_dungeon(
     mut commands: Commands,
     registry: Res<DungeonRegistry>,
     mut state: ResMut<DungeonState>,
+    town: Res<CurrentTown>,
     mut spawn_floor: MessageWriter<SpawnFloor>,
 ) {
     if !state.is_in_dungeon() {
-        state.enter_dungeon(LocationId::Home, &registry);
+        state.enter_dungeon(town.0.spec().home, &registry);
     }
 
     let Some(spawn_config) = state.get_spawn_config() else {
@@ -37,7 +38,7 @@ pub fn enter_dungeon(
 pub fn handle_floor_ready(
     mut commands: Commands,
     mut events: MessageReader<FloorReady>,
-    asset_server: Res<AssetServer>,
+    dungeon: Res<DungeonState>,
     camera_query: Single<Entity, With<Camera2d>>,
     floor_root_query: Query<Entity, With<FloorRoot>>,
 ) {
@@ -46,17 +47,12 @@ pub fn handle_floor_ready(
             commands.entity(floor_root).despawn();
         }
 
-        spawn_floor_ui(
-            &mut commands,
-            &asset_server,
-            event.floor_id,
-            *camera_query,
-        );
+        spawn_floor_ui(&mut commands, event.floor_id, dungeon.depth(), *camera_query);
     }
 }
 
-pub fn on_map_created_queue_player_spawn(
-    _trigger: On<TiledEvent<MapCreated>>,
+pub fn on_floor_map_ready_queue_player_spawn(
+    _trigger: On<FloorMapReady>,
     mut commands: Commands,
     existing_player: Query<Entity, With<DungeonPlayer>>,
 ) {
@@ -1,11 +1,22 @@
+mod boss_hud;
+mod combat_log;
 mod components;
 mod crafting_animation;
+mod escort_hud;
+mod event_banner;
+mod ghost_hud;
 
 mod interaction;
 mod lifecycle;
+mod night_tint;
+mod paper_doll;
 pub mod plugin;
+mod rescue_hud;
 mod spawn;
 mod systems;
+mod tier_select;
+mod town_header;
+mod transition_spinner;
 
 pub use components::{DungeonPlayer, FacingDirection, FloorRoot};
 pub use plugin::DungeonScreenPlugin;
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::world_event::GameClock;
+
+/// Alpha of the tint at full dark.
+const MAX_TINT_ALPHA: f32 = 0.45;
+
+/// Draw order of the tint: over the map, under every HUD element.
+const TINT_Z_INDEX: i32 = -1;
+
+#[derive(Component)]
+pub struct NightTint;
+
+fn tint_color(darkness: f32) -> Color {
+    Color::srgba(0.02, 0.03, 0.15, darkness * MAX_TINT_ALPHA)
+}
+
+/// Darkens the view as evening falls and lifts it again toward dawn.
+pub fn update_night_tint(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    mut tint: Query<&mut BackgroundColor, With<NightTint>>,
+) {
+    let color = tint_color(clock.darkness());
+
+    if let Ok(mut background) = tint.single_mut() {
+        if background.0 != color {
+            background.0 = color;
+        }
+        return;
+    }
+
+    commands.spawn((
+        NightTint,
+        BackgroundColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(TINT_Z_INDEX),
+    ));
+}
+
+pub fn despawn_night_tint(mut commands: Commands, tint: Query<Entity, With<NightTint>>) {
+    for entity in &tint {
+        commands.entity(entity).despawn();
+    }
+}
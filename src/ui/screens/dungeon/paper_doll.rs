@@ -0,0 +1,142 @@
+//! Gear drawn over the dungeon player sprite, so equipping something shows in-world.
+//!
+//! The animated player sprite is the body. Each equipped helmet, weapon and shield is a
+//! child sprite of it, nudged forward in depth so it always draws over the body.
+
+use bevy::prelude::*;
+
+use crate::assets::GameSprites;
+use crate::dungeon::DepthSorting;
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment};
+use crate::item::ItemId;
+use crate::player::PlayerMarker;
+use crate::ui::PlayerSpriteSheet;
+
+use super::components::{DungeonPlayer, FacingDirection};
+
+/// How far apart the layers sit, in pixels of world y fed through the depth-sorting
+/// factor. Small enough that nothing else on the floor lands between two layers.
+const LAYER_DEPTH_STEP: f32 = 0.01;
+
+/// A piece of gear drawn over the player's body.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DollLayer {
+    Shield,
+    Helmet,
+    Weapon,
+}
+
+impl DollLayer {
+    /// Back to front.
+    const ALL: [DollLayer; 3] = [DollLayer::Shield, DollLayer::Helmet, DollLayer::Weapon];
+
+    fn slot(self) -> EquipmentSlot {
+        match self {
+            DollLayer::Shield => EquipmentSlot::OffHand,
+            DollLayer::Helmet => EquipmentSlot::Head,
+            DollLayer::Weapon => EquipmentSlot::Weapon,
+        }
+    }
+
+    /// Where the layer sits facing right, and how big it is, as fractions of the body's
+    /// frame. Facing left mirrors it.
+    fn placement(self) -> (Vec2, f32) {
+        match self {
+            DollLayer::Shield => (Vec2::new(-0.22, -0.08), 0.4),
+            DollLayer::Helmet => (Vec2::new(0.0, 0.24), 0.45),
+            DollLayer::Weapon => (Vec2::new(0.26, -0.04), 0.5),
+        }
+    }
+
+    fn order(self) -> usize {
+        Self::ALL.iter().position(|layer| *layer == self).unwrap_or(0) + 1
+    }
+}
+
+/// The item a layer is showing, so its sprite is only rebuilt when the gear changes.
+#[derive(Component, Default)]
+pub struct DollItem(Option<ItemId>);
+
+/// Gives a freshly spawned dungeon player an empty layer for each piece of gear.
+pub fn spawn_paper_doll(
+    mut commands: Commands,
+    players: Query<Entity, Added<DungeonPlayer>>,
+    depth_sorting: Option<Res<DepthSorting>>,
+) {
+    let depth = depth_sorting.map(|d| *d).unwrap_or_default();
+    for player in &players {
+        for layer in DollLayer::ALL {
+            let z = depth.factor * LAYER_DEPTH_STEP * layer.order() as f32;
+            commands.spawn((
+                layer,
+                DollItem::default(),
+                Sprite::default(),
+                Transform::from_xyz(0.0, 0.0, z),
+                Visibility::Hidden,
+                ChildOf(player),
+            ));
+        }
+    }
+}
+
+/// The doll layers, with the body each one is drawn over.
+type DollLayers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Ref<'static, DollLayer>,
+        &'static ChildOf,
+        &'static mut DollItem,
+        &'static mut Sprite,
+        &'static mut Transform,
+        &'static mut Visibility,
+    ),
+>;
+
+/// Shows the equipped gear on each layer and mirrors the layers with the body.
+pub fn update_paper_doll(
+    inventory: Query<Ref<Inventory>, With<PlayerMarker>>,
+    players: Query<Ref<FacingDirection>, With<DungeonPlayer>>,
+    mut layers: DollLayers,
+    game_sprites: Res<GameSprites>,
+    player_sheet: Res<PlayerSpriteSheet>,
+) {
+    let Ok(inventory) = inventory.single() else {
+        return;
+    };
+    let frame = player_sheet.frame_size.as_vec2();
+
+    for (layer, child_of, mut shown, mut sprite, mut transform, mut visibility) in &mut layers {
+        let Ok(facing) = players.get(child_of.parent()) else {
+            continue;
+        };
+        if !inventory.is_changed() && !facing.is_changed() && !layer.is_added() {
+            continue;
+        }
+
+        let equipped = inventory.get_equipped_item(layer.slot()).map(|e| &e.item);
+        let item_id = equipped.map(|item| item.item_id);
+        if shown.0 != item_id {
+            let icon = equipped.and_then(|item| {
+                let sheet = game_sprites.get(item.sprite.sheet_key.into())?;
+                let (_, scale) = layer.placement();
+                sheet.sprite_sized(&item.sprite.name, Vec2::splat(frame.x * scale))
+            });
+            match icon {
+                Some(icon) => {
+                    *sprite = icon;
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+            shown.0 = item_id;
+        }
+
+        let (offset, _) = layer.placement();
+        let left = *facing == FacingDirection::Left;
+        let x = if left { -offset.x } else { offset.x };
+        transform.translation.x = x * frame.x;
+        transform.translation.y = offset.y * frame.y;
+        sprite.flip_x = left;
+    }
+}
@@ -1,34 +1,65 @@
 use bevy::prelude::*;
 
+use crate::combat::{BossPartDestroyed, CombatLog};
 use crate::crafting_station::{AnvilCraftingStarted, ForgeCraftingStarted};
 use crate::dungeon::{CraftingStationInteraction, FloorReady};
+use crate::game::{Escort, Ghost, Rescue};
 use crate::input::{emit_move_intent, request_menu_transition, GameAction};
-use crate::states::AppState;
+use crate::mob::BossParts;
+use crate::states::{AppState, ScreenSet};
 use crate::ui::screens::modal::ActiveModal;
 
+use super::boss_hud::{
+    announce_destroyed_parts, cycle_boss_target, despawn_boss_hud, update_boss_hud, BossHud,
+};
+use super::combat_log::{announce_combat_log, despawn_combat_log_hud, update_combat_log_hud};
 use super::components::PendingPlayerSpawn;
 use super::crafting_animation::{
     handle_anvil_crafting_started, handle_forge_crafting_started, on_anvil_timer_finished,
     on_forge_timer_finished,
 };
+use super::escort_hud::{update_escort_hud, EscortHud};
+use super::event_banner::{
+    despawn_event_banner, despawn_floor_event_banner, update_event_banner,
+    update_floor_event_banner,
+};
+use super::ghost_hud::{update_ghost_hud, GhostHud};
 use super::systems::update_player_sprite_direction;
 use super::interaction::{open_crafting_modal, process_interaction};
 use super::lifecycle::{
-    enter_dungeon, handle_floor_ready, on_map_created_queue_player_spawn, spawn_player_when_ready,
+    enter_dungeon, handle_floor_ready, on_floor_map_ready_queue_player_spawn, spawn_player_when_ready,
 };
+use super::night_tint::{despawn_night_tint, update_night_tint};
+use super::paper_doll::{spawn_paper_doll, update_paper_doll};
+use super::rescue_hud::{update_rescue_hud, RescueHud};
 use super::spawn::add_entity_visuals;
 use super::systems::cleanup_dungeon;
+use super::tier_select::cycle_dungeon_tier;
+use super::town_header::{despawn_town_header, update_town_header};
+use super::transition_spinner::{despawn_transition_spinner, update_transition_spinner};
 
 pub struct DungeonScreenPlugin;
 
 impl Plugin for DungeonScreenPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(add_entity_visuals)
-            .add_observer(on_map_created_queue_player_spawn)
+            .add_observer(on_floor_map_ready_queue_player_spawn)
             .add_observer(on_forge_timer_finished)
             .add_observer(on_anvil_timer_finished)
             .add_systems(OnEnter(AppState::Dungeon), enter_dungeon)
-            .add_systems(OnExit(AppState::Dungeon), cleanup_dungeon)
+            .add_systems(
+                OnExit(AppState::Dungeon),
+                (
+                    cleanup_dungeon,
+                    despawn_boss_hud,
+                    despawn_event_banner,
+                    despawn_floor_event_banner,
+                    despawn_combat_log_hud,
+                    despawn_night_tint,
+                    despawn_town_header,
+                    despawn_transition_spinner,
+                ),
+            )
             .add_systems(
                 FixedFirst,
                 emit_move_intent
@@ -39,18 +70,92 @@ impl Plugin for DungeonScreenPlugin {
             .add_systems(
                 Update,
                 (
-                    handle_floor_ready.run_if(on_message::<FloorReady>),
-                    spawn_player_when_ready.run_if(resource_exists::<PendingPlayerSpawn>),
-                    update_player_sprite_direction,
-                    process_interaction
+                    (
+                        process_interaction
+                            .run_if(on_message::<GameAction>)
+                            .run_if(|modal: Res<ActiveModal>| modal.modal.is_none()),
+                        request_menu_transition,
+                    )
+                        .chain()
+                        .in_set(ScreenSet::Input),
+                    (
+                        handle_floor_ready.run_if(on_message::<FloorReady>),
+                        spawn_player_when_ready.run_if(resource_exists::<PendingPlayerSpawn>),
+                        open_crafting_modal.run_if(on_message::<CraftingStationInteraction>),
+                        handle_forge_crafting_started.run_if(on_message::<ForgeCraftingStarted>),
+                        handle_anvil_crafting_started.run_if(on_message::<AnvilCraftingStarted>),
+                    )
+                        .chain()
+                        .in_set(ScreenSet::Logic),
+                    (update_player_sprite_direction, spawn_paper_doll, update_paper_doll)
+                        .chain()
+                        .in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                Update,
+                update_escort_hud
+                    .run_if(resource_exists::<Escort>.or(any_with_component::<EscortHud>))
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Render),
+            )
+            .add_systems(
+                Update,
+                update_ghost_hud
+                    .run_if(resource_exists::<Ghost>.or(any_with_component::<GhostHud>))
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Render),
+            )
+            .add_systems(
+                Update,
+                update_rescue_hud
+                    .run_if(resource_exists::<Rescue>.or(any_with_component::<RescueHud>))
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Render),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_event_banner,
+                    update_floor_event_banner,
+                    update_town_header,
+                    update_night_tint,
+                    update_transition_spinner,
+                )
+                    .in_set(ScreenSet::Render)
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                Update,
+                cycle_dungeon_tier
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    cycle_boss_target
                         .run_if(on_message::<GameAction>)
-                        .run_if(|modal: Res<ActiveModal>| modal.modal.is_none()),
-                    open_crafting_modal.run_if(on_message::<CraftingStationInteraction>),
-                    request_menu_transition,
-                    handle_forge_crafting_started.run_if(on_message::<ForgeCraftingStarted>),
-                    handle_anvil_crafting_started.run_if(on_message::<AnvilCraftingStarted>),
+                        .run_if(|modal: Res<ActiveModal>| modal.modal.is_none())
+                        .in_set(ScreenSet::Input),
+                    (
+                        update_boss_hud.run_if(
+                            any_with_component::<BossParts>.or(any_with_component::<BossHud>),
+                        ),
+                        announce_destroyed_parts.run_if(on_message::<BossPartDestroyed>),
+                    )
+                        .chain()
+                        .in_set(ScreenSet::Render),
                 )
-                    .chain()
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                Update,
+                (update_combat_log_hud, announce_combat_log)
+                    .in_set(ScreenSet::Render)
+                    .run_if(resource_changed::<CombatLog>)
                     .run_if(in_state(AppState::Dungeon)),
             );
     }
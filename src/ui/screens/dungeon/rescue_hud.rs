@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::game::rescue::CAPTIVE_HP;
+use crate::game::Rescue;
+use crate::i18n::Localization;
+
+const HUD_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
+const HEALTH_LOW_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+const HEALTH_LOW_FRACTION: f32 = 0.3;
+
+#[derive(Component)]
+pub struct RescueHud;
+
+/// Shows the freed captive's health while it is being led to the stairs. Sits below the
+/// escort and ghost lines so all of them can show at once.
+pub fn update_rescue_hud(
+    mut commands: Commands,
+    rescue: Option<Res<Rescue>>,
+    loc: Res<Localization>,
+    mut hud: Query<(Entity, &mut Text, &mut TextColor), With<RescueHud>>,
+) {
+    let Some(rescue) = rescue else {
+        for (entity, ..) in &hud {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let label = loc.format(
+        "rescue.hud",
+        &[("health", &rescue.health.ceil()), ("max", &CAPTIVE_HP)],
+    );
+    let color = if rescue.health < CAPTIVE_HP * HEALTH_LOW_FRACTION {
+        HEALTH_LOW_COLOR
+    } else {
+        HUD_COLOR
+    };
+
+    if let Ok((_, mut text, mut text_color)) = hud.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        *text_color = TextColor(color);
+        return;
+    }
+
+    commands.spawn((
+        RescueHud,
+        Text::new(label),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(color),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(36.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+    ));
+}
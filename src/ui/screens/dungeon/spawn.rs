@@ -1,27 +1,50 @@
 use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
-use bevy_ecs_tiled::prelude::*;
 use tracing::instrument;
 
 use crate::assets::{GameSprites, SpriteSheetKey};
-use crate::crafting_station::{AnvilCraftingState, CraftingStationType, ForgeCraftingState};
-use crate::dungeon::systems::on_map_created;
+use crate::crafting_station::{
+    AnvilCraftingState, CraftingStationType, ForgeCraftingState, StationFloor,
+};
+use crate::difficulty::AdaptiveDifficulty;
 use crate::assets::DungeonTileSlice;
 use crate::dungeon::constants::{
-    CHEST_SPRITE_NAME, FORGE_COLLIDER, MOB_COLLIDER, PLAYER_COLLIDER, STAIRS_COLLIDER,
-    STATIC_COLLIDER,
+    CHEST_SPRITE_NAME, DUMMY_SPRITE_NAME, FORGE_COLLIDER, HERB_SPRITE_NAME, MOB_COLLIDER,
+    NEST_SPRITE_NAME, PLAYER_COLLIDER, SHRINE_SPRITE_NAME, STAIRS_COLLIDER, STATIC_COLLIDER,
 };
 use crate::dungeon::{
     ChestEntity, CraftingStationEntity, DepthSorting, DoorEntity, DungeonEntityMarker,
-    FloorId, GameLayer, MobEntity, NpcEntity, RockEntity, StairsEntity,
+    DungeonState, FloorId, GameLayer, HerbEntity, LoadFloorMap, MobEntity, NestBundle, NestEntity,
+    NpcEntity, RockEntity, ShrineEntity, StairsEntity, TrainingDummyEntity,
 };
-use crate::mob::MobCombatBundle;
+use crate::i18n::Localization;
+use crate::mob::definitions::MobSpec;
+use crate::mob::{ArmorShell, BossParts, CombatStats, EliteModifier, MobCombatBundle};
+#[cfg(feature = "gui")]
 use crate::ui::player_sprite::PLAYER_IDLE_TAG;
 use crate::ui::{AseMobSheets, PlayerSpriteSheet, PlayerWalkTimer};
 
 use super::components::{DungeonPlayer, DungeonRoot, FacingDirection, FloorRoot};
 
+/// Height, in pixels, of the name shown over an elite mob.
+const ELITE_LABEL_FONT_SIZE: f32 = 7.0;
+
+/// Tint that sets a nest apart from an ordinary rock.
+const NEST_TINT: Color = Color::srgb(0.75, 0.45, 0.4);
+
+/// Marker-only dungeon entities that [`add_entity_visuals`] gives a sprite.
+#[derive(SystemParam)]
+pub struct PropQueries<'w, 's> {
+    stairs: Query<'w, 's, (), With<StairsEntity>>,
+    herb: Query<'w, 's, (), With<HerbEntity>>,
+    shrine: Query<'w, 's, (), With<ShrineEntity>>,
+    dummy: Query<'w, 's, (), With<TrainingDummyEntity>>,
+    door: Query<'w, 's, (), With<DoorEntity>>,
+}
+
 #[derive(Component)]
 pub struct DungeonCamera;
 
@@ -35,7 +58,6 @@ struct StaticEntityBundle {
 #[derive(Bundle)]
 struct AnimatedMobBundle {
     combat: MobCombatBundle,
-    ase_animation: AseAnimation,
     sprite: Sprite,
     transform: Transform,
     collider: Collider,
@@ -51,7 +73,6 @@ struct SensorEntityBundle {
 struct PlayerBundle {
     marker: DungeonPlayer,
     facing: FacingDirection,
-    ase_animation: AseAnimation,
     sprite: Sprite,
     transform: Transform,
     walk_timer: PlayerWalkTimer,
@@ -69,14 +90,17 @@ pub fn add_entity_visuals(
     marker_query: Query<&DungeonEntityMarker>,
     chest_query: Query<&ChestEntity>,
     rock_query: Query<&RockEntity>,
-    stairs_query: Query<(), With<StairsEntity>>,
+    props: PropQueries,
     crafting_query: Query<&CraftingStationEntity>,
-    door_query: Query<(), With<DoorEntity>>,
     mob_query: Query<&MobEntity>,
+    nest_query: Query<&NestEntity>,
     npc_query: Query<&NpcEntity>,
     game_sprites: Res<GameSprites>,
     ase_sheets: Res<AseMobSheets>,
+    difficulty: Res<AdaptiveDifficulty>,
+    dungeon: Res<DungeonState>,
     depth_sorting: Option<Res<DepthSorting>>,
+    loc: Res<Localization>,
 ) {
     let entity = trigger.entity;
     let Ok(marker) = marker_query.get(entity) else {
@@ -114,7 +138,62 @@ pub fn add_entity_visuals(
         return;
     }
 
-    if stairs_query.get(entity).is_ok() {
+    if props.herb.get(entity).is_ok() {
+        let Some(sheet) = game_sprites.get(SpriteSheetKey::IconItems) else {
+            return;
+        };
+        let Some(sprite) = sheet.sprite(HERB_SPRITE_NAME) else {
+            return;
+        };
+        let sprite_size = Vec2::new(marker.size.width, marker.size.height);
+        commands.entity(entity).insert(SensorEntityBundle {
+            transform: Transform::from_translation(world_pos),
+            collider: STATIC_COLLIDER.create_collider(sprite_size),
+        });
+        commands.entity(entity).insert(sprite);
+        return;
+    }
+
+    if props.shrine.get(entity).is_ok() {
+        let Some(sheet) = game_sprites.get(SpriteSheetKey::CraftingMaterials) else {
+            return;
+        };
+        let Some(sprite) = sheet.sprite(SHRINE_SPRITE_NAME) else {
+            return;
+        };
+        let sprite_size = Vec2::new(marker.size.width, marker.size.height);
+        commands.entity(entity).insert(SensorEntityBundle {
+            transform: Transform::from_translation(world_pos),
+            collider: STATIC_COLLIDER.create_collider(sprite_size),
+        });
+        commands.entity(entity).insert(sprite);
+        return;
+    }
+
+    if props.dummy.get(entity).is_ok() {
+        let Some(sheet) = game_sprites.get(SpriteSheetKey::CraftingMaterials) else {
+            return;
+        };
+        let Some(sprite) = sheet.sprite(DUMMY_SPRITE_NAME) else {
+            return;
+        };
+        let sprite_size = Vec2::new(marker.size.width, marker.size.height);
+        // Stats without health: hits go through defense like a mob's, but nothing kills it.
+        commands.entity(entity).insert((
+            StaticEntityBundle {
+                sprite,
+                transform: Transform::from_translation(world_pos),
+                collider: STATIC_COLLIDER.create_collider(sprite_size),
+            },
+            CombatStats {
+                attack: 0,
+                defense: 0,
+            },
+        ));
+        return;
+    }
+
+    if props.stairs.get(entity).is_ok() {
         let sprite_size = Vec2::new(marker.size.width, marker.size.height);
         let collider = STAIRS_COLLIDER.create_collider(sprite_size);
         let Some(sheet) = game_sprites.get(SpriteSheetKey::DungeonTileset) else {
@@ -159,10 +238,13 @@ pub fn add_entity_visuals(
                 commands.entity(entity).insert(AnvilCraftingState::default());
             }
         }
+        if let Some(floor) = dungeon.current_floor() {
+            commands.entity(entity).insert(StationFloor(floor));
+        }
         return;
     }
 
-    if door_query.get(entity).is_ok() {
+    if props.door.get(entity).is_ok() {
         let sprite_size = Vec2::new(marker.size.width, marker.size.height);
         let collider = STATIC_COLLIDER.create_collider(sprite_size);
         commands.entity(entity).insert(SensorEntityBundle {
@@ -173,24 +255,56 @@ pub fn add_entity_visuals(
     }
 
     if let Ok(mob) = mob_query.get(entity) {
-        add_animated_mob(
-            &mut commands,
-            entity,
-            world_pos,
-            mob.mob_id,
-            &ase_sheets,
-        );
+        let mut spec = mob
+            .mob_id
+            .spec()
+            .with_multiplier(difficulty.floor_scale() * dungeon.mob_stat_multiplier());
+        spec.attack = spec.attack.scale(dungeon.mob_attack_multiplier());
+        let elite = EliteModifier::roll(&spec, &mut rand::thread_rng());
+        if let Some(modifier) = elite {
+            spec = modifier.apply(&spec);
+        }
+        let tint = elite.map_or(Color::WHITE, EliteModifier::tint);
+        add_animated_mob(&mut commands, entity, world_pos, &spec, &ase_sheets, tint);
+
+        if let Some(modifier) = elite {
+            add_elite_label(&mut commands, entity, &spec, modifier, &loc, &ase_sheets);
+            commands.entity(entity).insert(modifier);
+            if modifier == EliteModifier::Armored {
+                commands.entity(entity).insert(ArmorShell);
+            }
+        }
+        return;
+    }
+
+    if let Ok(nest) = nest_query.get(entity) {
+        let Some(sheet) = game_sprites.get(SpriteSheetKey::CraftingMaterials) else {
+            return;
+        };
+        let Some(mut sprite) = sheet.sprite(NEST_SPRITE_NAME) else {
+            return;
+        };
+        sprite.color = NEST_TINT;
+        let spec = nest
+            .mob_id
+            .spec()
+            .with_multiplier(difficulty.floor_scale() * dungeon.mob_stat_multiplier());
+        let sprite_size = Vec2::new(marker.size.width, marker.size.height);
+        commands.entity(entity).insert((
+            StaticEntityBundle {
+                sprite,
+                transform: Transform::from_translation(world_pos),
+                collider: STATIC_COLLIDER.create_collider(sprite_size),
+            },
+            NestBundle::from_spec(&spec),
+        ));
         return;
     }
 
     if let Ok(npc) = npc_query.get(entity) {
-        add_animated_mob(
-            &mut commands,
-            entity,
-            world_pos,
-            npc.mob_id,
-            &ase_sheets,
-        );
+        let spec = npc.mob_id.spec();
+        let tint = Color::WHITE;
+        add_animated_mob(&mut commands, entity, world_pos, spec, &ase_sheets, tint);
     }
 }
 
@@ -222,41 +336,70 @@ fn add_animated_mob(
     commands: &mut Commands,
     entity: Entity,
     world_pos: Vec3,
-    mob_id: crate::mob::MobId,
+    spec: &MobSpec,
     ase_sheets: &AseMobSheets,
+    tint: Color,
 ) {
-    let Some(sheet) = ase_sheets.get(mob_id) else {
+    let Some(sheet) = ase_sheets.get(spec.id) else {
         return;
     };
     let collider = MOB_COLLIDER.create_collider(sheet.frame_size.as_vec2());
 
+    let combat = MobCombatBundle::from_spec(spec.id, spec);
+    if !spec.parts.is_empty() {
+        commands
+            .entity(entity)
+            .insert(BossParts::new(&spec.parts, combat.health.max));
+    }
     commands.entity(entity).insert(AnimatedMobBundle {
-        combat: MobCombatBundle::from_mob_id(mob_id),
-        ase_animation: AseAnimation {
-            aseprite: sheet.aseprite.clone(),
-            animation: Animation::tag(sheet.idle_tag)
-                .with_repeat(AnimationRepeat::Loop),
+        combat,
+        sprite: Sprite {
+            color: tint,
+            ..default()
         },
-        sprite: Sprite::default(),
         transform: Transform::from_translation(world_pos),
         collider,
     });
+    #[cfg(feature = "gui")]
+    commands.entity(entity).insert(sheet.idle_animation());
 }
 
-pub fn spawn_floor_ui(
+/// Names an elite above its head, e.g. "Frenzied Goblin", in the colour of its tint.
+fn add_elite_label(
     commands: &mut Commands,
-    asset_server: &AssetServer,
-    floor_id: FloorId,
-    camera_entity: Entity,
+    entity: Entity,
+    spec: &MobSpec,
+    modifier: EliteModifier,
+    loc: &Localization,
+    ase_sheets: &AseMobSheets,
 ) {
-    let path = floor_id.spec().path;
-    let map_handle: Handle<TiledMapAsset> = asset_server.load(path);
+    let Some(sheet) = ase_sheets.get(spec.id) else {
+        return;
+    };
+    let name = loc.format(
+        modifier.label_key(),
+        &[("mob", &loc.mob_name(spec.id, &spec.name))],
+    );
+    let height = sheet.frame_size.y as f32 / 2.0 + ELITE_LABEL_FONT_SIZE;
+    commands.spawn((
+        Text2d::new(name),
+        TextFont {
+            font_size: ELITE_LABEL_FONT_SIZE,
+            ..default()
+        },
+        TextColor(modifier.tint()),
+        Transform::from_xyz(0.0, height, 0.2),
+        ChildOf(entity),
+    ));
+}
 
+pub fn spawn_floor_ui(commands: &mut Commands, floor_id: FloorId, depth: u32, camera_entity: Entity) {
     let floor_root = commands.spawn((FloorRoot, Transform::default(), Visibility::default())).id();
 
-    commands
-        .spawn((TiledMap(map_handle), ChildOf(floor_root)))
-        .observe(on_map_created);
+    commands.trigger(LoadFloorMap {
+        path: floor_id.spec().map_path(depth),
+        floor_root,
+    });
 
     commands.entity(camera_entity).insert(DungeonCamera);
 
@@ -278,39 +421,46 @@ pub fn position_camera(commands: &mut Commands, camera_entity: Entity, center: V
 
 #[instrument(level = "debug", skip_all, fields(?player_pos))]
 pub fn spawn_player(commands: &mut Commands, player_pos: Vec2, player_sheet: &PlayerSpriteSheet, depth: &DepthSorting) {
+    #[cfg(feature = "gui")]
     let Some(aseprite) = player_sheet.aseprite.clone() else {
         return;
     };
+    #[cfg(feature = "gui")]
+    let animation = AseAnimation {
+        aseprite,
+        animation: Animation::tag(PLAYER_IDLE_TAG).with_repeat(AnimationRepeat::Loop),
+    };
+    #[cfg(not(feature = "gui"))]
+    let animation = ();
 
     let z = depth.entity_z(player_pos.y);
     let collider = PLAYER_COLLIDER.create_collider(player_sheet.frame_size.as_vec2());
 
-    commands.spawn(PlayerBundle {
-        marker: DungeonPlayer,
-        facing: FacingDirection::default(),
-        ase_animation: AseAnimation {
-            aseprite,
-            animation: Animation::tag(PLAYER_IDLE_TAG)
-                .with_repeat(AnimationRepeat::Loop),
+    commands.spawn((
+        PlayerBundle {
+            marker: DungeonPlayer,
+            facing: FacingDirection::default(),
+            sprite: Sprite::default(),
+            transform: Transform::from_translation(Vec3::new(player_pos.x, player_pos.y, z)),
+            walk_timer: PlayerWalkTimer(Timer::from_seconds(0.1, TimerMode::Once)),
+            rigid_body: RigidBody::Dynamic,
+            velocity: LinearVelocity::default(),
+            locked_axes: LockedAxes::ROTATION_LOCKED,
+            collider,
+            collision_layers: CollisionLayers::new(
+                GameLayer::Player,
+                [
+                    GameLayer::Default,
+                    GameLayer::Mob,
+                    GameLayer::StaticEntity,
+                    GameLayer::Trigger,
+                    GameLayer::Projectile,
+                ],
+            ),
+            collision_events: CollisionEventsEnabled,
         },
-        sprite: Sprite::default(),
-        transform: Transform::from_translation(Vec3::new(player_pos.x, player_pos.y, z)),
-        walk_timer: PlayerWalkTimer(Timer::from_seconds(0.1, TimerMode::Once)),
-        rigid_body: RigidBody::Dynamic,
-        velocity: LinearVelocity::default(),
-        locked_axes: LockedAxes::ROTATION_LOCKED,
-        collider,
-        collision_layers: CollisionLayers::new(
-            GameLayer::Player,
-            [
-                GameLayer::Default,
-                GameLayer::Mob,
-                GameLayer::StaticEntity,
-                GameLayer::Trigger,
-            ],
-        ),
-        collision_events: CollisionEventsEnabled,
-    });
+        animation,
+    ));
 }
 
 
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use super::super::components::FloorRoot;
+use crate::dungeon::systems::{PendingFloor, TransitionInProgress};
 use crate::dungeon::DungeonState;
 
 pub fn cleanup_dungeon(
@@ -11,5 +12,7 @@ pub fn cleanup_dungeon(
     if let Ok(floor_root) = floor_root_query.single() {
         commands.entity(floor_root).despawn();
     }
+    commands.remove_resource::<PendingFloor>();
+    commands.remove_resource::<TransitionInProgress>();
     state.exit_dungeon();
 }
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::entities::Progression;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+
+/// Steps the dungeon tier to the next one the player has the level for. Tiers are picked
+/// in town and take hold on the way down.
+pub fn cycle_dungeon_tier(
+    mut action_reader: MessageReader<GameAction>,
+    mut dungeon: ResMut<DungeonState>,
+    town: Res<CurrentTown>,
+    player: Query<&Progression, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::CycleDungeonTier {
+            continue;
+        }
+        if dungeon.current_location != Some(town.0.spec().home) {
+            toast_writer.write(ShowToast::new(loc.get("dungeon_tier.not_in_town")));
+            continue;
+        }
+        let Ok(progression) = player.single() else {
+            continue;
+        };
+
+        let next = dungeon.tier.next_unlocked(progression.level);
+        if next == dungeon.tier {
+            let locked = dungeon.tier.next_unlocked(i32::MAX);
+            toast_writer.write(ShowToast::new(loc.format(
+                "dungeon_tier.locked",
+                &[
+                    ("tier", &loc.get(locked.label_key())),
+                    ("level", &locked.required_level()),
+                ],
+            )));
+            continue;
+        }
+
+        dungeon.tier = next;
+        toast_writer.write(ShowToast::new(loc.format(
+            "dungeon_tier.changed",
+            &[("tier", &loc.get(next.label_key()))],
+        )));
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::i18n::Localization;
+use crate::location::CurrentTown;
+use crate::world_event::GameClock;
+
+const HEADER_COLOR: Color = Color::srgb(0.95, 0.9, 0.75);
+const HEADER_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// Full-width row the header is centered in.
+#[derive(Component)]
+pub struct TownHeader;
+
+#[derive(Component)]
+pub struct TownHeaderText;
+
+/// Shows the town's name, the day, the time and the dungeon tier picked at the top of the
+/// screen while in town.
+pub fn update_town_header(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    header: Query<Entity, With<TownHeader>>,
+    mut header_text: Query<&mut Text, With<TownHeaderText>>,
+) {
+    if dungeon.current_location != Some(town.0.spec().home) {
+        for entity in &header {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let label = loc.format(
+        "town.header",
+        &[
+            ("town", &loc.get(town.0.name_key())),
+            ("day", &(clock.day() + 1)),
+            ("time", &clock.time_label()),
+            ("tier", &loc.get(dungeon.tier.label_key())),
+        ],
+    );
+
+    if let Ok(mut text) = header_text.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        return;
+    }
+
+    commands
+        .spawn((
+            TownHeader,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+        ))
+        .with_children(|row| {
+            row.spawn((
+                TownHeaderText,
+                Text::new(label),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(HEADER_COLOR),
+                BackgroundColor(HEADER_BACKGROUND),
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_town_header(mut commands: Commands, header: Query<Entity, With<TownHeader>>) {
+    for entity in &header {
+        commands.entity(entity).despawn();
+    }
+}
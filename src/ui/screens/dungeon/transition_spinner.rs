@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::dungeon::systems::TransitionInProgress;
+use crate::i18n::Localization;
+
+const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+const SECS_PER_FRAME: f32 = 0.1;
+const SPINNER_COLOR: Color = Color::srgb(0.95, 0.9, 0.75);
+
+#[derive(Component)]
+pub struct TransitionSpinner;
+
+/// Shows a spinner in the corner while a floor is being generated.
+pub fn update_transition_spinner(
+    mut commands: Commands,
+    time: Res<Time>,
+    loc: Res<Localization>,
+    transition: Option<Res<TransitionInProgress>>,
+    mut spinner: Query<(Entity, &mut Text), With<TransitionSpinner>>,
+) {
+    if transition.is_none() {
+        for (entity, _) in &spinner {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let frame = (time.elapsed_secs() / SECS_PER_FRAME) as usize % SPINNER_FRAMES.len();
+    let label = loc.format("floor.generating", &[("spinner", &SPINNER_FRAMES[frame])]);
+
+    if let Ok((_, mut text)) = spinner.single_mut() {
+        if **text != label {
+            **text = label;
+        }
+        return;
+    }
+
+    commands.spawn((
+        TransitionSpinner,
+        Text::new(label),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(SPINNER_COLOR),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(16.0),
+            bottom: Val::Px(16.0),
+            ..default()
+        },
+    ));
+}
+
+pub fn despawn_transition_spinner(
+    mut commands: Commands,
+    spinner: Query<Entity, With<TransitionSpinner>>,
+) {
+    for entity in &spinner {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,249 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::item::ItemRegistry;
+use crate::location::PlayerLocation;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::modal::ActiveModal;
+use crate::economy::Wallet;
+use crate::player::PlayerMarker;
+use crate::world_event::{ActiveWorldEvent, EventShopPurchase, GameClock};
+
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.55, 0.2);
+const NORMAL_COLOR: Color = Color::WHITE;
+
+pub struct EventShopPlugin;
+
+impl Plugin for EventShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventShopSelection>()
+            .add_systems(OnEnter(AppState::EventShop), spawn_event_shop)
+            .add_systems(OnExit(AppState::EventShop), despawn_event_shop)
+            .add_systems(
+                Update,
+                open_event_shop
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_event_shop_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_event_shop_rows.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::EventShop)),
+            );
+    }
+}
+
+/// Index of the highlighted entry in the active event's shop.
+#[derive(Resource, Default)]
+struct EventShopSelection(usize);
+
+#[derive(Component)]
+struct EventShopRoot;
+
+#[derive(Component)]
+struct EventShopRow(usize);
+
+#[derive(Component)]
+struct EventShopBalance;
+
+/// The shop's item rows and the balance line under them.
+#[derive(SystemParam)]
+struct EventShopTexts<'w, 's> {
+    rows: Query<'w, 's, (&'static EventShopRow, &'static mut TextColor)>,
+    balance: Query<'w, 's, &'static mut Text, With<EventShopBalance>>,
+}
+
+fn open_event_shop(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    active: Res<ActiveWorldEvent>,
+    location: PlayerLocation,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenEventShop || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if active.0.is_none() {
+            toast_writer.write(ShowToast::new(loc.get("event.no_event")));
+        } else if !location.in_town() {
+            toast_writer.write(ShowToast::new(loc.get("event.not_in_town")));
+        } else {
+            state_requests.write(StateTransitionRequest::EventShop);
+        }
+    }
+}
+
+fn spawn_event_shop(
+    mut commands: Commands,
+    active: Res<ActiveWorldEvent>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut selection: ResMut<EventShopSelection>,
+) {
+    selection.0 = 0;
+    let Some(event) = &active.0 else {
+        return;
+    };
+    let currency = loc.get(event.currency.name_key());
+
+    commands
+        .spawn((
+            EventShopRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(event.name.clone()),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                Text::new(event.description.clone()),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+
+            parent.spawn((
+                EventShopBalance,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(palette.selection),
+            ));
+
+            parent.spawn(column_node(8.0)).with_children(|list| {
+                for (idx, entry) in event.shop.iter().enumerate() {
+                    let name = loc.item_name(entry.item, &registry.get(entry.item).name);
+                    list.spawn((
+                        EventShopRow(idx),
+                        Text::new(loc.format(
+                            "event.shop_row",
+                            &[("item", &name), ("price", &entry.price), ("currency", &currency)],
+                        )),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(NORMAL_COLOR),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                Text::new(loc.get("event.shop_hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+fn handle_event_shop_input(
+    mut action_reader: MessageReader<GameAction>,
+    active: Res<ActiveWorldEvent>,
+    mut selection: ResMut<EventShopSelection>,
+    mut purchase_writer: MessageWriter<EventShopPurchase>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = active.0.as_ref().map_or(0, |event| event.shop.len()).max(1);
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Select => {
+                purchase_writer.write(EventShopPurchase { index: selection.0 });
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenEventShop => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+
+    // The event may end while the shop is open.
+    if active.0.is_none() {
+        state_requests.write(StateTransitionRequest::Dungeon);
+    }
+}
+
+fn update_event_shop_rows(
+    selection: Res<EventShopSelection>,
+    active: Res<ActiveWorldEvent>,
+    clock: Res<GameClock>,
+    player: Query<&Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    texts: EventShopTexts,
+) {
+    let EventShopTexts {
+        mut rows,
+        balance: mut balance_text,
+    } = texts;
+    for (row, mut color) in &mut rows {
+        *color = TextColor(if row.0 == selection.0 {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        });
+    }
+
+    let (Some(event), Ok(wallet), Ok(mut text)) =
+        (&active.0, player.single(), balance_text.single_mut())
+    else {
+        return;
+    };
+    **text = loc.format(
+        "event.shop_balance",
+        &[
+            ("amount", &wallet.balance(event.currency)),
+            ("currency", &loc.get(event.currency.name_key())),
+            ("days", &(event.days_left(clock.day()).unwrap_or(0) + 1)),
+        ],
+    );
+}
+
+fn despawn_event_shop(mut commands: Commands, root: Query<Entity, With<EventShopRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
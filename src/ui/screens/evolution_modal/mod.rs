@@ -0,0 +1,8 @@
+//! The reveal shown when an item evolves at the anvil.
+
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::EvolutionModalPlugin;
+pub use state::EvolutionModal;
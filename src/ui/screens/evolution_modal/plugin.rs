@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use crate::crafting_station::ItemEvolved;
+use crate::input::GameAction;
+use crate::states::ScreenSet;
+use crate::ui::modal_registry::{modal_close_system, ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::{in_evolution_modal, ActiveModal, ModalType, OpenModal};
+
+use super::render::animate_evolution_reveal;
+use super::state::{EvolutionModal, EvolutionRevealName, PendingEvolution};
+
+pub struct EvolutionModalPlugin;
+
+impl Plugin for EvolutionModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<EvolutionModal>().add_systems(
+            Update,
+            (
+                (
+                    modal_close_system::<EvolutionModal>,
+                    close_on_select.run_if(on_message::<GameAction>),
+                )
+                    .in_set(ScreenSet::Input)
+                    .run_if(in_evolution_modal),
+                (
+                    queue_evolution_reveal.run_if(on_message::<ItemEvolved>),
+                    open_evolution_reveal
+                        .run_if(resource_exists::<PendingEvolution>.and(not(in_evolution_modal))),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+                animate_evolution_reveal
+                    .run_if(in_evolution_modal)
+                    .in_set(ScreenSet::Render),
+            ),
+        );
+    }
+}
+
+fn queue_evolution_reveal(mut commands: Commands, mut evolved: MessageReader<ItemEvolved>) {
+    if let Some(event) = evolved.read().last() {
+        commands.insert_resource(PendingEvolution {
+            from: event.from,
+            item: event.item.clone(),
+        });
+    }
+}
+
+/// Opens the reveal once the anvil has finished closing.
+fn open_evolution_reveal(mut commands: Commands, active_modal: Res<ActiveModal>) {
+    if active_modal.modal.is_none() {
+        commands.trigger(OpenModal(ModalType::Evolution));
+    }
+}
+
+/// Closes the reveal on Select, but only once it has played out, so the key press that
+/// evolved the item doesn't dismiss it straight away.
+fn close_on_select(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    names: Query<&EvolutionRevealName>,
+) {
+    let selected = action_reader
+        .read()
+        .any(|action| *action == GameAction::Select);
+    if selected && names.iter().all(|name| name.0.is_finished()) {
+        commands.close_modal::<EvolutionModal>();
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::i18n::Localization;
+use crate::item::ItemRegistry;
+use crate::ui::widgets::{Column, ItemDetailDisplay};
+use crate::ui::{Modal, SpawnModalExt};
+
+use super::state::{EvolutionModalRoot, EvolutionRevealName, PendingEvolution, REVEAL_SECS};
+
+const MODAL_WIDTH: f32 = 420.0;
+const PROMPT_SIZE: f32 = 18.0;
+const NAME_START_SIZE: f32 = 10.0;
+const NAME_END_SIZE: f32 = 30.0;
+
+const PROMPT_COLOR: Color = Color::srgb(0.7, 0.7, 0.9);
+const NAME_START_COLOR: Color = Color::WHITE;
+const NAME_END_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+pub fn do_spawn_evolution_modal(
+    mut commands: Commands,
+    pending: Option<Res<PendingEvolution>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+    let from = registry.get(pending.from);
+    let prompt = loc.format(
+        "evolution.reveal",
+        &[("item", &loc.item_name(from.id, &from.name))],
+    );
+    let name = loc
+        .item_name(pending.item.item_id, &pending.item.name)
+        .to_string();
+    let item = pending.item.clone();
+
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get("evolution.title"))
+            .size((MODAL_WIDTH, 0.0))
+            .hint(loc.get("evolution.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(EvolutionModalRoot);
+            }))
+            .content(Box::new(move |c| {
+                c.spawn(Column::new().gap(10.0).align_center())
+                    .with_children(|col| {
+                        col.spawn((
+                            Text::new(prompt),
+                            TextFont {
+                                font_size: PROMPT_SIZE,
+                                ..default()
+                            },
+                            TextColor(PROMPT_COLOR),
+                        ));
+                        col.spawn((
+                            EvolutionRevealName::default(),
+                            Text::new(name),
+                            TextFont {
+                                font_size: NAME_START_SIZE,
+                                ..default()
+                            },
+                            TextColor(NAME_START_COLOR),
+                        ));
+                        col.spawn(ItemDetailDisplay::builder(&item).build());
+                    });
+            }))
+            .build(),
+    );
+}
+
+/// Grows the successor's name from white to a full-size gold over the reveal.
+pub fn animate_evolution_reveal(
+    time: Res<Time>,
+    mut names: Query<(&mut EvolutionRevealName, &mut TextFont, &mut TextColor)>,
+) {
+    for (mut reveal, mut font, mut color) in &mut names {
+        if reveal.0.is_finished() {
+            continue;
+        }
+        reveal.0.tick(time.delta());
+        let t = reveal.0.elapsed_secs() / REVEAL_SECS;
+        font.font_size = NAME_START_SIZE + (NAME_END_SIZE - NAME_START_SIZE) * t;
+        color.0 = NAME_START_COLOR.mix(&NAME_END_COLOR, t);
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+
+use crate::item::{Item, ItemId};
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+
+use super::render::do_spawn_evolution_modal;
+
+/// How long the successor's name takes to blaze up to full size.
+pub const REVEAL_SECS: f32 = 1.2;
+
+#[derive(Component)]
+pub struct EvolutionModalRoot;
+
+/// The successor's name, which grows and turns gold as the reveal plays.
+#[derive(Component)]
+pub struct EvolutionRevealName(pub Timer);
+
+impl Default for EvolutionRevealName {
+    fn default() -> Self {
+        Self(Timer::from_seconds(REVEAL_SECS, TimerMode::Once))
+    }
+}
+
+/// An evolution waiting to be revealed, kept until the reveal closes.
+#[derive(Resource)]
+pub struct PendingEvolution {
+    pub from: ItemId,
+    pub item: Item,
+}
+
+pub struct EvolutionModal;
+
+impl RegisteredModal for EvolutionModal {
+    type Root = EvolutionModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Evolution;
+
+    fn spawn(world: &mut World) {
+        world.run_system_cached(do_spawn_evolution_modal).ok();
+    }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<PendingEvolution>();
+    }
+}
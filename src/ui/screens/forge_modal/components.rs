@@ -16,3 +16,7 @@ pub struct ForgeSlotItemSprite;
 
 #[derive(Component)]
 pub struct ForgeSlotQuantityText;
+
+/// Status line for the hired apprentice under the forge slots.
+#[derive(Component)]
+pub struct ForgeApprenticeText;
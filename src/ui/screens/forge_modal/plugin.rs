@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 
 use crate::crafting_station::TryStartForgeCrafting;
-use crate::input::{navigate_forge_ui, transfer_forge_items, GameAction};
+use crate::input::{
+    cycle_apprentice, fetch_forge_ingredients, navigate_forge_ui, transfer_forge_items,
+    GameAction,
+};
+use crate::states::ScreenSet;
 use crate::ui::focus::{tab_toggle_system, FocusPanel};
 use crate::ui::modal_registry::{ModalCommands, RegisterModalExt};
 use crate::ui::screens::modal::in_forge_modal;
@@ -10,8 +14,8 @@ use crate::ui::FocusState;
 
 use super::state::{ActiveForgeEntity, ForgeModal, ForgeModalState};
 use super::systems::{
-    populate_forge_detail_pane_content, refresh_forge_slots, update_forge_detail_pane_source,
-    update_forge_slot_selector,
+    populate_forge_detail_pane_content, refresh_forge_slots, update_apprentice_text,
+    update_forge_detail_pane_source, update_forge_slot_selector,
 };
 
 pub struct ForgeModalPlugin;
@@ -22,12 +26,21 @@ impl Plugin for ForgeModalPlugin {
             .add_systems(
                 Update,
                 (
-                    handle_forge_close.run_if(in_forge_modal),
+                    handle_forge_close
+                        .run_if(in_forge_modal)
+                        .in_set(ScreenSet::Input),
                     (
                         tab_toggle_system(FocusPanel::ForgeCraftingSlots, FocusPanel::ForgeInventory),
                         navigate_forge_ui,
                         transfer_forge_items,
+                        fetch_forge_ingredients,
+                        cycle_apprentice,
+                    )
+                        .in_set(ScreenSet::Input)
+                        .run_if(in_forge_modal),
+                    (
                         refresh_forge_slots,
+                        update_apprentice_text,
                         update_forge_detail_pane_source.run_if(
                             resource_exists::<FocusState>
                                 .and(resource_changed::<FocusState>)
@@ -39,6 +52,7 @@ impl Plugin for ForgeModalPlugin {
                         ),
                         populate_forge_detail_pane_content,
                     )
+                        .in_set(ScreenSet::Render)
                         .run_if(in_forge_modal),
                 ),
             )
@@ -14,8 +14,8 @@ use crate::ui::InfoPanelSource;
 use crate::ui::{Modal, ModalBackground, SpawnModalExt};
 
 use super::components::{
-    ForgeSlotCell, ForgeSlotItemSprite, ForgeSlotQuantityText, LABEL_FONT_SIZE, SLOT_GAP,
-    SLOT_SIZE,
+    ForgeApprenticeText, ForgeSlotCell, ForgeSlotItemSprite, ForgeSlotQuantityText,
+    LABEL_FONT_SIZE, SLOT_GAP, SLOT_SIZE,
 };
 use super::state::{
     ActiveForgeEntity, ForgeModalRoot, ForgeModalState, ForgePlayerGrid, ForgeSlotIndex,
@@ -86,7 +86,7 @@ fn spawn_crafting_slots(
     registry: &ItemRegistry,
 ) {
     let slots_width = 3.0 * SLOT_SIZE + 2.0 * SLOT_GAP + 32.0;
-    let slots_height = SLOT_SIZE + 40.0;
+    let slots_height = SLOT_SIZE + 60.0;
 
     parent
         .spawn((
@@ -141,6 +141,17 @@ fn spawn_crafting_slots(
                         registry,
                     );
                 });
+
+            container.spawn((
+                ForgeApprenticeText,
+                Text::new(""),
+                game_fonts.pixel_font(LABEL_FONT_SIZE),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                Node {
+                    margin: UiRect::top(Val::Px(8.0)),
+                    ..default()
+                },
+            ));
         });
 }
 
@@ -204,7 +215,7 @@ pub fn spawn_slot_item(
 ) {
     let item = registry.spawn(item_id);
     if let Some(icon_img) = game_sprites
-        .get(item.sprite.sheet_key)
+        .get(item.sprite.sheet_key.into())
         .and_then(|s| s.image_node(&item.sprite.name))
     {
         cell.spawn((
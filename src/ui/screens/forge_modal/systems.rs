@@ -1,10 +1,14 @@
 use bevy::prelude::*;
 
 use crate::assets::{GameFonts, GameSprites};
-use crate::crafting_station::ForgeCraftingState;
+use crate::crafting_station::{
+    can_hire, Apprentice, ApprenticeStall, ForgeCraftingState, APPRENTICE_LEVEL, APPRENTICE_WAGE,
+};
+use crate::i18n::Localization;
 use crate::inventory::{Inventory, ManagesItems};
 use crate::item::{ItemId, ItemRegistry};
 use crate::player::PlayerMarker;
+use crate::skills::Skills;
 use crate::ui::focus::{FocusPanel, FocusState};
 use crate::ui::widgets::{
     spawn_selector, AnimatedSelector, ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent,
@@ -12,7 +16,9 @@ use crate::ui::widgets::{
 };
 use crate::ui::InfoPanelSource;
 
-use super::components::{ForgeSlotCell, ForgeSlotItemSprite, ForgeSlotQuantityText};
+use super::components::{
+    ForgeApprenticeText, ForgeSlotCell, ForgeSlotItemSprite, ForgeSlotQuantityText,
+};
 use super::spawning::spawn_slot_item;
 use super::state::{ActiveForgeEntity, ForgeModalState, ForgePlayerGrid, ForgeSlotIndex};
 
@@ -203,3 +209,36 @@ pub fn populate_forge_detail_pane_content(
         });
     }
 }
+
+pub fn update_apprentice_text(
+    apprentice: Res<Apprentice>,
+    skills: Res<Skills>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut texts: Query<&mut Text, With<ForgeApprenticeText>>,
+    added: Query<(), Added<ForgeApprenticeText>>,
+) {
+    if !apprentice.is_changed() && !skills.is_changed() && added.is_empty() {
+        return;
+    }
+    let Ok(mut text) = texts.single_mut() else {
+        return;
+    };
+
+    **text = match apprentice.recipe {
+        None if !can_hire(&skills) => {
+            loc.format("apprentice.locked", &[("level", &APPRENTICE_LEVEL)])
+        }
+        None => loc.format("apprentice.none", &[("wage", &APPRENTICE_WAGE)]),
+        Some(recipe) => {
+            let output = recipe.spec().output;
+            let item = loc.item_name(output, &registry.get(output).name).to_string();
+            let key = match apprentice.stall {
+                Some(ApprenticeStall::MissingMaterials) => "apprentice.stalled_materials",
+                Some(ApprenticeStall::StorageFull) => "apprentice.stalled_full",
+                None => "apprentice.working",
+            };
+            loc.format(key, &[("item", &item), ("wage", &APPRENTICE_WAGE)])
+        }
+    };
+}
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::ShowToast;
+use crate::guild::{
+    current_day, GuildHall, GuildPerk, GuildRequest, MAX_HALL_LEVEL, XP_BONUS_PERCENT,
+};
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+const UNLOCKED_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+pub struct GuildScreenPlugin;
+
+impl Plugin for GuildScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Guild), spawn_guild_screen)
+            .add_systems(OnExit(AppState::Guild), despawn_guild_screen)
+            .add_systems(
+                Update,
+                open_guild_screen
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_guild_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_guild_body.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Guild)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct GuildScreenRoot;
+
+#[derive(Component)]
+struct GuildBody;
+
+#[derive(Component)]
+struct GuildHint;
+
+fn open_guild_screen(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenGuild || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::Guild);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("guild.not_in_town")));
+        }
+    }
+}
+
+fn spawn_guild_screen(mut commands: Commands, loc: Res<Localization>) {
+    commands
+        .spawn((
+            GuildScreenRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("guild.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                GuildBody,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                GuildHint,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+fn handle_guild_input(
+    mut action_reader: MessageReader<GameAction>,
+    hall: Res<GuildHall>,
+    mut guild_writer: MessageWriter<GuildRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        match action {
+            GameAction::Select if !hall.joined => {
+                guild_writer.write(GuildRequest::Join);
+            }
+            GameAction::Select => {
+                guild_writer.write(GuildRequest::Donate);
+            }
+            GameAction::NextTab if hall.joined => {
+                guild_writer.write(GuildRequest::ClaimPotion);
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenGuild => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Lists the hall's perks and what the next upgrade still needs, alongside how much of each
+/// material the player is carrying.
+fn body_text(
+    loc: &Localization,
+    hall: &GuildHall,
+    inventory: &Inventory,
+    registry: &ItemRegistry,
+) -> String {
+    if !hall.joined {
+        return loc.get("guild.not_joined").to_string();
+    }
+
+    let mut lines = vec![loc.format(
+        "guild.level",
+        &[("level", &hall.level), ("max", &MAX_HALL_LEVEL)],
+    )];
+    for perk in GuildPerk::ALL {
+        let key = if hall.has_perk(perk) {
+            "guild.perk_unlocked"
+        } else {
+            "guild.perk_locked"
+        };
+        let label = loc.format(perk.label_key(), &[("percent", &XP_BONUS_PERCENT)]);
+        lines.push(loc.format(key, &[("perk", &label), ("level", &perk.level())]));
+    }
+
+    lines.push(String::new());
+    if hall.is_max_level() {
+        lines.push(loc.get("guild.max_level").to_string());
+    } else {
+        lines.push(loc.get("guild.next_upgrade").to_string());
+        for (item_id, have, need) in hall.requirements() {
+            let name = loc.item_name(item_id, &registry.get(item_id).name);
+            lines.push(loc.format(
+                "guild.requirement",
+                &[
+                    ("item", &name),
+                    ("have", &have),
+                    ("need", &need),
+                    ("carrying", &inventory.count_item(item_id)),
+                ],
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+type BodyFilter = (With<GuildBody>, Without<GuildHint>);
+
+fn update_guild_body(
+    hall: Res<GuildHall>,
+    player: Query<Ref<Inventory>, With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut body: Query<(&mut Text, &mut TextColor), BodyFilter>,
+    mut hint: Query<&mut Text, (With<GuildHint>, Without<GuildBody>)>,
+    added: Query<(), Added<GuildBody>>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+    if !hall.is_changed() && !inventory.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok((mut text, mut color)) = body.single_mut() {
+        **text = body_text(&loc, &hall, &inventory, &registry);
+        *color = TextColor(if hall.is_max_level() {
+            UNLOCKED_COLOR
+        } else {
+            Color::WHITE
+        });
+    }
+
+    if let Ok(mut text) = hint.single_mut() {
+        **text = if !hall.joined {
+            loc.get("guild.hint_join").to_string()
+        } else if hall.can_claim_potion(current_day()) {
+            loc.get("guild.hint_potion").to_string()
+        } else {
+            loc.get("guild.hint").to_string()
+        };
+    }
+}
+
+fn despawn_guild_screen(mut commands: Commands, root: Query<Entity, With<GuildScreenRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,6 @@
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::HelpModalPlugin;
+pub use state::HelpModal;
@@ -0,0 +1,73 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::states::ScreenSet;
+use crate::ui::modal_registry::{ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::in_help_modal;
+
+use super::render::update_help_display;
+use super::state::{HelpModal, HelpModalState, HelpResultsList};
+
+pub struct HelpModalPlugin;
+
+impl Plugin for HelpModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<HelpModal>().add_systems(
+            Update,
+            (
+                handle_help_keys.in_set(ScreenSet::Input),
+                update_help_display
+                    .run_if(
+                        resource_exists_and_changed::<HelpModalState>
+                            .or(any_match_filter::<Added<HelpResultsList>>),
+                    )
+                    .in_set(ScreenSet::Render),
+            )
+                .run_if(in_help_modal.and(resource_exists::<HelpModalState>)),
+        );
+    }
+}
+
+/// Keyboard input for the help modal. Game actions are suppressed while it is open so that
+/// typing a search does not also open the inventory or move the player.
+fn handle_help_keys(
+    mut commands: Commands,
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    mut state: ResMut<HelpModalState>,
+) {
+    // Skip the key press that opened the modal, plus anything left over from a previous visit.
+    if state.is_added() {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Escape | Key::F1 => commands.close_modal::<HelpModal>(),
+            Key::ArrowUp => {
+                state.selected = state.selected.saturating_sub(1);
+            }
+            Key::ArrowDown => {
+                let last = state.results().len().saturating_sub(1);
+                state.selected = (state.selected + 1).min(last);
+            }
+            Key::Backspace => {
+                state.query.pop();
+                state.selected = 0;
+            }
+            Key::Space => {
+                state.query.push(' ');
+                state.selected = 0;
+            }
+            Key::Character(chars) => {
+                state.query.push_str(chars);
+                state.selected = 0;
+            }
+            _ => {}
+        }
+    }
+}
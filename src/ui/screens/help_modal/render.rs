@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use crate::i18n::Localization;
+use crate::ui::widgets::{Column, Row};
+use crate::ui::{Modal, SpawnModalExt};
+
+use super::state::{HelpDetailText, HelpModalRoot, HelpModalState, HelpResultsList, HelpSearchText};
+
+const MODAL_WIDTH: f32 = 760.0;
+const LIST_WIDTH: f32 = 240.0;
+const DETAIL_WIDTH: f32 = 440.0;
+const SEARCH_SIZE: f32 = 20.0;
+const ENTRY_SIZE: f32 = 18.0;
+const DETAIL_SIZE: f32 = 18.0;
+
+const SEARCH_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+const NORMAL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const CATEGORY_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+pub fn do_spawn_help_modal(mut commands: Commands, loc: Res<Localization>) {
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get("help.title"))
+            .size((MODAL_WIDTH, 0.0))
+            .hint(loc.get("help.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(HelpModalRoot);
+            }))
+            .content(Box::new(|c| {
+                c.spawn(Column::new().gap(12.0)).with_children(|col| {
+                    col.spawn((
+                        HelpSearchText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: SEARCH_SIZE,
+                            ..default()
+                        },
+                        TextColor(SEARCH_COLOR),
+                    ));
+
+                    col.spawn(Row::new().gap(20.0)).with_children(|row| {
+                        row.spawn((
+                            HelpResultsList,
+                            Column::new().gap(4.0).width(Val::Px(LIST_WIDTH)),
+                        ));
+                        row.spawn((
+                            HelpDetailText,
+                            Text::new(""),
+                            TextFont {
+                                font_size: DETAIL_SIZE,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                            Node {
+                                width: Val::Px(DETAIL_WIDTH),
+                                ..default()
+                            },
+                        ));
+                    });
+                });
+            }))
+            .build(),
+    );
+}
+
+pub fn update_help_display(
+    mut commands: Commands,
+    state: Res<HelpModalState>,
+    loc: Res<Localization>,
+    list: Query<Entity, With<HelpResultsList>>,
+    mut search_text: Query<&mut Text, (With<HelpSearchText>, Without<HelpDetailText>)>,
+    mut detail_text: Query<&mut Text, (With<HelpDetailText>, Without<HelpSearchText>)>,
+) {
+    let results = state.results();
+
+    if let Ok(mut text) = search_text.single_mut() {
+        **text = loc.format("help.search", &[("query", &format!("{}_", state.query))]);
+    }
+
+    if let Ok(mut text) = detail_text.single_mut() {
+        **text = match results.get(state.selected) {
+            Some(entry) => entry.body.clone(),
+            None => loc.format("help.no_results", &[("query", &state.query)]),
+        };
+    }
+
+    let Ok(list) = list.single() else {
+        return;
+    };
+
+    commands.entity(list).despawn_related::<Children>();
+    commands.entity(list).with_children(|parent| {
+        for (index, entry) in results.iter().enumerate() {
+            let color = if index == state.selected {
+                SELECTED_COLOR
+            } else {
+                NORMAL_COLOR
+            };
+
+            parent.spawn(Column::new()).with_children(|item| {
+                item.spawn((
+                    Text::new(entry.title.clone()),
+                    TextFont {
+                        font_size: ENTRY_SIZE,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+                item.spawn((
+                    Text::new(loc.get(entry.category.label_key())),
+                    TextFont {
+                        font_size: ENTRY_SIZE - 4.0,
+                        ..default()
+                    },
+                    TextColor(CATEGORY_COLOR),
+                ));
+            });
+        }
+    });
+}
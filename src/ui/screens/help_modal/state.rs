@@ -0,0 +1,62 @@
+use bevy::asset::LoadedFolder;
+use bevy::prelude::*;
+
+use crate::help::{search, HelpEntry, HelpLibrary};
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+
+use super::render::do_spawn_help_modal;
+
+#[derive(Component)]
+pub struct HelpModalRoot;
+
+#[derive(Component)]
+pub struct HelpSearchText;
+
+#[derive(Component)]
+pub struct HelpResultsList;
+
+#[derive(Component)]
+pub struct HelpDetailText;
+
+/// Entries snapshot and search state for the open help modal.
+#[derive(Resource, Default)]
+pub struct HelpModalState {
+    pub entries: Vec<HelpEntry>,
+    pub query: String,
+    pub selected: usize,
+}
+
+impl HelpModalState {
+    pub fn results(&self) -> Vec<&HelpEntry> {
+        search(&self.entries, &self.query)
+    }
+}
+
+pub struct HelpModal;
+
+impl RegisteredModal for HelpModal {
+    type Root = HelpModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Help;
+
+    fn spawn(world: &mut World) {
+        let entries = match (
+            world.get_resource::<HelpLibrary>(),
+            world.get_resource::<Assets<LoadedFolder>>(),
+            world.get_resource::<Assets<HelpEntry>>(),
+        ) {
+            (Some(library), Some(folders), Some(assets)) => library.entries(folders, assets),
+            _ => Vec::new(),
+        };
+
+        world.insert_resource(HelpModalState {
+            entries,
+            ..default()
+        });
+        world.run_system_cached(do_spawn_help_modal).ok();
+    }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<HelpModalState>();
+    }
+}
@@ -3,4 +3,7 @@ pub(crate) mod render;
 mod state;
 
 pub use plugin::InventoryModalPlugin;
-pub use state::{BackpackGrid, EquipmentGrid, InventoryModal};
+pub use state::{
+    BackpackGrid, EquipmentGrid, InventoryModal, InventoryView, ItemAction, ItemContextMenu,
+    ItemMenuTarget, SplitStackDialog,
+};
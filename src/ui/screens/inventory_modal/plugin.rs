@@ -1,28 +1,49 @@
 use bevy::prelude::*;
 
-use crate::input::{navigate_inventory_grid, toggle_equipment};
+use crate::input::{
+    handle_inventory_view_actions, handle_item_menu, handle_split_dialog, mark_selected_seen,
+    navigate_inventory_grid, open_item_menu, toggle_equipment, toggle_favorite,
+};
+use crate::states::ScreenSet;
 use crate::ui::focus::{tab_toggle_system, FocusPanel};
 use crate::ui::modal_registry::{modal_close_system, RegisterModalExt};
 use crate::ui::screens::modal::in_inventory_modal;
 use crate::ui::widgets::{update_detail_pane_source, ItemGridSelection};
 use crate::ui::FocusState;
 
-use super::render::{populate_inventory_detail_pane_content, sync_inventory_to_grids};
-use super::state::{InventoryDetailPane, InventoryModal};
+use super::render::{
+    populate_inventory_detail_pane_content, sync_inventory_to_grids, update_inventory_header,
+    update_item_menu_text, update_loadout_text,
+};
+use super::state::{
+    InventoryDetailPane, InventoryModal, InventoryView, InventoryViewText, ItemContextMenu,
+    SplitStackDialog,
+};
 
 pub struct InventoryModalPlugin;
 
 impl Plugin for InventoryModalPlugin {
     fn build(&self, app: &mut App) {
         app.register_modal::<InventoryModal>()
+            .init_resource::<InventoryView>()
             .add_systems(
                 Update,
                 (
-                    modal_close_system::<InventoryModal>,
+                    modal_close_system::<InventoryModal>.in_set(ScreenSet::Input),
                     (
                         tab_toggle_system(FocusPanel::EquipmentGrid, FocusPanel::BackpackGrid),
                         navigate_inventory_grid,
                         toggle_equipment,
+                        toggle_favorite,
+                        handle_inventory_view_actions,
+                        handle_split_dialog.run_if(resource_exists::<SplitStackDialog>),
+                        open_item_menu,
+                        handle_item_menu.run_if(resource_exists::<ItemContextMenu>),
+                    )
+                        .in_set(ScreenSet::Input)
+                        .run_if(in_inventory_modal),
+                    (
+                        mark_selected_seen,
                         sync_inventory_to_grids,
                         update_detail_pane_source::<InventoryDetailPane>.run_if(
                             resource_exists::<FocusState>
@@ -30,7 +51,19 @@ impl Plugin for InventoryModalPlugin {
                                 .or(any_match_filter::<Changed<ItemGridSelection>>),
                         ),
                         populate_inventory_detail_pane_content,
+                        update_inventory_header.run_if(
+                            resource_changed::<InventoryView>
+                                .or(resource_exists_and_changed::<SplitStackDialog>)
+                                .or(resource_removed::<SplitStackDialog>)
+                                .or(any_match_filter::<Added<InventoryViewText>>),
+                        ),
+                        update_loadout_text,
+                        update_item_menu_text.run_if(
+                            resource_exists_and_changed::<ItemContextMenu>
+                                .or(resource_removed::<ItemContextMenu>),
+                        ),
                     )
+                        .in_set(ScreenSet::Render)
                         .run_if(in_inventory_modal),
                 ),
             );
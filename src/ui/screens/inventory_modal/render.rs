@@ -1,20 +1,34 @@
 use bevy::prelude::*;
 
-use crate::inventory::{EquipmentSlot, Inventory, InventoryItem, ManagesEquipment, ManagesItems};
+use crate::i18n::Localization;
+use crate::inventory::{
+    EquipmentSlot, Inventory, InventoryItem, ItemCategory, Loadouts, ManagesEquipment,
+    ManagesItems,
+};
 use crate::player::PlayerMarker;
 use crate::ui::focus::FocusPanel;
 use crate::ui::modal_content_row;
 use crate::ui::InfoPanelSource;
 use crate::ui::widgets::{
-    ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent, ItemGrid, ItemGridEntry,
+    Column, ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent, ItemGrid, ItemGridEntry,
     ItemGridFocusPanel, ItemGridSelection,
 };
 use crate::ui::{FocusState, Modal, ModalBackground, SpawnModalExt};
 
-use super::state::{BackpackGrid, EquipmentGrid, InventoryModalRoot};
+use super::state::{
+    BackpackGrid, EquipmentGrid, InventoryModalRoot, InventoryView, InventoryViewText,
+    ItemContextMenu, ItemMenuTarget, ItemMenuText, LoadoutText, SplitDialogText,
+    SplitStackDialog,
+};
+
+const VIEW_TEXT_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const LOADOUT_TEXT_COLOR: Color = Color::srgb(0.7, 0.85, 0.7);
+const SPLIT_TEXT_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const MENU_TEXT_COLOR: Color = Color::srgb(0.6, 0.9, 1.0);
 
 pub fn sync_inventory_to_grids(
-    player: Query<&Inventory, (With<PlayerMarker>, Changed<Inventory>)>,
+    player: Query<Ref<Inventory>, With<PlayerMarker>>,
+    view: Res<InventoryView>,
     mut equipment_grids: Query<
         (&mut ItemGrid, &mut ItemGridSelection),
         (With<EquipmentGrid>, Without<BackpackGrid>),
@@ -27,9 +41,12 @@ pub fn sync_inventory_to_grids(
     let Ok(inventory) = player.single() else {
         return;
     };
+    if !inventory.is_changed() && !view.is_changed() {
+        return;
+    }
 
     if let Ok((mut eq_grid, mut eq_selection)) = equipment_grids.single_mut() {
-        eq_grid.items = get_equipment_items(inventory)
+        eq_grid.items = get_equipment_items(&inventory)
             .iter()
             .map(|inv_item| ItemGridEntry::from_inventory_item(inv_item))
             .collect();
@@ -37,7 +54,7 @@ pub fn sync_inventory_to_grids(
     }
 
     if let Ok((mut bp_grid, mut bp_selection)) = backpack_grids.single_mut() {
-        bp_grid.items = ItemGridEntry::from_inventory(inventory);
+        bp_grid.items = get_backpack_entries(&inventory, view.filter);
         bp_selection.clamp(bp_grid.items.len());
     }
 }
@@ -51,13 +68,39 @@ pub fn get_equipment_items(inventory: &Inventory) -> Vec<&InventoryItem> {
         .collect()
 }
 
-/// Returns backpack (non-equipped) items.
-pub fn get_backpack_items(inventory: &Inventory) -> Vec<&InventoryItem> {
-    inventory.get_inventory_items().iter().collect()
+/// Indices into the backpack of the items shown under `filter`, in display order.
+pub fn backpack_indices(inventory: &Inventory, filter: ItemCategory) -> Vec<usize> {
+    inventory
+        .get_inventory_items()
+        .iter()
+        .enumerate()
+        .filter(|(_, inv_item)| filter.contains(inv_item.item.item_type))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the backpack (non-equipped) items shown under `filter`.
+pub fn get_backpack_items(inventory: &Inventory, filter: ItemCategory) -> Vec<&InventoryItem> {
+    let items = inventory.get_inventory_items();
+    backpack_indices(inventory, filter)
+        .into_iter()
+        .map(|index| &items[index])
+        .collect()
+}
+
+fn get_backpack_entries(inventory: &Inventory, filter: ItemCategory) -> Vec<ItemGridEntry> {
+    get_backpack_items(inventory, filter)
+        .into_iter()
+        .map(ItemGridEntry::from_inventory_item)
+        .collect()
 }
 
 /// Spawn the inventory modal UI with an equipment grid, backpack grid, and detail pane.
-pub fn spawn_inventory_modal(commands: &mut Commands, inventory: &Inventory) {
+pub fn spawn_inventory_modal(
+    commands: &mut Commands,
+    inventory: &Inventory,
+    view: &InventoryView,
+) {
     commands.insert_resource(FocusState {
         focused: Some(FocusPanel::EquipmentGrid),
     });
@@ -67,7 +110,7 @@ pub fn spawn_inventory_modal(commands: &mut Commands, inventory: &Inventory) {
         .map(|inv_item| ItemGridEntry::from_inventory_item(inv_item))
         .collect();
 
-    let backpack_entries: Vec<ItemGridEntry> = ItemGridEntry::from_inventory(inventory);
+    let backpack_entries = get_backpack_entries(inventory, view.filter);
 
     commands.spawn_modal(
         Modal::builder()
@@ -76,27 +119,65 @@ pub fn spawn_inventory_modal(commands: &mut Commands, inventory: &Inventory) {
                 e.insert(InventoryModalRoot);
             }))
             .content(Box::new(move |c| {
-                c.spawn(modal_content_row()).with_children(|row| {
-                    row.spawn((
-                        EquipmentGrid,
-                        ItemGridFocusPanel(FocusPanel::EquipmentGrid),
-                        ItemGrid {
-                            items: equipment_entries,
-                            grid_size: 3,
+                c.spawn(Column::new().gap(8.0)).with_children(|col| {
+                    col.spawn((
+                        InventoryViewText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(VIEW_TEXT_COLOR),
+                    ));
+                    col.spawn((
+                        LoadoutText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(LOADOUT_TEXT_COLOR),
+                    ));
+                    col.spawn((
+                        SplitDialogText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
                         },
-                        ItemGridSelection::default(),
+                        TextColor(SPLIT_TEXT_COLOR),
                     ));
-                    row.spawn((
-                        BackpackGrid,
-                        ItemGridFocusPanel(FocusPanel::BackpackGrid),
-                        ItemGrid {
-                            items: backpack_entries,
-                            grid_size: 4,
+                    col.spawn((
+                        ItemMenuText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
                         },
-                        ItemGridSelection::default(),
+                        TextColor(MENU_TEXT_COLOR),
                     ));
-                    row.spawn(ItemDetailPane {
-                        source: InfoPanelSource::Equipment { selected_index: 0 },
+                    col.spawn(modal_content_row()).with_children(|row| {
+                        row.spawn((
+                            EquipmentGrid,
+                            ItemGridFocusPanel(FocusPanel::EquipmentGrid),
+                            ItemGrid {
+                                items: equipment_entries,
+                                grid_size: 4,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn((
+                            BackpackGrid,
+                            ItemGridFocusPanel(FocusPanel::BackpackGrid),
+                            ItemGrid {
+                                items: backpack_entries,
+                                grid_size: 4,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn(ItemDetailPane {
+                            source: InfoPanelSource::Equipment { selected_index: 0 },
+                        });
                     });
                 });
             }))
@@ -104,8 +185,124 @@ pub fn spawn_inventory_modal(commands: &mut Commands, inventory: &Inventory) {
     );
 }
 
+/// Refreshes the filter/sort line and the split dialog line.
+pub fn update_inventory_header(
+    view: Res<InventoryView>,
+    dialog: Option<Res<SplitStackDialog>>,
+    loc: Res<Localization>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    mut view_text: Query<&mut Text, (With<InventoryViewText>, Without<SplitDialogText>)>,
+    mut split_text: Query<&mut Text, (With<SplitDialogText>, Without<InventoryViewText>)>,
+) {
+    if let Ok(mut text) = view_text.single_mut() {
+        **text = loc.format(
+            "inventory.view",
+            &[
+                ("filter", &loc.get(view.filter.label_key())),
+                ("sort", &loc.get(view.sort.label_key())),
+            ],
+        );
+    }
+
+    let Ok(mut text) = split_text.single_mut() else {
+        return;
+    };
+    let stack = dialog.as_ref().and_then(|dialog| {
+        let inventory = player.single().ok()?;
+        let inv_item = inventory.get_inventory_items().get(dialog.inventory_index)?;
+        Some((dialog, inv_item))
+    });
+    **text = match stack {
+        Some((dialog, inv_item)) => loc.format(
+            "inventory.split_dialog",
+            &[
+                ("item", &inv_item.item.name),
+                ("amount", &dialog.amount),
+                ("rest", &(inv_item.quantity - dialog.amount)),
+            ],
+        ),
+        None => String::new(),
+    };
+}
+
+/// Lists the loadouts by number key, marking the one being worn.
+pub fn update_loadout_text(
+    loc: Res<Localization>,
+    player: Query<(Ref<Inventory>, Ref<Loadouts>), With<PlayerMarker>>,
+    mut loadout_text: Query<(&mut Text, Ref<LoadoutText>)>,
+) {
+    let (Ok((inventory, loadouts)), Ok((mut text, marker))) =
+        (player.single(), loadout_text.single_mut())
+    else {
+        return;
+    };
+    if !inventory.is_changed() && !loadouts.is_changed() && !marker.is_added() {
+        return;
+    }
+
+    let entries: Vec<String> = loadouts
+        .iter()
+        .enumerate()
+        .map(|(index, loadout)| {
+            let key = if loadout.is_worn(&inventory) {
+                "inventory.loadout_worn"
+            } else if loadout.is_empty() {
+                "inventory.loadout_empty"
+            } else {
+                "inventory.loadout"
+            };
+            loc.format(key, &[("key", &(index + 1)), ("name", &loadout.name)])
+        })
+        .collect();
+    **text = loc.format("inventory.loadouts", &[("loadouts", &entries.join("  "))]);
+}
+
+/// Shows the open context menu's actions, with the highlighted one in brackets.
+pub fn update_item_menu_text(
+    menu: Option<Res<ItemContextMenu>>,
+    loc: Res<Localization>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    mut menu_text: Query<&mut Text, With<ItemMenuText>>,
+) {
+    let Ok(mut text) = menu_text.single_mut() else {
+        return;
+    };
+    let target = menu.as_ref().and_then(|menu| {
+        let inventory = player.single().ok()?;
+        let inv_item = match menu.target {
+            ItemMenuTarget::Backpack(index) => inventory.get_inventory_items().get(index),
+            ItemMenuTarget::Equipped(slot) => inventory.get_equipped_item(slot),
+        }?;
+        Some((menu, inv_item))
+    });
+    **text = match target {
+        Some((menu, inv_item)) => {
+            let actions = menu
+                .actions
+                .iter()
+                .enumerate()
+                .map(|(index, action)| {
+                    let label = loc.get(action.label_key());
+                    if index == menu.selected {
+                        format!("[{label}]")
+                    } else {
+                        label.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            loc.format(
+                "item_menu.title",
+                &[("item", &inv_item.item.name), ("actions", &actions)],
+            )
+        }
+        None => String::new(),
+    };
+}
+
 pub fn populate_inventory_detail_pane_content(
     mut commands: Commands,
+    view: Res<InventoryView>,
     player: Query<&Inventory, With<PlayerMarker>>,
     panes: Query<Ref<ItemDetailPane>>,
     content_query: Query<(Entity, Option<&Children>), With<ItemDetailPaneContent>>,
@@ -115,7 +312,7 @@ pub fn populate_inventory_detail_pane_content(
     };
 
     for pane in &panes {
-        if !pane.is_changed() {
+        if !pane.is_changed() && !view.is_changed() {
             continue;
         }
 
@@ -134,7 +331,7 @@ pub fn populate_inventory_detail_pane_content(
                 get_equipment_items(inventory).into_iter().nth(selected_index)
             }
             InfoPanelSource::Inventory { selected_index } => {
-                get_backpack_items(inventory).into_iter().nth(selected_index)
+                get_backpack_items(inventory, view.filter).into_iter().nth(selected_index)
             }
             _ => None,
         };
@@ -145,7 +342,7 @@ pub fn populate_inventory_detail_pane_content(
 
         let item = &inv_item.item;
         let comparison = if matches!(pane.source, InfoPanelSource::Inventory { .. }) {
-            inventory.get_comparison_stats(item)
+            inventory.compare_to_equipped(item)
         } else {
             None
         };
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::inventory::Inventory;
+use crate::inventory::{EquipmentSlot, Inventory, InventoryItem, InventorySort, ItemCategory};
 use crate::player::PlayerMarker;
 use crate::ui::focus::FocusPanel;
 use crate::ui::modal_registry::RegisteredModal;
@@ -22,6 +22,143 @@ pub struct EquipmentGrid;
 #[derive(Component)]
 pub struct BackpackGrid;
 
+/// Marker for the filter and sort line above the grids.
+#[derive(Component)]
+pub struct InventoryViewText;
+
+/// Marker for the loadout selector line.
+#[derive(Component)]
+pub struct LoadoutText;
+
+/// Marker for the split dialog line, empty while no split is in progress.
+#[derive(Component)]
+pub struct SplitDialogText;
+
+/// How the backpack grid is shown. Kept between openings of the modal.
+#[derive(Resource, Debug, Default)]
+pub struct InventoryView {
+    pub filter: ItemCategory,
+    /// The sort applied on the next sort action.
+    pub sort: InventorySort,
+}
+
+/// Present while the player is choosing how many items to split off a stack.
+#[derive(Resource, Debug)]
+pub struct SplitStackDialog {
+    pub inventory_index: usize,
+    pub amount: u32,
+    /// Largest amount that still leaves one item behind.
+    pub max: u32,
+}
+
+impl SplitStackDialog {
+    /// Opens on half the stack, or `None` for stacks that cannot be split.
+    pub fn new(inventory_index: usize, quantity: u32) -> Option<Self> {
+        (quantity > 1).then(|| Self {
+            inventory_index,
+            amount: quantity / 2,
+            max: quantity - 1,
+        })
+    }
+
+    pub fn adjust(&mut self, delta: i64) {
+        self.amount = (self.amount as i64 + delta).clamp(1, self.max as i64) as u32;
+    }
+}
+
+/// Marker for the item context menu line, empty while the menu is closed.
+#[derive(Component)]
+pub struct ItemMenuText;
+
+/// Something the context menu can do to an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemAction {
+    Equip,
+    Unequip,
+    Split,
+    Lock,
+    Unlock,
+    Favorite,
+    Unfavorite,
+    Deposit,
+}
+
+impl ItemAction {
+    /// The actions that apply to `inv_item`. Depositing needs the player to be in town.
+    pub fn available(inv_item: &InventoryItem, equipped: bool, can_deposit: bool) -> Vec<Self> {
+        let item = &inv_item.item;
+        let mut actions = Vec::new();
+        if equipped {
+            actions.push(Self::Unequip);
+        } else if item.item_type.equipment_slot().is_some() {
+            actions.push(Self::Equip);
+        }
+        if !equipped && inv_item.quantity > 1 {
+            actions.push(Self::Split);
+        }
+        actions.push(if item.is_locked { Self::Unlock } else { Self::Lock });
+        actions.push(if item.is_favorite {
+            Self::Unfavorite
+        } else {
+            Self::Favorite
+        });
+        if !equipped && can_deposit {
+            actions.push(Self::Deposit);
+        }
+        actions
+    }
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::Equip => "item_menu.equip",
+            Self::Unequip => "item_menu.unequip",
+            Self::Split => "item_menu.split",
+            Self::Lock => "item_menu.lock",
+            Self::Unlock => "item_menu.unlock",
+            Self::Favorite => "item_menu.favorite",
+            Self::Unfavorite => "item_menu.unfavorite",
+            Self::Deposit => "item_menu.deposit",
+        }
+    }
+}
+
+/// The item a context menu was opened on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemMenuTarget {
+    /// Index into the backpack.
+    Backpack(usize),
+    Equipped(EquipmentSlot),
+}
+
+/// Present while the player is picking an action from an item's context menu.
+#[derive(Resource, Debug)]
+pub struct ItemContextMenu {
+    pub target: ItemMenuTarget,
+    pub actions: Vec<ItemAction>,
+    pub selected: usize,
+}
+
+impl ItemContextMenu {
+    /// Opens on the first action, or `None` when nothing applies.
+    pub fn new(target: ItemMenuTarget, actions: Vec<ItemAction>) -> Option<Self> {
+        (!actions.is_empty()).then_some(Self {
+            target,
+            actions,
+            selected: 0,
+        })
+    }
+
+    /// Moves the highlight by `delta`, wrapping around.
+    pub fn step(&mut self, delta: isize) {
+        let len = self.actions.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+
+    pub fn action(&self) -> ItemAction {
+        self.actions[self.selected]
+    }
+}
+
 pub struct InventoryDetailPane;
 
 impl DetailPaneContext for InventoryDetailPane {
@@ -60,14 +197,74 @@ impl RegisteredModal for InventoryModal {
     fn spawn(world: &mut World) {
         world.run_system_cached(do_spawn_inventory_modal).ok();
     }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<SplitStackDialog>();
+        world.remove_resource::<ItemContextMenu>();
+    }
 }
 
 fn do_spawn_inventory_modal(
     mut commands: Commands,
+    view: Res<InventoryView>,
     player: Query<&Inventory, With<PlayerMarker>>,
 ) {
     let Ok(inventory) = player.single() else {
         return;
     };
-    spawn_inventory_modal(&mut commands, inventory);
+    spawn_inventory_modal(&mut commands, inventory, &view);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::enums::{EquipmentType, ItemType, MaterialType};
+    use crate::item::{Item, ItemId};
+
+    #[test]
+    fn split_dialog_stays_within_the_stack() {
+        assert!(SplitStackDialog::new(0, 1).is_none());
+
+        let mut dialog = SplitStackDialog::new(3, 50).unwrap();
+        assert_eq!(dialog.amount, 25);
+        dialog.adjust(100);
+        assert_eq!(dialog.amount, 49);
+        dialog.adjust(-100);
+        assert_eq!(dialog.amount, 1);
+    }
+
+    fn stack(item_type: ItemType, quantity: u32) -> InventoryItem {
+        let mut inv_item = InventoryItem::new(Item::test(ItemId::Sword, item_type));
+        inv_item.quantity = quantity;
+        inv_item
+    }
+
+    #[test]
+    fn menu_offers_only_actions_that_fit_the_item() {
+        use ItemAction::*;
+
+        let sword = stack(ItemType::Equipment(EquipmentType::Weapon), 1);
+        assert_eq!(ItemAction::available(&sword, false, false), [Equip, Lock, Favorite]);
+        assert_eq!(ItemAction::available(&sword, true, true), [Unequip, Lock, Favorite]);
+
+        let mut ore = stack(ItemType::Material(MaterialType::Ore), 5);
+        ore.item.is_locked = true;
+        ore.item.is_favorite = true;
+        assert_eq!(
+            ItemAction::available(&ore, false, true),
+            [Split, Unlock, Unfavorite, Deposit]
+        );
+    }
+
+    #[test]
+    fn menu_selection_wraps_both_ways() {
+        let actions = vec![ItemAction::Equip, ItemAction::Lock, ItemAction::Favorite];
+        let mut menu = ItemContextMenu::new(ItemMenuTarget::Backpack(0), actions).unwrap();
+        menu.step(-1);
+        assert_eq!(menu.action(), ItemAction::Favorite);
+        menu.step(1);
+        assert_eq!(menu.action(), ItemAction::Equip);
+
+        assert!(ItemContextMenu::new(ItemMenuTarget::Backpack(0), Vec::new()).is_none());
+    }
 }
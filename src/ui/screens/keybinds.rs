@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 
+use crate::i18n::Localization;
 use crate::input::GameAction;
-use crate::states::{AppState, PreviousState, StateTransitionRequest};
+use crate::states::{AppState, PreviousState, ScreenSet, StateTransitionRequest};
 use crate::ui::column_node;
 
 pub struct KeybindsPlugin;
@@ -12,7 +13,9 @@ impl Plugin for KeybindsPlugin {
             .add_systems(OnExit(AppState::Keybinds), despawn_keybinds_screen)
             .add_systems(
                 Update,
-                handle_close_action.run_if(in_state(AppState::Keybinds)),
+                handle_close_action
+                    .run_if(in_state(AppState::Keybinds))
+                    .in_set(ScreenSet::Input),
             );
     }
 }
@@ -20,34 +23,89 @@ impl Plugin for KeybindsPlugin {
 #[derive(Component)]
 struct KeybindsScreenRoot;
 
-struct KeybindCategory {
-    name: &'static str,
-    bindings: Vec<(&'static str, &'static str)>,
+struct KeybindCategory<'a> {
+    name: &'a str,
+    bindings: Vec<(&'a str, &'a str)>,
 }
 
-fn spawn_keybinds_screen(mut commands: Commands) {
+fn spawn_keybinds_screen(mut commands: Commands, loc: Res<Localization>) {
     let categories = vec![
         KeybindCategory {
-            name: "Navigation",
+            name: loc.get("keybinds.category.navigation"),
             bindings: vec![
-                ("Arrow Keys", "Navigate menus and lists"),
-                ("Enter", "Select / Confirm"),
-                ("Backspace", "Back / Cancel"),
-                ("Tab", "Next tab"),
-                ("Shift+Tab", "Previous tab"),
+                ("Arrow Keys", loc.get("keybinds.navigate")),
+                ("Enter", loc.get("keybinds.select")),
+                ("Backspace", loc.get("keybinds.back")),
+                ("Tab", loc.get("keybinds.next_tab")),
+                ("Shift+Tab", loc.get("keybinds.prev_tab")),
             ],
         },
         KeybindCategory {
-            name: "Actions",
-            bindings: vec![("Space", "Mine / Attack")],
+            name: loc.get("keybinds.category.actions"),
+            bindings: vec![
+                ("Space", loc.get("keybinds.attack")),
+                ("Tab / Shift+Tab", loc.get("keybinds.boss_target")),
+                ("Shift+C", loc.get("keybinds.capture_orb")),
+                ("Shift+F", loc.get("keybinds.throw_flask")),
+                ("S", loc.get("keybinds.cast_spell")),
+                ("Shift+S", loc.get("keybinds.next_spell_page")),
+                ("W", loc.get("keybinds.spellcraft")),
+                ("Shift+W", loc.get("keybinds.word_index")),
+                ("G", loc.get("keybinds.deposit_gold")),
+                ("Shift+G", loc.get("keybinds.withdraw_gold")),
+                ("V", loc.get("keybinds.storage")),
+                ("J", loc.get("keybinds.sell_junk")),
+                ("Shift+B", loc.get("keybinds.gamble")),
+                ("R", loc.get("keybinds.sort_inventory")),
+                ("C", loc.get("keybinds.filter_inventory")),
+                ("Q", loc.get("keybinds.split_stack")),
+                ("Y", loc.get("keybinds.toggle_favorite")),
+                ("Shift+Enter", loc.get("keybinds.item_menu")),
+                ("1-4", loc.get("keybinds.switch_loadout")),
+                ("Shift+1-4", loc.get("keybinds.save_loadout")),
+                ("F", loc.get("keybinds.fetch_ingredients")),
+                ("Shift+Q", loc.get("keybinds.upgrade_quality")),
+                ("O", loc.get("keybinds.apprentice")),
+                ("X", loc.get("keybinds.dismiss_tip")),
+                ("Z", loc.get("keybinds.undo")),
+            ],
         },
         KeybindCategory {
-            name: "Menus & Modals",
+            name: loc.get("keybinds.category.menus"),
             bindings: vec![
-                ("I", "Open Inventory"),
-                ("P", "Open Profile"),
-                ("?", "Open Keybinds (this screen)"),
-                ("Escape", "Close modal"),
+                ("I", loc.get("keybinds.inventory")),
+                ("P", loc.get("keybinds.profile")),
+                ("Shift+P", loc.get("keybinds.run_planner")),
+                ("?", loc.get("keybinds.keybinds")),
+                ("M", loc.get("keybinds.mods")),
+                ("L", loc.get("keybinds.loot_filter")),
+                ("A", loc.get("keybinds.roster")),
+                ("Delete", loc.get("keybinds.delete_save_slot")),
+                ("Shift+L", loc.get("keybinds.combat_log")),
+                ("Shift+M", loc.get("keybinds.adaptive_difficulty")),
+                ("F1", loc.get("keybinds.help")),
+                ("F2", loc.get("keybinds.ui_sounds")),
+                ("Shift+F2", loc.get("keybinds.rumble")),
+                ("F4", loc.get("keybinds.damage_numbers")),
+                ("Shift+F4", loc.get("keybinds.screen_reader")),
+                ("F7", loc.get("keybinds.text_scale")),
+                ("Shift+F7", loc.get("keybinds.palette")),
+                ("F8", loc.get("keybinds.language")),
+                ("F12", loc.get("keybinds.screenshot")),
+                ("Shift+F12", loc.get("keybinds.clean_screenshot")),
+                ("T", loc.get("keybinds.world_map")),
+                ("Shift+T", loc.get("keybinds.dungeon_tier")),
+                ("H", loc.get("keybinds.guild")),
+                ("Shift+H", loc.get("keybinds.trophy_room")),
+                ("Shift+R", loc.get("keybinds.ranch")),
+                ("Shift+O", loc.get("keybinds.workers")),
+                ("E", loc.get("keybinds.event_shop")),
+                ("Shift+E", loc.get("keybinds.leaderboard")),
+                ("U", loc.get("keybinds.auction_house")),
+                ("Shift+U", loc.get("keybinds.town_upgrades")),
+                ("N", loc.get("keybinds.contracts")),
+                ("`", loc.get("keybinds.console")),
+                ("Escape", loc.get("keybinds.close_modal")),
             ],
         },
     ];
@@ -77,7 +135,7 @@ fn spawn_keybinds_screen(mut commands: Commands) {
                 })
                 .with_children(|parent| {
                     parent.spawn((
-                        Text::new("Keybinds & Controls"),
+                        Text::new(loc.get("keybinds.title")),
                         TextFont {
                             font_size: 48.0,
                             ..default()
@@ -98,7 +156,7 @@ fn spawn_keybinds_screen(mut commands: Commands) {
                         });
 
                     parent.spawn((
-                        Text::new("Press Escape to close"),
+                        Text::new(loc.get("keybinds.close")),
                         TextFont {
                             font_size: 20.0,
                             ..default()
@@ -0,0 +1,294 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::{
+    GameStats, IsoWeek, ShowToast, StartEndlessRun, StartWeeklyChallenge, WeeklyChallenge,
+    CHALLENGE_FLOORS,
+};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::location::CurrentTown;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(0.6, 0.85, 1.0);
+const BEST_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const WEEKLY_COLOR: Color = Color::srgb(0.8, 0.6, 1.0);
+
+/// Runs the screen can start, in display order.
+const RUN_OPTIONS: [&str; 2] = ["leaderboard.start_endless", "leaderboard.start_weekly"];
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSelection>()
+            .add_systems(OnEnter(AppState::Leaderboard), spawn_leaderboard)
+            .add_systems(OnExit(AppState::Leaderboard), despawn_leaderboard)
+            .add_systems(
+                Update,
+                open_leaderboard
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_leaderboard_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_run_options.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Leaderboard)),
+            );
+    }
+}
+
+/// Index into [`RUN_OPTIONS`] of the highlighted run.
+#[derive(Resource, Default)]
+struct RunSelection(usize);
+
+#[derive(Component)]
+struct LeaderboardRoot;
+
+#[derive(Component)]
+struct RunOption(usize);
+
+fn open_leaderboard(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenLeaderboard || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::Leaderboard);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("endless.not_in_town")));
+        }
+    }
+}
+
+/// Lines describing this week's challenge and how the player has done at it.
+fn weekly_lines(loc: &Localization, stats: &GameStats) -> Vec<String> {
+    let challenge = WeeklyChallenge::for_week(IsoWeek::current());
+    let modifiers: Vec<&str> = challenge
+        .modifiers
+        .iter()
+        .map(|modifier| loc.get(modifier.label_key()))
+        .collect();
+    let mut lines = vec![loc.format(
+        "leaderboard.weekly",
+        &[
+            ("week", &challenge.week.week),
+            ("modifiers", &modifiers.join(", ")),
+        ],
+    )];
+    lines.push(match stats.weekly_best(challenge.week) {
+        Some(best) if best.completed => loc.format(
+            "leaderboard.weekly_completed",
+            &[("name", &best.name), ("level", &best.level)],
+        ),
+        Some(best) => loc.format(
+            "leaderboard.weekly_best",
+            &[
+                ("floors", &best.floors_cleared),
+                ("total", &CHALLENGE_FLOORS),
+            ],
+        ),
+        None => loc.get("leaderboard.weekly_unplayed").to_string(),
+    });
+    if let Some(best) = stats.weekly_best(challenge.week) {
+        let trace = &best.trace;
+        if let Some(hp) = trace.final_floor_hp() {
+            lines.push(loc.format(
+                "leaderboard.weekly_ghost",
+                &[
+                    ("time", &format!("{:.1}", trace.total_seconds())),
+                    ("floors", &trace.splits.len()),
+                    ("hp", &hp),
+                ],
+            ));
+        }
+    }
+    if stats.weekly_reward_claimed(challenge.week) {
+        lines.push(loc.get("leaderboard.weekly_claimed").to_string());
+    }
+    lines
+}
+
+fn spawn_leaderboard(
+    mut commands: Commands,
+    stats: Res<GameStats>,
+    loc: Res<Localization>,
+    mut selection: ResMut<RunSelection>,
+) {
+    selection.0 = 0;
+
+    commands
+        .spawn((
+            LeaderboardRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("leaderboard.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                Text::new(loc.format("leaderboard.best", &[("depth", &stats.best_depth)])),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(BEST_COLOR),
+            ));
+
+            parent.spawn(column_node(8.0)).with_children(|list| {
+                if stats.endless_records.is_empty() {
+                    list.spawn((
+                        Text::new(loc.get("leaderboard.empty")),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                }
+                for (place, record) in stats.endless_records.iter().enumerate() {
+                    let weapon = match &record.weapon {
+                        Some(weapon) => weapon.clone(),
+                        None => loc.get("leaderboard.unarmed").to_string(),
+                    };
+                    list.spawn((
+                        Text::new(loc.format(
+                            "leaderboard.row",
+                            &[
+                                ("place", &(place + 1)),
+                                ("depth", &record.depth),
+                                ("name", &record.name),
+                                ("level", &record.level),
+                                ("weapon", &weapon),
+                                ("seed", &record.seed),
+                            ],
+                        )),
+                        TextFont {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                Text::new(weekly_lines(&loc, &stats).join("\n")),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(WEEKLY_COLOR),
+            ));
+
+            parent.spawn(column_node(8.0)).with_children(|options| {
+                for (index, key) in RUN_OPTIONS.iter().enumerate() {
+                    options.spawn((
+                        RunOption(index),
+                        Text::new(loc.get(key)),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                Text::new(loc.get("leaderboard.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_leaderboard_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<RunSelection>,
+    mut endless_writer: MessageWriter<StartEndlessRun>,
+    mut weekly_writer: MessageWriter<StartWeeklyChallenge>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = RUN_OPTIONS.len();
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Select if selection.0 == 0 => {
+                endless_writer.write(StartEndlessRun);
+            }
+            GameAction::Select => {
+                weekly_writer.write(StartWeeklyChallenge);
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenLeaderboard => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_run_options(
+    selection: Res<RunSelection>,
+    palette: Res<Palette>,
+    mut options: Query<(&RunOption, &mut TextColor)>,
+    added: Query<(), Added<RunOption>>,
+) {
+    if !selection.is_changed() && added.is_empty() {
+        return;
+    }
+    for (option, mut color) in &mut options {
+        color.0 = if option.0 == selection.0 {
+            palette.selection
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+fn despawn_leaderboard(mut commands: Commands, root: Query<Entity, With<LeaderboardRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
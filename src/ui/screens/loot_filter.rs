@@ -0,0 +1,235 @@
+use bevy::prelude::*;
+
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::inventory::ItemCategory;
+use crate::item::enums::ItemQuality;
+use crate::loot::{LootFilter, LootRule, FILTERED_CATEGORIES};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::{column_node, Palette};
+
+const NORMAL_COLOR: Color = Color::WHITE;
+
+pub struct LootFilterScreenPlugin;
+
+impl Plugin for LootFilterScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LootFilterSelection>()
+            .add_systems(OnEnter(AppState::LootFilter), spawn_loot_filter_screen)
+            .add_systems(OnExit(AppState::LootFilter), despawn_loot_filter_screen)
+            .add_systems(
+                Update,
+                (
+                    handle_loot_filter_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_loot_filter_rows
+                        .run_if(
+                            resource_changed::<LootFilterSelection>
+                                .or(resource_changed::<LootFilter>),
+                        )
+                        .in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::LootFilter)),
+            );
+    }
+}
+
+/// A line of the settings screen: the rule for a category or for a quality, or the
+/// store's junk threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterRow {
+    Category(ItemCategory),
+    Quality(ItemQuality),
+    JunkThreshold,
+}
+
+impl FilterRow {
+    fn all() -> Vec<FilterRow> {
+        FILTERED_CATEGORIES
+            .into_iter()
+            .map(FilterRow::Category)
+            .chain(ItemQuality::ALL.into_iter().map(FilterRow::Quality))
+            .chain([FilterRow::JunkThreshold])
+            .collect()
+    }
+
+    /// Steps the row's setting forwards or backwards through its options.
+    fn cycle(self, filter: &mut LootFilter, forward: bool) {
+        let step = |rule: LootRule| if forward { rule.next() } else { rule.prev() };
+        match self {
+            FilterRow::Category(category) => {
+                filter.set_category_rule(category, step(filter.category_rule(category)));
+            }
+            FilterRow::Quality(quality) => {
+                filter.set_quality_rule(quality, step(filter.quality_rule(quality)));
+            }
+            FilterRow::JunkThreshold => {
+                let count = ItemQuality::ALL.len();
+                let index = ItemQuality::ALL
+                    .iter()
+                    .position(|q| *q == filter.junk_threshold())
+                    .unwrap_or(0);
+                let index = (if forward { index + 1 } else { index + count - 1 }) % count;
+                filter.set_junk_threshold(ItemQuality::ALL[index]);
+            }
+        }
+    }
+
+    fn text(self, filter: &LootFilter, loc: &Localization) -> String {
+        let (label, rule) = match self {
+            FilterRow::Category(category) => (
+                loc.get(category.label_key()).to_string(),
+                filter.category_rule(category),
+            ),
+            FilterRow::Quality(quality) => (
+                loc.format("loot_filter.quality", &[("quality", &quality.display_name())]),
+                filter.quality_rule(quality),
+            ),
+            FilterRow::JunkThreshold => {
+                return loc.format(
+                    "loot_filter.junk_threshold",
+                    &[("quality", &filter.junk_threshold().display_name())],
+                );
+            }
+        };
+        loc.format(
+            "loot_filter.row",
+            &[("label", &label), ("rule", &loc.get(rule.label_key()))],
+        )
+    }
+}
+
+/// Index of the highlighted row in `FilterRow::all()`.
+#[derive(Resource, Default)]
+struct LootFilterSelection(usize);
+
+#[derive(Component)]
+struct LootFilterRoot;
+
+#[derive(Component)]
+struct LootFilterRowText(FilterRow);
+
+fn spawn_loot_filter_screen(
+    mut commands: Commands,
+    filter: Res<LootFilter>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut selection: ResMut<LootFilterSelection>,
+) {
+    selection.0 = 0;
+
+    commands
+        .spawn((
+            LootFilterRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("loot_filter.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(palette.selection),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("loot_filter.description")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+
+            parent.spawn(column_node(8.0)).with_children(|list| {
+                for row in FilterRow::all() {
+                    list.spawn((
+                        LootFilterRowText(row),
+                        Text::new(row.text(&filter, &loc)),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(NORMAL_COLOR),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                Text::new(loc.get("loot_filter.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+fn handle_loot_filter_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<LootFilterSelection>,
+    mut filter: ResMut<LootFilter>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let rows = FilterRow::all();
+    let count = rows.len();
+
+    for action in action_reader.read() {
+        let row = rows[selection.0];
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Right) | GameAction::Select => {
+                row.cycle(&mut filter, true);
+            }
+            GameAction::Navigate(NavigationDirection::Left) => row.cycle(&mut filter, false),
+            GameAction::Back | GameAction::OpenLootFilter => {
+                state_requests.write(StateTransitionRequest::Menu);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_loot_filter_rows(
+    selection: Res<LootFilterSelection>,
+    filter: Res<LootFilter>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut rows: Query<(&LootFilterRowText, &mut Text, &mut TextColor)>,
+) {
+    let selected = FilterRow::all()[selection.0];
+    for (row, mut text, mut color) in &mut rows {
+        **text = row.0.text(&filter, &loc);
+        *color = TextColor(if row.0 == selected {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        });
+    }
+}
+
+fn despawn_loot_filter_screen(
+    mut commands: Commands,
+    root: Query<Entity, With<LootFilterRoot>>,
+) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
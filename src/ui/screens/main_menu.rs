@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
 use crate::assets::{GameFonts, GameSprites, SpriteSheetKey, UiAllSlice};
+use crate::i18n::Localization;
 use crate::input::{GameAction, NavigationDirection};
-use crate::states::{AppState, StateTransitionRequest};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::Announce;
 
 pub struct MainMenuPlugin;
 
@@ -14,11 +16,14 @@ impl Plugin for MainMenuPlugin {
             .add_systems(
                 Update,
                 (
-                    handle_menu_navigation,
-                    handle_menu_selection,
-                    update_sprite_menu_items,
-                    populate_randr_title,
-                    populate_menu_background,
+                    (handle_menu_navigation, handle_menu_selection).in_set(ScreenSet::Input),
+                    (
+                        update_sprite_menu_items,
+                        announce_menu_selection.run_if(resource_changed::<MenuSelection>),
+                        populate_randr_title,
+                        populate_menu_background,
+                    )
+                        .in_set(ScreenSet::Render),
                 )
                     .run_if(in_state(AppState::Menu)),
             );
@@ -32,6 +37,8 @@ pub struct MenuSelection {
 
 impl MenuSelection {
     const MENU_ITEMS: usize = 3;
+    const LABEL_KEYS: [&'static str; Self::MENU_ITEMS] =
+        ["main_menu.play", "main_menu.profile", "main_menu.quit"];
 
     pub fn up(&mut self) {
         if self.index > 0 {
@@ -44,6 +51,11 @@ impl MenuSelection {
             self.index += 1;
         }
     }
+
+    /// Whether the highlighted entry quits the game.
+    pub fn is_quit(&self) -> bool {
+        self.index == Self::MENU_ITEMS - 1
+    }
 }
 
 #[derive(Component)]
@@ -182,6 +194,17 @@ fn reset_menu_selection(mut menu_selection: ResMut<MenuSelection>) {
     menu_selection.index = 0;
 }
 
+/// The buttons are sprites with their names drawn in, so the name is spelled out here.
+fn announce_menu_selection(
+    menu_selection: Res<MenuSelection>,
+    loc: Res<Localization>,
+    mut announce: MessageWriter<Announce>,
+) {
+    if let Some(key) = MenuSelection::LABEL_KEYS.get(menu_selection.index) {
+        announce.write(Announce::new(loc.get(key)));
+    }
+}
+
 fn update_sprite_menu_items(
     mut commands: Commands,
     menu_selection: Res<MenuSelection>,
@@ -4,4 +4,7 @@ mod render;
 mod state;
 
 pub use plugin::MerchantModalPlugin;
-pub use state::{MerchantModal, MerchantPlayerGrid, MerchantStock, MerchantStockGrid};
+pub use state::{
+    MerchantModal, MerchantPlayerGrid, MerchantStock, MerchantStockGrid, SellJunkDialog,
+    TownStocks,
+};
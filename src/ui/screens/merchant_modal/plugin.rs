@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 
-use crate::input::{navigate_merchant_grid, process_transaction};
+use crate::game::GambleOutcome;
+use crate::input::{
+    handle_sell_junk_dialog, navigate_merchant_grid, open_sell_junk_dialog, open_storage,
+    process_banking, process_transaction, request_gamble,
+};
+use crate::item::ItemRegistry;
+use crate::states::ScreenSet;
 use crate::ui::focus::{tab_toggle_system, FocusPanel};
 use crate::ui::modal_registry::{modal_close_system, RegisterModalExt};
 use crate::ui::screens::modal::in_merchant_modal;
@@ -8,23 +14,41 @@ use crate::ui::widgets::{update_detail_pane_source, ItemDetailPane, ItemGridSele
 use crate::ui::FocusState;
 
 use super::render::{
-    populate_merchant_detail_pane_content, sync_merchant_player_grid, sync_merchant_stock_grid,
+    animate_gamble_reveal, populate_merchant_detail_pane_content, start_gamble_reveal,
+    sync_merchant_player_grid, sync_merchant_stock_grid, update_sell_junk_text,
+};
+use super::state::{
+    GambleReveal, MerchantDetailPane, MerchantModal, MerchantStock, SellJunkDialog,
+    SellJunkDialogText, TownStocks,
 };
-use super::state::{MerchantDetailPane, MerchantModal, MerchantStock};
 
 pub struct MerchantModalPlugin;
 
 impl Plugin for MerchantModalPlugin {
     fn build(&self, app: &mut App) {
         app.register_modal::<MerchantModal>()
+            .init_resource::<TownStocks>()
             .add_systems(
                 Update,
                 (
-                    modal_close_system::<MerchantModal>,
+                    modal_close_system::<MerchantModal>.in_set(ScreenSet::Input),
                     (
                         tab_toggle_system(FocusPanel::MerchantStock, FocusPanel::PlayerInventory),
                         navigate_merchant_grid,
                         process_transaction,
+                        process_banking,
+                        open_storage,
+                        open_sell_junk_dialog,
+                        handle_sell_junk_dialog.run_if(resource_exists::<SellJunkDialog>),
+                        request_gamble,
+                    )
+                        .in_set(ScreenSet::Input)
+                        .run_if(in_merchant_modal),
+                    (
+                        start_gamble_reveal.run_if(on_message::<GambleOutcome>),
+                        animate_gamble_reveal.run_if(
+                            resource_exists::<GambleReveal>.and(resource_exists::<ItemRegistry>),
+                        ),
                         sync_merchant_stock_grid.run_if(
                             resource_exists::<MerchantStock>
                                 .and(resource_changed::<MerchantStock>),
@@ -40,7 +64,13 @@ impl Plugin for MerchantModalPlugin {
                                 .and(resource_changed::<MerchantStock>)
                                 .or(any_match_filter::<Changed<ItemDetailPane>>),
                         ),
+                        update_sell_junk_text.run_if(
+                            resource_exists_and_changed::<SellJunkDialog>
+                                .or(resource_removed::<SellJunkDialog>)
+                                .or(any_match_filter::<Added<SellJunkDialogText>>),
+                        ),
                     )
+                        .in_set(ScreenSet::Render)
                         .run_if(in_merchant_modal),
                 ),
             );
@@ -1,18 +1,43 @@
 use bevy::prelude::*;
 
-use crate::economy::WorthGold;
+use crate::economy::{CommodityMarket, Haggle};
+use crate::faction::Reputation;
+use crate::game::{GambleLedger, GambleOutcome};
+use crate::i18n::Localization;
 use crate::inventory::{Inventory, ManagesEquipment, ManagesItems};
+use crate::item::enums::ItemQuality;
+use crate::item::ItemRegistry;
+use crate::loot::LootFilter;
 use crate::player::PlayerMarker;
+use crate::stats::StatSheet;
 use crate::ui::focus::FocusPanel;
 use crate::ui::modal_content_row;
 use crate::ui::InfoPanelSource;
 use crate::ui::widgets::{
-    ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent, ItemGrid, ItemGridEntry,
+    Column, DetailPaneQueries, ItemDetailDisplay, ItemDetailPane, ItemGrid, ItemGridEntry,
     ItemGridFocusPanel, ItemGridSelection, PriceDisplay,
 };
-use crate::ui::{FocusState, Modal, ModalBackground, SpawnModalExt};
+use crate::ui::{FocusState, Modal, ModalBackground, Palette, SpawnModalExt};
 
-use super::state::{MerchantModalRoot, MerchantPlayerGrid, MerchantStock, MerchantStockGrid};
+use super::state::{
+    GambleReveal, GambleRevealText, MerchantModalRoot, MerchantPlayerGrid, MerchantStock,
+    MerchantStockGrid, SellJunkDialog, SellJunkDialogText,
+};
+
+const SELL_JUNK_TEXT_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
+/// How long a gamble takes to reveal.
+const REVEAL_SECS: f32 = 1.5;
+
+/// How often the reveal line switches quality while it spins.
+const REVEAL_FLICKERS_PER_SEC: f32 = 12.0;
+
+/// Height of a price history bar at list price.
+const SPARKLINE_HEIGHT: f32 = 24.0;
+const SPARKLINE_BAR_WIDTH: f32 = 4.0;
+const SPARKLINE_LABEL_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+const PRICE_HIGH_COLOR: Color = Color::srgb(0.4, 0.8, 0.4);
+const PRICE_LOW_COLOR: Color = Color::srgb(0.9, 0.4, 0.3);
 
 pub fn sync_merchant_stock_grid(
     stock: Res<MerchantStock>,
@@ -45,9 +70,11 @@ pub fn get_merchant_stock_entries(stock: &MerchantStock) -> Vec<ItemGridEntry> {
         .iter()
         .filter_map(|store_item| {
             store_item.display_item().map(|item| ItemGridEntry {
-                sprite_sheet_key: item.sprite.sheet_key,
+                sprite_sheet_key: item.sprite.sheet_key.into(),
                 sprite_name: item.sprite.name.clone(),
                 quantity: store_item.quantity() as u32,
+                is_favorite: false,
+                is_new: false,
             })
         })
         .collect()
@@ -75,27 +102,47 @@ pub fn spawn_merchant_modal_impl(
                 e.insert(MerchantModalRoot);
             }))
             .content(Box::new(move |c| {
-                c.spawn(modal_content_row()).with_children(|row| {
-                    row.spawn((
-                        MerchantStockGrid,
-                        ItemGridFocusPanel(FocusPanel::MerchantStock),
-                        ItemGrid {
-                            items: stock_entries,
-                            grid_size: 5,
+                c.spawn(Column::new().gap(8.0)).with_children(|col| {
+                    col.spawn((
+                        SellJunkDialogText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
                         },
-                        ItemGridSelection::default(),
+                        TextColor(SELL_JUNK_TEXT_COLOR),
                     ));
-                    row.spawn((
-                        MerchantPlayerGrid,
-                        ItemGridFocusPanel(FocusPanel::PlayerInventory),
-                        ItemGrid {
-                            items: player_entries,
-                            grid_size: 5,
+                    col.spawn((
+                        GambleRevealText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
                         },
-                        ItemGridSelection::default(),
+                        TextColor(Color::WHITE),
                     ));
-                    row.spawn(ItemDetailPane {
-                        source: InfoPanelSource::Store { selected_index: 0 },
+                    col.spawn(modal_content_row()).with_children(|row| {
+                        row.spawn((
+                            MerchantStockGrid,
+                            ItemGridFocusPanel(FocusPanel::MerchantStock),
+                            ItemGrid {
+                                items: stock_entries,
+                                grid_size: 5,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn((
+                            MerchantPlayerGrid,
+                            ItemGridFocusPanel(FocusPanel::PlayerInventory),
+                            ItemGrid {
+                                items: player_entries,
+                                grid_size: 5,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn(ItemDetailPane {
+                            source: InfoPanelSource::Store { selected_index: 0 },
+                        });
                     });
                 });
             }))
@@ -103,21 +150,104 @@ pub fn spawn_merchant_modal_impl(
     );
 }
 
+/// Shows the pending sell-junk totals, or clears the line once the dialog closes.
+pub fn update_sell_junk_text(
+    dialog: Option<Res<SellJunkDialog>>,
+    filter: Res<LootFilter>,
+    loc: Res<Localization>,
+    mut text: Query<&mut Text, With<SellJunkDialogText>>,
+) {
+    let Ok(mut text) = text.single_mut() else {
+        return;
+    };
+    **text = match dialog {
+        Some(dialog) => loc.format(
+            "merchant.sell_junk_confirm",
+            &[
+                ("count", &dialog.count),
+                ("gold", &dialog.gold),
+                ("quality", &filter.junk_threshold().display_name()),
+            ],
+        ),
+        None => String::new(),
+    };
+}
+
+/// Starts the reveal for the latest gamble, replacing any reveal still spinning.
+pub fn start_gamble_reveal(mut commands: Commands, mut outcomes: MessageReader<GambleOutcome>) {
+    if let Some(outcome) = outcomes.read().last() {
+        commands.insert_resource(GambleReveal {
+            outcome: outcome.clone(),
+            timer: Timer::from_seconds(REVEAL_SECS, TimerMode::Once),
+        });
+    }
+}
+
+/// Spins the reveal line through qualities, then settles on the item the player won.
+pub fn animate_gamble_reveal(
+    time: Res<Time>,
+    mut reveal: ResMut<GambleReveal>,
+    ledger: Res<GambleLedger>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut text: Query<(&mut Text, &mut TextColor), With<GambleRevealText>>,
+) {
+    if reveal.timer.elapsed() >= reveal.timer.duration() {
+        return;
+    }
+    let Ok((mut text, mut color)) = text.single_mut() else {
+        return;
+    };
+
+    if !reveal.timer.tick(time.delta()).just_finished() {
+        let spin = (reveal.timer.elapsed_secs() * REVEAL_FLICKERS_PER_SEC) as usize;
+        let quality = ItemQuality::ALL[spin % ItemQuality::ALL.len()];
+        **text = loc.format("gamble.revealing", &[("quality", &quality.display_name())]);
+        color.0 = palette.quality(quality);
+        return;
+    }
+
+    let outcome = &reveal.outcome;
+    let name = loc.item_name(outcome.item_id, &registry.get(outcome.item_id).name);
+    **text = loc.format(
+        "gamble.revealed",
+        &[
+            ("item", &name),
+            ("quality", &outcome.quality.display_name()),
+            ("value", &outcome.value),
+            ("net", &ledger.net()),
+        ],
+    );
+    color.0 = palette.quality(outcome.quality);
+}
+
 pub fn populate_merchant_detail_pane_content(
     mut commands: Commands,
     stock: Option<Res<MerchantStock>>,
-    player: Query<&Inventory, With<PlayerMarker>>,
-    panes: Query<Ref<ItemDetailPane>>,
-    content_query: Query<(Entity, Option<&Children>), With<ItemDetailPaneContent>>,
+    player: Query<(&Inventory, Ref<StatSheet>), With<PlayerMarker>>,
+    reputation: Res<Reputation>,
+    market: Res<CommodityMarket>,
+    loc: Res<Localization>,
+    detail_panes: DetailPaneQueries,
 ) {
+    let DetailPaneQueries {
+        panes,
+        content: content_query,
+    } = detail_panes;
     let Some(stock) = stock else {
         return;
     };
-    let Ok(inventory) = player.single() else {
+    let Ok((inventory, stats)) = player.single() else {
         return;
     };
 
-    let data_changed = stock.is_changed();
+    // Prices follow Charisma, standing and the material market, so redraw when any moves.
+    let data_changed = stock.is_changed()
+        || stats.is_changed()
+        || reputation.is_changed()
+        || market.is_changed();
+    let haggle = Haggle::for_player(&stats, inventory, &reputation);
 
     for pane in &panes {
         if !pane.is_changed() && !data_changed {
@@ -139,7 +269,7 @@ pub fn populate_merchant_detail_pane_content(
                 stock.items.get(selected_index).and_then(|store_item| {
                     store_item.display_item().map(|item| {
                         let qty = store_item.quantity() as u32;
-                        let price = PriceDisplay::Buy(item.purchase_price());
+                        let price = PriceDisplay::Buy(stock.buy_price(haggle, item));
                         (item, qty, price)
                     })
                 })
@@ -148,7 +278,8 @@ pub fn populate_merchant_detail_pane_content(
                 .get_inventory_items()
                 .get(selected_index)
                 .map(|inv_item| {
-                    let price = PriceDisplay::Sell(inv_item.item.sell_price());
+                    let price = market.adjust(&inv_item.item, haggle.sell_price(&inv_item.item));
+                    let price = PriceDisplay::Sell(price);
                     (&inv_item.item, inv_item.quantity, price)
                 }),
             _ => None,
@@ -158,7 +289,7 @@ pub fn populate_merchant_detail_pane_content(
             continue;
         };
 
-        let comparison = inventory.get_comparison_stats(item);
+        let comparison = inventory.compare_to_equipped(item);
 
         commands.entity(content_entity).with_children(|parent| {
             parent.spawn(
@@ -168,6 +299,43 @@ pub fn populate_merchant_detail_pane_content(
                     .maybe_comparison(comparison)
                     .build(),
             );
+            if CommodityMarket::is_commodity(item) {
+                spawn_price_history(parent, &market.history(item.item_id), &loc);
+            }
         });
     }
 }
+
+/// A label with today's material price and a bar per day of its recent history.
+fn spawn_price_history(parent: &mut ChildSpawnerCommands, history: &[i32], loc: &Localization) {
+    let today = history.last().copied().unwrap_or(100);
+    parent.spawn((
+        Text::new(loc.format("merchant.market_price", &[("percent", &today)])),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(SPARKLINE_LABEL_COLOR),
+    ));
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexEnd,
+            column_gap: Val::Px(1.0),
+            height: Val::Px(SPARKLINE_HEIGHT),
+            ..default()
+        })
+        .with_children(|bars| {
+            for &percent in history {
+                let color = if percent >= 100 { PRICE_HIGH_COLOR } else { PRICE_LOW_COLOR };
+                bars.spawn((
+                    Node {
+                        width: Val::Px(SPARKLINE_BAR_WIDTH),
+                        height: Val::Px(SPARKLINE_HEIGHT * percent as f32 / 100.0),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+            }
+        });
+}
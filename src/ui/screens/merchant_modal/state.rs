@@ -1,8 +1,16 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
 use rand::Rng;
 
+use crate::economy::{Haggle, WorthGold};
+use crate::faction::Reputation;
+use crate::game::GambleOutcome;
 use crate::item::{ItemId, ItemRegistry};
 use crate::location::store::StoreItem;
+use crate::location::TownId;
 use crate::ui::focus::FocusPanel;
 use crate::ui::modal_registry::RegisteredModal;
 use crate::ui::screens::modal::ModalType;
@@ -43,66 +51,52 @@ impl DetailPaneContext for MerchantDetailPane {
     }
 }
 
+/// Rare goods a traveling merchant can carry. None of them are sold by town merchants.
+const TRAVELING_POOL: &[(ItemId, RangeInclusive<i32>)] = &[
+    (ItemId::ImbaRing, 1..=1),
+    (ItemId::BonkStick, 1..=1),
+    (ItemId::QualityUpgradeStone, 1..=3),
+    (ItemId::WardingRune, 1..=2),
+    (ItemId::BlueCrystal, 1..=3),
+    (ItemId::RedCrystal, 1..=3),
+    (ItemId::GreenCrystal, 1..=3),
+    (ItemId::WhiteCrystal, 1..=2),
+    (ItemId::OrangeCrystal, 1..=2),
+    (ItemId::YellowCrystal, 1..=2),
+];
+
+/// How many of [`TRAVELING_POOL`] a traveling merchant carries at once.
+const TRAVELING_STOCK_SIZE: RangeInclusive<usize> = 3..=5;
+
+/// Markup a traveling merchant charges over list price, in percent.
+pub const TRAVELING_PREMIUM_PERCENT: i32 = 50;
+
 /// Resource holding the merchant's current stock.
 #[derive(Resource)]
 pub struct MerchantStock {
     pub items: Vec<StoreItem>,
+    /// Markup on every purchase, in percent, applied after haggling.
+    pub premium_percent: i32,
+    /// The town and day a town merchant put this stock out. Traveling merchants' stock
+    /// isn't kept between visits.
+    pub restocked: Option<(TownId, u32)>,
 }
 
 impl MerchantStock {
-    /// Generate random merchant stock from a pool of items.
-    pub fn generate(registry: &ItemRegistry) -> Self {
+    /// Generate random merchant stock from the town's item pool, followed by the faction
+    /// wares the player's reputation has unlocked. An upgraded store adds `extra_slots`.
+    pub fn generate(
+        registry: &ItemRegistry,
+        town: TownId,
+        reputation: &Reputation,
+        day: u32,
+        extra_slots: usize,
+    ) -> Self {
         let mut rng = rand::thread_rng();
+        let pool = &town.spec().merchant_pool;
 
-        // Pool of items the merchant can sell
-        let pool: Vec<(ItemId, i32)> = vec![
-            // Consumables
-            (ItemId::BasicHPPotion, rng.gen_range(3..=8)),
-            // Basic weapons
-            (ItemId::Sword, 1),
-            (ItemId::Dagger, 1),
-            (ItemId::GoldSword, 1),
-            (ItemId::IronSword, 1),
-            (ItemId::CopperSword, 1),
-            // Shields
-            (ItemId::BasicShield, 1),
-            // Copper armor
-            (ItemId::IronHelmet, 1),
-            (ItemId::IronChestplate, 1),
-            (ItemId::IronGauntlets, 1),
-            (ItemId::IronGreaves, 1),
-            (ItemId::IronLeggings, 1),
-            // Tin armor
-            (ItemId::GoldHelmet, 1),
-            (ItemId::GoldChestplate, 1),
-            (ItemId::GoldGauntlets, 1),
-            (ItemId::GoldGreaves, 1),
-            (ItemId::GoldLeggings, 1),
-            // Bronze armor
-            (ItemId::CopperHelmet, 1),
-            (ItemId::CopperChestplate, 1),
-            (ItemId::CopperGauntlets, 1),
-            (ItemId::CopperGreaves, 1),
-            (ItemId::CopperLeggings, 1),
-            // Tools
-            (ItemId::CopperPickaxe, 1),
-            // Accessories
-            (ItemId::GoldRing, 1),
-            // Ores
-            (ItemId::IronOre, rng.gen_range(5..=15)),
-            (ItemId::GoldOre, rng.gen_range(5..=15)),
-            (ItemId::Coal, rng.gen_range(5..=15)),
-            // Ingots
-            (ItemId::IronIngot, rng.gen_range(2..=5)),
-            (ItemId::GoldIngot, rng.gen_range(2..=5)),
-            (ItemId::CopperIngot, rng.gen_range(1..=3)),
-            // Materials
-            (ItemId::Cowhide, rng.gen_range(3..=8)),
-            (ItemId::SlimeGel, rng.gen_range(3..=8)),
-        ];
-
-        // Randomly select 8-12 items from the pool
-        let num_items = rng.gen_range(8..=12).min(pool.len());
+        // Randomly select 8-12 items from the pool, plus the store's extra slots
+        let num_items = (rng.gen_range(8..=12) + extra_slots).min(pool.len());
         let mut selected_indices: Vec<usize> = (0..pool.len()).collect();
 
         // Shuffle and take first num_items
@@ -111,19 +105,93 @@ impl MerchantStock {
             selected_indices.swap(i, swap_idx);
         }
 
-        let items = selected_indices
+        let mut items: Vec<StoreItem> = selected_indices
             .into_iter()
             .take(num_items)
             .map(|idx| {
-                let (item_id, quantity) = pool[idx];
-                StoreItem::new(item_id, quantity, registry)
+                let (item_id, quantity) = &pool[idx];
+                StoreItem::new(*item_id, rng.gen_range(quantity.clone()), registry)
             })
             .collect();
 
-        Self { items }
+        items.extend(reputation.unlocked_wares().map(|(item_id, quantity)| {
+            StoreItem::new(item_id, rng.gen_range(quantity), registry)
+        }));
+
+        Self {
+            items,
+            premium_percent: 0,
+            restocked: Some((town, day)),
+        }
+    }
+
+    /// Stock for a traveling merchant met in the dungeon: a few rare goods at a premium.
+    pub fn generate_traveling(registry: &ItemRegistry, rng: &mut impl Rng) -> Self {
+        let count = rng.gen_range(TRAVELING_STOCK_SIZE);
+        let items = TRAVELING_POOL
+            .choose_multiple(rng, count)
+            .map(|(item_id, quantity)| {
+                StoreItem::new(*item_id, rng.gen_range(quantity.clone()), registry)
+            })
+            .collect();
+
+        Self {
+            items,
+            premium_percent: TRAVELING_PREMIUM_PERCENT,
+            restocked: None,
+        }
+    }
+
+    /// What this merchant asks for `item` once haggling is taken into account.
+    pub fn buy_price(&self, haggle: Haggle, item: &impl WorthGold) -> i32 {
+        haggle.buy_price(item) * (100 + self.premium_percent) / 100
     }
 }
 
+/// Town merchants' stock between visits. Whatever the player left on the shelves stays
+/// there until the merchant restocks the next morning.
+#[derive(Resource, Default)]
+pub struct TownStocks(HashMap<TownId, MerchantStock>);
+
+impl TownStocks {
+    /// Takes `town`'s stock off the shelf, unless it was put out before `today`.
+    pub fn take_fresh(&mut self, town: TownId, today: u32) -> Option<MerchantStock> {
+        self.0
+            .remove(&town)
+            .filter(|stock| stock.restocked.is_some_and(|(_, day)| day == today))
+    }
+
+    /// Keeps a town merchant's stock for the next visit.
+    pub fn shelve(&mut self, stock: MerchantStock) {
+        if let Some((town, _)) = stock.restocked {
+            self.0.insert(town, stock);
+        }
+    }
+}
+
+/// Marker for the sell-junk confirmation line, empty while no confirmation is pending.
+#[derive(Component)]
+pub struct SellJunkDialogText;
+
+/// Present while the player is confirming a "sell all junk" order.
+#[derive(Resource, Debug)]
+pub struct SellJunkDialog {
+    pub count: u32,
+    pub gold: i32,
+}
+
+/// Marker for the gamble reveal line, empty until the player gambles.
+#[derive(Component)]
+pub struct GambleRevealText;
+
+/// A mystery item being unwrapped. The line flickers through qualities until the timer
+/// runs out, then shows what the player got.
+#[derive(Resource, Debug)]
+pub struct GambleReveal {
+    pub outcome: GambleOutcome,
+    pub timer: Timer,
+}
+
 /// Type-safe handle for the merchant modal.
 ///
 /// Used with `ModalCommands`:
@@ -142,7 +210,14 @@ impl RegisteredModal for MerchantModal {
     }
 
     fn cleanup(world: &mut World) {
-        world.remove_resource::<MerchantStock>();
+        let stock = world.remove_resource::<MerchantStock>();
+        if let (Some(stock), Some(mut town_stocks)) =
+            (stock, world.get_resource_mut::<TownStocks>())
+        {
+            town_stocks.shelve(stock);
+        }
+        world.remove_resource::<SellJunkDialog>();
+        world.remove_resource::<GambleReveal>();
     }
 }
 
@@ -156,3 +231,85 @@ fn do_spawn_merchant_modal(
     };
     super::render::spawn_merchant_modal_impl(commands, &stock, inventory);
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    struct Priced(i32);
+
+    impl WorthGold for Priced {
+        fn gold_value(&self) -> i32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn traveling_goods_are_not_sold_in_town() {
+        for town in TownId::ALL {
+            for (item_id, _) in &town.spec().merchant_pool {
+                assert!(
+                    TRAVELING_POOL.iter().all(|(rare, _)| rare != item_id),
+                    "{item_id:?} is sold in {town:?}"
+                );
+            }
+        }
+        assert!(TRAVELING_POOL.len() >= *TRAVELING_STOCK_SIZE.end());
+    }
+
+    #[test]
+    fn premium_applies_on_top_of_haggling() {
+        let traveling = MerchantStock {
+            items: Vec::new(),
+            premium_percent: TRAVELING_PREMIUM_PERCENT,
+            restocked: None,
+        };
+        let town = MerchantStock {
+            items: Vec::new(),
+            premium_percent: 0,
+            restocked: Some((TownId::ALL[0], 0)),
+        };
+        let haggle = Haggle::new(10, crate::faction::FactionRank::Stranger);
+
+        assert_eq!(town.buy_price(haggle, &Priced(100)), 90);
+        assert_eq!(traveling.buy_price(haggle, &Priced(100)), 135);
+    }
+
+    #[test]
+    fn traveling_stock_is_rare_goods_at_a_premium() {
+        let registry = ItemRegistry::from_assets();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let stock = MerchantStock::generate_traveling(&registry, &mut rng);
+        let haggle = Haggle::new(0, crate::faction::FactionRank::Stranger);
+
+        assert!(TRAVELING_STOCK_SIZE.contains(&stock.items.len()));
+        assert!(stock.restocked.is_none());
+        for store_item in &stock.items {
+            let item = registry.spawn(store_item.item_id);
+            assert!(stock.buy_price(haggle, &item) > haggle.buy_price(&item));
+        }
+    }
+
+    #[test]
+    fn town_stock_keeps_until_the_next_morning() {
+        let town = TownId::ALL[0];
+        let stock = |restocked| MerchantStock {
+            items: Vec::new(),
+            premium_percent: 0,
+            restocked,
+        };
+        let mut shelves = TownStocks::default();
+
+        shelves.shelve(stock(Some((town, 3))));
+        assert!(shelves.take_fresh(town, 3).is_some());
+        assert!(shelves.take_fresh(town, 3).is_none());
+
+        shelves.shelve(stock(Some((town, 3))));
+        assert!(shelves.take_fresh(town, 4).is_none());
+
+        shelves.shelve(stock(None));
+        assert!(shelves.take_fresh(town, 4).is_none());
+    }
+}
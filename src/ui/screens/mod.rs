@@ -1,19 +1,49 @@
 pub mod anvil_modal;
+mod auction_house;
+pub mod away_modal;
+mod contracts;
+pub mod dialogue_modal;
 mod dungeon;
+mod event_shop;
+pub mod evolution_modal;
 pub mod forge_modal;
+mod guild;
 pub mod health_bar;
+pub mod help_modal;
 pub mod inventory_modal;
 mod keybinds;
+mod leaderboard;
+mod loot_filter;
 mod main_menu;
 pub mod merchant_modal;
 pub mod modal;
+mod mods;
 pub mod monster_compendium;
 mod profile;
+mod ranch;
+mod roster;
+mod run_planner;
+pub mod shrine_modal;
 pub mod skills_modal;
+mod spellcraft;
+pub mod storage_modal;
+mod town_upgrades;
+mod trophy_room;
+mod workers;
+mod word_index;
+mod world_map;
 
 pub use anvil_modal::AnvilModalPlugin;
+pub use auction_house::AuctionHouseScreenPlugin;
+pub use away_modal::AwayModalPlugin;
+pub use contracts::ContractsScreenPlugin;
+pub use dialogue_modal::DialogueModalPlugin;
 pub use dungeon::{DungeonPlayer, DungeonScreenPlugin, FacingDirection, FloorRoot};
+pub use event_shop::EventShopPlugin;
+pub use evolution_modal::EvolutionModalPlugin;
 pub use forge_modal::ForgeModalPlugin;
+pub use guild::GuildScreenPlugin;
+pub use help_modal::HelpModalPlugin;
 pub use monster_compendium::MonsterCompendiumPlugin;
 pub use health_bar::{
     init_sprite_health_bars, update_health_bar, update_sprite_health_bar_visuals, HealthBar,
@@ -22,11 +52,25 @@ pub use health_bar::{
 };
 pub use inventory_modal::InventoryModalPlugin;
 pub use keybinds::KeybindsPlugin;
-pub use main_menu::MainMenuPlugin;
+pub use leaderboard::LeaderboardPlugin;
+pub use loot_filter::LootFilterScreenPlugin;
+pub use main_menu::{MainMenuPlugin, MenuSelection};
 pub use merchant_modal::MerchantModalPlugin;
 pub use modal::{
     ActiveModal, CloseModal, ModalOverlayBundle, ModalPlugin, ModalType, OpenModal,
     MODAL_OVERLAY_COLOR, MODAL_OVERLAY_Z_INDEX,
 };
+pub use mods::ModsPlugin;
 pub use profile::ProfilePlugin;
+pub use ranch::RanchScreenPlugin;
+pub use roster::RosterScreenPlugin;
+pub use run_planner::RunPlannerPlugin;
+pub use shrine_modal::ShrineModalPlugin;
 pub use skills_modal::SkillsModalPlugin;
+pub use spellcraft::SpellcraftScreenPlugin;
+pub use storage_modal::StorageModalPlugin;
+pub use town_upgrades::TownUpgradesScreenPlugin;
+pub use trophy_room::TrophyRoomScreenPlugin;
+pub use workers::WorkersScreenPlugin;
+pub use word_index::WordIndexPlugin;
+pub use world_map::WorldMapPlugin;
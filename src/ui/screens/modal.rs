@@ -35,6 +35,12 @@ pub enum ModalType {
     ForgeModal,
     AnvilModal,
     SkillsModal,
+    Help,
+    Storage,
+    Shrine,
+    Evolution,
+    Away,
+    Dialogue,
 }
 
 #[derive(Component, Default)]
@@ -125,6 +131,36 @@ pub fn in_skills_modal(active_modal: Res<ActiveModal>) -> bool {
     active_modal.modal == Some(ModalType::SkillsModal)
 }
 
+/// Run condition: returns true when the help modal is active.
+pub fn in_help_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Help)
+}
+
+/// Run condition: returns true when the storage modal is active.
+pub fn in_storage_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Storage)
+}
+
+/// Run condition: returns true when the shrine modal is active.
+pub fn in_shrine_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Shrine)
+}
+
+/// Run condition: returns true when the evolution reveal is active.
+pub fn in_evolution_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Evolution)
+}
+
+/// Run condition: returns true when the "while you were away" summary is active.
+pub fn in_away_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Away)
+}
+
+/// Run condition: returns true when a conversation with an NPC is active.
+pub fn in_dialogue_modal(active_modal: Res<ActiveModal>) -> bool {
+    active_modal.modal == Some(ModalType::Dialogue)
+}
+
 /// Run condition: returns true when the monster compendium modal is active.
 pub fn in_monster_compendium_modal(active_modal: Res<ActiveModal>) -> bool {
     active_modal.modal == Some(ModalType::MonsterCompendium)
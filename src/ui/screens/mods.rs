@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::data::LoadedMods;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::column_node;
+
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Mods), spawn_mods_screen)
+            .add_systems(OnExit(AppState::Mods), despawn_mods_screen)
+            .add_systems(
+                Update,
+                handle_back_action
+                    .run_if(in_state(AppState::Mods))
+                    .in_set(ScreenSet::Input),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ModsScreenRoot;
+
+fn spawn_mods_screen(
+    mut commands: Commands,
+    loaded: Option<Res<LoadedMods>>,
+    loc: Res<Localization>,
+) {
+    let loaded = loaded.map(|l| l.clone()).unwrap_or_default();
+
+    commands
+        .spawn((
+            ModsScreenRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("mods.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.9, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+
+            parent.spawn(column_node(10.0)).with_children(|list| {
+                if loaded.mods.is_empty() {
+                    spawn_line(list, loc.get("mods.none").to_string(), Color::srgb(0.7, 0.7, 0.7));
+                }
+
+                for (order, info) in loaded.mods.iter().enumerate() {
+                    spawn_line(
+                        list,
+                        loc.format(
+                            "mods.entry",
+                            &[
+                                ("order", &(order + 1)),
+                                ("name", &info.name),
+                                ("items", &info.items),
+                                ("mobs", &info.mobs),
+                                ("recipes", &info.recipes),
+                                ("spawns", &info.spawn_tables),
+                            ],
+                        ),
+                        Color::WHITE,
+                    );
+                }
+
+                for collision in &loaded.collisions {
+                    spawn_line(
+                        list,
+                        loc.format(
+                            "mods.collision",
+                            &[
+                                ("id", &collision.id),
+                                ("previous", &collision.previous),
+                                ("replaced_by", &collision.replaced_by),
+                            ],
+                        ),
+                        Color::srgb(1.0, 0.6, 0.3),
+                    );
+                }
+            });
+
+            parent.spawn((
+                Text::new(loc.get("mods.back")),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_line(parent: &mut ChildSpawnerCommands, text: String, color: Color) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size: 22.0,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
+
+fn handle_back_action(
+    mut action_reader: MessageReader<GameAction>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        if *action == GameAction::Back {
+            state_requests.write(StateTransitionRequest::Menu);
+        }
+    }
+}
+
+fn despawn_mods_screen(mut commands: Commands, root: Query<Entity, With<ModsScreenRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
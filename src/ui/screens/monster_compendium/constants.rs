@@ -19,13 +19,20 @@ pub const RIGHT_PAGE_HEIGHT: f32 = 320.0;
 
 // Sprite dimensions
 pub const MOB_SPRITE_SIZE: f32 = 96.0;
+pub const ITEM_SPRITE_SIZE: f32 = 48.0;
 
 // Typography
 pub const MONSTER_NAME_FONT_SIZE: f32 = 14.0;
+pub const SECTION_HEADER_FONT_SIZE: f32 = 16.0;
+pub const ITEM_TITLE_FONT_SIZE: f32 = 16.0;
+
+// Item list
+pub const ITEM_LIST_VISIBLE_ROWS: usize = 15;
 
 // Colors
 pub const SELECTED_COLOR: Color = Color::srgb(0.5, 0.3, 0.1);
 pub const NORMAL_COLOR: Color = Color::srgb(0.2, 0.15, 0.1);
+pub const UNDISCOVERED_COLOR: Color = Color::srgb(0.45, 0.4, 0.35);
 
 // Stats section layout
 pub const STAT_ICON_SIZE: f32 = 16.0;
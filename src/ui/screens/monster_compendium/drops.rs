@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::assets::GameSprites;
+use crate::i18n::Localization;
 use crate::item::ItemRegistry;
 use crate::loot::definition::LootItem;
 use crate::ui::{FocusPanel, FocusState, SelectionState};
@@ -21,6 +22,7 @@ pub fn update_drops_display(
     game_sprites: Res<GameSprites>,
     mut drops_section: Query<(Entity, &mut Node, Option<&Children>), With<CompendiumDropsSection>>,
     registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
 ) {
     let Some(monsters) = monsters else { return };
     let Some(entry) = monsters.get(list_state.selected) else { return };
@@ -51,14 +53,14 @@ pub fn update_drops_display(
     }
     commands.entity(section_entity).with_children(|parent| {
         parent.spawn((
-            Text::new("Drops:"),
+            Text::new(loc.get("compendium.drops")),
             TextFont { font_size: DROP_FONT_SIZE, ..default() },
             TextColor(NORMAL_COLOR),
         ));
 
         if entry.drops.is_empty() {
             parent.spawn((
-                Text::new("No item drops"),
+                Text::new(loc.get("compendium.no_drops")),
                 TextFont { font_size: DROP_FONT_SIZE, ..default() },
                 TextColor(NORMAL_COLOR),
             ));
@@ -93,7 +95,7 @@ fn spawn_drop_row(
         spec.name, loot_item.drop_chance_percent(), quantity_str
     );
 
-    let sprite_sheet_key = spec.sprite_sheet.unwrap_or(crate::assets::SpriteSheetKey::IconItems);
+    let sprite_sheet_key = crate::assets::SpriteSheetKey::from(spec.sprite_sheet.unwrap_or_default());
 
     parent
         .spawn((
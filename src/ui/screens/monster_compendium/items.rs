@@ -0,0 +1,176 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::assets::{GameSprites, SpriteSheetKey};
+use crate::i18n::Localization;
+use crate::item::ItemRegistry;
+use crate::ui::{FocusPanel, FocusState};
+
+use super::constants::*;
+use super::state::{
+    CompendiumItemDetails, CompendiumItemPage, CompendiumItems, CompendiumMonsterDetails,
+    CompendiumMonsterPage, CompendiumSection, CompendiumViewState, ItemListItem, ItemListState,
+    ItemSourceItem, ItemSourcesState,
+};
+
+type MonsterPageNodes<'w, 's> = Query<
+    'w,
+    's,
+    &'static mut Node,
+    Or<(With<CompendiumMonsterPage>, With<CompendiumMonsterDetails>)>,
+>;
+
+type ItemPageNodes<'w, 's> =
+    Query<'w, 's, &'static mut Node, Or<(With<CompendiumItemPage>, With<CompendiumItemDetails>)>>;
+
+/// The selected item, its selected source, and which panel has focus.
+#[derive(SystemParam)]
+pub struct ItemSelection<'w> {
+    list: Res<'w, ItemListState>,
+    sources: Res<'w, ItemSourcesState>,
+    focus: Option<Res<'w, FocusState>>,
+}
+
+/// Shows either the monster pages or the item pages depending on the active section.
+pub fn update_compendium_section(
+    view_state: Res<CompendiumViewState>,
+    mut pages: ParamSet<(MonsterPageNodes, ItemPageNodes)>,
+) {
+    let (monster_display, item_display) = match view_state.section {
+        CompendiumSection::Monsters => (Display::Flex, Display::None),
+        CompendiumSection::Items => (Display::None, Display::Flex),
+    };
+
+    for mut node in &mut pages.p0() {
+        node.display = monster_display;
+    }
+    for mut node in &mut pages.p1() {
+        node.display = item_display;
+    }
+}
+
+/// Highlights the selected item and scrolls the list so the selection stays visible.
+pub fn update_item_list_display(
+    list_state: Res<ItemListState>,
+    items: Option<Res<CompendiumItems>>,
+    focus_state: Option<Res<FocusState>>,
+    mut rows: Query<(&ItemListItem, &mut TextColor, &mut Node)>,
+) {
+    let Some(items) = items else { return };
+    let Some(focus_state) = focus_state else { return };
+    let list_focused = focus_state.is_focused(FocusPanel::CompendiumItemList);
+
+    let first_visible = list_state
+        .selected
+        .saturating_sub(ITEM_LIST_VISIBLE_ROWS - 1)
+        .min(items.len().saturating_sub(ITEM_LIST_VISIBLE_ROWS));
+    let visible = first_visible..first_visible + ITEM_LIST_VISIBLE_ROWS;
+
+    for (row, mut color, mut node) in &mut rows {
+        node.display = if visible.contains(&row.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        let discovered = items.get(row.0).is_some_and(|entry| entry.is_discovered());
+        *color = TextColor(if row.0 == list_state.selected && list_focused {
+            SELECTED_COLOR
+        } else if discovered {
+            NORMAL_COLOR
+        } else {
+            UNDISCOVERED_COLOR
+        });
+    }
+}
+
+/// Rebuilds the right-hand item page for the selected item.
+pub fn update_item_details(
+    mut commands: Commands,
+    selection: ItemSelection,
+    items: Option<Res<CompendiumItems>>,
+    game_sprites: Res<GameSprites>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    details: Query<(Entity, Option<&Children>), With<CompendiumItemDetails>>,
+) {
+    let Some(items) = items else { return };
+    let Some(entry) = items.get(selection.list.selected) else { return };
+    let Ok((details_entity, children)) = details.single() else { return };
+
+    let sources_focused = selection
+        .focus
+        .as_ref()
+        .is_some_and(|f| f.is_focused(FocusPanel::CompendiumItemSources));
+
+    if let Some(children) = children {
+        for child in children.iter() {
+            commands.entity(child).despawn();
+        }
+    }
+
+    commands.entity(details_entity).with_children(|page| {
+        if !entry.is_discovered() {
+            spawn_line(page, loc.get("compendium.unknown"), ITEM_TITLE_FONT_SIZE, NORMAL_COLOR);
+            spawn_line(page, loc.get("compendium.not_found"), DROP_FONT_SIZE, NORMAL_COLOR);
+            return;
+        }
+
+        let spec = registry.get(entry.item_id);
+        let sheet_key = SpriteSheetKey::from(spec.sprite_sheet.unwrap_or_default());
+        if let Some(sheet) = game_sprites.get(sheet_key)
+            && let Some(bundle) =
+                sheet.image_bundle(&spec.sprite_name, ITEM_SPRITE_SIZE, ITEM_SPRITE_SIZE)
+        {
+            page.spawn(bundle);
+        }
+
+        spawn_line(page, &entry.name, ITEM_TITLE_FONT_SIZE, NORMAL_COLOR);
+        spawn_line(
+            page,
+            &loc.format("compendium.found", &[("count", &entry.found)]),
+            DROP_FONT_SIZE,
+            NORMAL_COLOR,
+        );
+
+        if entry.dropped_by.is_empty() && entry.crafted_from.is_empty() {
+            spawn_line(page, loc.get("compendium.no_sources"), DROP_FONT_SIZE, NORMAL_COLOR);
+            return;
+        }
+
+        if !entry.dropped_by.is_empty() {
+            spawn_line(page, loc.get("compendium.dropped_by"), DROP_FONT_SIZE, NORMAL_COLOR);
+            for (idx, source) in entry.dropped_by.iter().enumerate() {
+                let is_selected = sources_focused && idx == selection.sources.selected;
+                let text_color = if is_selected { SELECTED_COLOR } else { NORMAL_COLOR };
+                page.spawn((
+                    ItemSourceItem(idx),
+                    Text::new(format!("{} - {:.0}%", source.name, source.drop_chance_percent)),
+                    TextFont {
+                        font_size: DROP_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(text_color),
+                ));
+            }
+        }
+
+        if !entry.crafted_from.is_empty() {
+            spawn_line(page, loc.get("compendium.crafted_from"), DROP_FONT_SIZE, NORMAL_COLOR);
+            for recipe_name in &entry.crafted_from {
+                spawn_line(page, recipe_name, DROP_FONT_SIZE, NORMAL_COLOR);
+            }
+        }
+    });
+}
+
+fn spawn_line(parent: &mut ChildSpawnerCommands, text: &str, font_size: f32, color: Color) {
+    parent.spawn((
+        Text::new(text),
+        TextFont {
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+    ));
+}
@@ -1,10 +1,15 @@
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_aseprite_ultra::prelude::*;
 
-use crate::ui::{AseMobSheets, FocusPanel, FocusState};
+#[cfg(feature = "gui")]
+use crate::ui::AseMobSheets;
+use crate::ui::{FocusPanel, FocusState};
 
 use super::constants::*;
-use super::state::{CompendiumListState, CompendiumMobSprite, CompendiumMonsters, MonsterListItem};
+use super::state::{CompendiumListState, MonsterListItem};
+#[cfg(feature = "gui")]
+use super::state::{CompendiumMobSprite, CompendiumMonsters};
 
 pub fn update_monster_list_display(
     list_state: Res<CompendiumListState>,
@@ -23,6 +28,7 @@ pub fn update_monster_list_display(
     }
 }
 
+#[cfg(feature = "gui")]
 pub fn update_compendium_mob_sprite(
     mut commands: Commands,
     list_state: Res<CompendiumListState>,
@@ -37,14 +43,9 @@ pub fn update_compendium_mob_sprite(
 
     for entity in &query {
         if let Some(sheet) = ase_sheets.get(entry.mob_id) {
-            commands.entity(entity).insert((
-                AseAnimation {
-                    aseprite: sheet.aseprite.clone(),
-                    animation: Animation::tag(sheet.idle_tag)
-                        .with_repeat(AnimationRepeat::Loop),
-                },
-                ImageNode::default(),
-            ));
+            commands
+                .entity(entity)
+                .insert((sheet.idle_animation(), ImageNode::default()));
         } else {
             commands
                 .entity(entity)
@@ -1,5 +1,6 @@
 mod constants;
 mod drops;
+mod items;
 
 mod list;
 mod plugin;
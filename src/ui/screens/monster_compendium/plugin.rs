@@ -1,15 +1,22 @@
 use bevy::prelude::*;
 
-use crate::input::{navigate_compendium, switch_compendium_panel};
+use crate::input::{follow_compendium_link, navigate_compendium, switch_compendium_panel};
+use crate::states::ScreenSet;
 use crate::ui::modal_registry::{modal_close_system, RegisterModalExt};
 use crate::ui::screens::modal::in_monster_compendium_modal;
 use crate::ui::FocusState;
 
 use super::drops::{update_drops_display, update_drops_list_colors};
-use super::list::{update_compendium_mob_sprite, update_monster_list_display};
+use super::items::{update_compendium_section, update_item_details, update_item_list_display};
+#[cfg(feature = "gui")]
+use super::list::update_compendium_mob_sprite;
+use super::list::update_monster_list_display;
+#[cfg(feature = "gui")]
+use super::state::CompendiumMobSprite;
 use super::state::{
-    CompendiumDropsSection, CompendiumListState, CompendiumMobSprite, CompendiumStatsSection,
-    CompendiumViewState, DropsListState, MonsterCompendiumModal,
+    CompendiumDropsSection, CompendiumListState, CompendiumStatsSection,
+    CompendiumItemDetails, CompendiumViewState, DropsListState, ItemListItem, ItemListState,
+    ItemSourcesState, MonsterCompendiumModal,
 };
 use super::stats::update_stats_display;
 
@@ -21,13 +28,34 @@ impl Plugin for MonsterCompendiumPlugin {
             .init_resource::<CompendiumListState>()
             .init_resource::<DropsListState>()
             .init_resource::<CompendiumViewState>()
+            .init_resource::<ItemListState>()
+            .init_resource::<ItemSourcesState>()
             .add_systems(
                 Update,
                 (
-                    modal_close_system::<MonsterCompendiumModal>,
+                    modal_close_system::<MonsterCompendiumModal>.in_set(ScreenSet::Input),
+                    (switch_compendium_panel, navigate_compendium, follow_compendium_link)
+                        .in_set(ScreenSet::Input)
+                        .run_if(in_monster_compendium_modal),
                     (
-                        switch_compendium_panel,
-                        navigate_compendium,
+                        update_compendium_section.run_if(resource_changed::<CompendiumViewState>),
+                        update_item_list_display.run_if(
+                            resource_changed::<ItemListState>
+                                .or(
+                                    resource_exists::<FocusState>
+                                        .and(resource_changed::<FocusState>),
+                                )
+                                .or(any_match_filter::<Added<ItemListItem>>),
+                        ),
+                        update_item_details.run_if(
+                            resource_changed::<ItemListState>
+                                .or(resource_changed::<ItemSourcesState>)
+                                .or(
+                                    resource_exists::<FocusState>
+                                        .and(resource_changed::<FocusState>),
+                                )
+                                .or(any_match_filter::<Added<CompendiumItemDetails>>),
+                        ),
                         update_monster_list_display.run_if(
                             resource_changed::<CompendiumListState>
                                 .or(
@@ -35,10 +63,6 @@ impl Plugin for MonsterCompendiumPlugin {
                                         .and(resource_changed::<FocusState>),
                                 ),
                         ),
-                        update_compendium_mob_sprite.run_if(
-                            resource_changed::<CompendiumListState>
-                                .or(any_match_filter::<Added<CompendiumMobSprite>>),
-                        ),
                         update_stats_display.run_if(
                             resource_changed::<CompendiumListState>
                                 .or(resource_changed::<CompendiumViewState>)
@@ -57,8 +81,22 @@ impl Plugin for MonsterCompendiumPlugin {
                                 ),
                         ),
                     )
+                        .in_set(ScreenSet::Render)
                         .run_if(in_monster_compendium_modal),
                 ),
             );
+
+        // The portrait is an Aseprite animation, so only `gui` builds draw it.
+        #[cfg(feature = "gui")]
+        app.add_systems(
+            Update,
+            update_compendium_mob_sprite
+                .run_if(
+                    resource_changed::<CompendiumListState>
+                        .or(any_match_filter::<Added<CompendiumMobSprite>>),
+                )
+                .in_set(ScreenSet::Render)
+                .run_if(in_monster_compendium_modal),
+        );
     }
 }
@@ -1,18 +1,22 @@
 use bevy::prelude::*;
 
 use crate::assets::{GameSprites, SpriteSheetKey, UiAllSlice};
+use crate::i18n::Localization;
 use crate::ui::{Modal, ModalBackground, SpawnModalExt};
 
 use super::constants::*;
 use super::state::{
-    CompendiumDropsSection, CompendiumMobSprite, CompendiumMonsters, CompendiumStatsSection,
-    MonsterCompendiumRoot, MonsterListItem,
+    CompendiumDropsSection, CompendiumItemDetails, CompendiumItemPage, CompendiumItems,
+    CompendiumMobSprite, CompendiumMonsterDetails, CompendiumMonsterPage, CompendiumMonsters,
+    CompendiumStatsSection, ItemListItem, MonsterCompendiumRoot, MonsterListItem,
 };
 
 pub fn do_spawn_monster_compendium(
     mut commands: Commands,
     game_sprites: Res<GameSprites>,
     monsters: Res<CompendiumMonsters>,
+    items: Res<CompendiumItems>,
+    loc: Res<Localization>,
 ) {
     let Some(ui_all) = game_sprites.get(SpriteSheetKey::UiAll) else {
         return;
@@ -22,6 +26,13 @@ pub fn do_spawn_monster_compendium(
     };
 
     let monsters = monsters.clone();
+    let items = items.clone();
+    let monsters_header = loc.get("compendium.monsters").to_string();
+    let items_header = loc.format(
+        "compendium.items",
+        &[("found", &items.discovered_count()), ("total", &items.len())],
+    );
+    let unknown_name = loc.get("compendium.unknown").to_string();
 
     commands.spawn_modal(
         Modal::builder()
@@ -35,15 +46,16 @@ pub fn do_spawn_monster_compendium(
                 e.insert(MonsterCompendiumRoot);
             }))
             .content(Box::new(move |book| {
-                spawn_left_page(book, &monsters);
+                spawn_left_page(book, &monsters, &monsters_header);
+                spawn_item_list_page(book, &items, &items_header, &unknown_name);
                 spawn_right_page(book);
             }))
             .build(),
     );
 }
 
-fn spawn_left_page(book: &mut ChildSpawnerCommands, monsters: &CompendiumMonsters) {
-    book.spawn(Node {
+fn left_page_node() -> Node {
+    Node {
         position_type: PositionType::Absolute,
         left: Val::Px(LEFT_PAGE_LEFT),
         top: Val::Px(LEFT_PAGE_TOP),
@@ -53,8 +65,23 @@ fn spawn_left_page(book: &mut ChildSpawnerCommands, monsters: &CompendiumMonster
         overflow: Overflow::clip(),
         row_gap: Val::Px(LEFT_PAGE_ROW_GAP),
         ..default()
-    })
-    .with_children(|left_page| {
+    }
+}
+
+fn spawn_section_header(page: &mut ChildSpawnerCommands, header: &str) {
+    page.spawn((
+        Text::new(header),
+        TextFont {
+            font_size: SECTION_HEADER_FONT_SIZE,
+            ..default()
+        },
+        TextColor(SELECTED_COLOR),
+    ));
+}
+
+fn spawn_left_page(book: &mut ChildSpawnerCommands, monsters: &CompendiumMonsters, header: &str) {
+    book.spawn((CompendiumMonsterPage, left_page_node())).with_children(|left_page| {
+        spawn_section_header(left_page, header);
         for (idx, entry) in monsters.iter().enumerate() {
             let is_selected = idx == 0;
             left_page.spawn((
@@ -74,6 +101,36 @@ fn spawn_left_page(book: &mut ChildSpawnerCommands, monsters: &CompendiumMonster
     });
 }
 
+fn spawn_item_list_page(
+    book: &mut ChildSpawnerCommands,
+    items: &CompendiumItems,
+    header: &str,
+    unknown_name: &str,
+) {
+    let mut node = left_page_node();
+    node.display = Display::None;
+
+    book.spawn((CompendiumItemPage, node)).with_children(|left_page| {
+        spawn_section_header(left_page, header);
+        for (idx, entry) in items.iter().enumerate() {
+            let (name, color) = if entry.is_discovered() {
+                (entry.name.as_str(), NORMAL_COLOR)
+            } else {
+                (unknown_name, UNDISCOVERED_COLOR)
+            };
+            left_page.spawn((
+                ItemListItem(idx),
+                Text::new(name),
+                TextFont {
+                    font_size: MONSTER_NAME_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(color),
+            ));
+        }
+    });
+}
+
 fn spawn_right_page(book: &mut ChildSpawnerCommands) {
     book.spawn(Node {
         position_type: PositionType::Absolute,
@@ -87,45 +144,73 @@ fn spawn_right_page(book: &mut ChildSpawnerCommands) {
         ..default()
     })
     .with_children(|right_page| {
+        right_page
+            .spawn((
+                CompendiumMonsterDetails,
+                Node {
+                    width: Val::Px(RIGHT_PAGE_WIDTH),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+            ))
+            .with_children(spawn_monster_details);
+
         right_page.spawn((
-            CompendiumMobSprite,
+            CompendiumItemDetails,
             Node {
-                width: Val::Px(MOB_SPRITE_SIZE),
-                height: Val::Px(MOB_SPRITE_SIZE),
-                ..default()
-            },
-        ));
-
-        right_page
-            .spawn(Node {
+                display: Display::None,
                 width: Val::Px(RIGHT_PAGE_WIDTH),
-                flex_grow: 1.0,
                 flex_direction: FlexDirection::Column,
-                justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
+                padding: UiRect::horizontal(Val::Px(SECTION_PADDING)),
+                row_gap: Val::Px(LEFT_PAGE_ROW_GAP),
                 ..default()
-            })
-            .with_children(|content_area| {
-                content_area.spawn((
-                    CompendiumStatsSection,
-                    Node {
-                        width: Val::Px(RIGHT_PAGE_WIDTH),
-                        flex_direction: FlexDirection::Column,
-                        padding: UiRect::horizontal(Val::Px(SECTION_PADDING)),
-                        ..default()
-                    },
-                ));
-
-                content_area.spawn((
-                    CompendiumDropsSection,
-                    Node {
-                        width: Val::Px(RIGHT_PAGE_WIDTH),
-                        flex_direction: FlexDirection::Column,
-                        overflow: Overflow::clip(),
-                        padding: UiRect::horizontal(Val::Px(SECTION_PADDING)),
-                        ..default()
-                    },
-                ));
-            });
+            },
+        ));
     });
 }
+
+fn spawn_monster_details(right_page: &mut ChildSpawnerCommands) {
+    right_page.spawn((
+        CompendiumMobSprite,
+        Node {
+            width: Val::Px(MOB_SPRITE_SIZE),
+            height: Val::Px(MOB_SPRITE_SIZE),
+            ..default()
+        },
+    ));
+
+    right_page
+        .spawn(Node {
+            width: Val::Px(RIGHT_PAGE_WIDTH),
+            flex_grow: 1.0,
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|content_area| {
+            content_area.spawn((
+                CompendiumStatsSection,
+                Node {
+                    width: Val::Px(RIGHT_PAGE_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::horizontal(Val::Px(SECTION_PADDING)),
+                    ..default()
+                },
+            ));
+
+            content_area.spawn((
+                CompendiumDropsSection,
+                Node {
+                    width: Val::Px(RIGHT_PAGE_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::clip(),
+                    padding: UiRect::horizontal(Val::Px(SECTION_PADDING)),
+                    ..default()
+                },
+            ));
+        });
+}
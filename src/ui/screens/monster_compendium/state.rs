@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
 use crate::data::StatRange;
+use crate::i18n::Localization;
+use crate::item::recipe::RecipeId;
+use crate::item::{ItemDiscovery, ItemId, ItemRegistry};
 use crate::loot::definition::LootItem;
 use crate::mob::MobId;
 use crate::ui::modal_registry::RegisteredModal;
@@ -30,6 +33,38 @@ pub struct CompendiumDropsSection;
 #[derive(Component)]
 pub struct DropListItem(pub usize);
 
+/// Left-page container for the monster list.
+#[derive(Component)]
+pub struct CompendiumMonsterPage;
+
+/// Right-page container for the selected monster's sprite, stats, and drops.
+#[derive(Component)]
+pub struct CompendiumMonsterDetails;
+
+/// Left-page container for the item list.
+#[derive(Component)]
+pub struct CompendiumItemPage;
+
+/// Right-page container for the selected item's details.
+#[derive(Component)]
+pub struct CompendiumItemDetails;
+
+/// Component marker for item list entries, with their index.
+#[derive(Component)]
+pub struct ItemListItem(pub usize);
+
+/// Component marker for an item's "dropped by" rows, with their index.
+#[derive(Component)]
+pub struct ItemSourceItem(pub usize);
+
+/// Which half of the compendium is shown.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum CompendiumSection {
+    #[default]
+    Monsters,
+    Items,
+}
+
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub enum CompendiumDetailView {
     #[default]
@@ -40,6 +75,7 @@ pub enum CompendiumDetailView {
 #[derive(Resource, Default)]
 pub struct CompendiumViewState {
     pub view: CompendiumDetailView,
+    pub section: CompendiumSection,
 }
 
 /// Resource tracking the selected monster in the compendium.
@@ -83,6 +119,48 @@ impl SelectionState for DropsListState {
     }
 }
 
+/// Resource tracking the selected item in the compendium.
+#[derive(Resource, Default)]
+pub struct ItemListState {
+    pub selected: usize,
+    pub count: usize,
+}
+
+impl SelectionState for ItemListState {
+    fn selected(&self) -> usize {
+        self.selected
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn set_selected(&mut self, index: usize) {
+        self.selected = index;
+    }
+}
+
+/// Resource tracking the selected "dropped by" row on an item page.
+#[derive(Resource, Default)]
+pub struct ItemSourcesState {
+    pub selected: usize,
+    pub count: usize,
+}
+
+impl SelectionState for ItemSourcesState {
+    fn selected(&self) -> usize {
+        self.selected
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn set_selected(&mut self, index: usize) {
+        self.selected = index;
+    }
+}
+
 #[derive(Clone)]
 pub struct MonsterEntry {
     pub name: String,
@@ -100,7 +178,7 @@ pub struct MonsterEntry {
 pub struct CompendiumMonsters(pub Vec<MonsterEntry>);
 
 impl CompendiumMonsters {
-    pub fn from_registry() -> Self {
+    pub fn from_registry(loc: &Localization) -> Self {
         Self(
             MobId::ALL
                 .iter()
@@ -119,7 +197,7 @@ impl CompendiumMonsters {
                     });
 
                     MonsterEntry {
-                        name: spec.name.clone(),
+                        name: loc.mob_name(*mob_id, &spec.name).to_string(),
                         mob_id: *mob_id,
                         drops,
                         max_health: spec.max_health.clone(),
@@ -137,6 +215,10 @@ impl CompendiumMonsters {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn get(&self, index: usize) -> Option<&MonsterEntry> {
         self.0.get(index)
     }
@@ -144,6 +226,111 @@ impl CompendiumMonsters {
     pub fn iter(&self) -> impl Iterator<Item = &MonsterEntry> {
         self.0.iter()
     }
+
+    pub fn index_of(&self, mob_id: MobId) -> Option<usize> {
+        self.0.iter().position(|entry| entry.mob_id == mob_id)
+    }
+}
+
+/// A monster that can drop an item, linking back to its monster page.
+#[derive(Clone)]
+pub struct ItemSource {
+    pub mob_id: MobId,
+    pub name: String,
+    pub drop_chance_percent: f32,
+}
+
+#[derive(Clone)]
+pub struct ItemEntry {
+    pub item_id: ItemId,
+    pub name: String,
+    pub found: u32,
+    pub dropped_by: Vec<ItemSource>,
+    pub crafted_from: Vec<&'static str>,
+}
+
+impl ItemEntry {
+    pub fn is_discovered(&self) -> bool {
+        self.found > 0
+    }
+}
+
+/// Pre-computed list of items for the compendium, with their sources and found counts.
+#[derive(Resource, Clone)]
+pub struct CompendiumItems(pub Vec<ItemEntry>);
+
+impl CompendiumItems {
+    pub fn build(loc: &Localization, registry: &ItemRegistry, discovery: &ItemDiscovery) -> Self {
+        Self(
+            ItemId::ALL
+                .iter()
+                .filter_map(|&item_id| {
+                    let spec = registry.try_get(item_id)?;
+
+                    let mut dropped_by: Vec<ItemSource> = MobId::ALL
+                        .iter()
+                        .flat_map(|&mob_id| {
+                            let spec = mob_id.spec();
+                            spec.loot
+                                .iter()
+                                .filter(|loot| loot.item_id() == item_id)
+                                .map(|loot| ItemSource {
+                                    mob_id,
+                                    name: loc.mob_name(mob_id, &spec.name).to_string(),
+                                    drop_chance_percent: loot.drop_chance_percent(),
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                        .collect();
+
+                    dropped_by.sort_by(|a, b| {
+                        b.drop_chance_percent
+                            .partial_cmp(&a.drop_chance_percent)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    });
+
+                    let crafted_from = RecipeId::ALL
+                        .iter()
+                        .map(|recipe_id| recipe_id.spec())
+                        .filter(|recipe| recipe.output == item_id)
+                        .map(|recipe| recipe.name)
+                        .collect();
+
+                    Some(ItemEntry {
+                        item_id,
+                        name: loc.item_name(item_id, &spec.name).to_string(),
+                        found: discovery.count(item_id),
+                        dropped_by,
+                        crafted_from,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ItemEntry> {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ItemEntry> {
+        self.0.iter()
+    }
+
+    pub fn index_of(&self, item_id: ItemId) -> Option<usize> {
+        self.0.iter().position(|entry| entry.item_id == item_id)
+    }
+
+    pub fn discovered_count(&self) -> usize {
+        self.0.iter().filter(|entry| entry.is_discovered()).count()
+    }
 }
 
 /// Type-safe handle for the monster compendium modal.
@@ -160,14 +347,24 @@ impl RegisteredModal for MonsterCompendiumModal {
     const MODAL_TYPE: ModalType = ModalType::MonsterCompendium;
 
     fn spawn(world: &mut World) {
-        let monsters = CompendiumMonsters::from_registry();
+        let monsters = CompendiumMonsters::from_registry(world.resource::<Localization>());
         let count = monsters.len();
+        let items = CompendiumItems::build(
+            world.resource::<Localization>(),
+            world.resource::<ItemRegistry>(),
+            world.resource::<ItemDiscovery>(),
+        );
+        let item_count = items.len();
 
         world.resource_mut::<CompendiumListState>().count = count;
         world.resource_mut::<CompendiumListState>().reset();
         world.resource_mut::<DropsListState>().reset();
-        world.resource_mut::<CompendiumViewState>().view = CompendiumDetailView::Stats;
+        world.resource_mut::<ItemListState>().count = item_count;
+        world.resource_mut::<ItemListState>().reset();
+        world.resource_mut::<ItemSourcesState>().reset();
+        *world.resource_mut::<CompendiumViewState>() = CompendiumViewState::default();
         world.insert_resource(monsters);
+        world.insert_resource(items);
 
         world.insert_resource(FocusState::default());
         world
@@ -179,6 +376,7 @@ impl RegisteredModal for MonsterCompendiumModal {
 
     fn cleanup(world: &mut World) {
         world.remove_resource::<CompendiumMonsters>();
+        world.remove_resource::<CompendiumItems>();
         world.remove_resource::<FocusState>();
     }
 }
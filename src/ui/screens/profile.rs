@@ -1,21 +1,34 @@
 use bevy::prelude::*;
 
+use crate::economy::{Currency, Wallet};
 use crate::entities::Progression;
+use crate::faction::{FactionId, Reputation};
+use crate::game::{GambleLedger, GameStats, ImportedProfile, SharedProfile};
+use crate::i18n::Localization;
 use crate::input::GameAction;
-use crate::player::{PlayerGold, PlayerMarker, PlayerName};
-use crate::stats::{HasStats, StatSheet};
-use crate::states::{AppState, StateTransitionRequest};
-use crate::ui::widgets::StatRow;
+use crate::inventory::Inventory;
+use crate::player::{BankedGold, PlayerMarker, PlayerName};
+use crate::skills::Skills;
+use crate::stats::{HasStats, StatSheet, StatType};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::row_node;
+use crate::ui::widgets::{CurrencyDisplay, StatRow};
 
 pub struct ProfilePlugin;
 
 impl Plugin for ProfilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Profile), spawn_profile_screen)
+        app.init_resource::<ImportedProfile>()
+            .add_systems(OnEnter(AppState::Profile), spawn_profile_screen)
             .add_systems(OnExit(AppState::Profile), despawn_profile_screen)
             .add_systems(
                 Update,
-                handle_back_action.run_if(in_state(AppState::Profile)),
+                (
+                    handle_back_action,
+                    export_profile.run_if(on_message::<GameAction>),
+                )
+                    .in_set(ScreenSet::Input)
+                    .run_if(in_state(AppState::Profile)),
             );
     }
 }
@@ -23,11 +36,22 @@ impl Plugin for ProfilePlugin {
 #[derive(Component)]
 struct ProfileScreenRoot;
 
+/// Where the share code appears after exporting.
+#[derive(Component)]
+struct ProfileShareText;
+
 fn spawn_profile_screen(
     mut commands: Commands,
-    player: Query<(&PlayerName, &PlayerGold, &StatSheet, &Progression), With<PlayerMarker>>,
+    loc: Res<Localization>,
+    reputation: Res<Reputation>,
+    ledger: Res<GambleLedger>,
+    imported: Res<ImportedProfile>,
+    player: Query<
+        (&PlayerName, &Wallet, &BankedGold, &StatSheet, &Progression),
+        With<PlayerMarker>,
+    >,
 ) {
-    let Ok((name, gold, stats, prog)) = player.single() else {
+    let Ok((name, wallet, banked, stats, prog)) = player.single() else {
         return;
     };
     commands
@@ -46,7 +70,7 @@ fn spawn_profile_screen(
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new(format!("{}'s Profile", name.0)),
+                Text::new(loc.format("profile.title", &[("name", &name.0)])),
                 TextFont {
                     font_size: 56.0,
                     ..default()
@@ -68,17 +92,61 @@ fn spawn_profile_screen(
                 })
                 .with_children(|parent| {
                     parent.spawn(
-                        StatRow::builder("HP:", format!("{} / {}", stats.hp(), stats.max_hp()))
+                        StatRow::builder(
+                            loc.get("profile.hp"),
+                            format!("{} / {}", stats.hp(), stats.max_hp()),
+                        )
+                        .label_width(150.0)
+                        .font_size(28.0)
+                        .column_gap(15.0)
+                        .label_color(Color::srgb(0.8, 0.8, 0.8))
+                        .value_color(Color::srgb(0.9, 0.2, 0.2))
+                        .build(),
+                    );
+
+                    parent.spawn(
+                        StatRow::builder(
+                            loc.get("profile.mp"),
+                            format!(
+                                "{} / {}",
+                                stats.value(StatType::Mana),
+                                stats.max_value(StatType::Mana)
+                            ),
+                        )
+                        .label_width(150.0)
+                        .font_size(28.0)
+                        .column_gap(15.0)
+                        .label_color(Color::srgb(0.8, 0.8, 0.8))
+                        .value_color(Color::srgb(0.4, 0.6, 1.0))
+                        .build(),
+                    );
+
+                    parent.spawn(
+                        StatRow::builder(loc.get("profile.gold"), format!("{}", wallet.gold()))
+                            .label_width(150.0)
+                            .font_size(28.0)
+                            .column_gap(15.0)
+                            .label_color(Color::srgb(0.8, 0.8, 0.8))
+                            .value_color(Color::srgb(1.0, 0.84, 0.0))
+                            .build(),
+                    );
+
+                    parent.spawn(
+                        StatRow::builder(loc.get("profile.banked"), format!("{}", banked.0))
                             .label_width(150.0)
                             .font_size(28.0)
                             .column_gap(15.0)
                             .label_color(Color::srgb(0.8, 0.8, 0.8))
-                            .value_color(Color::srgb(0.9, 0.2, 0.2))
+                            .value_color(Color::srgb(1.0, 0.84, 0.0))
                             .build(),
                     );
 
+                    let gambling = loc.format(
+                        "profile.gambling_net",
+                        &[("net", &ledger.net()), ("plays", &ledger.plays)],
+                    );
                     parent.spawn(
-                        StatRow::builder("Gold:", format!("{}", gold.0))
+                        StatRow::builder(loc.get("profile.gambling"), gambling)
                             .label_width(150.0)
                             .font_size(28.0)
                             .column_gap(15.0)
@@ -87,8 +155,26 @@ fn spawn_profile_screen(
                             .build(),
                     );
 
+                    parent.spawn(row_node(15.0)).with_children(|row| {
+                        row.spawn((
+                            Text::new(loc.get("profile.wallet")),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                            Node {
+                                width: Val::Px(150.0),
+                                ..default()
+                            },
+                        ));
+                        for currency in Currency::ALL.into_iter().filter(|c| *c != Currency::Gold) {
+                            row.spawn(CurrencyDisplay::builder(currency).font_size(28.0).build());
+                        }
+                    });
+
                     parent.spawn(
-                        StatRow::builder("Attack:", format!("{}", stats.attack()))
+                        StatRow::builder(loc.get("profile.attack"), format!("{}", stats.attack()))
                             .label_width(150.0)
                             .font_size(28.0)
                             .column_gap(15.0)
@@ -98,7 +184,7 @@ fn spawn_profile_screen(
                     );
 
                     parent.spawn(
-                        StatRow::builder("Defense:", format!("{}", stats.defense()))
+                        StatRow::builder(loc.get("profile.defense"), format!("{}", stats.defense()))
                             .label_width(150.0)
                             .font_size(28.0)
                             .column_gap(15.0)
@@ -108,7 +194,7 @@ fn spawn_profile_screen(
                     );
 
                     parent.spawn(
-                        StatRow::builder("Level:", format!("{}", prog.level))
+                        StatRow::builder(loc.get("profile.level"), format!("{}", prog.level))
                             .label_width(150.0)
                             .font_size(28.0)
                             .column_gap(15.0)
@@ -117,6 +203,33 @@ fn spawn_profile_screen(
                             .build(),
                     );
 
+                    for &faction in FactionId::ALL {
+                        let rank = loc.get(reputation.rank(faction).label_key());
+                        let standing = match reputation.next_rank_at(faction) {
+                            Some(next) => loc.format(
+                                "profile.reputation_next",
+                                &[
+                                    ("rank", &rank),
+                                    ("reputation", &reputation.get(faction)),
+                                    ("next", &next),
+                                ],
+                            ),
+                            None => loc.format(
+                                "profile.reputation",
+                                &[("rank", &rank), ("reputation", &reputation.get(faction))],
+                            ),
+                        };
+                        parent.spawn(
+                            StatRow::builder(loc.get(faction.name_key()), standing)
+                                .label_width(150.0)
+                                .font_size(28.0)
+                                .column_gap(15.0)
+                                .label_color(Color::srgb(0.8, 0.8, 0.8))
+                                .value_color(Color::srgb(0.7, 0.8, 1.0))
+                                .build(),
+                        );
+                    }
+
                     let xp_current = prog.xp;
                     let xp_needed = Progression::xp_to_next_level(prog.level);
                     let xp_percent = (xp_current as f32 / xp_needed as f32 * 100.0) as i32;
@@ -131,7 +244,14 @@ fn spawn_profile_screen(
                         })
                         .with_children(|parent| {
                             parent.spawn((
-                                Text::new(format!("XP: {} / {} ({}%)", xp_current, xp_needed, xp_percent)),
+                                Text::new(loc.format(
+                                    "profile.xp",
+                                    &[
+                                        ("current", &xp_current),
+                                        ("needed", &xp_needed),
+                                        ("percent", &xp_percent),
+                                    ],
+                                )),
                                 TextFont {
                                     font_size: 24.0,
                                     ..default()
@@ -150,21 +270,149 @@ fn spawn_profile_screen(
                         });
                 });
 
+            if let Some(profile) = &imported.0 {
+                parent.spawn((
+                    Text::new(imported_text(&loc, profile)),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.7, 0.8, 1.0)),
+                    Node {
+                        margin: UiRect::top(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            parent.spawn((
+                ProfileShareText,
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.9, 0.6)),
+                Node {
+                    margin: UiRect::top(Val::Px(20.0)),
+                    max_width: Val::Percent(80.0),
+                    ..default()
+                },
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("profile.share_hint")),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                Node {
+                    margin: UiRect::top(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
             parent.spawn((
-                Text::new("Press Backspace to return to Menu"),
+                Text::new(loc.get("profile.back")),
                 TextFont {
                     font_size: 20.0,
                     ..default()
                 },
                 TextColor(Color::srgb(0.6, 0.6, 0.6)),
                 Node {
-                    margin: UiRect::top(Val::Px(40.0)),
+                    margin: UiRect::top(Val::Px(10.0)),
                     ..default()
                 },
             ));
         });
 }
 
+/// A shared profile laid out for reading: who, their stats, gear and skills.
+fn imported_text(loc: &Localization, profile: &SharedProfile) -> String {
+    let mut lines = vec![
+        loc.format(
+            "profile.imported_title",
+            &[("name", &profile.name), ("level", &profile.level)],
+        ),
+        loc.format(
+            "profile.imported_stats",
+            &[
+                ("hp", &profile.max_hp),
+                ("attack", &profile.attack),
+                ("defense", &profile.defense),
+                ("depth", &profile.best_depth),
+            ],
+        ),
+    ];
+    for gear in &profile.gear {
+        let slot = loc.get(gear.slot.label_key());
+        lines.push(if gear.upgrades > 0 {
+            loc.format(
+                "profile.imported_upgraded",
+                &[
+                    ("slot", &slot),
+                    ("item", &gear.name),
+                    ("upgrades", &gear.upgrades),
+                ],
+            )
+        } else {
+            loc.format(
+                "profile.imported_gear",
+                &[("slot", &slot), ("item", &gear.name)],
+            )
+        });
+    }
+    let skills: Vec<String> = profile
+        .skills
+        .iter()
+        .map(|(skill, level)| format!("{} {level}", skill.display_name()))
+        .collect();
+    lines.push(loc.format("profile.imported_skills", &[("skills", &skills.join(", "))]));
+    lines.join("\n")
+}
+
+/// Enter writes the player's build to a file and shows its share code.
+fn export_profile(
+    mut action_reader: MessageReader<GameAction>,
+    loc: Res<Localization>,
+    skills: Res<Skills>,
+    game_stats: Res<GameStats>,
+    player: Query<(&PlayerName, &StatSheet, &Progression, &Inventory), With<PlayerMarker>>,
+    mut share_text: Query<&mut Text, With<ProfileShareText>>,
+) {
+    if !action_reader
+        .read()
+        .any(|action| *action == GameAction::Select)
+    {
+        return;
+    }
+    let (Ok((name, stats, prog, inventory)), Ok(mut text)) =
+        (player.single(), share_text.single_mut())
+    else {
+        return;
+    };
+
+    let profile = SharedProfile::new(
+        &name.0,
+        prog.level,
+        stats,
+        inventory,
+        &skills,
+        game_stats.best_depth,
+    );
+    let exported = profile
+        .export()
+        .and_then(|path| profile.to_code().map(|code| (path, code)));
+    **text = match exported {
+        Ok((path, code)) => loc.format(
+            "profile.exported",
+            &[("path", &path.display()), ("code", &code)],
+        ),
+        Err(e) => loc.format("profile.export_failed", &[("error", &e)]),
+    };
+}
+
 fn create_text_progress_bar(current: i32, max: i32, width: usize) -> String {
     let filled_count = if max > 0 {
         ((current as f32 / max as f32) * width as f32) as usize
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::item::ItemRegistry;
+use crate::location::CurrentTown;
+use crate::ranch::{product, Ranch, RanchRequest, RanchRole, MAX_RANCH_LEVEL};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(0.6, 0.9, 0.4);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+pub struct RanchScreenPlugin;
+
+impl Plugin for RanchScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RanchSelection>()
+            .add_systems(OnEnter(AppState::Ranch), spawn_ranch_screen)
+            .add_systems(OnExit(AppState::Ranch), despawn_ranch_screen)
+            .add_systems(
+                Update,
+                open_ranch_screen
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_ranch_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_ranch_body.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Ranch)),
+            );
+    }
+}
+
+/// Index of the highlighted mob in the ranch.
+#[derive(Resource, Default)]
+struct RanchSelection(usize);
+
+#[derive(Component)]
+struct RanchScreenRoot;
+
+#[derive(Component)]
+struct RanchBody;
+
+#[derive(Component)]
+struct RanchHint;
+
+fn open_ranch_screen(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenRanch || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::Ranch);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("ranch.not_in_town")));
+        }
+    }
+}
+
+fn spawn_ranch_screen(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<RanchSelection>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            RanchScreenRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("ranch.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                RanchBody,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                RanchHint,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_ranch_input(
+    mut action_reader: MessageReader<GameAction>,
+    ranch: Res<Ranch>,
+    mut selection: ResMut<RanchSelection>,
+    mut ranch_writer: MessageWriter<RanchRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = ranch.mobs().len().max(1);
+    // A release can leave the highlight past the end of the list.
+    selection.0 = selection.0.min(count - 1);
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Select => {
+                ranch_writer.write(RanchRequest::ToggleRole(selection.0));
+            }
+            GameAction::NextTab => {
+                ranch_writer.write(RanchRequest::Collect);
+            }
+            GameAction::PrevTab => {
+                ranch_writer.write(RanchRequest::Upgrade);
+            }
+            GameAction::DismissTip => {
+                ranch_writer.write(RanchRequest::Release(selection.0));
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenRanch => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pens in use, what is waiting to be collected, and every penned mob with its role.
+fn body_text(
+    loc: &Localization,
+    ranch: &Ranch,
+    selected: usize,
+    registry: &ItemRegistry,
+) -> String {
+    let mut lines = vec![loc.format(
+        "ranch.pens",
+        &[
+            ("used", &ranch.mobs().len()),
+            ("pens", &ranch.capacity()),
+            ("level", &ranch.level),
+            ("max", &MAX_RANCH_LEVEL),
+        ],
+    )];
+
+    let stock: Vec<String> = ranch
+        .stock()
+        .map(|(item_id, quantity)| {
+            let name = loc.item_name(item_id, &registry.get(item_id).name);
+            format!("{name} x{quantity}")
+        })
+        .collect();
+    lines.push(if stock.is_empty() {
+        loc.get("ranch.stock_empty").to_string()
+    } else {
+        loc.format("ranch.stock", &[("items", &stock.join(", "))])
+    });
+    lines.push(String::new());
+
+    if ranch.mobs().is_empty() {
+        lines.push(loc.get("ranch.empty").to_string());
+    }
+    for (index, mob) in ranch.mobs().iter().enumerate() {
+        let name = loc.mob_name(mob.mob_id, &mob.mob_id.spec().name);
+        let duty = match (mob.role, product(mob.mob_id)) {
+            (RanchRole::Producer, Some((item_id, _))) => loc.format(
+                "ranch.produces",
+                &[("item", &loc.item_name(item_id, &registry.get(item_id).name))],
+            ),
+            (role, _) => loc.get(role.label_key()).to_string(),
+        };
+        let marker = if index == selected { ">" } else { " " };
+        lines.push(format!("{marker} {name} - {duty}"));
+    }
+    lines.join("\n")
+}
+
+fn update_ranch_body(
+    ranch: Res<Ranch>,
+    selection: Res<RanchSelection>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut body: Query<&mut Text, (With<RanchBody>, Without<RanchHint>)>,
+    mut hint: Query<&mut Text, (With<RanchHint>, Without<RanchBody>)>,
+    added: Query<(), Added<RanchBody>>,
+) {
+    if !ranch.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = body.single_mut() {
+        **text = body_text(&loc, &ranch, selection.0, &registry);
+    }
+
+    if let Ok(mut text) = hint.single_mut() {
+        **text = match ranch.upgrade_cost() {
+            Some(cost) => loc.format("ranch.hint", &[("gold", &cost)]),
+            None => loc.get("ranch.hint_max").to_string(),
+        };
+    }
+}
+
+fn despawn_ranch_screen(mut commands: Commands, root: Query<Entity, With<RanchScreenRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+
+use crate::game::save_slots::now_secs;
+use crate::game::{CharacterSave, PlayedAgo, SaveSlotRequest, SaveSlots, MAX_SAVE_SLOTS};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::{column_node, Announce, Palette};
+
+const NORMAL_COLOR: Color = Color::WHITE;
+const WARNING_COLOR: Color = Color::srgb(1.0, 0.45, 0.35);
+
+pub struct RosterScreenPlugin;
+
+impl Plugin for RosterScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RosterSelection>()
+            .add_systems(OnEnter(AppState::Roster), spawn_roster_screen)
+            .add_systems(OnExit(AppState::Roster), despawn_roster_screen)
+            .add_systems(
+                Update,
+                (
+                    handle_roster_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_roster_rows
+                        .run_if(
+                            resource_changed::<RosterSelection>.or(resource_changed::<SaveSlots>),
+                        )
+                        .in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Roster)),
+            );
+    }
+}
+
+/// The highlighted slot, and whether erasing it is waiting on a second press of Delete.
+#[derive(Resource, Default)]
+struct RosterSelection {
+    index: usize,
+    confirm_delete: bool,
+    /// Set when Delete was pressed on the character being played.
+    refused_delete: bool,
+}
+
+#[derive(Component)]
+struct RosterRoot;
+
+#[derive(Component)]
+struct RosterRowText(usize);
+
+#[derive(Component)]
+struct RosterStatusText;
+
+fn played_text(save: &CharacterSave, now: u64, loc: &Localization) -> String {
+    match PlayedAgo::since(save.last_played, now) {
+        PlayedAgo::JustNow => loc.get("roster.played_just_now").to_string(),
+        PlayedAgo::Minutes(n) => loc.format("roster.played_minutes", &[("count", &n.to_string())]),
+        PlayedAgo::Hours(n) => loc.format("roster.played_hours", &[("count", &n.to_string())]),
+        PlayedAgo::Days(n) => loc.format("roster.played_days", &[("count", &n.to_string())]),
+    }
+}
+
+fn row_text(slots: &SaveSlots, index: usize, now: u64, loc: &Localization) -> String {
+    let slot = (index + 1).to_string();
+    let text = match slots.get(index) {
+        Some(save) => loc.format(
+            "roster.slot",
+            &[
+                ("slot", &slot),
+                ("name", &save.name),
+                ("level", &save.level.to_string()),
+                ("gold", &(save.gold + save.banked_gold).to_string()),
+                ("played", &played_text(save, now, loc)),
+            ],
+        ),
+        None => loc.format("roster.empty", &[("slot", &slot)]),
+    };
+    if index == slots.active {
+        format!("{text}  {}", loc.get("roster.playing"))
+    } else {
+        text
+    }
+}
+
+fn status_text(selection: &RosterSelection, slots: &SaveSlots, loc: &Localization) -> String {
+    if selection.refused_delete {
+        return loc.get("roster.delete_active").to_string();
+    }
+    match slots.get(selection.index) {
+        Some(save) if selection.confirm_delete => {
+            loc.format("roster.confirm_delete", &[("name", &save.name)])
+        }
+        _ => String::new(),
+    }
+}
+
+fn spawn_roster_screen(
+    mut commands: Commands,
+    slots: Res<SaveSlots>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut selection: ResMut<RosterSelection>,
+) {
+    *selection = RosterSelection {
+        index: slots.active,
+        ..default()
+    };
+    let now = now_secs();
+
+    commands
+        .spawn((
+            RosterRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("roster.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(palette.selection),
+            ));
+
+            parent.spawn(column_node(8.0)).with_children(|list| {
+                for index in 0..MAX_SAVE_SLOTS {
+                    list.spawn((
+                        RosterRowText(index),
+                        Text::new(row_text(&slots, index, now, &loc)),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(NORMAL_COLOR),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                RosterStatusText,
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(WARNING_COLOR),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("roster.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+/// Delete only erases a slot when pressed twice in a row; any other key cancels.
+fn handle_roster_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<RosterSelection>,
+    slots: Res<SaveSlots>,
+    mut slot_requests: MessageWriter<SaveSlotRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        let confirming = std::mem::take(&mut selection.confirm_delete);
+        selection.refused_delete = false;
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.index = (selection.index + MAX_SAVE_SLOTS - 1) % MAX_SAVE_SLOTS;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.index = (selection.index + 1) % MAX_SAVE_SLOTS;
+            }
+            GameAction::Select => {
+                slot_requests.write(SaveSlotRequest::Play {
+                    index: selection.index,
+                });
+                state_requests.write(StateTransitionRequest::Menu);
+            }
+            GameAction::DeleteSaveSlot => {
+                if selection.index == slots.active {
+                    selection.refused_delete = true;
+                } else if confirming {
+                    slot_requests.write(SaveSlotRequest::Delete {
+                        index: selection.index,
+                    });
+                } else if slots.get(selection.index).is_some() {
+                    selection.confirm_delete = true;
+                }
+            }
+            GameAction::Back | GameAction::OpenRoster => {
+                state_requests.write(StateTransitionRequest::Menu);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_roster_rows(
+    selection: Res<RosterSelection>,
+    slots: Res<SaveSlots>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut rows: Query<(&RosterRowText, &mut Text, &mut TextColor), Without<RosterStatusText>>,
+    mut status: Query<&mut Text, With<RosterStatusText>>,
+    mut announce: MessageWriter<Announce>,
+) {
+    let now = now_secs();
+    for (row, mut text, mut color) in &mut rows {
+        **text = row_text(&slots, row.0, now, &loc);
+        *color = TextColor(if row.0 == selection.index {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        });
+    }
+    if let Ok(mut text) = status.single_mut() {
+        **text = status_text(&selection, &slots, &loc);
+    }
+    announce.write(Announce::new(row_text(&slots, selection.index, now, &loc)));
+    announce.write(Announce::new(status_text(&selection, &slots, &loc)));
+}
+
+fn despawn_roster_screen(mut commands: Commands, root: Query<Entity, With<RosterRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,399 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::difficulty::AdaptiveDifficultyBand;
+use crate::dungeon::{handle_floor_transition, DungeonState, DungeonTier, FloorTransition};
+use crate::game::run_planner::{best_for_slot, is_potion, is_potion_type, potion_count, run_gaps};
+use crate::game::{
+    BuyItemEvent, RunGap, ShowToast, StorageWithdrawEvent, UndoHistory, UndoableAction,
+    RECOMMENDED_POTIONS,
+};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::inventory::{EquipmentSlot, Inventory, ManagesEquipment, ManagesItems};
+use crate::location::CurrentTown;
+use crate::player::PlayerMarker;
+use crate::settings::GameSettings;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::storage::Storage;
+use crate::ui::widgets::TextRows;
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::merchant_modal::MerchantStock;
+use crate::ui::screens::modal::ActiveModal;
+use crate::world_event::{ActiveWorldEvent, GameClock};
+
+const TITLE_COLOR: Color = Color::srgb(0.6, 0.85, 1.0);
+const GAP_COLOR: Color = Color::srgb(1.0, 0.6, 0.3);
+const READY_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+pub struct RunPlannerPlugin;
+
+impl Plugin for RunPlannerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GapSelection>()
+            .add_systems(OnEnter(AppState::RunPlanner), spawn_run_planner)
+            .add_systems(OnExit(AppState::RunPlanner), despawn_run_planner)
+            .add_systems(
+                Update,
+                (
+                    open_run_planner
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    remind_before_descent
+                        .run_if(on_message::<FloorTransition>)
+                        .before(handle_floor_transition),
+                )
+                    .run_if(in_state(AppState::Dungeon)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_run_planner_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_run_planner.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::RunPlanner)),
+            );
+    }
+}
+
+/// Index of the highlighted gap.
+#[derive(Resource, Default)]
+struct GapSelection(usize);
+
+#[derive(Component)]
+struct RunPlannerRoot;
+
+#[derive(Component)]
+struct RunPlannerSummary;
+
+#[derive(Component)]
+struct RunPlannerGaps;
+
+/// What closing a gap draws on: the backpack and undo history for equipping, and storage or
+/// the local merchant for potions.
+#[derive(SystemParam)]
+struct GapFixes<'w, 's> {
+    player: Query<'w, 's, &'static mut Inventory, With<PlayerMarker>>,
+    history: ResMut<'w, UndoHistory>,
+    storage: Res<'w, Storage>,
+    stock: Option<Res<'w, MerchantStock>>,
+    town: Res<'w, CurrentTown>,
+    withdraw_writer: MessageWriter<'w, StorageWithdrawEvent>,
+    buy_writer: MessageWriter<'w, BuyItemEvent>,
+}
+
+/// What can make the next run harder or more rewarding than usual.
+#[derive(SystemParam)]
+struct FloorConditions<'w> {
+    dungeon: Res<'w, DungeonState>,
+    clock: Res<'w, GameClock>,
+    event: Res<'w, ActiveWorldEvent>,
+    settings: Res<'w, GameSettings>,
+}
+
+/// The summary text and the gap rows, plus whether the rows were just spawned.
+#[derive(SystemParam)]
+struct RunPlannerTexts<'w, 's> {
+    summary: Query<'w, 's, &'static mut Text, With<RunPlannerSummary>>,
+    gaps: Query<'w, 's, &'static mut TextRows, With<RunPlannerGaps>>,
+    added: Query<'w, 's, (), Added<RunPlannerGaps>>,
+}
+
+fn open_run_planner(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenRunPlanner || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::RunPlanner);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("planner.not_in_town")));
+        }
+    }
+}
+
+/// Nudges the player toward the planner when they head down with gaps in their loadout.
+fn remind_before_descent(
+    mut transitions: MessageReader<FloorTransition>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let descending = transitions
+        .read()
+        .any(|transition| matches!(transition, FloorTransition::EnterDoor));
+    if !descending || dungeon.current_location != Some(town.0.spec().home) {
+        return;
+    }
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    let gaps = run_gaps(inventory).len();
+    if gaps > 0 {
+        toast_writer.write(ShowToast::new(
+            loc.format("planner.reminder", &[("count", &gaps)]),
+        ));
+    }
+}
+
+fn spawn_run_planner(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<GapSelection>,
+) {
+    selection.0 = 0;
+
+    commands
+        .spawn((
+            RunPlannerRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("planner.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                RunPlannerSummary,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((RunPlannerGaps, column_node(8.0), TextRows::new(22.0)));
+
+            parent.spawn((
+                Text::new(loc.get("planner.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+fn handle_run_planner_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<GapSelection>,
+    fixes: GapFixes,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let GapFixes {
+        mut player,
+        mut history,
+        storage,
+        stock,
+        town,
+        mut withdraw_writer,
+        mut buy_writer,
+    } = fixes;
+    let Ok(mut inventory) = player.single_mut() else {
+        return;
+    };
+    let gaps = run_gaps(&inventory);
+    let count = gaps.len().max(1);
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0.min(count - 1) + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0.min(count - 1) + 1) % count;
+            }
+            GameAction::Select => match gaps.get(selection.0) {
+                Some(RunGap::EmptySlot(slot)) => {
+                    equip_best(&mut inventory, *slot, &mut history, &loc, &mut toast_writer);
+                }
+                Some(RunGap::FewPotions { .. }) => {
+                    // Potions already paid for come out of storage before buying more.
+                    let stored = storage.inventory.items.iter().position(is_potion);
+                    let for_sale = stock
+                        .as_ref()
+                        .filter(|stock| stock.restocked.is_some_and(|(t, _)| t == town.0))
+                        .and_then(|stock| {
+                            stock.items.iter().position(|store_item| {
+                                store_item
+                                    .display_item()
+                                    .is_some_and(|item| is_potion_type(item.item_type))
+                            })
+                        });
+                    if let Some(storage_index) = stored {
+                        withdraw_writer.write(StorageWithdrawEvent { storage_index });
+                    } else if let Some(stock_index) = for_sale {
+                        buy_writer.write(BuyItemEvent { stock_index });
+                    } else {
+                        toast_writer.write(ShowToast::new(loc.get("planner.no_potions")));
+                    }
+                }
+                None => {}
+            },
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenRunPlanner => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Equips the most valuable backpack item for `slot`, recording it for undo.
+fn equip_best(
+    inventory: &mut Inventory,
+    slot: EquipmentSlot,
+    history: &mut UndoHistory,
+    loc: &Localization,
+    toast_writer: &mut MessageWriter<ShowToast>,
+) {
+    let Some(index) = best_for_slot(inventory, slot) else {
+        toast_writer.write(ShowToast::new(loc.format(
+            "planner.nothing_to_equip",
+            &[("slot", &loc.get(slot.label_key()))],
+        )));
+        return;
+    };
+    let uuid = inventory.get_inventory_items()[index].uuid();
+    inventory.equip_from_inventory(uuid, slot);
+    history.record(UndoableAction::Equip {
+        slot,
+        previous: None,
+    });
+}
+
+impl FloorConditions<'_> {
+    /// Lines describing what makes the next run harder or more rewarding than usual.
+    fn modifiers(&self, loc: &Localization) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.dungeon.tier != DungeonTier::Normal {
+            lines.push(loc.format(
+                "planner.modifier_tier",
+                &[("tier", &loc.get(self.dungeon.tier.label_key()))],
+            ));
+        }
+        if self.clock.is_night() {
+            lines.push(loc.get("planner.modifier_night").to_string());
+        }
+        if let Some(event) = &self.event.0 {
+            lines.push(loc.format("planner.modifier_event", &[("name", &event.name)]));
+        }
+        if self.settings.adaptive_difficulty != AdaptiveDifficultyBand::Off {
+            lines.push(loc.format(
+                "planner.modifier_adaptive",
+                &[("band", &loc.get(self.settings.adaptive_difficulty.label_key()))],
+            ));
+        }
+        if lines.is_empty() {
+            lines.push(loc.get("planner.no_modifiers").to_string());
+        }
+        lines
+    }
+}
+
+fn update_run_planner(
+    player: Query<Ref<Inventory>, With<PlayerMarker>>,
+    selection: Res<GapSelection>,
+    conditions: FloorConditions,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    texts: RunPlannerTexts,
+) {
+    let RunPlannerTexts {
+        mut summary,
+        gaps: mut gaps_list,
+        added,
+    } = texts;
+    let (Ok(inventory), Ok(mut list)) = (player.single(), gaps_list.single_mut()) else {
+        return;
+    };
+    if !inventory.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = summary.single_mut() {
+        let mut lines = vec![loc.get("planner.gear").to_string()];
+        for &slot in EquipmentSlot::all() {
+            let item = match inventory.get_equipped_item(slot) {
+                Some(equipped) => loc
+                    .item_name(equipped.item.item_id, &equipped.item.name)
+                    .to_string(),
+                None => loc.get("planner.empty").to_string(),
+            };
+            lines.push(format!("  {}: {item}", loc.get(slot.label_key())));
+        }
+        lines.push(loc.format(
+            "planner.potions",
+            &[
+                ("count", &potion_count(&inventory)),
+                ("recommended", &RECOMMENDED_POTIONS),
+            ],
+        ));
+        lines.push(loc.get("planner.modifiers").to_string());
+        for modifier in conditions.modifiers(&loc) {
+            lines.push(format!("  {modifier}"));
+        }
+        **text = lines.join("\n");
+    }
+
+    let gaps = run_gaps(&inventory);
+    let selected = selection.0.min(gaps.len().saturating_sub(1));
+    if gaps.is_empty() {
+        list.set([(loc.get("planner.ready").to_string(), READY_COLOR)]);
+        return;
+    }
+    list.set(gaps.iter().enumerate().map(|(index, gap)| {
+        let line = match gap {
+            RunGap::EmptySlot(slot) => {
+                loc.format("planner.gap_slot", &[("slot", &loc.get(slot.label_key()))])
+            }
+            RunGap::FewPotions { have } => loc.format(
+                "planner.gap_potions",
+                &[("have", have), ("recommended", &RECOMMENDED_POTIONS)],
+            ),
+        };
+        let color = if index == selected {
+            palette.selection
+        } else {
+            GAP_COLOR
+        };
+        (line, color)
+    }));
+}
+
+fn despawn_run_planner(mut commands: Commands, root: Query<Entity, With<RunPlannerRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,6 @@
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::ShrineModalPlugin;
+pub use state::{ActiveShrine, ShrineModal};
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+
+use crate::game::{ShrineOffer, UseShrine};
+use crate::input::{GameAction, NavigationDirection};
+use crate::states::ScreenSet;
+use crate::ui::modal_registry::{modal_close_system, ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::in_shrine_modal;
+
+use super::render::update_shrine_offers;
+use super::state::{ActiveShrine, ShrineModal, ShrineModalState, ShrineOfferText};
+
+pub struct ShrineModalPlugin;
+
+impl Plugin for ShrineModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<ShrineModal>().add_systems(
+            Update,
+            (
+                (
+                    modal_close_system::<ShrineModal>,
+                    handle_shrine_input.run_if(on_message::<GameAction>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Input),
+                update_shrine_offers
+                    .run_if(
+                        resource_exists_and_changed::<ShrineModalState>
+                            .or(any_match_filter::<Added<ShrineOfferText>>),
+                    )
+                    .in_set(ScreenSet::Render),
+            )
+                .run_if(in_shrine_modal.and(resource_exists::<ShrineModalState>)),
+        );
+    }
+}
+
+/// Moves between the offers and makes the highlighted one. The shrine takes a single
+/// offering, so the modal closes once it is made.
+fn handle_shrine_input(
+    mut commands: Commands,
+    mut action_reader: MessageReader<GameAction>,
+    mut state: ResMut<ShrineModalState>,
+    shrine: Option<Res<ActiveShrine>>,
+    mut shrine_writer: MessageWriter<UseShrine>,
+) {
+    let count = ShrineOffer::ALL.len();
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                state.selected = (state.selected + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                state.selected = (state.selected + 1) % count;
+            }
+            GameAction::Select => {
+                if let Some(shrine) = &shrine {
+                    shrine_writer.write(UseShrine {
+                        shrine: shrine.0,
+                        offer: ShrineOffer::ALL[state.selected],
+                    });
+                }
+                commands.close_modal::<ShrineModal>();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::shrine::BLESSING_FLOORS;
+use crate::game::ShrineOffer;
+use crate::i18n::Localization;
+use crate::ui::widgets::Column;
+use crate::ui::{Modal, SpawnModalExt};
+
+use super::state::{ShrineModalRoot, ShrineModalState, ShrineOfferText};
+
+const MODAL_WIDTH: f32 = 420.0;
+const PROMPT_SIZE: f32 = 18.0;
+const OFFER_SIZE: f32 = 20.0;
+
+const PROMPT_COLOR: Color = Color::srgb(0.7, 0.7, 0.9);
+const NORMAL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+const SELECTED_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
+pub fn do_spawn_shrine_modal(
+    mut commands: Commands,
+    dungeon: Res<DungeonState>,
+    loc: Res<Localization>,
+) {
+    let depth = dungeon.depth();
+    let offers: Vec<String> = ShrineOffer::ALL
+        .iter()
+        .map(|&offer| {
+            let gold = offer.gold_cost(depth);
+            match offer {
+                ShrineOffer::Sacrifice => loc.format(
+                    "shrine.sacrifice",
+                    &[("gold", &gold), ("floors", &BLESSING_FLOORS)],
+                ),
+                ShrineOffer::Pray => loc.get("shrine.pray").to_string(),
+                ShrineOffer::Cleanse => loc.format("shrine.cleanse", &[("gold", &gold)]),
+            }
+        })
+        .collect();
+    let prompt = loc.get("shrine.prompt").to_string();
+
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get("shrine.title"))
+            .size((MODAL_WIDTH, 0.0))
+            .hint(loc.get("shrine.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(ShrineModalRoot);
+            }))
+            .content(Box::new(move |c| {
+                c.spawn(Column::new().gap(10.0).align_center())
+                    .with_children(|col| {
+                        col.spawn((
+                            Text::new(prompt),
+                            TextFont {
+                                font_size: PROMPT_SIZE,
+                                ..default()
+                            },
+                            TextColor(PROMPT_COLOR),
+                        ));
+
+                        for (index, label) in offers.iter().enumerate() {
+                            col.spawn((
+                                ShrineOfferText(index),
+                                Text::new(label.clone()),
+                                TextFont {
+                                    font_size: OFFER_SIZE,
+                                    ..default()
+                                },
+                                TextColor(NORMAL_COLOR),
+                            ));
+                        }
+                    });
+            }))
+            .build(),
+    );
+}
+
+pub fn update_shrine_offers(
+    state: Res<ShrineModalState>,
+    mut offers: Query<(&ShrineOfferText, &mut TextColor)>,
+) {
+    for (offer, mut color) in &mut offers {
+        color.0 = if offer.0 == state.selected {
+            SELECTED_COLOR
+        } else {
+            NORMAL_COLOR
+        };
+    }
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+
+use super::render::do_spawn_shrine_modal;
+
+#[derive(Component)]
+pub struct ShrineModalRoot;
+
+/// One of the shrine's offers, by its index in `ShrineOffer::ALL`.
+#[derive(Component)]
+pub struct ShrineOfferText(pub usize);
+
+/// Tracks which shrine the modal is open for.
+#[derive(Resource)]
+pub struct ActiveShrine(pub Entity);
+
+/// The highlighted offer.
+#[derive(Resource, Default)]
+pub struct ShrineModalState {
+    pub selected: usize,
+}
+
+pub struct ShrineModal;
+
+impl RegisteredModal for ShrineModal {
+    type Root = ShrineModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Shrine;
+
+    fn spawn(world: &mut World) {
+        world.insert_resource(ShrineModalState::default());
+        world.run_system_cached(do_spawn_shrine_modal).ok();
+    }
+
+    fn cleanup(world: &mut World) {
+        world.remove_resource::<ActiveShrine>();
+        world.remove_resource::<ShrineModalState>();
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use crate::states::ScreenSet;
 use crate::ui::modal_registry::{modal_close_system, RegisterModalExt};
 use crate::ui::screens::modal::in_skills_modal;
 
@@ -10,6 +11,11 @@ pub struct SkillsModalPlugin;
 impl Plugin for SkillsModalPlugin {
     fn build(&self, app: &mut App) {
         app.register_modal::<SkillsModal>()
-            .add_systems(Update, modal_close_system::<SkillsModal>.run_if(in_skills_modal));
+            .add_systems(
+                Update,
+                modal_close_system::<SkillsModal>
+                    .run_if(in_skills_modal)
+                    .in_set(ScreenSet::Input),
+            );
     }
 }
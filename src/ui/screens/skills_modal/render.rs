@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use crate::i18n::Localization;
 use crate::skills::{xp_for_level, SkillType, Skills};
 use crate::ui::widgets::Column;
 use crate::ui::{Modal, SpawnModalExt};
@@ -15,7 +16,11 @@ const BAR_HEIGHT: f32 = 16.0;
 const XP_BAR_BG: Color = Color::srgb(0.2, 0.2, 0.2);
 const XP_BAR_FILL: Color = Color::srgb(0.2, 0.7, 0.3);
 
-pub fn do_spawn_skills_modal(mut commands: Commands, skills: Res<Skills>) {
+pub fn do_spawn_skills_modal(
+    mut commands: Commands,
+    skills: Res<Skills>,
+    loc: Res<Localization>,
+) {
     let skill_data: Vec<_> = SkillType::all()
         .iter()
         .map(|&skill_type| {
@@ -30,15 +35,23 @@ pub fn do_spawn_skills_modal(mut commands: Commands, skills: Res<Skills>) {
             } else {
                 1.0
             };
-            (skill_type, level, xp, xp_needed, progress)
+            let title = loc.format(
+                "skills.level",
+                &[("skill", &skill_type.display_name()), ("level", &level)],
+            );
+            let xp_text = loc.format(
+                "skills.xp",
+                &[("xp", &format_number(xp)), ("needed", &format_number(xp_needed))],
+            );
+            (title, xp_text, progress)
         })
         .collect();
 
-    let total_level = skills.player_level();
+    let total_text = loc.format("skills.total", &[("level", &skills.player_level())]);
 
     commands.spawn_modal(
         Modal::builder()
-            .title("Skills")
+            .title(loc.get("skills.title"))
             .size((MODAL_WIDTH, 0.0))
             .root_marker(Box::new(|e| {
                 e.insert(SkillsModalRoot);
@@ -46,15 +59,11 @@ pub fn do_spawn_skills_modal(mut commands: Commands, skills: Res<Skills>) {
             .content(Box::new(move |c| {
                 c.spawn(Column::new().gap(16.0).align_center())
                     .with_children(|col| {
-                        for (skill_type, level, xp, xp_needed, progress) in &skill_data {
+                        for (title, xp_text, progress) in &skill_data {
                             col.spawn(Column::new().gap(4.0).align_center())
                                 .with_children(|skill_col| {
                                     skill_col.spawn((
-                                        Text::new(format!(
-                                            "{}  Level {}/99",
-                                            skill_type.display_name(),
-                                            level
-                                        )),
+                                        Text::new(title.clone()),
                                         TextFont {
                                             font_size: SKILL_NAME_SIZE,
                                             ..default()
@@ -81,11 +90,7 @@ pub fn do_spawn_skills_modal(mut commands: Commands, skills: Res<Skills>) {
                                         });
 
                                     skill_col.spawn((
-                                        Text::new(format!(
-                                            "{} / {} XP",
-                                            format_number(*xp),
-                                            format_number(*xp_needed)
-                                        )),
+                                        Text::new(xp_text.clone()),
                                         TextFont {
                                             font_size: STAT_SIZE,
                                             ..default()
@@ -101,7 +106,7 @@ pub fn do_spawn_skills_modal(mut commands: Commands, skills: Res<Skills>) {
                         });
 
                         col.spawn((
-                            Text::new(format!("Total Level: {}", total_level)),
+                            Text::new(total_text),
                             TextFont {
                                 font_size: SKILL_NAME_SIZE,
                                 ..default()
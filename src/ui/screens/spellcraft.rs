@@ -0,0 +1,378 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::magic::{
+    ComputedSpell, KnownWords, Page, Tome, WordEffect, WordId, WordRegistry, MAX_PAGE_WORDS,
+    SUMMON_SECS,
+};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(0.55, 0.6, 1.0);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const PREVIEW_COLOR: Color = Color::srgb(0.7, 0.9, 1.0);
+const ERROR_COLOR: Color = Color::srgb(1.0, 0.45, 0.4);
+
+pub struct SpellcraftScreenPlugin;
+
+impl Plugin for SpellcraftScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpellDraft>()
+            .add_systems(OnEnter(AppState::Spellcraft), spawn_spellcraft_screen)
+            .add_systems(OnExit(AppState::Spellcraft), despawn_spellcraft_screen)
+            .add_systems(
+                Update,
+                open_spellcraft
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_spellcraft_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_spellcraft_screen.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Spellcraft)),
+            );
+    }
+}
+
+/// The page being composed and the highlighted word in the word list.
+#[derive(Resource, Default)]
+struct SpellDraft {
+    words: Vec<WordId>,
+    selected: usize,
+}
+
+#[derive(Component)]
+struct SpellcraftRoot;
+
+#[derive(Component)]
+struct SpellcraftWords;
+
+#[derive(Component)]
+struct SpellcraftPreview;
+
+#[derive(Component)]
+struct SpellcraftTome;
+
+fn open_spellcraft(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        if *action == GameAction::OpenSpellcraft && active_modal.modal.is_none() {
+            state_requests.write(StateTransitionRequest::Spellcraft);
+        }
+    }
+}
+
+fn spawn_spellcraft_screen(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut draft: ResMut<SpellDraft>,
+) {
+    *draft = SpellDraft::default();
+
+    let text = |size: f32, color: Color| {
+        (
+            Text::new(""),
+            TextFont {
+                font_size: size,
+                ..default()
+            },
+            TextColor(color),
+        )
+    };
+
+    commands
+        .spawn((
+            SpellcraftRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("spellcraft.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+            parent.spawn((SpellcraftWords, text(22.0, Color::WHITE)));
+            parent.spawn((SpellcraftPreview, text(22.0, PREVIEW_COLOR)));
+            parent.spawn((SpellcraftTome, text(18.0, Color::WHITE)));
+            parent.spawn((
+                Text::new(loc.get("spellcraft.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+/// The words the player knows and what each one does.
+#[derive(SystemParam)]
+struct Words<'w> {
+    registry: Res<'w, WordRegistry>,
+    known: Res<'w, KnownWords>,
+}
+
+fn handle_spellcraft_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut draft: ResMut<SpellDraft>,
+    mut tome: ResMut<Tome>,
+    words: Words,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let known = words.known.words();
+    let count = known.len();
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                draft.selected = (draft.selected + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                draft.selected = (draft.selected + 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Right) | GameAction::Select
+                if draft.words.len() < MAX_PAGE_WORDS =>
+            {
+                let word = known[draft.selected];
+                draft.words.push(word);
+            }
+            GameAction::Navigate(NavigationDirection::Left) => {
+                draft.words.pop();
+            }
+            GameAction::NextTab => {
+                let page = Page::new(page_name(&loc, &draft.words), draft.words.clone());
+                match words.registry.compute(&page) {
+                    Ok(_) => {
+                        toast_writer.write(ShowToast::new(
+                            loc.format("spellcraft.written", &[("spell", &page.name)]),
+                        ));
+                        tome.write(page);
+                        draft.words.clear();
+                    }
+                    Err(e) => {
+                        toast_writer.write(ShowToast::new(loc.format(
+                            "spellcraft.cannot_write",
+                            &[("reason", &loc.get(e.message_key()))],
+                        )));
+                    }
+                }
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenSpellcraft => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A written page is named after its words, e.g. "Bolt Fire".
+fn page_name(loc: &Localization, words: &[WordId]) -> String {
+    words
+        .iter()
+        .map(|word| loc.get(word.name_key()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// What a word adds to a page, e.g. "12 damage".
+pub(super) fn effect_text(loc: &Localization, effect: WordEffect) -> String {
+    match effect {
+        WordEffect::Form(form) => loc.format(
+            "spellcraft.effect_form",
+            &[("form", &loc.get(form.name_key()))],
+        ),
+        WordEffect::Damage(amount) => {
+            loc.format("spellcraft.effect_damage", &[("amount", &amount)])
+        }
+        WordEffect::Heal(amount) => loc.format("spellcraft.effect_heal", &[("amount", &amount)]),
+        WordEffect::Buff(kind) => loc.get(kind.label_key()).to_string(),
+        WordEffect::Amplify(percent) => {
+            loc.format("spellcraft.effect_amplify", &[("percent", &percent)])
+        }
+        WordEffect::Summon(mob_id) => loc.format(
+            "spellcraft.effect_summon",
+            &[("mob", &loc.mob_name(mob_id, &mob_id.spec().name))],
+        ),
+    }
+}
+
+/// The known words with each word's effect and cost, then the page written so far.
+fn words_text(
+    loc: &Localization,
+    registry: &WordRegistry,
+    known: &KnownWords,
+    draft: &SpellDraft,
+) -> String {
+    let mut lines: Vec<String> = known
+        .words()
+        .iter()
+        .enumerate()
+        .map(|(index, &word)| {
+            let spec = registry.spec(word);
+            let marker = if index == draft.selected { ">" } else { " " };
+            let line = loc.format(
+                "spellcraft.word",
+                &[
+                    ("word", &loc.get(word.name_key())),
+                    ("effect", &effect_text(loc, spec.effect)),
+                    ("mana", &spec.mana_cost),
+                ],
+            );
+            format!("{marker} {line}")
+        })
+        .collect();
+    lines.push(String::new());
+
+    let page = if draft.words.is_empty() {
+        loc.get("spellcraft.blank_page").to_string()
+    } else {
+        page_name(loc, &draft.words)
+    };
+    lines.push(loc.format(
+        "spellcraft.page",
+        &[
+            ("page", &page),
+            ("count", &draft.words.len()),
+            ("max", &MAX_PAGE_WORDS),
+        ],
+    ));
+    lines.join("\n")
+}
+
+/// What the page would do when cast.
+fn preview_text(loc: &Localization, spell: &ComputedSpell) -> String {
+    let mut lines = vec![loc.format(
+        "spellcraft.preview",
+        &[
+            ("form", &loc.get(spell.form.name_key())),
+            ("mana", &spell.mana_cost),
+        ],
+    )];
+    if spell.damage > 0 {
+        lines.push(loc.format("spellcraft.preview_damage", &[("amount", &spell.damage)]));
+    }
+    if spell.heal > 0 {
+        lines.push(loc.format("spellcraft.preview_heal", &[("amount", &spell.heal)]));
+    }
+    if !spell.buffs.is_empty() {
+        let buffs: Vec<_> = spell
+            .buffs
+            .iter()
+            .map(|kind| loc.get(kind.label_key()))
+            .collect();
+        lines.push(loc.format("spellcraft.preview_buffs", &[("buffs", &buffs.join(", "))]));
+    }
+    if !spell.summons.is_empty() {
+        let summons: Vec<_> = spell
+            .summons
+            .iter()
+            .map(|&mob_id| loc.mob_name(mob_id, &mob_id.spec().name))
+            .collect();
+        lines.push(loc.format(
+            "spellcraft.preview_summons",
+            &[("mobs", &summons.join(", ")), ("secs", &SUMMON_SECS)],
+        ));
+    }
+    lines.join("\n")
+}
+
+type PreviewFilter = (With<SpellcraftPreview>, Without<SpellcraftWords>);
+type TomeFilter = (
+    With<SpellcraftTome>,
+    Without<SpellcraftWords>,
+    Without<SpellcraftPreview>,
+);
+
+/// The screen's three text panels.
+#[derive(SystemParam)]
+struct SpellcraftTexts<'w, 's> {
+    words: Query<'w, 's, &'static mut Text, With<SpellcraftWords>>,
+    preview: Query<'w, 's, (&'static mut Text, &'static mut TextColor), PreviewFilter>,
+    tome: Query<'w, 's, &'static mut Text, TomeFilter>,
+}
+
+fn update_spellcraft_screen(
+    draft: Res<SpellDraft>,
+    tome: Res<Tome>,
+    registry: Res<WordRegistry>,
+    known: Res<KnownWords>,
+    loc: Res<Localization>,
+    mut texts: SpellcraftTexts,
+    added: Query<(), Added<SpellcraftRoot>>,
+) {
+    if !draft.is_changed() && !tome.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = texts.words.single_mut() {
+        **text = words_text(&loc, &registry, &known, &draft);
+    }
+
+    if let Ok((mut text, mut color)) = texts.preview.single_mut() {
+        let page = Page::new(String::new(), draft.words.clone());
+        let (preview, preview_color) = match registry.compute(&page) {
+            Ok(spell) => (preview_text(&loc, &spell), PREVIEW_COLOR),
+            Err(e) => (
+                loc.format(
+                    "spellcraft.invalid",
+                    &[("reason", &loc.get(e.message_key()))],
+                ),
+                ERROR_COLOR,
+            ),
+        };
+        **text = preview;
+        *color = TextColor(preview_color);
+    }
+
+    if let Ok(mut text) = texts.tome.single_mut() {
+        let pages: Vec<_> = tome.pages().iter().map(|page| page.name.as_str()).collect();
+        **text = loc.format("spellcraft.tome", &[("pages", &pages.join(", "))]);
+    }
+}
+
+fn despawn_spellcraft_screen(mut commands: Commands, root: Query<Entity, With<SpellcraftRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_are_named_after_their_words() {
+        let loc = Localization::default();
+
+        assert_eq!(
+            page_name(&loc, &[WordId::Nova, WordId::Frost, WordId::Amplify]),
+            "Nova Frost Amplify"
+        );
+    }
+}
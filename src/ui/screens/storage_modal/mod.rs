@@ -0,0 +1,6 @@
+mod plugin;
+mod render;
+mod state;
+
+pub use plugin::StorageModalPlugin;
+pub use state::{StorageItemsGrid, StorageModal, StoragePlayerGrid};
@@ -0,0 +1,205 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::game::{
+    ShowToast, Storage, StorageDepositAllEvent, StorageDepositEvent, StorageWithdrawEvent,
+};
+use crate::i18n::Localization;
+use crate::input::NavigationDirection;
+use crate::states::ScreenSet;
+use crate::storage::DepositFilter;
+use crate::ui::focus::FocusPanel;
+use crate::ui::modal_registry::{ModalCommands, RegisterModalExt};
+use crate::ui::screens::modal::in_storage_modal;
+use crate::ui::widgets::{update_detail_pane_source, ItemDetailPane, ItemGrid, ItemGridSelection};
+use crate::ui::FocusState;
+
+use super::render::{
+    populate_storage_detail_pane_content, sync_storage_grid, sync_storage_player_grid,
+    update_storage_header,
+};
+use super::state::{
+    StorageDetailPane, StorageItemsGrid, StorageModal, StorageModalRoot, StoragePlayerGrid,
+    StorageTabsText,
+};
+
+pub struct StorageModalPlugin;
+
+impl Plugin for StorageModalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_modal::<StorageModal>().add_systems(
+            Update,
+            (
+                handle_storage_keys.in_set(ScreenSet::Input),
+                (
+                    sync_storage_grid.run_if(resource_changed::<Storage>),
+                    sync_storage_player_grid,
+                    update_storage_header.run_if(
+                        resource_changed::<Storage>.or(any_match_filter::<Added<StorageTabsText>>),
+                    ),
+                    update_detail_pane_source::<StorageDetailPane>.run_if(
+                        resource_exists::<FocusState>
+                            .and(resource_changed::<FocusState>)
+                            .or(any_match_filter::<Changed<ItemGridSelection>>),
+                    ),
+                    populate_storage_detail_pane_content.run_if(
+                        resource_changed::<Storage>
+                            .or(any_match_filter::<Changed<ItemDetailPane>>),
+                    ),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Render),
+            )
+                .run_if(in_storage_modal),
+        );
+    }
+}
+
+type StorageGridFilter = (With<StorageItemsGrid>, Without<StoragePlayerGrid>);
+type PlayerGridFilter = (With<StoragePlayerGrid>, Without<StorageItemsGrid>);
+
+/// Key presses, and whether the modal opened this frame.
+#[derive(SystemParam)]
+struct StorageKeys<'w, 's> {
+    events: MessageReader<'w, 's, KeyboardInput>,
+    opened: Query<'w, 's, (), Added<StorageModalRoot>>,
+}
+
+/// The storage and inventory grids, and which of them has focus.
+#[derive(SystemParam)]
+struct StorageGrids<'w, 's> {
+    focus: Option<ResMut<'w, FocusState>>,
+    storage: Query<'w, 's, (&'static ItemGrid, &'static mut ItemGridSelection), StorageGridFilter>,
+    player: Query<'w, 's, (&'static ItemGrid, &'static mut ItemGridSelection), PlayerGridFilter>,
+}
+
+/// Requests to move items between storage and the inventory.
+#[derive(SystemParam)]
+struct StorageRequests<'w> {
+    deposit: MessageWriter<'w, StorageDepositEvent>,
+    withdraw: MessageWriter<'w, StorageWithdrawEvent>,
+    deposit_all: MessageWriter<'w, StorageDepositAllEvent>,
+}
+
+/// Keyboard input for the storage modal. Like the help modal, game actions are suppressed
+/// while it is open so typed characters go to the search filter; everything else uses keys
+/// that cannot be typed.
+fn handle_storage_keys(
+    mut commands: Commands,
+    mut keys: StorageKeys,
+    mut storage: ResMut<Storage>,
+    grids: StorageGrids,
+    mut requests: StorageRequests,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    // Skip the key press that opened the modal.
+    if !keys.opened.is_empty() {
+        keys.events.clear();
+        return;
+    }
+    let StorageGrids {
+        focus,
+        storage: mut storage_grids,
+        player: mut player_grids,
+    } = grids;
+    let Some(mut focus_state) = focus else {
+        return;
+    };
+
+    for event in keys.events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        let storage_focused = focus_state.is_focused(FocusPanel::StorageItems);
+
+        let direction = match &event.logical_key {
+            Key::ArrowUp => Some(NavigationDirection::Up),
+            Key::ArrowDown => Some(NavigationDirection::Down),
+            Key::ArrowLeft => Some(NavigationDirection::Left),
+            Key::ArrowRight => Some(NavigationDirection::Right),
+            _ => None,
+        };
+        if let Some(direction) = direction {
+            let grid = if storage_focused {
+                storage_grids.single_mut()
+            } else {
+                player_grids.single_mut()
+            };
+            if let Ok((grid, mut selection)) = grid {
+                selection.navigate(direction, grid.grid_size);
+            }
+            continue;
+        }
+
+        if event.repeat {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Escape if !storage.search.is_empty() => storage.search.clear(),
+            Key::Escape => commands.close_modal::<StorageModal>(),
+            Key::Tab => {
+                focus_state.toggle_between(FocusPanel::StorageItems, FocusPanel::PlayerInventory)
+            }
+            Key::Enter if storage_focused => {
+                let Ok((_, selection)) = storage_grids.single() else {
+                    continue;
+                };
+                let visible = storage.visible_items();
+                if let Some(&storage_index) = visible.get(selection.selected_index) {
+                    requests
+                        .withdraw
+                        .write(StorageWithdrawEvent { storage_index });
+                }
+            }
+            Key::Enter => {
+                let Ok((_, selection)) = player_grids.single() else {
+                    continue;
+                };
+                requests.deposit.write(StorageDepositEvent {
+                    inventory_index: selection.selected_index,
+                });
+            }
+            Key::PageUp => storage.cycle_tab(false),
+            Key::PageDown => storage.cycle_tab(true),
+            Key::F2 => storage.sort = storage.sort.next(),
+            Key::F3 => {
+                let name = std::mem::take(&mut storage.search);
+                if storage.add_tab(&name).is_none() {
+                    storage.search = name;
+                    toast_writer.write(ShowToast::new(loc.format(
+                        "storage.tabs_full",
+                        &[("max", &storage.max_tabs)],
+                    )));
+                }
+            }
+            Key::F4 if storage_focused => {
+                let Ok((_, selection)) = storage_grids.single() else {
+                    continue;
+                };
+                if let Some(&index) = storage.visible_items().get(selection.selected_index) {
+                    let uuid = storage.inventory.items[index].uuid();
+                    storage.move_to_next_tab(uuid);
+                }
+            }
+            Key::F5 => {
+                requests.deposit_all.write(StorageDepositAllEvent {
+                    filter: DepositFilter::Materials,
+                });
+            }
+            Key::F6 => {
+                requests.deposit_all.write(StorageDepositAllEvent {
+                    filter: DepositFilter::Unlocked,
+                });
+            }
+            Key::Backspace => {
+                storage.search.pop();
+            }
+            Key::Space => storage.search.push(' '),
+            Key::Character(chars) => storage.search.push_str(chars),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use bevy::prelude::*;
+
+use crate::economy::WorthGold;
+use crate::game::Storage;
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesEquipment, ManagesItems};
+use crate::player::PlayerMarker;
+use crate::ui::focus::FocusPanel;
+use crate::ui::widgets::{
+    Column, ItemDetailDisplay, ItemDetailPane, ItemDetailPaneContent, ItemGrid, ItemGridEntry,
+    ItemGridFocusPanel, ItemGridSelection, PriceDisplay,
+};
+use crate::ui::InfoPanelSource;
+use crate::ui::{modal_content_row, FocusState, Modal, SpawnModalExt};
+
+use super::state::{
+    StorageFilterText, StorageItemsGrid, StorageModalRoot, StoragePlayerGrid, StorageTabsText,
+};
+
+const HEADER_SIZE: f32 = 20.0;
+const TABS_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const FILTER_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+/// Grid entries for the storage's visible items, in display order.
+fn get_storage_entries(storage: &Storage) -> Vec<ItemGridEntry> {
+    storage
+        .visible_items()
+        .into_iter()
+        .map(|index| ItemGridEntry::from_inventory_item(&storage.inventory.items[index]))
+        .collect()
+}
+
+pub fn do_spawn_storage_modal(
+    mut commands: Commands,
+    storage: Res<Storage>,
+    loc: Res<Localization>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    commands.insert_resource(FocusState {
+        focused: Some(FocusPanel::StorageItems),
+    });
+
+    let storage_entries = get_storage_entries(&storage);
+    let player_entries = ItemGridEntry::from_inventory(inventory);
+
+    commands.spawn_modal(
+        Modal::builder()
+            .title(loc.get("storage.title"))
+            .hint(loc.get("storage.hint"))
+            .root_marker(Box::new(|e| {
+                e.insert(StorageModalRoot);
+            }))
+            .content(Box::new(move |c| {
+                c.spawn(Column::new().gap(12.0)).with_children(|col| {
+                    col.spawn((
+                        StorageTabsText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: HEADER_SIZE,
+                            ..default()
+                        },
+                        TextColor(TABS_COLOR),
+                    ));
+                    col.spawn((
+                        StorageFilterText,
+                        Text::new(""),
+                        TextFont {
+                            font_size: HEADER_SIZE,
+                            ..default()
+                        },
+                        TextColor(FILTER_COLOR),
+                    ));
+
+                    col.spawn(modal_content_row()).with_children(|row| {
+                        row.spawn((
+                            StorageItemsGrid,
+                            ItemGridFocusPanel(FocusPanel::StorageItems),
+                            ItemGrid {
+                                items: storage_entries,
+                                grid_size: 5,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn((
+                            StoragePlayerGrid,
+                            ItemGridFocusPanel(FocusPanel::PlayerInventory),
+                            ItemGrid {
+                                items: player_entries,
+                                grid_size: 5,
+                            },
+                            ItemGridSelection::default(),
+                        ));
+                        row.spawn(ItemDetailPane {
+                            source: InfoPanelSource::Storage { selected_index: 0 },
+                        });
+                    });
+                });
+            }))
+            .build(),
+    );
+}
+
+pub fn sync_storage_grid(
+    storage: Res<Storage>,
+    mut grids: Query<(&mut ItemGrid, &mut ItemGridSelection), With<StorageItemsGrid>>,
+) {
+    if let Ok((mut grid, mut selection)) = grids.single_mut() {
+        grid.items = get_storage_entries(&storage);
+        selection.clamp(grid.items.len());
+    }
+}
+
+pub fn sync_storage_player_grid(
+    player: Query<&Inventory, (With<PlayerMarker>, Changed<Inventory>)>,
+    mut grids: Query<(&mut ItemGrid, &mut ItemGridSelection), With<StoragePlayerGrid>>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    if let Ok((mut grid, mut selection)) = grids.single_mut() {
+        grid.items = ItemGridEntry::from_inventory(inventory);
+        selection.clamp(grid.items.len());
+    }
+}
+
+pub fn update_storage_header(
+    storage: Res<Storage>,
+    loc: Res<Localization>,
+    mut tabs_text: Query<&mut Text, (With<StorageTabsText>, Without<StorageFilterText>)>,
+    mut filter_text: Query<&mut Text, (With<StorageFilterText>, Without<StorageTabsText>)>,
+) {
+    if let Ok(mut text) = tabs_text.single_mut() {
+        **text = storage
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                if index == storage.active_tab {
+                    format!("[{name}]")
+                } else {
+                    name.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+    }
+
+    if let Ok(mut text) = filter_text.single_mut() {
+        **text = loc.format(
+            "storage.filter",
+            &[
+                ("query", &format!("{}_", storage.search)),
+                ("sort", &loc.get(storage.sort.label_key())),
+            ],
+        );
+    }
+}
+
+pub fn populate_storage_detail_pane_content(
+    mut commands: Commands,
+    storage: Res<Storage>,
+    player: Query<&Inventory, With<PlayerMarker>>,
+    panes: Query<Ref<ItemDetailPane>>,
+    content_query: Query<(Entity, Option<&Children>), With<ItemDetailPaneContent>>,
+) {
+    let Ok(inventory) = player.single() else {
+        return;
+    };
+
+    for pane in &panes {
+        if !pane.is_changed() && !storage.is_changed() {
+            continue;
+        }
+
+        let Ok((content_entity, children)) = content_query.single() else {
+            continue;
+        };
+
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(child).despawn();
+            }
+        }
+
+        let inv_item = match pane.source {
+            InfoPanelSource::Storage { selected_index } => storage
+                .visible_items()
+                .get(selected_index)
+                .map(|&index| &storage.inventory.items[index]),
+            InfoPanelSource::Inventory { selected_index } => {
+                inventory.get_inventory_items().get(selected_index)
+            }
+            _ => None,
+        };
+
+        let Some(inv_item) = inv_item else {
+            continue;
+        };
+
+        let comparison = inventory.compare_to_equipped(&inv_item.item);
+
+        commands.entity(content_entity).with_children(|parent| {
+            parent.spawn(
+                ItemDetailDisplay::builder(&inv_item.item)
+                    .quantity(inv_item.quantity)
+                    .price(PriceDisplay::Sell(inv_item.item.sell_price()))
+                    .maybe_comparison(comparison)
+                    .build(),
+            );
+        });
+    }
+}
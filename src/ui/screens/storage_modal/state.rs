@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::ui::focus::FocusPanel;
+use crate::ui::modal_registry::RegisteredModal;
+use crate::ui::screens::modal::ModalType;
+use crate::ui::widgets::{DetailPaneContext, ItemGridSelection};
+use crate::ui::InfoPanelSource;
+
+use super::render::do_spawn_storage_modal;
+
+/// Component marker for the storage modal UI.
+#[derive(Component)]
+pub struct StorageModalRoot;
+
+/// Marker for the grid of stored items (left side).
+#[derive(Component)]
+pub struct StorageItemsGrid;
+
+/// Marker for the player inventory grid (right side).
+#[derive(Component)]
+pub struct StoragePlayerGrid;
+
+/// Tab strip above the grids.
+#[derive(Component)]
+pub struct StorageTabsText;
+
+/// Search query and sort mode line.
+#[derive(Component)]
+pub struct StorageFilterText;
+
+pub struct StorageDetailPane;
+
+impl DetailPaneContext for StorageDetailPane {
+    type LeftGridMarker = StorageItemsGrid;
+    type RightGridMarker = StoragePlayerGrid;
+
+    const LEFT_FOCUS: FocusPanel = FocusPanel::StorageItems;
+    const RIGHT_FOCUS: FocusPanel = FocusPanel::PlayerInventory;
+
+    fn source_from_left_grid(selection: &ItemGridSelection) -> InfoPanelSource {
+        InfoPanelSource::Storage {
+            selected_index: selection.selected_index,
+        }
+    }
+
+    fn source_from_right_grid(selection: &ItemGridSelection) -> InfoPanelSource {
+        InfoPanelSource::Inventory {
+            selected_index: selection.selected_index,
+        }
+    }
+}
+
+/// Type-safe handle for the storage modal. Opened from the merchant with
+/// [`GameAction::OpenStorage`](crate::input::GameAction::OpenStorage).
+pub struct StorageModal;
+
+impl RegisteredModal for StorageModal {
+    type Root = StorageModalRoot;
+    const MODAL_TYPE: ModalType = ModalType::Storage;
+
+    fn spawn(world: &mut World) {
+        world.run_system_cached(do_spawn_storage_modal).ok();
+    }
+}
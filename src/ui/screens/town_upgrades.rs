@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::{ShowToast, TownUpgradeRequest};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::item::ItemRegistry;
+use crate::location::{
+    CurrentTown, TownBuilding, TownState, MAX_BUILDING_LEVEL, STOCK_SLOTS_PER_LEVEL,
+};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(0.85, 0.7, 0.4);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+pub struct TownUpgradesScreenPlugin;
+
+impl Plugin for TownUpgradesScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TownUpgradesSelection>()
+            .add_systems(OnEnter(AppState::TownUpgrades), spawn_town_upgrades_screen)
+            .add_systems(OnExit(AppState::TownUpgrades), despawn_town_upgrades_screen)
+            .add_systems(
+                Update,
+                open_town_upgrades_screen
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_town_upgrades_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_town_upgrades_body.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::TownUpgrades)),
+            );
+    }
+}
+
+/// Index into `TownBuilding::ALL` of the highlighted building.
+#[derive(Resource, Default)]
+struct TownUpgradesSelection(usize);
+
+#[derive(Component)]
+struct TownUpgradesScreenRoot;
+
+#[derive(Component)]
+struct TownUpgradesBody;
+
+fn open_town_upgrades_screen(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenTownUpgrades || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::TownUpgrades);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("town_upgrades.not_in_town")));
+        }
+    }
+}
+
+fn spawn_town_upgrades_screen(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<TownUpgradesSelection>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            TownUpgradesScreenRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("town_upgrades.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                TownUpgradesBody,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("town_upgrades.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_town_upgrades_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<TownUpgradesSelection>,
+    mut upgrade_writer: MessageWriter<TownUpgradeRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = TownBuilding::ALL.len();
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Select => {
+                upgrade_writer.write(TownUpgradeRequest(TownBuilding::ALL[selection.0]));
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenTownUpgrades => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What reaching `level` gets the player: recipes for the blacksmith and alchemist, stock
+/// slots for the store.
+fn perk_text(loc: &Localization, building: TownBuilding, level: u32) -> String {
+    if building == TownBuilding::Store {
+        return loc.format(
+            "town_upgrades.perk_stock",
+            &[("slots", &STOCK_SLOTS_PER_LEVEL)],
+        );
+    }
+    let recipes: Vec<&str> = building
+        .recipes_unlocked_at(level)
+        .map(|recipe| recipe.spec().name)
+        .collect();
+    loc.format(
+        "town_upgrades.perk_recipes",
+        &[("recipes", &recipes.join(", "))],
+    )
+}
+
+/// Every building in the current town with its level, what the next level unlocks and what
+/// it costs.
+fn body_text(
+    loc: &Localization,
+    state: &TownState,
+    town: &CurrentTown,
+    selected: usize,
+    registry: &ItemRegistry,
+) -> String {
+    let mut lines = Vec::new();
+    for (index, &building) in TownBuilding::ALL.iter().enumerate() {
+        let level = state.level(town.0, building);
+        let next = match state.upgrade_cost(town.0, building) {
+            Some(cost) => {
+                let materials: Vec<String> = cost
+                    .materials
+                    .iter()
+                    .map(|&(item_id, quantity)| {
+                        let name = loc.item_name(item_id, &registry.get(item_id).name);
+                        format!("{quantity} {name}")
+                    })
+                    .collect();
+                loc.format(
+                    "town_upgrades.next",
+                    &[
+                        ("perk", &perk_text(loc, building, level + 1)),
+                        ("gold", &cost.gold),
+                        ("materials", &materials.join(", ")),
+                    ],
+                )
+            }
+            None => loc.get("town_upgrades.max").to_string(),
+        };
+        let marker = if index == selected { ">" } else { " " };
+        let line = loc.format(
+            "town_upgrades.row",
+            &[
+                ("building", &loc.get(building.label_key())),
+                ("level", &level),
+                ("max", &MAX_BUILDING_LEVEL),
+            ],
+        );
+        lines.push(format!("{marker} {line}  {next}"));
+    }
+    lines.join("\n")
+}
+
+fn update_town_upgrades_body(
+    state: Res<TownState>,
+    town: Res<CurrentTown>,
+    selection: Res<TownUpgradesSelection>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut body: Query<&mut Text, With<TownUpgradesBody>>,
+    added: Query<(), Added<TownUpgradesBody>>,
+) {
+    if !state.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = body.single_mut() {
+        **text = body_text(&loc, &state, &town, selection.0, &registry);
+    }
+}
+
+fn despawn_town_upgrades_screen(
+    mut commands: Commands,
+    root: Query<Entity, With<TownUpgradesScreenRoot>>,
+) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
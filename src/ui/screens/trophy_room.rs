@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::location::CurrentTown;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::trophy::{TrophyCase, TrophyRequest, PEDESTALS};
+use crate::ui::screens::modal::ActiveModal;
+
+const TITLE_COLOR: Color = Color::srgb(0.95, 0.8, 0.35);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+pub struct TrophyRoomScreenPlugin;
+
+impl Plugin for TrophyRoomScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PedestalSelection>()
+            .add_systems(OnEnter(AppState::TrophyRoom), spawn_trophy_room)
+            .add_systems(OnExit(AppState::TrophyRoom), despawn_trophy_room)
+            .add_systems(
+                Update,
+                open_trophy_room
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_trophy_room_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_trophy_room_body.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::TrophyRoom)),
+            );
+    }
+}
+
+/// Index of the highlighted pedestal.
+#[derive(Resource, Default)]
+struct PedestalSelection(usize);
+
+#[derive(Component)]
+struct TrophyRoomRoot;
+
+#[derive(Component)]
+struct TrophyRoomBody;
+
+fn open_trophy_room(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenTrophyRoom || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::TrophyRoom);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("trophy.not_in_town")));
+        }
+    }
+}
+
+fn spawn_trophy_room(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<PedestalSelection>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            TrophyRoomRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("trophy.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                TrophyRoomBody,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("trophy.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_trophy_room_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<PedestalSelection>,
+    mut trophy_writer: MessageWriter<TrophyRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + PEDESTALS - 1) % PEDESTALS;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % PEDESTALS;
+            }
+            GameAction::Navigate(NavigationDirection::Right) | GameAction::Select => {
+                trophy_writer.write(TrophyRequest::Cycle {
+                    pedestal: selection.0,
+                    forward: true,
+                });
+            }
+            GameAction::Navigate(NavigationDirection::Left) => {
+                trophy_writer.write(TrophyRequest::Cycle {
+                    pedestal: selection.0,
+                    forward: false,
+                });
+            }
+            GameAction::DismissTip => {
+                trophy_writer.write(TrophyRequest::Clear(selection.0));
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenTrophyRoom => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Every pedestal with what stands on it, then how much of the collection is on show.
+fn body_text(loc: &Localization, case: &TrophyCase, selected: usize) -> String {
+    let mut lines: Vec<String> = case
+        .pedestals()
+        .iter()
+        .enumerate()
+        .map(|(index, trophy)| {
+            let shown = match trophy {
+                Some(trophy) => trophy.name(loc),
+                None => loc.get("trophy.empty").to_string(),
+            };
+            let marker = if index == selected { ">" } else { " " };
+            let pedestal = loc.format("trophy.pedestal", &[("number", &(index + 1))]);
+            format!("{marker} {pedestal}: {shown}")
+        })
+        .collect();
+    lines.push(String::new());
+
+    lines.push(if case.unlocked().is_empty() {
+        loc.get("trophy.none_yet").to_string()
+    } else {
+        let on_show = case.pedestals().iter().flatten().count();
+        loc.format(
+            "trophy.collection",
+            &[("shown", &on_show), ("count", &case.unlocked().len())],
+        )
+    });
+    lines.join("\n")
+}
+
+fn update_trophy_room_body(
+    case: Res<TrophyCase>,
+    selection: Res<PedestalSelection>,
+    loc: Res<Localization>,
+    mut body: Query<&mut Text, With<TrophyRoomBody>>,
+    added: Query<(), Added<TrophyRoomBody>>,
+) {
+    if !case.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = body.single_mut() {
+        **text = body_text(&loc, &case, selection.0);
+    }
+}
+
+fn despawn_trophy_room(mut commands: Commands, root: Query<Entity, With<TrophyRoomRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
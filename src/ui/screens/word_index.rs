@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::magic::{scroll_depth, KnownWords, WordId, WordRegistry};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+
+use super::spellcraft::effect_text;
+
+const TITLE_COLOR: Color = Color::srgb(0.55, 0.6, 1.0);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+const DETAIL_COLOR: Color = Color::srgb(0.8, 0.85, 1.0);
+
+pub struct WordIndexPlugin;
+
+impl Plugin for WordIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WordIndexSelection>()
+            .add_systems(OnEnter(AppState::WordIndex), spawn_word_index)
+            .add_systems(OnExit(AppState::WordIndex), despawn_word_index)
+            .add_systems(
+                Update,
+                open_word_index
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_word_index_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_word_index.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::WordIndex)),
+            );
+    }
+}
+
+/// Index into `WordId::ALL` of the highlighted word.
+#[derive(Resource, Default)]
+struct WordIndexSelection(usize);
+
+#[derive(Component)]
+struct WordIndexRoot;
+
+#[derive(Component)]
+struct WordIndexList;
+
+#[derive(Component)]
+struct WordIndexDetail;
+
+fn open_word_index(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    for action in action_reader.read() {
+        if *action == GameAction::OpenWordIndex && active_modal.modal.is_none() {
+            state_requests.write(StateTransitionRequest::WordIndex);
+        }
+    }
+}
+
+fn spawn_word_index(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<WordIndexSelection>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            WordIndexRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("word_index.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                WordIndexList,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                WordIndexDetail,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(DETAIL_COLOR),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("word_index.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_word_index_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<WordIndexSelection>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = WordId::ALL.len();
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenWordIndex => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Every word, hidden until learned, then how many are known.
+fn list_text(loc: &Localization, known: &KnownWords, selected: usize) -> String {
+    let mut lines: Vec<String> = WordId::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, &word)| {
+            let marker = if index == selected { ">" } else { " " };
+            let name = if known.knows(word) {
+                loc.get(word.name_key())
+            } else {
+                loc.get("word_index.unknown")
+            };
+            format!("{marker} {name}")
+        })
+        .collect();
+    lines.push(String::new());
+    lines.push(loc.format(
+        "word_index.progress",
+        &[
+            ("known", &known.words().len()),
+            ("count", &WordId::ALL.len()),
+        ],
+    ));
+    lines.join("\n")
+}
+
+/// What the highlighted word does and where it came from, or where to look for it.
+fn detail_text(
+    loc: &Localization,
+    registry: &WordRegistry,
+    known: &KnownWords,
+    word: WordId,
+) -> String {
+    let depth = scroll_depth(word);
+    if !known.knows(word) {
+        return loc.format("word_index.undiscovered", &[("depth", &depth.unwrap_or(1))]);
+    }
+    let spec = registry.spec(word);
+    let source = match depth {
+        Some(_) => loc.get("word_index.from_scroll"),
+        None => loc.get("word_index.starting"),
+    };
+    loc.format(
+        "word_index.detail",
+        &[
+            ("effect", &effect_text(loc, spec.effect)),
+            ("mana", &spec.mana_cost),
+            ("source", &source),
+        ],
+    )
+}
+
+fn update_word_index(
+    known: Res<KnownWords>,
+    registry: Res<WordRegistry>,
+    selection: Res<WordIndexSelection>,
+    loc: Res<Localization>,
+    mut list: Query<&mut Text, With<WordIndexList>>,
+    mut detail: Query<&mut Text, (With<WordIndexDetail>, Without<WordIndexList>)>,
+    added: Query<(), Added<WordIndexRoot>>,
+) {
+    if !known.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = list.single_mut() {
+        **text = list_text(&loc, &known, selection.0);
+    }
+    if let Ok(mut text) = detail.single_mut() {
+        **text = detail_text(&loc, &registry, &known, WordId::ALL[selection.0]);
+    }
+}
+
+fn despawn_word_index(mut commands: Commands, root: Query<Entity, With<WordIndexRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
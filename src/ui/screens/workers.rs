@@ -0,0 +1,246 @@
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::item::ItemRegistry;
+use crate::location::CurrentTown;
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::screens::modal::ActiveModal;
+use crate::workers::{WorkerKind, WorkerRequest, Workers, MAX_WORKERS, MAX_WORKER_LEVEL};
+
+const TITLE_COLOR: Color = Color::srgb(0.85, 0.7, 0.4);
+const HINT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+pub struct WorkersScreenPlugin;
+
+impl Plugin for WorkersScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorkersSelection>()
+            .add_systems(OnEnter(AppState::Workers), spawn_workers_screen)
+            .add_systems(OnExit(AppState::Workers), despawn_workers_screen)
+            .add_systems(
+                Update,
+                open_workers_screen
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_workers_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    update_workers_body.in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::Workers)),
+            );
+    }
+}
+
+/// Index of the highlighted worker.
+#[derive(Resource, Default)]
+struct WorkersSelection(usize);
+
+#[derive(Component)]
+struct WorkersScreenRoot;
+
+#[derive(Component)]
+struct WorkersBody;
+
+fn open_workers_screen(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenWorkers || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::Workers);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("workers.not_in_town")));
+        }
+    }
+}
+
+fn spawn_workers_screen(
+    mut commands: Commands,
+    loc: Res<Localization>,
+    mut selection: ResMut<WorkersSelection>,
+) {
+    selection.0 = 0;
+    commands
+        .spawn((
+            WorkersScreenRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("workers.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(TITLE_COLOR),
+            ));
+
+            parent.spawn((
+                WorkersBody,
+                Text::new(""),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent.spawn((
+                Text::new(loc.format(
+                    "workers.hint",
+                    &[
+                        ("miner", &WorkerKind::Miner.hire_cost()),
+                        ("herbalist", &WorkerKind::Herbalist.hire_cost()),
+                    ],
+                )),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(HINT_COLOR),
+            ));
+        });
+}
+
+fn handle_workers_input(
+    mut action_reader: MessageReader<GameAction>,
+    workers: Res<Workers>,
+    mut selection: ResMut<WorkersSelection>,
+    mut worker_writer: MessageWriter<WorkerRequest>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let count = workers.workers().len().max(1);
+    // Firing can leave the highlight past the end of the list.
+    selection.0 = selection.0.min(count - 1);
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.0 = (selection.0 + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.0 = (selection.0 + 1) % count;
+            }
+            GameAction::Select => {
+                worker_writer.write(WorkerRequest::Upgrade(selection.0));
+            }
+            GameAction::NextTab => {
+                worker_writer.write(WorkerRequest::Hire(WorkerKind::Miner));
+            }
+            GameAction::PrevTab => {
+                worker_writer.write(WorkerRequest::Hire(WorkerKind::Herbalist));
+            }
+            GameAction::DismissTip => {
+                worker_writer.write(WorkerRequest::Fire(selection.0));
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenWorkers => {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Payroll, then every worker with their level, pace and what they gather.
+fn body_text(
+    loc: &Localization,
+    workers: &Workers,
+    selected: usize,
+    registry: &ItemRegistry,
+) -> String {
+    let mut lines = vec![loc.format(
+        "workers.payroll",
+        &[
+            ("hired", &workers.workers().len()),
+            ("max", &MAX_WORKERS),
+            ("wages", &workers.daily_wages()),
+        ],
+    )];
+    if workers.storage_full {
+        lines.push(loc.get("workers.storage_full").to_string());
+    }
+    lines.push(String::new());
+
+    if workers.workers().is_empty() {
+        lines.push(loc.get("workers.empty").to_string());
+    }
+    for (index, worker) in workers.workers().iter().enumerate() {
+        let products: Vec<String> = worker
+            .kind
+            .products(worker.level)
+            .into_iter()
+            .map(|item_id| {
+                loc.item_name(item_id, &registry.get(item_id).name)
+                    .to_string()
+            })
+            .collect();
+        let upgrade = match worker.upgrade_cost() {
+            Some(cost) => loc.format("workers.upgrade_cost", &[("gold", &cost)]),
+            None => loc.get("workers.max").to_string(),
+        };
+        let marker = if index == selected { ">" } else { " " };
+        let line = loc.format(
+            "workers.row",
+            &[
+                ("worker", &loc.get(worker.kind.label_key())),
+                ("level", &worker.level),
+                ("max", &MAX_WORKER_LEVEL),
+                ("secs", &(worker.seconds_per_item().round() as u32)),
+                ("items", &products.join(", ")),
+                ("wage", &worker.wage()),
+            ],
+        );
+        lines.push(format!("{marker} {line}  {upgrade}"));
+    }
+    lines.join("\n")
+}
+
+fn update_workers_body(
+    workers: Res<Workers>,
+    selection: Res<WorkersSelection>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut body: Query<&mut Text, With<WorkersBody>>,
+    added: Query<(), Added<WorkersBody>>,
+) {
+    if !workers.is_changed() && !selection.is_changed() && added.is_empty() {
+        return;
+    }
+
+    if let Ok(mut text) = body.single_mut() {
+        **text = body_text(&loc, &workers, selection.0, &registry);
+    }
+}
+
+fn despawn_workers_screen(mut commands: Commands, root: Query<Entity, With<WorkersScreenRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
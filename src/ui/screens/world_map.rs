@@ -0,0 +1,402 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::dungeon::DungeonState;
+use crate::game::{
+    EscortOffers, EscortRequest, ExploreFieldRequest, Journey, ShowToast, TravelRequest,
+    TravelResult,
+};
+use crate::i18n::Localization;
+use crate::input::{GameAction, NavigationDirection};
+use crate::location::{CurrentTown, TownId, TravelRoute};
+use crate::states::{AppState, ScreenSet, StateTransitionRequest};
+use crate::ui::{column_node, Palette};
+use crate::ui::screens::modal::ActiveModal;
+
+/// Pixels per league on the drawn map.
+const MAP_SCALE: f32 = 12.0;
+const MAP_PADDING: f32 = 24.0;
+const MARKER_SIZE: f32 = 14.0;
+
+const NORMAL_COLOR: Color = Color::WHITE;
+const HERE_COLOR: Color = Color::srgb(0.5, 1.0, 0.5);
+
+pub struct WorldMapPlugin;
+
+impl Plugin for WorldMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldMapSelection>()
+            .add_systems(OnEnter(AppState::WorldMap), spawn_world_map)
+            .add_systems(OnExit(AppState::WorldMap), despawn_world_map)
+            .add_systems(
+                Update,
+                open_world_map
+                    .run_if(on_message::<GameAction>)
+                    .run_if(in_state(AppState::Dungeon))
+                    .in_set(ScreenSet::Input),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_world_map_input
+                        .run_if(on_message::<GameAction>)
+                        .in_set(ScreenSet::Input),
+                    (
+                        update_world_map_rows.run_if(resource_changed::<WorldMapSelection>),
+                        update_world_map_status,
+                    )
+                        .chain()
+                        .in_set(ScreenSet::Render),
+                )
+                    .run_if(in_state(AppState::WorldMap)),
+            )
+            .add_systems(
+                Update,
+                announce_arrival
+                    .run_if(on_message::<TravelResult>)
+                    .in_set(ScreenSet::Render),
+            );
+    }
+}
+
+/// The highlighted destination and whether rows show escort contracts instead of
+/// paid travel.
+#[derive(Resource, Default)]
+struct WorldMapSelection {
+    // Index into `TownId::ALL`
+    index: usize,
+    escort: bool,
+}
+
+#[derive(Component)]
+struct WorldMapRoot;
+
+#[derive(Component)]
+struct WorldMapRow(usize);
+
+#[derive(Component)]
+struct WorldMapStatus;
+
+/// Where the map can send the player: paid travel, an escort contract, or the home field.
+#[derive(SystemParam)]
+struct WorldMapRequests<'w> {
+    travel_writer: MessageWriter<'w, TravelRequest>,
+    escort_writer: MessageWriter<'w, EscortRequest>,
+    field_writer: MessageWriter<'w, ExploreFieldRequest>,
+}
+
+fn open_world_map(
+    mut action_reader: MessageReader<GameAction>,
+    active_modal: Res<ActiveModal>,
+    dungeon: Res<DungeonState>,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for action in action_reader.read() {
+        if *action != GameAction::OpenWorldMap || active_modal.modal.is_some() {
+            continue;
+        }
+
+        if dungeon.current_location == Some(town.0.spec().home) {
+            state_requests.write(StateTransitionRequest::WorldMap);
+        } else {
+            toast_writer.write(ShowToast::new(loc.get("world_map.not_in_town")));
+        }
+    }
+}
+
+fn spawn_world_map(
+    mut commands: Commands,
+    town: Res<CurrentTown>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut selection: ResMut<WorldMapSelection>,
+    mut offers: ResMut<EscortOffers>,
+) {
+    selection.index = TownId::ALL.iter().position(|t| *t == town.0).unwrap_or(0);
+    selection.escort = false;
+    offers.refresh(town.0);
+
+    commands
+        .spawn((
+            WorldMapRoot,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(loc.get("world_map.title")),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(palette.selection),
+            ));
+
+            spawn_map(parent, town.0, &loc);
+
+            parent.spawn(column_node(8.0)).with_children(|list| {
+                for (idx, &destination) in TownId::ALL.iter().enumerate() {
+                    list.spawn((
+                        WorldMapRow(idx),
+                        Text::new(row_text(&loc, town.0, destination, None)),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(NORMAL_COLOR),
+                    ));
+                }
+            });
+
+            parent.spawn((
+                WorldMapStatus,
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.6, 0.3)),
+            ));
+
+            parent.spawn((
+                Text::new(loc.get("world_map.hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            ));
+        });
+}
+
+/// Draws each town as a marker at its map position, scaled to fit the furthest town.
+fn spawn_map(parent: &mut ChildSpawnerCommands, current: TownId, loc: &Localization) {
+    let extent = TownId::ALL
+        .iter()
+        .map(|town| town.spec().map_position)
+        .fold(Vec2::ZERO, Vec2::max);
+    let size = extent * MAP_SCALE + Vec2::splat(MAP_PADDING * 2.0);
+
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(size.x + 160.0),
+                height: Val::Px(size.y),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.15, 0.2, 0.12)),
+        ))
+        .with_children(|map| {
+            for &town in TownId::ALL {
+                let pos = town.spec().map_position * MAP_SCALE + Vec2::splat(MAP_PADDING);
+                let color = if town == current { HERE_COLOR } else { NORMAL_COLOR };
+
+                map.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(pos.x - MARKER_SIZE / 2.0),
+                        top: Val::Px(pos.y - MARKER_SIZE / 2.0),
+                        width: Val::Px(MARKER_SIZE),
+                        height: Val::Px(MARKER_SIZE),
+                        border_radius: BorderRadius::MAX,
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                ));
+                map.spawn((
+                    Text::new(loc.get(town.name_key())),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(pos.x + MARKER_SIZE),
+                        top: Val::Px(pos.y - MARKER_SIZE / 2.0),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+/// Describes the trip to `destination`, or the caravan contract there when `offers` is
+/// given.
+fn row_text(
+    loc: &Localization,
+    current: TownId,
+    destination: TownId,
+    offers: Option<&EscortOffers>,
+) -> String {
+    let name = loc.get(destination.name_key());
+    if destination == current {
+        let key = if current.spec().field.is_some() {
+            "world_map.here_field"
+        } else {
+            "world_map.here"
+        };
+        return loc.format(key, &[("town", &name)]);
+    }
+
+    if let Some(contract) = offers.and_then(|offers| offers.0.get(&destination)) {
+        return loc.format(
+            "world_map.escort",
+            &[
+                ("town", &name),
+                ("legs", &contract.legs.len()),
+                ("danger", &contract.danger()),
+                ("pay", &contract.payout()),
+                ("patron", &loc.get(contract.patron.name_key())),
+            ],
+        );
+    }
+
+    let route = TravelRoute::new(current, destination);
+    loc.format(
+        "world_map.route",
+        &[
+            ("town", &name),
+            ("distance", &format!("{:.0}", route.distance)),
+            ("cost", &route.gold_cost()),
+            ("time", &format!("{:.1}", route.duration().as_secs_f32())),
+        ],
+    )
+}
+
+fn handle_world_map_input(
+    mut action_reader: MessageReader<GameAction>,
+    mut selection: ResMut<WorldMapSelection>,
+    journey: Option<Res<Journey>>,
+    requests: WorldMapRequests,
+    town: Res<CurrentTown>,
+    mut state_requests: MessageWriter<StateTransitionRequest>,
+) {
+    let WorldMapRequests {
+        mut travel_writer,
+        mut escort_writer,
+        mut field_writer,
+    } = requests;
+    let count = TownId::ALL.len();
+
+    for action in action_reader.read() {
+        match action {
+            GameAction::Navigate(NavigationDirection::Up) => {
+                selection.index = (selection.index + count - 1) % count;
+            }
+            GameAction::Navigate(NavigationDirection::Down) => {
+                selection.index = (selection.index + 1) % count;
+            }
+            GameAction::NextTab | GameAction::PrevTab if journey.is_none() => {
+                selection.escort = !selection.escort;
+            }
+            GameAction::Select if selection.escort && journey.is_none() => {
+                escort_writer.write(EscortRequest {
+                    to: TownId::ALL[selection.index],
+                });
+            }
+            // Choosing the town you're in heads out into its field.
+            GameAction::Select
+                if TownId::ALL[selection.index] == town.0 && journey.is_none() =>
+            {
+                field_writer.write(ExploreFieldRequest);
+            }
+            GameAction::Select => {
+                travel_writer.write(TravelRequest {
+                    to: TownId::ALL[selection.index],
+                });
+            }
+            GameAction::Back | GameAction::CloseModal | GameAction::OpenWorldMap
+                if journey.is_none() =>
+            {
+                state_requests.write(StateTransitionRequest::Dungeon);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_world_map_rows(
+    selection: Res<WorldMapSelection>,
+    town: Res<CurrentTown>,
+    offers: Res<EscortOffers>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
+    mut rows: Query<(&WorldMapRow, &mut Text, &mut TextColor)>,
+) {
+    let offers = selection.escort.then_some(&*offers);
+    for (row, mut text, mut color) in &mut rows {
+        **text = row_text(&loc, town.0, TownId::ALL[row.0], offers);
+        *color = TextColor(if row.0 == selection.index {
+            palette.selection
+        } else {
+            NORMAL_COLOR
+        });
+    }
+}
+
+fn update_world_map_status(
+    mut results: MessageReader<TravelResult>,
+    journey: Option<Res<Journey>>,
+    loc: Res<Localization>,
+    mut last_result: Local<String>,
+    mut status: Query<&mut Text, With<WorldMapStatus>>,
+) {
+    for result in results.read() {
+        *last_result = match result {
+            TravelResult::FailedNotEnoughGold { need, have } => loc.format(
+                "world_map.not_enough_gold",
+                &[("need", need), ("have", have)],
+            ),
+            TravelResult::FailedAlreadyThere => loc.get("world_map.already_here").to_string(),
+            TravelResult::FailedAlreadyTraveling
+            | TravelResult::Departed { .. }
+            | TravelResult::Arrived { .. } => String::new(),
+        };
+    }
+
+    let Ok(mut text) = status.single_mut() else {
+        return;
+    };
+    **text = match journey {
+        Some(journey) => loc.format(
+            "world_map.traveling",
+            &[
+                ("town", &loc.get(journey.route.to.name_key())),
+                ("time", &format!("{:.1}", journey.timer.remaining_secs())),
+            ],
+        ),
+        None => last_result.clone(),
+    };
+}
+
+fn announce_arrival(
+    mut results: MessageReader<TravelResult>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for result in results.read() {
+        if let TravelResult::Arrived { town } = result {
+            toast_writer.write(ShowToast::new(
+                loc.format("world_map.arrived", &[("town", &loc.get(town.name_key()))]),
+            ));
+        }
+    }
+}
+
+fn despawn_world_map(mut commands: Commands, root: Query<Entity, With<WorldMapRoot>>) {
+    if let Ok(entity) = root.single() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,146 @@
+//! Screenshots of the current frame, saved as timestamped PNGs (`gui` feature only).
+//!
+//! F12 takes one as the game looks. Shift+F12 hides every UI root for the frame the shot is
+//! taken on and shows them again once it has been captured; the toast with the saved path
+//! only appears after, so it never lands in the shot.
+
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured};
+
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::input::GameAction;
+
+/// Where screenshots are written, relative to the working directory.
+pub const SCREENSHOT_DIR: &str = "screenshots";
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HiddenUi>()
+            .add_systems(Update, take_screenshot.run_if(on_message::<GameAction>));
+    }
+}
+
+/// UI roots hidden for a clean shot, with the visibility each is restored to.
+#[derive(Resource, Default)]
+struct HiddenUi(Vec<(Entity, Visibility)>);
+
+/// The saved path of a requested shot, shown in a toast once it is captured.
+#[derive(Component)]
+struct ScreenshotPath(PathBuf);
+
+/// Top-level UI nodes, hidden for a clean shot.
+type UiRoots = (With<Node>, Without<ChildOf>);
+
+fn take_screenshot(
+    mut commands: Commands,
+    mut actions: MessageReader<GameAction>,
+    mut hidden: ResMut<HiddenUi>,
+    mut roots: Query<(Entity, &mut Visibility), UiRoots>,
+) {
+    let Some(clean) = actions.read().find_map(|action| match action {
+        GameAction::Screenshot => Some(false),
+        GameAction::CleanScreenshot => Some(true),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    if clean && hidden.0.is_empty() {
+        for (entity, mut visibility) in &mut roots {
+            hidden.0.push((entity, *visibility));
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    let path = screenshot_path(unix_secs());
+    commands
+        .spawn((Screenshot::primary_window(), ScreenshotPath(path.clone())))
+        .observe(save_to_disk(path))
+        .observe(finish_screenshot);
+}
+
+/// Shows the UI again and says where the shot went.
+fn finish_screenshot(
+    trigger: On<ScreenshotCaptured>,
+    paths: Query<&ScreenshotPath>,
+    mut hidden: ResMut<HiddenUi>,
+    mut visibilities: Query<&mut Visibility>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    for (entity, restored) in hidden.0.drain(..) {
+        if let Ok(mut visibility) = visibilities.get_mut(entity) {
+            *visibility = restored;
+        }
+    }
+    if let Ok(path) = paths.get(trigger.entity) {
+        toast_writer.write(ShowToast::new(
+            loc.format("screenshot.saved", &[("path", &path.0.display())]),
+        ));
+    }
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// A free path for a shot taken at `secs`, named after the UTC date and time. Shots in the
+/// same second get a number on the end.
+fn screenshot_path(secs: u64) -> PathBuf {
+    let stem = format!("randr_{}", timestamp(secs));
+    let dir = PathBuf::from(SCREENSHOT_DIR);
+    let mut path = dir.join(format!("{stem}.png"));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{stem}_{n}.png"));
+        n += 1;
+    }
+    path
+}
+
+/// `secs` after the Unix epoch as `YYYY-MM-DD_HH-MM-SS`, in UTC.
+fn timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let time = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}_{:02}-{:02}-{:02}",
+        time / 3_600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// The proleptic Gregorian date `days` after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_utc_dates_and_times() {
+        assert_eq!(timestamp(0), "1970-01-01_00-00-00");
+        assert_eq!(timestamp(951_782_400), "2000-02-29_00-00-00");
+        assert_eq!(timestamp(1_792_159_425), "2026-10-16_14-03-45");
+    }
+}
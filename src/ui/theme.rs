@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 
+use crate::assets::{GameSprites, HealthBarSlice, SpriteSheetKey};
+use crate::item::enums::{ItemQuality, QualityColor};
+use crate::settings::{ColorPalette, GameSettings};
+
 /// Trait for selectable list items that have an index and a display name.
 pub trait Selectable: Component {
     /// Returns the index of this item in the list.
@@ -95,3 +99,162 @@ where
         }
     }
 }
+
+/// Health bar colors as painted into the UI sheet.
+const SHEET_HEALTH_FILL: Color = Color::srgb_u8(228, 59, 68);
+const SHEET_HEALTH_SHADE: Color = Color::srgb_u8(158, 40, 53);
+
+/// Colors players have to tell apart at a glance, swapped as a set by the color-blind
+/// setting. Screens read these instead of keeping their own.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub kind: ColorPalette,
+    /// Text of the highlighted row in list screens.
+    pub selection: Color,
+    /// Health bar fill, and its darker lower edge.
+    pub health_fill: Color,
+    pub health_shade: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(ColorPalette::Standard)
+    }
+}
+
+impl Palette {
+    pub fn new(kind: ColorPalette) -> Self {
+        match kind {
+            ColorPalette::Standard => Self {
+                kind,
+                selection: Color::srgb(1.0, 0.9, 0.3),
+                health_fill: SHEET_HEALTH_FILL,
+                health_shade: SHEET_HEALTH_SHADE,
+            },
+            // Red and green blur together, so health is blue and highlights stay yellow.
+            ColorPalette::Deuteranopia | ColorPalette::Protanopia => Self {
+                kind,
+                selection: Color::srgb(0.94, 0.89, 0.26),
+                health_fill: Color::srgb(0.0, 0.45, 0.7),
+                health_shade: Color::srgb(0.0, 0.3, 0.48),
+            },
+            // Yellow washes out to white, so highlights are pink; red reads fine.
+            ColorPalette::Tritanopia => Self {
+                kind,
+                selection: Color::srgb(1.0, 0.45, 0.6),
+                health_fill: SHEET_HEALTH_FILL,
+                health_shade: SHEET_HEALTH_SHADE,
+            },
+        }
+    }
+
+    /// The color an item of `quality` is named in.
+    pub fn quality(&self, quality: ItemQuality) -> Color {
+        match (self.kind, quality) {
+            (ColorPalette::Standard, _) | (_, ItemQuality::Poor | ItemQuality::Normal) => {
+                quality.color()
+            }
+            (ColorPalette::Deuteranopia | ColorPalette::Protanopia, quality) => match quality {
+                ItemQuality::Improved => Color::srgb(0.35, 0.7, 0.9),
+                ItemQuality::WellForged => Color::srgb(0.2, 0.35, 1.0),
+                ItemQuality::Masterworked => Color::srgb(0.8, 0.47, 0.65),
+                _ => Color::srgb(0.9, 0.6, 0.0),
+            },
+            (ColorPalette::Tritanopia, quality) => match quality {
+                ItemQuality::Improved => Color::srgb(0.35, 0.7, 0.9),
+                ItemQuality::WellForged => Color::srgb(0.8, 0.47, 0.65),
+                ItemQuality::Masterworked => Color::srgb(1.0, 0.2, 0.6),
+                _ => Color::srgb(0.84, 0.37, 0.0),
+            },
+        }
+    }
+}
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Palette>().add_systems(
+            Update,
+            (
+                sync_palette.run_if(resource_changed::<GameSettings>),
+                recolor_health_bars,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn sync_palette(settings: Res<GameSettings>, mut palette: ResMut<Palette>) {
+    if palette.kind != settings.color_palette {
+        *palette = Palette::new(settings.color_palette);
+    }
+}
+
+/// Health bars are slices of the UI sheet rather than colored nodes, so their fill is
+/// repainted in the sheet itself. The colors last painted are remembered so the swap can be
+/// undone by the next one.
+fn recolor_health_bars(
+    palette: Res<Palette>,
+    mut painted: Local<Option<(Color, Color)>>,
+    game_sprites: Res<GameSprites>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (from_fill, from_shade) = painted.unwrap_or((SHEET_HEALTH_FILL, SHEET_HEALTH_SHADE));
+    if (from_fill, from_shade) == (palette.health_fill, palette.health_shade) {
+        return;
+    }
+    let Some(sheet) = game_sprites.get(SpriteSheetKey::UiAll) else {
+        return;
+    };
+    let (Some(layout), Some(image)) = (layouts.get(&sheet.layout), images.get_mut(&sheet.texture))
+    else {
+        return;
+    };
+
+    let swaps = [
+        (rgb(from_fill), rgb(palette.health_fill)),
+        (rgb(from_shade), rgb(palette.health_shade)),
+    ];
+    for slice in HealthBarSlice::ALL {
+        let Some(rect) = sheet.get(slice.as_str()).and_then(|i| layout.textures.get(i)) else {
+            continue;
+        };
+        for y in rect.min.y..rect.max.y {
+            for x in rect.min.x..rect.max.x {
+                let Some(pixel) = image.pixel_bytes_mut(UVec3::new(x, y, 0)) else {
+                    continue;
+                };
+                if let Some((_, to)) = swaps.iter().find(|(from, _)| pixel[..3] == *from) {
+                    pixel[..3].copy_from_slice(to);
+                }
+            }
+        }
+    }
+    *painted = Some((palette.health_fill, palette.health_shade));
+}
+
+fn rgb(color: Color) -> [u8; 3] {
+    let [r, g, b, _] = color.to_srgba().to_u8_array();
+    [r, g, b]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_palette_keeps_qualities_apart() {
+        for kind in ColorPalette::ALL {
+            let palette = Palette::new(kind);
+            let colors: Vec<_> = ItemQuality::ALL
+                .iter()
+                .map(|quality| rgb(palette.quality(*quality)))
+                .collect();
+            for (i, color) in colors.iter().enumerate() {
+                assert!(!colors[i + 1..].contains(color), "{kind:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use bon::Builder;
+
+use crate::assets::{GameSprites, SpriteSheetKey, UiAllSlice};
+use crate::economy::{Currency, Wallet};
+use crate::i18n::Localization;
+use crate::player::PlayerMarker;
+use crate::plugins::CurrencyChanged;
+use crate::ui::row_node;
+
+pub struct CurrencyDisplayPlugin;
+
+impl Plugin for CurrencyDisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_add_currency_display).add_systems(
+            Update,
+            update_currency_displays.run_if(on_message::<CurrencyChanged>),
+        );
+    }
+}
+
+/// The player's balance in one currency, kept up to date as it changes. Gold is shown
+/// with the coin icon, other currencies by name.
+#[derive(Component, Builder)]
+pub struct CurrencyDisplay {
+    #[builder(start_fn)]
+    pub currency: Currency,
+    #[builder(default = 16.0)]
+    pub font_size: f32,
+    #[builder(default = Color::srgb(1.0, 0.84, 0.0))]
+    pub text_color: Color,
+}
+
+#[derive(Component)]
+struct CurrencyDisplayText(Currency);
+
+fn on_add_currency_display(
+    trigger: On<Add, CurrencyDisplay>,
+    mut commands: Commands,
+    query: Query<&CurrencyDisplay>,
+    player: Query<&Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    game_sprites: Res<GameSprites>,
+) {
+    let entity = trigger.entity;
+    let Ok(display) = query.get(entity) else {
+        return;
+    };
+    let currency = display.currency;
+    let amount = player.single().map_or(0, |wallet| wallet.balance(currency));
+
+    let gold_image = (currency == Currency::Gold)
+        .then(|| game_sprites.get(SpriteSheetKey::UiAll))
+        .flatten()
+        .and_then(|s| s.image_node(UiAllSlice::GoldIcon.as_str()));
+    let icon_size = display.font_size;
+    let text = (
+        CurrencyDisplayText(currency),
+        Text::new(format_currency(&loc, currency, amount)),
+        TextFont {
+            font_size: display.font_size,
+            ..default()
+        },
+        TextColor(display.text_color),
+    );
+
+    commands
+        .entity(entity)
+        .remove::<CurrencyDisplay>()
+        .insert(row_node(4.0))
+        .with_children(|row| {
+            if let Some(img) = gold_image {
+                row.spawn((
+                    Node {
+                        width: Val::Px(icon_size),
+                        height: Val::Px(icon_size),
+                        ..default()
+                    },
+                    img,
+                ));
+            }
+            row.spawn(text);
+        });
+}
+
+fn update_currency_displays(
+    mut events: MessageReader<CurrencyChanged>,
+    loc: Res<Localization>,
+    mut texts: Query<(&CurrencyDisplayText, &mut Text)>,
+) {
+    for event in events.read() {
+        for (display, mut text) in &mut texts {
+            if display.0 == event.currency {
+                **text = format_currency(&loc, event.currency, event.new_total);
+            }
+        }
+    }
+}
+
+/// Gold is shown as a bare number next to its icon; other currencies are named.
+fn format_currency(loc: &Localization, currency: Currency, amount: i32) -> String {
+    match currency {
+        Currency::Gold => amount.to_string(),
+        _ => loc.format(
+            "currency.amount",
+            &[("amount", &amount), ("currency", &loc.get(currency.name_key()))],
+        ),
+    }
+}
@@ -16,6 +16,8 @@ impl Plugin for GoldDisplayPlugin {
 pub struct GoldDisplay {
     #[builder(start_fn)]
     pub amount: i32,
+    /// Banked gold shown alongside the carried amount, if set.
+    pub banked: Option<i32>,
     #[builder(default = 16.0)]
     pub font_size: f32,
     #[builder(default = Color::srgb(0.4, 0.25, 0.15))]
@@ -54,7 +56,7 @@ fn on_add_gold_display(
             }
 
             row.spawn((
-                Text::new(format!("{}", gold_display.amount)),
+                Text::new(format_gold(gold_display.amount, gold_display.banked)),
                 TextFont {
                     font_size: gold_display.font_size,
                     ..default()
@@ -63,3 +65,11 @@ fn on_add_gold_display(
             ));
         });
 }
+
+/// Formats carried gold, with the banked amount appended when present.
+pub fn format_gold(carried: i32, banked: Option<i32>) -> String {
+    match banked {
+        Some(banked) => format!("{} ({} banked)", carried, banked),
+        None => format!("{}", carried),
+    }
+}
@@ -2,8 +2,12 @@ use bevy::prelude::*;
 use bon::Builder;
 
 use crate::assets::GameFonts;
-use crate::item::Item;
+use crate::i18n::Localization;
+use crate::inventory::EquipmentComparison;
+use crate::item::enums::ItemQuality;
+use crate::item::{Item, ItemId};
 use crate::stats::StatType;
+use crate::ui::Palette;
 
 use super::{ItemStatsDisplay, OutlinedText};
 
@@ -37,25 +41,27 @@ pub struct ItemDetailDisplay {
     item: ItemData,
     #[builder(default = 1)]
     quantity: u32,
-    comparison: Option<Vec<(StatType, i32)>>,
+    comparison: Option<EquipmentComparison>,
     price: Option<PriceDisplay>,
 }
 
 struct ItemData {
+    item_id: ItemId,
     name: String,
     item_type: String,
     quality_name: String,
-    quality_color: Color,
+    quality: ItemQuality,
     stats: Vec<(StatType, i32)>,
 }
 
 impl From<&Item> for ItemData {
     fn from(item: &Item) -> Self {
         Self {
+            item_id: item.item_id,
             name: item.name.clone(),
             item_type: format!("{}", item.item_type),
             quality_name: item.quality.display_name().to_string(),
-            quality_color: item.quality.color(),
+            quality: item.quality,
             stats: item
                 .stats
                 .stats()
@@ -71,16 +77,18 @@ fn on_add_item_detail_display(
     mut commands: Commands,
     query: Query<&ItemDetailDisplay>,
     game_fonts: Res<GameFonts>,
+    loc: Res<Localization>,
+    palette: Res<Palette>,
 ) {
     let entity = trigger.entity;
     let Ok(display) = query.get(entity) else {
         return;
     };
 
-    let name = display.item.name.clone();
+    let name = loc.item_name(display.item.item_id, &display.item.name).to_string();
     let item_type = display.item.item_type.clone();
     let quality_name = display.item.quality_name.clone();
-    let quality_color = display.item.quality_color;
+    let quality_color = palette.quality(display.item.quality);
     let quantity = display.quantity;
     let stats = display.item.stats.clone();
     let comparison = display.comparison.clone();
@@ -131,7 +139,20 @@ fn on_add_item_detail_display(
                 ));
             }
 
-            if !stats.is_empty() {
+            if let Some(comparison) = &comparison {
+                let label = match &comparison.equipped_name {
+                    Some(equipped) => loc.format("inventory.compared_to", &[("item", equipped)]),
+                    None => loc.get("inventory.slot_empty").to_string(),
+                };
+                parent.spawn((
+                    Text::new(label),
+                    game_fonts.pixel_font(12.0),
+                    TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                ));
+            }
+
+            let has_deltas = comparison.as_ref().is_some_and(|c| !c.deltas.is_empty());
+            if !stats.is_empty() || has_deltas {
                 parent.spawn(
                     ItemStatsDisplay::builder(stats)
                         .font_size(14.0)
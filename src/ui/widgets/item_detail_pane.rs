@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use super::nine_slice::spawn_nine_slice_panel;
@@ -21,6 +22,13 @@ pub struct ItemDetailPane {
 #[derive(Component)]
 pub struct ItemDetailPaneContent;
 
+/// The detail panes and their content containers, for the systems that fill them in.
+#[derive(SystemParam)]
+pub struct DetailPaneQueries<'w, 's> {
+    pub panes: Query<'w, 's, Ref<'static, ItemDetailPane>>,
+    pub content: Query<'w, 's, (Entity, Option<&'static Children>), With<ItemDetailPaneContent>>,
+}
+
 fn on_add_item_detail_pane(
     trigger: On<Add, ItemDetailPane>,
     mut commands: Commands,
@@ -9,14 +9,20 @@ pub struct ItemGridEntry {
     pub sprite_sheet_key: crate::assets::SpriteSheetKey,
     pub sprite_name: String,
     pub quantity: u32,
+    /// Draws a star in the cell's corner.
+    pub is_favorite: bool,
+    /// Draws a "new" marker in the cell's corner.
+    pub is_new: bool,
 }
 
 impl ItemGridEntry {
     pub fn from_inventory_item(inv_item: &InventoryItem) -> Self {
         Self {
-            sprite_sheet_key: inv_item.item.sprite.sheet_key,
+            sprite_sheet_key: inv_item.item.sprite.sheet_key.into(),
             sprite_name: inv_item.item.sprite.name.clone(),
             quantity: inv_item.quantity,
+            is_favorite: inv_item.item.is_favorite,
+            is_new: inv_item.is_new,
         }
     }
 
@@ -88,3 +94,6 @@ pub(super) struct GridItemSprite;
 
 #[derive(Component)]
 pub(super) struct GridItemQuantityText;
+
+#[derive(Component)]
+pub(super) struct GridItemBadge;
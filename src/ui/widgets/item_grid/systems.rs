@@ -2,7 +2,8 @@ use bevy::prelude::*;
 
 use super::cell::{GridCell, GridCellBundle, GridContainer};
 use super::components::{
-    GridItemQuantityText, GridItemSprite, ItemGrid, ItemGridFocusPanel, ItemGridSelection,
+    GridItemBadge, GridItemQuantityText, GridItemSprite, ItemGrid, ItemGridEntry,
+    ItemGridFocusPanel, ItemGridSelection,
 };
 use super::{CELL_SIZE, GAP, NINE_SLICE_INSET};
 use crate::assets::{GameFonts, GameSprites, GridSlotSlice, ShopBgSlice, SpriteSheetKey};
@@ -12,6 +13,35 @@ use crate::ui::widgets::outlined_text::{spawn_outlined_quantity_text, OutlinedQu
 use crate::ui::widgets::selector::{spawn_selector, AnimatedSelector};
 
 const ITEM_SPRITE_SIZE: f32 = 32.0;
+const BADGE_FONT_SIZE: f32 = 12.0;
+const FAVORITE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+const NEW_COLOR: Color = Color::srgb(0.4, 1.0, 0.4);
+
+/// Corner markers for favorite and newly added entries.
+fn spawn_badges(cell: &mut ChildSpawnerCommands, game_fonts: &GameFonts, entry: &ItemGridEntry) {
+    let badges = [
+        (entry.is_favorite, "*", FAVORITE_COLOR, Val::Px(2.0), Val::Auto),
+        (entry.is_new, "!", NEW_COLOR, Val::Auto, Val::Px(2.0)),
+    ];
+    for (shown, label, color, left, right) in badges {
+        if !shown {
+            continue;
+        }
+        cell.spawn((
+            GridItemBadge,
+            Text::new(label),
+            game_fonts.pixel_font(BADGE_FONT_SIZE),
+            TextColor(color),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left,
+                right,
+                ..default()
+            },
+        ));
+    }
+}
 
 pub fn on_add_item_grid(
     trigger: On<Add, ItemGrid>,
@@ -104,6 +134,7 @@ pub fn on_add_item_grid(
                                         GridItemQuantityText,
                                     );
                                 }
+                                spawn_badges(cell_content, &game_fonts, entry);
                             });
                         }
                     }
@@ -121,6 +152,7 @@ pub fn update_grid_items(
     grid_cells: Query<(Entity, &GridCell, Option<&Children>)>,
     item_sprites: Query<Entity, With<GridItemSprite>>,
     quantity_texts: Query<Entity, With<GridItemQuantityText>>,
+    badges: Query<Entity, With<GridItemBadge>>,
 ) {
     for (item_grid, item_grid_children) in &item_grids {
         let Some(container_children) = item_grid_children
@@ -137,10 +169,12 @@ pub fn update_grid_items(
 
             if let Some(children) = cell_children {
                 for cell_child in children.iter() {
-                    if item_sprites.contains(cell_child) || quantity_texts.contains(cell_child) {
-                        if commands.get_entity(cell_child).is_ok() {
-                            commands.entity(cell_child).despawn();
-                        }
+                    if (item_sprites.contains(cell_child)
+                        || quantity_texts.contains(cell_child)
+                        || badges.contains(cell_child))
+                        && commands.get_entity(cell_child).is_ok()
+                    {
+                        commands.entity(cell_child).despawn();
                     }
                 }
             }
@@ -170,6 +204,7 @@ pub fn update_grid_items(
                                 GridItemQuantityText,
                             );
                         }
+                        spawn_badges(cell_content, &game_fonts, entry);
                     });
                 }
             }
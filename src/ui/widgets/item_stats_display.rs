@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bon::Builder;
 
 use crate::assets::{GameFonts, GameSprites, ItemDetailIconsSlice};
+use crate::inventory::EquipmentComparison;
 use crate::stats::StatType;
 
 pub struct ItemStatsDisplayPlugin;
@@ -23,7 +24,9 @@ pub enum StatsDisplayMode {
 pub struct ItemStatsDisplay {
     #[builder(start_fn)]
     pub stats: Vec<(StatType, i32)>,
-    pub comparison: Option<Vec<(StatType, i32)>>,
+    /// When set, rows follow the comparison so stats only the equipped item has are listed
+    /// too, each with its change.
+    pub comparison: Option<EquipmentComparison>,
     #[builder(default = 18.0)]
     pub font_size: f32,
     #[builder(default = Color::srgb(0.4, 0.25, 0.15))]
@@ -44,8 +47,18 @@ fn on_add_item_stats_display(
         return;
     };
 
-    let stats = display.stats.clone();
-    let comparison = display.comparison.clone();
+    let rows: Vec<(StatType, i32, Option<i32>)> = match &display.comparison {
+        Some(comparison) => comparison
+            .deltas
+            .iter()
+            .map(|&(stat_type, value, delta)| (stat_type, value, Some(delta)))
+            .collect(),
+        None => display
+            .stats
+            .iter()
+            .map(|&(stat_type, value)| (stat_type, value, None))
+            .collect(),
+    };
     let font_size = display.font_size;
     let text_color = display.text_color;
     let mode = display.mode;
@@ -58,16 +71,7 @@ fn on_add_item_stats_display(
             ..default()
         })
         .with_children(|parent| {
-            for (stat_type, value) in stats {
-                let delta = comparison.as_ref().map(|comp_stats| {
-                    let equipped_value = comp_stats
-                        .iter()
-                        .find(|(t, _)| *t == stat_type)
-                        .map(|(_, v)| *v)
-                        .unwrap_or(0);
-                    value - equipped_value
-                });
-
+            for (stat_type, value, delta) in rows {
                 match mode {
                     StatsDisplayMode::TextOnly => {
                         parent
@@ -1,4 +1,5 @@
 mod column;
+mod currency_display;
 mod detail_pane_system;
 mod gold_display;
 mod icon_value_row;
@@ -10,21 +11,27 @@ mod list_item;
 mod nine_slice;
 mod outlined_text;
 mod player_stats;
+mod rich_text;
 mod row;
 mod selector;
 mod stack;
 mod stat_row;
+mod text_rows;
 mod three_slice;
 
 pub use detail_pane_system::{update_detail_pane_source, DetailPaneContext};
-pub use item_detail_pane::{ItemDetailPane, ItemDetailPaneContent, ItemDetailPanePlugin};
+pub use item_detail_pane::{
+    DetailPaneQueries, ItemDetailPane, ItemDetailPaneContent, ItemDetailPanePlugin,
+};
 pub use item_detail_display::{ItemDetailDisplay, ItemDetailDisplayPlugin, PriceDisplay};
 pub use column::{Column, ColumnPlugin};
 pub use nine_slice::spawn_nine_slice_panel;
 pub use row::{Row, RowPlugin};
 pub use stack::{Stack, StackPlugin};
+pub use text_rows::{TextRow, TextRows, TextRowsPlugin};
 pub use three_slice::spawn_three_slice_banner;
-pub use gold_display::{GoldDisplay, GoldDisplayPlugin};
+pub use currency_display::{CurrencyDisplay, CurrencyDisplayPlugin};
+pub use gold_display::{format_gold, GoldDisplay, GoldDisplayPlugin};
 pub use icon_value_row::{IconSource, IconValueRow, IconValueRowPlugin};
 pub use item_grid::{ItemGrid, ItemGridEntry, ItemGridFocusPanel, ItemGridPlugin, ItemGridSelection};
 pub use item_stats_display::{ItemStatsDisplay, ItemStatsDisplayPlugin, StatsDisplayMode};
@@ -36,5 +43,6 @@ pub use outlined_text::{
     spawn_outlined_quantity_text, OutlinedQuantityConfig, OutlinedText, OutlinedTextPlugin,
 };
 pub use player_stats::{PlayerStats, PlayerStatsPlugin};
+pub use rich_text::{parse_markup, MarkupSpan, RichText, RichTextPlugin};
 pub use selector::{spawn_selector, AnimatedSelector, SelectorPlugin};
 pub use stat_row::{StatRow, StatRowPlugin};
@@ -1,11 +1,16 @@
 use bevy::prelude::*;
 
 use crate::assets::{GameSprites, SpriteSheetKey, TravelBookSlice, UiAllSlice};
+use crate::economy::Wallet;
 use crate::entities::Progression;
-use crate::player::{PlayerGold, PlayerMarker};
+use crate::player::{BankedGold, PlayerMarker};
 use crate::stats::{StatSheet, StatType};
 use crate::ui::{row_node, UiText};
 
+use super::format_gold;
+
+const MANA_COLOR: Color = Color::srgb(0.4, 0.6, 1.0);
+
 pub struct PlayerStatsPlugin;
 
 impl Plugin for PlayerStatsPlugin {
@@ -14,7 +19,12 @@ impl Plugin for PlayerStatsPlugin {
             .add_systems(Startup, spawn_player_stats)
             .add_systems(
                 Update,
-                (update_gold_display, update_hp_display, update_xp_display),
+                (
+                    update_gold_display,
+                    update_hp_display,
+                    update_mana_display,
+                    update_xp_display,
+                ),
             );
     }
 }
@@ -35,6 +45,10 @@ pub struct PlayerGoldText;
 #[derive(Component)]
 pub struct PlayerHpText;
 
+/// Marker for the mana text so it can be updated reactively.
+#[derive(Component)]
+pub struct PlayerManaText;
+
 /// Marker for the XP/Level text so it can be updated reactively.
 #[derive(Component)]
 pub struct PlayerXpText;
@@ -42,10 +56,10 @@ pub struct PlayerXpText;
 fn on_add_player_stats(
     trigger: On<Add, PlayerStats>,
     mut commands: Commands,
-    player: Query<(&StatSheet, &Progression, &PlayerGold), With<PlayerMarker>>,
+    player: Query<(&StatSheet, &Progression, &Wallet, &BankedGold), With<PlayerMarker>>,
     game_sprites: Res<GameSprites>,
 ) {
-    let Ok((stats, progression, gold)) = player.single() else {
+    let Ok((stats, progression, wallet, banked)) = player.single() else {
         return;
     };
     let entity = trigger.entity;
@@ -62,6 +76,8 @@ fn on_add_player_stats(
 
     let hp = stats.value(StatType::Health);
     let max_hp = stats.max_value(StatType::Health);
+    let mana = stats.value(StatType::Mana);
+    let max_mana = stats.max_value(StatType::Mana);
 
     let mut entity_commands = commands.entity(entity);
     entity_commands.insert(Node {
@@ -97,6 +113,15 @@ fn on_add_player_stats(
                 ));
             });
 
+        // Mana
+        stats_node.spawn((
+            PlayerManaText,
+            UiText::new(format!("MP {}/{}", mana, max_mana))
+                .size(16.0)
+                .color(MANA_COLOR)
+                .build(),
+        ));
+
         // Level & XP
         stats_node.spawn((
             PlayerXpText,
@@ -128,21 +153,27 @@ fn on_add_player_stats(
                 // Gold value
                 gold_row.spawn((
                     PlayerGoldText,
-                    UiText::new(format!("{}", gold.0)).size(16.0).gold().build(),
+                    UiText::new(format_gold(wallet.gold(), Some(banked.0)))
+                        .size(16.0)
+                        .gold()
+                        .build(),
                 ));
             });
     });
 }
 
+/// Carried or banked gold changed.
+type WalletOrBankChanged = Or<(Changed<Wallet>, Changed<BankedGold>)>;
+
 fn update_gold_display(
-    player: Query<&PlayerGold, (With<PlayerMarker>, Changed<PlayerGold>)>,
+    player: Query<(&Wallet, &BankedGold), (With<PlayerMarker>, WalletOrBankChanged)>,
     mut query: Query<&mut Text, With<PlayerGoldText>>,
 ) {
-    let Ok(gold) = player.single() else {
+    let Ok((wallet, banked)) = player.single() else {
         return;
     };
     for mut text in query.iter_mut() {
-        **text = format!("{}", gold.0);
+        **text = format_gold(wallet.gold(), Some(banked.0));
     }
 }
 
@@ -160,6 +191,20 @@ fn update_hp_display(
     }
 }
 
+fn update_mana_display(
+    player: Query<&StatSheet, (With<PlayerMarker>, Changed<StatSheet>)>,
+    mut query: Query<&mut Text, With<PlayerManaText>>,
+) {
+    let Ok(stats) = player.single() else {
+        return;
+    };
+    let mana = stats.value(StatType::Mana);
+    let max_mana = stats.max_value(StatType::Mana);
+    for mut text in query.iter_mut() {
+        **text = format!("MP {}/{}", mana, max_mana);
+    }
+}
+
 fn update_xp_display(
     player: Query<&Progression, (With<PlayerMarker>, Changed<Progression>)>,
     mut query: Query<&mut Text, With<PlayerXpText>>,
@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use bon::Builder;
+
+use crate::assets::{GameSprites, ItemDetailIconsSlice};
+use crate::item::enums::{ItemQuality, QualityColor};
+
+pub struct RichTextPlugin;
+
+impl Plugin for RichTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_add_rich_text);
+    }
+}
+
+/// A line of text with inline markup for icons and colors.
+///
+/// - `{icon:name}` draws an icon sized to the text: `gold`, `attack`, `health`, `defense`
+///   or `stat`.
+/// - `{color:name}...{/color}` colors the text between the tags, and can nest. Names are
+///   item qualities (`poor` through `mythic`), the rarity words `common`, `uncommon`,
+///   `rare`, `epic` and `legendary`, or one of `gold`, `red`, `green`, `blue` and `gray`.
+///
+/// Tags that don't parse are shown as written.
+///
+/// ```ignore
+/// parent.spawn(RichText::builder("{icon:gold} 250").build());
+/// parent.spawn(
+///     RichText::builder("Found {color:rare}Gilded Blade{/color}!")
+///         .font_size(20.0)
+///         .build(),
+/// );
+/// ```
+#[derive(Component, Builder)]
+pub struct RichText {
+    #[builder(start_fn, into)]
+    pub markup: String,
+    #[builder(default = 16.0)]
+    pub font_size: f32,
+    /// Color for text outside any color tag.
+    #[builder(default = Color::WHITE)]
+    pub color: Color,
+}
+
+/// One piece of parsed markup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupSpan {
+    /// Text in the given color, or the widget's own color when `None`.
+    Text {
+        text: String,
+        color: Option<Color>,
+    },
+    Icon(ItemDetailIconsSlice),
+}
+
+enum Tag {
+    Icon(ItemDetailIconsSlice),
+    Color(Color),
+    EndColor,
+}
+
+fn parse_tag(tag: &str) -> Option<Tag> {
+    if tag == "/color" {
+        return Some(Tag::EndColor);
+    }
+    let (kind, name) = tag.split_once(':')?;
+    match kind {
+        "icon" => icon_named(name).map(Tag::Icon),
+        "color" => color_named(name).map(Tag::Color),
+        _ => None,
+    }
+}
+
+fn icon_named(name: &str) -> Option<ItemDetailIconsSlice> {
+    Some(match name {
+        "gold" => ItemDetailIconsSlice::GoldIcon,
+        "attack" => ItemDetailIconsSlice::AttackIcon,
+        "health" => ItemDetailIconsSlice::HealthIcon,
+        "defense" => ItemDetailIconsSlice::DefenseIcon,
+        "stat" => ItemDetailIconsSlice::DefaultStatIcon,
+        _ => return None,
+    })
+}
+
+fn color_named(name: &str) -> Option<Color> {
+    let quality = match name {
+        "poor" => ItemQuality::Poor,
+        "normal" | "common" => ItemQuality::Normal,
+        "improved" | "uncommon" => ItemQuality::Improved,
+        "well_forged" | "rare" => ItemQuality::WellForged,
+        "masterworked" | "epic" => ItemQuality::Masterworked,
+        "mythic" | "legendary" => ItemQuality::Mythic,
+        "gold" => return Some(Color::srgb(1.0, 0.84, 0.0)),
+        "red" => return Some(Color::srgb(0.9, 0.3, 0.3)),
+        "green" => return Some(Color::srgb(0.4, 0.9, 0.4)),
+        "blue" => return Some(Color::srgb(0.4, 0.6, 1.0)),
+        "gray" => return Some(Color::srgb(0.6, 0.6, 0.6)),
+        _ => return None,
+    };
+    Some(quality.color())
+}
+
+/// Splits `markup` into runs of text and icons.
+pub fn parse_markup(markup: &str) -> Vec<MarkupSpan> {
+    let mut spans = Vec::new();
+    let mut colors: Vec<Color> = Vec::new();
+    let mut text = String::new();
+    let mut rest = markup;
+
+    let flush = |spans: &mut Vec<MarkupSpan>, text: &mut String, color: Option<&Color>| {
+        if !text.is_empty() {
+            spans.push(MarkupSpan::Text {
+                text: std::mem::take(text),
+                color: color.copied(),
+            });
+        }
+    };
+
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let Some(len) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        let close = open + len;
+
+        match parse_tag(&rest[open + 1..close]) {
+            Some(Tag::Icon(icon)) => {
+                flush(&mut spans, &mut text, colors.last());
+                spans.push(MarkupSpan::Icon(icon));
+            }
+            Some(Tag::Color(color)) => {
+                flush(&mut spans, &mut text, colors.last());
+                colors.push(color);
+            }
+            Some(Tag::EndColor) => {
+                flush(&mut spans, &mut text, colors.last());
+                colors.pop();
+            }
+            None => text.push_str(&rest[open..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    text.push_str(rest);
+    flush(&mut spans, &mut text, colors.last());
+    spans
+}
+
+fn on_add_rich_text(
+    trigger: On<Add, RichText>,
+    mut commands: Commands,
+    query: Query<&RichText>,
+    game_sprites: Res<GameSprites>,
+) {
+    let entity = trigger.entity;
+    let Ok(rich_text) = query.get(entity) else {
+        return;
+    };
+
+    let spans = parse_markup(&rich_text.markup);
+    let font_size = rich_text.font_size;
+    let default_color = rich_text.color;
+
+    commands
+        .entity(entity)
+        .remove::<RichText>()
+        .insert(Node {
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(2.0),
+            ..default()
+        })
+        .with_children(|line| {
+            for span in spans {
+                match span {
+                    MarkupSpan::Text { text, color } => {
+                        line.spawn((
+                            Text::new(text),
+                            TextFont {
+                                font_size,
+                                ..default()
+                            },
+                            TextColor(color.unwrap_or(default_color)),
+                        ));
+                    }
+                    MarkupSpan::Icon(icon) => {
+                        let mut icon_entity = line.spawn(Node {
+                            width: Val::Px(font_size),
+                            height: Val::Px(font_size),
+                            ..default()
+                        });
+                        if let Some(img) = game_sprites
+                            .get(icon.sprite_sheet_key())
+                            .and_then(|sheet| sheet.image_node(icon.as_str()))
+                        {
+                            icon_entity.insert(img);
+                        }
+                    }
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str, color: Option<Color>) -> MarkupSpan {
+        MarkupSpan::Text {
+            text: text.to_string(),
+            color,
+        }
+    }
+
+    #[test]
+    fn icons_split_the_surrounding_text() {
+        assert_eq!(
+            parse_markup("{icon:gold} 250"),
+            vec![
+                MarkupSpan::Icon(ItemDetailIconsSlice::GoldIcon),
+                text(" 250", None)
+            ],
+        );
+    }
+
+    #[test]
+    fn colors_apply_until_closed_and_nest() {
+        let rare = ItemQuality::WellForged.color();
+        let red = color_named("red");
+        assert_eq!(
+            parse_markup("A {color:rare}Gilded {color:red}Blade{/color}!{/color} sold"),
+            vec![
+                text("A ", None),
+                text("Gilded ", Some(rare)),
+                text("Blade", red),
+                text("!", Some(rare)),
+                text(" sold", None),
+            ],
+        );
+    }
+
+    #[test]
+    fn unknown_or_unclosed_tags_are_shown_as_written() {
+        assert_eq!(
+            parse_markup("{icon:sword} costs {gold} {color:rare"),
+            vec![text("{icon:sword} costs {gold} {color:rare", None)],
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+
+/// Plugin for the retained text list widget.
+pub struct TextRowsPlugin;
+
+impl Plugin for TextRowsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, sync_text_rows);
+    }
+}
+
+/// A column of single-line text rows kept in step with a list of strings.
+///
+/// Screens replace the rows whenever their data changes, and the rows already spawned are
+/// patched in place: only text and colors that differ are written, and entities are only
+/// spawned or despawned when the number of rows changes. Moving the selection through a
+/// long list recolors two rows instead of rebuilding all of them.
+///
+/// # Examples
+///
+/// ```ignore
+/// parent.spawn((MyList, column_node(8.0), TextRows::new(22.0)));
+///
+/// // Later, when the data or selection changes:
+/// let rows = items.iter().enumerate().map(|(i, item)| (item.name.clone(), color(i)));
+/// list.single_mut()?.set(rows);
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TextRows {
+    pub font_size: f32,
+    rows: Vec<(String, Color)>,
+}
+
+impl TextRows {
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            font_size,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn set(&mut self, rows: impl IntoIterator<Item = (String, Color)>) {
+        self.rows = rows.into_iter().collect();
+    }
+}
+
+/// One row spawned by [`TextRows`].
+#[derive(Component)]
+pub struct TextRow;
+
+fn sync_text_rows(
+    mut commands: Commands,
+    lists: Query<(Entity, &TextRows, Option<&Children>), Changed<TextRows>>,
+    mut row_query: Query<(&mut Text, &mut TextColor), With<TextRow>>,
+) {
+    for (list, text_rows, children) in &lists {
+        let existing: Vec<Entity> = children
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|child| row_query.contains(*child))
+            .collect();
+
+        for (index, (line, color)) in text_rows.rows.iter().enumerate() {
+            let Some(&row) = existing.get(index) else {
+                commands.spawn((
+                    TextRow,
+                    Text::new(line.clone()),
+                    TextFont {
+                        font_size: text_rows.font_size,
+                        ..default()
+                    },
+                    TextColor(*color),
+                    ChildOf(list),
+                ));
+                continue;
+            };
+            let Ok((mut text, mut text_color)) = row_query.get_mut(row) else {
+                continue;
+            };
+            if text.0 != *line {
+                text.0.clone_from(line);
+            }
+            text_color.set_if_neq(TextColor(*color));
+        }
+
+        for &row in existing.iter().skip(text_rows.rows.len()) {
+            commands.entity(row).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[&str]) -> Vec<(String, Color)> {
+        lines
+            .iter()
+            .map(|line| (line.to_string(), Color::WHITE))
+            .collect()
+    }
+
+    fn row_entities(world: &mut World, list: Entity) -> Vec<(Entity, String)> {
+        let children = world.get::<Children>(list).map(|c| c.to_vec());
+        children
+            .unwrap_or_default()
+            .into_iter()
+            .map(|row| (row, world.get::<Text>(row).unwrap().0.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn rows_are_patched_in_place() {
+        let mut app = App::new();
+        app.add_plugins(TextRowsPlugin);
+        let list = app.world_mut().spawn(TextRows::new(22.0)).id();
+
+        app.world_mut()
+            .get_mut::<TextRows>(list)
+            .unwrap()
+            .set(rows(&["a", "b", "c"]));
+        app.update();
+        let before = row_entities(app.world_mut(), list);
+        assert_eq!(before.len(), 3);
+
+        app.world_mut()
+            .get_mut::<TextRows>(list)
+            .unwrap()
+            .set(rows(&["a", "B"]));
+        app.update();
+        let after = row_entities(app.world_mut(), list);
+        assert_eq!(
+            after,
+            [(before[0].0, "a".to_string()), (before[1].0, "B".to_string())]
+        );
+    }
+}
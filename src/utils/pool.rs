@@ -0,0 +1,66 @@
+//! Entities hidden and reused instead of despawned, for things that come and go many times a
+//! second in a fight.
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+/// Hidden entities of one kind, told apart by the marker component `M`, ready to be shown
+/// again.
+///
+/// [`EntityPool::acquire`] hands back a parked entity, if there is one, for the caller to
+/// reset and show; otherwise the caller spawns a new one. [`EntityPool::release`] parks an
+/// entity the caller has just hidden. An acquired entity may have been despawned since it
+/// was parked, so callers look it up and spawn fresh when that fails.
+#[derive(Resource, Debug)]
+pub struct EntityPool<M> {
+    free: Vec<Entity>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<M> Default for EntityPool<M> {
+    fn default() -> Self {
+        Self {
+            free: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M> EntityPool<M> {
+    pub fn acquire(&mut self) -> Option<Entity> {
+        self.free.pop()
+    }
+
+    /// Parks `entity`. Releasing one that is already parked does nothing.
+    pub fn release(&mut self, entity: Entity) {
+        if !self.free.contains(&entity) {
+            self.free.push(entity);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_hands_back_each_entity_once() {
+        let mut pool = EntityPool::<()>::default();
+        let entity = World::new().spawn_empty().id();
+        pool.release(entity);
+        pool.release(entity);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.acquire(), Some(entity));
+        assert!(pool.is_empty());
+        assert_eq!(pool.acquire(), None);
+    }
+}
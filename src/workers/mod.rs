@@ -0,0 +1,11 @@
+//! Town workers: miners and herbalists hired for gold who gather materials into storage
+//! over time.
+
+pub mod plugin;
+pub mod state;
+
+pub use plugin::{WorkerRequest, WorkersPlugin};
+pub use state::{
+    Worker, WorkerKind, Workers, BASE_SECONDS_PER_ITEM, MAX_WORKERS, MAX_WORKER_LEVEL,
+    WORKERS_SAVE_PATH,
+};
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+
+use crate::economy::{Currency, Wallet};
+use crate::game::{CharacterDataExt, ShowToast};
+use crate::i18n::Localization;
+use crate::item::ItemRegistry;
+use crate::player::PlayerMarker;
+use crate::states::{AppState, ScreenSet};
+use crate::storage::Storage;
+use crate::ui::UiFeedback;
+use crate::world_event::GameClock;
+
+use super::state::{WorkerKind, Workers};
+
+/// Something the player asked of the town's workers.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerRequest {
+    Hire(WorkerKind),
+    /// Level up the worker at this index.
+    Upgrade(usize),
+    Fire(usize),
+}
+
+pub struct WorkersPlugin;
+
+impl Plugin for WorkersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_character_resource::<Workers>()
+            .add_message::<WorkerRequest>()
+            .add_systems(
+                Update,
+                (
+                    handle_worker_requests.run_if(on_message::<WorkerRequest>),
+                    pay_workers,
+                    run_workers
+                        .run_if(in_state(AppState::Dungeon))
+                        .run_if(resource_exists::<ItemRegistry>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            );
+    }
+}
+
+fn handle_worker_requests(
+    mut requests: MessageReader<WorkerRequest>,
+    mut workers: ResMut<Workers>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    clock: Res<GameClock>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+    mut feedback: MessageWriter<UiFeedback>,
+) {
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    for request in requests.read() {
+        let message = match *request {
+            WorkerRequest::Hire(kind) => {
+                let cost = kind.hire_cost();
+                if workers.is_full() {
+                    feedback.write(UiFeedback::Error);
+                    loc.get("workers.full").to_string()
+                } else if wallet.spend(Currency::Gold, cost).is_err() {
+                    feedback.write(UiFeedback::Error);
+                    loc.format("workers.cannot_afford", &[("gold", &cost)])
+                } else {
+                    workers.hire(kind, clock.day());
+                    loc.format("workers.hired", &[("worker", &loc.get(kind.label_key()))])
+                }
+            }
+            WorkerRequest::Upgrade(index) => {
+                let Some(worker) = workers.workers().get(index) else {
+                    continue;
+                };
+                let name = loc.get(worker.kind.label_key()).to_string();
+                match worker.upgrade_cost() {
+                    None => {
+                        feedback.write(UiFeedback::Error);
+                        loc.format("workers.max_level", &[("worker", &name)])
+                    }
+                    Some(cost) if wallet.spend(Currency::Gold, cost).is_err() => {
+                        feedback.write(UiFeedback::Error);
+                        loc.format("workers.cannot_afford", &[("gold", &cost)])
+                    }
+                    Some(_) => {
+                        workers.upgrade(index);
+                        let level = workers.workers()[index].level;
+                        loc.format("workers.upgraded", &[("worker", &name), ("level", &level)])
+                    }
+                }
+            }
+            WorkerRequest::Fire(index) => match workers.fire(index) {
+                Some(worker) => loc.format(
+                    "workers.fired",
+                    &[("worker", &loc.get(worker.kind.label_key()))],
+                ),
+                None => continue,
+            },
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
+
+/// Pays wages for each day that has begun. Workers who can't be paid all quit.
+fn pay_workers(
+    clock: Res<GameClock>,
+    mut workers: ResMut<Workers>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    if workers.workers().is_empty() {
+        return;
+    }
+    let days = workers.wages_due(clock.day());
+    if days == 0 {
+        return;
+    }
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    let wages = days as i32 * workers.daily_wages();
+    let message = match wallet.spend(Currency::Gold, wages) {
+        Ok(()) => loc.format("workers.paid", &[("gold", &wages)]),
+        Err(e) => {
+            debug!("{e}");
+            workers.fire_all();
+            loc.format("workers.quit", &[("gold", &wages)])
+        }
+    };
+    toast_writer.write(ShowToast::new(message));
+}
+
+/// Puts what the workers gather into storage.
+fn run_workers(
+    time: Res<Time>,
+    mut workers: ResMut<Workers>,
+    mut storage: ResMut<Storage>,
+    registry: Res<ItemRegistry>,
+) {
+    // Progress ticks every frame; only a finished item is worth saving.
+    let mut gathered = Vec::new();
+    for worker in workers.bypass_change_detection().workers_mut() {
+        gathered.extend(worker.work(time.delta_secs()));
+    }
+    if gathered.is_empty() {
+        return;
+    }
+
+    let storage_full = gathered
+        .into_iter()
+        .any(|item_id| storage.deposit(registry.spawn(item_id)).is_err());
+    workers.storage_full = storage_full;
+}
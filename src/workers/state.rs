@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::CharacterResource;
+use crate::item::ItemId;
+
+/// Where the hired workers are saved in each character's slot.
+pub const WORKERS_SAVE_PATH: &str = "workers.ron";
+
+/// Most workers the town has room for.
+pub const MAX_WORKERS: usize = 4;
+
+pub const MAX_WORKER_LEVEL: u32 = 5;
+
+/// Seconds a level 1 worker needs per item. Each level divides this further.
+pub const BASE_SECONDS_PER_ITEM: f32 = 60.0;
+
+/// Gold for a worker's first upgrade; each one after costs this much more than the last.
+pub const WORKER_UPGRADE_STEP: i32 = 100;
+
+/// A trade a town worker can be hired for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum WorkerKind {
+    Miner,
+    Herbalist,
+}
+
+impl WorkerKind {
+    pub const ALL: [WorkerKind; 2] = [WorkerKind::Miner, WorkerKind::Herbalist];
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            WorkerKind::Miner => "workers.miner",
+            WorkerKind::Herbalist => "workers.herbalist",
+        }
+    }
+
+    pub fn hire_cost(self) -> i32 {
+        match self {
+            WorkerKind::Miner => 150,
+            WorkerKind::Herbalist => 100,
+        }
+    }
+
+    /// Gold a level 1 worker of this trade is paid each day.
+    pub fn base_wage(self) -> i32 {
+        match self {
+            WorkerKind::Miner => 15,
+            WorkerKind::Herbalist => 10,
+        }
+    }
+
+    /// What a worker at `level` gathers, taken in turn. Miners reach the rarer ores as they
+    /// level up.
+    pub fn products(self, level: u32) -> Vec<ItemId> {
+        match self {
+            WorkerKind::Miner => {
+                let mut products = vec![ItemId::Coal, ItemId::CopperOre];
+                if level >= 2 {
+                    products.push(ItemId::IronOre);
+                }
+                if level >= 4 {
+                    products.push(ItemId::GoldOre);
+                }
+                products
+            }
+            WorkerKind::Herbalist => vec![ItemId::Herb],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Worker {
+    pub kind: WorkerKind,
+    pub level: u32,
+    /// Items gathered so far, which picks the next product in turn.
+    gathered: u32,
+    progress: f32,
+}
+
+impl Worker {
+    pub fn new(kind: WorkerKind) -> Self {
+        Self {
+            kind,
+            level: 1,
+            gathered: 0,
+            progress: 0.0,
+        }
+    }
+
+    pub fn wage(&self) -> i32 {
+        self.kind.base_wage() * self.level as i32
+    }
+
+    pub fn seconds_per_item(&self) -> f32 {
+        BASE_SECONDS_PER_ITEM / self.level as f32
+    }
+
+    /// Gold the next upgrade costs, if there is one.
+    pub fn upgrade_cost(&self) -> Option<i32> {
+        (self.level < MAX_WORKER_LEVEL).then(|| self.level as i32 * WORKER_UPGRADE_STEP)
+    }
+
+    /// Adds `secs` of work, returning the items finished.
+    pub fn work(&mut self, secs: f32) -> Vec<ItemId> {
+        self.progress += secs;
+        let count = (self.progress / self.seconds_per_item()) as u32;
+        self.progress -= count as f32 * self.seconds_per_item();
+
+        let products = self.kind.products(self.level);
+        (0..count)
+            .map(|_| {
+                let item = products[self.gathered as usize % products.len()];
+                self.gathered += 1;
+                item
+            })
+            .collect()
+    }
+}
+
+/// The town's hired workers.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workers {
+    workers: Vec<Worker>,
+    paid_through: u32,
+    /// Set when storage had no room for something a worker gathered.
+    #[serde(skip)]
+    pub storage_full: bool,
+}
+
+impl CharacterResource for Workers {
+    const FILE: &'static str = WORKERS_SAVE_PATH;
+}
+
+impl Workers {
+    pub fn workers(&self) -> &[Worker] {
+        &self.workers
+    }
+
+    pub fn workers_mut(&mut self) -> &mut [Worker] {
+        &mut self.workers
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.workers.len() >= MAX_WORKERS
+    }
+
+    /// Gold owed each day for everyone on the payroll.
+    pub fn daily_wages(&self) -> i32 {
+        self.workers.iter().map(Worker::wage).sum()
+    }
+
+    /// Takes on a worker. Wages start with the next day. Returns `false` if there is no room.
+    pub fn hire(&mut self, kind: WorkerKind, today: u32) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        if self.workers.is_empty() {
+            self.paid_through = today;
+        }
+        self.workers.push(Worker::new(kind));
+        true
+    }
+
+    pub fn upgrade(&mut self, index: usize) {
+        if let Some(worker) = self.workers.get_mut(index) {
+            worker.level = (worker.level + 1).min(MAX_WORKER_LEVEL);
+        }
+    }
+
+    pub fn fire(&mut self, index: usize) -> Option<Worker> {
+        (index < self.workers.len()).then(|| self.workers.remove(index))
+    }
+
+    /// Lets everyone go, as when wages can't be paid.
+    pub fn fire_all(&mut self) {
+        self.workers.clear();
+    }
+
+    /// Days of wages owed as of `today`, marking them paid.
+    pub fn wages_due(&mut self, today: u32) -> u32 {
+        let days = today.saturating_sub(self.paid_through);
+        self.paid_through = self.paid_through.max(today);
+        days
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn levels_speed_up_gathering_and_raise_wages() {
+        let mut miner = Worker::new(WorkerKind::Miner);
+        assert_eq!(miner.work(BASE_SECONDS_PER_ITEM * 1.5).len(), 1);
+        miner.level = 3;
+        assert_eq!(miner.wage(), 45);
+        // Half an item carried over, plus a full item's time at triple speed.
+        assert_eq!(miner.work(BASE_SECONDS_PER_ITEM / 3.0).len(), 2);
+    }
+
+    #[test]
+    fn miners_take_their_products_in_turn() {
+        let mut miner = Worker::new(WorkerKind::Miner);
+        miner.level = 2;
+        let gathered = miner.work(BASE_SECONDS_PER_ITEM * 2.0);
+        assert_eq!(
+            gathered,
+            vec![
+                ItemId::Coal,
+                ItemId::CopperOre,
+                ItemId::IronOre,
+                ItemId::Coal
+            ]
+        );
+    }
+
+    #[test]
+    fn hiring_stops_at_capacity_and_wages_start_the_next_day() {
+        let mut workers = Workers::default();
+        for _ in 0..MAX_WORKERS {
+            assert!(workers.hire(WorkerKind::Herbalist, 2));
+        }
+        assert!(!workers.hire(WorkerKind::Miner, 2));
+        assert_eq!(workers.wages_due(2), 0);
+        assert_eq!(workers.wages_due(4), 2);
+        assert_eq!(workers.daily_wages(), 40);
+    }
+
+    #[test]
+    fn upgrades_stop_at_the_max_level() {
+        let mut workers = Workers::default();
+        workers.hire(WorkerKind::Miner, 0);
+        for _ in 0..10 {
+            workers.upgrade(0);
+        }
+        assert_eq!(workers.workers()[0].level, MAX_WORKER_LEVEL);
+        assert_eq!(workers.workers()[0].upgrade_cost(), None);
+    }
+
+    #[test]
+    fn saves_round_trip() {
+        let mut workers = Workers::default();
+        workers.hire(WorkerKind::Miner, 1);
+        let ron = to_ron(&workers).unwrap();
+        assert_eq!(from_ron::<Workers>(&ron).unwrap(), workers);
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where the clock is saved, so days and the wages counted against them survive a restart.
+pub const GAME_CLOCK_SAVE_PATH: &str = "game_clock.ron";
+
+/// Seconds of play that make up one calendar day.
+pub const DAY_LENGTH_SECS: f32 = 240.0;
+
+/// Hour of the morning each day begins at.
+pub const DAWN_HOUR: f32 = 6.0;
+
+/// Hour the light starts to fade.
+const DUSK_HOUR: f32 = 18.0;
+
+/// Hour it's fully dark, until the two hours before the next dawn.
+const NIGHT_HOUR: f32 = 20.0;
+
+/// The in-game clock world events are scheduled on. Days are counted from zero, start at
+/// dawn, and pass while the player is out in the world; long actions like travel move the
+/// clock forward as well.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SavedClock", into = "SavedClock")]
+pub struct GameClock {
+    day: u32,
+    timer: Timer,
+}
+
+/// The day and how far into it the clock is, which is all a restart needs back.
+#[derive(Serialize, Deserialize)]
+struct SavedClock {
+    day: u32,
+    #[serde(default)]
+    seconds: f32,
+}
+
+impl From<SavedClock> for GameClock {
+    fn from(saved: SavedClock) -> Self {
+        let mut clock = Self {
+            day: saved.day,
+            ..default()
+        };
+        clock
+            .timer
+            .set_elapsed(Duration::from_secs_f32(saved.seconds.clamp(0.0, DAY_LENGTH_SECS)));
+        clock
+    }
+}
+
+impl From<GameClock> for SavedClock {
+    fn from(clock: GameClock) -> Self {
+        Self {
+            day: clock.day,
+            seconds: clock.timer.elapsed_secs(),
+        }
+    }
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            timer: Timer::from_seconds(DAY_LENGTH_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+impl GameClock {
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Advances the clock, returning whether a new day began.
+    pub fn advance(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta);
+        let days = self.timer.times_finished_this_tick();
+        self.day += days;
+        days > 0
+    }
+
+    /// Advances the clock by `hours` of in-game time, returning whether a new day began.
+    pub fn pass_hours(&mut self, hours: f32) -> bool {
+        self.advance(Duration::from_secs_f32(hours / 24.0 * DAY_LENGTH_SECS))
+    }
+
+    /// The hour of the day, from [`DAWN_HOUR`] up to but not including the next dawn, so
+    /// the small hours read as 24 and up.
+    pub fn hour(&self) -> f32 {
+        DAWN_HOUR + self.timer.fraction() * 24.0
+    }
+
+    /// The time as `HH:MM` on a 24-hour clock.
+    pub fn time_label(&self) -> String {
+        let minutes = (self.hour() * 60.0).round() as u32 % (24 * 60);
+        format!("{:02}:{:02}", minutes / 60, minutes % 60)
+    }
+
+    /// How dark it is, from 0 in daylight to 1 at night. Dusk and the hours before dawn
+    /// fade between the two.
+    pub fn darkness(&self) -> f32 {
+        let hour = self.hour();
+        let predawn = DAWN_HOUR + 24.0 - (NIGHT_HOUR - DUSK_HOUR);
+        if hour < DUSK_HOUR {
+            0.0
+        } else if hour < NIGHT_HOUR {
+            (hour - DUSK_HOUR) / (NIGHT_HOUR - DUSK_HOUR)
+        } else if hour < predawn {
+            1.0
+        } else {
+            (DAWN_HOUR + 24.0 - hour) / (NIGHT_HOUR - DUSK_HOUR)
+        }
+    }
+
+    pub fn is_night(&self) -> bool {
+        self.hour() >= NIGHT_HOUR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn days_roll_over_after_a_full_day() {
+        let mut clock = GameClock::default();
+        assert!(!clock.advance(Duration::from_secs_f32(DAY_LENGTH_SECS - 1.0)));
+        assert_eq!(clock.day(), 0);
+
+        assert!(clock.advance(Duration::from_secs_f32(DAY_LENGTH_SECS * 2.0)));
+        assert_eq!(clock.day(), 2);
+    }
+
+    #[test]
+    fn days_start_at_dawn_and_darken_after_dusk() {
+        let mut clock = GameClock::default();
+        assert_eq!(clock.time_label(), "06:00");
+        assert_eq!(clock.darkness(), 0.0);
+
+        clock.pass_hours(13.0);
+        assert_eq!(clock.time_label(), "19:00");
+        assert!(clock.darkness() > 0.0 && clock.darkness() < 1.0);
+        assert!(!clock.is_night());
+
+        clock.pass_hours(4.0);
+        assert_eq!(clock.time_label(), "23:00");
+        assert_eq!(clock.darkness(), 1.0);
+        assert!(clock.is_night());
+    }
+
+    #[test]
+    fn the_night_lightens_before_the_next_day() {
+        let mut clock = GameClock::default();
+        assert!(!clock.pass_hours(23.0));
+        assert_eq!(clock.time_label(), "05:00");
+        assert!(clock.darkness() < 1.0);
+
+        assert!(clock.pass_hours(2.0));
+        assert_eq!(clock.day(), 1);
+        assert_eq!(clock.time_label(), "07:00");
+    }
+
+    #[test]
+    fn saves_keep_the_day_and_time() {
+        let mut clock = GameClock::default();
+        clock.pass_hours(24.0 * 3.0 + 6.0);
+
+        let loaded: GameClock = from_ron(&to_ron(&clock).unwrap()).unwrap();
+        assert_eq!(loaded.day(), 3);
+        assert_eq!(loaded.time_label(), "12:00");
+    }
+}
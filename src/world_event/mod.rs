@@ -0,0 +1,9 @@
+pub mod clock;
+pub mod plugin;
+pub mod spec;
+
+pub use clock::{GameClock, DAWN_HOUR, DAY_LENGTH_SECS, GAME_CLOCK_SAVE_PATH};
+pub use plugin::{
+    ActiveWorldEvent, EventShopPurchase, WorldEventLibrary, WorldEventPlugin, EVENTS_DIR,
+};
+pub use spec::{EventShopEntry, TokenDrop, WorldEventSpec};
@@ -0,0 +1,284 @@
+use bevy::asset::LoadedFolder;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+use rand::Rng;
+
+use crate::dungeon::SpawnTable;
+use crate::economy::{Currency, Wallet};
+use crate::game::ShowToast;
+use crate::i18n::Localization;
+use crate::inventory::{Inventory, ManagesItems};
+use crate::item::ItemRegistry;
+use crate::mob::MobId;
+use crate::player::PlayerMarker;
+use crate::plugins::MobDefeated;
+use crate::save::{load_ron, save_ron};
+use crate::states::{AppState, ScreenSet};
+
+use super::clock::{GameClock, GAME_CLOCK_SAVE_PATH};
+use super::spec::WorldEventSpec;
+
+/// Directory under `assets/` that world events are loaded from.
+pub const EVENTS_DIR: &str = "data/events";
+
+/// Mobs that come out in force on floors entered after dark, and by how much.
+const NIGHT_SPAWN_BOOSTS: [(MobId, u32); 1] = [(MobId::Goblin, 3)];
+
+/// Handle to the loaded event folder, read on demand like the help library.
+#[derive(Resource)]
+pub struct WorldEventLibrary {
+    folder: Handle<LoadedFolder>,
+}
+
+impl WorldEventLibrary {
+    /// The first event scheduled for `day`, if any.
+    pub fn active_on(
+        &self,
+        day: u32,
+        folders: &Assets<LoadedFolder>,
+        assets: &Assets<WorldEventSpec>,
+    ) -> Option<WorldEventSpec> {
+        folders
+            .get(&self.folder)?
+            .handles
+            .iter()
+            .filter_map(|h| h.id().try_typed::<WorldEventSpec>().ok())
+            .filter_map(|id| assets.get(id))
+            .find(|event| event.is_active(day))
+            .cloned()
+    }
+}
+
+/// The event library along with the asset stores its events are read from.
+#[derive(SystemParam)]
+struct LoadedEvents<'w> {
+    library: Option<Res<'w, WorldEventLibrary>>,
+    folders: Res<'w, Assets<LoadedFolder>>,
+    assets: Res<'w, Assets<WorldEventSpec>>,
+}
+
+/// The event running today, if any.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveWorldEvent(pub Option<WorldEventSpec>);
+
+/// Buy the entry at `index` in the active event's shop.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventShopPurchase {
+    pub index: usize,
+}
+
+pub struct WorldEventPlugin;
+
+impl Plugin for WorldEventPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<WorldEventSpec>::new(&["event.ron"]))
+            .insert_resource(load_ron::<GameClock>(GAME_CLOCK_SAVE_PATH))
+            .init_resource::<ActiveWorldEvent>()
+            .add_message::<EventShopPurchase>()
+            .add_systems(Startup, load_event_folder)
+            .add_systems(
+                Update,
+                (
+                    advance_clock.run_if(in_state(AppState::Dungeon)),
+                    save_clock_on_new_day,
+                    refresh_active_event,
+                    boost_event_spawns.run_if(resource_exists_and_changed::<SpawnTable>),
+                    boost_night_spawns.run_if(resource_exists_and_changed::<SpawnTable>),
+                    drop_event_currency.run_if(on_message::<MobDefeated>),
+                    handle_event_shop_purchases.run_if(on_message::<EventShopPurchase>),
+                )
+                    .chain()
+                    .in_set(ScreenSet::Logic),
+            )
+            .add_systems(OnExit(AppState::Dungeon), save_clock)
+            .add_systems(Last, save_clock.run_if(on_message::<AppExit>));
+    }
+}
+
+fn load_event_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WorldEventLibrary {
+        folder: asset_server.load_folder(EVENTS_DIR),
+    });
+}
+
+fn advance_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+    clock.advance(time.delta());
+}
+
+fn save_clock(clock: Res<GameClock>) {
+    if let Err(e) = save_ron(GAME_CLOCK_SAVE_PATH, &*clock) {
+        warn!("{e}");
+    }
+}
+
+/// Saves whenever a day passes, whether by playing or travelling. Time within the day is
+/// saved when leaving the dungeon and on exit.
+fn save_clock_on_new_day(clock: Res<GameClock>, mut saved_day: Local<Option<u32>>) {
+    if saved_day.replace(clock.day()).is_some_and(|day| day != clock.day()) {
+        save_clock(clock);
+    }
+}
+
+fn refresh_active_event(
+    clock: Res<GameClock>,
+    events: LoadedEvents,
+    mut active: ResMut<ActiveWorldEvent>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let LoadedEvents {
+        library,
+        folders,
+        assets,
+    } = events;
+    let Some(library) = library else {
+        return;
+    };
+
+    let today = library.active_on(clock.day(), &folders, &assets);
+    let current = active.0.as_ref().map(|event| &event.name);
+    if today.as_ref().map(|event| &event.name) == current {
+        return;
+    }
+
+    if let Some(ended) = &active.0 {
+        toast_writer.write(ShowToast::new(
+            loc.format("event.ended", &[("name", &ended.name)]),
+        ));
+        cash_out_currency(ended, &mut player, &loc, &mut toast_writer);
+    }
+    if let Some(started) = &today {
+        toast_writer.write(ShowToast::new(
+            loc.format("event.started", &[("name", &started.name)]),
+        ));
+    }
+    active.0 = today;
+}
+
+/// Converts whatever is left of an ended event's currency into carried gold.
+fn cash_out_currency(
+    ended: &WorldEventSpec,
+    player: &mut Query<&mut Wallet, With<PlayerMarker>>,
+    loc: &Localization,
+    toast_writer: &mut MessageWriter<ShowToast>,
+) {
+    let Ok(mut wallet) = player.single_mut() else {
+        return;
+    };
+
+    let amount = wallet.balance(ended.currency);
+    let value = wallet.cash_out(ended.currency);
+    if value > 0 {
+        wallet.add(Currency::Gold, value);
+        toast_writer.write(ShowToast::new(loc.format(
+            "event.currency_converted",
+            &[
+                ("amount", &amount),
+                ("currency", &loc.get(ended.currency.name_key())),
+                ("gold", &value),
+            ],
+        )));
+    }
+}
+
+/// Scales up the event's mobs in each newly entered floor's spawn table. Change detection
+/// is bypassed so the boosted table isn't boosted again next frame.
+fn boost_event_spawns(active: Res<ActiveWorldEvent>, mut table: ResMut<SpawnTable>) {
+    let Some(event) = &active.0 else {
+        return;
+    };
+
+    let table = table.bypass_change_detection();
+    for (&mob_id, &multiplier) in &event.spawn_boosts {
+        table.boost_mob(mob_id, multiplier);
+    }
+}
+
+/// Scales up nocturnal mobs in floors entered at night, the same way event boosts do.
+fn boost_night_spawns(clock: Res<GameClock>, mut table: ResMut<SpawnTable>) {
+    if !clock.is_night() {
+        return;
+    }
+
+    let table = table.bypass_change_detection();
+    for (mob_id, multiplier) in NIGHT_SPAWN_BOOSTS {
+        table.boost_mob(mob_id, multiplier);
+    }
+}
+
+fn drop_event_currency(
+    mut events: MessageReader<MobDefeated>,
+    active: Res<ActiveWorldEvent>,
+    mut player: Query<&mut Wallet, With<PlayerMarker>>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let (Some(event), Ok(mut wallet)) = (&active.0, player.single_mut()) else {
+        events.clear();
+        return;
+    };
+
+    let mut rng = rand::thread_rng();
+    for defeated in events.read() {
+        let Some(drop) = event.token_drops.get(&defeated.mob_id) else {
+            continue;
+        };
+        if !rng.gen_bool(drop.chance.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let amount = rng.gen_range(drop.min..=drop.max.max(drop.min));
+        wallet.add(event.currency, amount);
+        toast_writer.write(ShowToast::new(loc.format(
+            "event.currency_found",
+            &[
+                ("amount", &amount),
+                ("currency", &loc.get(event.currency.name_key())),
+                ("total", &wallet.balance(event.currency)),
+            ],
+        )));
+    }
+}
+
+fn handle_event_shop_purchases(
+    mut requests: MessageReader<EventShopPurchase>,
+    active: Res<ActiveWorldEvent>,
+    mut player: Query<(&mut Inventory, &mut Wallet), With<PlayerMarker>>,
+    registry: Res<ItemRegistry>,
+    loc: Res<Localization>,
+    mut toast_writer: MessageWriter<ShowToast>,
+) {
+    let (Some(event), Ok((mut inventory, mut wallet))) = (&active.0, player.single_mut()) else {
+        return;
+    };
+    let currency = loc.get(event.currency.name_key());
+
+    for request in requests.read() {
+        let Some(entry) = event.shop.get(request.index) else {
+            continue;
+        };
+
+        let name = loc.item_name(entry.item, &registry.get(entry.item).name);
+        let message = match wallet.spend(event.currency, entry.price) {
+            Err(_) => loc.format(
+                "event.not_enough_currency",
+                &[
+                    ("need", &entry.price),
+                    ("have", &wallet.balance(event.currency)),
+                    ("currency", &currency),
+                ],
+            ),
+            Ok(()) if inventory.add_to_inv(registry.spawn(entry.item)).is_err() => {
+                wallet.add(event.currency, entry.price);
+                loc.get("event.inventory_full").to_string()
+            }
+            Ok(()) => loc.format(
+                "event.purchased",
+                &[("item", &name), ("price", &entry.price), ("currency", &currency)],
+            ),
+        };
+        toast_writer.write(ShowToast::new(message));
+    }
+}
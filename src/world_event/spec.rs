@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::economy::Currency;
+use crate::item::ItemId;
+use crate::mob::MobId;
+
+/// Event currency a slain mob may drop.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct TokenDrop {
+    pub chance: f64,
+    pub min: i32,
+    pub max: i32,
+}
+
+/// An item the event shop sells for the event's currency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct EventShopEntry {
+    pub item: ItemId,
+    pub price: i32,
+}
+
+/// A world event, loaded from `assets/data/events/*.event.ron`.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Deserialize)]
+pub struct WorldEventSpec {
+    pub name: String,
+    pub description: String,
+    /// First calendar day of the event's first run.
+    pub start_day: u32,
+    /// Last calendar day of the first run, inclusive.
+    pub end_day: u32,
+    /// Days from one run's start to the next. Events without one run once.
+    #[serde(default)]
+    pub repeat_every: Option<u32>,
+    /// How many times as often each listed mob spawns while the event runs.
+    #[serde(default)]
+    pub spawn_boosts: HashMap<MobId, u32>,
+    /// What `token_drops` pay out and the shop charges. Leftovers become gold at the end.
+    pub currency: Currency,
+    #[serde(default)]
+    pub token_drops: HashMap<MobId, TokenDrop>,
+    #[serde(default)]
+    pub shop: Vec<EventShopEntry>,
+}
+
+impl WorldEventSpec {
+    /// Day within the current run, counting from zero, or `None` when the event is not
+    /// running on `day`.
+    fn day_of_run(&self, day: u32) -> Option<u32> {
+        let since_start = day.checked_sub(self.start_day)?;
+        let offset = match self.repeat_every {
+            Some(period) if period > 0 => since_start % period,
+            _ => since_start,
+        };
+        (offset <= self.end_day.saturating_sub(self.start_day)).then_some(offset)
+    }
+
+    pub fn is_active(&self, day: u32) -> bool {
+        self.day_of_run(day).is_some()
+    }
+
+    /// Days left in the current run after `day`, or `None` when the event is not running.
+    pub fn days_left(&self, day: u32) -> Option<u32> {
+        let length = self.end_day.saturating_sub(self.start_day);
+        self.day_of_run(day).map(|offset| length - offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekend_event() -> WorldEventSpec {
+        WorldEventSpec {
+            name: "Weekend".to_string(),
+            description: String::new(),
+            start_day: 5,
+            end_day: 6,
+            repeat_every: Some(7),
+            spawn_boosts: HashMap::new(),
+            currency: Currency::WarToken,
+            token_drops: HashMap::new(),
+            shop: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn repeating_events_recur_every_period() {
+        let event = weekend_event();
+        let active: Vec<u32> = (0..21).filter(|&day| event.is_active(day)).collect();
+        assert_eq!(active, [5, 6, 12, 13, 19, 20]);
+
+        assert_eq!(event.days_left(12), Some(1));
+        assert_eq!(event.days_left(13), Some(0));
+        assert_eq!(event.days_left(14), None);
+    }
+
+    #[test]
+    fn one_off_events_end_for_good() {
+        let event = WorldEventSpec {
+            repeat_every: None,
+            ..weekend_event()
+        };
+        assert!(event.is_active(6));
+        assert!(!event.is_active(12));
+    }
+
+    #[test]
+    fn specs_parse_from_ron() {
+        let event: WorldEventSpec = ron::from_str(
+            r#"(
+                name: "Invasion",
+                description: "Goblins!",
+                start_day: 1,
+                end_day: 2,
+                spawn_boosts: { Goblin: 2 },
+                currency: WarToken,
+                token_drops: { Goblin: (chance: 0.5, min: 1, max: 2) },
+                shop: [(item: BasicHPPotion, price: 3)],
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(event.repeat_every, None);
+        assert_eq!(event.spawn_boosts[&MobId::Goblin], 2);
+        assert_eq!(event.currency, Currency::WarToken);
+        assert_eq!(event.shop[0].item, ItemId::BasicHPPotion);
+    }
+}
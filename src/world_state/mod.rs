@@ -0,0 +1,5 @@
+pub mod plugin;
+pub mod state;
+
+pub use plugin::WorldStatePlugin;
+pub use state::{FlagValue, WorldFlags, WORLD_FLAGS_SAVE_PATH};
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::save::{load_ron, save_ron};
+
+use super::state::{WorldFlags, WORLD_FLAGS_SAVE_PATH};
+
+pub struct WorldStatePlugin;
+
+impl Plugin for WorldStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_ron::<WorldFlags>(WORLD_FLAGS_SAVE_PATH))
+            .add_systems(
+                Update,
+                save_world_flags
+                    .run_if(resource_changed::<WorldFlags>.and(not(resource_added::<WorldFlags>))),
+            );
+    }
+}
+
+fn save_world_flags(flags: Res<WorldFlags>) {
+    if let Err(e) = save_ron(WORLD_FLAGS_SAVE_PATH, &*flags) {
+        warn!("{e}");
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where world flags are saved, next to the trophy case.
+pub const WORLD_FLAGS_SAVE_PATH: &str = "world_flags.ron";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlagValue {
+    Bool(bool),
+    Count(i32),
+    Text(String),
+}
+
+/// Facts about the world that outlast a run: a rescued prisoner, a shrine the player keeps
+/// feeding. Keys are dotted names owned by whatever writes them, like `rescue.MinersGuild`.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorldFlags {
+    #[serde(default)]
+    flags: BTreeMap<String, FlagValue>,
+}
+
+impl WorldFlags {
+    pub fn get(&self, key: &str) -> Option<&FlagValue> {
+        self.flags.get(key)
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: FlagValue) {
+        self.flags.insert(key.into(), value);
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<FlagValue> {
+        self.flags.remove(key)
+    }
+
+    /// Whether `key` has been raised. Counts above zero and any text count as raised.
+    pub fn is_set(&self, key: &str) -> bool {
+        match self.flags.get(key) {
+            Some(FlagValue::Bool(set)) => *set,
+            Some(FlagValue::Count(count)) => *count > 0,
+            Some(FlagValue::Text(_)) => true,
+            None => false,
+        }
+    }
+
+    pub fn raise(&mut self, key: impl Into<String>) {
+        self.set(key, FlagValue::Bool(true));
+    }
+
+    /// The count stored under `key`, or zero if there is none.
+    pub fn count(&self, key: &str) -> i32 {
+        match self.flags.get(key) {
+            Some(FlagValue::Count(count)) => *count,
+            _ => 0,
+        }
+    }
+
+    /// Adds `amount` to the count under `key`, starting from zero. Returns the new count.
+    pub fn add(&mut self, key: impl Into<String>, amount: i32) -> i32 {
+        let key = key.into();
+        let count = self.count(&key) + amount;
+        self.flags.insert(key, FlagValue::Count(count));
+        count
+    }
+
+    pub fn text(&self, key: &str) -> Option<&str> {
+        match self.flags.get(key) {
+            Some(FlagValue::Text(text)) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &FlagValue)> {
+        self.flags.iter().map(|(key, value)| (key.as_str(), value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{from_ron, to_ron};
+
+    #[test]
+    fn flags_read_back_what_was_written() {
+        let mut flags = WorldFlags::default();
+        assert!(!flags.is_set("shortcut.opened"));
+
+        flags.raise("shortcut.opened");
+        assert!(flags.is_set("shortcut.opened"));
+
+        assert_eq!(flags.add("rescue.MinersGuild", 1), 1);
+        assert_eq!(flags.add("rescue.MinersGuild", 2), 3);
+        assert_eq!(flags.count("rescue.MinersGuild"), 3);
+        assert_eq!(flags.count("shortcut.opened"), 0);
+
+        flags.set("npc.nickname", FlagValue::Text("Pip".to_string()));
+        assert_eq!(flags.text("npc.nickname"), Some("Pip"));
+
+        flags.set("shortcut.opened", FlagValue::Bool(false));
+        assert!(!flags.is_set("shortcut.opened"));
+        assert_eq!(flags.remove("npc.nickname"), Some(FlagValue::Text("Pip".to_string())));
+    }
+
+    #[test]
+    fn world_flags_round_trip_through_ron() {
+        let mut flags = WorldFlags::default();
+        flags.raise("shortcut.opened");
+        flags.add("shrine.sacrifices", 4);
+        let ron = to_ron(&flags).unwrap();
+        assert_eq!(from_ron::<WorldFlags>(&ron).unwrap(), flags);
+        assert_eq!(from_ron::<WorldFlags>("()").unwrap(), WorldFlags::default());
+    }
+}
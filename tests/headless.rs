@@ -0,0 +1,68 @@
+//! End-to-end runs of the game with no window or GPU. Run with
+//! `cargo test --features headless`.
+
+use bevy::prelude::*;
+
+use game::economy::{Currency, Wallet};
+use game::headless::{SimStep, Simulation};
+use game::input::GameAction;
+use game::player::PlayerMarker;
+use game::states::AppState;
+
+/// Ten seconds of frames: plenty for assets to load or a floor to generate.
+const MAX_WAIT: u32 = 600;
+
+fn player_wallet(sim: &mut Simulation) -> Option<Wallet> {
+    let mut query = sim
+        .world_mut()
+        .query_filtered::<&Wallet, With<PlayerMarker>>();
+    query.single(sim.world()).ok().cloned()
+}
+
+fn in_dungeon() -> Simulation {
+    let mut sim = Simulation::new();
+    assert!(sim.run_until(MAX_WAIT, |sim| sim.state() == AppState::Menu));
+    sim.action(GameAction::Select);
+    assert!(sim.run_until(MAX_WAIT, |sim| sim.state() == AppState::Dungeon));
+    sim
+}
+
+#[test]
+fn boots_to_the_main_menu() {
+    let mut sim = Simulation::new();
+    assert!(sim.run_until(MAX_WAIT, |sim| sim.state() == AppState::Menu));
+}
+
+#[test]
+fn play_enters_the_dungeon_with_a_player() {
+    let mut sim = in_dungeon();
+    sim.run_frames(MAX_WAIT);
+    assert!(player_wallet(&mut sim).is_some());
+}
+
+#[test]
+fn console_commands_change_the_player() {
+    let mut sim = in_dungeon();
+    sim.run_script(&[
+        SimStep::Wait(MAX_WAIT),
+        SimStep::Command("gold 250".to_string()),
+        SimStep::Command("currency Gold -5".to_string()),
+        SimStep::Wait(1),
+    ]);
+
+    let wallet = player_wallet(&mut sim).expect("player spawned");
+    assert_eq!(wallet.balance(Currency::Gold), 250);
+}
+
+#[test]
+fn opening_and_closing_the_inventory_stays_in_the_dungeon() {
+    let mut sim = in_dungeon();
+    sim.run_script(&[
+        SimStep::Wait(MAX_WAIT),
+        SimStep::Action(GameAction::OpenInventory),
+        SimStep::Wait(5),
+        SimStep::Action(GameAction::CloseModal),
+        SimStep::Wait(5),
+    ]);
+    assert_eq!(sim.state(), AppState::Dungeon);
+}